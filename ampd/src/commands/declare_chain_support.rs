@@ -20,7 +20,7 @@ pub struct Args {
 }
 
 pub async fn run(config: Config, state_path: &Path, args: Args) -> Result<Option<String>, Error> {
-    let pub_key = worker_pub_key(state_path, config.tofnd_config.clone()).await?;
+    let pub_key = worker_pub_key(state_path, &config).await?;
 
     let msg = serde_json::to_vec(&ExecuteMsg::DeclareChainSupport {
         service_name: args.service_name.into(),
@@ -0,0 +1,437 @@
+use std::path::Path;
+
+use axelar_wasm_std::nonempty;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::query_client::QueryClient;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    query_client::QueryClient as WasmQueryClient, QuerySmartContractStateRequest,
+};
+use error_stack::{report, Context, Result, ResultExt};
+use itertools::Itertools;
+use multisig::key::{KeyType, PublicKey as MultisigPublicKey};
+use serde::{de::DeserializeOwned, Serialize};
+use service_registry::state::{BondingState, Worker};
+use tonic::transport::Channel;
+use valuable::Valuable;
+
+use crate::broadcaster::accounts::account;
+use crate::commands::ServiceRegistryConfig;
+use crate::config::Config;
+use crate::evm::json_rpc::EthereumClient;
+use crate::handlers::config::Config as HandlerConfig;
+use crate::tm_client::TmClient;
+use crate::types::{PublicKey, TMAddress};
+use crate::{json_rpc, signer, state, Error, PREFIX};
+
+#[derive(clap::Args, Debug, Valuable)]
+pub struct Args {
+    /// Name of the service the worker is bonded to in the service registry
+    pub service_name: nonempty::String,
+}
+
+/// Outcome of a single check, with details on success and the reason on failure
+struct Check {
+    name: String,
+    result: std::result::Result<String, String>,
+}
+
+impl Check {
+    fn new<C: Context>(name: impl Into<String>, result: Result<String, C>) -> Self {
+        Self {
+            name: name.into(),
+            result: result.map_err(|report| format!("{report:#}")),
+        }
+    }
+}
+
+/// Runs all checks, even if some of them fail, so a single run reports every misconfiguration.
+/// Fails if any check fails.
+pub async fn run(
+    config: Result<Config, Error>,
+    state_path: &Path,
+    args: Args,
+) -> Result<Option<String>, Error> {
+    let config = match config {
+        Ok(config) => config,
+        Err(report) => return to_result(vec![Check::new("config", Err::<String, _>(report))]),
+    };
+
+    let mut checks = validate_config(&config);
+    checks.push(Check::new(
+        "tendermint rpc",
+        latest_block_height(&config).await,
+    ));
+    checks.extend(chain_rpc_checks(&config).await);
+
+    let pub_key = signer_pub_key(state_path, &config).await;
+    checks.push(Check {
+        name: "signer".to_string(),
+        result: pub_key
+            .as_ref()
+            .map(|pub_key| format!("worker address {}", worker_address(pub_key)))
+            .map_err(|report| format!("{report:#}")),
+    });
+
+    // the remaining checks depend on the worker address
+    if let Ok(pub_key) = pub_key {
+        let worker = worker_address(&pub_key);
+        checks.push(Check::new(
+            "account",
+            account_sequence(&config, &worker).await,
+        ));
+        checks.extend(registration_checks(&config, &args.service_name, &worker, &pub_key).await);
+    }
+
+    to_result(checks)
+}
+
+fn to_result(checks: Vec<Check>) -> Result<Option<String>, Error> {
+    let report = format_report(&checks);
+
+    if checks.iter().any(|check| check.result.is_err()) {
+        return Err(report!(Error::DoctorChecks)).attach_printable(report);
+    }
+
+    Ok(Some(report))
+}
+
+fn format_report(checks: &[Check]) -> String {
+    checks
+        .iter()
+        .map(|check| match &check.result {
+            Ok(details) => format!("[PASS] {}: {}", check.name, details),
+            Err(reason) => format!("[FAIL] {}: {}", check.name, reason),
+        })
+        .join("\n")
+}
+
+fn validate_config(config: &Config) -> Vec<Check> {
+    let handlers = match config.handlers.len() {
+        0 => Err(report!(Error::LoadConfig)).attach_printable("no handlers are configured"),
+        count => Ok(format!("{} handlers configured", count)),
+    };
+
+    let service_registry = if config.service_registry == ServiceRegistryConfig::default() {
+        Err(report!(Error::LoadConfig))
+            .attach_printable("the service registry contract address is not set")
+    } else {
+        Ok(config.service_registry.cosmwasm_contract.to_string())
+    };
+
+    vec![
+        Check::new("config handlers", handlers),
+        Check::new("config service registry", service_registry),
+    ]
+}
+
+async fn latest_block_height(config: &Config) -> Result<String, Error> {
+    let client = tendermint_rpc::HttpClient::new(config.tm_jsonrpc.to_string().as_str())
+        .change_context(Error::Connection)?;
+
+    let block = client
+        .latest_block()
+        .await
+        .change_context(Error::Connection)?;
+
+    Ok(format!("latest block height {}", block.block.header.height))
+}
+
+async fn chain_rpc_checks(config: &Config) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for handler in &config.handlers {
+        let (label, result) = match handler {
+            HandlerConfig::EvmMsgVerifier { chain, .. } => (
+                format!("{}-msg-verifier rpc", chain.name),
+                evm_block_number(&chain.rpc_url).await,
+            ),
+            HandlerConfig::EvmWorkerSetVerifier { chain, .. } => (
+                format!("{}-worker-set-verifier rpc", chain.name),
+                evm_block_number(&chain.rpc_url).await,
+            ),
+            HandlerConfig::SuiMsgVerifier { rpc_url, .. } => (
+                "sui-msg-verifier rpc".to_string(),
+                sui_checkpoint(rpc_url).await,
+            ),
+            HandlerConfig::SuiWorkerSetVerifier { rpc_url, .. } => (
+                "sui-worker-set-verifier rpc".to_string(),
+                sui_checkpoint(rpc_url).await,
+            ),
+            HandlerConfig::MultisigSigner { .. } => continue,
+        };
+
+        checks.push(Check::new(label, result));
+    }
+
+    checks
+}
+
+async fn evm_block_number(rpc_url: &crate::url::Url) -> Result<String, Error> {
+    let block_number = json_rpc::Client::new_http(rpc_url)
+        .change_context(Error::Connection)?
+        .block_number()
+        .await
+        .change_context(Error::Connection)?;
+
+    Ok(format!("latest block {}", block_number))
+}
+
+async fn sui_checkpoint(rpc_url: &crate::url::Url) -> Result<String, Error> {
+    let checkpoint: String = json_rpc::Client::new_http(rpc_url)
+        .change_context(Error::Connection)?
+        .request("sui_getLatestCheckpointSequenceNumber", ())
+        .await
+        .change_context(Error::Connection)?;
+
+    Ok(format!("latest checkpoint {}", checkpoint))
+}
+
+/// Connects to the signer even if the public key is cached in the state file, so the connection gets checked
+async fn signer_pub_key(state_path: &Path, config: &Config) -> Result<PublicKey, Error> {
+    let pub_key = signer::connect(config.signer.clone(), config.tofnd_config.clone())
+        .await?
+        .keygen(&config.tofnd_config.key_uid)
+        .await
+        .change_context(Error::Tofnd)?;
+
+    match state::load(state_path)
+        .change_context(Error::LoadConfig)?
+        .pub_key
+    {
+        Some(state_pub_key) if state_pub_key != pub_key => Err(report!(Error::Tofnd))
+            .attach_printable("the public key in the state file does not match the signer's key"),
+        _ => Ok(pub_key),
+    }
+}
+
+fn worker_address(pub_key: &PublicKey) -> TMAddress {
+    pub_key
+        .account_id(PREFIX)
+        .expect("failed to convert to account identifier")
+        .into()
+}
+
+async fn account_sequence(config: &Config, worker: &TMAddress) -> Result<String, Error> {
+    let query_client = QueryClient::connect(config.tm_grpc.to_string())
+        .await
+        .change_context(Error::Connection)?;
+
+    let account = account(query_client, worker)
+        .await
+        .change_context(Error::Broadcaster)?;
+
+    Ok(format!(
+        "account number {}, sequence {}",
+        account.account_number, account.sequence
+    ))
+}
+
+async fn registration_checks(
+    config: &Config,
+    service_name: &nonempty::String,
+    worker: &TMAddress,
+    pub_key: &PublicKey,
+) -> Vec<Check> {
+    let client = match WasmQueryClient::connect(config.tm_grpc.to_string())
+        .await
+        .change_context(Error::Connection)
+    {
+        Ok(client) => client,
+        Err(report) => {
+            return vec![Check::new(
+                "on-chain registration",
+                Err::<String, _>(report),
+            )]
+        }
+    };
+
+    let mut checks = vec![Check::new(
+        "bond",
+        bond(client.clone(), config, service_name, worker).await,
+    )];
+
+    let chains = config
+        .handlers
+        .iter()
+        .filter_map(|handler| match handler {
+            HandlerConfig::EvmMsgVerifier { chain, .. }
+            | HandlerConfig::EvmWorkerSetVerifier { chain, .. } => Some(chain.name.to_string()),
+            // sui handler configs don't carry a chain name, so their chain support can't be checked
+            _ => None,
+        })
+        .unique();
+    for chain in chains {
+        checks.push(Check::new(
+            format!("{} chain support", chain),
+            chain_support(client.clone(), config, service_name, worker, &chain).await,
+        ));
+    }
+
+    let multisig = config.handlers.iter().find_map(|handler| match handler {
+        HandlerConfig::MultisigSigner { cosmwasm_contract } => Some(cosmwasm_contract),
+        _ => None,
+    });
+    if let Some(multisig) = multisig {
+        checks.push(Check::new(
+            "multisig public key",
+            multisig_pub_key(client, multisig, worker, pub_key).await,
+        ));
+    }
+
+    checks
+}
+
+async fn bond(
+    client: WasmQueryClient<Channel>,
+    config: &Config,
+    service_name: &nonempty::String,
+    worker: &TMAddress,
+) -> Result<String, Error> {
+    let worker: Worker = query_contract(
+        client,
+        &config.service_registry.cosmwasm_contract,
+        &service_registry::msg::QueryMsg::GetWorker {
+            service_name: service_name.to_string(),
+            worker: worker.to_string(),
+        },
+    )
+    .await?;
+
+    match worker.bonding_state {
+        BondingState::Bonded { amount } => Ok(format!("bonded {}", amount)),
+        bonding_state => Err(report!(Error::InvalidInput))
+            .attach_printable(format!("worker is not bonded: {:?}", bonding_state)),
+    }
+}
+
+async fn chain_support(
+    client: WasmQueryClient<Channel>,
+    config: &Config,
+    service_name: &nonempty::String,
+    worker: &TMAddress,
+    chain: &str,
+) -> Result<String, Error> {
+    let active_workers: Vec<Worker> = query_contract(
+        client,
+        &config.service_registry.cosmwasm_contract,
+        &service_registry::msg::QueryMsg::GetActiveWorkers {
+            service_name: service_name.to_string(),
+            chain_name: chain.parse().change_context(Error::InvalidInput)?,
+        },
+    )
+    .await?;
+
+    if !active_workers
+        .iter()
+        .any(|active_worker| active_worker.address.as_str() == worker.to_string())
+    {
+        return Err(report!(Error::InvalidInput)).attach_printable(
+            "worker is not active for the chain, it must be bonded, authorized and declare chain support",
+        );
+    }
+
+    Ok("worker is active".to_string())
+}
+
+async fn multisig_pub_key(
+    client: WasmQueryClient<Channel>,
+    multisig: &TMAddress,
+    worker: &TMAddress,
+    pub_key: &PublicKey,
+) -> Result<String, Error> {
+    let registered: MultisigPublicKey = query_contract(
+        client,
+        multisig,
+        &multisig::msg::QueryMsg::GetPublicKey {
+            worker_address: worker.to_string(),
+            key_type: KeyType::Ecdsa,
+        },
+    )
+    .await?;
+
+    let expected = MultisigPublicKey::try_from((KeyType::Ecdsa, pub_key.to_bytes().into()))
+        .change_context(Error::Tofnd)?;
+    if registered != expected {
+        return Err(report!(Error::InvalidInput))
+            .attach_printable("the registered public key does not match the signer's key");
+    }
+
+    Ok("registered".to_string())
+}
+
+async fn query_contract<Q, R>(
+    mut client: WasmQueryClient<Channel>,
+    contract: &TMAddress,
+    query: &Q,
+) -> Result<R, Error>
+where
+    Q: Serialize,
+    R: DeserializeOwned,
+{
+    let response = client
+        .smart_contract_state(QuerySmartContractStateRequest {
+            address: contract.to_string(),
+            query_data: serde_json::to_vec(query).expect("query msg should serialize"),
+        })
+        .await
+        .change_context(Error::QueryContract)?
+        .into_inner();
+
+    serde_json::from_slice(&response.data).change_context(Error::QueryContract)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmrs::AccountId;
+
+    use crate::commands::ServiceRegistryConfig;
+    use crate::config::Config;
+    use crate::handlers::config::Config as HandlerConfig;
+    use crate::PREFIX;
+
+    use super::{format_report, to_result, validate_config, Check};
+
+    #[test]
+    fn default_config_fails_validation() {
+        let checks = validate_config(&Config::default());
+
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|check| check.result.is_err()));
+    }
+
+    #[test]
+    fn complete_config_passes_validation() {
+        let config = Config {
+            handlers: vec![HandlerConfig::MultisigSigner {
+                cosmwasm_contract: AccountId::new(PREFIX, &[1; 32]).unwrap().into(),
+            }],
+            service_registry: ServiceRegistryConfig {
+                cosmwasm_contract: AccountId::new(PREFIX, &[2; 32]).unwrap().into(),
+            },
+            ..Config::default()
+        };
+
+        assert!(validate_config(&config)
+            .iter()
+            .all(|check| check.result.is_ok()));
+    }
+
+    #[test]
+    fn report_lists_every_check() {
+        let checks = vec![
+            Check {
+                name: "first".to_string(),
+                result: Ok("fine".to_string()),
+            },
+            Check {
+                name: "second".to_string(),
+                result: Err("broken".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            format_report(&checks),
+            "[PASS] first: fine\n[FAIL] second: broken"
+        );
+        assert!(to_result(checks).is_err());
+    }
+}
@@ -16,14 +16,14 @@ use valuable::Valuable;
 use crate::broadcaster::{accounts::account, Broadcaster};
 use crate::config::Config as AmpdConfig;
 use crate::state;
-use crate::tofnd::grpc::{MultisigClient, SharableEcdsaClient};
 use crate::types::{PublicKey, TMAddress};
-use crate::{broadcaster, Error};
-use crate::{tofnd, PREFIX};
+use crate::PREFIX;
+use crate::{broadcaster, signer, Error};
 
 pub mod bond_worker;
 pub mod daemon;
 pub mod declare_chain_support;
+pub mod doctor;
 pub mod register_public_key;
 pub mod worker_address;
 
@@ -36,9 +36,11 @@ pub enum SubCommand {
     /// Declare chain support to the service registry contract
     DeclareChainSupport(declare_chain_support::Args),
     /// Register public key to the multisig contract
-    RegisterPublicKey,
+    RegisterPublicKey(register_public_key::Args),
     /// Query the worker address
     WorkerAddress,
+    /// Check the config, the connections to all configured endpoints and the worker's on-chain registrations
+    Doctor(doctor::Args),
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
@@ -54,19 +56,16 @@ impl Default for ServiceRegistryConfig {
     }
 }
 
-async fn worker_pub_key(state_path: &Path, config: tofnd::Config) -> Result<PublicKey, Error> {
+async fn worker_pub_key(state_path: &Path, config: &AmpdConfig) -> Result<PublicKey, Error> {
     let state = state::load(state_path).change_context(Error::LoadConfig)?;
 
     match state.pub_key {
         Some(pub_key) => Ok(pub_key),
-        None => SharableEcdsaClient::new(
-            MultisigClient::connect(config.party_uid, config.url)
-                .await
-                .change_context(Error::Connection)?,
-        )
-        .keygen(&config.key_uid)
-        .await
-        .change_context(Error::Tofnd),
+        None => signer::connect(config.signer.clone(), config.tofnd_config.clone())
+            .await?
+            .keygen(&config.tofnd_config.key_uid)
+            .await
+            .change_context(Error::Tofnd),
     }
 }
 
@@ -79,6 +78,7 @@ async fn broadcast_tx(
         tm_grpc,
         broadcast,
         tofnd_config,
+        signer,
         ..
     } = config;
 
@@ -90,11 +90,7 @@ async fn broadcast_tx(
         .await
         .change_context(Error::Connection)?;
 
-    let ecdsa_client = SharableEcdsaClient::new(
-        MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
-            .await
-            .change_context(Error::Connection)?,
-    );
+    let ecdsa_client = signer::connect(signer, tofnd_config.clone()).await?;
 
     broadcaster::BroadcastClientBuilder::default()
         .client(service_client)
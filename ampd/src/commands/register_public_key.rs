@@ -8,22 +8,49 @@ use multisig::{
     msg::ExecuteMsg,
 };
 use report::ResultCompatExt;
+use valuable::Valuable;
 
 use crate::commands::{broadcast_tx, worker_pub_key};
 use crate::config::Config;
+use crate::signer::bls::LocalBlsSigner;
 use crate::types::TMAddress;
 use crate::{handlers, Error, PREFIX};
 
-pub async fn run(config: Config, state_path: &Path) -> Result<Option<String>, Error> {
-    let pub_key = worker_pub_key(state_path, config.tofnd_config.clone()).await?;
+#[derive(clap::Args, Debug, Valuable)]
+pub struct Args {
+    /// Register the bls12-381 key configured in bls_signer instead of the worker's ecdsa key
+    #[arg(long)]
+    pub bls12_381: bool,
+}
+
+pub async fn run(config: Config, state_path: &Path, args: Args) -> Result<Option<String>, Error> {
+    let pub_key = worker_pub_key(state_path, &config).await?;
 
     let multisig_address = get_multisig_address(&config)?;
 
-    let msg = serde_json::to_vec(&ExecuteMsg::RegisterPublicKey {
-        public_key: PublicKey::try_from((KeyType::Ecdsa, pub_key.to_bytes().into()))
-            .change_context(Error::Tofnd)?,
-    })
-    .expect("register public key msg should serialize");
+    let msg = if args.bls12_381 {
+        let bls_signer = config
+            .bls_signer
+            .as_ref()
+            .ok_or(Error::LoadConfig)
+            .attach_printable("no bls12-381 key found in config")
+            .and_then(|bls_config| {
+                LocalBlsSigner::load(bls_config).change_context(Error::LoadConfig)
+            })?;
+
+        ExecuteMsg::RegisterPublicKey {
+            public_key: PublicKey::try_from((KeyType::Bls12_381, bls_signer.pub_key()))
+                .change_context(Error::LoadConfig)?,
+            proof_of_possession: Some(bls_signer.prove_possession()),
+        }
+    } else {
+        ExecuteMsg::RegisterPublicKey {
+            public_key: PublicKey::try_from((KeyType::Ecdsa, pub_key.to_bytes().into()))
+                .change_context(Error::Tofnd)?,
+            proof_of_possession: None,
+        }
+    };
+    let msg = serde_json::to_vec(&msg).expect("register public key msg should serialize");
 
     let tx = MsgExecuteContract {
         sender: pub_key.account_id(PREFIX).change_context(Error::Tofnd)?,
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::broadcaster;
 use crate::commands::ServiceRegistryConfig;
 use crate::handlers::{self, config::deserialize_handler_configs};
+use crate::signer;
 use crate::tofnd::Config as TofndConfig;
 use crate::url::Url;
 
@@ -16,6 +17,8 @@ pub struct Config {
     #[serde(deserialize_with = "deserialize_handler_configs")]
     pub handlers: Vec<handlers::config::Config>,
     pub tofnd_config: TofndConfig,
+    pub signer: signer::Config,
+    pub bls_signer: Option<signer::bls::Config>,
     pub service_registry: ServiceRegistryConfig,
 }
 
@@ -27,6 +30,8 @@ impl Default for Config {
             broadcast: broadcaster::Config::default(),
             handlers: vec![],
             tofnd_config: TofndConfig::default(),
+            signer: signer::Config::default(),
+            bls_signer: None,
             event_buffer_cap: 100000,
             service_registry: ServiceRegistryConfig::default(),
         }
@@ -46,6 +51,7 @@ mod tests {
     use crate::evm::ChainName;
     use crate::handlers::config::Chain;
     use crate::handlers::config::Config as HandlerConfig;
+    use crate::signer;
     use crate::types::TMAddress;
     use crate::url::Url;
 
@@ -207,6 +213,42 @@ mod tests {
         assert_eq!(cfg.tofnd_config.key_uid.as_str(), key_uid);
     }
 
+    #[test]
+    fn deserialize_signer_config() {
+        let cfg: Config = toml::from_str("").unwrap();
+        assert_eq!(cfg.signer, signer::Config::Tofnd);
+        assert_eq!(cfg.bls_signer, None);
+
+        let config_str = "
+            [signer]
+            type = 'LocalKeystore'
+            path = '/home/ampd/keystore.json'
+            password_env_var = 'AMPD_KEYSTORE_PASSWORD'
+            ";
+
+        let cfg: Config = toml::from_str(config_str).unwrap();
+        assert_eq!(
+            cfg.signer,
+            signer::Config::LocalKeystore {
+                path: PathBuf::from("/home/ampd/keystore.json"),
+                password_env_var: "AMPD_KEYSTORE_PASSWORD".to_string(),
+            }
+        );
+
+        let config_str = "
+            [bls_signer]
+            path = '/home/ampd/bls_key'
+            ";
+
+        let cfg: Config = toml::from_str(config_str).unwrap();
+        assert_eq!(
+            cfg.bls_signer,
+            Some(signer::bls::Config {
+                path: PathBuf::from("/home/ampd/bls_key"),
+            })
+        );
+    }
+
     #[test]
     fn can_serialize_deserialize_config() {
         let cfg = config_template();
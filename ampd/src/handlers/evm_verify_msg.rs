@@ -125,9 +125,18 @@ where
         .collect())
     }
 
-    async fn broadcast_votes(&self, poll_id: PollId, votes: Vec<Vote>) -> Result<()> {
-        let msg = serde_json::to_vec(&ExecuteMsg::Vote { poll_id, votes })
-            .expect("vote msg should serialize");
+    async fn broadcast_votes(
+        &self,
+        poll_id: PollId,
+        votes: Vec<Vote>,
+        expires_at: u64,
+    ) -> Result<()> {
+        let msg = serde_json::to_vec(&ExecuteMsg::Vote {
+            poll_id,
+            votes,
+            justification: None,
+        })
+        .expect("vote msg should serialize");
         let tx = MsgExecuteContract {
             sender: self.worker.as_ref().clone(),
             contract: self.voting_verifier.as_ref().clone(),
@@ -136,7 +145,7 @@ where
         };
 
         self.broadcast_client
-            .broadcast(tx)
+            .broadcast_with_deadline(tx, expires_at)
             .await
             .change_context(Error::Broadcaster)
     }
@@ -228,7 +237,7 @@ where
             votes
         });
 
-        self.broadcast_votes(poll_id, votes).await
+        self.broadcast_votes(poll_id, votes, expires_at).await
     }
 }
 
@@ -118,10 +118,11 @@ where
         }))
     }
 
-    async fn broadcast_vote(&self, poll_id: PollId, vote: Vote) -> Result<()> {
+    async fn broadcast_vote(&self, poll_id: PollId, vote: Vote, expires_at: u64) -> Result<()> {
         let msg = serde_json::to_vec(&ExecuteMsg::Vote {
             poll_id,
             votes: vec![vote],
+            justification: None,
         })
         .expect("vote msg should serialize");
         let tx = MsgExecuteContract {
@@ -132,7 +133,7 @@ where
         };
 
         self.broadcast_client
-            .broadcast(tx)
+            .broadcast_with_deadline(tx, expires_at)
             .await
             .change_context(Error::Broadcaster)
     }
@@ -207,7 +208,7 @@ where
             vote
         });
 
-        self.broadcast_vote(poll_id, vote).await
+        self.broadcast_vote(poll_id, vote, expires_at).await
     }
 }
 
@@ -5,7 +5,7 @@ use async_trait::async_trait;
 use cosmrs::cosmwasm::MsgExecuteContract;
 use cosmwasm_std::{HexBinary, Uint64};
 use ecdsa::VerifyingKey;
-use error_stack::ResultExt;
+use error_stack::{Report, ResultExt};
 use hex::encode;
 use serde::de::Error as DeserializeError;
 use serde::{Deserialize, Deserializer};
@@ -15,10 +15,12 @@ use events::Error::EventTypeMismatch;
 use events_derive;
 use events_derive::try_from;
 use multisig::msg::ExecuteMsg;
+use multisig::types::{DigestScheme, MsgToSign};
 
 use crate::event_processor::EventHandler;
 use crate::handlers::errors::Error::{self, DeserializeEvent};
 use crate::queue::queued_broadcaster::BroadcasterClient;
+use crate::signer::bls::LocalBlsSigner;
 use crate::tofnd::grpc::SharableEcdsaClient;
 use crate::tofnd::MessageDigest;
 use crate::types::PublicKey;
@@ -31,14 +33,23 @@ struct SigningStartedEvent {
     contract_address: TMAddress,
     session_id: u64,
     #[serde(deserialize_with = "deserialize_public_keys")]
-    pub_keys: HashMap<TMAddress, PublicKey>,
+    pub_keys: HashMap<TMAddress, SignerKey>,
     #[serde(with = "hex")]
     msg: MessageDigest,
+    #[serde(default)]
+    digest_scheme: DigestScheme,
+}
+
+// tofnd holds the ecdsa and ed25519 keys, bls12-381 keys are held by the local bls signer
+#[derive(Debug)]
+enum SignerKey {
+    Tofnd(PublicKey),
+    Bls12_381(HexBinary),
 }
 
 fn deserialize_public_keys<'de, D>(
     deserializer: D,
-) -> Result<HashMap<TMAddress, PublicKey>, D::Error>
+) -> Result<HashMap<TMAddress, SignerKey>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -50,9 +61,11 @@ where
         .map(|(address, pk)| match pk {
             multisig::key::PublicKey::Ecdsa(hex) => Ok((
                 address,
-                VerifyingKey::from_sec1_bytes(hex.as_ref())
-                    .map_err(D::Error::custom)?
-                    .into(),
+                SignerKey::Tofnd(
+                    VerifyingKey::from_sec1_bytes(hex.as_ref())
+                        .map_err(D::Error::custom)?
+                        .into(),
+                ),
             )),
 
             multisig::key::PublicKey::Ed25519(hex) => {
@@ -60,8 +73,10 @@ where
                     cosmrs::tendermint::crypto::ed25519::VerificationKey::try_from(hex.as_ref())
                         .map_err(D::Error::custom)?
                         .into();
-                Ok((address, pk.into()))
+                Ok((address, SignerKey::Tofnd(pk.into())))
             }
+
+            multisig::key::PublicKey::Bls12_381(hex) => Ok((address, SignerKey::Bls12_381(hex))),
         })
         .collect()
 }
@@ -74,6 +89,7 @@ where
     multisig: TMAddress,
     broadcaster: B,
     signer: SharableEcdsaClient,
+    bls_signer: Option<LocalBlsSigner>,
 }
 
 impl<B> Handler<B>
@@ -85,12 +101,14 @@ where
         multisig: TMAddress,
         broadcaster: B,
         signer: SharableEcdsaClient,
+        bls_signer: Option<LocalBlsSigner>,
     ) -> Self {
         Self {
             worker,
             multisig,
             broadcaster,
             signer,
+            bls_signer,
         }
     }
 
@@ -119,6 +137,14 @@ where
     }
 }
 
+fn digest(digest_scheme: DigestScheme, msg: &MessageDigest) -> MessageDigest {
+    let digest = digest_scheme.digest(&MsgToSign::unchecked(HexBinary::from(msg.as_ref())));
+
+    <[u8; 32]>::try_from(digest.as_ref())
+        .expect("violated invariant: digest schemes hash to 32 bytes")
+        .into()
+}
+
 #[async_trait]
 impl<B> EventHandler for Handler<B>
 where
@@ -132,6 +158,7 @@ where
             session_id,
             pub_keys,
             msg,
+            digest_scheme,
         } = match event.try_into() as error_stack::Result<_, _> {
             Err(report) if matches!(report.current_context(), EventTypeMismatch(_)) => {
                 return Ok(());
@@ -149,25 +176,31 @@ where
             "get signing request",
         );
 
-        match pub_keys.get(&self.worker) {
-            Some(pub_key) => {
-                let signature = self
-                    .signer
-                    .sign(self.multisig.to_string().as_str(), msg.clone(), pub_key)
-                    .await
-                    .change_context(Error::Sign)?;
-
-                info!(signature = encode(&signature), "ready to submit signature");
-
-                self.broadcast_signature(session_id, signature).await?;
-
-                Ok(())
-            }
+        // the multisig contract verifies signatures over the msg hashed with the key's digest scheme
+        let msg = digest(digest_scheme, &msg);
+
+        let signature = match pub_keys.get(&self.worker) {
+            Some(SignerKey::Tofnd(pub_key)) => self
+                .signer
+                .sign(self.multisig.to_string().as_str(), msg.clone(), pub_key)
+                .await
+                .change_context(Error::Sign)?,
+            Some(SignerKey::Bls12_381(pub_key)) => self
+                .bls_signer
+                .as_ref()
+                .ok_or_else(|| Report::new(Error::Sign))
+                .attach_printable("no bls12-381 key is configured")?
+                .sign(&msg, pub_key)
+                .change_context(Error::Sign)?,
             None => {
                 info!("worker is not a participant");
-                Ok(())
+                return Ok(());
             }
-        }
+        };
+
+        info!(signature = encode(&signature), "ready to submit signature");
+
+        self.broadcast_signature(session_id, signature).await
     }
 }
 
@@ -196,6 +229,7 @@ mod test {
 
     use crate::broadcaster::MockBroadcaster;
     use crate::queue::queued_broadcaster::{QueuedBroadcaster, QueuedBroadcasterClient};
+    use crate::signer::bls;
     use crate::tofnd;
     use crate::tofnd::grpc::{MockEcdsaClient, SharableEcdsaClient};
     use crate::types;
@@ -237,12 +271,24 @@ mod test {
             .map(|_| (rand_account().to_string(), rand_public_key()))
             .collect::<HashMap<String, PublicKey>>();
 
+        signing_started_event_with_keys(pub_keys)
+    }
+
+    fn signing_started_event_with_keys(pub_keys: HashMap<String, PublicKey>) -> events::Event {
+        signing_started_event_with_digest_scheme(pub_keys, DigestScheme::Raw)
+    }
+
+    fn signing_started_event_with_digest_scheme(
+        pub_keys: HashMap<String, PublicKey>,
+        digest_scheme: DigestScheme,
+    ) -> events::Event {
         let poll_started = SigningStarted {
             session_id: Uint64::one(),
             worker_set_id: "worker_set_id".to_string(),
             pub_keys,
             msg: MsgToSign::unchecked(rand_message()),
             chain_name: rand_chain_name(),
+            digest_scheme,
         };
 
         let mut event: cosmwasm_std::Event = poll_started.into();
@@ -265,6 +311,7 @@ mod test {
         worker: TMAddress,
         multisig: TMAddress,
         signer: SharableEcdsaClient,
+        bls_signer: Option<LocalBlsSigner>,
     ) -> Handler<QueuedBroadcasterClient> {
         let mut broadcaster = MockBroadcaster::new();
         broadcaster
@@ -274,7 +321,7 @@ mod test {
         let (broadcaster, _) =
             QueuedBroadcaster::new(broadcaster, Gas::default(), 100, Duration::from_secs(5));
 
-        Handler::new(worker, multisig, broadcaster.client(), signer)
+        Handler::new(worker, multisig, broadcaster.client(), signer, bls_signer)
     }
 
     #[test]
@@ -343,6 +390,7 @@ mod test {
             rand_account(),
             rand_account(),
             SharableEcdsaClient::new(client),
+            None,
         );
 
         assert!(handler.handle(&signing_started_event()).await.is_ok());
@@ -359,6 +407,7 @@ mod test {
             rand_account(),
             TMAddress::from(MULTISIG_ADDRESS.parse::<AccountId>().unwrap()),
             SharableEcdsaClient::new(client),
+            None,
         );
 
         assert!(handler.handle(&signing_started_event()).await.is_ok());
@@ -378,6 +427,7 @@ mod test {
             worker,
             TMAddress::from(MULTISIG_ADDRESS.parse::<AccountId>().unwrap()),
             SharableEcdsaClient::new(client),
+            None,
         );
 
         assert!(matches!(
@@ -385,4 +435,86 @@ mod test {
             Error::Sign
         ));
     }
+
+    #[tokio::test]
+    async fn should_sign_msg_hashed_with_digest_scheme() {
+        let worker = rand_account();
+        let event = signing_started_event_with_digest_scheme(
+            HashMap::from([(worker.to_string(), rand_public_key())]),
+            DigestScheme::Keccak256,
+        );
+        let signing_started: SigningStartedEvent = ((&event).try_into() as Result<_, _>).unwrap();
+        let expected_digest = ethers::utils::keccak256(signing_started.msg);
+
+        let mut client = MockEcdsaClient::new();
+        client
+            .expect_sign()
+            .withf(move |_, digest, _| digest.as_ref() == expected_digest)
+            .times(1)
+            .returning(|_, _, _| Ok(vec![1u8; 64]));
+
+        let handler = get_handler(
+            worker,
+            TMAddress::from(MULTISIG_ADDRESS.parse::<AccountId>().unwrap()),
+            SharableEcdsaClient::new(client),
+            None,
+        );
+
+        assert!(handler.handle(&event).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_sign_with_bls_key() {
+        let mut client = MockEcdsaClient::new();
+        client.expect_sign().never();
+
+        let path = std::env::temp_dir().join(random_string::generate(10, "abcdefghijklmnop"));
+        let bls_signer = LocalBlsSigner::load(&bls::Config { path: path.clone() }).unwrap();
+
+        let worker = rand_account();
+        let pub_keys = HashMap::from([(
+            worker.to_string(),
+            PublicKey::Bls12_381(bls_signer.pub_key()),
+        )]);
+        let handler = get_handler(
+            worker,
+            TMAddress::from(MULTISIG_ADDRESS.parse::<AccountId>().unwrap()),
+            SharableEcdsaClient::new(client),
+            Some(bls_signer),
+        );
+
+        assert!(handler
+            .handle(&signing_started_event_with_keys(pub_keys))
+            .await
+            .is_ok());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_not_handle_event_with_bls_key_if_no_bls_key_is_configured() {
+        let mut client = MockEcdsaClient::new();
+        client.expect_sign().never();
+
+        let worker = rand_account();
+        let pub_keys = HashMap::from([(
+            worker.to_string(),
+            PublicKey::Bls12_381(multisig::bls::SecretKey::from_seed(&[1u8; 64]).pub_key()),
+        )]);
+        let handler = get_handler(
+            worker,
+            TMAddress::from(MULTISIG_ADDRESS.parse::<AccountId>().unwrap()),
+            SharableEcdsaClient::new(client),
+            None,
+        );
+
+        assert!(matches!(
+            *handler
+                .handle(&signing_started_event_with_keys(pub_keys))
+                .await
+                .unwrap_err()
+                .current_context(),
+            Error::Sign
+        ));
+    }
 }
@@ -76,9 +76,18 @@ where
             latest_block_height,
         }
     }
-    async fn broadcast_votes(&self, poll_id: PollId, votes: Vec<Vote>) -> Result<()> {
-        let msg = serde_json::to_vec(&ExecuteMsg::Vote { poll_id, votes })
-            .expect("vote msg should serialize");
+    async fn broadcast_votes(
+        &self,
+        poll_id: PollId,
+        votes: Vec<Vote>,
+        expires_at: u64,
+    ) -> Result<()> {
+        let msg = serde_json::to_vec(&ExecuteMsg::Vote {
+            poll_id,
+            votes,
+            justification: None,
+        })
+        .expect("vote msg should serialize");
         let tx = MsgExecuteContract {
             sender: self.worker.as_ref().clone(),
             contract: self.voting_verifier.as_ref().clone(),
@@ -87,7 +96,7 @@ where
         };
 
         self.broadcast_client
-            .broadcast(tx)
+            .broadcast_with_deadline(tx, expires_at)
             .await
             .change_context(Error::Broadcaster)
     }
@@ -151,7 +160,7 @@ where
             })
             .collect();
 
-        self.broadcast_votes(poll_id, votes).await
+        self.broadcast_votes(poll_id, votes, expires_at).await
     }
 }
 
@@ -290,11 +299,9 @@ mod tests {
             .returning(|_| Ok(HashMap::new()));
 
         let mut broadcast_client = MockBroadcasterClient::new();
-        broadcast_client
-            .expect_broadcast()
-            .returning(move |_: MsgExecuteContract| {
-                Err(Report::from(queued_broadcaster::Error::Broadcast))
-            });
+        broadcast_client.expect_broadcast_with_deadline().returning(
+            move |_: MsgExecuteContract, _| Err(Report::from(queued_broadcaster::Error::Broadcast)),
+        );
 
         let voting_verifier = TMAddress::random(PREFIX);
         let worker = TMAddress::random(PREFIX);
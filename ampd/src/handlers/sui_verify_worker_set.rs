@@ -81,10 +81,16 @@ where
             latest_block_height,
         }
     }
-    async fn broadcast_vote(&self, poll_id: PollId, vote: Vote) -> error_stack::Result<(), Error> {
+    async fn broadcast_vote(
+        &self,
+        poll_id: PollId,
+        vote: Vote,
+        expires_at: u64,
+    ) -> error_stack::Result<(), Error> {
         let msg = serde_json::to_vec(&ExecuteMsg::Vote {
             poll_id,
             votes: vec![vote],
+            justification: None,
         })
         .expect("vote msg should serialize");
         let tx = MsgExecuteContract {
@@ -95,7 +101,7 @@ where
         };
 
         self.broadcast_client
-            .broadcast(tx)
+            .broadcast_with_deadline(tx, expires_at)
             .await
             .change_context(Error::Broadcaster)
     }
@@ -166,7 +172,7 @@ where
             vote
         });
 
-        self.broadcast_vote(poll_id, vote).await
+        self.broadcast_vote(poll_id, vote, expires_at).await
     }
 }
 
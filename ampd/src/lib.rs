@@ -17,8 +17,9 @@ use broadcaster::{accounts::account, Broadcaster};
 use event_processor::{EventHandler, EventProcessor};
 use events::Event;
 use queue::queued_broadcaster::{QueuedBroadcaster, QueuedBroadcasterDriver};
+use signer::bls::LocalBlsSigner;
 use state::StateUpdater;
-use tofnd::grpc::{MultisigClient, SharableEcdsaClient};
+use tofnd::grpc::SharableEcdsaClient;
 use types::TMAddress;
 
 use crate::config::Config;
@@ -35,6 +36,7 @@ mod evm;
 mod handlers;
 mod json_rpc;
 mod queue;
+mod signer;
 pub mod state;
 mod sui;
 mod tm_client;
@@ -62,6 +64,8 @@ async fn prepare_app(cfg: Config, state: State) -> Result<App<impl Broadcaster>,
         broadcast,
         handlers,
         tofnd_config,
+        signer,
+        bls_signer,
         event_buffer_cap,
         service_registry: _service_registry,
     } = cfg;
@@ -74,10 +78,11 @@ async fn prepare_app(cfg: Config, state: State) -> Result<App<impl Broadcaster>,
     let query_client = QueryClient::connect(tm_grpc.to_string())
         .await
         .change_context(Error::Connection)?;
-    let multisig_client = MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
-        .await
-        .change_context(Error::Connection)?;
-    let ecdsa_client = SharableEcdsaClient::new(multisig_client);
+    let ecdsa_client = signer::connect(signer, tofnd_config.clone()).await?;
+    let bls_signer = bls_signer
+        .map(|config| signer::bls::LocalBlsSigner::load(&config))
+        .transpose()
+        .change_context(Error::LoadConfig)?;
 
     let block_height_monitor = BlockHeightMonitor::connect(tm_client.clone())
         .await
@@ -120,6 +125,7 @@ async fn prepare_app(cfg: Config, state: State) -> Result<App<impl Broadcaster>,
         broadcaster,
         state_updater,
         ecdsa_client,
+        bls_signer,
         broadcast,
         event_buffer_cap,
         block_height_monitor,
@@ -138,6 +144,7 @@ where
     broadcaster_driver: QueuedBroadcasterDriver,
     state_updater: StateUpdater,
     ecdsa_client: SharableEcdsaClient,
+    bls_signer: Option<LocalBlsSigner>,
     block_height_monitor: BlockHeightMonitor<tendermint_rpc::HttpClient>,
     token: CancellationToken,
 }
@@ -151,6 +158,7 @@ where
         broadcaster: T,
         state_updater: StateUpdater,
         ecdsa_client: SharableEcdsaClient,
+        bls_signer: Option<LocalBlsSigner>,
         broadcast_cfg: broadcaster::Config,
         event_buffer_cap: usize,
         block_height_monitor: BlockHeightMonitor<tendermint_rpc::HttpClient>,
@@ -178,6 +186,7 @@ where
             broadcaster_driver,
             state_updater,
             ecdsa_client,
+            bls_signer,
             block_height_monitor,
             token,
         }
@@ -220,16 +229,20 @@ where
                         self.block_height_monitor.latest_block_height(),
                     ),
                 ),
-                handlers::config::Config::MultisigSigner { cosmwasm_contract } => self
-                    .configure_handler(
+                handlers::config::Config::MultisigSigner { cosmwasm_contract } => {
+                    // there is only one multisig signer, so it can take the bls signer
+                    let bls_signer = self.bls_signer.take();
+                    self.configure_handler(
                         "multisig-signer",
                         handlers::multisig::Handler::new(
                             worker.clone(),
                             cosmwasm_contract,
                             self.broadcaster.client(),
                             self.ecdsa_client.clone(),
+                            bls_signer,
                         ),
-                    ),
+                    )
+                }
                 handlers::config::Config::SuiMsgVerifier {
                     cosmwasm_contract,
                     rpc_url,
@@ -369,4 +382,8 @@ pub enum Error {
     InvalidInput,
     #[error("block height monitor failed")]
     BlockHeightMonitor,
+    #[error("failed to query contract")]
+    QueryContract,
+    #[error("doctor checks failed")]
+    DoctorChecks,
 }
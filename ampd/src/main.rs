@@ -11,7 +11,8 @@ use tracing::{error, info};
 use valuable::Valuable;
 
 use ampd::commands::{
-    bond_worker, daemon, declare_chain_support, register_public_key, worker_address, SubCommand,
+    bond_worker, daemon, declare_chain_support, doctor, register_public_key, worker_address,
+    SubCommand,
 };
 use ampd::config::Config;
 use ampd::Error;
@@ -65,8 +66,14 @@ async fn main() -> ExitCode {
         Some(SubCommand::DeclareChainSupport(args)) => {
             declare_chain_support::run(cfg, &state_path, args).await
         }
-        Some(SubCommand::RegisterPublicKey) => register_public_key::run(cfg, &state_path).await,
-        Some(SubCommand::WorkerAddress) => worker_address::run(cfg.tofnd_config, &state_path).await,
+        Some(SubCommand::RegisterPublicKey(args)) => {
+            register_public_key::run(cfg, &state_path, args).await
+        }
+        Some(SubCommand::WorkerAddress) => worker_address::run(cfg, &state_path).await,
+        // the config is loaded again, because the doctor needs to report why it could not be parsed
+        Some(SubCommand::Doctor(doctor_args)) => {
+            doctor::run(load_config(&args.config), &state_path, doctor_args).await
+        }
     };
 
     match result {
@@ -101,14 +108,17 @@ fn set_up_logger(output: &Output) {
 }
 
 fn init_config(config_paths: &[PathBuf]) -> Config {
-    let files = find_config_files(config_paths);
-
-    parse_config(files)
-        .change_context(Error::LoadConfig)
+    load_config(config_paths)
         .tap_err(|report| error!(err = LoggableError::from(report).as_value(), "{report}"))
         .unwrap_or(Config::default())
 }
 
+fn load_config(config_paths: &[PathBuf]) -> error_stack::Result<Config, Error> {
+    let files = find_config_files(config_paths);
+
+    parse_config(files).change_context(Error::LoadConfig)
+}
+
 fn find_config_files(config: &[PathBuf]) -> Vec<File<FileSourceFile, FileFormat>> {
     let files = config
         .iter()
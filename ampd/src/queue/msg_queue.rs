@@ -1,23 +1,60 @@
 use cosmrs::{Any, Gas};
 
+struct QueuedMsg {
+    msg: Any,
+    gas_cost: Gas,
+    deadline: Option<u64>,
+}
+
+/// Messages are kept ordered by deadline, so messages closest to their deadline are broadcast first.
+/// Messages without a deadline come last, and messages with the same deadline keep their insertion order.
 #[derive(Default)]
 pub struct MsgQueue {
-    msgs: Vec<Any>,
+    msgs: Vec<QueuedMsg>,
     gas_cost: Gas,
 }
 
 impl MsgQueue {
-    pub fn push(&mut self, msg: Any, gas_cost: Gas) {
-        self.msgs.push(msg);
+    pub fn push(&mut self, msg: Any, gas_cost: Gas, deadline: Option<u64>) {
+        let pos = self
+            .msgs
+            .partition_point(|queued| priority(queued.deadline) <= priority(deadline));
+        self.msgs.insert(
+            pos,
+            QueuedMsg {
+                msg,
+                gas_cost,
+                deadline,
+            },
+        );
         self.gas_cost += gas_cost;
     }
 
     pub fn pop_all(&mut self) -> Vec<Any> {
-        let msgs = self.msgs.clone();
-        self.msgs.clear();
         self.gas_cost = 0;
 
-        msgs
+        self.msgs.drain(..).map(|queued| queued.msg).collect()
+    }
+
+    /// Pops the most urgent messages whose combined gas cost stays below the gas limit.
+    /// At least one message is popped if the queue is not empty, even if it exceeds the gas limit on its own.
+    pub fn pop_batch(&mut self, gas_limit: Gas) -> Vec<Any> {
+        let mut batch_gas_cost = 0;
+        let batch_size = self
+            .msgs
+            .iter()
+            .take_while(|queued| {
+                batch_gas_cost += queued.gas_cost;
+                batch_gas_cost < gas_limit
+            })
+            .count()
+            .max(1)
+            .min(self.msgs.len());
+
+        let batch: Vec<_> = self.msgs.drain(..batch_size).collect();
+        self.gas_cost -= batch.iter().map(|queued| queued.gas_cost).sum::<Gas>();
+
+        batch.into_iter().map(|queued| queued.msg).collect()
     }
 
     pub fn gas_cost(&self) -> Gas {
@@ -27,12 +64,27 @@ impl MsgQueue {
     pub fn len(&self) -> usize {
         self.msgs.len()
     }
+
+    pub fn earliest_deadline(&self) -> Option<u64> {
+        self.msgs.first().and_then(|queued| queued.deadline)
+    }
+
+    pub fn deadlines(&self) -> Vec<u64> {
+        self.msgs
+            .iter()
+            .filter_map(|queued| queued.deadline)
+            .collect()
+    }
+}
+
+fn priority(deadline: Option<u64>) -> u64 {
+    deadline.unwrap_or(u64::MAX)
 }
 
 #[cfg(test)]
 mod test {
     use cosmos_sdk_proto::Any;
-    use cosmrs::{bank::MsgSend, tx::Msg, AccountId};
+    use cosmrs::{bank::MsgSend, tx::Msg, AccountId, Coin};
 
     use super::MsgQueue;
 
@@ -40,7 +92,7 @@ mod test {
     fn msg_queue_push_should_work() {
         let mut queue = MsgQueue::default();
         for gas_cost in 1..5 {
-            queue.push(dummy_msg(), gas_cost);
+            queue.push(dummy_msg(), gas_cost, None);
         }
 
         assert_eq!(queue.gas_cost(), 10);
@@ -51,7 +103,7 @@ mod test {
     fn msg_queue_pop_all_should_work() {
         let mut queue = MsgQueue::default();
         for gas_cost in 1..5 {
-            queue.push(dummy_msg(), gas_cost);
+            queue.push(dummy_msg(), gas_cost, None);
         }
 
         assert_eq!(queue.pop_all().len(), 4);
@@ -59,11 +111,61 @@ mod test {
         assert_eq!(queue.msgs.len(), 0);
     }
 
+    #[test]
+    fn msg_queue_should_order_by_deadline() {
+        let mut queue = MsgQueue::default();
+        queue.push(dummy_msg_with_amount(0), 1, None);
+        queue.push(dummy_msg_with_amount(1), 1, Some(300));
+        queue.push(dummy_msg_with_amount(2), 1, Some(100));
+        queue.push(dummy_msg_with_amount(3), 1, Some(300));
+        queue.push(dummy_msg_with_amount(4), 1, Some(200));
+
+        assert_eq!(queue.earliest_deadline(), Some(100));
+        assert_eq!(queue.deadlines(), vec![100, 200, 300, 300]);
+        assert_eq!(
+            queue.pop_all(),
+            vec![
+                dummy_msg_with_amount(2),
+                dummy_msg_with_amount(4),
+                dummy_msg_with_amount(1),
+                dummy_msg_with_amount(3),
+                dummy_msg_with_amount(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn msg_queue_pop_batch_should_pop_most_urgent_msgs_within_gas_limit() {
+        let mut queue = MsgQueue::default();
+        queue.push(dummy_msg_with_amount(0), 40, Some(300));
+        queue.push(dummy_msg_with_amount(1), 40, Some(200));
+        queue.push(dummy_msg_with_amount(2), 40, Some(100));
+
+        assert_eq!(
+            queue.pop_batch(100),
+            vec![dummy_msg_with_amount(2), dummy_msg_with_amount(1)]
+        );
+        assert_eq!(queue.gas_cost(), 40);
+        assert_eq!(queue.earliest_deadline(), Some(300));
+
+        // a single message exceeding the gas limit is still popped
+        assert_eq!(queue.pop_batch(10), vec![dummy_msg_with_amount(0)]);
+        assert_eq!(queue.gas_cost(), 0);
+        assert!(queue.pop_batch(10).is_empty());
+    }
+
     fn dummy_msg() -> Any {
+        dummy_msg_with_amount(0)
+    }
+
+    fn dummy_msg_with_amount(amount: u128) -> Any {
         MsgSend {
             from_address: AccountId::new("", &[1, 2, 3]).unwrap(),
             to_address: AccountId::new("", &[4, 5, 6]).unwrap(),
-            amount: vec![],
+            amount: vec![Coin {
+                denom: "uaxl".parse().unwrap(),
+                amount,
+            }],
         }
         .to_any()
         .unwrap()
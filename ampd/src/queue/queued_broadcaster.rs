@@ -45,28 +45,56 @@ pub trait BroadcasterClient {
     async fn broadcast<T>(&self, tx: T) -> Result
     where
         T: Msg + Send + Sync + 'static;
+
+    /// Broadcasts a message that must be included in a block before the given block height, e.g. a vote before its poll expires.
+    /// Queued messages are broadcast in order of their deadlines, regardless of which handler sent them.
+    async fn broadcast_with_deadline<T>(&self, tx: T, deadline: u64) -> Result
+    where
+        T: Msg + Send + Sync + 'static;
+}
+
+struct QueuedTx {
+    msg: Any,
+    deadline: Option<u64>,
 }
 
 pub struct QueuedBroadcasterClient {
-    sender: mpsc::Sender<Any>,
+    sender: mpsc::Sender<QueuedTx>,
 }
 
-#[async_trait]
-impl BroadcasterClient for QueuedBroadcasterClient {
-    async fn broadcast<T>(&self, tx: T) -> Result
+impl QueuedBroadcasterClient {
+    async fn send<T>(&self, tx: T, deadline: Option<u64>) -> Result
     where
         T: Msg + Send + Sync + 'static,
     {
+        let msg = tx
+            .into_any()
+            .map_err(|err| Report::new(Error::Proto(err.to_string())))?;
+
         self.sender
-            .send(
-                tx.into_any()
-                    .map_err(|err| Report::new(Error::Proto(err.to_string())))?,
-            )
+            .send(QueuedTx { msg, deadline })
             .await
             .map_err(|_| Report::new(Error::Broadcast))
     }
 }
 
+#[async_trait]
+impl BroadcasterClient for QueuedBroadcasterClient {
+    async fn broadcast<T>(&self, tx: T) -> Result
+    where
+        T: Msg + Send + Sync + 'static,
+    {
+        self.send(tx, None).await
+    }
+
+    async fn broadcast_with_deadline<T>(&self, tx: T, deadline: u64) -> Result
+    where
+        T: Msg + Send + Sync + 'static,
+    {
+        self.send(tx, Some(deadline)).await
+    }
+}
+
 pub struct QueuedBroadcaster<T>
 where
     T: Broadcaster,
@@ -75,7 +103,7 @@ where
     queue: MsgQueue,
     batch_gas_limit: Gas,
     broadcast_interval: Duration,
-    channel: (mpsc::Sender<Any>, mpsc::Receiver<Any>),
+    channel: (mpsc::Sender<QueuedTx>, mpsc::Receiver<QueuedTx>),
     broadcast_rx: mpsc::Receiver<()>,
 }
 
@@ -115,24 +143,29 @@ where
 
         loop {
             select! {
-              msg = rx.recv() => match msg {
+              tx = rx.recv() => match tx {
                 None => break,
-                Some(msg) => {
+                Some(QueuedTx { msg, deadline }) => {
                   let fee = broadcaster.estimate_fee(vec![msg.clone()]).await.change_context(Error::EstimateFee)?;
 
-                  if fee.gas_limit + queue.gas_cost() >= self.batch_gas_limit {
-                    interval.reset();
-                    broadcast_all(&mut queue, &mut broadcaster).await?;
-                  }
-
                   let message_type = msg.type_url.clone();
-                  queue.push(msg, fee.gas_limit);
+                  queue.push(msg, fee.gas_limit, deadline);
                   info!(
                     message_type,
+                    deadline,
                     queue_size = queue.len(),
                     queue_gas_cost = queue.gas_cost(),
+                    queue_earliest_deadline = queue.earliest_deadline(),
+                    queue_deadlines = ?queue.deadlines(),
                     "pushed a new message into the queue"
                   );
+
+                  // the most urgent messages go first, so a burst of new messages cannot hold back ones close to their deadline
+                  while queue.gas_cost() >= self.batch_gas_limit {
+                    interval.reset();
+                    let msgs = queue.pop_batch(self.batch_gas_limit);
+                    broadcast(msgs, &mut broadcaster).await?;
+                  }
                 }
               },
               _ = interval.tick() => broadcast_all(&mut queue, &mut broadcaster).await?,
@@ -159,8 +192,13 @@ async fn broadcast_all<T>(queue: &mut MsgQueue, broadcaster: &mut T) -> Result
 where
     T: Broadcaster,
 {
-    let msgs = queue.pop_all();
+    broadcast(queue.pop_all(), broadcaster).await
+}
 
+async fn broadcast<T>(msgs: Vec<Any>, broadcaster: &mut T) -> Result
+where
+    T: Broadcaster,
+{
     match msgs.len() {
         0 => Ok(()),
         n => {
@@ -179,7 +217,7 @@ where
 mod test {
     use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
     use cosmrs::tx::Fee;
-    use cosmrs::{bank::MsgSend, tx::Msg, AccountId};
+    use cosmrs::{bank::MsgSend, tx::Msg, AccountId, Coin};
     use tokio::test;
     use tokio::time::{sleep, Duration};
 
@@ -374,6 +412,73 @@ mod test {
         assert!(handler.await.is_ok());
     }
 
+    #[test]
+    async fn should_broadcast_msgs_closest_to_deadline_first() {
+        let batch_gas_limit = 100;
+        let gas_limit = 40;
+
+        let mut broadcaster = MockBroadcaster::new();
+        broadcaster
+            .expect_estimate_fee()
+            .times(4)
+            .returning(move |_| {
+                Ok(Fee {
+                    gas_limit,
+                    amount: vec![],
+                    granter: None,
+                    payer: None,
+                })
+            });
+        broadcaster
+            .expect_broadcast()
+            .once()
+            .returning(move |msgs| {
+                assert_eq!(
+                    msgs,
+                    vec![
+                        dummy_msg_with_amount(2).to_any().unwrap(),
+                        dummy_msg_with_amount(1).to_any().unwrap()
+                    ]
+                );
+
+                Ok(TxResponse::default())
+            });
+        broadcaster
+            .expect_broadcast()
+            .once()
+            .returning(move |msgs| {
+                assert_eq!(
+                    msgs,
+                    vec![
+                        dummy_msg_with_amount(0).to_any().unwrap(),
+                        dummy_msg_with_amount(3).to_any().unwrap()
+                    ]
+                );
+
+                Ok(TxResponse::default())
+            });
+
+        let (client, _driver) =
+            QueuedBroadcaster::new(broadcaster, batch_gas_limit, 4, Duration::from_secs(5));
+
+        let tx = client.client();
+        tx.broadcast_with_deadline(dummy_msg_with_amount(0), 300)
+            .await
+            .unwrap();
+        tx.broadcast_with_deadline(dummy_msg_with_amount(1), 200)
+            .await
+            .unwrap();
+        // the queue exceeds the gas limit with this message, so the two most urgent ones are broadcast right away
+        tx.broadcast_with_deadline(dummy_msg_with_amount(2), 100)
+            .await
+            .unwrap();
+        // messages without deadline are broadcast last
+        tx.broadcast(dummy_msg_with_amount(3)).await.unwrap();
+        drop(tx);
+
+        assert!(client.run().await.is_ok());
+    }
+
     fn dummy_msg() -> impl Msg {
         MsgSend {
             from_address: AccountId::new("", &[1, 2, 3]).unwrap(),
@@ -381,4 +486,15 @@ mod test {
             amount: vec![],
         }
     }
+
+    fn dummy_msg_with_amount(amount: u128) -> MsgSend {
+        MsgSend {
+            from_address: AccountId::new("", &[1, 2, 3]).unwrap(),
+            to_address: AccountId::new("", &[4, 5, 6]).unwrap(),
+            amount: vec![Coin {
+                denom: "uaxl".parse().unwrap(),
+                amount,
+            }],
+        }
+    }
 }
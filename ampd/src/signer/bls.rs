@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cosmwasm_std::HexBinary;
+use ecdsa::signature::rand_core::{OsRng, RngCore};
+use error_stack::{Report, ResultExt};
+use multisig::bls::SecretKey;
+use serde::{Deserialize, Serialize};
+
+use crate::tofnd::{error::Error, MessageDigest, Signature};
+
+type Result<T> = error_stack::Result<T, Error>;
+
+/// Location of the worker's bls12-381 key. Only workers that sign for chains with bls12-381 keys need one
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+pub struct Config {
+    pub path: PathBuf,
+}
+
+/// Signs with a bls12-381 key stored hex encoded in a file on the local disk, because tofnd only holds ecdsa keys.
+/// The key is generated on first use if the file doesn't exist yet
+pub struct LocalBlsSigner {
+    secret_key: SecretKey,
+}
+
+impl LocalBlsSigner {
+    pub fn load(config: &Config) -> Result<Self> {
+        let secret_key = if config.path.exists() {
+            Self::read_key(&config.path)
+        } else {
+            Self::create_key(&config.path)
+        }
+        .attach_printable(format!("{{ bls_key = {} }}", config.path.display()))?;
+
+        Ok(Self { secret_key })
+    }
+
+    fn read_key(path: &Path) -> Result<SecretKey> {
+        let hex = fs::read_to_string(path).change_context(Error::KeygenFailed)?;
+        let bytes = HexBinary::from_hex(hex.trim()).change_context(Error::ParsingFailed)?;
+
+        <[u8; 32]>::try_from(bytes.as_slice())
+            .ok()
+            .and_then(|bytes| SecretKey::from_bytes(&bytes))
+            .ok_or_else(|| Report::new(Error::ParsingFailed))
+            .attach_printable("not a valid bls12-381 secret key")
+    }
+
+    fn create_key(path: &Path) -> Result<SecretKey> {
+        let mut seed = [0u8; 64];
+        OsRng.fill_bytes(&mut seed);
+        let secret_key = SecretKey::from_seed(&seed);
+
+        fs::write(path, HexBinary::from(secret_key.to_bytes()).to_hex())
+            .change_context(Error::KeygenFailed)?;
+
+        Ok(secret_key)
+    }
+
+    pub fn pub_key(&self) -> HexBinary {
+        self.secret_key.pub_key()
+    }
+
+    /// Signature of the public key that the multisig contract requires to register it
+    pub fn prove_possession(&self) -> HexBinary {
+        self.secret_key.prove_possession()
+    }
+
+    pub fn sign(&self, data: &MessageDigest, pub_key: &HexBinary) -> Result<Signature> {
+        if self.pub_key() != *pub_key {
+            return Err(Report::new(Error::SignFailed))
+                .attach_printable("public key does not match the bls12-381 key");
+        }
+
+        Ok(self.secret_key.sign(data.as_ref()).to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::HexBinary;
+
+    use crate::tofnd::MessageDigest;
+
+    use super::{Config, LocalBlsSigner};
+
+    #[test]
+    fn load_creates_and_reloads_key() {
+        let dir = std::env::temp_dir().join(random_string::generate(10, "abcdefghijklmnop"));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = Config {
+            path: dir.join("bls_key"),
+        };
+
+        let pub_key = LocalBlsSigner::load(&config).unwrap().pub_key();
+        assert!(config.path.exists());
+        assert_eq!(LocalBlsSigner::load(&config).unwrap().pub_key(), pub_key);
+
+        std::fs::write(&config.path, "not a key").unwrap();
+        assert!(LocalBlsSigner::load(&config).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sign_succeeds_with_own_key() {
+        let dir = std::env::temp_dir().join(random_string::generate(10, "abcdefghijklmnop"));
+        std::fs::create_dir_all(&dir).unwrap();
+        let signer = LocalBlsSigner::load(&Config {
+            path: dir.join("bls_key"),
+        })
+        .unwrap();
+
+        let digest: MessageDigest = rand::random::<[u8; 32]>().into();
+        let signature = signer.sign(&digest, &signer.pub_key()).unwrap();
+        assert!(multisig::bls::bls12_381_verify(
+            digest.as_ref(),
+            &signature,
+            signer.pub_key().as_slice()
+        )
+        .unwrap());
+
+        let other_pub_key = HexBinary::from([1u8; 48].as_slice());
+        assert!(signer.sign(&digest, &other_pub_key).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
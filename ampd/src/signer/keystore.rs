@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use ecdsa::signature::rand_core::OsRng;
+use error_stack::{Report, ResultExt};
+use ethers::signers::LocalWallet;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+
+use crate::tofnd::{error::Error, grpc::EcdsaClient, MessageDigest, Signature};
+use crate::types::PublicKey;
+
+type Result<T> = error_stack::Result<T, Error>;
+
+/// Signs with a secp256k1 key stored in an encrypted (web3 secret storage) keystore file.
+/// The key uid is ignored, because the keystore only ever holds a single key.
+pub struct LocalKeystoreClient {
+    path: PathBuf,
+    password: String,
+    wallet: Option<LocalWallet>,
+}
+
+impl LocalKeystoreClient {
+    pub fn new(path: PathBuf, password: String) -> Self {
+        Self {
+            path,
+            password,
+            wallet: None,
+        }
+    }
+
+    // decrypting the keystore is expensive, so the wallet is cached after the first access
+    fn wallet(&mut self) -> Result<&LocalWallet> {
+        if self.wallet.is_none() {
+            let wallet = if self.path.exists() {
+                LocalWallet::decrypt_keystore(&self.path, &self.password)
+                    .change_context(Error::KeygenFailed)
+            } else {
+                self.create_keystore()
+            }
+            .attach_printable(format!("{{ keystore = {} }}", self.path.display()))?;
+
+            self.wallet = Some(wallet);
+        }
+
+        Ok(self.wallet.as_ref().expect("wallet must have been loaded"))
+    }
+
+    fn create_keystore(&self) -> Result<LocalWallet> {
+        let dir = self
+            .path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        let name = self
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| Report::new(Error::KeygenFailed))
+            .attach_printable("invalid keystore file name")?;
+
+        LocalWallet::new_keystore(dir, &mut OsRng, &self.password, Some(name))
+            .map(|(wallet, _)| wallet)
+            .change_context(Error::KeygenFailed)
+    }
+}
+
+#[async_trait]
+impl EcdsaClient for LocalKeystoreClient {
+    async fn keygen(&mut self, _key_uid: &str) -> Result<PublicKey> {
+        Ok(self.wallet()?.signer().verifying_key().into())
+    }
+
+    async fn sign(
+        &mut self,
+        _key_uid: &str,
+        data: MessageDigest,
+        pub_key: &PublicKey,
+    ) -> Result<Signature> {
+        let signer = self.wallet().change_context(Error::SignFailed)?.signer();
+
+        if PublicKey::from(signer.verifying_key()) != *pub_key {
+            return Err(Report::new(Error::SignFailed))
+                .attach_printable("public key does not match the keystore");
+        }
+
+        PrehashSigner::<k256::ecdsa::Signature>::sign_prehash(signer, data.as_ref())
+            .change_context(Error::SignFailed)
+            .map(|signature| signature.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ecdsa::signature::{hazmat::PrehashVerifier, rand_core::OsRng};
+    use k256::ecdsa::{SigningKey, VerifyingKey};
+
+    use crate::tofnd::{grpc::EcdsaClient, MessageDigest};
+
+    use super::LocalKeystoreClient;
+
+    #[tokio::test]
+    async fn keygen_creates_and_reloads_keystore() {
+        let dir = std::env::temp_dir().join(random_string::generate(10, "abcdefghijklmnop"));
+        let path = dir.join("keystore.json");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let pub_key = LocalKeystoreClient::new(path.clone(), "password".into())
+            .keygen("")
+            .await
+            .unwrap();
+        assert!(path.exists());
+
+        let reloaded = LocalKeystoreClient::new(path.clone(), "password".into())
+            .keygen("")
+            .await
+            .unwrap();
+        assert_eq!(pub_key, reloaded);
+
+        assert!(LocalKeystoreClient::new(path, "wrong password".into())
+            .keygen("")
+            .await
+            .is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn sign_succeeds_with_keystore_key() {
+        let dir = std::env::temp_dir().join(random_string::generate(10, "abcdefghijklmnop"));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut client = LocalKeystoreClient::new(dir.join("keystore.json"), "password".into());
+        let pub_key = client.keygen("").await.unwrap();
+
+        let digest: MessageDigest = rand::random::<[u8; 32]>().into();
+        let signature = client.sign("", digest.clone(), &pub_key).await.unwrap();
+
+        let verifying_key = VerifyingKey::from_sec1_bytes(&pub_key.to_bytes()).unwrap();
+        let signature = k256::ecdsa::Signature::from_slice(&signature).unwrap();
+        assert!(verifying_key
+            .verify_prehash(digest.as_ref(), &signature)
+            .is_ok());
+
+        let other_pub_key = SigningKey::random(&mut OsRng).verifying_key().into();
+        assert!(client.sign("", digest, &other_pub_key).await.is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,55 @@
+use std::env;
+use std::path::PathBuf;
+
+use error_stack::{Result, ResultExt};
+use serde::{Deserialize, Serialize};
+
+use crate::tofnd::{
+    self,
+    grpc::{MultisigClient, SharableEcdsaClient},
+};
+use crate::Error;
+
+pub mod bls;
+pub mod keystore;
+
+/// Selects the backend that holds the worker's signing key. Every backend implements the
+/// [EcdsaClient](crate::tofnd::grpc::EcdsaClient) trait, so the rest of ampd doesn't depend on where the key lives.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[serde(tag = "type")]
+pub enum Config {
+    /// Sign with tofnd, as configured in the tofnd_config section
+    #[default]
+    Tofnd,
+    /// Sign with a key from a password-encrypted keystore file on the local disk. Only meant for devnets.
+    /// The key is generated on first use if the file doesn't exist yet
+    LocalKeystore {
+        path: PathBuf,
+        /// name of the environment variable that holds the keystore password
+        password_env_var: String,
+    },
+}
+
+pub async fn connect(
+    config: Config,
+    tofnd_config: tofnd::Config,
+) -> Result<SharableEcdsaClient, Error> {
+    match config {
+        Config::Tofnd => MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
+            .await
+            .change_context(Error::Connection)
+            .map(SharableEcdsaClient::new),
+        Config::LocalKeystore {
+            path,
+            password_env_var,
+        } => {
+            let password = env::var(&password_env_var)
+                .change_context(Error::LoadConfig)
+                .attach_printable(format!("{{ password_env_var = {} }}", password_env_var))?;
+
+            Ok(SharableEcdsaClient::new(
+                keystore::LocalKeystoreClient::new(path, password),
+            ))
+        }
+    }
+}
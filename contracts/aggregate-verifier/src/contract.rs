@@ -1,18 +1,25 @@
-use connection_router::state::CrossChainId;
+use std::collections::HashMap;
+
+use axelar_wasm_std::submsg::{ReplyOutcome, RetryPolicy};
+use connection_router::state::{CrossChainId, Message};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, QueryRequest, Reply, Response,
-    StdResult, WasmQuery,
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, QueryRequest, Reply,
+    Response, StdResult, SubMsgResponse, SubMsgResult, WasmQuery,
 };
-use cw_utils::{parse_reply_execute_data, MsgExecuteContractResponse};
+use cw_utils::{parse_execute_response_data, MsgExecuteContractResponse};
 
 use voting_verifier::msg as voting_msg;
 
 use crate::{
     error::ContractError,
+    events::{VerificationFailed, VerificationRetryScheduled},
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{Config, CONFIG},
+    state::{
+        Config, PendingVerification, VerificationRound, CONFIG, PENDING_VERIFICATIONS,
+        VERIFICATION_ROUNDS, VERIFIED_MESSAGES,
+    },
 };
 
 use self::execute::verify_messages;
@@ -24,8 +31,28 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
-    let verifier = deps.api.addr_validate(&msg.verifier_address)?;
-    CONFIG.save(deps.storage, &Config { verifier })?;
+    let verifiers = msg
+        .verifier_addresses
+        .iter()
+        .map(|address| deps.api.addr_validate(address))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if msg.quorum == 0 || msg.quorum as usize > verifiers.len() {
+        return Err(ContractError::InvalidQuorum {
+            quorum: msg.quorum,
+            verifiers: verifiers.len(),
+        }
+        .into());
+    }
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            verifiers,
+            quorum: msg.quorum,
+            retry_policy: msg.retry_policy,
+        },
+    )?;
 
     Ok(Response::new())
 }
@@ -33,56 +60,278 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
     match msg {
         ExecuteMsg::VerifyMessages { messages } => verify_messages(deps, messages),
+        ExecuteMsg::RetryVerification { reply_id } => {
+            execute::retry_verification(deps, env, reply_id)
+        }
     }
     .map_err(axelar_wasm_std::ContractError::from)
 }
 
 pub mod execute {
-    use cosmwasm_std::{to_binary, SubMsg, WasmMsg};
+    use cosmwasm_std::{to_binary, ReplyOn, WasmMsg};
 
-    use connection_router::state::Message;
+    use crate::state::VERIFICATION_ROUND_ID;
 
     use super::*;
 
     pub fn verify_messages(deps: DepsMut, msgs: Vec<Message>) -> Result<Response, ContractError> {
-        // Simply pass through to a single verifier for now. If there are multiple verification
-        // methods in the future, as well as support for a callback when a message is actually
-        // verified, we can store the verification status. But for now, simple pass through works
-        let verifier = CONFIG.load(deps.storage)?.verifier;
-        Ok(Response::new().add_submessage(SubMsg::reply_on_success(
-            WasmMsg::Execute {
-                contract_addr: verifier.to_string(),
-                msg: to_binary(&voting_msg::ExecuteMsg::VerifyMessages { messages: msgs })?,
-                funds: vec![],
+        // a verifier can revoke a verified result, e.g. after a governance override, so cached messages are checked
+        // against the current status of the verifiers. Querying them doesn't start a new verification
+        let cached = msgs
+            .iter()
+            .filter(|msg| VERIFIED_MESSAGES.has(deps.storage, &msg.hash()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let still_verified = if cached.is_empty() {
+            HashMap::new()
+        } else {
+            query::is_verified(deps.as_ref(), cached.clone())?
+                .into_iter()
+                .collect::<HashMap<_, _>>()
+        };
+        for msg in cached {
+            if still_verified.get(&msg.cc_id) != Some(&true) {
+                VERIFIED_MESSAGES.remove(deps.storage, &msg.hash());
+            }
+        }
+
+        // only the messages that are not known to be verified are sent to the verifiers
+        let unverified = msgs
+            .iter()
+            .filter(|msg| !VERIFIED_MESSAGES.has(deps.storage, &msg.hash()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if unverified.is_empty() {
+            let results = msgs
+                .into_iter()
+                .map(|msg| (msg.cc_id, true))
+                .collect::<Vec<_>>();
+            return Ok(Response::new().set_data(to_binary(&results)?));
+        }
+
+        let config = CONFIG.load(deps.storage)?;
+        let round_id = VERIFICATION_ROUND_ID.incr(deps.storage)?;
+        VERIFICATION_ROUNDS.save(
+            deps.storage,
+            round_id,
+            &VerificationRound {
+                msgs,
+                results: vec![None; config.verifiers.len()],
             },
-            VERIFY_REPLY,
-        )))
+        )?;
+
+        let verify_msg = to_binary(&voting_msg::ExecuteMsg::VerifyMessages {
+            messages: unverified,
+        })?;
+
+        // every verifier gets its own reply id, the context tells the replies apart
+        let sub_msgs = config
+            .verifiers
+            .into_iter()
+            .enumerate()
+            .map(|(verifier, address)| {
+                let msg = WasmMsg::Execute {
+                    contract_addr: address.to_string(),
+                    msg: verify_msg.clone(),
+                    funds: vec![],
+                };
+
+                PENDING_VERIFICATIONS.submit(
+                    deps.storage,
+                    msg.into(),
+                    PendingVerification { round_id, verifier },
+                    ReplyOn::Always,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new().add_submessages(sub_msgs))
     }
-}
 
-// not totally necessary, since there is only one possible submessage reply
-const VERIFY_REPLY: u64 = 0;
+    pub fn retry_verification(
+        deps: DepsMut,
+        env: Env,
+        reply_id: u64,
+    ) -> Result<Response, ContractError> {
+        Ok(Response::new().add_submessage(PENDING_VERIFICATIONS.retry(
+            deps.storage,
+            env.block.height,
+            reply_id,
+        )?))
+    }
+}
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(
-    _deps: DepsMut,
-    _: Env,
+    deps: DepsMut,
+    env: Env,
     reply: Reply,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
-    match parse_reply_execute_data(reply) {
-        Ok(MsgExecuteContractResponse { data: Some(data) }) => {
-            // check format of data
-            let _: Vec<(CrossChainId, bool)> = from_binary(&data)?;
+    let reply_id = reply.id;
+    let config = CONFIG.load(deps.storage)?;
+    let retry_policy = config.retry_policy.unwrap_or(RetryPolicy::NO_RETRIES);
+    let (reply, retriable) = reject_invalid_reply(reply);
+
+    match PENDING_VERIFICATIONS
+        .handle_reply(
+            deps.storage,
+            env.block.height,
+            reply,
+            &retry_policy,
+            retriable,
+        )
+        .map_err(ContractError::from)?
+    {
+        ReplyOutcome::Succeeded { context, response } => verifier_response(deps, context, response),
+        ReplyOutcome::RetryScheduled {
+            context,
+            error,
+            retry_after,
+        } => Ok(Response::new().add_event(
+            VerificationRetryScheduled {
+                reply_id,
+                verifier: config.verifiers[context.verifier].clone(),
+                error,
+                retry_after,
+            }
+            .into(),
+        )),
+        ReplyOutcome::Failed { context, error } => verification_failed(
+            deps,
+            config.verifiers[context.verifier].clone(),
+            context,
+            error,
+        ),
+    }
+    .map_err(axelar_wasm_std::ContractError::from)
+}
+
+// a reply without valid results is handled like a failed call. Unlike a call that failed to execute, e.g. because the verifier
+// was paused or ran out of gas, it is not retriable, because the verifier would send the same reply again
+fn reject_invalid_reply(Reply { id, result }: Reply) -> (Reply, bool) {
+    let (result, retriable) = match result {
+        SubMsgResult::Ok(response) => match parse_verifier_results(response.clone()) {
+            Ok(_) => (SubMsgResult::Ok(response), true),
+            Err(err) => (SubMsgResult::Err(err.to_string()), false),
+        },
+        SubMsgResult::Err(error) => (SubMsgResult::Err(error), true),
+    };
 
-            // only one verifier, so just return the response as is
-            Ok(Response::new().set_data(data))
+    (Reply { id, result }, retriable)
+}
+
+fn verification_failed(
+    deps: DepsMut,
+    verifier: Addr,
+    pending: PendingVerification,
+    error: String,
+) -> Result<Response, ContractError> {
+    // without the results of this verifier the round can't be aggregated, so the messages have to be submitted again.
+    // The round is already gone if another verifier of the round ran out of attempts before
+    let messages = match VERIFICATION_ROUNDS.may_load(deps.storage, pending.round_id)? {
+        Some(round) => {
+            VERIFICATION_ROUNDS.remove(deps.storage, pending.round_id);
+            round
+                .msgs
+                .into_iter()
+                .filter(|msg| !VERIFIED_MESSAGES.has(deps.storage, &msg.hash()))
+                .map(|msg| msg.cc_id)
+                .collect()
         }
+        None => vec![],
+    };
+
+    Ok(Response::new().add_event(
+        VerificationFailed {
+            verifier,
+            error,
+            messages,
+        }
+        .into(),
+    ))
+}
+
+fn verifier_response(
+    deps: DepsMut,
+    pending: PendingVerification,
+    response: SubMsgResponse,
+) -> Result<Response, ContractError> {
+    // results that arrive after the round failed are ignored
+    let mut round = match VERIFICATION_ROUNDS.may_load(deps.storage, pending.round_id)? {
+        Some(round) => round,
+        None => return Ok(Response::new()),
+    };
+    round.results[pending.verifier] = Some(parse_verifier_results(response)?);
+
+    // the replies of a round are handled one after another, so only the last one has all results to aggregate
+    if round.results.iter().any(Option::is_none) {
+        VERIFICATION_ROUNDS.save(deps.storage, pending.round_id, &round)?;
+        return Ok(Response::new());
+    }
+
+    VERIFICATION_ROUNDS.remove(deps.storage, pending.round_id);
+
+    let quorum = CONFIG.load(deps.storage)?.quorum;
+    let verifier_results = round
+        .results
+        .into_iter()
+        .flatten()
+        .map(|results| results.into_iter().collect::<HashMap<_, _>>())
+        .collect::<Vec<_>>();
+
+    // messages without verifier results were already known to be verified, so they weren't sent to the verifiers
+    let results = round
+        .msgs
+        .into_iter()
+        .map(|msg| {
+            let hash = msg.hash();
+            if VERIFIED_MESSAGES.has(deps.storage, &hash) {
+                return Ok((msg.cc_id, true));
+            }
+
+            let agreeing_verifiers = verifier_results
+                .iter()
+                .map(|results| {
+                    results.get(&msg.cc_id).copied().ok_or_else(|| {
+                        ContractError::InvalidVerifierReply(format!(
+                            "missing result for message {}",
+                            msg.cc_id
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter(|verified| *verified)
+                .count();
+
+            let verified = agreeing_verifiers >= quorum as usize;
+            if verified {
+                VERIFIED_MESSAGES.save(deps.storage, &hash, &())?;
+            }
+
+            Ok((msg.cc_id, verified))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    Ok(Response::new().set_data(to_binary(&results)?))
+}
+
+fn parse_verifier_results(
+    response: SubMsgResponse,
+) -> Result<Vec<(CrossChainId, bool)>, ContractError> {
+    let data = response
+        .data
+        .ok_or_else(|| ContractError::InvalidVerifierReply("missing reply data".to_string()))?;
+
+    match parse_execute_response_data(&data) {
+        Ok(MsgExecuteContractResponse { data: Some(data) }) => Ok(from_binary(&data)?),
         Ok(MsgExecuteContractResponse { data: None }) => {
             Err(ContractError::InvalidVerifierReply("no data".to_string()))
         }
@@ -91,18 +340,46 @@ pub fn reply(
             e
         ))),
     }
-    .map_err(axelar_wasm_std::ContractError::from)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::IsVerified { messages } => {
-            let verifier = CONFIG.load(deps.storage)?.verifier;
-            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr: verifier.to_string(),
-                msg: to_binary(&voting_msg::QueryMsg::IsVerified { messages })?,
-            }))
-        }
+        QueryMsg::IsVerified { messages } => to_binary(&query::is_verified(deps, messages)?),
+    }
+}
+
+pub mod query {
+    use super::*;
+
+    pub fn is_verified(deps: Deps, messages: Vec<Message>) -> StdResult<Vec<(CrossChainId, bool)>> {
+        let config = CONFIG.load(deps.storage)?;
+
+        let verifier_results = config
+            .verifiers
+            .iter()
+            .map(|verifier| {
+                deps.querier
+                    .query::<Vec<(CrossChainId, bool)>>(&QueryRequest::Wasm(WasmQuery::Smart {
+                        contract_addr: verifier.to_string(),
+                        msg: to_binary(&voting_msg::QueryMsg::IsVerified {
+                            messages: messages.clone(),
+                        })?,
+                    }))
+                    .map(|results| results.into_iter().collect::<HashMap<_, _>>())
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(messages
+            .into_iter()
+            .map(|msg| {
+                let agreeing_verifiers = verifier_results
+                    .iter()
+                    .filter(|results| results.get(&msg.cc_id) == Some(&true))
+                    .count();
+
+                (msg.cc_id, agreeing_verifiers >= config.quorum as usize)
+            })
+            .collect())
     }
 }
@@ -10,6 +10,12 @@ pub enum ContractError {
     #[error(transparent)]
     RouterError(#[from] connection_router::ContractError),
 
+    #[error(transparent)]
+    SubMsg(#[from] axelar_wasm_std::submsg::Error),
+
     #[error("received invalid verifier reply: {0}")]
     InvalidVerifierReply(String),
+
+    #[error("quorum {quorum} must be between 1 and the number of verifiers {verifiers}")]
+    InvalidQuorum { quorum: u32, verifiers: usize },
 }
@@ -0,0 +1,42 @@
+use cosmwasm_std::{Addr, Event};
+
+use connection_router::state::CrossChainId;
+
+/// Emitted when a call to a verifier failed, but can be retried with RetryVerification
+pub struct VerificationRetryScheduled {
+    pub reply_id: u64,
+    pub verifier: Addr,
+    pub error: String,
+    /// block height from which the retry is allowed
+    pub retry_after: u64,
+}
+
+impl From<VerificationRetryScheduled> for Event {
+    fn from(other: VerificationRetryScheduled) -> Self {
+        Event::new("verification_retry_scheduled")
+            .add_attribute("reply_id", other.reply_id.to_string())
+            .add_attribute("verifier", other.verifier)
+            .add_attribute("error", other.error)
+            .add_attribute("retry_after", other.retry_after.to_string())
+    }
+}
+
+/// Emitted when a call to a verifier failed and no attempts are left. The messages of the request are not verified,
+/// so they have to be submitted for verification again
+pub struct VerificationFailed {
+    pub verifier: Addr,
+    pub error: String,
+    pub messages: Vec<CrossChainId>,
+}
+
+impl From<VerificationFailed> for Event {
+    fn from(other: VerificationFailed) -> Self {
+        Event::new("verification_failed")
+            .add_attribute("verifier", other.verifier)
+            .add_attribute("error", other.error)
+            .add_attribute(
+                "messages",
+                serde_json::to_string(&other.messages).expect("failed to serialize messages"),
+            )
+    }
+}
@@ -1,15 +1,26 @@
+use axelar_wasm_std::submsg::RetryPolicy;
 use connection_router::state::Message;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    pub verifier_address: String,
+    // every message is sent to all of these verifiers, e.g. the voting verifier and a light client verifier
+    pub verifier_addresses: Vec<String>,
+    // number of verifiers that must report a message as verified for it to count as verified
+    pub quorum: u32,
+    // if set, failed calls to a verifier can be retried with RetryVerification. No retries if not set
+    pub retry_policy: Option<RetryPolicy>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     // Permissionless
     VerifyMessages { messages: Vec<Message> },
+
+    // Permissionless
+    // Calls a verifier again whose previous attempt failed, once the backoff of the retry policy has passed.
+    // The reply id is part of the verification_retry_scheduled event
+    RetryVerification { reply_id: u64 },
 }
 
 #[cw_serde]
@@ -1,10 +1,50 @@
+use axelar_wasm_std::{
+    counter::Counter,
+    hash::Hash,
+    submsg::{PendingSubMsgs, RetryPolicy},
+};
+use connection_router::state::{CrossChainId, Message};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
-    pub verifier: Addr,
+    /// verification backends every message is sent to, e.g. the voting verifier and a light client verifier
+    pub verifiers: Vec<Addr>,
+    /// number of verifiers that must report a message as verified for it to count as verified
+    pub quorum: u32,
+    /// failed calls to a verifier can be retried according to this policy, no retries if not set
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// A verification request that was sent to all verifiers, and the results of the verifiers that have replied so far
+#[cw_serde]
+pub struct VerificationRound {
+    /// all messages of the request, including the ones that were already known to be verified
+    pub msgs: Vec<Message>,
+    /// results reported by each verifier, in the order of the verifiers in the config
+    pub results: Vec<Option<Vec<(CrossChainId, bool)>>>,
+}
+
+#[cw_serde]
+pub struct PendingVerification {
+    pub round_id: u64,
+    /// index of the verifier in the config
+    pub verifier: usize,
+}
+
+pub const VERIFICATION_ROUND_ID: Counter<u64> = Counter::new("verification_round_id");
+
+pub const VERIFICATION_ROUNDS: Map<u64, VerificationRound> = Map::new("verification_rounds");
+
+// requests sent to a verifier that are waiting for a reply
+pub const PENDING_VERIFICATIONS: PendingSubMsgs<PendingVerification> =
+    PendingSubMsgs::new("verification_reply_id", "pending_verifications");
+
+// messages the verifiers reported as verified. They are not forwarded to the verifiers again as long as the verifiers
+// still report them as verified, but a verifier can revoke a result, so the cache is never the only source of truth
+pub const VERIFIED_MESSAGES: Map<&Hash, ()> = Map::new("verified_messages");
@@ -1,16 +1,24 @@
-use aggregate_verifier::error::ContractError;
+use aggregate_verifier::{error::ContractError, msg::QueryMsg};
 use connection_router::state::{CrossChainId, Message};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_binary, Addr, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
 use cw_multi_test::{App, ContractWrapper, Executor};
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
 
 const MOCK_VOTING_VERIFIER_MESSAGES: Map<CrossChainId, bool> = Map::new("voting_verifier_messages");
+const MOCK_VOTING_VERIFIER_FAILING: Item<bool> = Item::new("voting_verifier_failing");
+const MOCK_VOTING_VERIFIER_WITHOUT_RESULTS: Item<bool> =
+    Item::new("voting_verifier_without_results");
 
 #[cw_serde]
 pub enum MockVotingVerifierExecuteMsg {
     VerifyMessages { messages: Vec<Message> },
     MessagesVerified { messages: Vec<Message> },
+    MessagesRevoked { messages: Vec<Message> },
+    SetFailing { failing: bool },
+    SetWithoutResults { without_results: bool },
 }
 
 #[cw_serde]
@@ -24,6 +32,19 @@ pub fn mock_verifier_execute(
 ) -> Result<Response, ContractError> {
     match msg {
         MockVotingVerifierExecuteMsg::VerifyMessages { messages } => {
+            if MOCK_VOTING_VERIFIER_FAILING
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+            {
+                return Err(StdError::generic_err("verifier failed").into());
+            }
+            if MOCK_VOTING_VERIFIER_WITHOUT_RESULTS
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+            {
+                return Ok(Response::new());
+            }
+
             let mut res = vec![];
             for m in messages {
                 match MOCK_VOTING_VERIFIER_MESSAGES.may_load(deps.storage, m.cc_id.clone())? {
@@ -39,6 +60,36 @@ pub fn mock_verifier_execute(
             }
             Ok(Response::new())
         }
+        MockVotingVerifierExecuteMsg::MessagesRevoked { messages } => {
+            for m in messages {
+                MOCK_VOTING_VERIFIER_MESSAGES.save(deps.storage, m.cc_id, &false)?;
+            }
+            Ok(Response::new())
+        }
+        MockVotingVerifierExecuteMsg::SetFailing { failing } => {
+            MOCK_VOTING_VERIFIER_FAILING.save(deps.storage, &failing)?;
+            Ok(Response::new())
+        }
+        MockVotingVerifierExecuteMsg::SetWithoutResults { without_results } => {
+            MOCK_VOTING_VERIFIER_WITHOUT_RESULTS.save(deps.storage, &without_results)?;
+            Ok(Response::new())
+        }
+    }
+}
+
+pub fn mock_verifier_query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::IsVerified { messages } => to_binary(
+            &messages
+                .into_iter()
+                .map(|m| {
+                    let verified = MOCK_VOTING_VERIFIER_MESSAGES
+                        .may_load(deps.storage, m.cc_id.clone())?
+                        .unwrap_or_default();
+                    Ok((m.cc_id, verified))
+                })
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
     }
 }
 
@@ -52,25 +103,53 @@ pub fn mark_messages_as_verified(app: &mut App, voting_verifier_address: Addr, m
     .unwrap();
 }
 
+pub fn revoke_verification(app: &mut App, voting_verifier_address: Addr, msgs: Vec<Message>) {
+    app.execute_contract(
+        Addr::unchecked("relayer"),
+        voting_verifier_address,
+        &MockVotingVerifierExecuteMsg::MessagesRevoked { messages: msgs },
+        &[],
+    )
+    .unwrap();
+}
+
+pub fn set_failing(app: &mut App, voting_verifier_address: Addr, failing: bool) {
+    app.execute_contract(
+        Addr::unchecked("relayer"),
+        voting_verifier_address,
+        &MockVotingVerifierExecuteMsg::SetFailing { failing },
+        &[],
+    )
+    .unwrap();
+}
+
+pub fn set_without_results(app: &mut App, voting_verifier_address: Addr, without_results: bool) {
+    app.execute_contract(
+        Addr::unchecked("relayer"),
+        voting_verifier_address,
+        &MockVotingVerifierExecuteMsg::SetWithoutResults { without_results },
+        &[],
+    )
+    .unwrap();
+}
+
 pub fn make_mock_voting_verifier(app: &mut App) -> Addr {
     let code = ContractWrapper::new(
         mock_verifier_execute,
         |_, _, _, _: MockVotingVerifierInstantiateMsg| {
             Ok::<Response, ContractError>(Response::new())
         },
-        |_, _, _: aggregate_verifier::msg::QueryMsg| to_binary(&()),
+        mock_verifier_query,
     );
     let code_id = app.store_code(Box::new(code));
 
-    let contract_address = app
-        .instantiate_contract(
-            code_id,
-            Addr::unchecked("voting_verifier"),
-            &MockVotingVerifierInstantiateMsg {},
-            &[],
-            "Contract",
-            None,
-        )
-        .unwrap();
-    contract_address
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked("voting_verifier"),
+        &MockVotingVerifierInstantiateMsg {},
+        &[],
+        "Contract",
+        None,
+    )
+    .unwrap()
 }
@@ -1,11 +1,15 @@
 use aggregate_verifier::contract::*;
-use aggregate_verifier::msg::{ExecuteMsg, InstantiateMsg};
+use aggregate_verifier::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use axelar_wasm_std::submsg::RetryPolicy;
 use connection_router::state::{CrossChainId, Message, ID_SEPARATOR};
 use cosmwasm_std::from_binary;
 use cosmwasm_std::Addr;
-use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 
-use crate::mock::{make_mock_voting_verifier, mark_messages_as_verified};
+use crate::mock::{
+    make_mock_voting_verifier, mark_messages_as_verified, revoke_verification, set_failing,
+    set_without_results,
+};
 pub mod mock;
 
 fn generate_messages(count: usize) -> Vec<Message> {
@@ -22,6 +26,7 @@ fn generate_messages(count: usize) -> Vec<Message> {
             destination_chain: "mock-chain-2".parse().unwrap(),
             source_address: "idc".parse().unwrap(),
             payload_hash: [x as u8; 32],
+            token: None,
         });
     }
     msgs
@@ -40,7 +45,9 @@ fn verify_messages_empty() {
             code_id,
             Addr::unchecked("gateway"),
             &InstantiateMsg {
-                verifier_address: voting_verifier_address.to_string(),
+                verifier_addresses: vec![voting_verifier_address.to_string()],
+                quorum: 1,
+                retry_policy: None,
             },
             &[],
             "Contract",
@@ -73,7 +80,9 @@ fn verify_messages_not_verified() {
             code_id,
             Addr::unchecked("gateway"),
             &InstantiateMsg {
-                verifier_address: voting_verifier_address.to_string(),
+                verifier_addresses: vec![voting_verifier_address.to_string()],
+                quorum: 1,
+                retry_policy: None,
             },
             &[],
             "Contract",
@@ -114,7 +123,9 @@ fn verify_messages_verified() {
             code_id,
             Addr::unchecked("gateway"),
             &InstantiateMsg {
-                verifier_address: voting_verifier_address.to_string(),
+                verifier_addresses: vec![voting_verifier_address.to_string()],
+                quorum: 1,
+                retry_policy: None,
             },
             &[],
             "Contract",
@@ -157,7 +168,9 @@ fn verify_messages_mixed_status() {
             code_id,
             Addr::unchecked("gateway"),
             &InstantiateMsg {
-                verifier_address: voting_verifier_address.to_string(),
+                verifier_addresses: vec![voting_verifier_address.to_string()],
+                quorum: 1,
+                retry_policy: None,
             },
             &[],
             "Contract",
@@ -184,11 +197,7 @@ fn verify_messages_mixed_status() {
         ret,
         msgs.iter()
             .map(|msg| {
-                if verified
-                    .iter()
-                    .find(|verified_msg| *verified_msg == msg)
-                    .is_some()
-                {
+                if verified.iter().any(|verified_msg| verified_msg == msg) {
                     (msg.cc_id.clone(), true)
                 } else {
                     (msg.cc_id.clone(), false)
@@ -197,3 +206,322 @@ fn verify_messages_mixed_status() {
             .collect::<Vec<(CrossChainId, bool)>>()
     );
 }
+
+#[test]
+fn verify_messages_cached() {
+    let mut app = App::default();
+    let voting_verifier_address = make_mock_voting_verifier(&mut app);
+
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    let code_id = app.store_code(Box::new(code));
+
+    let verifier_address = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("gateway"),
+            &InstantiateMsg {
+                verifier_addresses: vec![voting_verifier_address.to_string()],
+                quorum: 1,
+                retry_policy: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let msgs = generate_messages(10);
+    let (verified, _) = msgs.split_at(5);
+    mark_messages_as_verified(&mut app, voting_verifier_address.clone(), verified.to_vec());
+
+    let verify = |app: &mut App, msgs: Vec<Message>| {
+        app.execute_contract(
+            Addr::unchecked("relayer"),
+            verifier_address.clone(),
+            &ExecuteMsg::VerifyMessages { messages: msgs },
+            &[],
+        )
+        .unwrap()
+    };
+    let executed_contracts = |res: &AppResponse| {
+        res.events
+            .iter()
+            .filter(|event| event.ty == "execute")
+            .count()
+    };
+
+    let res = verify(&mut app, msgs.clone());
+    assert_eq!(executed_contracts(&res), 2);
+
+    // verified messages are answered from the cache without calling the voting verifier again
+    let res = verify(&mut app, verified.to_vec());
+    assert_eq!(executed_contracts(&res), 1);
+    let ret: Vec<(CrossChainId, bool)> = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(
+        ret,
+        verified
+            .iter()
+            .map(|msg| (msg.cc_id.clone(), true))
+            .collect::<Vec<(CrossChainId, bool)>>()
+    );
+
+    // unverified messages are still forwarded, and the results keep the order of the request
+    let res = verify(&mut app, msgs.iter().rev().cloned().collect());
+    assert_eq!(executed_contracts(&res), 2);
+    let ret: Vec<(CrossChainId, bool)> = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(
+        ret,
+        msgs.iter()
+            .rev()
+            .map(|msg| (msg.cc_id.clone(), verified.contains(msg)))
+            .collect::<Vec<(CrossChainId, bool)>>()
+    );
+
+    // a result the voting verifier revoked is no longer answered from the cache
+    let (revoked, still_verified) = verified.split_at(1);
+    revoke_verification(&mut app, voting_verifier_address, revoked.to_vec());
+
+    let is_verified: Vec<(CrossChainId, bool)> = app
+        .wrap()
+        .query_wasm_smart(
+            verifier_address.clone(),
+            &QueryMsg::IsVerified {
+                messages: verified.to_vec(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        is_verified,
+        verified
+            .iter()
+            .map(|msg| (msg.cc_id.clone(), still_verified.contains(msg)))
+            .collect::<Vec<(CrossChainId, bool)>>()
+    );
+
+    let res = verify(&mut app, verified.to_vec());
+    assert_eq!(executed_contracts(&res), 2);
+    let ret: Vec<(CrossChainId, bool)> = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(ret, is_verified);
+}
+
+#[test]
+fn verify_messages_with_quorum() {
+    let mut app = App::default();
+    let first_verifier = make_mock_voting_verifier(&mut app);
+    let second_verifier = make_mock_voting_verifier(&mut app);
+
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate = |app: &mut App, quorum: u32| {
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked("gateway"),
+            &InstantiateMsg {
+                verifier_addresses: vec![first_verifier.to_string(), second_verifier.to_string()],
+                quorum,
+                retry_policy: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+    };
+
+    assert!(instantiate(&mut app, 0).is_err());
+    assert!(instantiate(&mut app, 3).is_err());
+
+    let msgs = generate_messages(3);
+    mark_messages_as_verified(&mut app, first_verifier.clone(), msgs[..2].to_vec());
+    mark_messages_as_verified(&mut app, second_verifier.clone(), msgs[1..].to_vec());
+
+    // with a quorum of 1 a single verifier suffices, with a quorum of 2 both verifiers need to agree
+    for (quorum, expected) in [(1, [true, true, true]), (2, [false, true, false])] {
+        let verifier_address = instantiate(&mut app, quorum).unwrap();
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked("relayer"),
+                verifier_address.clone(),
+                &ExecuteMsg::VerifyMessages {
+                    messages: msgs.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+        let ret: Vec<(CrossChainId, bool)> = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            ret,
+            msgs.iter()
+                .map(|msg| msg.cc_id.clone())
+                .zip(expected)
+                .collect::<Vec<(CrossChainId, bool)>>()
+        );
+    }
+}
+
+#[test]
+fn verify_messages_retried() {
+    let mut app = App::default();
+    let voting_verifier_address = make_mock_voting_verifier(&mut app);
+
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    let code_id = app.store_code(Box::new(code));
+
+    let verifier_address = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("gateway"),
+            &InstantiateMsg {
+                verifier_addresses: vec![voting_verifier_address.to_string()],
+                quorum: 1,
+                retry_policy: Some(RetryPolicy {
+                    max_attempts: 2,
+                    base_backoff_blocks: 10,
+                }),
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let execute = |app: &mut App, msg: &ExecuteMsg| {
+        app.execute_contract(
+            Addr::unchecked("relayer"),
+            verifier_address.clone(),
+            msg,
+            &[],
+        )
+    };
+    let event_attribute = |res: &AppResponse, ty: &str, key: &str| {
+        res.events
+            .iter()
+            .find(|event| event.ty == ty)
+            .and_then(|event| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.key == key)
+            })
+            .map(|attribute| attribute.value.clone())
+    };
+
+    let msgs = generate_messages(2);
+    mark_messages_as_verified(&mut app, voting_verifier_address.clone(), msgs.clone());
+    set_failing(&mut app, voting_verifier_address.clone(), true);
+
+    // the failed call doesn't fail the request, it is scheduled for a retry instead
+    let res = execute(
+        &mut app,
+        &ExecuteMsg::VerifyMessages {
+            messages: msgs[..1].to_vec(),
+        },
+    )
+    .unwrap();
+    let reply_id: u64 = event_attribute(&res, "wasm-verification_retry_scheduled", "reply_id")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let retry = ExecuteMsg::RetryVerification { reply_id };
+
+    // retries are only possible once the backoff has passed
+    assert!(execute(&mut app, &retry).is_err());
+
+    set_failing(&mut app, voting_verifier_address.clone(), false);
+    app.update_block(|block| block.height += 10);
+
+    let res = execute(&mut app, &retry).unwrap();
+    let ret: Vec<(CrossChainId, bool)> = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(ret, vec![(msgs[0].cc_id.clone(), true)]);
+
+    // succeeded calls can't be retried
+    assert!(execute(&mut app, &retry).is_err());
+
+    // once the last attempt failed, the messages of the request are reported as failed
+    set_failing(&mut app, voting_verifier_address, true);
+    let res = execute(
+        &mut app,
+        &ExecuteMsg::VerifyMessages {
+            messages: msgs.clone(),
+        },
+    )
+    .unwrap();
+    let reply_id: u64 = event_attribute(&res, "wasm-verification_retry_scheduled", "reply_id")
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    app.update_block(|block| block.height += 10);
+    let res = execute(&mut app, &ExecuteMsg::RetryVerification { reply_id }).unwrap();
+    let failed_msgs: Vec<CrossChainId> = serde_json::from_str(
+        &event_attribute(&res, "wasm-verification_failed", "messages").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(failed_msgs, vec![msgs[1].cc_id.clone()]);
+}
+
+#[test]
+fn verify_messages_not_retried_on_invalid_reply() {
+    let mut app = App::default();
+    let voting_verifier_address = make_mock_voting_verifier(&mut app);
+
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    let code_id = app.store_code(Box::new(code));
+
+    let verifier_address = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("gateway"),
+            &InstantiateMsg {
+                verifier_addresses: vec![voting_verifier_address.to_string()],
+                quorum: 1,
+                retry_policy: Some(RetryPolicy {
+                    max_attempts: 2,
+                    base_backoff_blocks: 10,
+                }),
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let msgs = generate_messages(2);
+    set_without_results(&mut app, voting_verifier_address, true);
+
+    // the verifier would send the same reply again, so the messages are reported as failed without using up the retries
+    let res = app
+        .execute_contract(
+            Addr::unchecked("relayer"),
+            verifier_address,
+            &ExecuteMsg::VerifyMessages {
+                messages: msgs.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(!res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-verification_retry_scheduled"));
+
+    let failed = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-verification_failed")
+        .unwrap();
+    let failed_msgs: Vec<CrossChainId> = serde_json::from_str(
+        &failed
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == "messages")
+            .unwrap()
+            .value,
+    )
+    .unwrap();
+    assert_eq!(
+        failed_msgs,
+        msgs.into_iter().map(|msg| msg.cc_id).collect::<Vec<_>>()
+    );
+}
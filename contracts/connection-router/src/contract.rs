@@ -43,7 +43,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
@@ -74,7 +74,10 @@ pub fn execute(
             execute::require_admin(&deps, info)?;
             execute::unfreeze_chain(deps, chain, direction)
         }
-        ExecuteMsg::RouteMessages(msgs) => Ok(contract.route_messages(info.sender, msgs)?),
+        ExecuteMsg::RouteMessages(msgs) => {
+            Ok(contract.route_messages(info.sender, msgs, env.block.height)?)
+        }
+        ExecuteMsg::ArchiveMessages { message_ids } => execute::archive_messages(deps, message_ids),
     }
     .map_err(axelar_wasm_std::ContractError::from)
 }
@@ -107,6 +110,10 @@ pub fn query(
 ) -> Result<Binary, axelar_wasm_std::ContractError> {
     match msg {
         QueryMsg::GetChainInfo(chain) => to_binary(&query::get_chain_info(deps, chain)?),
+        QueryMsg::TraceMessage { id } => to_binary(&query::trace_message(deps, id)?),
+        QueryMsg::VerifyArchivedMessage { message } => {
+            to_binary(&query::verify_archived_message(deps, message)?)
+        }
     }
     .map_err(axelar_wasm_std::ContractError::from)
 }
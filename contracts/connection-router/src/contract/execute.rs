@@ -6,13 +6,18 @@ use itertools::Itertools;
 
 use axelar_wasm_std::flagset::FlagSet;
 
-use crate::events::{ChainFrozen, ChainRegistered, GatewayInfo, GatewayUpgraded, MessageRouted};
+use crate::events::{
+    ChainFrozen, ChainRegistered, ChainUnfrozen, GatewayInfo, GatewayUpgraded, MessageRouted,
+    MessagesArchived,
+};
 use crate::msg::ExecuteMsg;
 use crate::state::{
-    chain_endpoints, ChainEndpoint, ChainName, Gateway, GatewayDirection, Message, Store, CONFIG,
+    chain_endpoints, ArchivedMessage, ChainEndpoint, ChainName, CrossChainId, Gateway,
+    GatewayDirection, Message, MessageTrace, Store, ARCHIVED_MESSAGES, CONFIG, MESSAGE_TRACES,
 };
 use crate::ContractError;
 
+use super::query::is_delivered;
 use super::Contract;
 
 pub fn register_chain(
@@ -85,7 +90,13 @@ pub fn freeze_chain(
             Ok(chain)
         }
     })?;
-    Ok(Response::new().add_event(ChainFrozen { name: chain }.into()))
+    Ok(Response::new().add_event(
+        ChainFrozen {
+            name: chain,
+            direction,
+        }
+        .into(),
+    ))
 }
 
 pub fn unfreeze_chain(
@@ -100,7 +111,56 @@ pub fn unfreeze_chain(
             Ok(chain)
         }
     })?;
-    Ok(Response::new().add_event(ChainFrozen { name: chain }.into()))
+    Ok(Response::new().add_event(
+        ChainUnfrozen {
+            name: chain,
+            direction,
+        }
+        .into(),
+    ))
+}
+
+/// Archives the traces of messages the destination gateway has stored. Messages that are unknown, not delivered yet,
+/// routed to the nexus gateway or routed before message hashes were recorded are skipped
+pub fn archive_messages(
+    deps: DepsMut,
+    message_ids: Vec<CrossChainId>,
+) -> Result<Response, ContractError> {
+    let nexus_gateway = CONFIG.load(deps.storage)?.nexus_gateway;
+
+    let mut archived_ids = vec![];
+    for id in message_ids {
+        let trace = match MESSAGE_TRACES.may_load(deps.storage, id.clone())? {
+            Some(trace) if trace.destination_gateway != nexus_gateway => trace,
+            _ => continue,
+        };
+        let digest = match trace.message_hash {
+            Some(digest) => digest,
+            None => continue,
+        };
+        if !is_delivered(deps.as_ref(), &trace.destination_gateway, &id) {
+            continue;
+        }
+
+        ARCHIVED_MESSAGES.save(
+            deps.storage,
+            id.clone(),
+            &ArchivedMessage {
+                digest,
+                received_at: trace.received_at,
+                routed_at: trace.routed_at,
+            },
+        )?;
+        MESSAGE_TRACES.remove(deps.storage, id.clone());
+        archived_ids.push(id);
+    }
+
+    Ok(Response::new().add_event(
+        MessagesArchived {
+            message_ids: archived_ids,
+        }
+        .into(),
+    ))
 }
 
 pub fn require_admin(deps: &DepsMut, info: MessageInfo) -> Result<(), ContractError> {
@@ -151,10 +211,26 @@ where
         Ok(msgs)
     }
 
+    fn record_trace(
+        &mut self,
+        msg: &Message,
+        sender: &Addr,
+        gateway: &Addr,
+        block_height: u64,
+    ) -> error_stack::Result<(), ContractError> {
+        let trace = match self.store.load_message_trace(&msg.cc_id)? {
+            Some(trace) => trace.retry(gateway.clone(), block_height, msg.hash()),
+            None => MessageTrace::new(sender.clone(), gateway.clone(), block_height, msg.hash()),
+        };
+
+        self.store.save_message_trace(&msg.cc_id, trace)
+    }
+
     pub fn route_messages(
-        self,
+        mut self,
         sender: Addr,
         msgs: Vec<Message>,
+        block_height: u64,
     ) -> error_stack::Result<Response, ContractError> {
         let msgs = self.validate_msgs(&sender, msgs)?;
 
@@ -179,10 +255,15 @@ where
                     _ => return Err(report!(ContractError::ChainNotFound)),
                 };
 
+                let msgs: Vec<Message> = msgs.cloned().collect();
+                for msg in &msgs {
+                    self.record_trace(msg, &sender, &gateway, block_height)?;
+                }
+
                 Ok(WasmMsg::Execute {
                     contract_addr: gateway.to_string(),
                     // TODO: this happens to work because the router and the gateways have the same definition of RouteMessages
-                    msg: to_binary(&ExecuteMsg::RouteMessages(msgs.cloned().collect()))
+                    msg: to_binary(&ExecuteMsg::RouteMessages(msgs))
                         .expect("must serialize message"),
                     funds: vec![],
                 })
@@ -205,8 +286,8 @@ mod test {
     use crate::{
         contract::Contract,
         state::{
-            ChainEndpoint, ChainName, Config, CrossChainId, Gateway, GatewayDirection, MockStore,
-            ID_SEPARATOR,
+            ChainEndpoint, ChainName, Config, CrossChainId, Gateway, GatewayDirection,
+            MessageTrace, MockStore, ID_SEPARATOR,
         },
         ContractError, Message,
     };
@@ -243,6 +324,7 @@ mod test {
             destination_chain,
             destination_address,
             payload_hash,
+            token: None,
         }
     }
 
@@ -270,7 +352,11 @@ mod test {
         let contract = Contract::new(store);
 
         assert!(contract
-            .route_messages(sender, vec![rand_message(source_chain, destination_chain)])
+            .route_messages(
+                sender,
+                vec![rand_message(source_chain, destination_chain)],
+                1
+            )
             .is_err_and(move |err| {
                 matches!(err.current_context(), ContractError::GatewayNotRegistered)
             }));
@@ -307,7 +393,7 @@ mod test {
         let contract = Contract::new(store);
 
         assert!(contract
-            .route_messages(sender, vec![rand_message(source_chain.clone(), destination_chain)])
+            .route_messages(sender, vec![rand_message(source_chain.clone(), destination_chain)], 1)
             .is_err_and(move |err| {
                 matches!(err.current_context(), ContractError::ChainFrozen { chain } if *chain == source_chain)
             }));
@@ -346,7 +432,8 @@ mod test {
         assert!(contract
             .route_messages(
                 sender,
-                vec![rand_message("polygon".parse().unwrap(), destination_chain)]
+                vec![rand_message("polygon".parse().unwrap(), destination_chain)],
+                1
             )
             .is_err_and(|err| {
                 matches!(err.current_context(), ContractError::WrongSourceChain)
@@ -396,7 +483,7 @@ mod test {
         let contract = Contract::new(store);
 
         assert!(contract
-            .route_messages(sender, vec![rand_message(source_chain, destination_chain.clone())])
+            .route_messages(sender, vec![rand_message(source_chain, destination_chain.clone())], 1)
             .is_err_and(move |err| {
                 matches!(err.current_context(), ContractError::ChainFrozen { chain } if *chain == destination_chain)
             }));
@@ -455,6 +542,15 @@ mod test {
             .with(predicate::eq(destination_chain_2.clone()))
             .return_once(|_| Ok(Some(destination_chain_endpoint_2)));
 
+        store
+            .expect_load_message_trace()
+            .times(4)
+            .returning(|_| Ok(None));
+        store
+            .expect_save_message_trace()
+            .times(4)
+            .returning(|_, _| Ok(()));
+
         let contract = Contract::new(store);
 
         assert!(contract
@@ -465,7 +561,8 @@ mod test {
                     rand_message(source_chain.clone(), destination_chain_1.clone()),
                     rand_message(source_chain.clone(), destination_chain_1.clone()),
                     rand_message(source_chain.clone(), destination_chain_2.clone()),
-                ]
+                ],
+                1
             )
             .is_ok_and(|res| { res.messages.len() == 2 }));
     }
@@ -511,6 +608,15 @@ mod test {
             .with(predicate::eq(destination_chain_2.clone()))
             .return_once(|_| Ok(Some(destination_chain_endpoint_2)));
 
+        store
+            .expect_load_message_trace()
+            .times(4)
+            .returning(|_| Ok(None));
+        store
+            .expect_save_message_trace()
+            .times(4)
+            .returning(|_, _| Ok(()));
+
         let contract = Contract::new(store);
 
         assert!(contract
@@ -521,7 +627,8 @@ mod test {
                     rand_message(source_chain.clone(), destination_chain_1.clone()),
                     rand_message(source_chain.clone(), destination_chain_1.clone()),
                     rand_message(source_chain.clone(), destination_chain_2.clone()),
-                ]
+                ],
+                1
             )
             .is_ok_and(|res| { res.messages.len() == 2 }));
     }
@@ -555,7 +662,8 @@ mod test {
                 vec![rand_message(
                     source_chain.clone(),
                     destination_chain.clone()
-                )]
+                )],
+                1
             )
             .is_err_and(|err| { matches!(err.current_context(), ContractError::ChainNotFound) }));
     }
@@ -593,6 +701,15 @@ mod test {
             .with(predicate::eq(destination_chain.clone()))
             .return_once(|_| Ok(None));
 
+        store
+            .expect_load_message_trace()
+            .times(1)
+            .returning(|_| Ok(None));
+        store
+            .expect_save_message_trace()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
         let contract = Contract::new(store);
 
         assert!(contract
@@ -601,8 +718,81 @@ mod test {
                 vec![rand_message(
                     source_chain.clone(),
                     destination_chain.clone()
-                )]
+                )],
+                1
             )
             .is_ok_and(|res| { res.messages.len() == 1 }));
     }
+
+    #[test]
+    fn route_messages_records_retries() {
+        let config = Config {
+            admin: Addr::unchecked("admin"),
+            governance: Addr::unchecked("governance"),
+            nexus_gateway: Addr::unchecked("nexus_gateway"),
+        };
+        let sender = Addr::unchecked("sender");
+        let destination_gateway = Addr::unchecked("destination_gateway");
+        let source_chain: ChainName = "ethereum".parse().unwrap();
+        let destination_chain: ChainName = "bitcoin".parse().unwrap();
+        let msg = rand_message(source_chain.clone(), destination_chain.clone());
+
+        let mut store = MockStore::new();
+        store
+            .expect_load_config()
+            .returning(move || Ok(config.clone()));
+        let source_chain_endpoint = ChainEndpoint {
+            name: source_chain.clone(),
+            gateway: Gateway {
+                address: sender.clone(),
+            },
+            frozen_status: FlagSet::from(GatewayDirection::None),
+        };
+        store
+            .expect_load_chain_by_gateway()
+            .once()
+            .with(predicate::eq(sender.clone()))
+            .return_once(|_| Ok(Some(source_chain_endpoint)));
+        let destination_chain_endpoint = ChainEndpoint {
+            name: destination_chain.clone(),
+            gateway: Gateway {
+                address: destination_gateway.clone(),
+            },
+            frozen_status: FlagSet::from(GatewayDirection::None),
+        };
+        store
+            .expect_load_chain_by_chain_name()
+            .once()
+            .with(predicate::eq(destination_chain.clone()))
+            .return_once(|_| Ok(Some(destination_chain_endpoint)));
+        let previous_trace =
+            MessageTrace::new(sender.clone(), destination_gateway.clone(), 5, msg.hash());
+        store
+            .expect_load_message_trace()
+            .once()
+            .with(predicate::eq(msg.cc_id.clone()))
+            .return_once(|_| Ok(Some(previous_trace)));
+        let expected_trace = MessageTrace {
+            source_gateway: sender.clone(),
+            destination_gateway,
+            received_at: 5,
+            routed_at: 10,
+            retries: 1,
+            message_hash: Some(msg.hash().into()),
+        };
+        store
+            .expect_save_message_trace()
+            .once()
+            .with(
+                predicate::eq(msg.cc_id.clone()),
+                predicate::eq(expected_trace),
+            )
+            .return_once(|_, _| Ok(()));
+
+        let contract = Contract::new(store);
+
+        assert!(contract
+            .route_messages(sender, vec![msg], 10)
+            .is_ok_and(|res| { res.messages.len() == 1 }));
+    }
 }
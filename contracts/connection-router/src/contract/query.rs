@@ -1,7 +1,11 @@
-use cosmwasm_std::Deps;
+use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, WasmQuery};
 
 use crate::{
-    state::{chain_endpoints, ChainEndpoint, ChainName},
+    msg::{GatewayQueryMsg, TraceMessageResponse},
+    state::{
+        chain_endpoints, ChainEndpoint, ChainName, CrossChainId, Message, ARCHIVED_MESSAGES,
+        CONFIG, MESSAGE_TRACES,
+    },
     ContractError,
 };
 use error_stack::{Result, ResultExt};
@@ -13,6 +17,58 @@ pub fn get_chain_info(deps: Deps, chain: ChainName) -> Result<ChainEndpoint, Con
         .ok_or(ContractError::ChainNotFound.into())
 }
 
+pub fn trace_message(deps: Deps, id: CrossChainId) -> Result<TraceMessageResponse, ContractError> {
+    let trace = MESSAGE_TRACES
+        .may_load(deps.storage, id.clone())
+        .change_context(ContractError::StoreFailure)?;
+    let nexus_gateway = CONFIG
+        .load(deps.storage)
+        .change_context(ContractError::StoreFailure)?
+        .nexus_gateway;
+
+    let archived = ARCHIVED_MESSAGES
+        .may_load(deps.storage, id.clone())
+        .change_context(ContractError::StoreFailure)?;
+
+    // only delivered messages get archived
+    let delivered = match archived {
+        Some(_) => Some(true),
+        None => trace
+            .as_ref()
+            .filter(|trace| trace.destination_gateway != nexus_gateway)
+            .map(|trace| is_delivered(deps, &trace.destination_gateway, &id)),
+    };
+
+    Ok(TraceMessageResponse {
+        verified: trace.is_some() || archived.is_some(),
+        id,
+        trace,
+        archived,
+        delivered,
+    })
+}
+
+pub fn verify_archived_message(deps: Deps, message: Message) -> Result<bool, ContractError> {
+    let archived = ARCHIVED_MESSAGES
+        .may_load(deps.storage, message.cc_id.clone())
+        .change_context(ContractError::StoreFailure)?;
+
+    Ok(archived.is_some_and(|archived| archived.digest.as_slice() == message.hash().as_slice()))
+}
+
+// the gateway fails the query for messages it doesn't know, so any error counts as not delivered
+pub(super) fn is_delivered(deps: Deps, gateway: &Addr, id: &CrossChainId) -> bool {
+    deps.querier
+        .query::<Vec<Message>>(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: gateway.to_string(),
+            msg: to_binary(&GatewayQueryMsg::GetMessages {
+                message_ids: vec![id.clone()],
+            })
+            .expect("must serialize message"),
+        }))
+        .is_ok_and(|msgs| msgs.iter().any(|msg| msg.cc_id == *id))
+}
+
 #[cfg(test)]
 mod test {
     use axelar_wasm_std::flagset::FlagSet;
@@ -1,7 +1,7 @@
 use cosmwasm_std::{Addr, Attribute, Event, HexBinary};
 use std::ops::Deref;
 
-use crate::state::{ChainName, Message};
+use crate::state::{ChainName, CrossChainId, GatewayDirection, Message};
 
 pub struct RouterInstantiated {
     pub admin: Addr,
@@ -33,16 +33,22 @@ pub struct GatewayUnfrozen {
 
 pub struct ChainFrozen {
     pub name: ChainName,
+    pub direction: GatewayDirection,
 }
 
 pub struct ChainUnfrozen {
     pub name: ChainName,
+    pub direction: GatewayDirection,
 }
 
 pub struct MessageRouted {
     pub msg: Message,
 }
 
+pub struct MessagesArchived {
+    pub message_ids: Vec<CrossChainId>,
+}
+
 impl From<RouterInstantiated> for Event {
     fn from(other: RouterInstantiated) -> Self {
         Event::new("router_instantiated")
@@ -91,13 +97,23 @@ impl From<GatewayUnfrozen> for Event {
 
 impl From<ChainFrozen> for Event {
     fn from(other: ChainFrozen) -> Self {
-        Event::new("chain_frozen").add_attribute("name", other.name)
+        Event::new("chain_frozen")
+            .add_attribute("name", other.name)
+            .add_attribute(
+                "direction",
+                serde_json::to_string(&other.direction).expect("failed to serialize direction"),
+            )
     }
 }
 
 impl From<ChainUnfrozen> for Event {
     fn from(other: ChainUnfrozen) -> Self {
-        Event::new("chain_unfrozen").add_attribute("name", other.name)
+        Event::new("chain_unfrozen")
+            .add_attribute("name", other.name)
+            .add_attribute(
+                "direction",
+                serde_json::to_string(&other.direction).expect("failed to serialize direction"),
+            )
     }
 }
 
@@ -128,3 +144,12 @@ impl From<MessageRouted> for Event {
         make_message_event("message_routed", other.msg)
     }
 }
+
+impl From<MessagesArchived> for Event {
+    fn from(other: MessagesArchived) -> Self {
+        Event::new("messages_archived").add_attribute(
+            "message_ids",
+            serde_json::to_string(&other.message_ids).expect("must serialize message ids"),
+        )
+    }
+}
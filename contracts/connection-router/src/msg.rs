@@ -1,4 +1,7 @@
-use crate::state::{ChainEndpoint, ChainName, GatewayDirection, Message};
+use crate::state::{
+    ArchivedMessage, ChainEndpoint, ChainName, CrossChainId, GatewayDirection, Message,
+    MessageTrace,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 #[cw_serde]
@@ -50,6 +53,15 @@ pub enum ExecuteMsg {
     // Routes each message to the gateway registered to the destination chain.
     // Called by a registered gateway
     RouteMessages(Vec<Message>),
+
+    /*
+     * Permissionless Methods
+     */
+    // Replaces the traces of delivered messages with a compact record of the message hash and block heights.
+    // Messages that are unknown, not delivered yet or routed to the nexus gateway are skipped
+    ArchiveMessages {
+        message_ids: Vec<CrossChainId>,
+    },
 }
 
 #[cw_serde]
@@ -57,4 +69,36 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ChainEndpoint)]
     GetChainInfo(ChainName),
+
+    // Returns everything the router knows about a message, including whether the destination gateway holds it
+    #[returns(TraceMessageResponse)]
+    TraceMessage { id: CrossChainId },
+
+    // Returns true if the message was archived and its hash matches the archived digest
+    #[returns(bool)]
+    VerifyArchivedMessage { message: Message },
+}
+
+// The queries every gateway connected to the router answers. The gateway contract uses this as its QueryMsg,
+// because it depends on the router and the router can't depend on it in turn
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum GatewayQueryMsg {
+    #[returns(Vec<Message>)]
+    GetMessages { message_ids: Vec<CrossChainId> },
+}
+
+#[cw_serde]
+pub struct TraceMessageResponse {
+    pub id: CrossChainId,
+    // None if the message was never routed, or its trace was archived
+    pub trace: Option<MessageTrace>,
+    // Set once the trace of the delivered message was archived
+    pub archived: Option<ArchivedMessage>,
+    // Messages are only handed to the router after being verified by the source gateway,
+    // so any routed message counts as verified
+    pub verified: bool,
+    // Whether the destination gateway has the message stored. None if the message was never routed,
+    // or was routed to the nexus gateway, which cannot be queried for it. Archived messages are always delivered
+    pub delivered: Option<bool>,
 }
@@ -6,9 +6,9 @@ use std::str::FromStr;
 
 use axelar_wasm_std::hash::Hash;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, DepsMut, Order, StdError, StdResult, Storage};
+use cosmwasm_std::{Addr, DepsMut, HexBinary, Order, StdError, StdResult, Storage};
 use cw_storage_plus::{
-    Index, IndexList, IndexedMap, Item, Key, KeyDeserialize, MultiIndex, Prefixer, PrimaryKey,
+    Index, IndexList, IndexedMap, Item, Key, KeyDeserialize, Map, MultiIndex, Prefixer, PrimaryKey,
 };
 use error_stack::{Report, ResultExt};
 use flagset::flags;
@@ -37,6 +37,15 @@ pub trait Store {
         &self,
         chain_name: &ChainName,
     ) -> error_stack::Result<Option<ChainEndpoint>, ContractError>;
+    fn load_message_trace(
+        &self,
+        cc_id: &CrossChainId,
+    ) -> error_stack::Result<Option<MessageTrace>, ContractError>;
+    fn save_message_trace(
+        &mut self,
+        cc_id: &CrossChainId,
+        trace: MessageTrace,
+    ) -> error_stack::Result<(), ContractError>;
 }
 
 pub struct RouterStore<'a> {
@@ -75,6 +84,25 @@ impl Store for RouterStore<'_> {
             .may_load(self.storage, chain_name.clone())
             .change_context(ContractError::StoreFailure)
     }
+
+    fn load_message_trace(
+        &self,
+        cc_id: &CrossChainId,
+    ) -> error_stack::Result<Option<MessageTrace>, ContractError> {
+        MESSAGE_TRACES
+            .may_load(self.storage, cc_id.clone())
+            .change_context(ContractError::StoreFailure)
+    }
+
+    fn save_message_trace(
+        &mut self,
+        cc_id: &CrossChainId,
+        trace: MessageTrace,
+    ) -> error_stack::Result<(), ContractError> {
+        MESSAGE_TRACES
+            .save(self.storage, cc_id.clone(), &trace)
+            .change_context(ContractError::StoreFailure)
+    }
 }
 
 impl<'a> RouterStore<'a> {
@@ -92,6 +120,66 @@ pub struct Config {
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// Routing history of a single message, as observed by the router
+#[cw_serde]
+pub struct MessageTrace {
+    /// gateway that handed the message to the router
+    pub source_gateway: Addr,
+    /// gateway the message was last routed to
+    pub destination_gateway: Addr,
+    /// block height at which the router first received the message
+    pub received_at: u64,
+    /// block height at which the message was last routed
+    pub routed_at: u64,
+    /// number of times the message was routed again after the first time
+    pub retries: u32,
+    /// hash of the message that was last routed, kept when the trace is archived.
+    /// None for messages routed before the hash was recorded
+    #[serde(default)]
+    pub message_hash: Option<HexBinary>,
+}
+
+impl MessageTrace {
+    pub fn new(
+        source_gateway: Addr,
+        destination_gateway: Addr,
+        block_height: u64,
+        message_hash: Hash,
+    ) -> Self {
+        Self {
+            source_gateway,
+            destination_gateway,
+            received_at: block_height,
+            routed_at: block_height,
+            retries: 0,
+            message_hash: Some(message_hash.into()),
+        }
+    }
+
+    pub fn retry(self, destination_gateway: Addr, block_height: u64, message_hash: Hash) -> Self {
+        Self {
+            destination_gateway,
+            routed_at: block_height,
+            retries: self.retries.saturating_add(1),
+            message_hash: Some(message_hash.into()),
+            ..self
+        }
+    }
+}
+
+pub const MESSAGE_TRACES: Map<CrossChainId, MessageTrace> = Map::new("message_traces");
+
+/// Compact record of a delivered message whose trace was archived
+#[cw_serde]
+pub struct ArchivedMessage {
+    /// hash of the routed message, see [Message::hash]
+    pub digest: HexBinary,
+    pub received_at: u64,
+    pub routed_at: u64,
+}
+
+pub const ARCHIVED_MESSAGES: Map<CrossChainId, ArchivedMessage> = Map::new("archived_messages");
+
 pub struct ChainEndpointIndexes<'a> {
     pub gateway: GatewayIndex<'a>,
 }
@@ -168,6 +256,10 @@ pub struct Message {
     #[serde(with = "axelar_wasm_std::hex")]
     #[schemars(with = "String")] // necessary attribute in conjunction with #[serde(with ...)]
     pub payload_hash: [u8; 32],
+    /// token that is transferred to the destination address together with the message.
+    /// Only messages coming from the nexus can carry a token, verifiers don't check token transfers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<TokenTransfer>,
 }
 
 impl Message {
@@ -179,10 +271,21 @@ impl Message {
         hasher.update(self.destination_chain.as_ref());
         hasher.update(self.destination_address.as_str());
         hasher.update(self.payload_hash);
+        // messages without a token keep the hash they had before tokens were supported
+        if let Some(token) = &self.token {
+            hasher.update(token.symbol.as_str());
+            hasher.update(cosmwasm_std::Uint256::from(token.amount).to_be_bytes());
+        }
         hasher.finalize().into()
     }
 }
 
+#[cw_serde]
+pub struct TokenTransfer {
+    pub symbol: nonempty::String,
+    pub amount: nonempty::Uint256,
+}
+
 #[cw_serde]
 pub struct Address(nonempty::String);
 
@@ -510,6 +613,7 @@ mod tests {
             destination_chain: "destination_chain".parse().unwrap(),
             destination_address: "destination_address".parse().unwrap(),
             payload_hash: [1; 32].into(),
+            token: None,
         }
     }
 }
@@ -30,14 +30,14 @@ pub fn mock_gateway_execute(
 
 #[cw_serde]
 pub enum MockGatewayQueryMsg {
-    GetMessages { ids: Vec<CrossChainId> },
+    GetMessages { message_ids: Vec<CrossChainId> },
 }
 pub fn mock_gateway_query(deps: Deps, _env: Env, msg: MockGatewayQueryMsg) -> StdResult<Binary> {
     let mut msgs = vec![];
 
     match msg {
-        MockGatewayQueryMsg::GetMessages { ids } => {
-            for id in ids {
+        MockGatewayQueryMsg::GetMessages { message_ids } => {
+            for id in message_ids {
                 match MOCK_GATEWAY_MESSAGES.may_load(deps.storage, id)? {
                     Some(m) => msgs.push(m),
                     None => (),
@@ -57,7 +57,7 @@ pub fn get_gateway_messages(
         .query_wasm_smart(
             gateway_address,
             &MockGatewayQueryMsg::GetMessages {
-                ids: msgs.iter().map(|msg| msg.cc_id.clone()).collect(),
+                message_ids: msgs.iter().map(|msg| msg.cc_id.clone()).collect(),
             },
         )
         .unwrap()
@@ -1,13 +1,15 @@
 use std::str::FromStr;
 use std::{collections::HashMap, vec};
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Event};
 use cw_multi_test::{App, ContractWrapper, Executor};
 
 use connection_router::contract::*;
 use connection_router::error::ContractError;
-use connection_router::msg::{ExecuteMsg, InstantiateMsg};
-use connection_router::state::{ChainName, CrossChainId, GatewayDirection, Message};
+use connection_router::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TraceMessageResponse};
+use connection_router::state::{
+    ArchivedMessage, ChainName, CrossChainId, GatewayDirection, Message, MessageTrace,
+};
 
 pub mod mock;
 
@@ -95,6 +97,7 @@ fn generate_messages(
             destination_chain: dest_chain.chain_name.clone(),
             source_address: "idc".parse().unwrap(),
             payload_hash: [x as u8; 32],
+            token: None,
         })
     }
     msgs
@@ -139,6 +142,172 @@ fn route() {
     assert!(res.is_ok());
 }
 
+#[test]
+fn trace_message() {
+    let mut config = setup();
+    let eth = make_chain("ethereum", &mut config);
+    let polygon = make_chain("polygon", &mut config);
+
+    register_chain(&mut config, &eth);
+    register_chain(&mut config, &polygon);
+
+    let nonce: &mut usize = &mut 0;
+    let msg = generate_messages(&eth, &polygon, nonce, 1).remove(0);
+
+    let res: TraceMessageResponse = config
+        .app
+        .wrap()
+        .query_wasm_smart(
+            config.contract_address.clone(),
+            &QueryMsg::TraceMessage {
+                id: msg.cc_id.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.trace, None);
+    assert!(!res.verified);
+    assert_eq!(res.delivered, None);
+
+    let received_at = config.app.block_info().height;
+    for _ in 0..2 {
+        config
+            .app
+            .execute_contract(
+                eth.gateway.clone(),
+                config.contract_address.clone(),
+                &ExecuteMsg::RouteMessages(vec![msg.clone()]),
+                &[],
+            )
+            .unwrap();
+        config.app.update_block(|block| block.height += 1);
+    }
+
+    let res: TraceMessageResponse = config
+        .app
+        .wrap()
+        .query_wasm_smart(
+            config.contract_address.clone(),
+            &QueryMsg::TraceMessage {
+                id: msg.cc_id.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.trace,
+        Some(MessageTrace {
+            source_gateway: eth.gateway,
+            destination_gateway: polygon.gateway,
+            received_at,
+            routed_at: received_at + 1,
+            retries: 1,
+            message_hash: Some(msg.hash().into()),
+        })
+    );
+    assert_eq!(res.archived, None);
+    assert!(res.verified);
+    assert_eq!(res.delivered, Some(true));
+}
+
+#[test]
+fn archive_delivered_messages() {
+    let mut config = setup();
+    let eth = make_chain("ethereum", &mut config);
+    let polygon = make_chain("polygon", &mut config);
+
+    register_chain(&mut config, &eth);
+    register_chain(&mut config, &polygon);
+
+    let nonce: &mut usize = &mut 0;
+    let msgs = generate_messages(&eth, &polygon, nonce, 2);
+    let unknown_msg = generate_messages(&eth, &polygon, nonce, 1).remove(0);
+
+    let received_at = config.app.block_info().height;
+    config
+        .app
+        .execute_contract(
+            eth.gateway.clone(),
+            config.contract_address.clone(),
+            &ExecuteMsg::RouteMessages(msgs.clone()),
+            &[],
+        )
+        .unwrap();
+
+    // anyone can archive, unknown messages are skipped
+    let res = config
+        .app
+        .execute_contract(
+            Addr::unchecked("random"),
+            config.contract_address.clone(),
+            &ExecuteMsg::ArchiveMessages {
+                message_ids: vec![msgs[0].cc_id.clone(), unknown_msg.cc_id.clone()],
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(
+        res.has_event(&Event::new("wasm-messages_archived").add_attribute(
+            "message_ids",
+            serde_json::to_string(&vec![msgs[0].cc_id.clone()]).unwrap()
+        ))
+    );
+
+    let res: TraceMessageResponse = config
+        .app
+        .wrap()
+        .query_wasm_smart(
+            config.contract_address.clone(),
+            &QueryMsg::TraceMessage {
+                id: msgs[0].cc_id.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.trace, None);
+    assert_eq!(
+        res.archived,
+        Some(ArchivedMessage {
+            digest: msgs[0].hash().into(),
+            received_at,
+            routed_at: received_at,
+        })
+    );
+    assert!(res.verified);
+    assert_eq!(res.delivered, Some(true));
+
+    // the trace of the message that was not archived is untouched
+    let res: TraceMessageResponse = config
+        .app
+        .wrap()
+        .query_wasm_smart(
+            config.contract_address.clone(),
+            &QueryMsg::TraceMessage {
+                id: msgs[1].cc_id.clone(),
+            },
+        )
+        .unwrap();
+    assert!(res.trace.is_some());
+    assert_eq!(res.archived, None);
+
+    let verify = |config: &TestConfig, message: Message| -> bool {
+        config
+            .app
+            .wrap()
+            .query_wasm_smart(
+                config.contract_address.clone(),
+                &QueryMsg::VerifyArchivedMessage { message },
+            )
+            .unwrap()
+    };
+    assert!(verify(&config, msgs[0].clone()));
+    assert!(!verify(&config, msgs[1].clone()));
+    assert!(!verify(
+        &config,
+        Message {
+            payload_hash: [1; 32],
+            ..msgs[0].clone()
+        }
+    ));
+}
+
 #[test]
 fn wrong_source_chain() {
     let mut config = setup();
@@ -676,7 +845,7 @@ fn freeze_incoming() {
     register_chain(&mut config, &eth);
     register_chain(&mut config, &polygon);
 
-    let _ = config
+    let res = config
         .app
         .execute_contract(
             config.admin_address.clone(),
@@ -688,6 +857,11 @@ fn freeze_incoming() {
             &[],
         )
         .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-chain_frozen")
+            .add_attribute("name", polygon.chain_name.to_string())
+            .add_attribute("direction", "\"Incoming\"")
+    ));
 
     let msg = &generate_messages(&polygon, &eth, &mut 0, 1)[0];
     // can't route from frozen incoming gateway
@@ -723,16 +897,23 @@ fn freeze_incoming() {
         mock::get_gateway_messages(&mut config.app, polygon.gateway.clone(), &vec![msg.clone()]);
     assert_eq!(&msgs[0], msg);
 
-    let res = config.app.execute_contract(
-        config.admin_address.clone(),
-        config.contract_address.clone(),
-        &ExecuteMsg::UnfreezeChain {
-            chain: polygon.chain_name.clone(),
-            direction: GatewayDirection::Incoming,
-        },
-        &[],
-    );
-    assert!(res.is_ok());
+    let res = config
+        .app
+        .execute_contract(
+            config.admin_address.clone(),
+            config.contract_address.clone(),
+            &ExecuteMsg::UnfreezeChain {
+                chain: polygon.chain_name.clone(),
+                direction: GatewayDirection::Incoming,
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-chain_unfrozen")
+            .add_attribute("name", polygon.chain_name.to_string())
+            .add_attribute("direction", "\"Incoming\"")
+    ));
 
     let msg = &generate_messages(&polygon, &eth, &mut 0, 1)[0];
     let res = config.app.execute_contract(
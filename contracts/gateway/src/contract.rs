@@ -21,7 +21,14 @@ pub fn instantiate(
     let router = deps.api.addr_validate(&msg.router_address)?;
     let verifier = deps.api.addr_validate(&msg.verifier_address)?;
 
-    CONFIG.save(deps.storage, &Config { verifier, router })?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            verifier,
+            router,
+            ibc_subscribers: msg.ibc_subscribers,
+        },
+    )?;
 
     Ok(Response::new())
 }
@@ -29,11 +36,11 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
-    let mut contract = Contract::new(deps);
+    let mut contract = Contract::new(deps, &env.block);
     match msg {
         ExecuteMsg::VerifyMessages(msgs) => contract.verify_messages(msgs),
         ExecuteMsg::RouteMessages(msgs) => contract.route_messages(info.sender, msgs),
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use cosmwasm_std::{to_binary, Addr, DepsMut, Response, WasmMsg};
+use cosmwasm_std::{to_binary, Addr, BlockInfo, DepsMut, IbcMsg, IbcTimeout, Response, WasmMsg};
 use error_stack::{Result, ResultExt};
 use itertools::Itertools;
 
@@ -10,9 +10,12 @@ use crate::error::ContractError;
 use connection_router::state::Message;
 
 use crate::events::GatewayEvent;
+use crate::msg::{StatusUpdatePacket, VerificationStatus};
 use crate::state;
 use crate::state::{Config, Store};
 
+const STATUS_UPDATE_TIMEOUT_SECONDS: u64 = 60 * 60; // 1 hour
+
 pub struct Contract<V, S>
 where
     V: Verifier,
@@ -21,10 +24,14 @@ where
     pub config: Config,
     pub verifier: V,
     pub store: S,
+    pub status_update_timeout: IbcTimeout,
 }
 
 impl<'a> Contract<query::VerifierApi<'a>, state::GatewayStore<'a>> {
-    pub fn new(deps: DepsMut) -> Contract<query::VerifierApi, state::GatewayStore> {
+    pub fn new(
+        deps: DepsMut<'a>,
+        block: &BlockInfo,
+    ) -> Contract<query::VerifierApi<'a>, state::GatewayStore<'a>> {
         let store = state::GatewayStore {
             storage: deps.storage,
         };
@@ -37,6 +44,10 @@ impl<'a> Contract<query::VerifierApi<'a>, state::GatewayStore<'a>> {
                 address: verifier_addr,
                 querier: deps.querier,
             },
+            status_update_timeout: block
+                .time
+                .plus_seconds(STATUS_UPDATE_TIMEOUT_SECONDS)
+                .into(),
         }
     }
 }
@@ -46,7 +57,7 @@ where
     V: Verifier,
     S: Store,
 {
-    pub fn verify_messages(&self, msgs: Vec<Message>) -> Result<Response, ContractError> {
+    pub fn verify_messages(&mut self, msgs: Vec<Message>) -> Result<Response, ContractError> {
         // short circuit if there are no messages there is no need to interact with the verifier so it saves gas
         if msgs.is_empty() {
             return Ok(Response::new());
@@ -54,15 +65,17 @@ where
 
         ensure_unique_ids(&msgs)?;
 
-        let (_, unverified) = self.partition_by_verified(msgs)?;
+        let (verified, unverified) = self.partition_by_verified(msgs)?;
+
+        let response = Response::new().add_messages(self.status_updates(&verified, &unverified)?);
 
         // short circuit if there are no unverified messages
         // there is no need to interact with the verifier so it saves gas
         if unverified.is_empty() {
-            return Ok(Response::new());
+            return Ok(response);
         }
 
-        Ok(Response::new().add_message(WasmMsg::Execute {
+        Ok(response.add_message(WasmMsg::Execute {
             contract_addr: self.config.verifier.to_string(),
             msg: to_binary(&aggregate_verifier::msg::ExecuteMsg::VerifyMessages {
                 messages: unverified,
@@ -97,7 +110,7 @@ where
         ))
     }
 
-    fn route_incoming_messages(&self, msgs: Vec<Message>) -> Result<Response, ContractError> {
+    fn route_incoming_messages(&mut self, msgs: Vec<Message>) -> Result<Response, ContractError> {
         ensure_unique_ids(&msgs)?;
 
         let (verified, unverified) = self.partition_by_verified(msgs)?;
@@ -105,6 +118,7 @@ where
         let any_verified = !verified.is_empty();
 
         let mut response = Response::new()
+            .add_messages(self.status_updates(&verified, &unverified)?)
             .add_events(
                 verified
                     .clone()
@@ -145,6 +159,57 @@ where
             .into_iter()
             .partition(|msg| -> bool { is_verified.get(&msg.cc_id).copied().unwrap_or(false) }))
     }
+
+    /// Creates a status update packet for every open channel subscribed to a message whose status changed
+    /// since the subscribers were last notified
+    fn status_updates(
+        &mut self,
+        verified: &[Message],
+        unverified: &[Message],
+    ) -> Result<Vec<IbcMsg>, ContractError> {
+        let statuses = verified
+            .iter()
+            .map(|msg| (msg, VerificationStatus::Verified))
+            .chain(
+                unverified
+                    .iter()
+                    .map(|msg| (msg, VerificationStatus::Unverified)),
+            );
+
+        let mut packets = vec![];
+        for (msg, status) in statuses {
+            let mut subscription = match self.store.load_subscription(msg.cc_id.clone())? {
+                Some(subscription) => subscription,
+                None => continue,
+            };
+
+            if subscription.last_status.as_ref() == Some(&status) {
+                continue;
+            }
+
+            subscription.last_status = Some(status.clone());
+            self.store
+                .save_subscription(msg.cc_id.clone(), &subscription)?;
+
+            let data = to_binary(&StatusUpdatePacket {
+                message_id: msg.cc_id.clone(),
+                status,
+            })
+            .change_context(ContractError::CreateStatusUpdatePacket)?;
+
+            for channel_id in subscription.channels {
+                if self.store.is_channel_open(&channel_id)? {
+                    packets.push(IbcMsg::SendPacket {
+                        channel_id,
+                        data: data.clone(),
+                        timeout: self.status_update_timeout.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(packets)
+    }
 }
 
 fn ensure_unique_ids(msgs: &[Message]) -> Result<(), ContractError> {
@@ -168,9 +233,12 @@ mod tests {
     use crate::contract::execute::Contract;
     use crate::contract::query;
     use crate::error::ContractError;
+    use crate::msg::{StatusUpdatePacket, VerificationStatus};
     use crate::state;
+    use crate::state::Subscription;
     use connection_router::state::{CrossChainId, Message, ID_SEPARATOR};
-    use cosmwasm_std::{Addr, CosmosMsg, SubMsg, WasmMsg};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{from_binary, Addr, CosmosMsg, IbcMsg, SubMsg, WasmMsg};
     use error_stack::bail;
     use std::collections::HashMap;
     use std::sync::{Arc, RwLock};
@@ -182,7 +250,7 @@ mod tests {
         let mut msgs = generate_messages(10);
         // no messages are verified
         let is_verified = HashMap::new();
-        let contract = create_contract(msg_store.clone(), is_verified);
+        let mut contract = create_contract(msg_store.clone(), is_verified);
 
         // duplicate some IDs
         msgs[5..]
@@ -201,7 +269,7 @@ mod tests {
         let msgs = generate_messages(10);
         // mark all generated messages as verified
         let is_verified = msgs.iter().map(|msg| (msg.cc_id.clone(), true)).collect();
-        let contract = create_contract(msg_store.clone(), is_verified);
+        let mut contract = create_contract(msg_store.clone(), is_verified);
 
         // try zero, one, many messages
         let inputs = vec![vec![], msgs[..1].to_vec(), msgs];
@@ -219,7 +287,7 @@ mod tests {
         let msgs = generate_messages(10);
         // no messages are verified
         let is_verified = HashMap::new();
-        let contract = create_contract(msg_store.clone(), is_verified);
+        let mut contract = create_contract(msg_store.clone(), is_verified);
 
         // try one and many messages (zero messages are tested in verify_all_verified)
         let inputs = vec![msgs[..1].to_vec(), msgs];
@@ -246,7 +314,7 @@ mod tests {
             .iter()
             .map(|msg| (msg.cc_id.clone(), true))
             .collect();
-        let contract = create_contract(msg_store.clone(), is_verified);
+        let mut contract = create_contract(msg_store.clone(), is_verified);
 
         // expect: no error, only the unverified messages get verified
         let result = contract.verify_messages(msgs.clone());
@@ -268,7 +336,7 @@ mod tests {
             .iter()
             .map(|msg| (msg.cc_id.clone(), true))
             .collect();
-        let contract = create_contract(msg_store.clone(), is_verified);
+        let mut contract = create_contract(msg_store.clone(), is_verified);
 
         // expect: same response when called multiple times and no messages are stored
         let result1 = contract.verify_messages(msgs.clone());
@@ -288,7 +356,7 @@ mod tests {
         verifier
             .expect_verify()
             .returning(|_| bail!(ContractError::QueryVerifier));
-        let contract = Contract {
+        let mut contract = Contract {
             verifier,
             ..create_contract(msg_store.clone(), HashMap::new())
         };
@@ -470,15 +538,85 @@ mod tests {
         assert_correct_messages_stored(&msg_store, &msgs);
     }
 
-    /// This uses a RwLock for the msg_store so it can also be used in assertions while it is borrowed by the contract
+    /// Subscribers should be notified once per status change, and only on open channels
+    #[test]
+    fn status_updates_sent_to_subscribers_on_status_change() {
+        let msg_store = Arc::new(RwLock::new(HashMap::new()));
+        let msgs = generate_messages(2);
+        let subscribed = msgs[0].cc_id.clone();
+        let subscriptions = Arc::new(RwLock::new(HashMap::from([(
+            subscribed.clone(),
+            Subscription {
+                channels: vec!["open-channel".to_string(), "closed-channel".to_string()],
+                last_status: None,
+            },
+        )])));
+
+        let mut contract = create_contract_with_subscriptions(
+            msg_store.clone(),
+            HashMap::new(),
+            subscriptions.clone(),
+        );
+
+        // expect: one status update for the subscribed message and the verifier call
+        let result = contract.verify_messages(msgs.clone()).unwrap();
+        assert_eq!(result.messages.len(), 2);
+        assert_status_update_sent(
+            &result.messages[0],
+            &subscribed,
+            VerificationStatus::Unverified,
+        );
+
+        // expect: no status update when the status did not change
+        let result = contract.verify_messages(msgs.clone()).unwrap();
+        assert_eq!(result.messages.len(), 1);
+
+        let is_verified = msgs.iter().map(|msg| (msg.cc_id.clone(), true)).collect();
+        let mut contract = create_contract_with_subscriptions(
+            msg_store.clone(),
+            is_verified,
+            subscriptions.clone(),
+        );
+
+        // expect: one status update when the message becomes verified, followed by the router call
+        let result = contract
+            .route_messages(Addr::unchecked("not a router"), msgs.clone())
+            .unwrap();
+        assert_eq!(result.messages.len(), 2);
+        assert_status_update_sent(
+            &result.messages[0],
+            &subscribed,
+            VerificationStatus::Verified,
+        );
+        assert_eq!(
+            subscriptions.read().unwrap()[&subscribed].last_status,
+            Some(VerificationStatus::Verified)
+        );
+    }
+
     fn create_contract(
+        msg_store: Arc<RwLock<HashMap<CrossChainId, Message>>>,
+        is_verified: HashMap<CrossChainId, bool>,
+    ) -> Contract<query::MockVerifier, state::MockStore> {
+        create_contract_with_subscriptions(
+            msg_store,
+            is_verified,
+            Arc::new(RwLock::new(HashMap::new())),
+        )
+    }
+
+    /// This uses a RwLock for the msg_store and subscriptions so they can also be used in assertions while they are borrowed by the contract.
+    /// Only channels named "open-channel" are considered open.
+    fn create_contract_with_subscriptions(
         // the store mock requires a 'static type that can be moved into the closure, so we need to use an Arc<> here
         msg_store: Arc<RwLock<HashMap<CrossChainId, Message>>>,
         is_verified: HashMap<CrossChainId, bool>,
+        subscriptions: Arc<RwLock<HashMap<CrossChainId, Subscription>>>,
     ) -> Contract<query::MockVerifier, state::MockStore> {
         let config = state::Config {
             verifier: Addr::unchecked("verifier"),
             router: Addr::unchecked("router"),
+            ibc_subscribers: vec![],
         };
 
         let mut store = state::MockStore::new();
@@ -490,6 +628,22 @@ mod tests {
                 msg_store.insert(key, msg.clone());
                 Ok(())
             });
+        let subscriptions_clone = subscriptions.clone();
+        store
+            .expect_load_subscription()
+            .returning(move |key| Ok(subscriptions_clone.read().unwrap().get(&key).cloned()));
+        store
+            .expect_save_subscription()
+            .returning(move |key, subscription: &Subscription| {
+                subscriptions
+                    .write()
+                    .unwrap()
+                    .insert(key, subscription.clone());
+                Ok(())
+            });
+        store
+            .expect_is_channel_open()
+            .returning(|channel_id| Ok(channel_id == "open-channel"));
 
         let mut verifier = query::MockVerifier::new();
         verifier.expect_verify().returning(move |msg| match msg {
@@ -508,6 +662,7 @@ mod tests {
             config,
             store,
             verifier,
+            status_update_timeout: mock_env().block.time.into(),
         }
     }
 
@@ -522,10 +677,33 @@ mod tests {
                 destination_chain: "mock-chain-2".parse().unwrap(),
                 source_address: "idc".parse().unwrap(),
                 payload_hash: [i as u8; 32],
+                token: None,
             })
             .collect()
     }
 
+    fn assert_status_update_sent(
+        msg: &SubMsg,
+        expected_message_id: &CrossChainId,
+        expected_status: VerificationStatus,
+    ) {
+        match msg.msg.clone() {
+            CosmosMsg::Ibc(IbcMsg::SendPacket {
+                channel_id, data, ..
+            }) => {
+                assert_eq!(channel_id, "open-channel");
+                assert_eq!(
+                    from_binary::<StatusUpdatePacket>(&data).unwrap(),
+                    StatusUpdatePacket {
+                        message_id: expected_message_id.clone(),
+                        status: expected_status,
+                    }
+                );
+            }
+            _ => panic!("unexpected message type"),
+        }
+    }
+
     fn assert_correct_messages_verified(
         verified_msgs: Vec<SubMsg>,
         expected_verifier: &Addr,
@@ -544,6 +722,7 @@ mod tests {
                     aggregate_verifier::msg::ExecuteMsg::VerifyMessages { messages } => {
                         assert_eq!(messages.as_slice(), expected_msgs)
                     }
+                    _ => panic!("unexpected verifier message"),
                 }
             }
             _ => panic!("unexpected message type"),
@@ -20,4 +20,34 @@ pub enum ContractError {
 
     #[error("could not create the execute message to start routing")]
     CreateRouterExecuteMsg,
+
+    #[error("could not store subscription")]
+    StoreSubscription,
+
+    #[error("could not load subscription")]
+    LoadSubscription,
+
+    #[error("could not load ibc channel")]
+    LoadChannel,
+
+    #[error("could not store ibc channel")]
+    StoreChannel,
+
+    #[error("could not create the status update packet")]
+    CreateStatusUpdatePacket,
+
+    #[error("invalid subscription packet")]
+    InvalidSubscriptionPacket,
+
+    #[error("ibc channel must be unordered")]
+    InvalidIbcChannelOrder,
+
+    #[error("invalid ibc channel version")]
+    InvalidIbcVersion,
+
+    #[error("could not load config")]
+    LoadConfig,
+
+    #[error("ibc channel counterparty is not an allowed subscriber")]
+    IbcSubscriberNotAllowed,
 }
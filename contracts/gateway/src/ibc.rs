@@ -0,0 +1,378 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Binary, DepsMut, Env, IbcBasicResponse, IbcChannel, IbcChannelCloseMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcOrder, IbcPacketAckMsg,
+    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Storage,
+};
+use error_stack::{report, Result, ResultExt};
+
+use crate::error::ContractError;
+use crate::msg::{SubscriptionAck, SubscriptionPacket};
+use crate::state::{CONFIG, OPEN_CHANNELS, SUBSCRIPTIONS};
+
+pub const IBC_VERSION: &str = "axelar-gateway-subscriptions-1";
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> core::result::Result<IbcChannelOpenResponse, axelar_wasm_std::ContractError> {
+    validate_channel(deps.storage, msg.channel(), msg.counterparty_version())?;
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> core::result::Result<IbcBasicResponse, axelar_wasm_std::ContractError> {
+    let channel = msg.channel();
+    validate_channel(deps.storage, channel, msg.counterparty_version())?;
+
+    OPEN_CHANNELS
+        .save(deps.storage, &channel.endpoint.channel_id, &())
+        .change_context(ContractError::StoreChannel)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_connect")
+        .add_attribute("channel_id", &channel.endpoint.channel_id))
+}
+
+/// Subscriptions of a closed channel are kept, status updates for them are skipped until a channel with the same id is reopened
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> core::result::Result<IbcBasicResponse, axelar_wasm_std::ContractError> {
+    let channel = msg.channel();
+
+    OPEN_CHANNELS.remove(deps.storage, &channel.endpoint.channel_id);
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_close")
+        .add_attribute("channel_id", &channel.endpoint.channel_id))
+}
+
+/// Invalid subscription packets are acknowledged with an error instead of failing,
+/// so the subscriber learns why its subscription was not registered
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> core::result::Result<IbcReceiveResponse, axelar_wasm_std::ContractError> {
+    let ack = match receive_subscription_packet(
+        deps.storage,
+        &msg.packet.dest.channel_id,
+        &msg.packet.data,
+    ) {
+        Ok(()) => SubscriptionAck::Ok,
+        Err(err) => SubscriptionAck::Error(err.current_context().to_string()),
+    };
+
+    Ok(IbcReceiveResponse::new()
+        .set_ack(to_binary(&ack)?)
+        .add_attribute("action", "ibc_packet_receive"))
+}
+
+/// Status updates are best effort, so there is nothing to do when they are acknowledged
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketAckMsg,
+) -> core::result::Result<IbcBasicResponse, axelar_wasm_std::ContractError> {
+    Ok(IbcBasicResponse::new().add_attribute("action", "ibc_packet_ack"))
+}
+
+/// Status updates are best effort, so they are not resent when they time out
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> core::result::Result<IbcBasicResponse, axelar_wasm_std::ContractError> {
+    Ok(IbcBasicResponse::new().add_attribute("action", "ibc_packet_timeout"))
+}
+
+// every open channel adds a packet to each status update of the messages it subscribes to, so only allowed subscribers can open one
+fn validate_channel(
+    storage: &dyn Storage,
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    let config = CONFIG
+        .load(storage)
+        .change_context(ContractError::LoadConfig)?;
+    if !config.ibc_subscribers.iter().any(|subscriber| {
+        subscriber.connection_id == channel.connection_id
+            && subscriber.port_id == channel.counterparty_endpoint.port_id
+    }) {
+        return Err(report!(ContractError::IbcSubscriberNotAllowed)).attach_printable(format!(
+            "{{ connection_id = {}, port_id = {} }}",
+            channel.connection_id, channel.counterparty_endpoint.port_id
+        ));
+    }
+
+    if channel.order != IbcOrder::Unordered {
+        return Err(report!(ContractError::InvalidIbcChannelOrder));
+    }
+
+    if channel.version != IBC_VERSION {
+        return Err(report!(ContractError::InvalidIbcVersion))
+            .attach_printable(channel.version.clone());
+    }
+
+    match counterparty_version {
+        Some(version) if version != IBC_VERSION => {
+            Err(report!(ContractError::InvalidIbcVersion)).attach_printable(version.to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn receive_subscription_packet(
+    storage: &mut dyn Storage,
+    channel_id: &str,
+    data: &Binary,
+) -> Result<(), ContractError> {
+    let packet: SubscriptionPacket =
+        from_binary(data).change_context(ContractError::InvalidSubscriptionPacket)?;
+
+    match packet {
+        SubscriptionPacket::Subscribe { message_ids } => {
+            for message_id in message_ids {
+                SUBSCRIPTIONS
+                    .update(storage, message_id, |subscription| {
+                        let mut subscription = subscription.unwrap_or_default();
+                        if !subscription.channels.iter().any(|id| id == channel_id) {
+                            subscription.channels.push(channel_id.to_string());
+                        }
+                        Ok::<_, cosmwasm_std::StdError>(subscription)
+                    })
+                    .change_context(ContractError::StoreSubscription)?;
+            }
+        }
+        SubscriptionPacket::Unsubscribe { message_ids } => {
+            for message_id in message_ids {
+                let mut subscription = match SUBSCRIPTIONS
+                    .may_load(storage, message_id.clone())
+                    .change_context(ContractError::LoadSubscription)?
+                {
+                    Some(subscription) => subscription,
+                    None => continue,
+                };
+
+                subscription.channels.retain(|id| id != channel_id);
+                if subscription.channels.is_empty() {
+                    SUBSCRIPTIONS.remove(storage, message_id);
+                } else {
+                    SUBSCRIPTIONS
+                        .save(storage, message_id, &subscription)
+                        .change_context(ContractError::StoreSubscription)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use connection_router::state::CrossChainId;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_ibc_channel, mock_ibc_channel_close_init,
+        mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_ibc_packet_recv, MockApi,
+        MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::Addr;
+
+    use crate::msg::IbcSubscriber;
+    use crate::state::Config;
+
+    use super::*;
+
+    const CHANNEL_ID: &str = "channel-1";
+
+    // the mock channels connect to the port "their_port" over "connection-2"
+    fn mock_dependencies_with_subscriber(
+    ) -> cosmwasm_std::OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    verifier: Addr::unchecked("verifier"),
+                    router: Addr::unchecked("router"),
+                    ibc_subscribers: vec![IbcSubscriber {
+                        connection_id: "connection-2".to_string(),
+                        port_id: "their_port".to_string(),
+                    }],
+                },
+            )
+            .unwrap();
+        deps
+    }
+
+    #[test]
+    fn channel_open_validates_order_and_version() {
+        let mut deps = mock_dependencies_with_subscriber();
+
+        let res = ibc_channel_open(
+            deps.as_mut(),
+            mock_env(),
+            mock_ibc_channel_open_init(CHANNEL_ID, IbcOrder::Ordered, IBC_VERSION),
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains(&ContractError::InvalidIbcChannelOrder.to_string()));
+
+        let res = ibc_channel_open(
+            deps.as_mut(),
+            mock_env(),
+            mock_ibc_channel_open_init(CHANNEL_ID, IbcOrder::Unordered, "some-other-version"),
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains(&ContractError::InvalidIbcVersion.to_string()));
+
+        let res = ibc_channel_open(
+            deps.as_mut(),
+            mock_env(),
+            mock_ibc_channel_open_init(CHANNEL_ID, IbcOrder::Unordered, IBC_VERSION),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn channel_open_requires_allowed_subscriber() {
+        let mut deps = mock_dependencies_with_subscriber();
+
+        let mut other_port = mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, IBC_VERSION);
+        other_port.counterparty_endpoint.port_id = "other_port".to_string();
+        let mut other_connection = mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, IBC_VERSION);
+        other_connection.connection_id = "connection-3".to_string();
+
+        for channel in [other_port, other_connection] {
+            let res = ibc_channel_open(
+                deps.as_mut(),
+                mock_env(),
+                IbcChannelOpenMsg::new_init(channel.clone()),
+            );
+            assert!(res
+                .unwrap_err()
+                .to_string()
+                .contains(&ContractError::IbcSubscriberNotAllowed.to_string()));
+
+            let res = ibc_channel_connect(
+                deps.as_mut(),
+                mock_env(),
+                IbcChannelConnectMsg::new_ack(channel, IBC_VERSION),
+            );
+            assert!(res
+                .unwrap_err()
+                .to_string()
+                .contains(&ContractError::IbcSubscriberNotAllowed.to_string()));
+            assert!(!OPEN_CHANNELS.has(deps.as_ref().storage, CHANNEL_ID));
+        }
+    }
+
+    #[test]
+    fn channel_connect_and_close_track_open_channels() {
+        let mut deps = mock_dependencies_with_subscriber();
+
+        ibc_channel_connect(
+            deps.as_mut(),
+            mock_env(),
+            mock_ibc_channel_connect_ack(CHANNEL_ID, IbcOrder::Unordered, IBC_VERSION),
+        )
+        .unwrap();
+        assert!(OPEN_CHANNELS.has(deps.as_ref().storage, CHANNEL_ID));
+
+        ibc_channel_close(
+            deps.as_mut(),
+            mock_env(),
+            mock_ibc_channel_close_init(CHANNEL_ID, IbcOrder::Unordered, IBC_VERSION),
+        )
+        .unwrap();
+        assert!(!OPEN_CHANNELS.has(deps.as_ref().storage, CHANNEL_ID));
+    }
+
+    #[test]
+    fn subscribe_and_unsubscribe() {
+        let mut deps = mock_dependencies();
+        let message_id = CrossChainId {
+            chain: "mock-chain".parse().unwrap(),
+            id: "hash:0".parse().unwrap(),
+        };
+
+        let subscribe = SubscriptionPacket::Subscribe {
+            message_ids: vec![message_id.clone()],
+        };
+        for _ in 0..2 {
+            let res = ibc_packet_receive(
+                deps.as_mut(),
+                mock_env(),
+                mock_ibc_packet_recv(CHANNEL_ID, &subscribe).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(
+                res.acknowledgement,
+                to_binary(&SubscriptionAck::Ok).unwrap()
+            );
+        }
+
+        // subscribing twice from the same channel is a no-op
+        let subscription = SUBSCRIPTIONS
+            .load(deps.as_ref().storage, message_id.clone())
+            .unwrap();
+        assert_eq!(subscription.channels, vec![CHANNEL_ID.to_string()]);
+        assert_eq!(subscription.last_status, None);
+
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            mock_ibc_packet_recv(
+                CHANNEL_ID,
+                &SubscriptionPacket::Unsubscribe {
+                    message_ids: vec![message_id.clone()],
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.acknowledgement,
+            to_binary(&SubscriptionAck::Ok).unwrap()
+        );
+        assert!(!SUBSCRIPTIONS.has(deps.as_ref().storage, message_id));
+    }
+
+    #[test]
+    fn invalid_subscription_packet_is_acknowledged_with_error() {
+        let mut deps = mock_dependencies();
+
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            mock_ibc_packet_recv(CHANNEL_ID, &"not a subscription packet").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.acknowledgement,
+            to_binary(&SubscriptionAck::Error(
+                ContractError::InvalidSubscriptionPacket.to_string()
+            ))
+            .unwrap()
+        );
+    }
+}
@@ -1,10 +1,21 @@
 use connection_router::state::{CrossChainId, Message};
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_schema::cw_serde;
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub verifier_address: String,
     pub router_address: String,
+    /// Contracts on other chains that may open a channel to subscribe to status updates.
+    /// Every status update is sent to each subscribed channel, so subscriptions are not open to everyone
+    #[serde(default)]
+    pub ibc_subscribers: Vec<IbcSubscriber>,
+}
+
+/// Counterparty of a subscription channel, identified by its port on the other end of the IBC connection
+#[cw_serde]
+pub struct IbcSubscriber {
+    pub connection_id: String,
+    pub port_id: String,
 }
 
 #[cw_serde]
@@ -16,9 +27,37 @@ pub enum ExecuteMsg {
     RouteMessages(Vec<Message>),
 }
 
+/// Packets that contracts on other chains send over an IBC channel with the gateway to manage their subscriptions
 #[cw_serde]
-#[derive(QueryResponses)]
-pub enum QueryMsg {
-    #[returns(Vec<Message>)]
-    GetMessages { message_ids: Vec<CrossChainId> },
+pub enum SubscriptionPacket {
+    /// Get notified on the channel whenever the verification status of any of the messages changes
+    Subscribe {
+        message_ids: Vec<CrossChainId>,
+    },
+    Unsubscribe {
+        message_ids: Vec<CrossChainId>,
+    },
 }
+
+/// Acknowledgement of a SubscriptionPacket
+#[cw_serde]
+pub enum SubscriptionAck {
+    Ok,
+    Error(String),
+}
+
+/// Packet sent to the subscribers of a message when the gateway observes a change of its verification status.
+/// Status updates are best effort, they are not resent if the packet times out
+#[cw_serde]
+pub struct StatusUpdatePacket {
+    pub message_id: CrossChainId,
+    pub status: VerificationStatus,
+}
+
+#[cw_serde]
+pub enum VerificationStatus {
+    Unverified,
+    Verified,
+}
+
+pub use connection_router::msg::GatewayQueryMsg as QueryMsg;
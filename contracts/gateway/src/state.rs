@@ -1,4 +1,5 @@
 use crate::error::ContractError;
+use crate::msg::{IbcSubscriber, VerificationStatus};
 use connection_router::state::{CrossChainId, Message};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Storage};
@@ -14,17 +15,39 @@ pub trait Store {
         key: CrossChainId,
         value: &Message,
     ) -> Result<(), ContractError>;
+    fn load_subscription(&self, key: CrossChainId) -> Result<Option<Subscription>, ContractError>;
+    fn save_subscription(
+        &mut self,
+        key: CrossChainId,
+        value: &Subscription,
+    ) -> Result<(), ContractError>;
+    fn is_channel_open(&self, channel_id: &str) -> Result<bool, ContractError>;
 }
 
 #[cw_serde]
 pub struct Config {
     pub verifier: Addr,
     pub router: Addr,
+    #[serde(default)]
+    pub ibc_subscribers: Vec<IbcSubscriber>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const OUTGOING_MESSAGES: Map<CrossChainId, Message> = Map::new("outgoing_messages");
 
+#[cw_serde]
+#[derive(Default)]
+pub struct Subscription {
+    // ibc channels of the subscribers of the message
+    pub channels: Vec<String>,
+    // status the subscribers were last notified of, if any
+    pub last_status: Option<VerificationStatus>,
+}
+
+pub const SUBSCRIPTIONS: Map<CrossChainId, Subscription> = Map::new("subscriptions");
+// subscribers of closed channels are skipped, so status updates never fail because of a closed channel
+pub const OPEN_CHANNELS: Map<&str, ()> = Map::new("open_channels");
+
 pub struct GatewayStore<'a> {
     pub storage: &'a mut dyn Storage,
 }
@@ -45,4 +68,27 @@ impl Store for GatewayStore<'_> {
             .save(self.storage, key, value)
             .change_context(ContractError::StoreOutgoingMessage)
     }
+
+    fn load_subscription(&self, key: CrossChainId) -> Result<Option<Subscription>, ContractError> {
+        SUBSCRIPTIONS
+            .may_load(self.storage, key)
+            .change_context(ContractError::LoadSubscription)
+    }
+
+    fn save_subscription(
+        &mut self,
+        key: CrossChainId,
+        value: &Subscription,
+    ) -> Result<(), ContractError> {
+        SUBSCRIPTIONS
+            .save(self.storage, key, value)
+            .change_context(ContractError::StoreSubscription)
+    }
+
+    fn is_channel_open(&self, channel_id: &str) -> Result<bool, ContractError> {
+        OPEN_CHANNELS
+            .may_load(self.storage, channel_id)
+            .map(|channel| channel.is_some())
+            .change_context(ContractError::LoadChannel)
+    }
 }
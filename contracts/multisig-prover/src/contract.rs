@@ -1,24 +1,30 @@
+use axelar_wasm_std::{ensure_state_versions, versioning};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, Storage,
 };
+use error_stack::Report;
 
 use std::str::FromStr;
 
 use connection_router::state::ChainName;
 
 use crate::{
+    encoding,
     error::ContractError,
     execute,
     msg::ExecuteMsg,
-    msg::{InstantiateMsg, QueryMsg},
+    msg::{InstantiateMsg, MigrateMsg, QueryMsg},
     query, reply,
-    state::{Config, CONFIG},
+    state::{backfill_worker_set_generations, Config, CONFIG, STATE_VERSIONS},
 };
 
 pub const START_MULTISIG_REPLY_ID: u64 = 1;
 
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -27,10 +33,15 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
     let admin = deps.api.addr_validate(&msg.admin_address)?;
+    let governance = deps.api.addr_validate(&msg.governance_address)?;
     let gateway = deps.api.addr_validate(&msg.gateway_address)?;
     let multisig = deps.api.addr_validate(&msg.multisig_address)?;
     let service_registry = deps.api.addr_validate(&msg.service_registry_address)?;
     let voting_verifier = deps.api.addr_validate(&msg.voting_verifier_address)?;
+    if let Some(domain) = &msg.eip712_domain {
+        encoding::validate_eip712_domain(domain, msg.encoder)?;
+    }
+    encoding::validate_key_type(msg.key_type, msg.encoder)?;
 
     let config = Config {
         admin,
@@ -46,26 +57,134 @@ pub fn instantiate(
         worker_set_diff_threshold: msg.worker_set_diff_threshold,
         encoder: msg.encoder,
         key_type: msg.key_type,
+        safe_mode: msg.safe_mode,
+        embed_fee_payer: msg.embed_fee_payer,
+        digest_scheme: msg.digest_scheme,
+        eip712_domain: msg.eip712_domain,
+        governance: Some(governance),
+        retention_policy: None,
+        max_operators: msg.max_operators,
     };
 
     CONFIG.save(deps.storage, &config)?;
 
+    versioning::init_versions(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &STATE_VERSIONS,
+    )?;
+
     Ok(Response::default())
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    msg: MigrateMsg,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    versioning::migrate_state(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &STATE_VERSIONS,
+        migrate_namespace,
+    )?;
+
+    if let Some(governance_address) = msg.governance_address {
+        let governance = deps.api.addr_validate(&governance_address)?;
+        CONFIG.update(deps.storage, |mut config| -> StdResult<Config> {
+            config.governance = Some(governance);
+            Ok(config)
+        })?;
+    }
+
+    match msg.config_update {
+        Some(config_update) => execute::update_config(deps, config_update)
+            .map_err(axelar_wasm_std::ContractError::from),
+        None => Ok(Response::default()),
+    }
+}
+
+/// Brings the stored data of a namespace from its stored schema version to the one in STATE_VERSIONS
+fn migrate_namespace(
+    storage: &mut dyn Storage,
+    namespace: &str,
+    stored: Option<u64>,
+) -> Result<(), axelar_wasm_std::ContractError> {
+    match (namespace, stored) {
+        // worker sets only get a generation once they become current, so the current one needs it backfilled
+        ("worker_set_generations", None) => Ok(backfill_worker_set_generations(storage)?),
+        // state written before versions were recorded is still readable thanks to serde defaults
+        (_, None) => Ok(()),
+        (_, Some(stored)) => Err(Report::new(versioning::Error::MissingMigration {
+            namespace: namespace.to_string(),
+            stored,
+        })
+        .into()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
+    ensure_state_versions!(deps.storage, &STATE_VERSIONS);
+
     match msg {
         ExecuteMsg::ConstructProof { message_ids } => {
             execute::construct_proof(deps, env, message_ids)
         }
         ExecuteMsg::UpdateWorkerSet {} => execute::update_worker_set(deps, env),
         ExecuteMsg::ConfirmWorkerSet {} => execute::confirm_worker_set(deps),
+        ExecuteMsg::ConfirmExecutedMessages {
+            multisig_session_id,
+            message_ids,
+        } => {
+            execute::require_admin(&deps, info.sender)?;
+            execute::confirm_executed_messages(deps, env, multisig_session_id, message_ids)
+        }
+        ExecuteMsg::RebatchUnexecutedMessages {
+            multisig_session_id,
+        } => execute::rebatch_unexecuted_messages(deps, env, multisig_session_id),
+        ExecuteMsg::RetryProof { batch_id } => execute::retry_proof(deps, env, batch_id),
+        ExecuteMsg::ConfirmSigningSession {
+            multisig_session_id,
+        } => {
+            execute::require_admin(&deps, info.sender)?;
+            execute::confirm_signing_session(deps, multisig_session_id)
+        }
+        ExecuteMsg::CancelSigningSession {
+            multisig_session_id,
+        } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::cancel_signing_session(deps, multisig_session_id)
+        }
+        ExecuteMsg::UpdateConfig { config_update } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::update_config(deps, config_update)
+        }
+        ExecuteMsg::UpdateRetentionPolicy { retention_policy } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::update_retention_policy(deps, retention_policy)
+        }
+        ExecuteMsg::PruneProofs { limit } => execute::prune_proofs(deps, env, limit),
+        ExecuteMsg::SigningCompleted { session_id } => {
+            execute::require_multisig(&deps, info.sender)?;
+            execute::signing_completed(deps, env, session_id)
+        }
+        ExecuteMsg::RegisterRecoveryWorkerSet { worker_set } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::register_recovery_worker_set(deps, worker_set)
+        }
+        ExecuteMsg::ActivateRecovery {} => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::activate_recovery(deps, env)
+        }
     }
     .map_err(axelar_wasm_std::ContractError::from)
 }
@@ -73,23 +192,43 @@ pub fn execute(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     reply: Reply,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
     match reply.id {
-        START_MULTISIG_REPLY_ID => reply::start_multisig_reply(deps, reply),
+        START_MULTISIG_REPLY_ID => reply::start_multisig_reply(deps, env, reply),
         _ => unreachable!("unknown reply ID"),
     }
     .map_err(axelar_wasm_std::ContractError::from)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetProof {
             multisig_session_id,
-        } => to_binary(&query::get_proof(deps, multisig_session_id)?),
+            fee_payer,
+            include_signing_progress,
+        } => to_binary(&query::get_proof(
+            deps,
+            multisig_session_id,
+            fee_payer,
+            include_signing_progress,
+        )?),
         QueryMsg::GetWorkerSet {} => to_binary(&query::get_worker_set(deps)?),
+        QueryMsg::GetWorkerSetGeneration { worker_set_id } => {
+            to_binary(&query::get_worker_set_generation(deps, worker_set_id)?)
+        }
+        QueryMsg::GetUnexecutedMessages {
+            multisig_session_id,
+        } => to_binary(&query::get_unexecuted_messages(deps, multisig_session_id)?),
+        QueryMsg::GetBatchingStatus {} => to_binary(&query::get_batching_status(deps)?),
+        QueryMsg::GetRecoveryWorkerSet {} => to_binary(&query::get_recovery_worker_set(deps)?),
+        QueryMsg::BatchByMessageId { message_id } => {
+            to_binary(&query::batch_by_message_id(deps, message_id)?)
+        }
+        QueryMsg::WorkerSetDiff {} => to_binary(&query::worker_set_diff(deps, env)?),
+        QueryMsg::BatchMetrics { batch_id } => to_binary(&query::batch_metrics(deps, batch_id)?),
     }
 }
 
@@ -97,23 +236,33 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 mod tests {
 
     use anyhow::Error;
-    use axelar_wasm_std::Threshold;
+    use axelar_wasm_std::{nonempty, Threshold};
     use connection_router::state::CrossChainId;
     use cosmwasm_std::{
         testing::{mock_dependencies, mock_env, mock_info},
-        Addr, Fraction, Uint256, Uint64,
+        Addr, Fraction, HexBinary, Uint256, Uint64,
     };
-    use cw_multi_test::{AppResponse, Executor};
-    use multisig::{msg::Signer, worker_set::WorkerSet};
+    use cw_multi_test::{next_block, AppResponse, Executor};
+    use multisig::{msg::Signer, types::RetentionPolicy, worker_set::WorkerSet};
 
     use crate::{
-        encoding::Encoder,
-        msg::{GetProofResponse, ProofStatus},
+        encoding::{Eip712Domain, Encoder},
+        msg::{
+            BatchByMessageIdResponse, BatchingStatus, ConfigUpdate, GetProofResponse, ProofStatus,
+            SignerProgress, WorkerSetDiff,
+        },
+        state::{
+            BatchMetrics, BlockTime, WorkerSetGeneration, CURRENT_WORKER_SET,
+            WORKER_SET_GENERATIONS,
+        },
         test::{
             mocks,
-            multicontract::{setup_test_case, TestCaseConfig},
+            multicontract::{
+                setup_safe_mode_test_case, setup_test_case, TestCaseConfig, GOVERNANCE,
+            },
             test_data::{self, TestOperator},
         },
+        types::{BatchId, CommandType},
     };
 
     use crate::contract::execute::should_update_worker_set;
@@ -177,6 +326,60 @@ mod tests {
             test_case.prover_address.clone(),
             &QueryMsg::GetProof {
                 multisig_session_id,
+                fee_payer: None,
+                include_signing_progress: false,
+            },
+        )
+    }
+
+    fn execute_confirm_executed_messages(
+        test_case: &mut TestCaseConfig,
+        sender: Addr,
+        message_ids: Vec<CrossChainId>,
+    ) -> Result<AppResponse, Error> {
+        let msg = ExecuteMsg::ConfirmExecutedMessages {
+            multisig_session_id: MULTISIG_SESSION_ID,
+            message_ids,
+        };
+        test_case
+            .app
+            .execute_contract(sender, test_case.prover_address.clone(), &msg, &[])
+    }
+
+    fn execute_rebatch_unexecuted_messages(
+        test_case: &mut TestCaseConfig,
+    ) -> Result<AppResponse, Error> {
+        let msg = ExecuteMsg::RebatchUnexecutedMessages {
+            multisig_session_id: MULTISIG_SESSION_ID,
+        };
+        test_case.app.execute_contract(
+            Addr::unchecked(RELAYER),
+            test_case.prover_address.clone(),
+            &msg,
+            &[],
+        )
+    }
+
+    fn execute_retry_proof(
+        test_case: &mut TestCaseConfig,
+        batch_id: BatchId,
+    ) -> Result<AppResponse, Error> {
+        let msg = ExecuteMsg::RetryProof { batch_id };
+        test_case.app.execute_contract(
+            Addr::unchecked(RELAYER),
+            test_case.prover_address.clone(),
+            &msg,
+            &[],
+        )
+    }
+
+    fn query_get_unexecuted_messages(
+        test_case: &mut TestCaseConfig,
+    ) -> StdResult<Vec<CrossChainId>> {
+        test_case.app.wrap().query_wasm_smart(
+            test_case.prover_address.clone(),
+            &QueryMsg::GetUnexecutedMessages {
+                multisig_session_id: MULTISIG_SESSION_ID,
             },
         )
     }
@@ -188,6 +391,71 @@ mod tests {
             .query_wasm_smart(test_case.prover_address.clone(), &QueryMsg::GetWorkerSet {})
     }
 
+    fn query_get_worker_set_generation(
+        test_case: &mut TestCaseConfig,
+        worker_set_id: String,
+    ) -> StdResult<WorkerSetGeneration> {
+        test_case.app.wrap().query_wasm_smart(
+            test_case.prover_address.clone(),
+            &QueryMsg::GetWorkerSetGeneration { worker_set_id },
+        )
+    }
+
+    fn execute_register_recovery_worker_set(
+        test_case: &mut TestCaseConfig,
+        sender: Addr,
+        worker_set: WorkerSet,
+    ) -> Result<AppResponse, Error> {
+        let msg = ExecuteMsg::RegisterRecoveryWorkerSet { worker_set };
+        test_case
+            .app
+            .execute_contract(sender, test_case.prover_address.clone(), &msg, &[])
+    }
+
+    fn execute_activate_recovery(test_case: &mut TestCaseConfig) -> Result<AppResponse, Error> {
+        let msg = ExecuteMsg::ActivateRecovery {};
+        test_case.app.execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            test_case.prover_address.clone(),
+            &msg,
+            &[],
+        )
+    }
+
+    fn confirm_on_gateway(test_case: &mut TestCaseConfig, worker_set: &WorkerSet) {
+        test_case
+            .app
+            .execute_contract(
+                Addr::unchecked(RELAYER),
+                test_case.voting_verifier_address.clone(),
+                &voting_verifier::msg::ExecuteMsg::VerifyWorkerSet {
+                    message_id: "ethereum:00".parse().unwrap(),
+                    new_operators: crate::encoding::make_operators(
+                        worker_set.clone(),
+                        Encoder::Abi,
+                    ),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn query_get_recovery_worker_set(
+        test_case: &mut TestCaseConfig,
+    ) -> StdResult<Option<WorkerSet>> {
+        test_case.app.wrap().query_wasm_smart(
+            test_case.prover_address.clone(),
+            &QueryMsg::GetRecoveryWorkerSet {},
+        )
+    }
+
+    fn query_get_batching_status(test_case: &mut TestCaseConfig) -> StdResult<BatchingStatus> {
+        test_case.app.wrap().query_wasm_smart(
+            test_case.prover_address.clone(),
+            &QueryMsg::GetBatchingStatus {},
+        )
+    }
+
     #[test]
     fn test_instantiation() {
         let instantiator = "instantiator";
@@ -205,13 +473,14 @@ mod tests {
         .try_into()
         .unwrap();
         let service_name = "service_name";
-        for encoding in vec![Encoder::Abi, Encoder::Bcs] {
+        for encoding in [Encoder::Abi, Encoder::Bcs, Encoder::AptosBcs] {
             let mut deps = mock_dependencies();
             let info = mock_info(&instantiator, &[]);
             let env = mock_env();
 
             let msg = InstantiateMsg {
                 admin_address: admin.to_string(),
+                governance_address: "governance".to_string(),
                 gateway_address: gateway_address.to_string(),
                 multisig_address: multisig_address.to_string(),
                 voting_verifier_address: voting_verifier_address.to_string(),
@@ -223,6 +492,11 @@ mod tests {
                 worker_set_diff_threshold: 0,
                 encoder: encoding.clone(),
                 key_type: multisig::key::KeyType::Ecdsa,
+                safe_mode: false,
+                embed_fee_payer: false,
+                digest_scheme: None,
+                eip712_domain: None,
+                max_operators: None,
             };
 
             let res = instantiate(deps.as_mut(), env, info, msg);
@@ -247,6 +521,348 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_instantiation_with_eip712_domain() {
+        let domain = Eip712Domain {
+            name: "AxelarGateway".to_string(),
+            version: "1".to_string(),
+            verifying_contract: "0x4F4495243837681061C4743b74B3eEdf548D56A5".to_string(),
+        };
+
+        for (encoder, succeeds) in [
+            (Encoder::Abi, true),
+            (Encoder::Bcs, false),
+            (Encoder::AptosBcs, false),
+            (Encoder::Solana, false),
+            (Encoder::StellarXdr, false),
+        ] {
+            let mut deps = mock_dependencies();
+            let msg = InstantiateMsg {
+                admin_address: "admin".to_string(),
+                governance_address: "governance".to_string(),
+                gateway_address: "gateway_address".to_string(),
+                multisig_address: "multisig_address".to_string(),
+                voting_verifier_address: "voting_verifier".to_string(),
+                service_registry_address: "service_registry_address".to_string(),
+                destination_chain_id: Uint256::one(),
+                signing_threshold: test_data::threshold(),
+                service_name: "service_name".to_string(),
+                chain_name: "Ethereum".to_string(),
+                worker_set_diff_threshold: 0,
+                encoder,
+                key_type: multisig::key::KeyType::Ecdsa,
+                safe_mode: false,
+                embed_fee_payer: false,
+                digest_scheme: None,
+                eip712_domain: Some(domain.clone()),
+                max_operators: None,
+            };
+
+            let res = instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("instantiator", &[]),
+                msg,
+            );
+
+            if succeeds {
+                assert!(res.is_ok());
+                assert_eq!(
+                    CONFIG.load(deps.as_ref().storage).unwrap().eip712_domain,
+                    Some(domain.clone())
+                );
+            } else {
+                assert_eq!(
+                    res.unwrap_err().to_string(),
+                    axelar_wasm_std::ContractError::from(ContractError::InvalidEip712Domain {
+                        reason: "typed data signing is only supported by evm gateways".to_string()
+                    })
+                    .to_string()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_instantiation_with_unsupported_key_type() {
+        for (key_type, succeeds) in [
+            (multisig::key::KeyType::Ecdsa, false),
+            (multisig::key::KeyType::Ed25519, true),
+        ] {
+            let mut deps = mock_dependencies();
+            let msg = InstantiateMsg {
+                admin_address: "admin".to_string(),
+                governance_address: "governance".to_string(),
+                gateway_address: "gateway_address".to_string(),
+                multisig_address: "multisig_address".to_string(),
+                voting_verifier_address: "voting_verifier".to_string(),
+                service_registry_address: "service_registry_address".to_string(),
+                destination_chain_id: Uint256::one(),
+                signing_threshold: test_data::threshold(),
+                service_name: "service_name".to_string(),
+                chain_name: "solana".to_string(),
+                worker_set_diff_threshold: 0,
+                encoder: Encoder::Solana,
+                key_type,
+                safe_mode: false,
+                embed_fee_payer: false,
+                digest_scheme: None,
+                eip712_domain: None,
+                max_operators: None,
+            };
+
+            let res = instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("instantiator", &[]),
+                msg,
+            );
+
+            if succeeds {
+                assert!(res.is_ok());
+            } else {
+                assert_eq!(
+                    res.unwrap_err().to_string(),
+                    axelar_wasm_std::ContractError::from(ContractError::InvalidKeyType {
+                        reason: "solana gateways only support ed25519 signers".to_string()
+                    })
+                    .to_string()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_config() {
+        let mut test_case = setup_test_case();
+
+        let msg = ExecuteMsg::UpdateConfig {
+            config_update: ConfigUpdate {
+                destination_chain_id: Some(Uint256::from(5u128)),
+                service_name: Some("amplifier".to_string()),
+                ..ConfigUpdate::default()
+            },
+        };
+
+        let res = test_case.app.execute_contract(
+            test_case.admin.clone(),
+            test_case.prover_address.clone(),
+            &msg,
+            &[],
+        );
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+
+        let res = test_case
+            .app
+            .execute_contract(
+                Addr::unchecked(GOVERNANCE),
+                test_case.prover_address.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|event| event.ty == "wasm-config_updated"));
+
+        let config: Config = cosmwasm_std::from_slice(
+            &test_case
+                .app
+                .wrap()
+                .query_wasm_raw(test_case.prover_address.clone(), b"config".as_slice())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(config.destination_chain_id, Uint256::from(5u128));
+        assert_eq!(config.service_name, "amplifier");
+        assert_eq!(config.chain_name.to_string(), "ganache-0");
+    }
+
+    #[test]
+    fn test_update_worker_set_caps_operators() {
+        let mut test_case = setup_test_case();
+
+        let update_max_operators = |test_case: &mut TestCaseConfig, max_operators: u64| {
+            test_case
+                .app
+                .execute_contract(
+                    Addr::unchecked(GOVERNANCE),
+                    test_case.prover_address.clone(),
+                    &ExecuteMsg::UpdateConfig {
+                        config_update: ConfigUpdate {
+                            max_operators: Some(Some(max_operators.try_into().unwrap())),
+                            ..ConfigUpdate::default()
+                        },
+                    },
+                    &[],
+                )
+                .unwrap();
+        };
+
+        // 3 of the 5 workers hold less than the quorum of all workers
+        update_max_operators(&mut test_case, 3);
+        let res = execute_update_worker_set(&mut test_case);
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::OperatorCapBelowQuorum).to_string()
+        );
+
+        update_max_operators(&mut test_case, 4);
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let worker_set = query_get_worker_set(&mut test_case).unwrap();
+        let mut expected_operators: Vec<_> = test_data::operators()
+            .into_iter()
+            .map(|operator| operator.address.to_string())
+            .collect();
+        expected_operators.sort();
+        expected_operators.truncate(4);
+        assert_eq!(
+            worker_set.signers.into_keys().collect::<Vec<_>>(),
+            expected_operators
+        );
+        // the kept operators need the quorum of all 5 workers, not the signing threshold of their own weight
+        assert_eq!(worker_set.threshold, Uint256::from(4u128));
+    }
+
+    #[test]
+    fn test_update_config_removes_operator_cap() {
+        let mut test_case = setup_test_case();
+
+        let update_max_operators =
+            |test_case: &mut TestCaseConfig, max_operators: Option<nonempty::Uint64>| {
+                test_case
+                    .app
+                    .execute_contract(
+                        Addr::unchecked(GOVERNANCE),
+                        test_case.prover_address.clone(),
+                        &ExecuteMsg::UpdateConfig {
+                            config_update: ConfigUpdate {
+                                max_operators: Some(max_operators),
+                                ..ConfigUpdate::default()
+                            },
+                        },
+                        &[],
+                    )
+                    .unwrap();
+            };
+
+        update_max_operators(&mut test_case, Some(3u64.try_into().unwrap()));
+        assert!(execute_update_worker_set(&mut test_case).is_err());
+
+        update_max_operators(&mut test_case, None);
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let worker_set = query_get_worker_set(&mut test_case).unwrap();
+        assert_eq!(worker_set.signers.len(), test_data::operators().len());
+
+        // a missing field leaves the cap unchanged, null removes it
+        assert_eq!(
+            cosmwasm_std::from_slice::<ConfigUpdate>(br#"{}"#)
+                .unwrap()
+                .max_operators,
+            None
+        );
+        assert_eq!(
+            cosmwasm_std::from_slice::<ConfigUpdate>(br#"{"max_operators":null}"#)
+                .unwrap()
+                .max_operators,
+            Some(None)
+        );
+    }
+
+    #[test]
+    fn test_migrate_sets_governance_and_updates_config() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin_address: "admin".to_string(),
+            governance_address: "governance".to_string(),
+            gateway_address: "gateway_address".to_string(),
+            multisig_address: "multisig_address".to_string(),
+            voting_verifier_address: "voting_verifier".to_string(),
+            service_registry_address: "service_registry_address".to_string(),
+            destination_chain_id: Uint256::one(),
+            signing_threshold: test_data::threshold(),
+            service_name: "service_name".to_string(),
+            chain_name: "Ethereum".to_string(),
+            worker_set_diff_threshold: 0,
+            encoder: Encoder::Abi,
+            key_type: multisig::key::KeyType::Ecdsa,
+            safe_mode: false,
+            embed_fee_payer: false,
+            digest_scheme: None,
+            eip712_domain: Some(Eip712Domain {
+                name: "AxelarGateway".to_string(),
+                version: "1".to_string(),
+                verifying_contract: "0x4F4495243837681061C4743b74B3eEdf548D56A5".to_string(),
+            }),
+            max_operators: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("instantiator", &[]),
+            msg,
+        )
+        .unwrap();
+
+        // provers instantiated before governance was introduced have none
+        CONFIG
+            .update(deps.as_mut().storage, |mut config| -> StdResult<Config> {
+                config.governance = None;
+                Ok(config)
+            })
+            .unwrap();
+
+        let res = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                governance_address: Some("new_governance".to_string()),
+                config_update: Some(ConfigUpdate {
+                    encoder: Some(Encoder::Bcs),
+                    ..ConfigUpdate::default()
+                }),
+            },
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::InvalidEip712Domain {
+                reason: "typed data signing is only supported by evm gateways".to_string()
+            })
+            .to_string()
+        );
+
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                governance_address: Some("new_governance".to_string()),
+                config_update: Some(ConfigUpdate {
+                    gateway_address: Some("new_gateway_address".to_string()),
+                    ..ConfigUpdate::default()
+                }),
+            },
+        )
+        .unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.governance, Some(Addr::unchecked("new_governance")));
+        assert_eq!(config.gateway, Addr::unchecked("new_gateway_address"));
+        assert_eq!(config.encoder, Encoder::Abi);
+    }
+
     fn test_operators_to_worker_set(operators: Vec<TestOperator>, nonce: u64) -> WorkerSet {
         let total_weight: Uint256 = operators
             .iter()
@@ -291,6 +907,50 @@ mod tests {
         assert_eq!(worker_set, expected_worker_set);
     }
 
+    #[test]
+    fn test_query_worker_set_diff() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let query_diff = |test_case: &mut TestCaseConfig| -> WorkerSetDiff {
+            test_case
+                .app
+                .wrap()
+                .query_wasm_smart(test_case.prover_address.clone(), &QueryMsg::WorkerSetDiff)
+                .unwrap()
+        };
+
+        assert_eq!(
+            query_diff(&mut test_case),
+            WorkerSetDiff {
+                added: vec![],
+                removed: vec![],
+                weight_changes: vec![],
+                rotation_due: false,
+            }
+        );
+
+        let mut workers = test_data::operators();
+        let removed = workers.pop().unwrap();
+        mocks::service_registry::set_active_workers(
+            &mut test_case.app,
+            test_case.service_registry_address.clone(),
+            workers,
+        );
+
+        let diff = query_diff(&mut test_case);
+        assert_eq!(diff.added, vec![]);
+        assert_eq!(
+            diff.removed
+                .into_iter()
+                .map(|signer| signer.address)
+                .collect::<Vec<_>>(),
+            vec![removed.address]
+        );
+        assert_eq!(diff.weight_changes, vec![]);
+        assert!(diff.rotation_due);
+    }
+
     #[test]
     fn test_update_worker_set_remove_one() {
         let mut test_case = setup_test_case();
@@ -451,18 +1111,68 @@ mod tests {
             test_operators_to_worker_set(new_worker_set, test_case.app.block_info().height);
 
         assert_eq!(worker_set, expected_worker_set);
+
+        // the confirmation is announced, so the switch to the new worker set can be tracked
+        assert!(res.unwrap().events.iter().any(|event| {
+            event.ty == "wasm-worker_set_confirmed"
+                && event.attributes.iter().any(|attribute| {
+                    attribute.key == "worker_set_id" && attribute.value == expected_worker_set.id()
+                })
+        }));
+
+        // both the initial and the confirmed worker set are kept by key generation
+        let initial_worker_set =
+            test_operators_to_worker_set(test_data::operators(), test_case.app.block_info().height);
+        assert_eq!(
+            query_get_worker_set_generation(&mut test_case, initial_worker_set.id()).unwrap(),
+            WorkerSetGeneration {
+                generation: 1,
+                worker_set: initial_worker_set,
+            }
+        );
+        assert_eq!(
+            query_get_worker_set_generation(&mut test_case, expected_worker_set.id()).unwrap(),
+            WorkerSetGeneration {
+                generation: 2,
+                worker_set: expected_worker_set,
+            }
+        );
+        assert!(query_get_worker_set_generation(&mut test_case, "unknown".to_string()).is_err());
     }
 
     #[test]
-    fn test_confirm_worker_set_unconfirmed() {
-        let mut test_case = setup_test_case();
-        let res = execute_update_worker_set(&mut test_case);
+    fn migrate_backfills_generation_of_current_worker_set() {
+        let mut deps = mock_dependencies();
+        let worker_set = test_data::new_worker_set();
+        CURRENT_WORKER_SET
+            .save(deps.as_mut().storage, &worker_set)
+            .unwrap();
 
-        assert!(res.is_ok());
+        migrate_namespace(deps.as_mut().storage, "worker_set_generations", None).unwrap();
+        // backfilling again doesn't give the worker set a new generation
+        migrate_namespace(deps.as_mut().storage, "worker_set_generations", None).unwrap();
 
-        let mut new_worker_set = test_data::operators();
-        new_worker_set.pop();
-        mocks::service_registry::set_active_workers(
+        assert_eq!(
+            WORKER_SET_GENERATIONS
+                .load(deps.as_ref().storage, &worker_set.id())
+                .unwrap(),
+            WorkerSetGeneration {
+                generation: 1,
+                worker_set,
+            }
+        );
+    }
+
+    #[test]
+    fn test_confirm_worker_set_unconfirmed() {
+        let mut test_case = setup_test_case();
+        let res = execute_update_worker_set(&mut test_case);
+
+        assert!(res.is_ok());
+
+        let mut new_worker_set = test_data::operators();
+        new_worker_set.pop();
+        mocks::service_registry::set_active_workers(
             &mut test_case.app,
             test_case.service_registry_address.clone(),
             new_worker_set.clone(),
@@ -557,14 +1267,432 @@ mod tests {
 
         assert_eq!(res.multisig_session_id, MULTISIG_SESSION_ID);
         assert_eq!(res.message_ids.len(), 1);
+        assert_eq!(
+            res.worker_set_hash,
+            Some(query_get_worker_set(&mut test_case).unwrap().hash())
+        );
         match res.status {
             ProofStatus::Completed { execute_data } => {
                 assert_eq!(execute_data, test_data::execute_data());
+
+                let estimate = res.execution_estimate.unwrap();
+                assert_eq!(estimate.calldata_size, execute_data.len() as u64);
+                assert!(estimate.gas.is_some());
             }
             _ => panic!("Expected proof status to be completed"), // multisig mock will always return completed multisig
         }
     }
 
+    #[test]
+    fn test_query_batch_by_message_id() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let message_id = test_data::messages().remove(0).cc_id;
+        let query_batch = |test_case: &mut TestCaseConfig| -> Option<BatchByMessageIdResponse> {
+            test_case
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    test_case.prover_address.clone(),
+                    &QueryMsg::BatchByMessageId {
+                        message_id: message_id.clone(),
+                    },
+                )
+                .unwrap()
+        };
+
+        assert_eq!(query_batch(&mut test_case), None);
+
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let proof = query_get_proof(&mut test_case, None).unwrap();
+        let res = query_batch(&mut test_case).unwrap();
+        assert_eq!(res.multisig_session_id, MULTISIG_SESSION_ID);
+        assert_eq!(res.status, proof.status);
+        assert_eq!(
+            res.batch_id,
+            BatchId::new(
+                &test_case.prover_address,
+                test_data::destination_chain_id(),
+                &[message_id.clone()],
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_query_proof_with_fee_payer_fails_if_unsupported() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let res: StdResult<GetProofResponse> = test_case.app.wrap().query_wasm_smart(
+            test_case.prover_address.clone(),
+            &QueryMsg::GetProof {
+                multisig_session_id: MULTISIG_SESSION_ID,
+                fee_payer: Some("0x4c0a9e6a0c1b5b6d3c5d2b1e6f9a8c7d6e5f4a3b".to_string()),
+                include_signing_progress: false,
+            },
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("destination gateway does not support a fee payer"));
+    }
+
+    #[test]
+    fn test_query_proof_with_signing_progress() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let proof = query_get_proof(&mut test_case, None).unwrap();
+        assert_eq!(proof.signing_progress, None);
+
+        let proof: GetProofResponse = test_case
+            .app
+            .wrap()
+            .query_wasm_smart(
+                test_case.prover_address.clone(),
+                &QueryMsg::GetProof {
+                    multisig_session_id: MULTISIG_SESSION_ID,
+                    fee_payer: None,
+                    include_signing_progress: true,
+                },
+            )
+            .unwrap();
+
+        let operators = test_data::operators();
+        let progress = proof.signing_progress.unwrap();
+        assert_eq!(progress.quorum, test_data::quorum());
+        assert_eq!(
+            progress.signers,
+            operators
+                .iter()
+                .map(|op| SignerProgress {
+                    address: op.address.clone(),
+                    weight: op.weight,
+                    signed: op.signature.is_some(),
+                })
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            progress.signed_weight,
+            operators
+                .iter()
+                .filter(|op| op.signature.is_some())
+                .fold(Uint256::zero(), |acc, op| acc + op.weight)
+        );
+    }
+
+    #[test]
+    fn test_confirm_executed_messages() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let message_ids = test_data::messages()
+            .into_iter()
+            .map(|msg| msg.cc_id)
+            .collect::<Vec<CrossChainId>>();
+        assert_eq!(
+            query_get_unexecuted_messages(&mut test_case).unwrap(),
+            message_ids
+        );
+
+        let admin = test_case.admin.clone();
+        execute_confirm_executed_messages(&mut test_case, admin, message_ids).unwrap();
+        assert!(query_get_unexecuted_messages(&mut test_case)
+            .unwrap()
+            .is_empty());
+
+        let res = execute_rebatch_unexecuted_messages(&mut test_case);
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::NoUnexecutedMessages).to_string()
+        );
+    }
+
+    #[test]
+    fn test_query_batch_metrics() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+        let constructed = BlockTime::from(&test_case.app.block_info());
+
+        let message_ids = test_data::messages()
+            .into_iter()
+            .map(|msg| msg.cc_id)
+            .collect::<Vec<CrossChainId>>();
+        let batch_id = test_case
+            .app
+            .wrap()
+            .query_wasm_smart::<Option<BatchByMessageIdResponse>>(
+                test_case.prover_address.clone(),
+                &QueryMsg::BatchByMessageId {
+                    message_id: message_ids[0].clone(),
+                },
+            )
+            .unwrap()
+            .unwrap()
+            .batch_id;
+        let query_metrics = |test_case: &mut TestCaseConfig, batch_id: BatchId| {
+            test_case
+                .app
+                .wrap()
+                .query_wasm_smart::<Option<BatchMetrics>>(
+                    test_case.prover_address.clone(),
+                    &QueryMsg::BatchMetrics { batch_id },
+                )
+                .unwrap()
+        };
+
+        let mut expected = BatchMetrics {
+            constructed: constructed.clone(),
+            signing_started: Some(constructed),
+            quorum_reached: None,
+            executed: None,
+        };
+        assert_eq!(
+            query_metrics(&mut test_case, batch_id.clone()),
+            Some(expected.clone())
+        );
+
+        test_case.app.update_block(next_block);
+        test_case
+            .app
+            .execute_contract(
+                test_case.multisig_address.clone(),
+                test_case.prover_address.clone(),
+                &ExecuteMsg::SigningCompleted {
+                    session_id: MULTISIG_SESSION_ID,
+                },
+                &[],
+            )
+            .unwrap();
+        expected.quorum_reached = Some(BlockTime::from(&test_case.app.block_info()));
+
+        test_case.app.update_block(next_block);
+        let admin = test_case.admin.clone();
+        execute_confirm_executed_messages(&mut test_case, admin, message_ids).unwrap();
+        expected.executed = Some(BlockTime::from(&test_case.app.block_info()));
+
+        assert_eq!(query_metrics(&mut test_case, batch_id), Some(expected));
+        assert_eq!(
+            query_metrics(&mut test_case, HexBinary::from_hex("00").unwrap().into()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_confirm_executed_messages_unauthorized() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let message_ids = test_data::messages()
+            .into_iter()
+            .map(|msg| msg.cc_id)
+            .collect::<Vec<CrossChainId>>();
+        let res = execute_confirm_executed_messages(
+            &mut test_case,
+            Addr::unchecked(RELAYER),
+            message_ids,
+        );
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+    }
+
+    #[test]
+    fn test_confirm_signing_session_unauthorized() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let res = test_case.app.execute_contract(
+            Addr::unchecked(RELAYER),
+            test_case.prover_address.clone(),
+            &ExecuteMsg::ConfirmSigningSession {
+                multisig_session_id: MULTISIG_SESSION_ID,
+            },
+            &[],
+        );
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+    }
+
+    #[test]
+    fn test_cancel_signing_session() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let msg = ExecuteMsg::CancelSigningSession {
+            multisig_session_id: MULTISIG_SESSION_ID,
+        };
+        let res = test_case.app.execute_contract(
+            test_case.admin.clone(),
+            test_case.prover_address.clone(),
+            &msg,
+            &[],
+        );
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+
+        let res = test_case.app.execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            test_case.prover_address.clone(),
+            &msg,
+            &[],
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_confirm_executed_messages_not_in_batch() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let message_id = CrossChainId {
+            chain: "unknown-chain".parse().unwrap(),
+            id: "0xabc:0".parse().unwrap(),
+        };
+        let admin = test_case.admin.clone();
+        let res =
+            execute_confirm_executed_messages(&mut test_case, admin, vec![message_id.clone()]);
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::MessageNotInBatch {
+                message_id: message_id.to_string()
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_rebatch_unexecuted_messages() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let res = execute_rebatch_unexecuted_messages(&mut test_case).unwrap();
+
+        let event = res
+            .events
+            .iter()
+            .find(|event| event.ty == "wasm-proof_under_construction");
+
+        assert!(event.is_some());
+    }
+
+    #[test]
+    fn test_retry_proof() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let message_ids = test_data::messages()
+            .into_iter()
+            .map(|msg| msg.cc_id)
+            .collect::<Vec<CrossChainId>>();
+        let batch_id = BatchId::new(
+            &test_case.prover_address,
+            test_data::destination_chain_id(),
+            &message_ids,
+            None,
+        );
+
+        let res = execute_retry_proof(&mut test_case, batch_id.clone());
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::BatchNotFound).to_string()
+        );
+
+        // a batch that rotates the worker set is signed over a different message, so its session stays pending
+        let mut new_operators = test_data::operators();
+        new_operators.pop();
+        mocks::service_registry::set_active_workers(
+            &mut test_case.app,
+            test_case.service_registry_address.clone(),
+            new_operators.clone(),
+        );
+        execute_construct_proof(&mut test_case, None).unwrap();
+        let next_worker_set =
+            test_operators_to_worker_set(new_operators.clone(), test_case.app.block_info().height);
+        let rotation_batch_id = BatchId::new(
+            &test_case.prover_address,
+            test_data::destination_chain_id(),
+            &message_ids,
+            Some(next_worker_set.clone()),
+        );
+
+        let res = execute_retry_proof(&mut test_case, rotation_batch_id.clone());
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::ProofStillPending).to_string()
+        );
+
+        // once the worker set rotated, the pending session can't complete anymore
+        mocks::voting_verifier::confirm_worker_set(
+            &mut test_case.app,
+            test_case.voting_verifier_address.clone(),
+            new_operators,
+            next_worker_set.threshold,
+        );
+        confirm_worker_set(&mut test_case).unwrap();
+
+        let res = execute_retry_proof(&mut test_case, rotation_batch_id).unwrap();
+
+        let event = res
+            .events
+            .iter()
+            .find(|event| event.ty == "wasm-proof_under_construction");
+
+        assert!(event.is_some());
+
+        let proof = query_get_proof(&mut test_case, None).unwrap();
+        assert_eq!(proof.message_ids, message_ids);
+
+        // the rebuilt batch was signed without the rotation and its proof is completed
+        let res = execute_retry_proof(&mut test_case, batch_id);
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::ProofAlreadyCompleted).to_string()
+        );
+    }
+
     #[test]
     fn test_construct_proof_updates_worker_set() {
         let mut test_case = setup_test_case();
@@ -602,6 +1730,144 @@ mod tests {
         assert_eq!(worker_set, expected_worker_set);
     }
 
+    #[test]
+    fn test_signing_completed_callback() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let msg = ExecuteMsg::SigningCompleted {
+            session_id: MULTISIG_SESSION_ID,
+        };
+
+        let res = test_case.app.execute_contract(
+            Addr::unchecked(RELAYER),
+            test_case.prover_address.clone(),
+            &msg,
+            &[],
+        );
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+
+        let res = test_case
+            .app
+            .execute_contract(
+                test_case.multisig_address.clone(),
+                test_case.prover_address.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap();
+
+        let event = res
+            .events
+            .iter()
+            .find(|event| event.ty == "wasm-proof_completed");
+
+        assert!(event.is_some());
+    }
+
+    #[test]
+    fn test_prune_proofs() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let prune = |test_case: &mut TestCaseConfig| {
+            test_case.app.execute_contract(
+                Addr::unchecked(RELAYER),
+                test_case.prover_address.clone(),
+                &ExecuteMsg::PruneProofs { limit: 10 },
+                &[],
+            )
+        };
+        let pruned_events = |res: AppResponse| {
+            res.events
+                .into_iter()
+                .filter(|event| event.ty == "wasm-proof_pruned")
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            prune(&mut test_case)
+                .unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::NoRetentionPolicy).to_string()
+        );
+
+        let msg = ExecuteMsg::UpdateRetentionPolicy {
+            retention_policy: Some(RetentionPolicy::MaxAge { blocks: 0 }),
+        };
+        assert!(test_case
+            .app
+            .execute_contract(
+                test_case.admin.clone(),
+                test_case.prover_address.clone(),
+                &msg,
+                &[]
+            )
+            .is_err());
+        test_case
+            .app
+            .execute_contract(
+                Addr::unchecked(GOVERNANCE),
+                test_case.prover_address.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap();
+
+        // pending proofs are never pruned
+        test_case.app.update_block(next_block);
+        assert!(pruned_events(prune(&mut test_case).unwrap()).is_empty());
+
+        test_case
+            .app
+            .execute_contract(
+                test_case.multisig_address.clone(),
+                test_case.prover_address.clone(),
+                &ExecuteMsg::SigningCompleted {
+                    session_id: MULTISIG_SESSION_ID,
+                },
+                &[],
+            )
+            .unwrap();
+
+        // too recent to be pruned
+        assert!(pruned_events(prune(&mut test_case).unwrap()).is_empty());
+
+        test_case.app.update_block(next_block);
+        let events = pruned_events(prune(&mut test_case).unwrap());
+        assert_eq!(events.len(), 1);
+        assert!(events[0]
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "batch_pruned" && attr.value == "true"));
+
+        assert!(query_get_proof(&mut test_case, None).is_err());
+        let batch: Option<BatchByMessageIdResponse> = test_case
+            .app
+            .wrap()
+            .query_wasm_smart(
+                test_case.prover_address.clone(),
+                &QueryMsg::BatchByMessageId {
+                    message_id: test_data::messages().remove(0).cc_id,
+                },
+            )
+            .unwrap();
+        assert_eq!(batch, None);
+
+        // the pruned proof is not pruned again
+        assert!(pruned_events(prune(&mut test_case).unwrap()).is_empty());
+    }
+
     #[test]
     fn test_construct_proof_no_worker_set() {
         let mut test_case = setup_test_case();
@@ -616,6 +1882,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_construct_proof_blocked_while_rotation_pending_in_safe_mode() {
+        let mut test_case = setup_safe_mode_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let status = query_get_batching_status(&mut test_case).unwrap();
+        assert_eq!(
+            status,
+            BatchingStatus {
+                safe_mode: true,
+                rotation_pending: false,
+                new_batches_blocked: false,
+            }
+        );
+
+        let mut new_worker_set = test_data::operators();
+        new_worker_set.pop();
+        mocks::service_registry::set_active_workers(
+            &mut test_case.app,
+            test_case.service_registry_address.clone(),
+            new_worker_set,
+        );
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let status = query_get_batching_status(&mut test_case).unwrap();
+        assert_eq!(
+            status,
+            BatchingStatus {
+                safe_mode: true,
+                rotation_pending: true,
+                new_batches_blocked: true,
+            }
+        );
+
+        let res = execute_construct_proof(&mut test_case, None);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::WorkerSetRotationPending)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_construct_proof_not_blocked_while_rotation_pending_without_safe_mode() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let mut new_worker_set = test_data::operators();
+        new_worker_set.pop();
+        mocks::service_registry::set_active_workers(
+            &mut test_case.app,
+            test_case.service_registry_address.clone(),
+            new_worker_set,
+        );
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let status = query_get_batching_status(&mut test_case).unwrap();
+        assert_eq!(
+            status,
+            BatchingStatus {
+                safe_mode: false,
+                rotation_pending: true,
+                new_batches_blocked: false,
+            }
+        );
+
+        assert!(execute_construct_proof(&mut test_case, None).is_ok());
+    }
+
+    #[test]
+    fn test_construct_proof_carries_pending_rotation() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let mut new_worker_set = test_data::operators();
+        new_worker_set.pop();
+        mocks::service_registry::set_active_workers(
+            &mut test_case.app,
+            test_case.service_registry_address.clone(),
+            new_worker_set.clone(),
+        );
+        execute_update_worker_set(&mut test_case).unwrap();
+        let rotation = query_get_proof(&mut test_case, None).unwrap().data.commands[0].clone();
+
+        // the active workers changed again, but the batch still rotates to the pending worker set
+        new_worker_set.pop();
+        mocks::service_registry::set_active_workers(
+            &mut test_case.app,
+            test_case.service_registry_address.clone(),
+            new_worker_set,
+        );
+        execute_construct_proof(&mut test_case, None).unwrap();
+
+        let commands = query_get_proof(&mut test_case, None).unwrap().data.commands;
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0], rotation);
+        assert_eq!(commands[1].ty, CommandType::ApproveContractCall);
+    }
+
     #[test]
     fn should_update_worker_set_no_change() {
         let worker_set = test_data::new_worker_set();
@@ -668,4 +2037,167 @@ mod tests {
         );
         assert!(should_update_worker_set(&worker_set, &new_worker_set, 0));
     }
+
+    fn assert_contract_err(res: Result<AppResponse, Error>, err: ContractError) {
+        assert_eq!(
+            res.unwrap_err()
+                .downcast::<axelar_wasm_std::ContractError>()
+                .unwrap()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(err).to_string()
+        );
+    }
+
+    #[test]
+    fn register_recovery_worker_set() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+        let recovery_worker_set = test_data::new_worker_set();
+
+        for sender in [Addr::unchecked(RELAYER), test_case.admin.clone()] {
+            let res = execute_register_recovery_worker_set(
+                &mut test_case,
+                sender,
+                recovery_worker_set.clone(),
+            );
+            assert_contract_err(res, ContractError::Unauthorized);
+        }
+
+        let mut unreachable_threshold = recovery_worker_set.clone();
+        unreachable_threshold.threshold = Uint256::from(100u128);
+        let res = execute_register_recovery_worker_set(
+            &mut test_case,
+            Addr::unchecked(GOVERNANCE),
+            unreachable_threshold,
+        );
+        assert_contract_err(
+            res,
+            ContractError::InvalidRecoveryWorkerSet {
+                reason: "threshold must be positive and reachable".to_string(),
+            },
+        );
+
+        let cur_worker_set = query_get_worker_set(&mut test_case).unwrap();
+        let res = execute_register_recovery_worker_set(
+            &mut test_case,
+            Addr::unchecked(GOVERNANCE),
+            cur_worker_set,
+        );
+        assert_contract_err(
+            res,
+            ContractError::InvalidRecoveryWorkerSet {
+                reason: "signers must differ from the current worker set".to_string(),
+            },
+        );
+
+        let res = execute_register_recovery_worker_set(
+            &mut test_case,
+            Addr::unchecked(GOVERNANCE),
+            recovery_worker_set.clone(),
+        );
+        assert_contract_err(
+            res,
+            ContractError::InvalidRecoveryWorkerSet {
+                reason: "must be confirmed by the destination gateway".to_string(),
+            },
+        );
+
+        confirm_on_gateway(&mut test_case, &recovery_worker_set);
+        let res = execute_register_recovery_worker_set(
+            &mut test_case,
+            Addr::unchecked(GOVERNANCE),
+            recovery_worker_set.clone(),
+        )
+        .unwrap();
+        assert!(res.events.iter().any(|event| {
+            event.ty == "wasm-recovery_worker_set_registered"
+                && event.attributes.iter().any(|attribute| {
+                    attribute.key == "worker_set_id" && attribute.value == recovery_worker_set.id()
+                })
+        }));
+        assert_eq!(
+            query_get_recovery_worker_set(&mut test_case).unwrap(),
+            Some(recovery_worker_set)
+        );
+    }
+
+    #[test]
+    fn activate_recovery() {
+        let mut test_case = setup_test_case();
+        execute_update_worker_set(&mut test_case).unwrap();
+
+        let res = execute_activate_recovery(&mut test_case);
+        assert_contract_err(res, ContractError::NoRecoveryWorkerSet);
+
+        let recovery_worker_set = test_data::new_worker_set();
+        confirm_on_gateway(&mut test_case, &recovery_worker_set);
+        execute_register_recovery_worker_set(
+            &mut test_case,
+            Addr::unchecked(GOVERNANCE),
+            recovery_worker_set.clone(),
+        )
+        .unwrap();
+
+        // all workers are still active
+        let res = execute_activate_recovery(&mut test_case);
+        assert_contract_err(res, ContractError::WorkerSetQuorumIntact);
+
+        // only 2 of 5 workers of the current worker set are still active, which is below its threshold of 4
+        let mut new_workers = test_data::operators();
+        new_workers.truncate(2);
+        mocks::service_registry::set_active_workers(
+            &mut test_case.app,
+            test_case.service_registry_address.clone(),
+            new_workers.clone(),
+        );
+
+        let res = execute_activate_recovery(&mut test_case).unwrap();
+        let new_worker_set =
+            test_operators_to_worker_set(new_workers.clone(), test_case.app.block_info().height);
+
+        // the rotation is signed by the recovery worker set
+        assert!(res.events.iter().any(|event| {
+            event.attributes.iter().any(|attribute| {
+                attribute.key == "worker_set_id" && attribute.value == recovery_worker_set.id()
+            })
+        }));
+        assert!(res
+            .events
+            .iter()
+            .any(|event| event.ty == "wasm-recovery_activated"));
+        assert_eq!(
+            query_get_proof(&mut test_case, None)
+                .unwrap()
+                .worker_set_hash,
+            Some(recovery_worker_set.hash())
+        );
+        assert!(
+            query_get_batching_status(&mut test_case)
+                .unwrap()
+                .rotation_pending
+        );
+
+        let res = execute_activate_recovery(&mut test_case);
+        assert_contract_err(res, ContractError::RecoveryAlreadyActivated);
+
+        let total_weight: Uint256 = new_workers
+            .iter()
+            .fold(Uint256::zero(), |acc, x| acc + x.weight);
+        let quorum = total_weight.mul_ceil(test_data::threshold());
+        mocks::voting_verifier::confirm_worker_set(
+            &mut test_case.app,
+            test_case.voting_verifier_address.clone(),
+            new_workers,
+            quorum,
+        );
+        confirm_worker_set(&mut test_case).unwrap();
+        assert_eq!(
+            query_get_worker_set(&mut test_case).unwrap(),
+            new_worker_set
+        );
+
+        // recovery can be activated again for the next loss of quorum
+        let res = execute_activate_recovery(&mut test_case);
+        assert_contract_err(res, ContractError::WorkerSetQuorumIntact);
+    }
 }
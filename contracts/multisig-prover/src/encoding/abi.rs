@@ -8,6 +8,7 @@ use k256::{elliptic_curve::sec1::ToEncodedPoint, PublicKey};
 use sha3::{Digest, Keccak256};
 
 use axelar_wasm_std::operators::Operators;
+use connection_router::state::TokenTransfer;
 use multisig::{key::Signature, msg::Signer, worker_set::WorkerSet};
 
 use crate::{
@@ -15,11 +16,101 @@ use crate::{
     types::{CommandBatch, Operator},
 };
 
-use super::Data;
+use super::{
+    address::decode_destination_address,
+    gas::{GasSchedule, EVM_GAS_SCHEDULE},
+    AddressFormat, Codec, Data, Eip712Domain, FeePayer,
+};
 
 pub const GATEWAY_EXECUTE_FUNCTION_NAME: &str = "execute";
 
-pub fn encode(data: &Data) -> HexBinary {
+const EVM_ADDRESS_LEN: usize = 20;
+
+const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const EIP712_BATCH_TYPE: &str =
+    "CommandBatch(uint256 chainId,bytes32[] commandIds,string[] commands,bytes[] params)";
+
+const ADDRESS_FORMAT: AddressFormat = AddressFormat::Evm;
+
+pub struct AbiCodec;
+
+impl Codec for AbiCodec {
+    fn address_format(&self) -> AddressFormat {
+        ADDRESS_FORMAT
+    }
+
+    fn command_params(
+        &self,
+        source_chain: String,
+        source_address: String,
+        destination_address: String,
+        payload_hash: &[u8; 32],
+    ) -> Result<HexBinary, ContractError> {
+        command_params(
+            source_chain,
+            source_address,
+            destination_address,
+            payload_hash,
+        )
+    }
+
+    fn command_params_with_mint(
+        &self,
+        source_chain: String,
+        source_address: String,
+        destination_address: String,
+        payload_hash: &[u8; 32],
+        token: &TokenTransfer,
+    ) -> Result<HexBinary, ContractError> {
+        command_params_with_mint(
+            source_chain,
+            source_address,
+            destination_address,
+            payload_hash,
+            token,
+        )
+    }
+
+    fn transfer_operatorship_params(
+        &self,
+        worker_set: &WorkerSet,
+    ) -> Result<HexBinary, ContractError> {
+        transfer_operatorship_params(worker_set)
+    }
+
+    fn make_operators(&self, worker_set: WorkerSet) -> Operators {
+        make_operators(worker_set)
+    }
+
+    fn encode(&self, data: &Data) -> HexBinary {
+        encode(data)
+    }
+
+    fn msg_digest(&self, command_batch: &CommandBatch) -> HexBinary {
+        msg_digest(command_batch)
+    }
+
+    fn validate_eip712_domain(&self, domain: &Eip712Domain) -> Result<(), ContractError> {
+        eip712_verifying_contract(domain).map(|_| ())
+    }
+
+    fn encode_execute_data(
+        &self,
+        command_batch: &CommandBatch,
+        quorum: Uint256,
+        signers: Vec<(Signer, Option<Signature>)>,
+        fee_payer: FeePayer,
+    ) -> Result<HexBinary, ContractError> {
+        encode_execute_data(command_batch, quorum, signers, fee_payer)
+    }
+
+    fn gas_schedule(&self) -> Option<&'static GasSchedule> {
+        Some(&EVM_GAS_SCHEDULE)
+    }
+}
+
+fn encode(data: &Data) -> HexBinary {
     let destination_chain_id = Token::Uint(ethabi::ethereum_types::U256::from_big_endian(
         &data.destination_chain_id.to_be_bytes(),
     ));
@@ -45,7 +136,11 @@ pub fn encode(data: &Data) -> HexBinary {
     .into()
 }
 
-pub fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
+fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
+    if let Some(domain) = &command_batch.eip712_domain {
+        return eip712_digest(domain, &command_batch.data);
+    }
+
     let msg = Keccak256::digest(encode(&command_batch.data).as_slice());
 
     // Prefix for standard EVM signed data https://eips.ethereum.org/EIPS/eip-191
@@ -58,34 +153,105 @@ pub fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
     Keccak256::digest(unsigned).as_slice().into()
 }
 
-pub fn encode_execute_data(
+// https://eips.ethereum.org/EIPS/eip-712
+fn eip712_digest(domain: &Eip712Domain, data: &Data) -> HexBinary {
+    let unsigned = [
+        "\x19\x01".as_bytes(),
+        eip712_domain_separator(domain, data.destination_chain_id).as_slice(),
+        eip712_batch_hash(data).as_slice(),
+    ]
+    .concat();
+
+    Keccak256::digest(unsigned).as_slice().into()
+}
+
+fn eip712_domain_separator(domain: &Eip712Domain, chain_id: Uint256) -> Vec<u8> {
+    let verifying_contract =
+        eip712_verifying_contract(domain).expect("eip-712 domain is validated on instantiation");
+
+    Keccak256::digest(ethabi::encode(&[
+        Token::FixedBytes(Keccak256::digest(EIP712_DOMAIN_TYPE).to_vec()),
+        Token::FixedBytes(Keccak256::digest(&domain.name).to_vec()),
+        Token::FixedBytes(Keccak256::digest(&domain.version).to_vec()),
+        Token::Uint(ethereum_types::U256::from_big_endian(
+            &chain_id.to_be_bytes(),
+        )),
+        Token::Address(verifying_contract),
+    ]))
+    .to_vec()
+}
+
+// arrays are hashed as the concatenation of their encoded elements, dynamic elements are encoded as their hash
+fn eip712_batch_hash(data: &Data) -> Vec<u8> {
+    let (command_ids, commands, params): (Vec<_>, Vec<_>, Vec<_>) = data
+        .commands
+        .iter()
+        .map(|command| {
+            (
+                command.id.to_vec(),
+                Keccak256::digest(command.ty.to_string()).to_vec(),
+                Keccak256::digest(command.params.as_slice()).to_vec(),
+            )
+        })
+        .multiunzip();
+
+    Keccak256::digest(ethabi::encode(&[
+        Token::FixedBytes(Keccak256::digest(EIP712_BATCH_TYPE).to_vec()),
+        Token::Uint(ethereum_types::U256::from_big_endian(
+            &data.destination_chain_id.to_be_bytes(),
+        )),
+        Token::FixedBytes(Keccak256::digest(command_ids.concat()).to_vec()),
+        Token::FixedBytes(Keccak256::digest(commands.concat()).to_vec()),
+        Token::FixedBytes(Keccak256::digest(params.concat()).to_vec()),
+    ]))
+    .to_vec()
+}
+
+fn eip712_verifying_contract(
+    domain: &Eip712Domain,
+) -> Result<ethereum_types::Address, ContractError> {
+    ethereum_types::Address::from_str(&domain.verifying_contract).map_err(|err| {
+        ContractError::InvalidEip712Domain {
+            reason: format!("verifying_contract is not a valid EVM address: {}", err),
+        }
+    })
+}
+
+fn encode_execute_data(
     command_batch: &CommandBatch,
     quorum: Uint256,
     signers: Vec<(Signer, Option<Signature>)>,
+    fee_payer: FeePayer,
 ) -> Result<HexBinary, ContractError> {
+    let msg_digest = command_batch.msg_digest();
     let signers = signers
         .into_iter()
         .map(|(signer, signature)| {
-            let mut signature = signature;
-            if let Some(Signature::Ecdsa(nonrecoverable)) = signature {
-                signature = nonrecoverable
-                    .to_recoverable(
-                        command_batch.msg_digest().as_slice(),
-                        &signer.pub_key,
-                        add27,
-                    )
-                    .map(Signature::EcdsaRecoverable)
-                    .ok();
-            }
+            let signature = signature
+                .map(|signature| evm_signature(signature, msg_digest.as_slice(), &signer.pub_key))
+                .transpose()?;
 
-            (signer, signature)
+            Ok((signer, signature))
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, ContractError>>()?;
 
-    let param = ethabi::encode(&[
+    let mut tokens = vec![
         Token::Bytes(encode(&command_batch.data).into()),
         Token::Bytes(encode_proof(quorum, signers)?.into()),
-    ]);
+    ];
+    match fee_payer {
+        FeePayer::Unsupported => {}
+        FeePayer::Unset => tokens.push(Token::Address(ethereum_types::Address::zero())),
+        FeePayer::Address(address) => tokens.push(Token::Address(
+            ethereum_types::Address::from_str(address).map_err(|err| {
+                ContractError::InvalidFeePayer {
+                    reason: format!("not a valid EVM address: {}", err),
+                }
+            })?,
+        )),
+    }
+
+    let param = ethabi::encode(&tokens);
 
     let input = ethabi::encode(&[Token::Bytes(param)]);
 
@@ -100,14 +266,14 @@ fn encode_proof(
     quorum: Uint256,
     signers: Vec<(Signer, Option<Signature>)>,
 ) -> Result<HexBinary, ContractError> {
-    let mut operators = make_evm_operators_with_sigs(signers)?;
+    let mut operators = make_operators_with_sigs(signers)?;
     operators.sort();
 
     let (addresses, weights, signatures): (Vec<_>, Vec<_>, Vec<_>) = operators
         .into_iter()
         .map(|op| {
             (
-                Token::Address(ethereum_types::Address::from_slice(op.address.as_slice())),
+                operator_token(&op.address),
                 Token::Uint(ethereum_types::U256::from_big_endian(
                     &op.weight.to_be_bytes(),
                 )),
@@ -130,26 +296,18 @@ fn encode_proof(
     .into())
 }
 
-pub fn make_operators(worker_set: WorkerSet) -> Operators {
-    let operators: Vec<(HexBinary, Uint256)> = worker_set
-        .signers
-        .values()
-        .map(|signer| {
-            (
-                evm_address(signer.pub_key.as_ref())
-                    .expect("couldn't convert pubkey to evm address"),
-                signer.weight,
-            )
-        })
-        .collect();
+fn make_operators(worker_set: WorkerSet) -> Operators {
+    let operators = worker_set
+        .operators(operator_address)
+        .expect("couldn't convert pubkey to operator address");
     Operators::new(operators, worker_set.threshold)
 }
 
-fn make_evm_operators_with_sigs(
+fn make_operators_with_sigs(
     signers_with_sigs: Vec<(Signer, Option<Signature>)>,
 ) -> Result<Vec<Operator>, ContractError> {
     axelar_wasm_std::utils::try_map(signers_with_sigs, |(signer, sig)| {
-        make_evm_operator(signer).map(|op| {
+        make_operator(signer).map(|op| {
             if let Some(sig) = sig {
                 return op.with_signature(sig);
             }
@@ -158,9 +316,9 @@ fn make_evm_operators_with_sigs(
     })
 }
 
-fn make_evm_operator(signer: Signer) -> Result<Operator, ContractError> {
+fn make_operator(signer: Signer) -> Result<Operator, ContractError> {
     Ok(Operator {
-        address: evm_address(signer.pub_key.as_ref())?,
+        address: operator_address(&signer.pub_key)?,
         weight: signer.weight,
         signature: None,
     })
@@ -170,24 +328,34 @@ fn add27(recovery_byte: u8) -> u8 {
     recovery_byte + 27
 }
 
-pub fn transfer_operatorship_params(worker_set: &WorkerSet) -> Result<HexBinary, ContractError> {
-    let mut operators: Vec<(HexBinary, Uint256)> = worker_set
-        .signers
-        .values()
-        .map(|signer| {
-            (
-                evm_address(signer.pub_key.as_ref())
-                    .expect("couldn't convert pubkey to evm address"),
-                signer.weight,
-            )
+// workers submit ecdsa signatures without a recovery byte, or with one that isn't in the 27/28 form the gateway expects,
+// so it is always derived again by recovering the operator's key from the signature
+fn evm_signature(
+    signature: Signature,
+    msg_digest: &[u8],
+    pub_key: &multisig::key::PublicKey,
+) -> Result<Signature, ContractError> {
+    let non_recoverable = match signature {
+        Signature::Ecdsa(non_recoverable) => non_recoverable,
+        Signature::EcdsaRecoverable(recoverable) => recoverable.to_non_recoverable(),
+        signature => return Ok(signature),
+    };
+
+    non_recoverable
+        .to_recoverable(msg_digest, pub_key, add27)
+        .map(Signature::EcdsaRecoverable)
+        .map_err(|err| ContractError::InvalidSignature {
+            reason: format!("signature doesn't match the operator's key: {}", err),
         })
-        .collect();
-    operators.sort_by_key(|op| op.0.clone());
+}
+
+fn transfer_operatorship_params(worker_set: &WorkerSet) -> Result<HexBinary, ContractError> {
+    let operators = worker_set.operators(operator_address)?;
     let (addresses, weights): (Vec<Token>, Vec<Token>) = operators
         .iter()
         .map(|operator| {
             (
-                Token::Address(ethereum_types::Address::from_slice(operator.0.as_slice())),
+                operator_token(&operator.0),
                 Token::Uint(ethereum_types::U256::from_big_endian(
                     &operator.1.to_be_bytes(),
                 )),
@@ -202,6 +370,22 @@ pub fn transfer_operatorship_params(worker_set: &WorkerSet) -> Result<HexBinary,
     Ok(ethabi::encode(&[Token::Array(addresses), Token::Array(weights), quorum]).into())
 }
 
+// the gateway identifies ecdsa operators by their evm address, and ed25519 operators by their public key
+fn operator_address(pub_key: &multisig::key::PublicKey) -> Result<HexBinary, ContractError> {
+    match pub_key {
+        multisig::key::PublicKey::Ed25519(pub_key) => Ok(pub_key.clone()),
+        _ => evm_address(pub_key.as_ref()),
+    }
+}
+
+// ed25519 public keys don't fit into an address, so they are encoded as bytes32 instead
+fn operator_token(address: &HexBinary) -> Token {
+    match address.len() {
+        EVM_ADDRESS_LEN => Token::Address(ethereum_types::Address::from_slice(address.as_slice())),
+        _ => Token::FixedBytes(address.to_vec()),
+    }
+}
+
 fn evm_address(pub_key: &[u8]) -> Result<HexBinary, ContractError> {
     let pub_key =
         PublicKey::from_sec1_bytes(pub_key).map_err(|err| ContractError::InvalidPublicKey {
@@ -212,18 +396,13 @@ fn evm_address(pub_key: &[u8]) -> Result<HexBinary, ContractError> {
     Ok(Keccak256::digest(&pub_key.as_bytes()[1..]).as_slice()[12..].into())
 }
 
-pub fn command_params(
+fn command_params(
     source_chain: String,
     source_address: String,
     destination_address: String,
     payload_hash: &[u8; 32],
 ) -> Result<HexBinary, ContractError> {
-    let destination_address =
-        ethereum_types::Address::from_str(&destination_address).map_err(|err| {
-            ContractError::InvalidMessage {
-                reason: format!("destination_address is not a valid EVM address: {}", err),
-            }
-        })?;
+    let destination_address = parse_destination_address(&destination_address)?;
     Ok(ethabi::encode(&[
         Token::String(source_chain),
         Token::String(source_address),
@@ -235,14 +414,51 @@ pub fn command_params(
     .into())
 }
 
+// same layout as the params of a plain contract call, with the minted token inserted after the payload hash
+fn command_params_with_mint(
+    source_chain: String,
+    source_address: String,
+    destination_address: String,
+    payload_hash: &[u8; 32],
+    token: &TokenTransfer,
+) -> Result<HexBinary, ContractError> {
+    let destination_address = parse_destination_address(&destination_address)?;
+    Ok(ethabi::encode(&[
+        Token::String(source_chain),
+        Token::String(source_address),
+        Token::Address(destination_address),
+        Token::FixedBytes(payload_hash.to_vec()),
+        Token::String(token.symbol.to_string()),
+        Token::Uint(ethereum_types::U256::from_big_endian(
+            &token.amount.as_ref().to_be_bytes(),
+        )),
+        Token::FixedBytes(vec![]), // TODO: Dummy data for now while Gateway is updated to not require these fields
+        Token::Uint(ethereum_types::U256::zero()),
+    ])
+    .into())
+}
+
+fn parse_destination_address(
+    destination_address: &str,
+) -> Result<ethereum_types::Address, ContractError> {
+    decode_destination_address(destination_address, ADDRESS_FORMAT)
+        .map(|address| ethereum_types::Address::from_slice(&address))
+}
+
 #[cfg(test)]
 mod test {
-    use connection_router::state::CrossChainId;
+    use axelar_wasm_std::test_utils::{check_invariant, nonempty_string};
+    use connection_router::state::{CrossChainId, Message};
+    use cosmwasm_std::Addr;
     use elliptic_curve::consts::U32;
-    use ethers::types::Signature as EthersSignature;
+    use ethers::types::{
+        transaction::eip712::{Eip712, TypedData},
+        Signature as EthersSignature,
+    };
     use generic_array::GenericArray;
     use hex::FromHex;
     use k256::ecdsa::Signature as K256Signature;
+    use proptest::prelude::*;
 
     use multisig::key::KeyType;
 
@@ -319,6 +535,9 @@ mod test {
                                 id: id.to_owned().try_into().unwrap(),
                                 ty: match ty.as_str() {
                                     "approveContractCall" => CommandType::ApproveContractCall,
+                                    "approveContractCallWithMint" => {
+                                        CommandType::ApproveContractCallWithMint
+                                    }
                                     "transferOperatorship" => CommandType::TransferOperatorship,
                                     &_ => panic!("undecodable command type"),
                                 },
@@ -364,6 +583,105 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_command_params_round_trip() {
+        let strategy = (
+            nonempty_string(),
+            nonempty_string(),
+            any::<[u8; 20]>(),
+            any::<[u8; 32]>(),
+        );
+
+        check_invariant(
+            strategy,
+            |(source_chain, source_address, destination_address, payload_hash)| {
+                let destination_address = ethereum_types::Address::from(destination_address);
+
+                let encoded = command_params(
+                    source_chain.to_string(),
+                    source_address.to_string(),
+                    format!("{:#x}", destination_address),
+                    &payload_hash,
+                )
+                .unwrap();
+
+                prop_assert_eq!(
+                    decode_command_params(encoded),
+                    vec![
+                        Token::String(source_chain.into()),
+                        Token::String(source_address.into()),
+                        Token::Address(destination_address),
+                        Token::FixedBytes(payload_hash.to_vec()),
+                    ]
+                );
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn test_command_params_with_mint() {
+        let router_message = test_data::messages().first().unwrap().clone();
+        let token = TokenTransfer {
+            symbol: "AXL".try_into().unwrap(),
+            amount: Uint256::from(1000u128).try_into().unwrap(),
+        };
+
+        let encoded = command_params_with_mint(
+            router_message.cc_id.chain.to_string(),
+            router_message.source_address.to_string(),
+            router_message.destination_address.to_string(),
+            &router_message.payload_hash,
+            &token,
+        )
+        .unwrap();
+
+        let tokens = ethabi::decode(
+            &[
+                ParamType::String,
+                ParamType::String,
+                ParamType::Address,
+                ParamType::FixedBytes(32),
+                ParamType::String,
+                ParamType::Uint(256),
+            ],
+            &encoded,
+        )
+        .unwrap();
+        assert_eq!(
+            tokens[..4],
+            decode_command_params(
+                command_params(
+                    router_message.cc_id.chain.to_string(),
+                    router_message.source_address.to_string(),
+                    router_message.destination_address.to_string(),
+                    &router_message.payload_hash,
+                )
+                .unwrap()
+            )[..]
+        );
+        assert_eq!(tokens[4], Token::String("AXL".to_string()));
+        assert_eq!(tokens[5], Token::Uint(ethereum_types::U256::from(1000)));
+
+        let mut builder = CommandBatchBuilder::new(
+            test_data::prover(),
+            test_data::destination_chain_id(),
+            Encoder::Abi,
+        );
+        builder
+            .add_message(Message {
+                token: Some(token),
+                ..router_message.clone()
+            })
+            .unwrap();
+        let batch = builder.build().unwrap();
+        assert_eq!(batch.message_ids, vec![router_message.cc_id]);
+        assert_eq!(
+            decode_data(&encode(&batch.data)).commands[0].ty,
+            CommandType::ApproveContractCallWithMint
+        );
+    }
+
     #[test]
     fn test_command_from_router_message_invalid_dest_addr() {
         let mut router_message = test_data::messages().first().unwrap().clone();
@@ -378,7 +696,7 @@ mod test {
         assert_eq!(
             res.unwrap_err(),
             ContractError::InvalidMessage {
-                reason: "destination_address is not a valid EVM address: Invalid character 'i' at position 0".into()
+                reason: "destination_address invalid is not a valid address, expected a 20 byte hex EVM address".into()
             }
         );
     }
@@ -419,12 +737,112 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_eip712_msg_digest() {
+        let domain = Eip712Domain {
+            name: "AxelarGateway".to_string(),
+            version: "1".to_string(),
+            verifying_contract: "0x4F4495243837681061C4743b74B3eEdf548D56A5".to_string(),
+        };
+
+        let mut builder = CommandBatchBuilder::new(
+            test_data::prover(),
+            test_data::destination_chain_id(),
+            Encoder::Abi,
+        )
+        .with_eip712_domain(Some(domain.clone()));
+        for msg in test_data::messages() {
+            builder.add_message(msg).unwrap();
+        }
+        builder
+            .add_new_worker_set(test_data::new_worker_set())
+            .unwrap();
+        let batch = builder.build().unwrap();
+
+        let hex = |bytes: &HexBinary| format!("0x{}", bytes.to_hex());
+        let (command_ids, commands, params): (Vec<_>, Vec<_>, Vec<_>) = batch
+            .data
+            .commands
+            .iter()
+            .map(|command| {
+                (
+                    hex(&command.id),
+                    command.ty.to_string(),
+                    hex(&command.params),
+                )
+            })
+            .multiunzip();
+        let typed_data: TypedData = serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "CommandBatch": [
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "commandIds", "type": "bytes32[]" },
+                    { "name": "commands", "type": "string[]" },
+                    { "name": "params", "type": "bytes[]" }
+                ]
+            },
+            "primaryType": "CommandBatch",
+            "domain": {
+                "name": domain.name,
+                "version": domain.version,
+                "chainId": batch.data.destination_chain_id.to_string(),
+                "verifyingContract": domain.verifying_contract
+            },
+            "message": {
+                "chainId": batch.data.destination_chain_id.to_string(),
+                "commandIds": command_ids,
+                "commands": commands,
+                "params": params
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            batch.msg_digest(),
+            HexBinary::from(typed_data.encode_eip712().unwrap())
+        );
+
+        // the same batch signed as a personal message has a different digest
+        let personal_sign_batch = CommandBatch {
+            eip712_domain: None,
+            ..batch.clone()
+        };
+        assert_ne!(personal_sign_batch.msg_digest(), batch.msg_digest());
+    }
+
+    #[test]
+    fn test_eip712_domain_validation() {
+        let domain = Eip712Domain {
+            name: "AxelarGateway".to_string(),
+            version: "1".to_string(),
+            verifying_contract: "not an address".to_string(),
+        };
+
+        assert!(matches!(
+            AbiCodec.validate_eip712_domain(&domain),
+            Err(ContractError::InvalidEip712Domain { .. })
+        ));
+        assert!(AbiCodec
+            .validate_eip712_domain(&Eip712Domain {
+                verifying_contract: "0x4F4495243837681061C4743b74B3eEdf548D56A5".to_string(),
+                ..domain
+            })
+            .is_ok());
+    }
+
     #[test]
     fn test_new_command_batch() {
         let messages = test_data::messages();
         let destination_chain_id = test_data::destination_chain_id();
         let test_data = decode_data(&test_data::encoded_data());
-        let mut builder = CommandBatchBuilder::new(destination_chain_id, Encoder::Abi);
+        let mut builder =
+            CommandBatchBuilder::new(test_data::prover(), destination_chain_id, Encoder::Abi);
         for msg in messages {
             builder.add_message(msg).unwrap();
         }
@@ -460,8 +878,11 @@ mod test {
     #[test]
     fn test_new_command_batch_with_operator_transfer() {
         let test_data = decode_data(&test_data::encoded_data_with_operator_transfer());
-        let mut builder =
-            CommandBatchBuilder::new(test_data::chain_id_operator_transfer(), Encoder::Abi);
+        let mut builder = CommandBatchBuilder::new(
+            test_data::prover(),
+            test_data::chain_id_operator_transfer(),
+            Encoder::Abi,
+        );
         let res = builder.add_new_worker_set(test_data::new_worker_set());
         assert!(res.is_ok());
         let res = builder.build();
@@ -469,6 +890,35 @@ mod test {
         assert_eq!(res.unwrap().data, test_data);
     }
 
+    #[test]
+    fn test_data_round_trip() {
+        let command = (
+            any::<[u8; 32]>(),
+            prop_oneof![
+                Just(CommandType::ApproveContractCall),
+                Just(CommandType::ApproveContractCallWithMint),
+                Just(CommandType::TransferOperatorship)
+            ],
+            prop::collection::vec(any::<u8>(), 0..256),
+        )
+            .prop_map(|(id, ty, params)| Command {
+                id: HexBinary::from(id.to_vec()),
+                ty,
+                params: HexBinary::from(params),
+            });
+        let strategy = (any::<[u8; 32]>(), prop::collection::vec(command, 0..10)).prop_map(
+            |(destination_chain_id, commands)| Data {
+                destination_chain_id: Uint256::from_be_bytes(destination_chain_id),
+                commands,
+            },
+        );
+
+        check_invariant(strategy, |data| {
+            prop_assert_eq!(decode_data(&encode(&data)), data);
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_batch_with_proof() {
         let messages = test_data::messages();
@@ -476,7 +926,8 @@ mod test {
         let operators = test_data::operators();
         let quorum = test_data::quorum();
 
-        let mut builder = CommandBatchBuilder::new(destination_chain_id, Encoder::Abi);
+        let mut builder =
+            CommandBatchBuilder::new(test_data::prover(), destination_chain_id, Encoder::Abi);
         for msg in messages {
             let res = builder.add_message(msg);
             assert!(res.is_ok());
@@ -497,7 +948,9 @@ mod test {
             })
             .collect::<Vec<(Signer, Option<Signature>)>>();
 
-        let execute_data = &batch.encode_execute_data(quorum, signers).unwrap();
+        let execute_data = &batch
+            .encode_execute_data(quorum, signers, FeePayer::Unsupported)
+            .unwrap();
 
         let tokens = ethabi::decode(
             &[ParamType::Bytes],
@@ -560,6 +1013,7 @@ mod test {
             message_ids: vec![],
             data: decode_data(&test_data::encoded_data()),
             encoder: Encoder::Abi,
+            eip712_domain: None,
         };
 
         let signers = operators
@@ -576,10 +1030,66 @@ mod test {
             })
             .collect::<Vec<(Signer, Option<Signature>)>>();
 
-        let res = batch.encode_execute_data(quorum, signers).unwrap();
+        let res = batch
+            .encode_execute_data(quorum, signers, FeePayer::Unsupported)
+            .unwrap();
         assert_eq!(res, test_data::execute_data());
     }
 
+    #[test]
+    fn test_execute_data_with_fee_payer() {
+        let batch = CommandBatch {
+            id: HexBinary::from_hex("00").unwrap().into(),
+            message_ids: vec![],
+            data: decode_data(&test_data::encoded_data()),
+            encoder: Encoder::Abi,
+            eip712_domain: None,
+        };
+        let fee_payer = "0x4c0a9e6a0c1b5b6d3c5d2b1e6f9a8c7d6e5f4a3b";
+
+        let decode_fee_payer = |execute_data: HexBinary| {
+            let input = match ethabi::decode(&[ParamType::Bytes], &execute_data.as_slice()[4..])
+                .unwrap()
+                .remove(0)
+            {
+                Token::Bytes(input) => input,
+                _ => panic!("Invalid execute data"),
+            };
+
+            ethabi::decode(
+                &[ParamType::Bytes, ParamType::Bytes, ParamType::Address],
+                input.as_slice(),
+            )
+            .unwrap()
+            .remove(2)
+        };
+
+        let execute_data = batch
+            .encode_execute_data(test_data::quorum(), vec![], FeePayer::Address(fee_payer))
+            .unwrap();
+        assert_eq!(
+            decode_fee_payer(execute_data),
+            Token::Address(ethereum_types::Address::from_str(fee_payer).unwrap())
+        );
+
+        let execute_data = batch
+            .encode_execute_data(test_data::quorum(), vec![], FeePayer::Unset)
+            .unwrap();
+        assert_eq!(
+            decode_fee_payer(execute_data),
+            Token::Address(ethereum_types::Address::zero())
+        );
+
+        assert!(matches!(
+            batch.encode_execute_data(
+                test_data::quorum(),
+                vec![],
+                FeePayer::Address("not an address")
+            ),
+            Err(ContractError::InvalidFeePayer { .. })
+        ));
+    }
+
     #[test]
     fn test_data_encode() {
         let encoded_data = test_data::encoded_data();
@@ -607,6 +1117,7 @@ mod test {
             message_ids: vec![],
             data: decode_data(&test_data::encoded_data()),
             encoder: Encoder::Abi,
+            eip712_domain: None,
         };
 
         let res = batch.msg_digest();
@@ -652,7 +1163,7 @@ mod test {
             ),
         ];
 
-        let mut operators = make_evm_operators_with_sigs(signers).unwrap();
+        let mut operators = make_operators_with_sigs(signers).unwrap();
         operators.sort();
 
         assert_eq!(
@@ -665,6 +1176,101 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_encode_proof_with_ed25519_signers() {
+        let signer = |pub_key: &str, signature: Option<&str>| {
+            (
+                Signer {
+                    address: Addr::unchecked(pub_key),
+                    weight: Uint256::one(),
+                    pub_key: multisig::key::PublicKey::Ed25519(
+                        HexBinary::from_hex(pub_key).unwrap(),
+                    ),
+                },
+                signature.map(|sig| Signature::Ed25519(HexBinary::from_hex(sig).unwrap())),
+            )
+        };
+        let pub_key_1 = "ff".repeat(32);
+        let pub_key_2 = "11".repeat(32);
+        let signature = "22".repeat(64);
+
+        let proof = encode_proof(
+            Uint256::one(),
+            vec![
+                signer(&pub_key_1, None),
+                signer(&pub_key_2, Some(&signature)),
+            ],
+        )
+        .unwrap();
+
+        let tokens = ethabi::decode(
+            &[
+                ParamType::Array(Box::new(ParamType::FixedBytes(32))),
+                ParamType::Array(Box::new(ParamType::Uint(256))),
+                ParamType::Uint(256),
+                ParamType::Array(Box::new(ParamType::Bytes)),
+            ],
+            proof.as_slice(),
+        )
+        .unwrap();
+
+        // operators are identified by their public key, in ascending order
+        assert_eq!(
+            tokens[0],
+            Token::Array(vec![
+                Token::FixedBytes(Vec::from_hex(&pub_key_2).unwrap()),
+                Token::FixedBytes(Vec::from_hex(&pub_key_1).unwrap()),
+            ])
+        );
+        assert_eq!(
+            tokens[3],
+            Token::Array(vec![Token::Bytes(Vec::from_hex(&signature).unwrap())])
+        );
+    }
+
+    #[test]
+    fn should_normalize_recovery_byte() {
+        let ethers_signature = EthersSignature::from_str("74ab5ec395cdafd861dec309c30f6cf8884fc9905eb861171e636d9797478adb60b2bfceb7db0a08769ed7a60006096d3e0f6d3783d125600ac6306180ecbc6f1b").unwrap();
+        let pub_key = multisig::key::PublicKey::Ecdsa(
+            HexBinary::from_hex(
+                "03571a2dcec96eecc7950c9f36367fd459b8d334bac01ac153b7ed3dcf4025fc22",
+            )
+            .unwrap(),
+        );
+        let digest =
+            HexBinary::from_hex("6ac52b00f4256d98d53c256949288135c14242a39001d5fdfa564ea003ccaf92")
+                .unwrap();
+
+        let expected = ethers_signature.to_vec();
+        let (sig, _) = expected.split_at(64);
+
+        // without recovery byte, with a raw recovery id, with the evm recovery byte and with a malformed one
+        for recovery_byte in [vec![], vec![0u8], vec![27u8], vec![1u8], vec![42u8]] {
+            let signature: Signature = (
+                KeyType::Ecdsa,
+                HexBinary::from([sig, recovery_byte.as_slice()].concat()),
+            )
+                .try_into()
+                .unwrap();
+
+            assert_eq!(
+                evm_signature(signature, digest.as_slice(), &pub_key)
+                    .unwrap()
+                    .as_ref(),
+                expected.as_slice()
+            );
+        }
+
+        let other_pub_key = test_data::operators().remove(0).pub_key;
+        let signature: Signature = (KeyType::Ecdsa, HexBinary::from(expected.clone()))
+            .try_into()
+            .unwrap();
+        assert!(matches!(
+            evm_signature(signature, digest.as_slice(), &other_pub_key),
+            Err(ContractError::InvalidSignature { .. })
+        ));
+    }
+
     #[test]
     fn should_convert_signature_to_recoverable() {
         let ethers_signature = EthersSignature::from_str("74ab5ec395cdafd861dec309c30f6cf8884fc9905eb861171e636d9797478adb60b2bfceb7db0a08769ed7a60006096d3e0f6d3783d125600ac6306180ecbc6f1b").unwrap();
@@ -0,0 +1,236 @@
+use std::fmt::Display;
+
+use bech32::FromBase32;
+use cosmwasm_std::HexBinary;
+
+use crate::error::ContractError;
+
+/// Format of the addresses of a destination chain. Every encoder decodes destination addresses in the format of its gateway
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressFormat {
+    /// 20 byte hex address with an optional 0x prefix
+    Evm,
+    /// 32 byte hex object id with an optional 0x prefix
+    Sui,
+    /// 32 byte hex address with an optional 0x prefix, whose leading zeros may be left out, e.g. 0x1
+    Aptos,
+    /// 32 byte base58 address, e.g. a Solana program id
+    Base58,
+    /// bech32 address with any human readable part
+    Bech32,
+    /// strkey of a Stellar contract, e.g. CAAA...
+    Stellar,
+}
+
+impl Display for AddressFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressFormat::Evm => write!(f, "20 byte hex EVM address"),
+            AddressFormat::Sui => write!(f, "32 byte hex Sui object id"),
+            AddressFormat::Aptos => write!(f, "hex Aptos address of at most 32 bytes"),
+            AddressFormat::Base58 => write!(f, "32 byte base58 address"),
+            AddressFormat::Bech32 => write!(f, "bech32 address"),
+            AddressFormat::Stellar => write!(f, "Stellar contract strkey"),
+        }
+    }
+}
+
+impl AddressFormat {
+    /// Returns the raw bytes of the address, or None if it isn't in this format
+    pub fn decode(self, address: &str) -> Option<Vec<u8>> {
+        match self {
+            AddressFormat::Evm => decode_hex(address).filter(|address| address.len() == 20),
+            AddressFormat::Sui => decode_hex(address).filter(|address| address.len() == 32),
+            AddressFormat::Aptos => {
+                let address = address.strip_prefix("0x").unwrap_or(address);
+                if address.is_empty() || address.len() > 64 {
+                    return None;
+                }
+                decode_hex(&format!("{:0>64}", address))
+            }
+            AddressFormat::Base58 => bs58::decode(address)
+                .into_vec()
+                .ok()
+                .filter(|address| address.len() == 32),
+            AddressFormat::Bech32 => bech32::decode(address)
+                .ok()
+                .and_then(|(_, data, _)| Vec::<u8>::from_base32(&data).ok()),
+            AddressFormat::Stellar => decode_strkey(address)
+                .filter(|(version, _)| *version == STRKEY_CONTRACT)
+                .map(|(_, payload)| payload.to_vec()),
+        }
+    }
+}
+
+/// Version byte of the strkey of a Stellar account
+pub(super) const STRKEY_ACCOUNT: u8 = 6 << 3;
+/// Version byte of the strkey of a Stellar contract
+pub(super) const STRKEY_CONTRACT: u8 = 2 << 3;
+
+/// Decodes a Stellar strkey with a 32 byte payload into its version byte and payload.
+/// A strkey is the base32 encoding of the version byte, the payload and a little-endian crc16 checksum of both
+pub(super) fn decode_strkey(address: &str) -> Option<(u8, [u8; 32])> {
+    if address.len() != 56 {
+        return None;
+    }
+
+    let decoded = decode_base32(address)?;
+    let (data, checksum) = decoded.split_at(33);
+    if crc16_xmodem(data).to_le_bytes() != checksum {
+        return None;
+    }
+
+    let payload =
+        <[u8; 32]>::try_from(&data[1..]).expect("violated invariant: payload is 32 bytes");
+    Some((data[0], payload))
+}
+
+// RFC 4648 base32 without padding
+fn decode_base32(input: &str) -> Option<Vec<u8>> {
+    let mut decoded = vec![];
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for char in input.bytes() {
+        let value = match char {
+            b'A'..=b'Z' => char - b'A',
+            b'2'..=b'7' => char - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(decoded)
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, byte| {
+        (0..8).fold(crc ^ (u16::from(*byte) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+fn decode_hex(address: &str) -> Option<Vec<u8>> {
+    HexBinary::from_hex(address.strip_prefix("0x").unwrap_or(address))
+        .ok()
+        .map(Vec::from)
+}
+
+pub(super) fn decode_destination_address(
+    destination_address: &str,
+    format: AddressFormat,
+) -> Result<Vec<u8>, ContractError> {
+    format
+        .decode(destination_address)
+        .ok_or_else(|| ContractError::InvalidMessage {
+            reason: format!(
+                "destination_address {} is not a valid address, expected a {}",
+                destination_address, format
+            ),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let sui_address = format!("0x{}", "ab".repeat(32));
+        let cases = [
+            (
+                AddressFormat::Evm,
+                "0x4F4495243837681061C4743b74B3eEdf548D56A5",
+                20,
+            ),
+            (
+                AddressFormat::Evm,
+                "4F4495243837681061C4743b74B3eEdf548D56A5",
+                20,
+            ),
+            (AddressFormat::Sui, sui_address.as_str(), 32),
+            (AddressFormat::Aptos, "0x1", 32),
+            (
+                AddressFormat::Base58,
+                "11111111111111111111111111111111",
+                32,
+            ),
+            (
+                AddressFormat::Bech32,
+                "axelar1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5mv6kda",
+                20,
+            ),
+            (
+                AddressFormat::Stellar,
+                "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4",
+                32,
+            ),
+        ];
+
+        for (format, address, len) in cases {
+            assert_eq!(
+                format.decode(address).map(|address| address.len()),
+                Some(len)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_other_formats() {
+        let evm_address = "0x4F4495243837681061C4743b74B3eEdf548D56A5";
+        let sui_address = "ab".repeat(32);
+
+        assert_eq!(AddressFormat::Evm.decode(&sui_address), None);
+        assert_eq!(AddressFormat::Sui.decode(evm_address), None);
+        assert_eq!(AddressFormat::Aptos.decode(&"ab".repeat(33)), None);
+        assert_eq!(AddressFormat::Base58.decode(evm_address), None);
+        assert_eq!(AddressFormat::Bech32.decode(evm_address), None);
+        assert_eq!(AddressFormat::Stellar.decode(&sui_address), None);
+    }
+
+    #[test]
+    fn test_decode_strkey() {
+        assert_eq!(
+            decode_strkey("GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"),
+            Some((STRKEY_ACCOUNT, [0; 32]))
+        );
+        assert_eq!(
+            decode_strkey("CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O"),
+            Some((
+                STRKEY_CONTRACT,
+                <[u8; 32]>::try_from((0..32).collect::<Vec<u8>>()).unwrap()
+            ))
+        );
+
+        // wrong checksum
+        assert_eq!(
+            decode_strkey("GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHG"),
+            None
+        );
+        // accounts are not contracts
+        assert_eq!(
+            AddressFormat::Stellar
+                .decode("GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_error_names_expected_format() {
+        assert_eq!(
+            decode_destination_address("invalid", AddressFormat::Evm).unwrap_err(),
+            ContractError::InvalidMessage {
+                reason: "destination_address invalid is not a valid address, expected a 20 byte hex EVM address"
+                    .to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use axelar_wasm_std::operators::Operators;
+use bcs::to_bytes;
+use cosmwasm_std::{HexBinary, Uint256};
+use itertools::Itertools;
+use multisig::{key::Signature, msg::Signer, worker_set::WorkerSet};
+use sha3::{Digest, Sha3_256};
+
+use crate::{
+    error::ContractError,
+    types::{CommandBatch, CommandType},
+};
+
+use super::{
+    address::decode_destination_address,
+    bcs::{
+        encode_proof, make_command_id, make_operators, transfer_operatorship_params, u256_to_u64,
+        with_recoverable_signatures,
+    },
+    AddressFormat, Codec, Data, Eip712Domain, FeePayer,
+};
+
+// type of the batch in the gateway module, Aptos domain-separates signed values by their type name
+const BATCH_TYPE_NAME: &str = "axelar::gateway::Batch";
+
+/// Encoding of the Aptos gateway module. It shares the BCS layout of signers and proofs with the Sui gateway,
+/// but names commands after the module's entry functions and signs batches the way Aptos signs values
+const ADDRESS_FORMAT: AddressFormat = AddressFormat::Aptos;
+
+pub struct AptosBcsCodec;
+
+impl Codec for AptosBcsCodec {
+    fn address_format(&self) -> AddressFormat {
+        ADDRESS_FORMAT
+    }
+
+    fn command_params(
+        &self,
+        source_chain: String,
+        source_address: String,
+        destination_address: String,
+        payload_hash: &[u8; 32],
+    ) -> Result<HexBinary, ContractError> {
+        command_params(
+            source_chain,
+            source_address,
+            destination_address,
+            payload_hash,
+        )
+    }
+
+    fn transfer_operatorship_params(
+        &self,
+        worker_set: &WorkerSet,
+    ) -> Result<HexBinary, ContractError> {
+        transfer_operatorship_params(worker_set)
+    }
+
+    fn make_operators(&self, worker_set: WorkerSet) -> Operators {
+        make_operators(worker_set)
+    }
+
+    fn encode(&self, data: &Data) -> HexBinary {
+        encode(data)
+    }
+
+    fn msg_digest(&self, command_batch: &CommandBatch) -> HexBinary {
+        msg_digest(command_batch)
+    }
+
+    fn validate_eip712_domain(&self, _domain: &Eip712Domain) -> Result<(), ContractError> {
+        Err(ContractError::InvalidEip712Domain {
+            reason: "typed data signing is only supported by evm gateways".to_string(),
+        })
+    }
+
+    fn encode_execute_data(
+        &self,
+        command_batch: &CommandBatch,
+        quorum: Uint256,
+        signers: Vec<(Signer, Option<Signature>)>,
+        fee_payer: FeePayer,
+    ) -> Result<HexBinary, ContractError> {
+        encode_execute_data(command_batch, quorum, signers, fee_payer)
+    }
+}
+
+fn command_params(
+    source_chain: String,
+    source_address: String,
+    destination_address: String,
+    payload_hash: &[u8; 32],
+) -> Result<HexBinary, ContractError> {
+    let destination_address = <[u8; 32]>::try_from(
+        decode_destination_address(&destination_address, ADDRESS_FORMAT)?.as_slice(),
+    )
+    .expect("violated invariant: Aptos addresses are 32 bytes");
+
+    Ok(to_bytes(&(
+        source_chain,
+        source_address,
+        destination_address,
+        payload_hash.to_vec(),
+    ))?
+    .into())
+}
+
+// name of the gateway module's entry function that executes the command
+fn command_type(ty: &CommandType) -> &'static str {
+    match ty {
+        CommandType::ApproveContractCall => "approve_contract_call",
+        CommandType::ApproveContractCallWithMint => {
+            panic!("violated invariant: the Aptos gateway doesn't support token transfers")
+        }
+        CommandType::TransferOperatorship => "rotate_signers",
+    }
+}
+
+fn encode(data: &Data) -> HexBinary {
+    let destination_chain_id = u256_to_u64(data.destination_chain_id);
+
+    let (commands_ids, command_types, command_params): (Vec<[u8; 32]>, Vec<String>, Vec<Vec<u8>>) =
+        data.commands
+            .iter()
+            .map(|command| {
+                (
+                    make_command_id(&command.id),
+                    command_type(&command.ty).to_string(),
+                    command.params.to_vec(),
+                )
+            })
+            .multiunzip();
+
+    to_bytes(&(
+        destination_chain_id,
+        commands_ids,
+        command_types,
+        command_params,
+    ))
+    .expect("couldn't encode batch as bcs")
+    .into()
+}
+
+// Aptos signs the bcs bytes of a value prefixed by the sha3-256 hash of "APTOS::" and the value's type name.
+// Workers sign the sha3-256 hash of that signing message, so the gateway can verify it with a fixed length input
+fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
+    let prefix = Sha3_256::digest(format!("APTOS::{}", BATCH_TYPE_NAME).as_bytes());
+    let signing_message = [prefix.as_slice(), encode(&command_batch.data).as_slice()].concat();
+
+    Sha3_256::digest(signing_message).as_slice().into()
+}
+
+fn encode_execute_data(
+    command_batch: &CommandBatch,
+    quorum: Uint256,
+    signers: Vec<(Signer, Option<Signature>)>,
+    fee_payer: FeePayer,
+) -> Result<HexBinary, ContractError> {
+    let signers = with_recoverable_signatures(command_batch, signers);
+    let data = encode(&command_batch.data).to_vec();
+    let proof = encode_proof(quorum, signers)?.to_vec();
+
+    let input = match fee_payer {
+        FeePayer::Unsupported => to_bytes(&(data, proof))?,
+        FeePayer::Unset => to_bytes(&(data, proof, [0u8; 32]))?,
+        FeePayer::Address(address) => {
+            let address = aptos_address(address).ok_or_else(|| ContractError::InvalidFeePayer {
+                reason: format!("not a valid Aptos address: {}", address),
+            })?;
+            to_bytes(&(data, proof, address))?
+        }
+    };
+    Ok(input.into())
+}
+
+fn aptos_address(address: &str) -> Option<[u8; 32]> {
+    ADDRESS_FORMAT
+        .decode(address)
+        .and_then(|address| <[u8; 32]>::try_from(address.as_slice()).ok())
+}
+
+#[cfg(test)]
+mod test {
+    use bcs::from_bytes;
+    use connection_router::state::Message;
+    use cosmwasm_std::{HexBinary, Uint256};
+    use sha3::{Digest, Sha3_256};
+
+    use crate::{
+        encoding::{CommandBatchBuilder, Data, Encoder, FeePayer},
+        error::ContractError,
+        test::test_data,
+        types::{BatchId, Command, CommandBatch, CommandType},
+    };
+
+    use super::{
+        command_params, encode, encode_execute_data, msg_digest, transfer_operatorship_params,
+    };
+
+    fn batch_with_message(destination_address: &str) -> CommandBatch {
+        let mut builder =
+            CommandBatchBuilder::new(test_data::prover(), 1u128.into(), Encoder::AptosBcs);
+        builder
+            .add_message(Message {
+                cc_id: "ethereum:foobar:1".parse().unwrap(),
+                destination_address: destination_address.parse().unwrap(),
+                destination_chain: "aptos".parse().unwrap(),
+                source_address: "0x00".parse().unwrap(),
+                payload_hash: [1; 32],
+                token: None,
+            })
+            .unwrap();
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_command_params_pads_short_addresses() {
+        let res = command_params("Ethereum".into(), "00".into(), "0x1".into(), &[2; 32]).unwrap();
+
+        let (_, _, destination_address, _): (String, String, [u8; 32], Vec<u8>) =
+            from_bytes(&res).unwrap();
+        let mut expected = [0u8; 32];
+        expected[31] = 1;
+        assert_eq!(destination_address, expected);
+    }
+
+    #[test]
+    fn test_invalid_destination_address() {
+        let too_long = "01".repeat(33);
+        for address in ["", "0x", "zz", too_long.as_str()] {
+            assert!(matches!(
+                command_params("Ethereum".into(), "00".into(), address.into(), &[2; 32]),
+                Err(ContractError::InvalidMessage { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_encode_uses_entry_function_names() {
+        let data = Data {
+            destination_chain_id: 1u64.into(),
+            commands: vec![
+                Command {
+                    id: HexBinary::from_hex(&"FF".repeat(32)).unwrap(),
+                    ty: CommandType::ApproveContractCall,
+                    params: command_params("Ethereum".into(), "AA".into(), "0x1".into(), &[0; 32])
+                        .unwrap(),
+                },
+                Command {
+                    id: HexBinary::from_hex(&"EE".repeat(32)).unwrap(),
+                    ty: CommandType::TransferOperatorship,
+                    params: transfer_operatorship_params(&test_data::new_worker_set()).unwrap(),
+                },
+            ],
+        };
+
+        type EncodedData = (u64, Vec<[u8; 32]>, Vec<String>, Vec<Vec<u8>>);
+        let (chain_id, command_ids, command_types, params): EncodedData =
+            from_bytes(&encode(&data)).unwrap();
+
+        assert_eq!(chain_id, 1);
+        assert_eq!(command_ids, vec![[0xff; 32], [0xee; 32]]);
+        assert_eq!(
+            command_types,
+            vec!["approve_contract_call", "rotate_signers"]
+        );
+        assert_eq!(
+            params,
+            data.commands
+                .iter()
+                .map(|command| command.params.to_vec())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_msg_digest() {
+        let batch = batch_with_message(&"0F".repeat(32));
+
+        let signing_message = [
+            Sha3_256::digest(b"APTOS::axelar::gateway::Batch").as_slice(),
+            encode(&batch.data).as_slice(),
+        ]
+        .concat();
+        assert_eq!(
+            msg_digest(&batch),
+            HexBinary::from(Sha3_256::digest(signing_message).as_slice())
+        );
+        assert_eq!(batch.msg_digest(), msg_digest(&batch));
+
+        assert_ne!(
+            msg_digest(&batch),
+            msg_digest(&batch_with_message(&"0A".repeat(32)))
+        );
+    }
+
+    #[test]
+    fn test_encode_execute_data_with_fee_payer() {
+        let command_batch = CommandBatch {
+            message_ids: vec![],
+            id: BatchId::new(&test_data::prover(), 1u32.into(), &[], None),
+            data: Data {
+                destination_chain_id: 1u32.into(),
+                commands: vec![],
+            },
+            encoder: Encoder::AptosBcs,
+            eip712_domain: None,
+        };
+        let quorum = Uint256::from(10u128);
+
+        let encoded =
+            encode_execute_data(&command_batch, quorum, vec![], FeePayer::Unsupported).unwrap();
+        let (data, _): (Vec<u8>, Vec<u8>) = from_bytes(encoded.as_slice()).unwrap();
+        assert_eq!(data, encode(&command_batch.data).to_vec());
+
+        let encoded =
+            encode_execute_data(&command_batch, quorum, vec![], FeePayer::Address("0xab")).unwrap();
+        let (_, _, fee_payer): (Vec<u8>, Vec<u8>, [u8; 32]) =
+            from_bytes(encoded.as_slice()).unwrap();
+        let mut expected = [0u8; 32];
+        expected[31] = 0xab;
+        assert_eq!(fee_payer, expected);
+
+        assert!(matches!(
+            encode_execute_data(
+                &command_batch,
+                quorum,
+                vec![],
+                FeePayer::Address("not an address")
+            ),
+            Err(ContractError::InvalidFeePayer { .. })
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use std::convert::identity;
+use std::convert::{identity, Infallible};
 
 use axelar_wasm_std::operators::Operators;
 use bcs::to_bytes;
@@ -7,32 +7,92 @@ use cosmwasm_std::{HexBinary, Uint256};
 use crate::error::ContractError;
 
 use itertools::Itertools;
-use multisig::{key::Signature, msg::Signer, worker_set::WorkerSet};
+use multisig::{
+    key::{PublicKey, Signature},
+    msg::Signer,
+    worker_set::WorkerSet,
+};
 
 use crate::types::{CommandBatch, Operator};
 
-use super::Data;
+use super::{
+    address::decode_destination_address, AddressFormat, Codec, Data, Eip712Domain, FeePayer,
+};
 use sha3::{Digest, Keccak256};
 
-// TODO: all of the public functions in this file should be moved to a trait,
-// that has an abi and bcs implementation (and possibly others)
+const ADDRESS_FORMAT: AddressFormat = AddressFormat::Sui;
 
-pub fn make_operators(worker_set: WorkerSet) -> Operators {
-    let operators: Vec<(HexBinary, Uint256)> = worker_set
-        .signers
-        .values()
-        .map(|signer| (signer.pub_key.clone().into(), signer.weight))
-        .collect();
+pub struct BcsCodec;
+
+impl Codec for BcsCodec {
+    fn address_format(&self) -> AddressFormat {
+        ADDRESS_FORMAT
+    }
+
+    fn command_params(
+        &self,
+        source_chain: String,
+        source_address: String,
+        destination_address: String,
+        payload_hash: &[u8; 32],
+    ) -> Result<HexBinary, ContractError> {
+        command_params(
+            source_chain,
+            source_address,
+            destination_address,
+            payload_hash,
+        )
+    }
+
+    fn transfer_operatorship_params(
+        &self,
+        worker_set: &WorkerSet,
+    ) -> Result<HexBinary, ContractError> {
+        transfer_operatorship_params(worker_set)
+    }
+
+    fn make_operators(&self, worker_set: WorkerSet) -> Operators {
+        make_operators(worker_set)
+    }
+
+    fn encode(&self, data: &Data) -> HexBinary {
+        encode(data)
+    }
+
+    fn msg_digest(&self, command_batch: &CommandBatch) -> HexBinary {
+        msg_digest(command_batch)
+    }
+
+    fn validate_eip712_domain(&self, _domain: &Eip712Domain) -> Result<(), ContractError> {
+        Err(ContractError::InvalidEip712Domain {
+            reason: "typed data signing is only supported by evm gateways".to_string(),
+        })
+    }
+
+    fn encode_execute_data(
+        &self,
+        command_batch: &CommandBatch,
+        quorum: Uint256,
+        signers: Vec<(Signer, Option<Signature>)>,
+        fee_payer: FeePayer,
+    ) -> Result<HexBinary, ContractError> {
+        encode_execute_data(command_batch, quorum, signers, fee_payer)
+    }
+}
+
+pub(super) fn make_operators(worker_set: WorkerSet) -> Operators {
+    let operators = worker_set
+        .operators(pub_key_address)
+        .unwrap_or_else(|err: Infallible| match err {});
     Operators::new(operators, worker_set.threshold)
 }
 
-pub fn transfer_operatorship_params(worker_set: &WorkerSet) -> Result<HexBinary, ContractError> {
-    let mut operators: Vec<(HexBinary, Uint256)> = worker_set
-        .signers
-        .values()
-        .map(|signer| (signer.pub_key.clone().into(), signer.weight))
-        .collect();
-    operators.sort_by_key(|op| op.0.clone());
+pub(super) fn transfer_operatorship_params(
+    worker_set: &WorkerSet,
+) -> Result<HexBinary, ContractError> {
+    let operators = worker_set
+        .operators(pub_key_address)
+        .unwrap_or_else(|err: Infallible| match err {});
     let (addresses, weights): (Vec<Vec<u8>>, Vec<_>) = operators
         .into_iter()
         .map(|(pub_key, weight)| (pub_key.to_vec(), u256_to_u128(weight)))
@@ -41,8 +101,7 @@ pub fn transfer_operatorship_params(worker_set: &WorkerSet) -> Result<HexBinary,
     Ok(to_bytes(&(addresses, weights, u256_to_u128(worker_set.threshold)))?.into())
 }
 
-#[allow(dead_code)]
-fn encode_proof(
+pub(super) fn encode_proof(
     quorum: Uint256,
     signers: Vec<(Signer, Option<Signature>)>,
 ) -> Result<HexBinary, ContractError> {
@@ -65,6 +124,11 @@ fn encode_proof(
     Ok(to_bytes(&(addresses, weights, quorum, signatures))?.into())
 }
 
+// the gateway identifies operators by their public key
+fn pub_key_address(pub_key: &PublicKey) -> Result<HexBinary, Infallible> {
+    Ok(pub_key.clone().into())
+}
+
 fn make_operators_with_sigs(signers_with_sigs: Vec<(Signer, Option<Signature>)>) -> Vec<Operator> {
     signers_with_sigs
         .into_iter()
@@ -76,26 +140,16 @@ fn make_operators_with_sigs(signers_with_sigs: Vec<(Signer, Option<Signature>)>)
         .collect()
 }
 
-pub fn command_params(
+fn command_params(
     source_chain: String,
     source_address: String,
     destination_address: String,
     payload_hash: &[u8; 32],
 ) -> Result<HexBinary, ContractError> {
     let destination_address = <[u8; 32]>::try_from(
-        HexBinary::from_hex(
-            destination_address
-                .strip_prefix("0x")
-                .unwrap_or(&destination_address),
-        )?
-        .to_vec(),
+        decode_destination_address(&destination_address, ADDRESS_FORMAT)?.as_slice(),
     )
-    .map_err(|_| ContractError::InvalidMessage {
-        reason: format!(
-            "destination_address is not a valid Sui address: {}",
-            destination_address
-        ),
-    })?;
+    .expect("violated invariant: Sui addresses are 32 bytes");
 
     Ok(to_bytes(&(
         source_chain,
@@ -107,7 +161,7 @@ pub fn command_params(
     .into())
 }
 
-fn make_command_id(command_id: &HexBinary) -> [u8; 32] {
+pub(super) fn make_command_id(command_id: &HexBinary) -> [u8; 32] {
     // command-ids are fixed length sequences
     command_id
         .to_vec()
@@ -115,7 +169,7 @@ fn make_command_id(command_id: &HexBinary) -> [u8; 32] {
         .expect("couldn't convert command id to 32 byte array")
 }
 
-pub fn encode(data: &Data) -> HexBinary {
+fn encode(data: &Data) -> HexBinary {
     // destination chain id must be u64 for sui
     let destination_chain_id = u256_to_u64(data.destination_chain_id);
 
@@ -141,7 +195,7 @@ pub fn encode(data: &Data) -> HexBinary {
     .into()
 }
 
-pub fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
+fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
     // Sui is just mimicking EVM here
     let unsigned = [
         "\x19Sui Signed Message:\n".as_bytes(), // Keccek256 hash length = 32
@@ -151,41 +205,61 @@ pub fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
 
     Keccak256::digest(unsigned).as_slice().into()
 }
-pub fn encode_execute_data(
+fn encode_execute_data(
     command_batch: &CommandBatch,
     quorum: Uint256,
     signers: Vec<(Signer, Option<Signature>)>,
+    fee_payer: FeePayer,
 ) -> Result<HexBinary, ContractError> {
-    let signers = signers
+    let signers = with_recoverable_signatures(command_batch, signers);
+    let data = encode(&command_batch.data).to_vec();
+    let proof = encode_proof(quorum, signers)?.to_vec();
+
+    let input = match fee_payer {
+        FeePayer::Unsupported => to_bytes(&(data, proof))?,
+        FeePayer::Unset => to_bytes(&(data, proof, [0u8; 32]))?,
+        FeePayer::Address(address) => to_bytes(&(data, proof, sui_address(address)?))?,
+    };
+    Ok(input.into())
+}
+
+// the gateway recovers ecdsa signers, so the signatures need a recovery byte
+pub(super) fn with_recoverable_signatures(
+    command_batch: &CommandBatch,
+    signers: Vec<(Signer, Option<Signature>)>,
+) -> Vec<(Signer, Option<Signature>)> {
+    let msg_digest = command_batch.msg_digest();
+
+    signers
         .into_iter()
         .map(|(signer, signature)| {
             let mut signature = signature;
             if let Some(Signature::Ecdsa(nonrecoverable)) = signature {
                 signature = nonrecoverable
-                    .to_recoverable(
-                        command_batch.msg_digest().as_slice(),
-                        &signer.pub_key,
-                        identity,
-                    )
+                    .to_recoverable(msg_digest.as_slice(), &signer.pub_key, identity)
                     .map(Signature::EcdsaRecoverable)
                     .ok();
             }
 
             (signer, signature)
         })
-        .collect::<Vec<_>>();
-    let input = to_bytes(&(
-        encode(&command_batch.data).to_vec(),
-        encode_proof(quorum, signers)?.to_vec(),
-    ))?;
-    Ok(input.into())
+        .collect()
 }
 
-fn u256_to_u128(val: Uint256) -> u128 {
+fn sui_address(address: &str) -> Result<[u8; 32], ContractError> {
+    ADDRESS_FORMAT
+        .decode(address)
+        .and_then(|address| <[u8; 32]>::try_from(address.as_slice()).ok())
+        .ok_or_else(|| ContractError::InvalidFeePayer {
+            reason: format!("not a valid Sui address: {}", address),
+        })
+}
+
+pub(super) fn u256_to_u128(val: Uint256) -> u128 {
     val.to_string().parse().expect("value is larger than u128")
 }
 
-fn u256_to_u64(chain_id: Uint256) -> u64 {
+pub(super) fn u256_to_u64(chain_id: Uint256) -> u64 {
     chain_id
         .to_string()
         .parse()
@@ -213,8 +287,9 @@ mod test {
                 command_params, encode, encode_execute_data, encode_proof, make_command_id,
                 make_operators, transfer_operatorship_params, u256_to_u128, u256_to_u64,
             },
-            CommandBatchBuilder, Data,
+            CommandBatchBuilder, Data, FeePayer,
         },
+        error::ContractError,
         test::test_data,
         types::{BatchId, Command, CommandBatch},
     };
@@ -475,7 +550,11 @@ mod test {
 
     #[test]
     fn test_msg_to_sign() {
-        let mut builder = CommandBatchBuilder::new(1u128.into(), crate::encoding::Encoder::Bcs);
+        let mut builder = CommandBatchBuilder::new(
+            test_data::prover(),
+            1u128.into(),
+            crate::encoding::Encoder::Bcs,
+        );
         let _ = builder
             .add_message(Message {
                 cc_id: "ethereum:foobar:1".parse().unwrap(),
@@ -483,13 +562,18 @@ mod test {
                 destination_chain: "sui".parse().unwrap(),
                 source_address: "0x00".parse().unwrap(),
                 payload_hash: [1; 32],
+                token: None,
             })
             .unwrap();
         let batch = builder.build().unwrap();
         let msg = msg_digest(&batch);
         assert_eq!(msg.len(), 32);
 
-        let mut builder = CommandBatchBuilder::new(1u128.into(), crate::encoding::Encoder::Bcs);
+        let mut builder = CommandBatchBuilder::new(
+            test_data::prover(),
+            1u128.into(),
+            crate::encoding::Encoder::Bcs,
+        );
         let _ = builder
             .add_message(Message {
                 cc_id: "ethereum:foobar:2".parse().unwrap(),
@@ -497,6 +581,7 @@ mod test {
                 destination_chain: "sui".parse().unwrap(),
                 source_address: "0x00".parse().unwrap(),
                 payload_hash: [2; 32],
+                token: None,
             })
             .unwrap();
 
@@ -538,6 +623,8 @@ mod test {
         let command_batch = CommandBatch {
             message_ids: vec![],
             id: BatchId::new(
+                &test_data::prover(),
+                1u32.into(),
                 &vec![CrossChainId {
                     chain: "AXELAR".to_string().try_into().unwrap(),
                     id: "foobar".to_string().try_into().unwrap(),
@@ -546,6 +633,7 @@ mod test {
             ),
             data,
             encoder: crate::encoding::Encoder::Bcs,
+            eip712_domain: None,
         };
         let quorum = 10u128;
 
@@ -565,10 +653,54 @@ mod test {
             &command_batch,
             Uint256::from(quorum),
             vec![(signer, Some(signature))],
+            FeePayer::Unsupported,
         );
         assert!(encoded.is_ok());
         let encoded = encoded.unwrap();
         assert_eq!(encoded.len(), approval.to_vec().len());
         assert_eq!(encoded.to_vec(), approval.to_vec());
     }
+
+    #[test]
+    fn test_encode_execute_data_with_fee_payer() {
+        let command_batch = CommandBatch {
+            message_ids: vec![],
+            id: BatchId::new(&test_data::prover(), 1u32.into(), &[], None),
+            data: Data {
+                destination_chain_id: 1u32.into(),
+                commands: vec![],
+            },
+            encoder: crate::encoding::Encoder::Bcs,
+            eip712_domain: None,
+        };
+        let quorum = Uint256::from(10u128);
+
+        let decode_fee_payer = |encoded: HexBinary| {
+            from_bytes::<(Vec<u8>, Vec<u8>, [u8; 32])>(encoded.as_slice())
+                .unwrap()
+                .2
+        };
+
+        let encoded = encode_execute_data(
+            &command_batch,
+            quorum,
+            vec![],
+            FeePayer::Address(&format!("0x{}", "ab".repeat(32))),
+        )
+        .unwrap();
+        assert_eq!(decode_fee_payer(encoded), [0xab; 32]);
+
+        let encoded = encode_execute_data(&command_batch, quorum, vec![], FeePayer::Unset).unwrap();
+        assert_eq!(decode_fee_payer(encoded), [0; 32]);
+
+        assert!(matches!(
+            encode_execute_data(
+                &command_batch,
+                quorum,
+                vec![],
+                FeePayer::Address(&"ab".repeat(20))
+            ),
+            Err(ContractError::InvalidFeePayer { .. })
+        ));
+    }
 }
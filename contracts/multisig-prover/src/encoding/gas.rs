@@ -0,0 +1,121 @@
+use cosmwasm_schema::cw_serde;
+
+use crate::types::{CommandBatch, CommandType};
+
+/// Rough cost of submitting execute data to the destination gateway, so relayers can set a gas limit
+/// and decide whether relaying a batch pays off
+#[cw_serde]
+pub struct ExecutionEstimate {
+    /// size of the execute data in bytes
+    pub calldata_size: u64,
+    /// gas needed to execute the batch, None if there is no gas heuristic for the destination gateway
+    pub gas: Option<u64>,
+}
+
+/// Per-item gas costs of a destination gateway. The costs are heuristics, not an exact metering of the gateway
+pub(super) struct GasSchedule {
+    /// fixed cost of the transaction submitting the execute data
+    pub transaction: u64,
+    pub calldata_zero_byte: u64,
+    pub calldata_nonzero_byte: u64,
+    /// cost of verifying one signature of the proof
+    pub signature: u64,
+    pub approve_contract_call: u64,
+    pub approve_contract_call_with_mint: u64,
+    pub transfer_operatorship: u64,
+}
+
+// calldata costs as of EIP-2028, the rest approximates the storage writes and events of the axelar gateway
+pub(super) const EVM_GAS_SCHEDULE: GasSchedule = GasSchedule {
+    transaction: 21_000,
+    calldata_zero_byte: 4,
+    calldata_nonzero_byte: 16,
+    signature: 6_000,
+    approve_contract_call: 45_000,
+    approve_contract_call_with_mint: 75_000,
+    transfer_operatorship: 70_000,
+};
+
+impl GasSchedule {
+    fn estimate(&self, command_batch: &CommandBatch, execute_data: &[u8], signatures: u64) -> u64 {
+        let calldata = execute_data
+            .iter()
+            .map(|byte| match byte {
+                0 => self.calldata_zero_byte,
+                _ => self.calldata_nonzero_byte,
+            })
+            .sum::<u64>();
+
+        let commands = command_batch
+            .data
+            .commands
+            .iter()
+            .map(|command| match command.ty {
+                CommandType::ApproveContractCall => self.approve_contract_call,
+                CommandType::ApproveContractCallWithMint => self.approve_contract_call_with_mint,
+                CommandType::TransferOperatorship => self.transfer_operatorship,
+            })
+            .sum::<u64>();
+
+        self.transaction + calldata + signatures * self.signature + commands
+    }
+}
+
+pub(super) fn estimate_execution(
+    schedule: Option<&GasSchedule>,
+    command_batch: &CommandBatch,
+    execute_data: &[u8],
+    signatures: u64,
+) -> ExecutionEstimate {
+    ExecutionEstimate {
+        calldata_size: execute_data.len() as u64,
+        gas: schedule.map(|schedule| schedule.estimate(command_batch, execute_data, signatures)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{HexBinary, Uint256};
+
+    use crate::{
+        encoding::{Data, Encoder},
+        types::Command,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_estimate_execution() {
+        let command = |ty| Command {
+            id: HexBinary::from_hex("01").unwrap(),
+            ty,
+            params: HexBinary::from_hex("02").unwrap(),
+        };
+        let batch = CommandBatch {
+            id: HexBinary::from_hex("00").unwrap().into(),
+            message_ids: vec![],
+            data: Data {
+                destination_chain_id: Uint256::one(),
+                commands: vec![
+                    command(CommandType::ApproveContractCall),
+                    command(CommandType::ApproveContractCallWithMint),
+                    command(CommandType::TransferOperatorship),
+                ],
+            },
+            encoder: Encoder::Abi,
+            eip712_domain: None,
+        };
+        let execute_data = [0, 0, 1, 2];
+
+        let estimate = estimate_execution(Some(&EVM_GAS_SCHEDULE), &batch, &execute_data, 3);
+        assert_eq!(estimate.calldata_size, 4);
+        assert_eq!(
+            estimate.gas,
+            Some(21_000 + 2 * 4 + 2 * 16 + 3 * 6_000 + 45_000 + 75_000 + 70_000)
+        );
+
+        let estimate = estimate_execution(None, &batch, &execute_data, 3);
+        assert_eq!(estimate.calldata_size, 4);
+        assert_eq!(estimate.gas, None);
+    }
+}
@@ -1,13 +1,25 @@
 mod abi;
+mod address;
+mod aptos;
 mod bcs;
+mod gas;
+mod solana;
+mod stellar;
 
 use axelar_wasm_std::operators::Operators;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{HexBinary, Uint256};
+use cosmwasm_std::{Addr, HexBinary, Uint256};
 use sha3::{Digest, Keccak256};
 
-use connection_router::state::{CrossChainId, Message};
-use multisig::{key::Signature, msg::Signer, worker_set::WorkerSet};
+use connection_router::state::{CrossChainId, Message, TokenTransfer};
+use multisig::{
+    key::{KeyType, Signature},
+    msg::Signer,
+    worker_set::WorkerSet,
+};
+
+pub use address::AddressFormat;
+pub use gas::ExecutionEstimate;
 
 use crate::{
     error::ContractError,
@@ -19,73 +31,223 @@ use crate::{
 pub enum Encoder {
     Abi,
     Bcs,
+    AptosBcs,
+    Solana,
+    StellarXdr,
 }
 
-fn make_command(msg: Message, encoding: Encoder) -> Result<Command, ContractError> {
-    Ok(Command {
-        ty: CommandType::ApproveContractCall, // TODO: this would change when other command types are supported
-        params: match encoding {
-            Encoder::Abi => abi::command_params(
-                msg.cc_id.chain.to_string(),
-                msg.source_address.to_string(),
-                msg.destination_address.to_string(),
+impl Encoder {
+    // every encoder is registered here, the rest of the module only goes through the codec
+    fn codec(self) -> &'static dyn Codec {
+        match self {
+            Encoder::Abi => &abi::AbiCodec,
+            Encoder::Bcs => &bcs::BcsCodec,
+            Encoder::AptosBcs => &aptos::AptosBcsCodec,
+            Encoder::Solana => &solana::SolanaCodec,
+            Encoder::StellarXdr => &stellar::StellarXdrCodec,
+        }
+    }
+
+    /// Format of the destination addresses of messages, any other address fails the message when building a batch
+    pub fn address_format(self) -> AddressFormat {
+        self.codec().address_format()
+    }
+}
+
+/// Encoding rules of a destination chain's gateway. Supporting a new chain means implementing this trait
+/// in a new module and registering the implementation with a new `Encoder` variant in `Encoder::codec`
+trait Codec {
+    fn address_format(&self) -> AddressFormat;
+
+    fn command_params(
+        &self,
+        source_chain: String,
+        source_address: String,
+        destination_address: String,
+        payload_hash: &[u8; 32],
+    ) -> Result<HexBinary, ContractError>;
+
+    // only gateways that can mint tokens support contract calls that carry a token transfer
+    fn command_params_with_mint(
+        &self,
+        _source_chain: String,
+        _source_address: String,
+        _destination_address: String,
+        _payload_hash: &[u8; 32],
+        _token: &TokenTransfer,
+    ) -> Result<HexBinary, ContractError> {
+        Err(ContractError::InvalidMessage {
+            reason: "the destination gateway doesn't support token transfers".to_string(),
+        })
+    }
+
+    fn transfer_operatorship_params(
+        &self,
+        worker_set: &WorkerSet,
+    ) -> Result<HexBinary, ContractError>;
+
+    fn make_operators(&self, worker_set: WorkerSet) -> Operators;
+
+    fn encode(&self, data: &Data) -> HexBinary;
+
+    fn msg_digest(&self, command_batch: &CommandBatch) -> HexBinary;
+
+    fn validate_eip712_domain(&self, domain: &Eip712Domain) -> Result<(), ContractError>;
+
+    // the key type that workers sign with, most gateways can verify ecdsa and ed25519 signatures
+    fn validate_key_type(&self, key_type: KeyType) -> Result<(), ContractError> {
+        match key_type {
+            KeyType::Ecdsa | KeyType::Ed25519 => Ok(()),
+            KeyType::Bls12_381 => Err(ContractError::InvalidKeyType {
+                reason: "no gateway supports bls12-381 signers".to_string(),
+            }),
+        }
+    }
+
+    fn encode_execute_data(
+        &self,
+        command_batch: &CommandBatch,
+        quorum: Uint256,
+        signers: Vec<(Signer, Option<Signature>)>,
+        fee_payer: FeePayer,
+    ) -> Result<HexBinary, ContractError>;
+
+    // heuristic costs of executing a batch on the gateway, None if there is no gas estimate for the destination chain
+    fn gas_schedule(&self) -> Option<&'static gas::GasSchedule> {
+        None
+    }
+}
+
+/// EIP-712 domain of the destination gateway. The chain id of the domain is the destination chain id
+#[cw_serde]
+pub struct Eip712Domain {
+    pub name: String,
+    pub version: String,
+    pub verifying_contract: String,
+}
+
+/// Fee recipient field of the execute data, for destination gateways that refund the relayer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeePayer<'a> {
+    /// The destination gateway doesn't support a fee payer, so the field is left out
+    Unsupported,
+    /// The field is set to the zero address, so no fees are refunded
+    Unset,
+    Address(&'a str),
+}
+
+// messages that carry a token transfer are approved together with minting the token to the destination contract
+fn make_command(
+    msg: Message,
+    encoding: Encoder,
+    prover: &Addr,
+    destination_chain_id: Uint256,
+) -> Result<Command, ContractError> {
+    let source_chain = msg.cc_id.chain.to_string();
+    let source_address = msg.source_address.to_string();
+    let destination_address = msg.destination_address.to_string();
+
+    let (ty, params) = match &msg.token {
+        None => (
+            CommandType::ApproveContractCall,
+            encoding.codec().command_params(
+                source_chain,
+                source_address,
+                destination_address,
                 &msg.payload_hash,
             )?,
-            Encoder::Bcs => bcs::command_params(
-                msg.cc_id.chain.to_string(),
-                msg.source_address.to_string(),
-                msg.destination_address.to_string(),
+        ),
+        Some(token) => (
+            CommandType::ApproveContractCallWithMint,
+            encoding.codec().command_params_with_mint(
+                source_chain,
+                source_address,
+                destination_address,
                 &msg.payload_hash,
+                token,
             )?,
-        },
-        id: command_id(msg.cc_id.to_string()),
+        ),
+    };
+
+    Ok(Command {
+        ty,
+        params,
+        id: command_id(prover, destination_chain_id, msg.cc_id.to_string()),
     })
 }
 
 fn make_transfer_operatorship(
     worker_set: WorkerSet,
     encoding: Encoder,
+    prover: &Addr,
+    destination_chain_id: Uint256,
 ) -> Result<Command, ContractError> {
-    let params = match encoding {
-        Encoder::Abi => abi::transfer_operatorship_params(&worker_set),
-        Encoder::Bcs => bcs::transfer_operatorship_params(&worker_set),
-    }?;
+    let params = encoding.codec().transfer_operatorship_params(&worker_set)?;
     Ok(Command {
         ty: CommandType::TransferOperatorship,
         params,
-        id: worker_set.hash(),
+        id: command_id(prover, destination_chain_id, worker_set.hash().to_string()),
     })
 }
 
 pub struct CommandBatchBuilder {
+    prover: Addr,
     message_ids: Vec<CrossChainId>,
     new_worker_set: Option<WorkerSet>,
     commands: Vec<Command>,
     destination_chain_id: Uint256,
     encoding: Encoder,
+    eip712_domain: Option<Eip712Domain>,
 }
 
 impl CommandBatchBuilder {
-    pub fn new(destination_chain_id: Uint256, encoding: Encoder) -> Self {
+    pub fn new(prover: Addr, destination_chain_id: Uint256, encoding: Encoder) -> Self {
         Self {
+            prover,
             message_ids: vec![],
             new_worker_set: None,
             commands: vec![],
             destination_chain_id,
             encoding,
+            eip712_domain: None,
+        }
+    }
+
+    /// The batch is signed as EIP-712 typed data of the given domain instead of an EIP-191 personal message
+    pub fn with_eip712_domain(self, eip712_domain: Option<Eip712Domain>) -> Self {
+        Self {
+            eip712_domain,
+            ..self
         }
     }
 
     pub fn add_message(&mut self, msg: Message) -> Result<(), ContractError> {
         self.message_ids.push(msg.cc_id.clone());
-        self.commands.push(make_command(msg, self.encoding)?);
+        self.commands.push(make_command(
+            msg,
+            self.encoding,
+            &self.prover,
+            self.destination_chain_id,
+        )?);
         Ok(())
     }
 
+    /// The rotation is always the first command of the batch, so the gateway executes it before any approval of the same batch
     pub fn add_new_worker_set(&mut self, worker_set: WorkerSet) -> Result<(), ContractError> {
+        if self.new_worker_set.is_some() {
+            return Err(ContractError::DuplicateWorkerSetRotation);
+        }
+
         self.new_worker_set = Some(worker_set.clone());
-        self.commands
-            .push(make_transfer_operatorship(worker_set, self.encoding)?);
+        self.commands.insert(
+            0,
+            make_transfer_operatorship(
+                worker_set,
+                self.encoding,
+                &self.prover,
+                self.destination_chain_id,
+            )?,
+        );
         Ok(())
     }
 
@@ -95,34 +257,60 @@ impl CommandBatchBuilder {
             commands: self.commands,
         };
 
-        let id = BatchId::new(&self.message_ids, self.new_worker_set);
+        let id = BatchId::new(
+            &self.prover,
+            self.destination_chain_id,
+            &self.message_ids,
+            self.new_worker_set,
+        );
 
         Ok(CommandBatch {
             id,
             message_ids: self.message_ids,
             data,
             encoder: self.encoding,
+            eip712_domain: self.eip712_domain,
         })
     }
 }
 
 impl CommandBatch {
     pub fn msg_digest(&self) -> HexBinary {
-        match self.encoder {
-            Encoder::Abi => abi::msg_digest(self),
-            Encoder::Bcs => bcs::msg_digest(self),
-        }
+        self.encoder.codec().msg_digest(self)
+    }
+
+    /// A batch that transfers operatorship hands over control of the destination gateway,
+    /// so it is signed in a high-value session that must be confirmed after a delay
+    pub fn is_high_value(&self) -> bool {
+        self.data
+            .commands
+            .iter()
+            .any(|command| command.ty == CommandType::TransferOperatorship)
     }
 
     pub fn encode_execute_data(
         &self,
         quorum: Uint256,
         signers: Vec<(Signer, Option<Signature>)>,
+        fee_payer: FeePayer,
     ) -> Result<HexBinary, ContractError> {
-        match self.encoder {
-            Encoder::Abi => abi::encode_execute_data(self, quorum, signers),
-            Encoder::Bcs => bcs::encode_execute_data(self, quorum, signers),
-        }
+        self.encoder
+            .codec()
+            .encode_execute_data(self, quorum, signers, fee_payer)
+    }
+
+    /// Estimates the cost of submitting the execute data of this batch, signed with the given number of signatures
+    pub fn estimate_execution(
+        &self,
+        execute_data: &HexBinary,
+        signatures: u64,
+    ) -> ExecutionEstimate {
+        gas::estimate_execution(
+            self.encoder.codec().gas_schedule(),
+            self,
+            execute_data.as_slice(),
+            signatures,
+        )
     }
 }
 
@@ -134,23 +322,32 @@ pub struct Data {
 
 impl Data {
     pub fn encode(&self, encoder: Encoder) -> HexBinary {
-        match encoder {
-            Encoder::Abi => abi::encode(self),
-            Encoder::Bcs => bcs::encode(self),
-        }
+        encoder.codec().encode(self)
     }
 }
 
-fn command_id(message_id: String) -> HexBinary {
+// Command ids are domain-separated by the prover and the destination chain id. Every codec signs the command ids as part of the data,
+// so the same messages routed through different provers or to different chains never produce the same digest
+fn command_id(prover: &Addr, destination_chain_id: Uint256, id: String) -> HexBinary {
     // TODO: we might need to change the command id format to match the one in core for migration purposes
-    Keccak256::digest(message_id.as_bytes()).as_slice().into()
+    Keccak256::digest([prover.to_string(), destination_chain_id.to_string(), id].join(","))
+        .as_slice()
+        .into()
+}
+
+pub fn validate_eip712_domain(
+    domain: &Eip712Domain,
+    encoder: Encoder,
+) -> Result<(), ContractError> {
+    encoder.codec().validate_eip712_domain(domain)
+}
+
+pub fn validate_key_type(key_type: KeyType, encoder: Encoder) -> Result<(), ContractError> {
+    encoder.codec().validate_key_type(key_type)
 }
 
 pub fn make_operators(worker_set: WorkerSet, encoder: Encoder) -> Operators {
-    match encoder {
-        Encoder::Abi => abi::make_operators(worker_set),
-        Encoder::Bcs => bcs::make_operators(worker_set),
-    }
+    encoder.codec().make_operators(worker_set)
 }
 
 #[cfg(test)]
@@ -166,50 +363,254 @@ mod test {
         let mut message_ids: Vec<CrossChainId> =
             messages.into_iter().map(|msg| msg.cc_id).collect();
 
-        let res = BatchId::new(&message_ids, None);
+        let res = BatchId::new(
+            &test_data::prover(),
+            test_data::destination_chain_id(),
+            &message_ids,
+            None,
+        );
 
         message_ids.reverse();
-        let res2 = BatchId::new(&message_ids, None);
+        let res2 = BatchId::new(
+            &test_data::prover(),
+            test_data::destination_chain_id(),
+            &message_ids,
+            None,
+        );
 
         assert_eq!(res, res2);
     }
 
+    #[test]
+    fn test_batch_id_is_domain_separated() {
+        let message_ids: Vec<CrossChainId> = test_data::messages()
+            .into_iter()
+            .map(|msg| msg.cc_id)
+            .collect();
+        let batch_id = |prover: &Addr, destination_chain_id: Uint256| {
+            BatchId::new(prover, destination_chain_id, &message_ids, None)
+        };
+
+        let res = batch_id(&test_data::prover(), test_data::destination_chain_id());
+
+        assert_ne!(
+            res,
+            batch_id(&Addr::unchecked("other"), test_data::destination_chain_id())
+        );
+        assert_ne!(res, batch_id(&test_data::prover(), Uint256::one()));
+    }
+
     #[test]
     fn test_command_from_router_message() {
         let messages = test_data::messages();
         let router_message = messages.first().unwrap();
 
-        let res = make_command(router_message.to_owned(), Encoder::Abi);
+        let res = make_command(
+            router_message.to_owned(),
+            Encoder::Abi,
+            &test_data::prover(),
+            test_data::destination_chain_id(),
+        );
         assert!(res.is_ok());
 
         let res = res.unwrap();
 
         assert_eq!(
             res.id,
-            HexBinary::from_hex("3ee2f8af2201994e3518c9ce6848774785c2eef3bdbf9f954899497616dd59af")
+            HexBinary::from_hex("ff4c4421f7c4a5a3a7c118e83ce2181645a0a6cc39db019a5a7c864c29fd09fe")
                 .unwrap()
         );
         assert_eq!(res.ty, CommandType::ApproveContractCall);
 
         let mut router_message = router_message.to_owned();
         router_message.destination_address = "FF".repeat(32).parse().unwrap();
-        let res = make_command(router_message.to_owned(), Encoder::Bcs);
+        let res = make_command(
+            router_message.to_owned(),
+            Encoder::Bcs,
+            &test_data::prover(),
+            test_data::destination_chain_id(),
+        );
         assert!(res.is_ok());
 
         let res = res.unwrap();
 
         assert_eq!(
             res.id,
-            HexBinary::from_hex("3ee2f8af2201994e3518c9ce6848774785c2eef3bdbf9f954899497616dd59af")
+            HexBinary::from_hex("ff4c4421f7c4a5a3a7c118e83ce2181645a0a6cc39db019a5a7c864c29fd09fe")
                 .unwrap()
         );
         assert_eq!(res.ty, CommandType::ApproveContractCall);
     }
 
+    #[test]
+    fn test_msg_digest_is_domain_separated() {
+        let digest = |prover: Addr, destination_chain_id: Uint256| {
+            let mut builder = CommandBatchBuilder::new(prover, destination_chain_id, Encoder::Abi);
+            for msg in test_data::messages() {
+                builder.add_message(msg).unwrap();
+            }
+            builder.build().unwrap().msg_digest()
+        };
+
+        let res = digest(test_data::prover(), test_data::destination_chain_id());
+
+        assert_ne!(
+            res,
+            digest(Addr::unchecked("other"), test_data::destination_chain_id())
+        );
+        assert_ne!(res, digest(test_data::prover(), Uint256::one()));
+    }
+
+    #[test]
+    fn test_command_with_token_from_router_message() {
+        let router_message = test_data::messages().first().unwrap().to_owned();
+        let message_with_token = Message {
+            token: Some(TokenTransfer {
+                symbol: "AXL".try_into().unwrap(),
+                amount: Uint256::from(100u128).try_into().unwrap(),
+            }),
+            ..router_message.clone()
+        };
+
+        let res = make_command(
+            message_with_token.clone(),
+            Encoder::Abi,
+            &test_data::prover(),
+            test_data::destination_chain_id(),
+        )
+        .unwrap();
+        assert_eq!(res.ty, CommandType::ApproveContractCallWithMint);
+        assert_eq!(
+            res.id,
+            make_command(
+                router_message,
+                Encoder::Abi,
+                &test_data::prover(),
+                test_data::destination_chain_id()
+            )
+            .unwrap()
+            .id
+        );
+
+        assert!(matches!(
+            make_command(
+                message_with_token,
+                Encoder::Bcs,
+                &test_data::prover(),
+                test_data::destination_chain_id()
+            ),
+            Err(ContractError::InvalidMessage { .. })
+        ));
+    }
+
+    #[test]
+    fn test_destination_address_format_of_encoder() {
+        let router_message = test_data::messages().first().unwrap().to_owned();
+        assert_eq!(Encoder::Abi.address_format(), AddressFormat::Evm);
+        assert!(Encoder::Abi
+            .address_format()
+            .decode(router_message.destination_address.as_str())
+            .is_some());
+
+        // the EVM address is too short for a Sui object id and no base58 address
+        for encoder in [Encoder::Bcs, Encoder::Solana] {
+            let res = make_command(
+                router_message.clone(),
+                encoder,
+                &test_data::prover(),
+                test_data::destination_chain_id(),
+            );
+            assert_eq!(
+                res.unwrap_err(),
+                ContractError::InvalidMessage {
+                    reason: format!(
+                        "destination_address {} is not a valid address, expected a {}",
+                        router_message.destination_address.as_str(),
+                        encoder.address_format()
+                    )
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotation_is_first_command_of_batch() {
+        let mut builder =
+            CommandBatchBuilder::new(test_data::prover(), Uint256::one(), Encoder::Abi);
+        for msg in test_data::messages() {
+            builder.add_message(msg).unwrap();
+        }
+        builder
+            .add_new_worker_set(test_data::new_worker_set())
+            .unwrap();
+
+        assert_eq!(
+            builder.add_new_worker_set(test_data::new_worker_set()),
+            Err(ContractError::DuplicateWorkerSetRotation)
+        );
+
+        let batch = builder.build().unwrap();
+        assert_eq!(batch.data.commands[0].ty, CommandType::TransferOperatorship);
+        assert!(batch.data.commands[1..]
+            .iter()
+            .all(|command| command.ty == CommandType::ApproveContractCall));
+    }
+
+    #[test]
+    fn test_batch_with_rotation_is_high_value() {
+        let build_batch = |rotate: bool| {
+            let mut builder =
+                CommandBatchBuilder::new(test_data::prover(), Uint256::one(), Encoder::Abi);
+            if rotate {
+                builder
+                    .add_new_worker_set(test_data::new_worker_set())
+                    .unwrap();
+            }
+            for msg in test_data::messages() {
+                builder.add_message(msg).unwrap();
+            }
+            builder.build().unwrap()
+        };
+
+        assert!(!build_batch(false).is_high_value());
+        assert!(build_batch(true).is_high_value());
+    }
+
+    #[test]
+    fn test_validate_key_type() {
+        for encoder in [Encoder::Abi, Encoder::Bcs, Encoder::AptosBcs] {
+            assert!(validate_key_type(KeyType::Ecdsa, encoder).is_ok());
+            assert!(validate_key_type(KeyType::Ed25519, encoder).is_ok());
+        }
+
+        for encoder in [Encoder::Solana, Encoder::StellarXdr] {
+            assert!(validate_key_type(KeyType::Ecdsa, encoder).is_err());
+            assert!(validate_key_type(KeyType::Ed25519, encoder).is_ok());
+        }
+
+        for encoder in [
+            Encoder::Abi,
+            Encoder::Bcs,
+            Encoder::AptosBcs,
+            Encoder::Solana,
+            Encoder::StellarXdr,
+        ] {
+            assert!(matches!(
+                validate_key_type(KeyType::Bls12_381, encoder),
+                Err(ContractError::InvalidKeyType { .. })
+            ));
+        }
+    }
+
     #[test]
     fn test_command_operator_transfer() {
         let new_worker_set = test_data::new_worker_set();
-        let res = make_transfer_operatorship(new_worker_set.clone(), Encoder::Abi);
+        let res = make_transfer_operatorship(
+            new_worker_set.clone(),
+            Encoder::Abi,
+            &test_data::prover(),
+            test_data::destination_chain_id(),
+        );
         assert!(res.is_ok());
 
         assert_eq!(res.unwrap().ty, CommandType::TransferOperatorship);
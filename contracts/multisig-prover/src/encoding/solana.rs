@@ -0,0 +1,477 @@
+use axelar_wasm_std::operators::Operators;
+use borsh::BorshSerialize;
+use cosmwasm_std::{HexBinary, Uint256};
+use multisig::{
+    key::{KeyType, PublicKey, Signature},
+    msg::Signer,
+    worker_set::WorkerSet,
+};
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    error::ContractError,
+    types::{CommandBatch, CommandType},
+};
+
+use super::{
+    address::decode_destination_address,
+    bcs::{make_command_id, make_operators, u256_to_u128, u256_to_u64},
+    AddressFormat, Codec, Data, Eip712Domain, FeePayer,
+};
+
+const ADDRESS_FORMAT: AddressFormat = AddressFormat::Base58;
+
+/// Encoding of the Solana gateway program. Everything is serialized with borsh,
+/// and the gateway verifies the signatures of an ed25519 signer set
+pub struct SolanaCodec;
+
+impl Codec for SolanaCodec {
+    fn address_format(&self) -> AddressFormat {
+        ADDRESS_FORMAT
+    }
+
+    fn command_params(
+        &self,
+        source_chain: String,
+        source_address: String,
+        destination_address: String,
+        payload_hash: &[u8; 32],
+    ) -> Result<HexBinary, ContractError> {
+        command_params(
+            source_chain,
+            source_address,
+            destination_address,
+            payload_hash,
+        )
+    }
+
+    fn transfer_operatorship_params(
+        &self,
+        worker_set: &WorkerSet,
+    ) -> Result<HexBinary, ContractError> {
+        transfer_operatorship_params(worker_set)
+    }
+
+    fn make_operators(&self, worker_set: WorkerSet) -> Operators {
+        make_operators(worker_set)
+    }
+
+    fn encode(&self, data: &Data) -> HexBinary {
+        encode(data)
+    }
+
+    fn msg_digest(&self, command_batch: &CommandBatch) -> HexBinary {
+        msg_digest(command_batch)
+    }
+
+    fn validate_eip712_domain(&self, _domain: &Eip712Domain) -> Result<(), ContractError> {
+        Err(ContractError::InvalidEip712Domain {
+            reason: "typed data signing is only supported by evm gateways".to_string(),
+        })
+    }
+
+    fn validate_key_type(&self, key_type: KeyType) -> Result<(), ContractError> {
+        match key_type {
+            KeyType::Ed25519 => Ok(()),
+            _ => Err(ContractError::InvalidKeyType {
+                reason: "solana gateways only support ed25519 signers".to_string(),
+            }),
+        }
+    }
+
+    fn encode_execute_data(
+        &self,
+        command_batch: &CommandBatch,
+        quorum: Uint256,
+        signers: Vec<(Signer, Option<Signature>)>,
+        fee_payer: FeePayer,
+    ) -> Result<HexBinary, ContractError> {
+        encode_execute_data(command_batch, quorum, signers, fee_payer)
+    }
+}
+
+#[derive(BorshSerialize)]
+struct ApproveContractCallParams {
+    source_chain: String,
+    source_address: String,
+    destination_program: [u8; 32],
+    payload_hash: [u8; 32],
+}
+
+#[derive(BorshSerialize)]
+struct TransferOperatorshipParams {
+    operators: Vec<[u8; 32]>,
+    weights: Vec<u128>,
+    quorum: u128,
+}
+
+// borsh encodes the variant as a single byte in declaration order, which the gateway program matches on
+#[derive(BorshSerialize)]
+enum SolanaCommandType {
+    ApproveContractCall,
+    TransferOperatorship,
+}
+
+#[derive(BorshSerialize)]
+struct SolanaCommand {
+    id: [u8; 32],
+    ty: SolanaCommandType,
+    params: Vec<u8>,
+}
+
+#[derive(BorshSerialize)]
+struct SolanaCommandBatch {
+    chain_id: u64,
+    commands: Vec<SolanaCommand>,
+}
+
+// signatures are aligned with the operators, so the gateway knows which key to verify each signature with
+#[derive(BorshSerialize)]
+struct Proof {
+    operators: Vec<[u8; 32]>,
+    weights: Vec<u128>,
+    quorum: u128,
+    signatures: Vec<Option<[u8; 64]>>,
+}
+
+fn command_params(
+    source_chain: String,
+    source_address: String,
+    destination_address: String,
+    payload_hash: &[u8; 32],
+) -> Result<HexBinary, ContractError> {
+    let destination_program = <[u8; 32]>::try_from(
+        decode_destination_address(&destination_address, ADDRESS_FORMAT)?.as_slice(),
+    )
+    .expect("violated invariant: base58 addresses are 32 bytes");
+
+    Ok(borsh::to_vec(&ApproveContractCallParams {
+        source_chain,
+        source_address,
+        destination_program,
+        payload_hash: *payload_hash,
+    })
+    .expect("couldn't serialize command as borsh")
+    .into())
+}
+
+fn transfer_operatorship_params(worker_set: &WorkerSet) -> Result<HexBinary, ContractError> {
+    let (operators, weights): (Vec<[u8; 32]>, Vec<u128>) = worker_set
+        .operators(ed25519_pub_key)?
+        .into_iter()
+        .map(|(pub_key, weight)| {
+            (
+                <[u8; 32]>::try_from(pub_key.as_slice())
+                    .expect("violated invariant: ed25519 public key is not 32 bytes"),
+                u256_to_u128(weight),
+            )
+        })
+        .unzip();
+
+    Ok(borsh::to_vec(&TransferOperatorshipParams {
+        operators,
+        weights,
+        quorum: u256_to_u128(worker_set.threshold),
+    })
+    .expect("couldn't serialize command as borsh")
+    .into())
+}
+
+fn encode(data: &Data) -> HexBinary {
+    let commands = data
+        .commands
+        .iter()
+        .map(|command| SolanaCommand {
+            id: make_command_id(&command.id),
+            ty: match command.ty {
+                CommandType::ApproveContractCall => SolanaCommandType::ApproveContractCall,
+                CommandType::ApproveContractCallWithMint => {
+                    panic!("violated invariant: the Solana gateway doesn't support token transfers")
+                }
+                CommandType::TransferOperatorship => SolanaCommandType::TransferOperatorship,
+            },
+            params: command.params.to_vec(),
+        })
+        .collect();
+
+    borsh::to_vec(&SolanaCommandBatch {
+        chain_id: u256_to_u64(data.destination_chain_id),
+        commands,
+    })
+    .expect("couldn't encode batch as borsh")
+    .into()
+}
+
+fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
+    let unsigned = [
+        "\x19Solana Signed Message:\n".as_bytes(),
+        encode(&command_batch.data).as_slice(),
+    ]
+    .concat();
+
+    Keccak256::digest(unsigned).as_slice().into()
+}
+
+fn encode_proof(
+    quorum: Uint256,
+    signers: Vec<(Signer, Option<Signature>)>,
+) -> Result<Proof, ContractError> {
+    let mut operators = signers
+        .into_iter()
+        .map(|(signer, signature)| {
+            let pub_key = ed25519_pub_key(&signer.pub_key)?;
+            let signature = signature.map(ed25519_signature).transpose()?;
+            Ok((pub_key, signer.weight, signature))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+    operators.sort_by(|(a, _, _), (b, _, _)| a.cmp(b)); // same order as the operators of the signer set
+
+    let mut proof = Proof {
+        operators: vec![],
+        weights: vec![],
+        quorum: u256_to_u128(quorum),
+        signatures: vec![],
+    };
+    for (pub_key, weight, signature) in operators {
+        proof.operators.push(
+            <[u8; 32]>::try_from(pub_key.as_slice())
+                .expect("violated invariant: ed25519 public key is not 32 bytes"),
+        );
+        proof.weights.push(u256_to_u128(weight));
+        proof.signatures.push(signature);
+    }
+
+    Ok(proof)
+}
+
+fn encode_execute_data(
+    command_batch: &CommandBatch,
+    quorum: Uint256,
+    signers: Vec<(Signer, Option<Signature>)>,
+    fee_payer: FeePayer,
+) -> Result<HexBinary, ContractError> {
+    let data = encode(&command_batch.data).to_vec();
+    let proof = encode_proof(quorum, signers)?;
+
+    let input = match fee_payer {
+        FeePayer::Unsupported => borsh::to_vec(&(data, proof)),
+        FeePayer::Unset => borsh::to_vec(&(data, proof, [0u8; 32])),
+        FeePayer::Address(address) => {
+            let address =
+                solana_address(address).ok_or_else(|| ContractError::InvalidFeePayer {
+                    reason: format!("not a valid Solana address: {}", address),
+                })?;
+            borsh::to_vec(&(data, proof, address))
+        }
+    }
+    .expect("couldn't encode execute data as borsh");
+
+    Ok(input.into())
+}
+
+// the gateway identifies operators by their ed25519 public key
+fn ed25519_pub_key(pub_key: &PublicKey) -> Result<HexBinary, ContractError> {
+    match pub_key {
+        PublicKey::Ed25519(pub_key) if pub_key.len() == 32 => Ok(pub_key.clone()),
+        _ => Err(ContractError::InvalidPublicKey {
+            reason: "solana gateways only support ed25519 signers".to_string(),
+        }),
+    }
+}
+
+fn ed25519_signature(signature: Signature) -> Result<[u8; 64], ContractError> {
+    match signature {
+        Signature::Ed25519(signature) => <[u8; 64]>::try_from(signature.as_slice()).map_err(|_| {
+            ContractError::InvalidSignature {
+                reason: "ed25519 signature is not 64 bytes".to_string(),
+            }
+        }),
+        _ => Err(ContractError::InvalidSignature {
+            reason: "solana gateways only support ed25519 signatures".to_string(),
+        }),
+    }
+}
+
+// Solana addresses are base58 encoded 32 byte public keys
+fn solana_address(address: &str) -> Option<[u8; 32]> {
+    ADDRESS_FORMAT
+        .decode(address)
+        .and_then(|address| <[u8; 32]>::try_from(address.as_slice()).ok())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use cosmwasm_std::{Addr, HexBinary, Uint256};
+    use multisig::{
+        key::{PublicKey, Signature},
+        msg::Signer,
+        worker_set::WorkerSet,
+    };
+
+    use crate::{
+        encoding::{Data, Encoder, FeePayer},
+        error::ContractError,
+        test::test_data,
+        types::{BatchId, Command, CommandBatch, CommandType},
+    };
+
+    use super::{
+        command_params, encode, encode_execute_data, msg_digest, transfer_operatorship_params,
+    };
+
+    const PROGRAM_ID: &str = "gtwgM94UYHwBh3g7rWi1tcpkgELxHQRLPpPHsaECW57";
+
+    fn ed25519_signer(pub_key: u8) -> Signer {
+        Signer {
+            address: Addr::unchecked(format!("signer{}", pub_key)),
+            weight: Uint256::one(),
+            pub_key: PublicKey::Ed25519(HexBinary::from(vec![pub_key; 32])),
+        }
+    }
+
+    fn command_batch(commands: Vec<Command>) -> CommandBatch {
+        CommandBatch {
+            message_ids: vec![],
+            id: BatchId::new(&test_data::prover(), 1u32.into(), &[], None),
+            data: Data {
+                destination_chain_id: 1u32.into(),
+                commands,
+            },
+            encoder: Encoder::Solana,
+            eip712_domain: None,
+        }
+    }
+
+    #[test]
+    fn test_command_params() {
+        let res = command_params("Ethereum".into(), "00".into(), PROGRAM_ID.into(), &[2; 32]);
+
+        let (source_chain, source_address, destination_program, payload_hash): (
+            String,
+            String,
+            [u8; 32],
+            [u8; 32],
+        ) = borsh::from_slice(&res.unwrap()).unwrap();
+        assert_eq!(source_chain, "Ethereum");
+        assert_eq!(source_address, "00");
+        assert_eq!(
+            destination_program.to_vec(),
+            bs58::decode(PROGRAM_ID).into_vec().unwrap()
+        );
+        assert_eq!(payload_hash, [2; 32]);
+
+        assert!(matches!(
+            command_params("Ethereum".into(), "00".into(), "ff".repeat(32), &[2; 32]),
+            Err(ContractError::InvalidMessage { .. })
+        ));
+    }
+
+    #[test]
+    fn test_transfer_operatorship_params() {
+        let worker_set = WorkerSet {
+            signers: BTreeMap::from_iter([2u8, 1u8].map(|pub_key| {
+                let signer = ed25519_signer(pub_key);
+                (signer.address.to_string(), signer)
+            })),
+            threshold: Uint256::from(2u128),
+            created_at: 1,
+        };
+
+        let (operators, weights, quorum): (Vec<[u8; 32]>, Vec<u128>, u128) =
+            borsh::from_slice(&transfer_operatorship_params(&worker_set).unwrap()).unwrap();
+        assert_eq!(operators, vec![[1; 32], [2; 32]]);
+        assert_eq!(weights, vec![1, 1]);
+        assert_eq!(quorum, 2);
+
+        assert!(matches!(
+            transfer_operatorship_params(&test_data::new_worker_set()),
+            Err(ContractError::InvalidPublicKey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_encode() {
+        let data = Data {
+            destination_chain_id: 5u32.into(),
+            commands: vec![Command {
+                id: HexBinary::from(vec![0xff; 32]),
+                ty: CommandType::TransferOperatorship,
+                params: HexBinary::from(vec![1, 2, 3]),
+            }],
+        };
+
+        type EncodedData = (u64, Vec<([u8; 32], u8, Vec<u8>)>);
+        let (chain_id, commands): EncodedData = borsh::from_slice(&encode(&data)).unwrap();
+        assert_eq!(chain_id, 5);
+        assert_eq!(commands, vec![([0xff; 32], 1, vec![1, 2, 3])]);
+    }
+
+    #[test]
+    fn test_msg_digest() {
+        let batch = command_batch(vec![]);
+        assert_eq!(msg_digest(&batch).len(), 32);
+        assert_eq!(batch.msg_digest(), msg_digest(&batch));
+
+        let other_batch = command_batch(vec![Command {
+            id: HexBinary::from(vec![0xff; 32]),
+            ty: CommandType::ApproveContractCall,
+            params: HexBinary::from(vec![1, 2, 3]),
+        }]);
+        assert_ne!(msg_digest(&batch), msg_digest(&other_batch));
+    }
+
+    #[test]
+    fn test_encode_execute_data() {
+        let batch = command_batch(vec![]);
+        let signature = [7u8; 64];
+
+        let encoded = encode_execute_data(
+            &batch,
+            Uint256::one(),
+            vec![
+                (ed25519_signer(2), None),
+                (
+                    ed25519_signer(1),
+                    Some(Signature::Ed25519(HexBinary::from(signature.to_vec()))),
+                ),
+            ],
+            FeePayer::Address(PROGRAM_ID),
+        )
+        .unwrap();
+
+        type Proof = (Vec<[u8; 32]>, Vec<u128>, u128, Vec<Option<[u8; 64]>>);
+        let (data, proof, fee_payer): (Vec<u8>, Proof, [u8; 32]) =
+            borsh::from_slice(&encoded).unwrap();
+        assert_eq!(data, encode(&batch.data).to_vec());
+        assert_eq!(
+            proof,
+            (
+                vec![[1; 32], [2; 32]],
+                vec![1, 1],
+                1,
+                vec![Some(signature), None]
+            )
+        );
+        assert_eq!(
+            fee_payer.to_vec(),
+            bs58::decode(PROGRAM_ID).into_vec().unwrap()
+        );
+
+        let ecdsa_signer = Signer {
+            address: Addr::unchecked("ecdsa"),
+            weight: Uint256::one(),
+            pub_key: test_data::operators().remove(0).pub_key,
+        };
+        assert!(matches!(
+            encode_execute_data(
+                &batch,
+                Uint256::one(),
+                vec![(ecdsa_signer, None)],
+                FeePayer::Unsupported
+            ),
+            Err(ContractError::InvalidPublicKey { .. })
+        ));
+    }
+}
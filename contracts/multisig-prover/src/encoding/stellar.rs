@@ -0,0 +1,528 @@
+use axelar_wasm_std::operators::Operators;
+use cosmwasm_std::{HexBinary, Uint256};
+use multisig::{
+    key::{KeyType, PublicKey, Signature},
+    msg::Signer,
+    worker_set::WorkerSet,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::ContractError,
+    types::{CommandBatch, CommandType},
+};
+
+use super::{
+    address::{decode_destination_address, decode_strkey, STRKEY_ACCOUNT, STRKEY_CONTRACT},
+    bcs::{make_command_id, make_operators, u256_to_u128, u256_to_u64},
+    AddressFormat, Codec, Data, Eip712Domain, FeePayer,
+};
+
+const ADDRESS_FORMAT: AddressFormat = AddressFormat::Stellar;
+
+/// Encoding of the Stellar gateway contract. Everything is serialized as XDR, the way Soroban contracts read their inputs,
+/// and the gateway verifies the signatures of an ed25519 signer set
+pub struct StellarXdrCodec;
+
+impl Codec for StellarXdrCodec {
+    fn address_format(&self) -> AddressFormat {
+        ADDRESS_FORMAT
+    }
+
+    fn command_params(
+        &self,
+        source_chain: String,
+        source_address: String,
+        destination_address: String,
+        payload_hash: &[u8; 32],
+    ) -> Result<HexBinary, ContractError> {
+        command_params(
+            source_chain,
+            source_address,
+            destination_address,
+            payload_hash,
+        )
+    }
+
+    fn transfer_operatorship_params(
+        &self,
+        worker_set: &WorkerSet,
+    ) -> Result<HexBinary, ContractError> {
+        transfer_operatorship_params(worker_set)
+    }
+
+    fn make_operators(&self, worker_set: WorkerSet) -> Operators {
+        make_operators(worker_set)
+    }
+
+    fn encode(&self, data: &Data) -> HexBinary {
+        encode(data)
+    }
+
+    fn msg_digest(&self, command_batch: &CommandBatch) -> HexBinary {
+        msg_digest(command_batch)
+    }
+
+    fn validate_eip712_domain(&self, _domain: &Eip712Domain) -> Result<(), ContractError> {
+        Err(ContractError::InvalidEip712Domain {
+            reason: "typed data signing is only supported by evm gateways".to_string(),
+        })
+    }
+
+    fn validate_key_type(&self, key_type: KeyType) -> Result<(), ContractError> {
+        match key_type {
+            KeyType::Ed25519 => Ok(()),
+            _ => Err(ContractError::InvalidKeyType {
+                reason: "stellar gateways only support ed25519 signers".to_string(),
+            }),
+        }
+    }
+
+    fn encode_execute_data(
+        &self,
+        command_batch: &CommandBatch,
+        quorum: Uint256,
+        signers: Vec<(Signer, Option<Signature>)>,
+        fee_payer: FeePayer,
+    ) -> Result<HexBinary, ContractError> {
+        encode_execute_data(command_batch, quorum, signers, fee_payer)
+    }
+}
+
+// XDR writes every value big-endian, and pads opaque data and strings with zeros to a multiple of 4 bytes
+#[derive(Default)]
+struct XdrWriter(Vec<u8>);
+
+impl XdrWriter {
+    fn u32(&mut self, value: u32) -> &mut Self {
+        self.0.extend(value.to_be_bytes());
+        self
+    }
+
+    fn u64(&mut self, value: u64) -> &mut Self {
+        self.0.extend(value.to_be_bytes());
+        self
+    }
+
+    // Soroban's UInt128Parts, the high half followed by the low half
+    fn u128(&mut self, value: u128) -> &mut Self {
+        self.u64((value >> 64) as u64).u64(value as u64)
+    }
+
+    fn fixed_opaque(&mut self, bytes: &[u8]) -> &mut Self {
+        self.0.extend(bytes);
+        self.0.extend(vec![0; (4 - bytes.len() % 4) % 4]);
+        self
+    }
+
+    fn var_opaque(&mut self, bytes: &[u8]) -> &mut Self {
+        self.u32(bytes.len() as u32).fixed_opaque(bytes)
+    }
+
+    fn string(&mut self, value: &str) -> &mut Self {
+        self.var_opaque(value.as_bytes())
+    }
+
+    fn array<T>(&mut self, items: &[T], write: impl Fn(&mut Self, &T)) -> &mut Self {
+        self.u32(items.len() as u32);
+        for item in items {
+            write(self, item);
+        }
+        self
+    }
+
+    fn optional<T>(&mut self, value: &Option<T>, write: impl Fn(&mut Self, &T)) -> &mut Self {
+        match value {
+            None => self.u32(0),
+            Some(value) => {
+                self.u32(1);
+                write(self, value);
+                self
+            }
+        }
+    }
+
+    // ScAddress, a union of an ed25519 account id and a contract hash
+    fn sc_address(&mut self, address: &StellarAddress) -> &mut Self {
+        match address {
+            StellarAddress::Account(pub_key) => self.u32(0).u32(0).fixed_opaque(pub_key),
+            StellarAddress::Contract(hash) => self.u32(1).fixed_opaque(hash),
+        }
+    }
+
+    fn into_bytes(self) -> HexBinary {
+        self.0.into()
+    }
+}
+
+enum StellarAddress {
+    Account([u8; 32]),
+    Contract([u8; 32]),
+}
+
+impl StellarAddress {
+    fn from_strkey(address: &str) -> Option<Self> {
+        match decode_strkey(address)? {
+            (STRKEY_ACCOUNT, pub_key) => Some(StellarAddress::Account(pub_key)),
+            (STRKEY_CONTRACT, hash) => Some(StellarAddress::Contract(hash)),
+            _ => None,
+        }
+    }
+}
+
+fn command_params(
+    source_chain: String,
+    source_address: String,
+    destination_address: String,
+    payload_hash: &[u8; 32],
+) -> Result<HexBinary, ContractError> {
+    let destination_contract = <[u8; 32]>::try_from(
+        decode_destination_address(&destination_address, ADDRESS_FORMAT)?.as_slice(),
+    )
+    .expect("violated invariant: contract strkeys are 32 bytes");
+
+    let mut writer = XdrWriter::default();
+    writer
+        .string(&source_chain)
+        .string(&source_address)
+        .sc_address(&StellarAddress::Contract(destination_contract))
+        .fixed_opaque(payload_hash);
+    Ok(writer.into_bytes())
+}
+
+fn transfer_operatorship_params(worker_set: &WorkerSet) -> Result<HexBinary, ContractError> {
+    let signers = worker_set
+        .operators(ed25519_pub_key)?
+        .into_iter()
+        .map(|(pub_key, weight)| (pub_key, u256_to_u128(weight)))
+        .collect::<Vec<_>>();
+
+    let mut writer = XdrWriter::default();
+    writer
+        .array(&signers, |writer, (pub_key, weight)| {
+            writer.fixed_opaque(pub_key).u128(*weight);
+        })
+        .u128(u256_to_u128(worker_set.threshold));
+    Ok(writer.into_bytes())
+}
+
+// the gateway matches on the command type as an XDR enum
+fn command_type(ty: &CommandType) -> u32 {
+    match ty {
+        CommandType::ApproveContractCall => 0,
+        CommandType::TransferOperatorship => 1,
+        CommandType::ApproveContractCallWithMint => {
+            panic!("violated invariant: the Stellar gateway doesn't support token transfers")
+        }
+    }
+}
+
+fn encode(data: &Data) -> HexBinary {
+    let mut writer = XdrWriter::default();
+    writer
+        .u64(u256_to_u64(data.destination_chain_id))
+        .array(&data.commands, |writer, command| {
+            writer
+                .fixed_opaque(&make_command_id(&command.id))
+                .u32(command_type(&command.ty))
+                .var_opaque(&command.params);
+        });
+    writer.into_bytes()
+}
+
+// Soroban hashes authorization payloads with sha256, so the gateway verifies the signers' signatures
+// over the sha256 hash of the batch it approves
+fn msg_digest(command_batch: &CommandBatch) -> HexBinary {
+    Sha256::digest(encode(&command_batch.data).as_slice())
+        .as_slice()
+        .into()
+}
+
+fn encode_execute_data(
+    command_batch: &CommandBatch,
+    quorum: Uint256,
+    signers: Vec<(Signer, Option<Signature>)>,
+    fee_payer: FeePayer,
+) -> Result<HexBinary, ContractError> {
+    let mut operators = signers
+        .into_iter()
+        .map(|(signer, signature)| {
+            let pub_key = ed25519_pub_key(&signer.pub_key)?;
+            let signature = signature.map(ed25519_signature).transpose()?;
+            Ok((pub_key, u256_to_u128(signer.weight), signature))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+    operators.sort_by(|(a, _, _), (b, _, _)| a.cmp(b)); // same order as the signers of the signer set
+
+    let mut writer = XdrWriter::default();
+    writer
+        .var_opaque(&encode(&command_batch.data))
+        .array(&operators, |writer, (pub_key, weight, signature)| {
+            writer
+                .fixed_opaque(pub_key)
+                .u128(*weight)
+                .optional(signature, |writer, signature| {
+                    writer.fixed_opaque(signature);
+                });
+        })
+        .u128(u256_to_u128(quorum));
+
+    match fee_payer {
+        FeePayer::Unsupported => {}
+        FeePayer::Unset => {
+            writer.sc_address(&StellarAddress::Account([0; 32]));
+        }
+        FeePayer::Address(address) => {
+            let address = StellarAddress::from_strkey(address).ok_or_else(|| {
+                ContractError::InvalidFeePayer {
+                    reason: format!("not a valid Stellar address: {}", address),
+                }
+            })?;
+            writer.sc_address(&address);
+        }
+    }
+
+    Ok(writer.into_bytes())
+}
+
+// the gateway identifies signers by their ed25519 public key
+fn ed25519_pub_key(pub_key: &PublicKey) -> Result<HexBinary, ContractError> {
+    match pub_key {
+        PublicKey::Ed25519(pub_key) if pub_key.len() == 32 => Ok(pub_key.clone()),
+        _ => Err(ContractError::InvalidPublicKey {
+            reason: "stellar gateways only support ed25519 signers".to_string(),
+        }),
+    }
+}
+
+fn ed25519_signature(signature: Signature) -> Result<[u8; 64], ContractError> {
+    match signature {
+        Signature::Ed25519(signature) => <[u8; 64]>::try_from(signature.as_slice()).map_err(|_| {
+            ContractError::InvalidSignature {
+                reason: "ed25519 signature is not 64 bytes".to_string(),
+            }
+        }),
+        _ => Err(ContractError::InvalidSignature {
+            reason: "stellar gateways only support ed25519 signatures".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use cosmwasm_std::{Addr, HexBinary, Uint256};
+    use multisig::{
+        key::{PublicKey, Signature},
+        msg::Signer,
+        worker_set::WorkerSet,
+    };
+    use sha2::{Digest, Sha256};
+
+    use crate::{
+        encoding::{Data, Encoder, FeePayer},
+        error::ContractError,
+        test::test_data,
+        types::{BatchId, Command, CommandBatch, CommandType},
+    };
+
+    use super::{
+        command_params, encode, encode_execute_data, msg_digest, transfer_operatorship_params,
+    };
+
+    // strkeys of the contract and the account whose 32 byte payload is all zeros
+    const CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+    const ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+    fn ed25519_signer(pub_key: u8) -> Signer {
+        Signer {
+            address: Addr::unchecked(format!("signer{}", pub_key)),
+            weight: Uint256::one(),
+            pub_key: PublicKey::Ed25519(HexBinary::from(vec![pub_key; 32])),
+        }
+    }
+
+    fn command_batch(commands: Vec<Command>) -> CommandBatch {
+        CommandBatch {
+            message_ids: vec![],
+            id: BatchId::new(&test_data::prover(), 1u32.into(), &[], None),
+            data: Data {
+                destination_chain_id: 1u32.into(),
+                commands,
+            },
+            encoder: Encoder::StellarXdr,
+            eip712_domain: None,
+        }
+    }
+
+    fn u128_parts(value: u128) -> Vec<u8> {
+        value.to_be_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_command_params() {
+        let res = command_params("Ethereum".into(), "00".into(), CONTRACT.into(), &[2; 32]);
+
+        let expected = [
+            vec![0, 0, 0, 8],
+            b"Ethereum".to_vec(),
+            vec![0, 0, 0, 2],
+            b"00\0\0".to_vec(),
+            vec![0, 0, 0, 1],
+            vec![0; 32],
+            vec![2; 32],
+        ]
+        .concat();
+        assert_eq!(res.unwrap(), HexBinary::from(expected));
+
+        assert!(matches!(
+            command_params("Ethereum".into(), "00".into(), ACCOUNT.into(), &[2; 32]),
+            Err(ContractError::InvalidMessage { .. })
+        ));
+    }
+
+    #[test]
+    fn test_transfer_operatorship_params() {
+        let worker_set = WorkerSet {
+            signers: BTreeMap::from_iter([2u8, 1u8].map(|pub_key| {
+                let signer = ed25519_signer(pub_key);
+                (signer.address.to_string(), signer)
+            })),
+            threshold: Uint256::from(2u128),
+            created_at: 1,
+        };
+
+        let expected = [
+            vec![0, 0, 0, 2],
+            vec![1; 32],
+            u128_parts(1),
+            vec![2; 32],
+            u128_parts(1),
+            u128_parts(2),
+        ]
+        .concat();
+        assert_eq!(
+            transfer_operatorship_params(&worker_set).unwrap(),
+            HexBinary::from(expected)
+        );
+
+        assert!(matches!(
+            transfer_operatorship_params(&test_data::new_worker_set()),
+            Err(ContractError::InvalidPublicKey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_encode() {
+        let data = Data {
+            destination_chain_id: 5u32.into(),
+            commands: vec![Command {
+                id: HexBinary::from(vec![0xff; 32]),
+                ty: CommandType::TransferOperatorship,
+                params: HexBinary::from(vec![1, 2, 3]),
+            }],
+        };
+
+        let expected = [
+            vec![0, 0, 0, 0, 0, 0, 0, 5],
+            vec![0, 0, 0, 1],
+            vec![0xff; 32],
+            vec![0, 0, 0, 1],
+            vec![0, 0, 0, 3, 1, 2, 3, 0],
+        ]
+        .concat();
+        assert_eq!(encode(&data), HexBinary::from(expected));
+    }
+
+    #[test]
+    fn test_msg_digest() {
+        let batch = command_batch(vec![]);
+        assert_eq!(
+            msg_digest(&batch),
+            HexBinary::from(Sha256::digest(encode(&batch.data).as_slice()).as_slice())
+        );
+        assert_eq!(batch.msg_digest(), msg_digest(&batch));
+
+        let other_batch = command_batch(vec![Command {
+            id: HexBinary::from(vec![0xff; 32]),
+            ty: CommandType::ApproveContractCall,
+            params: HexBinary::from(vec![1, 2, 3]),
+        }]);
+        assert_ne!(msg_digest(&batch), msg_digest(&other_batch));
+    }
+
+    #[test]
+    fn test_encode_execute_data() {
+        let batch = command_batch(vec![]);
+        let signature = [7u8; 64];
+        let signers = vec![
+            (ed25519_signer(2), None),
+            (
+                ed25519_signer(1),
+                Some(Signature::Ed25519(HexBinary::from(signature.to_vec()))),
+            ),
+        ];
+
+        let encoded = encode_execute_data(
+            &batch,
+            Uint256::one(),
+            signers.clone(),
+            FeePayer::Address(ACCOUNT),
+        )
+        .unwrap();
+
+        let data = encode(&batch.data).to_vec();
+        let expected = [
+            (data.len() as u32).to_be_bytes().to_vec(),
+            data,
+            vec![0, 0, 0, 2],
+            vec![1; 32],
+            u128_parts(1),
+            vec![0, 0, 0, 1],
+            signature.to_vec(),
+            vec![2; 32],
+            u128_parts(1),
+            vec![0, 0, 0, 0],
+            u128_parts(1),
+            vec![0; 8], // account fee payer with an ed25519 key
+            vec![0; 32],
+        ]
+        .concat();
+        assert_eq!(encoded, HexBinary::from(expected));
+
+        let unsupported = encode_execute_data(
+            &batch,
+            Uint256::one(),
+            signers.clone(),
+            FeePayer::Unsupported,
+        )
+        .unwrap();
+        assert_eq!(unsupported.as_slice(), &encoded[..encoded.len() - 40]);
+
+        assert!(matches!(
+            encode_execute_data(
+                &batch,
+                Uint256::one(),
+                signers,
+                FeePayer::Address("not an address")
+            ),
+            Err(ContractError::InvalidFeePayer { .. })
+        ));
+
+        let ecdsa_signer = Signer {
+            address: Addr::unchecked("ecdsa"),
+            weight: Uint256::one(),
+            pub_key: test_data::operators().remove(0).pub_key,
+        };
+        assert!(matches!(
+            encode_execute_data(
+                &batch,
+                Uint256::one(),
+                vec![(ecdsa_signer, None)],
+                FeePayer::Unsupported
+            ),
+            Err(ContractError::InvalidPublicKey { .. })
+        ));
+    }
+}
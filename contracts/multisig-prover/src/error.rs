@@ -52,4 +52,55 @@ pub enum ContractError {
 
     #[error("no worker set stored")]
     NoWorkerSet,
+
+    #[error("new batches are blocked until the pending worker set rotation is confirmed")]
+    WorkerSetRotationPending,
+
+    #[error("message {message_id} is not part of the batch")]
+    MessageNotInBatch { message_id: String },
+
+    #[error("all messages of the batch have been executed")]
+    NoUnexecutedMessages,
+
+    #[error("invalid recovery worker set: {reason}")]
+    InvalidRecoveryWorkerSet { reason: String },
+
+    #[error("no recovery worker set registered")]
+    NoRecoveryWorkerSet,
+
+    #[error("recovery has already been activated")]
+    RecoveryAlreadyActivated,
+
+    #[error("the current worker set can still meet its threshold")]
+    WorkerSetQuorumIntact,
+
+    #[error("fee payer is invalid: {reason}")]
+    InvalidFeePayer { reason: String },
+
+    #[error("eip-712 domain is invalid: {reason}")]
+    InvalidEip712Domain { reason: String },
+
+    #[error("key type is invalid: {reason}")]
+    InvalidKeyType { reason: String },
+
+    #[error("no retention policy is set, so no proofs can be pruned")]
+    NoRetentionPolicy,
+
+    #[error("batch not found")]
+    BatchNotFound,
+
+    #[error("the latest proof of the batch is already completed")]
+    ProofAlreadyCompleted,
+
+    #[error("the latest proof of the batch is still being signed by the current worker set")]
+    ProofStillPending,
+
+    #[error("the worker set rotation of the batch is no longer pending")]
+    StaleWorkerSetRotation,
+
+    #[error("a batch can rotate to at most one worker set")]
+    DuplicateWorkerSetRotation,
+
+    #[error("the operators kept by the operator cap hold less weight than the quorum of all active workers")]
+    OperatorCapBelowQuorum,
 }
@@ -1,3 +1,4 @@
+use connection_router::state::CrossChainId;
 use cosmwasm_std::Uint64;
 use serde_json::to_string;
 
@@ -8,6 +9,28 @@ pub enum Event {
         command_batch_id: BatchId,
         multisig_session_id: Uint64,
     },
+    ProofCompleted {
+        command_batch_id: BatchId,
+        multisig_session_id: Uint64,
+    },
+    RecoveryWorkerSetRegistered {
+        worker_set_id: String,
+    },
+    RecoveryActivated {
+        recovery_worker_set_id: String,
+        new_worker_set_id: String,
+    },
+    WorkerSetConfirmed {
+        worker_set_id: String,
+    },
+    ConfigUpdated,
+    // Emitted for every proof removed according to the retention policy. The batch is only removed with its latest proof
+    ProofPruned {
+        command_batch_id: BatchId,
+        multisig_session_id: Uint64,
+        message_ids: Vec<CrossChainId>,
+        batch_pruned: bool,
+    },
 }
 
 impl From<Event> for cosmwasm_std::Event {
@@ -27,6 +50,57 @@ impl From<Event> for cosmwasm_std::Event {
                     to_string(&multisig_session_id)
                         .expect("violated invariant: multisig_session_id is not serializable"),
                 ),
+            Event::ProofCompleted {
+                command_batch_id,
+                multisig_session_id,
+            } => cosmwasm_std::Event::new("proof_completed")
+                .add_attribute(
+                    "command_batch_id",
+                    to_string(&command_batch_id)
+                        .expect("violated invariant: command_batch_id is not serializable"),
+                )
+                .add_attribute(
+                    "multisig_session_id",
+                    to_string(&multisig_session_id)
+                        .expect("violated invariant: multisig_session_id is not serializable"),
+                ),
+            Event::RecoveryWorkerSetRegistered { worker_set_id } => {
+                cosmwasm_std::Event::new("recovery_worker_set_registered")
+                    .add_attribute("worker_set_id", worker_set_id)
+            }
+            Event::RecoveryActivated {
+                recovery_worker_set_id,
+                new_worker_set_id,
+            } => cosmwasm_std::Event::new("recovery_activated")
+                .add_attribute("recovery_worker_set_id", recovery_worker_set_id)
+                .add_attribute("new_worker_set_id", new_worker_set_id),
+            Event::WorkerSetConfirmed { worker_set_id } => {
+                cosmwasm_std::Event::new("worker_set_confirmed")
+                    .add_attribute("worker_set_id", worker_set_id)
+            }
+            Event::ConfigUpdated => cosmwasm_std::Event::new("config_updated"),
+            Event::ProofPruned {
+                command_batch_id,
+                multisig_session_id,
+                message_ids,
+                batch_pruned,
+            } => cosmwasm_std::Event::new("proof_pruned")
+                .add_attribute(
+                    "command_batch_id",
+                    to_string(&command_batch_id)
+                        .expect("violated invariant: command_batch_id is not serializable"),
+                )
+                .add_attribute(
+                    "multisig_session_id",
+                    to_string(&multisig_session_id)
+                        .expect("violated invariant: multisig_session_id is not serializable"),
+                )
+                .add_attribute(
+                    "message_ids",
+                    to_string(&message_ids)
+                        .expect("violated invariant: message_ids are not serializable"),
+                )
+                .add_attribute("batch_pruned", batch_pruned.to_string()),
         }
     }
 }
@@ -1,9 +1,14 @@
 use cosmwasm_std::{
-    to_binary, wasm_execute, Addr, DepsMut, Env, QuerierWrapper, QueryRequest, Response, Storage,
-    SubMsg, WasmQuery,
+    to_binary, wasm_execute, Addr, Deps, DepsMut, Env, Order, QuerierWrapper, QueryRequest,
+    Response, StdResult, Storage, SubMsg, Uint256, Uint64, WasmQuery,
 };
 
-use multisig::{key::PublicKey, msg::Signer, worker_set::WorkerSet};
+use multisig::{
+    key::{KeyTyped, PublicKey},
+    msg::{Multisig, Signer},
+    types::{MultisigState, RetentionPolicy},
+    worker_set::WorkerSet,
+};
 
 use axelar_wasm_std::snapshot;
 use connection_router::state::{ChainName, CrossChainId, Message};
@@ -11,20 +16,145 @@ use service_registry::state::Worker;
 
 use crate::{
     contract::START_MULTISIG_REPLY_ID,
-    encoding::{make_operators, CommandBatchBuilder},
+    encoding::{self, make_operators, CommandBatchBuilder},
     error::ContractError,
-    state::{Config, COMMANDS_BATCH, CONFIG, CURRENT_WORKER_SET, NEXT_WORKER_SET, REPLY_BATCH},
-    types::{BatchId, WorkersInfo},
+    events::Event,
+    msg::ConfigUpdate,
+    query,
+    state::{
+        save_worker_set_generation, update_batch_metrics, BatchMetrics, Config, BATCH_METRICS,
+        BATCH_MULTISIG_SESSION, COMMANDS_BATCH, COMPLETED_SESSIONS, CONFIG, CURRENT_WORKER_SET,
+        EXECUTED_MESSAGES, MESSAGE_MULTISIG_SESSION, MULTISIG_SESSION_BATCH,
+        MULTISIG_SESSION_WORKER_SET, NEXT_WORKER_SET, RECOVERY_ACTIVATED_AT, RECOVERY_WORKER_SET,
+        REPLY_BATCH, REPLY_WORKER_SET_HASH,
+    },
+    types::{BatchId, CommandBatch, CommandType, WorkersInfo},
 };
 
+pub fn require_admin(deps: &DepsMut, sender: Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin != sender {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+pub fn require_governance(deps: &DepsMut, sender: Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.governance != Some(sender) {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+pub fn require_multisig(deps: &DepsMut, sender: Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.multisig != sender {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
 pub fn construct_proof(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     message_ids: Vec<CrossChainId>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let batch_id = BatchId::new(&message_ids, None);
+    if config.safe_mode && NEXT_WORKER_SET.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::WorkerSetRotationPending);
+    }
+
+    let batch_id = BatchId::new(
+        &env.contract.address,
+        config.destination_chain_id,
+        &message_ids,
+        None,
+    );
+
+    let command_batch = match COMMANDS_BATCH.may_load(deps.storage, &batch_id)? {
+        Some(batch) => batch,
+        None => build_batch(&mut deps, &env, &config, message_ids)?,
+    };
+
+    start_signing_session(deps, config, command_batch)
+}
+
+/// Opens a new signing session for an existing batch under the current worker set, because the worker set rotated
+/// before its latest session reached quorum
+pub fn retry_proof(
+    mut deps: DepsMut,
+    env: Env,
+    batch_id: BatchId,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let batch = COMMANDS_BATCH
+        .may_load(deps.storage, &batch_id)?
+        .ok_or(ContractError::BatchNotFound)?;
+
+    let rotation_pending = NEXT_WORKER_SET.may_load(deps.storage)?.is_some();
+    if config.safe_mode && rotation_pending && !batch.message_ids.is_empty() {
+        return Err(ContractError::WorkerSetRotationPending);
+    }
+
+    ensure_retriable(deps.as_ref(), &config, &batch.id)?;
+
+    let batch = match (
+        has_stale_rotation(deps.storage, &batch)?,
+        batch.message_ids.is_empty(),
+    ) {
+        (false, _) => batch,
+        (true, true) => return Err(ContractError::StaleWorkerSetRotation),
+        // signing the stale rotation would make the gateway trust a worker set the prover doesn't use,
+        // so a new batch of the same messages is built from the current state instead
+        (true, false) => build_batch(&mut deps, &env, &config, batch.message_ids)?,
+    };
+
+    start_signing_session(deps, config, batch)
+}
+
+// A batch can only be retried if its latest session can't complete anymore, i.e. it is still pending but its worker set
+// is no longer the current one. Batches whose latest session wasn't recorded can always be retried
+fn ensure_retriable(deps: Deps, config: &Config, batch_id: &BatchId) -> Result<(), ContractError> {
+    let session_id = match BATCH_MULTISIG_SESSION.may_load(deps.storage, batch_id)? {
+        Some(session_id) => session_id,
+        None => return Ok(()),
+    };
+
+    let query = multisig::msg::QueryMsg::GetMultisig {
+        session_id: session_id.into(),
+    };
+    let multisig: Multisig = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: config.multisig.to_string(),
+        msg: to_binary(&query)?,
+    }))?;
+
+    match multisig.state {
+        MultisigState::Completed { .. } => Err(ContractError::ProofAlreadyCompleted),
+        MultisigState::Pending => {
+            let signing_worker_set =
+                MULTISIG_SESSION_WORKER_SET.may_load(deps.storage, session_id)?;
+            let current_worker_set = CURRENT_WORKER_SET.may_load(deps.storage)?;
+
+            if signing_worker_set.is_some()
+                && signing_worker_set == current_worker_set.map(|worker_set| worker_set.hash())
+            {
+                return Err(ContractError::ProofStillPending);
+            }
+
+            Ok(())
+        }
+    }
+}
 
+// Builds and stores the batch of the given messages, together with a rotation to the next worker set if one is due.
+// A pending rotation goes with every batch until it is confirmed, so no other rotation can be sequenced before it
+fn build_batch(
+    deps: &mut DepsMut,
+    env: &Env,
+    config: &Config,
+    message_ids: Vec<CrossChainId>,
+) -> Result<CommandBatch, ContractError> {
     let messages = get_messages(
         deps.querier,
         message_ids,
@@ -32,28 +162,73 @@ pub fn construct_proof(
         config.chain_name.clone(),
     )?;
 
-    let command_batch = match COMMANDS_BATCH.may_load(deps.storage, &batch_id)? {
-        Some(batch) => batch,
-        None => {
-            let new_worker_set = get_next_worker_set(&deps, &env, &config)?;
-            let mut builder = CommandBatchBuilder::new(config.destination_chain_id, config.encoder);
+    let new_worker_set = match NEXT_WORKER_SET.may_load(deps.storage)? {
+        Some(pending_worker_set) => Some(pending_worker_set),
+        None => get_next_worker_set(deps, env, config)?,
+    };
+    let mut builder = CommandBatchBuilder::new(
+        env.contract.address.clone(),
+        config.destination_chain_id,
+        config.encoder,
+    )
+    .with_eip712_domain(config.eip712_domain.clone());
+
+    if let Some(new_worker_set) = new_worker_set {
+        save_next_worker_set(deps.storage, &new_worker_set)?;
+        builder.add_new_worker_set(new_worker_set)?;
+    }
 
-            if let Some(new_worker_set) = new_worker_set {
-                save_next_worker_set(deps.storage, &new_worker_set)?;
-                builder.add_new_worker_set(new_worker_set)?;
-            }
+    for msg in messages {
+        builder.add_message(msg)?;
+    }
+    let batch = builder.build()?;
 
-            for msg in messages {
-                builder.add_message(msg)?;
-            }
-            let batch = builder.build()?;
+    save_batch(deps.storage, env, &batch)?;
 
-            COMMANDS_BATCH.save(deps.storage, &batch.id, &batch)?;
+    Ok(batch)
+}
 
-            batch
-        }
-    };
+// A rebuilt batch keeps the metrics of its first construction
+fn save_batch(
+    storage: &mut dyn Storage,
+    env: &Env,
+    batch: &CommandBatch,
+) -> Result<(), ContractError> {
+    COMMANDS_BATCH.save(storage, &batch.id, batch)?;
+
+    if !BATCH_METRICS.has(storage, &batch.id) {
+        BATCH_METRICS.save(
+            storage,
+            &batch.id,
+            &BatchMetrics {
+                constructed: (&env.block).into(),
+                signing_started: None,
+                quorum_reached: None,
+                executed: None,
+            },
+        )?;
+    }
 
+    Ok(())
+}
+
+// A rotation is stale once its worker set is no longer the next one, i.e. it was confirmed or replaced
+fn has_stale_rotation(storage: &dyn Storage, batch: &CommandBatch) -> Result<bool, ContractError> {
+    let next_worker_set_hash = NEXT_WORKER_SET
+        .may_load(storage)?
+        .map(|worker_set| worker_set.hash());
+
+    Ok(batch.data.commands.iter().any(|command| {
+        command.ty == CommandType::TransferOperatorship
+            && Some(&command.id) != next_worker_set_hash.as_ref()
+    }))
+}
+
+fn start_signing_session(
+    deps: DepsMut,
+    config: Config,
+    command_batch: CommandBatch,
+) -> Result<Response, ContractError> {
     // keep track of the batch id to use during submessage reply
     REPLY_BATCH.save(deps.storage, &command_batch.id)?;
 
@@ -63,6 +238,9 @@ pub fn construct_proof(
         msg: command_batch.msg_digest(),
         chain_name: config.chain_name,
         sig_verifier: None,
+        high_value: command_batch.is_high_value(),
+        callback: true,
+        threshold: None,
     };
 
     let wasm_msg = wasm_execute(config.multisig, &start_sig_msg, vec![])?;
@@ -99,33 +277,49 @@ fn get_messages(
     Ok(messages)
 }
 
-fn get_workers_info(deps: &DepsMut, config: &Config) -> Result<WorkersInfo, ContractError> {
+fn get_active_workers(
+    querier: QuerierWrapper,
+    config: &Config,
+) -> Result<Vec<Worker>, ContractError> {
     let active_workers_query = service_registry::msg::QueryMsg::GetActiveWorkers {
         service_name: config.service_name.clone(),
         chain_name: config.chain_name.clone(),
     };
 
-    let workers: Vec<Worker> = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+    Ok(querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: config.service_registry.to_string(),
         msg: to_binary(&active_workers_query)?,
-    }))?;
+    }))?)
+}
+
+fn get_workers_info(
+    querier: QuerierWrapper,
+    config: &Config,
+) -> Result<WorkersInfo, ContractError> {
+    let workers = get_active_workers(querier, config)?;
 
     let participants = workers
-        .clone()
         .into_iter()
         .map(service_registry::state::Worker::try_into)
         .collect::<Result<Vec<snapshot::Participant>, _>>()?;
-
-    let snapshot =
-        snapshot::Snapshot::new(config.signing_threshold, participants.clone().try_into()?);
+    // a quorum derived from the capped participants' own weight could be reached with less support than the signing threshold
+    // requires from all active workers, so the capped worker set keeps the quorum of all of them
+    let quorum =
+        snapshot::Snapshot::new(config.signing_threshold, participants.clone().try_into()?).quorum;
+    let participants = cap_participants(participants, quorum.as_ref(), config)?;
+
+    let snapshot = snapshot::Snapshot {
+        quorum,
+        ..snapshot::Snapshot::new(config.signing_threshold, participants.clone().try_into()?)
+    };
 
     let mut pub_keys = vec![];
-    for worker in &workers {
+    for participant in &participants {
         let pub_key_query = multisig::msg::QueryMsg::GetPublicKey {
-            worker_address: worker.address.to_string(),
+            worker_address: participant.address.to_string(),
             key_type: config.key_type,
         };
-        let pub_key: PublicKey = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        let pub_key: PublicKey = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: config.multisig.to_string(),
             msg: to_binary(&pub_key_query)?,
         }))?;
@@ -138,8 +332,48 @@ fn get_workers_info(deps: &DepsMut, config: &Config) -> Result<WorkersInfo, Cont
     })
 }
 
-fn make_worker_set(deps: &DepsMut, env: &Env, config: &Config) -> Result<WorkerSet, ContractError> {
-    let workers_info = get_workers_info(deps, config)?;
+// Keeps the participants with the highest weight, up to the configured maximum number of operators. The kept participants
+// must still be able to reach the given quorum of all participants, which stays the quorum of the capped worker set
+fn cap_participants(
+    mut participants: Vec<snapshot::Participant>,
+    quorum: &Uint256,
+    config: &Config,
+) -> Result<Vec<snapshot::Participant>, ContractError> {
+    let max_operators = match config.max_operators {
+        Some(max_operators) if participants.len() as u64 > u64::from(max_operators) => {
+            u64::from(max_operators) as usize
+        }
+        _ => return Ok(participants),
+    };
+
+    // ties are broken by address, so every prover picks the same operators
+    participants.sort_by(|a, b| {
+        b.weight
+            .as_ref()
+            .cmp(a.weight.as_ref())
+            .then_with(|| a.address.cmp(&b.address))
+    });
+    participants.truncate(max_operators);
+
+    let kept_weight = participants
+        .iter()
+        .fold(Uint256::zero(), |total, participant| {
+            total + participant.weight.as_ref()
+        });
+    if kept_weight < *quorum {
+        return Err(ContractError::OperatorCapBelowQuorum);
+    }
+
+    Ok(participants)
+}
+
+/// Worker set of the currently active workers, as it would be rotated to now
+pub fn make_worker_set(
+    querier: QuerierWrapper,
+    env: &Env,
+    config: &Config,
+) -> Result<WorkerSet, ContractError> {
+    let workers_info = get_workers_info(querier, config)?;
     Ok(WorkerSet::new(
         workers_info.pubkeys_by_participant,
         workers_info.snapshot.quorum.into(),
@@ -153,7 +387,7 @@ fn get_next_worker_set(
     config: &Config,
 ) -> Result<Option<WorkerSet>, ContractError> {
     let cur_worker_set = CURRENT_WORKER_SET.may_load(deps.storage)?;
-    let new_worker_set = make_worker_set(deps, env, config)?;
+    let new_worker_set = make_worker_set(deps.querier, env, config)?;
 
     match cur_worker_set {
         Some(cur_worker_set) => {
@@ -171,6 +405,16 @@ fn get_next_worker_set(
     }
 }
 
+fn save_current_worker_set(
+    storage: &mut dyn Storage,
+    worker_set: &WorkerSet,
+) -> Result<(), ContractError> {
+    CURRENT_WORKER_SET.save(storage, worker_set)?;
+    save_worker_set_generation(storage, worker_set)?;
+
+    Ok(())
+}
+
 fn save_next_worker_set(
     storage: &mut dyn Storage,
     new_worker_set: &WorkerSet,
@@ -189,13 +433,14 @@ pub fn update_worker_set(deps: DepsMut, env: Env) -> Result<Response, ContractEr
     match cur_worker_set {
         None => {
             // if no worker set, just store it and return
-            let new_worker_set = make_worker_set(&deps, &env, &config)?;
-            CURRENT_WORKER_SET.save(deps.storage, &new_worker_set)?;
+            let new_worker_set = make_worker_set(deps.querier, &env, &config)?;
+            save_current_worker_set(deps.storage, &new_worker_set)?;
 
             Ok(Response::new().add_message(wasm_execute(
                 config.multisig,
                 &multisig::msg::ExecuteMsg::RegisterWorkerSet {
                     worker_set: new_worker_set,
+                    digest_scheme: config.digest_scheme,
                 },
                 vec![],
             )?))
@@ -206,12 +451,17 @@ pub fn update_worker_set(deps: DepsMut, env: Env) -> Result<Response, ContractEr
 
             save_next_worker_set(deps.storage, &new_worker_set)?;
 
-            let mut builder = CommandBatchBuilder::new(config.destination_chain_id, config.encoder);
+            let mut builder = CommandBatchBuilder::new(
+                env.contract.address.clone(),
+                config.destination_chain_id,
+                config.encoder,
+            )
+            .with_eip712_domain(config.eip712_domain.clone());
             builder.add_new_worker_set(new_worker_set)?;
 
             let batch = builder.build()?;
 
-            COMMANDS_BATCH.save(deps.storage, &batch.id, &batch)?;
+            save_batch(deps.storage, &env, &batch)?;
             REPLY_BATCH.save(deps.storage, &batch.id)?;
 
             let start_sig_msg = multisig::msg::ExecuteMsg::StartSigningSession {
@@ -219,6 +469,9 @@ pub fn update_worker_set(deps: DepsMut, env: Env) -> Result<Response, ContractEr
                 msg: batch.msg_digest(),
                 sig_verifier: None,
                 chain_name: config.chain_name,
+                high_value: batch.is_high_value(),
+                callback: true,
+                threshold: None,
             };
 
             Ok(Response::new().add_submessage(SubMsg::reply_on_success(
@@ -234,29 +487,435 @@ pub fn confirm_worker_set(deps: DepsMut) -> Result<Response, ContractError> {
 
     let worker_set = NEXT_WORKER_SET.load(deps.storage)?;
 
+    if !is_worker_set_verified(deps.querier, &config, &worker_set)? {
+        return Err(ContractError::WorkerSetNotConfirmed);
+    }
+
+    save_current_worker_set(deps.storage, &worker_set)?;
+    NEXT_WORKER_SET.remove(deps.storage);
+    RECOVERY_ACTIVATED_AT.remove(deps.storage);
+
+    let worker_set_id = worker_set.id();
+
+    Ok(Response::new()
+        .add_message(wasm_execute(
+            config.multisig,
+            &multisig::msg::ExecuteMsg::RegisterWorkerSet {
+                worker_set,
+                digest_scheme: config.digest_scheme,
+            },
+            vec![],
+        )?)
+        .add_event(Event::WorkerSetConfirmed { worker_set_id }.into()))
+}
+
+// whether the gateway on the destination chain has confirmed the worker set, as observed by the voting verifier
+fn is_worker_set_verified(
+    querier: QuerierWrapper,
+    config: &Config,
+    worker_set: &WorkerSet,
+) -> Result<bool, ContractError> {
     let query = voting_verifier::msg::QueryMsg::IsWorkerSetVerified {
         new_operators: make_operators(worker_set.clone(), config.encoder),
     };
 
-    let is_confirmed: bool = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+    Ok(querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: config.voting_verifier.to_string(),
         msg: to_binary(&query)?,
-    }))?;
+    }))?)
+}
 
-    if !is_confirmed {
-        return Err(ContractError::WorkerSetNotConfirmed);
+pub fn confirm_executed_messages(
+    deps: DepsMut,
+    env: Env,
+    multisig_session_id: Uint64,
+    message_ids: Vec<CrossChainId>,
+) -> Result<Response, ContractError> {
+    let batch_id = MULTISIG_SESSION_BATCH.load(deps.storage, multisig_session_id.u64())?;
+    let batch = COMMANDS_BATCH.load(deps.storage, &batch_id)?;
+
+    if let Some(message_id) = message_ids
+        .iter()
+        .find(|id| !batch.message_ids.contains(id))
+    {
+        return Err(ContractError::MessageNotInBatch {
+            message_id: message_id.to_string(),
+        });
     }
 
-    CURRENT_WORKER_SET.save(deps.storage, &worker_set)?;
-    NEXT_WORKER_SET.remove(deps.storage);
+    let mut executed = EXECUTED_MESSAGES
+        .may_load(deps.storage, &batch_id)?
+        .unwrap_or_default();
+    for message_id in message_ids {
+        if !executed.contains(&message_id) {
+            executed.push(message_id);
+        }
+    }
+    EXECUTED_MESSAGES.save(deps.storage, &batch_id, &executed)?;
+
+    if batch.message_ids.iter().all(|id| executed.contains(id)) {
+        update_batch_metrics(deps.storage, &batch_id, |metrics| BatchMetrics {
+            executed: metrics.executed.or_else(|| Some((&env.block).into())),
+            ..metrics
+        })?;
+    }
+
+    Ok(Response::new())
+}
+
+pub fn signing_completed(
+    deps: DepsMut,
+    env: Env,
+    session_id: Uint64,
+) -> Result<Response, ContractError> {
+    let command_batch_id = MULTISIG_SESSION_BATCH.load(deps.storage, session_id.u64())?;
+    COMPLETED_SESSIONS.save(deps.storage, (env.block.height, session_id.u64()), &())?;
+    update_batch_metrics(deps.storage, &command_batch_id, |metrics| BatchMetrics {
+        quorum_reached: Some((&env.block).into()),
+        ..metrics
+    })?;
+
+    Ok(Response::new().add_event(
+        Event::ProofCompleted {
+            command_batch_id,
+            multisig_session_id: session_id,
+        }
+        .into(),
+    ))
+}
+
+pub fn confirm_signing_session(
+    deps: DepsMut,
+    multisig_session_id: Uint64,
+) -> Result<Response, ContractError> {
+    if !MULTISIG_SESSION_BATCH.has(deps.storage, multisig_session_id.u64()) {
+        return Err(ContractError::BatchNotFound);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
 
     Ok(Response::new().add_message(wasm_execute(
         config.multisig,
-        &multisig::msg::ExecuteMsg::RegisterWorkerSet { worker_set },
+        &multisig::msg::ExecuteMsg::ConfirmSigningSession {
+            session_id: multisig_session_id,
+        },
         vec![],
     )?))
 }
 
+pub fn cancel_signing_session(
+    deps: DepsMut,
+    multisig_session_id: Uint64,
+) -> Result<Response, ContractError> {
+    if !MULTISIG_SESSION_BATCH.has(deps.storage, multisig_session_id.u64()) {
+        return Err(ContractError::BatchNotFound);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(Response::new().add_message(wasm_execute(
+        config.multisig,
+        &multisig::msg::ExecuteMsg::CancelSigningSession {
+            session_id: multisig_session_id,
+        },
+        vec![],
+    )?))
+}
+
+pub fn rebatch_unexecuted_messages(
+    deps: DepsMut,
+    env: Env,
+    multisig_session_id: Uint64,
+) -> Result<Response, ContractError> {
+    let message_ids = query::get_unexecuted_messages(deps.as_ref(), multisig_session_id)?;
+    if message_ids.is_empty() {
+        return Err(ContractError::NoUnexecutedMessages);
+    }
+
+    construct_proof(deps, env, message_ids)
+}
+
+pub fn register_recovery_worker_set(
+    deps: DepsMut,
+    worker_set: WorkerSet,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if RECOVERY_ACTIVATED_AT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::RecoveryAlreadyActivated);
+    }
+
+    validate_recovery_worker_set(deps.storage, deps.querier, &config, &worker_set)?;
+
+    RECOVERY_WORKER_SET.save(deps.storage, &worker_set)?;
+
+    Ok(Response::new()
+        .add_message(wasm_execute(
+            config.multisig,
+            &multisig::msg::ExecuteMsg::RegisterWorkerSet {
+                worker_set: worker_set.clone(),
+                digest_scheme: config.digest_scheme,
+            },
+            vec![],
+        )?)
+        .add_event(
+            Event::RecoveryWorkerSetRegistered {
+                worker_set_id: worker_set.id(),
+            }
+            .into(),
+        ))
+}
+
+fn validate_recovery_worker_set(
+    storage: &dyn Storage,
+    querier: QuerierWrapper,
+    config: &Config,
+    worker_set: &WorkerSet,
+) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidRecoveryWorkerSet {
+        reason: reason.to_string(),
+    };
+
+    let total_weight = worker_set
+        .signers
+        .values()
+        .fold(Uint256::zero(), |acc, signer| acc + signer.weight);
+
+    if worker_set.threshold.is_zero() || worker_set.threshold > total_weight {
+        return Err(invalid("threshold must be positive and reachable"));
+    }
+
+    if worker_set
+        .signers
+        .values()
+        .any(|signer| signer.pub_key.key_type() != config.key_type)
+    {
+        return Err(invalid("public keys must match the key type of the prover"));
+    }
+
+    if let Some(cur_worker_set) = CURRENT_WORKER_SET.may_load(storage)? {
+        if cur_worker_set.signers == worker_set.signers {
+            return Err(invalid("signers must differ from the current worker set"));
+        }
+    }
+
+    // the recovery worker set can only sign a rotation the destination gateway accepts if the gateway trusts it
+    if !is_worker_set_verified(querier, config, worker_set)? {
+        return Err(invalid("must be confirmed by the destination gateway"));
+    }
+
+    Ok(())
+}
+
+pub fn activate_recovery(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let recovery_worker_set = RECOVERY_WORKER_SET
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoRecoveryWorkerSet)?;
+
+    if RECOVERY_ACTIVATED_AT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::RecoveryAlreadyActivated);
+    }
+
+    let cur_worker_set = CURRENT_WORKER_SET
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoWorkerSet)?;
+
+    if has_quorum(&cur_worker_set, &get_active_workers(deps.querier, &config)?) {
+        return Err(ContractError::WorkerSetQuorumIntact);
+    }
+
+    // the current worker set can't sign anymore, so the rotation it might have started is replaced
+    let new_worker_set = make_worker_set(deps.querier, &env, &config)?;
+    NEXT_WORKER_SET.save(deps.storage, &new_worker_set)?;
+
+    let mut builder = CommandBatchBuilder::new(
+        env.contract.address.clone(),
+        config.destination_chain_id,
+        config.encoder,
+    )
+    .with_eip712_domain(config.eip712_domain.clone());
+    builder.add_new_worker_set(new_worker_set.clone())?;
+    let batch = builder.build()?;
+
+    save_batch(deps.storage, &env, &batch)?;
+    REPLY_BATCH.save(deps.storage, &batch.id)?;
+    REPLY_WORKER_SET_HASH.save(deps.storage, &recovery_worker_set.hash())?;
+    RECOVERY_ACTIVATED_AT.save(deps.storage, &env.block.height)?;
+
+    let start_sig_msg = multisig::msg::ExecuteMsg::StartSigningSession {
+        worker_set_id: recovery_worker_set.id(),
+        msg: batch.msg_digest(),
+        sig_verifier: None,
+        chain_name: config.chain_name,
+        high_value: batch.is_high_value(),
+        callback: true,
+        threshold: None,
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            wasm_execute(config.multisig, &start_sig_msg, vec![])?,
+            START_MULTISIG_REPLY_ID,
+        ))
+        .add_event(
+            Event::RecoveryActivated {
+                recovery_worker_set_id: recovery_worker_set.id(),
+                new_worker_set_id: new_worker_set.id(),
+            }
+            .into(),
+        ))
+}
+
+// Applies the parameters that are set in the update, the others are left unchanged
+pub fn update_config(
+    deps: DepsMut,
+    config_update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let ConfigUpdate {
+        gateway_address,
+        destination_chain_id,
+        signing_threshold,
+        service_name,
+        encoder,
+        max_operators,
+    } = config_update;
+
+    if let Some(gateway_address) = gateway_address {
+        config.gateway = deps.api.addr_validate(&gateway_address)?;
+    }
+    if let Some(destination_chain_id) = destination_chain_id {
+        config.destination_chain_id = destination_chain_id;
+    }
+    if let Some(signing_threshold) = signing_threshold {
+        config.signing_threshold = signing_threshold;
+    }
+    if let Some(service_name) = service_name {
+        config.service_name = service_name;
+    }
+    if let Some(encoder) = encoder {
+        if let Some(domain) = &config.eip712_domain {
+            encoding::validate_eip712_domain(domain, encoder)?;
+        }
+        encoding::validate_key_type(config.key_type, encoder)?;
+        config.encoder = encoder;
+    }
+    if let Some(max_operators) = max_operators {
+        config.max_operators = max_operators;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_event(Event::ConfigUpdated.into()))
+}
+
+pub fn update_retention_policy(
+    deps: DepsMut,
+    retention_policy: Option<RetentionPolicy>,
+) -> Result<Response, ContractError> {
+    CONFIG.update(deps.storage, |config| -> Result<Config, ContractError> {
+        Ok(Config {
+            retention_policy,
+            ..config
+        })
+    })?;
+
+    Ok(Response::default())
+}
+
+pub fn prune_proofs(deps: DepsMut, env: Env, limit: u32) -> Result<Response, ContractError> {
+    let retention_policy = CONFIG
+        .load(deps.storage)?
+        .retention_policy
+        .ok_or(ContractError::NoRetentionPolicy)?;
+    let latest_session_id = MULTISIG_SESSION_BATCH
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .unwrap_or_default();
+
+    let prunable = COMPLETED_SESSIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .filter(|key| {
+            key.as_ref().map_or(true, |(completed_at, session_id)| {
+                retention_policy.allows_pruning(
+                    *session_id,
+                    *completed_at,
+                    latest_session_id,
+                    env.block.height,
+                )
+            })
+        })
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let events = prunable
+        .into_iter()
+        .map(|(completed_at, session_id)| {
+            COMPLETED_SESSIONS.remove(deps.storage, (completed_at, session_id));
+            prune_proof(deps.storage, session_id).map(cosmwasm_std::Event::from)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Response::new().add_events(events))
+}
+
+// The batch of the proof is kept if a later proof of the same batch was started, e.g. by retrying it
+fn prune_proof(storage: &mut dyn Storage, session_id: u64) -> Result<Event, ContractError> {
+    let command_batch_id = MULTISIG_SESSION_BATCH.load(storage, session_id)?;
+    MULTISIG_SESSION_BATCH.remove(storage, session_id);
+    MULTISIG_SESSION_WORKER_SET.remove(storage, session_id);
+
+    let batch_pruned = BATCH_MULTISIG_SESSION
+        .may_load(storage, &command_batch_id)?
+        .map_or(true, |latest_session_id| latest_session_id == session_id);
+
+    let message_ids = match COMMANDS_BATCH.may_load(storage, &command_batch_id)? {
+        Some(batch) if batch_pruned => {
+            for message_id in &batch.message_ids {
+                if MESSAGE_MULTISIG_SESSION.may_load(storage, message_id.clone())?
+                    == Some(session_id)
+                {
+                    MESSAGE_MULTISIG_SESSION.remove(storage, message_id.clone());
+                }
+            }
+
+            COMMANDS_BATCH.remove(storage, &command_batch_id);
+            EXECUTED_MESSAGES.remove(storage, &command_batch_id);
+            BATCH_MULTISIG_SESSION.remove(storage, &command_batch_id);
+            BATCH_METRICS.remove(storage, &command_batch_id);
+
+            batch.message_ids
+        }
+        Some(batch) => batch.message_ids,
+        None => vec![],
+    };
+
+    Ok(Event::ProofPruned {
+        command_batch_id,
+        multisig_session_id: session_id.into(),
+        message_ids,
+        batch_pruned,
+    })
+}
+
+// Returns true if the signers of the worker set that are still active workers can meet its threshold
+fn has_quorum(worker_set: &WorkerSet, active_workers: &[Worker]) -> bool {
+    let active_weight = worker_set
+        .signers
+        .values()
+        .filter(|signer| {
+            active_workers
+                .iter()
+                .any(|worker| worker.address == signer.address)
+        })
+        .fold(Uint256::zero(), |acc, signer| acc + signer.weight);
+
+    active_weight >= worker_set.threshold
+}
+
 pub fn should_update_worker_set(
     new_workers: &WorkerSet,
     cur_workers: &WorkerSet,
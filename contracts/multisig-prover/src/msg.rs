@@ -1,14 +1,26 @@
-use axelar_wasm_std::MajorityThreshold;
+use axelar_wasm_std::{nonempty, MajorityThreshold};
 use connection_router::state::CrossChainId;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{HexBinary, Uint256, Uint64};
-use multisig::key::KeyType;
+use cosmwasm_std::{Addr, HexBinary, Uint256, Uint64};
+use multisig::{
+    key::KeyType,
+    msg::Signer,
+    types::{DigestScheme, RetentionPolicy},
+    worker_set::WorkerSet,
+};
+use serde::{Deserialize, Deserializer};
 
-use crate::encoding::{Data, Encoder};
+use crate::{
+    encoding::{Data, Eip712Domain, Encoder, ExecutionEstimate},
+    state::BatchMetrics,
+    types::BatchId,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin_address: String,
+    // the governance address is allowed to update the config after instantiation
+    pub governance_address: String,
     pub gateway_address: String,
     pub multisig_address: String,
     pub service_registry_address: String,
@@ -20,25 +32,180 @@ pub struct InstantiateMsg {
     pub worker_set_diff_threshold: u32,
     pub encoder: Encoder,
     pub key_type: KeyType,
+    // If set, no new batches can be constructed while a worker set rotation is pending confirmation on the destination chain,
+    // so no messages get signed by a worker set that the destination chain is about to stop trusting
+    pub safe_mode: bool,
+    // If set, execute data includes a fee recipient chosen by the relayer when querying the proof,
+    // for destination gateways that refund the relayer
+    pub embed_fee_payer: bool,
+    // Hash that the destination chain expects signers to apply to the batch digest before signing it.
+    // Registered with every worker set in the multisig contract, which verifies signatures accordingly
+    pub digest_scheme: Option<DigestScheme>,
+    // If set, batches are signed as EIP-712 typed data of the destination gateway's domain instead of
+    // an EIP-191 personal message, for hardware signers and custody integrations. Only supported by the Abi encoder
+    pub eip712_domain: Option<Eip712Domain>,
+    // If set, worker sets only include this many of the active workers, those with the highest weight, to keep proofs cheap to verify.
+    // The kept workers must hold at least the quorum of all active workers, otherwise the worker set update fails
+    pub max_operators: Option<nonempty::Uint64>,
+}
+
+/// Config parameters that can be corrected after instantiation. Parameters that are not set are left unchanged
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub gateway_address: Option<String>,
+    pub destination_chain_id: Option<Uint256>,
+    pub signing_threshold: Option<MajorityThreshold>,
+    pub service_name: Option<String>,
+    pub encoder: Option<Encoder>,
+    // null removes the operator cap
+    #[serde(
+        default,
+        deserialize_with = "deserialize_set",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_operators: Option<Option<nonempty::Uint64>>,
+}
+
+// distinguishes a field that is set to null from a missing one, which serde treats the same for nested options
+fn deserialize_set<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    // Sets the governance address of provers that were instantiated before it was introduced
+    pub governance_address: Option<String>,
+    pub config_update: Option<ConfigUpdate>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     // Start building a proof that includes specified messages
     // Queries the gateway for actual message contents
-    ConstructProof { message_ids: Vec<CrossChainId> },
+    ConstructProof {
+        message_ids: Vec<CrossChainId>,
+    },
     UpdateWorkerSet,
     ConfirmWorkerSet,
+    // Marks messages of a proof as executed on the destination chain.
+    // Needed for destination gateways that execute commands individually instead of the whole batch at once.
+    // Can only be called by the admin
+    ConfirmExecutedMessages {
+        multisig_session_id: Uint64,
+        message_ids: Vec<CrossChainId>,
+    },
+    // Start building a new proof that only includes the messages of an existing proof that have not been executed yet
+    RebatchUnexecutedMessages {
+        multisig_session_id: Uint64,
+    },
+    // Starts a new signing session for an existing batch under the current worker set, if its latest session is still pending
+    // but was started by a previous worker set. A batch whose worker set rotation is no longer pending is rebuilt from its messages
+    RetryProof {
+        batch_id: BatchId,
+    },
+    // Proofs that transfer operatorship are signed in high-value sessions, which the multisig contract only opens
+    // for signing once the prover confirms them after the confirmation delay. Can only be called by the admin
+    ConfirmSigningSession {
+        multisig_session_id: Uint64,
+    },
+    // Cancels a high-value session of a proof before it is confirmed, e.g. because it transfers operatorship
+    // to a malicious worker set. Can only be called by governance
+    CancelSigningSession {
+        multisig_session_id: Uint64,
+    },
+    // Corrects config parameters without redeploying the contract. Batches that were already built keep their encoding.
+    // Can only be called by governance
+    UpdateConfig {
+        config_update: ConfigUpdate,
+    },
+    // Can only be called by governance. Pruning is disabled while no retention policy is set.
+    // Sessions are counted by id for the MaxCount policy
+    UpdateRetentionPolicy {
+        retention_policy: Option<RetentionPolicy>,
+    },
+    // Removes up to `limit` completed proofs (oldest completion first) that the retention policy allows to prune,
+    // together with their batches once no later proof of the batch is kept. Can be called by anyone
+    PruneProofs {
+        limit: u32,
+    },
+    // Callback from the multisig contract once the signing session of a proof is completed.
+    // Can only be called by the multisig contract
+    SigningCompleted {
+        session_id: Uint64,
+    },
+    // Registers a worker set, e.g. a cold-storage committee, that can sign a rotation to a new worker set
+    // if the current worker set permanently loses its quorum. The destination gateway must have confirmed it,
+    // as observed by the voting verifier. Can only be called by governance
+    RegisterRecoveryWorkerSet {
+        worker_set: WorkerSet,
+    },
+    // Starts a rotation to the current active workers that is signed by the recovery worker set.
+    // Only possible if the active workers that are part of the current worker set can't meet its threshold anymore,
+    // and no other recovery is in progress. Replaces any pending rotation. Can only be called by governance
+    ActivateRecovery,
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    // The fee payer is embedded in the execute data if the destination gateway supports it,
+    // defaulting to the zero address. It's not signed, so it can differ between queries of the same proof.
+    // If include_signing_progress is set, the response also shows which operators have signed so far
     #[returns(GetProofResponse)]
-    GetProof { multisig_session_id: Uint64 },
+    GetProof {
+        multisig_session_id: Uint64,
+        #[serde(default)]
+        fee_payer: Option<String>,
+        #[serde(default)]
+        include_signing_progress: bool,
+    },
 
     #[returns(multisig::worker_set::WorkerSet)]
     GetWorkerSet,
+
+    // Returns the key generation of a worker set that was current at some point, by worker set id, i.e. the hex encoded
+    // worker set hash of a proof, so past batches can be traced back to the set that signed them.
+    // Generations are numbered from 1 in the order the worker sets became current
+    #[returns(crate::state::WorkerSetGeneration)]
+    GetWorkerSetGeneration { worker_set_id: String },
+
+    // Returns the messages of a proof that have not been confirmed as executed yet
+    #[returns(Vec<CrossChainId>)]
+    GetUnexecutedMessages { multisig_session_id: Uint64 },
+
+    // Returns whether construction of new batches is currently blocked by a pending worker set rotation
+    #[returns(BatchingStatus)]
+    GetBatchingStatus,
+
+    // Returns the registered recovery worker set, if any
+    #[returns(Option<multisig::worker_set::WorkerSet>)]
+    GetRecoveryWorkerSet,
+
+    // Returns the batch that most recently included the message, together with the status of its proof,
+    // or nothing if the message has not been part of a proof yet
+    #[returns(Option<BatchByMessageIdResponse>)]
+    BatchByMessageId { message_id: CrossChainId },
+
+    // Compares the current worker set with the worker set the active workers would form now
+    #[returns(WorkerSetDiff)]
+    WorkerSetDiff,
+
+    // Returns the blocks at which a batch reached each stage of the proving pipeline, if they were recorded
+    #[returns(Option<BatchMetrics>)]
+    BatchMetrics { batch_id: BatchId },
+}
+
+#[cw_serde]
+pub struct BatchingStatus {
+    pub safe_mode: bool,
+    pub rotation_pending: bool,
+    pub new_batches_blocked: bool,
 }
 
 #[cw_serde]
@@ -53,4 +220,56 @@ pub struct GetProofResponse {
     pub message_ids: Vec<CrossChainId>,
     pub data: Data,
     pub status: ProofStatus,
+    // Hash of the worker set that signs the proof. It's the id under which the worker set is registered in the multisig contract,
+    // so the signers in execute_data can be checked against it. None for sessions started before the hash was recorded
+    pub worker_set_hash: Option<HexBinary>,
+    // Only set if requested by the query
+    #[serde(default)]
+    pub signing_progress: Option<SigningProgress>,
+    // Rough cost of submitting the execute data, only set once the proof is completed
+    #[serde(default)]
+    pub execution_estimate: Option<ExecutionEstimate>,
+}
+
+/// Progress of the multisig session of a proof, so it can be seen which operators are holding up the proof
+#[cw_serde]
+pub struct SigningProgress {
+    pub signers: Vec<SignerProgress>,
+    // accumulated weight of the operators that have signed so far
+    pub signed_weight: Uint256,
+    pub quorum: Uint256,
+}
+
+#[cw_serde]
+pub struct SignerProgress {
+    pub address: Addr,
+    pub weight: Uint256,
+    pub signed: bool,
+}
+
+/// Difference between the current worker set and the worker set that a rotation would switch to now
+#[cw_serde]
+pub struct WorkerSetDiff {
+    // signers of the new worker set that are not part of the current one
+    pub added: Vec<Signer>,
+    // signers of the current worker set that are not part of the new one
+    pub removed: Vec<Signer>,
+    // signers that are part of both worker sets with a different weight
+    pub weight_changes: Vec<WeightChange>,
+    // whether UpdateWorkerSet would start a rotation, given the configured worker_set_diff_threshold
+    pub rotation_due: bool,
+}
+
+#[cw_serde]
+pub struct WeightChange {
+    pub address: Addr,
+    pub old_weight: Uint256,
+    pub new_weight: Uint256,
+}
+
+#[cw_serde]
+pub struct BatchByMessageIdResponse {
+    pub batch_id: BatchId,
+    pub multisig_session_id: Uint64,
+    pub status: ProofStatus,
 }
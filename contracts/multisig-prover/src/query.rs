@@ -1,5 +1,6 @@
+use connection_router::state::CrossChainId;
 use cosmwasm_std::{
-    to_binary, Deps, QueryRequest, StdError, StdResult, Uint256, Uint64, WasmQuery,
+    to_binary, Deps, Env, QueryRequest, StdError, StdResult, Uint256, Uint64, WasmQuery,
 };
 
 use itertools::Itertools;
@@ -11,13 +12,39 @@ use multisig::{
 };
 
 use crate::{
-    msg::{GetProofResponse, ProofStatus},
-    state::{COMMANDS_BATCH, CONFIG, CURRENT_WORKER_SET, MULTISIG_SESSION_BATCH},
+    encoding::FeePayer,
+    execute::{make_worker_set, should_update_worker_set},
+    msg::{
+        BatchByMessageIdResponse, BatchingStatus, GetProofResponse, ProofStatus, SignerProgress,
+        SigningProgress, WeightChange, WorkerSetDiff,
+    },
+    state::{
+        BatchMetrics, WorkerSetGeneration, BATCH_METRICS, COMMANDS_BATCH, CONFIG,
+        CURRENT_WORKER_SET, EXECUTED_MESSAGES, MESSAGE_MULTISIG_SESSION, MULTISIG_SESSION_BATCH,
+        MULTISIG_SESSION_WORKER_SET, NEXT_WORKER_SET, RECOVERY_WORKER_SET, WORKER_SET_GENERATIONS,
+    },
+    types::BatchId,
 };
 
-pub fn get_proof(deps: Deps, multisig_session_id: Uint64) -> StdResult<GetProofResponse> {
+pub fn get_proof(
+    deps: Deps,
+    multisig_session_id: Uint64,
+    fee_payer: Option<String>,
+    include_signing_progress: bool,
+) -> StdResult<GetProofResponse> {
     let config = CONFIG.load(deps.storage)?;
 
+    let fee_payer = match (config.embed_fee_payer, fee_payer.as_deref()) {
+        (false, None) => FeePayer::Unsupported,
+        (false, Some(_)) => {
+            return Err(StdError::generic_err(
+                "destination gateway does not support a fee payer",
+            ))
+        }
+        (true, None) => FeePayer::Unset,
+        (true, Some(address)) => FeePayer::Address(address),
+    };
+
     let batch_id = MULTISIG_SESSION_BATCH.load(deps.storage, multisig_session_id.u64())?;
 
     let batch = COMMANDS_BATCH.load(deps.storage, &batch_id)?;
@@ -32,34 +59,177 @@ pub fn get_proof(deps: Deps, multisig_session_id: Uint64) -> StdResult<GetProofR
         msg: to_binary(&query_msg)?,
     }))?;
 
-    let status = match multisig.state {
-        MultisigState::Pending => ProofStatus::Pending,
+    let signing_progress =
+        include_signing_progress.then(|| signing_progress(&multisig.signers, multisig.quorum));
+
+    let (status, execution_estimate) = match multisig.state {
+        MultisigState::Pending => (ProofStatus::Pending, None),
         MultisigState::Completed { .. } => {
+            let signers = optimize_signers(multisig.signers, multisig.quorum);
+            let signatures = signers
+                .iter()
+                .filter(|(_, signature)| signature.is_some())
+                .count() as u64;
+
             let execute_data = batch
-                .encode_execute_data(
-                    multisig.quorum,
-                    optimize_signers(multisig.signers, multisig.quorum),
-                )
+                .encode_execute_data(multisig.quorum, signers, fee_payer)
                 .map_err(|err| {
                     StdError::generic_err(format!("failed to encode execute data: {}", err))
                 })?;
+            let execution_estimate = batch.estimate_execution(&execute_data, signatures);
 
-            ProofStatus::Completed { execute_data }
+            (
+                ProofStatus::Completed { execute_data },
+                Some(execution_estimate),
+            )
         }
     };
 
+    let worker_set_hash =
+        MULTISIG_SESSION_WORKER_SET.may_load(deps.storage, multisig_session_id.u64())?;
+
     Ok(GetProofResponse {
         multisig_session_id,
         message_ids: batch.message_ids,
         data: batch.data,
         status,
+        worker_set_hash,
+        signing_progress,
+        execution_estimate,
     })
 }
 
+fn signing_progress(signers: &[(Signer, Option<Signature>)], quorum: Uint256) -> SigningProgress {
+    let signers = signers
+        .iter()
+        .map(|(signer, signature)| SignerProgress {
+            address: signer.address.clone(),
+            weight: signer.weight,
+            signed: signature.is_some(),
+        })
+        .collect::<Vec<_>>();
+
+    let signed_weight = signers
+        .iter()
+        .filter(|signer| signer.signed)
+        .fold(Uint256::zero(), |acc, signer| acc + signer.weight);
+
+    SigningProgress {
+        signers,
+        signed_weight,
+        quorum,
+    }
+}
+
+pub fn batch_by_message_id(
+    deps: Deps,
+    message_id: CrossChainId,
+) -> StdResult<Option<BatchByMessageIdResponse>> {
+    let multisig_session_id = match MESSAGE_MULTISIG_SESSION.may_load(deps.storage, message_id)? {
+        Some(multisig_session_id) => Uint64::from(multisig_session_id),
+        None => return Ok(None),
+    };
+
+    let batch_id = MULTISIG_SESSION_BATCH.load(deps.storage, multisig_session_id.u64())?;
+    let proof = get_proof(deps, multisig_session_id, None, false)?;
+
+    Ok(Some(BatchByMessageIdResponse {
+        batch_id,
+        multisig_session_id,
+        status: proof.status,
+    }))
+}
+
+pub fn batch_metrics(deps: Deps, batch_id: BatchId) -> StdResult<Option<BatchMetrics>> {
+    BATCH_METRICS.may_load(deps.storage, &batch_id)
+}
+
 pub fn get_worker_set(deps: Deps) -> StdResult<WorkerSet> {
     CURRENT_WORKER_SET.load(deps.storage)
 }
 
+pub fn get_worker_set_generation(
+    deps: Deps,
+    worker_set_id: String,
+) -> StdResult<WorkerSetGeneration> {
+    WORKER_SET_GENERATIONS.load(deps.storage, &worker_set_id)
+}
+
+pub fn get_recovery_worker_set(deps: Deps) -> StdResult<Option<WorkerSet>> {
+    RECOVERY_WORKER_SET.may_load(deps.storage)
+}
+
+pub fn get_unexecuted_messages(
+    deps: Deps,
+    multisig_session_id: Uint64,
+) -> StdResult<Vec<CrossChainId>> {
+    let batch_id = MULTISIG_SESSION_BATCH.load(deps.storage, multisig_session_id.u64())?;
+    let batch = COMMANDS_BATCH.load(deps.storage, &batch_id)?;
+    let executed = EXECUTED_MESSAGES
+        .may_load(deps.storage, &batch_id)?
+        .unwrap_or_default();
+
+    Ok(batch
+        .message_ids
+        .into_iter()
+        .filter(|id| !executed.contains(id))
+        .collect())
+}
+
+pub fn get_batching_status(deps: Deps) -> StdResult<BatchingStatus> {
+    let config = CONFIG.load(deps.storage)?;
+    let rotation_pending = NEXT_WORKER_SET.may_load(deps.storage)?.is_some();
+
+    Ok(BatchingStatus {
+        safe_mode: config.safe_mode,
+        rotation_pending,
+        new_batches_blocked: config.safe_mode && rotation_pending,
+    })
+}
+
+pub fn worker_set_diff(deps: Deps, env: Env) -> StdResult<WorkerSetDiff> {
+    let config = CONFIG.load(deps.storage)?;
+    let cur_worker_set = CURRENT_WORKER_SET.load(deps.storage)?;
+    let new_worker_set = make_worker_set(deps.querier, &env, &config)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    let added = new_worker_set
+        .signers
+        .values()
+        .filter(|signer| !cur_worker_set.signers.contains_key(signer.address.as_str()))
+        .cloned()
+        .collect();
+    let removed = cur_worker_set
+        .signers
+        .values()
+        .filter(|signer| !new_worker_set.signers.contains_key(signer.address.as_str()))
+        .cloned()
+        .collect();
+    let weight_changes = cur_worker_set
+        .signers
+        .values()
+        .filter_map(|old| {
+            let new = new_worker_set.signers.get(old.address.as_str())?;
+            (old.weight != new.weight).then(|| WeightChange {
+                address: old.address.clone(),
+                old_weight: old.weight,
+                new_weight: new.weight,
+            })
+        })
+        .collect();
+
+    Ok(WorkerSetDiff {
+        added,
+        removed,
+        weight_changes,
+        rotation_due: should_update_worker_set(
+            &new_worker_set,
+            &cur_worker_set,
+            config.worker_set_diff_threshold as usize,
+        ),
+    })
+}
+
 /// Returns the minimum amount of signatures to satisfy the quorum, sorted by weight
 fn optimize_signers(
     signers: Vec<(Signer, Option<Signature>)>,
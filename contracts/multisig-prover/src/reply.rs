@@ -1,13 +1,21 @@
-use cosmwasm_std::{from_binary, DepsMut, Reply, Response, Uint64};
+use cosmwasm_std::{from_binary, DepsMut, Env, Reply, Response, Uint64};
 use cw_utils::{parse_reply_execute_data, MsgExecuteContractResponse};
 
 use crate::{
     error::ContractError,
     events::Event,
-    state::{MULTISIG_SESSION_BATCH, REPLY_BATCH},
+    state::{
+        update_batch_metrics, BatchMetrics, BATCH_MULTISIG_SESSION, COMMANDS_BATCH,
+        CURRENT_WORKER_SET, MESSAGE_MULTISIG_SESSION, MULTISIG_SESSION_BATCH,
+        MULTISIG_SESSION_WORKER_SET, REPLY_BATCH, REPLY_WORKER_SET_HASH,
+    },
 };
 
-pub fn start_multisig_reply(deps: DepsMut, reply: Reply) -> Result<Response, ContractError> {
+pub fn start_multisig_reply(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
     match parse_reply_execute_data(reply) {
         Ok(MsgExecuteContractResponse { data: Some(data) }) => {
             let command_batch_id = REPLY_BATCH.load(deps.storage)?;
@@ -22,6 +30,41 @@ pub fn start_multisig_reply(deps: DepsMut, reply: Reply) -> Result<Response, Con
                 multisig_session_id.u64(),
                 &command_batch_id,
             )?;
+            BATCH_MULTISIG_SESSION.save(
+                deps.storage,
+                &command_batch_id,
+                &multisig_session_id.u64(),
+            )?;
+
+            update_batch_metrics(deps.storage, &command_batch_id, |metrics| BatchMetrics {
+                signing_started: Some((&env.block).into()),
+                quorum_reached: None,
+                ..metrics
+            })?;
+
+            let batch = COMMANDS_BATCH.load(deps.storage, &command_batch_id)?;
+            for message_id in batch.message_ids {
+                MESSAGE_MULTISIG_SESSION.save(
+                    deps.storage,
+                    message_id,
+                    &multisig_session_id.u64(),
+                )?;
+            }
+
+            // the session was started in the same transaction, so the current worker set is the one that signs it,
+            // unless the session was started by another worker set, like the recovery worker set
+            let worker_set_hash = match REPLY_WORKER_SET_HASH.may_load(deps.storage)? {
+                Some(worker_set_hash) => {
+                    REPLY_WORKER_SET_HASH.remove(deps.storage);
+                    worker_set_hash
+                }
+                None => CURRENT_WORKER_SET.load(deps.storage)?.hash(),
+            };
+            MULTISIG_SESSION_WORKER_SET.save(
+                deps.storage,
+                multisig_session_id.u64(),
+                &worker_set_hash,
+            )?;
 
             Ok(Response::new().add_event(
                 Event::ProofUnderConstruction {
@@ -1,12 +1,13 @@
-use axelar_wasm_std::MajorityThreshold;
-use connection_router::state::ChainName;
+use axelar_wasm_std::{counter::Counter, nonempty, versioning::StateVersion, MajorityThreshold};
+use connection_router::state::{ChainName, CrossChainId};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint256};
+use cosmwasm_std::{Addr, BlockInfo, HexBinary, StdResult, Storage, Timestamp, Uint256};
 use cw_storage_plus::{Item, Map};
 use multisig::key::KeyType;
+use multisig::types::{DigestScheme, RetentionPolicy};
 use multisig::worker_set::WorkerSet;
 
-use crate::encoding::Encoder;
+use crate::encoding::{Eip712Domain, Encoder};
 use crate::types::{BatchId, CommandBatch};
 
 #[cw_serde]
@@ -23,13 +24,147 @@ pub struct Config {
     pub worker_set_diff_threshold: u32,
     pub encoder: Encoder,
     pub key_type: KeyType,
+    #[serde(default)]
+    pub safe_mode: bool,
+    #[serde(default)]
+    pub embed_fee_payer: bool,
+    #[serde(default)]
+    pub digest_scheme: Option<DigestScheme>,
+    #[serde(default)]
+    pub eip712_domain: Option<Eip712Domain>,
+    // None for provers instantiated before governance was introduced, until it is set by a migration
+    #[serde(default)]
+    pub governance: Option<Addr>,
+    // Pruning of completed proofs is disabled while no retention policy is set
+    #[serde(default)]
+    pub retention_policy: Option<RetentionPolicy>,
+    // Maximum number of operators of a worker set. If more workers are active, only the ones with the highest weight are kept
+    #[serde(default)]
+    pub max_operators: Option<nonempty::Uint64>,
 }
 
+/// Schema version of each storage namespace this code expects. Bump a version whenever its schema changes
+/// in a way that requires migrating stored data, and migrate the data in the migrate entry point
+pub const STATE_VERSIONS: [StateVersion; 4] = [
+    ("config", 1),
+    ("command_batch", 1),
+    ("worker_set_generations", 1),
+    ("worker_sets", 1),
+];
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const COMMANDS_BATCH: Map<&BatchId, CommandBatch> = Map::new("command_batch");
 pub const MULTISIG_SESSION_BATCH: Map<u64, BatchId> = Map::new("multisig_session_batch");
+// Hash of the worker set that signs a multisig session. The same batch can be signed by different worker sets across sessions,
+// so the hash is pinned per session. It doubles as the id of the worker set registered in the multisig contract
+pub const MULTISIG_SESSION_WORKER_SET: Map<u64, HexBinary> =
+    Map::new("multisig_session_worker_set");
+// Multisig session of the latest proof that included a message. Only recorded for sessions started after it was introduced
+pub const MESSAGE_MULTISIG_SESSION: Map<CrossChainId, u64> = Map::new("message_multisig_session");
+// Messages of a batch that have been confirmed as executed on the destination chain
+pub const EXECUTED_MESSAGES: Map<&BatchId, Vec<CrossChainId>> = Map::new("executed_messages");
+// Latest multisig session of a batch. Only recorded for sessions started after it was introduced
+pub const BATCH_MULTISIG_SESSION: Map<&BatchId, u64> = Map::new("batch_multisig_session");
+// Multisig sessions that the multisig contract reported as completed, keyed by (completion height, session id),
+// so they can be pruned oldest first. Only recorded for sessions completed after it was introduced
+pub const COMPLETED_SESSIONS: Map<(u64, u64), ()> = Map::new("completed_sessions");
+
+/// Block at which a batch reached a stage of the proving pipeline
+#[cw_serde]
+pub struct BlockTime {
+    pub height: u64,
+    pub timestamp: Timestamp,
+}
+
+impl From<&BlockInfo> for BlockTime {
+    fn from(block: &BlockInfo) -> Self {
+        Self {
+            height: block.height,
+            timestamp: block.time,
+        }
+    }
+}
+
+/// Progress of a batch through the proving pipeline. Stages the batch hasn't reached yet are None
+#[cw_serde]
+pub struct BatchMetrics {
+    pub constructed: BlockTime,
+    // start of the latest signing session of the batch
+    pub signing_started: Option<BlockTime>,
+    // quorum of the latest signing session of the batch
+    pub quorum_reached: Option<BlockTime>,
+    // all messages of the batch were confirmed as executed on the destination chain
+    pub executed: Option<BlockTime>,
+}
+
+// Only recorded for batches constructed after it was introduced
+pub const BATCH_METRICS: Map<&BatchId, BatchMetrics> = Map::new("batch_metrics");
+
+/// Updates the metrics of a batch, if they are recorded
+pub fn update_batch_metrics(
+    storage: &mut dyn Storage,
+    batch_id: &BatchId,
+    update: impl FnOnce(BatchMetrics) -> BatchMetrics,
+) -> StdResult<()> {
+    if let Some(metrics) = BATCH_METRICS.may_load(storage, batch_id)? {
+        BATCH_METRICS.save(storage, batch_id, &update(metrics))?;
+    }
+
+    Ok(())
+}
 
 pub const REPLY_BATCH: Item<BatchId> = Item::new("reply_tracker");
+// Hash of the worker set signing the session that is being started, if it's not the current worker set.
+// Only set until the reply of the same transaction
+pub const REPLY_WORKER_SET_HASH: Item<HexBinary> = Item::new("reply_worker_set_hash");
 
 pub const CURRENT_WORKER_SET: Item<WorkerSet> = Item::new("current_worker_set");
+
+#[cw_serde]
+pub struct WorkerSetGeneration {
+    pub generation: u64,
+    pub worker_set: WorkerSet,
+}
+
+// Every worker set that became the current one, by worker set id, so the worker set hash of a proof leads to its generation.
+// The first worker set is generation 1
+pub const WORKER_SET_GENERATIONS: Map<&str, WorkerSetGeneration> =
+    Map::new("worker_set_generations");
+pub const WORKER_SET_GENERATION_COUNTER: Counter<u64> =
+    Counter::new("worker_set_generation_counter");
+
+/// Records the worker set as the next key generation, unless it already has one
+pub fn save_worker_set_generation(
+    storage: &mut dyn Storage,
+    worker_set: &WorkerSet,
+) -> StdResult<()> {
+    let worker_set_id = worker_set.id();
+    if WORKER_SET_GENERATIONS.has(storage, &worker_set_id) {
+        return Ok(());
+    }
+
+    let generation = WORKER_SET_GENERATION_COUNTER.incr(storage)?;
+    WORKER_SET_GENERATIONS.save(
+        storage,
+        &worker_set_id,
+        &WorkerSetGeneration {
+            generation,
+            worker_set: worker_set.clone(),
+        },
+    )
+}
+
+/// Gives the current worker set of a prover that didn't record generations yet a generation.
+/// Worker sets it replaced before that are not known to the prover anymore, so they can't be backfilled
+pub fn backfill_worker_set_generations(storage: &mut dyn Storage) -> StdResult<()> {
+    match CURRENT_WORKER_SET.may_load(storage)? {
+        Some(worker_set) => save_worker_set_generation(storage, &worker_set),
+        None => Ok(()),
+    }
+}
 pub const NEXT_WORKER_SET: Item<WorkerSet> = Item::new("next_worker_set");
+
+// Worker set that signs a rotation away from a current worker set that permanently lost its quorum
+pub const RECOVERY_WORKER_SET: Item<WorkerSet> = Item::new("recovery_worker_set");
+// Block height at which recovery was activated. Cleared once the rotation started by the recovery is confirmed
+pub const RECOVERY_ACTIVATED_AT: Item<u64> = Item::new("recovery_activated_at");
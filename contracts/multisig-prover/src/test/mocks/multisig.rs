@@ -1,15 +1,16 @@
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint64,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, HexBinary, MessageInfo, Response, StdError,
+    StdResult, Uint64,
 };
 use cw_multi_test::{App, Executor};
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
 use multisig::key::{KeyType, KeyTyped, PublicKey};
 use multisig::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     types::MultisigState,
 };
 
-use crate::test::test_data::TestOperator;
+use crate::test::test_data::{self, TestOperator};
 
 use self::query::get_public_key_query_success;
 
@@ -23,6 +24,8 @@ pub fn instantiate(
 }
 
 pub const PUB_KEYS: Map<(String, KeyType), PublicKey> = Map::new("registered_pub_keys");
+// the test operators only signed the test message, so sessions for any other message stay pending
+pub const SESSION_MSG: Item<HexBinary> = Item::new("session_msg");
 pub fn execute(
     deps: DepsMut,
     _env: Env,
@@ -31,17 +34,33 @@ pub fn execute(
 ) -> Result<Response, StdError> {
     match msg {
         ExecuteMsg::StartSigningSession {
-            worker_set_id: _,
-            msg: _,
+            worker_set_id,
+            msg,
             sig_verifier: _,
             chain_name: _,
-        } => Ok(Response::new().set_data(to_binary(&Uint64::one())?)),
+            high_value: _,
+            callback: _,
+            threshold: _,
+        } => {
+            SESSION_MSG.save(deps.storage, &msg)?;
+            Ok(Response::new()
+                .set_data(to_binary(&Uint64::one())?)
+                .add_attribute("worker_set_id", worker_set_id))
+        }
+        ExecuteMsg::ConfirmSigningSession { session_id: _ } => unimplemented!(),
+        ExecuteMsg::CancelSigningSession { session_id: _ } => Ok(Response::default()),
         ExecuteMsg::SubmitSignature {
             session_id: _,
             signature: _,
         } => unimplemented!(),
-        ExecuteMsg::RegisterWorkerSet { worker_set: _ } => Ok(Response::default()),
-        ExecuteMsg::RegisterPublicKey { public_key } => {
+        ExecuteMsg::RegisterWorkerSet {
+            worker_set: _,
+            digest_scheme: _,
+        } => Ok(Response::default()),
+        ExecuteMsg::RegisterPublicKey {
+            public_key,
+            proof_of_possession: _,
+        } => {
             PUB_KEYS.save(
                 deps.storage,
                 (info.sender.to_string(), public_key.key_type()),
@@ -55,6 +74,13 @@ pub fn execute(
         ExecuteMsg::UnauthorizeCaller {
             contract_address: _,
         } => Ok(Response::default()),
+        ExecuteMsg::UpdateGracePeriod { grace_period: _ } => unimplemented!(),
+        ExecuteMsg::UpdateRetentionPolicy {
+            retention_policy: _,
+        } => unimplemented!(),
+        ExecuteMsg::PruneSessions { limit: _ } => unimplemented!(),
+        ExecuteMsg::DisableKey { worker_set_id: _ } => unimplemented!(),
+        ExecuteMsg::EnableKey { worker_set_id: _ } => unimplemented!(),
     }
 }
 
@@ -65,6 +91,7 @@ pub fn register_pub_keys(app: &mut App, multisig_address: Addr, workers: Vec<Tes
             multisig_address.clone(),
             &ExecuteMsg::RegisterPublicKey {
                 public_key: worker.pub_key.into(),
+                proof_of_possession: None,
             },
             &[],
         )
@@ -74,7 +101,10 @@ pub fn register_pub_keys(app: &mut App, multisig_address: Addr, workers: Vec<Tes
 
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::GetMultisig { session_id: _ } => to_binary(&query::query_success()),
+        QueryMsg::GetMultisig { session_id: _ } => match SESSION_MSG.may_load(deps.storage)? {
+            Some(msg) if msg != test_data::msg_to_sign() => to_binary(&query::query_pending()),
+            _ => to_binary(&query::query_success()),
+        },
         QueryMsg::GetWorkerSet { worker_set_id: _ } => unimplemented!(),
         QueryMsg::GetPublicKey {
             worker_address,
@@ -84,6 +114,26 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             worker_address,
             key_type,
         )),
+        QueryMsg::GetKeyStats { worker_set_id: _ } => unimplemented!(),
+        QueryMsg::GetSignerLiveness { signer: _ } => unimplemented!(),
+        QueryMsg::GetArchivedSession { session_id: _ } => unimplemented!(),
+        QueryMsg::PendingSessions {
+            participant: _,
+            start_after: _,
+            limit: _,
+        } => unimplemented!(),
+        QueryMsg::GetAggregateSignature { session_id: _ } => unimplemented!(),
+        QueryMsg::ListSigningSessions {
+            start_after: _,
+            limit: _,
+            status_filter: _,
+        } => unimplemented!(),
+        QueryMsg::ListSessionSignatures {
+            session_id: _,
+            start_after: _,
+            limit: _,
+        } => unimplemented!(),
+        QueryMsg::VerifySessionSignatures { session_id: _ } => unimplemented!(),
     }
 }
 
@@ -118,9 +168,30 @@ mod query {
                 completed_at: 12345,
             },
             quorum,
+            session_threshold: quorum,
+            signers,
+        }
+    }
+
+    pub fn query_pending() -> Multisig {
+        let Multisig {
+            quorum,
+            session_threshold,
             signers,
+            ..
+        } = query_success();
+
+        Multisig {
+            state: MultisigState::Pending,
+            quorum,
+            session_threshold,
+            signers: signers
+                .into_iter()
+                .map(|(signer, _)| (signer, None))
+                .collect(),
         }
     }
+
     pub fn get_public_key_query_success(
         deps: Deps,
         worker: String,
@@ -96,5 +96,6 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::GetService { .. } => todo!(),
         QueryMsg::GetWorker { .. } => todo!(),
+        QueryMsg::GetVestingBond { .. } => todo!(),
     }
 }
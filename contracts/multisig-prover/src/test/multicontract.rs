@@ -5,6 +5,7 @@ use super::{mocks, test_data};
 
 pub const INSTANTIATOR: &str = "instantiator";
 pub const RELAYER: &str = "relayer";
+pub const GOVERNANCE: &str = "governance";
 
 pub struct TestCaseConfig {
     pub app: App,
@@ -46,6 +47,7 @@ fn instantiate_mock_multisig(app: &mut App) -> Addr {
         governance_address: "governance".parse().unwrap(),
         rewards_address: "rewards".to_string(),
         grace_period: 2,
+        confirmation_delay: 10,
     };
 
     app.instantiate_contract(
@@ -73,6 +75,7 @@ fn instantiate_mock_gateway(app: &mut App) -> Addr {
     let msg = gateway::msg::InstantiateMsg {
         verifier_address: "verifier".to_string(),
         router_address: "router".to_string(),
+        ibc_subscribers: vec![],
     };
 
     app.instantiate_contract(
@@ -152,10 +155,12 @@ fn instantiate_prover(
     multisig_address: String,
     service_registry_address: String,
     voting_verifier_address: String,
+    safe_mode: bool,
 ) -> Addr {
     let code_id = app.store_code(contract_prover());
     let msg = crate::msg::InstantiateMsg {
         admin_address: INSTANTIATOR.to_string(),
+        governance_address: GOVERNANCE.to_string(),
         gateway_address,
         multisig_address,
         service_registry_address,
@@ -167,6 +172,11 @@ fn instantiate_prover(
         worker_set_diff_threshold: 0,
         encoder: crate::encoding::Encoder::Abi,
         key_type: multisig::key::KeyType::Ecdsa,
+        safe_mode,
+        embed_fee_payer: false,
+        digest_scheme: None,
+        eip712_domain: None,
+        max_operators: None,
     };
 
     app.instantiate_contract(
@@ -181,6 +191,14 @@ fn instantiate_prover(
 }
 
 pub fn setup_test_case() -> TestCaseConfig {
+    setup(false)
+}
+
+pub fn setup_safe_mode_test_case() -> TestCaseConfig {
+    setup(true)
+}
+
+fn setup(safe_mode: bool) -> TestCaseConfig {
     let mut app = mock_app();
 
     let gateway_address = instantiate_mock_gateway(&mut app);
@@ -194,6 +212,7 @@ pub fn setup_test_case() -> TestCaseConfig {
         multisig_address.to_string(),
         service_registry_address.to_string(),
         voting_verifier_address.to_string(),
+        safe_mode,
     );
 
     app.update_block(next_block);
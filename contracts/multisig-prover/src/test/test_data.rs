@@ -94,15 +94,21 @@ pub fn messages() -> Vec<Message> {
         .unwrap()
         .to_array::<32>()
         .unwrap(),
+        token: None,
     }]
 }
 
+// address of the prover in the multicontract test case, so the signatures of the test operators match the proofs of both
+pub fn prover() -> Addr {
+    Addr::unchecked("contract4")
+}
+
 pub fn destination_chain_id() -> Uint256 {
     Uint256::from(1337u128)
 }
 
 pub fn encoded_data_with_operator_transfer() -> HexBinary {
-    HexBinary::from_hex("0000000000000000000000000000000000000000000000000000000000000539000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000001db7362a425610350d24692e913a3ac1b709565274875883962b48d1d3ed43e5c0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000147472616e736665724f70657261746f72736869700000000000000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001e000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000120000000000000000000000000000000000000000000000000000000000000001e00000000000000000000000000000000000000000000000000000000000000050000000000000000000000000249c31dd0eacb2d73bbe5a0513416cd3888cb5b0000000000000000000000001ae3758c032ae8ebf6f075bb5b6ff6129b56e632000000000000000000000000adb32b50b13f962d302619111de6a1020fbd55f7000000000000000000000000defab04334a82fdea683bca3617c33bc469d4cc9000000000000000000000000e6857cf86038ba741e64ce0d3c883a26a7d3cb460000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a").unwrap()
+    HexBinary::from_hex("0000000000000000000000000000000000000000000000000000000000000539000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000001de3b00230bb88a5813e548fdfb91436e9577800114c621ded9066d50d10b4e890000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000147472616e736665724f70657261746f72736869700000000000000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001e000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000120000000000000000000000000000000000000000000000000000000000000001e00000000000000000000000000000000000000000000000000000000000000050000000000000000000000000249c31dd0eacb2d73bbe5a0513416cd3888cb5b0000000000000000000000001ae3758c032ae8ebf6f075bb5b6ff6129b56e632000000000000000000000000adb32b50b13f962d302619111de6a1020fbd55f7000000000000000000000000defab04334a82fdea683bca3617c33bc469d4cc9000000000000000000000000e6857cf86038ba741e64ce0d3c883a26a7d3cb460000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000a").unwrap()
 }
 
 pub fn chain_id_operator_transfer() -> Uint256 {
@@ -111,19 +117,19 @@ pub fn chain_id_operator_transfer() -> Uint256 {
 
 pub fn encoded_data() -> HexBinary {
     HexBinary::from_hex(
-        "0000000000000000000000000000000000000000000000000000000000000539000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000013ee2f8af2201994e3518c9ce6848774785c2eef3bdbf9f954899497616dd59af000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000013617070726f7665436f6e747261637443616c6c0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000a4f10f76b86e01b98daf66a3d02a65e14adb07678c3685dc41c2eca11426f8035742fb97ea9f14931152670a5703f18fe8b392f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000967616e616368652d310000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a30783532343434663138333541646330323038366333374362323236353631363035653245313639396200000000000000000000000000000000000000000000").unwrap()
+        "0000000000000000000000000000000000000000000000000000000000000539000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000001ff4c4421f7c4a5a3a7c118e83ce2181645a0a6cc39db019a5a7c864c29fd09fe000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000013617070726f7665436f6e747261637443616c6c0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000a4f10f76b86e01b98daf66a3d02a65e14adb07678c3685dc41c2eca11426f8035742fb97ea9f14931152670a5703f18fe8b392f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000967616e616368652d310000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a30783532343434663138333541646330323038366333374362323236353631363035653245313639396200000000000000000000000000000000000000000000").unwrap()
 }
 
 pub fn msg_to_sign() -> HexBinary {
-    HexBinary::from_hex("7140b89fc7207cd96a262ef8e3f9301cdd72549e01894070ce5a7f2d4096819f").unwrap()
+    HexBinary::from_hex("9e96754be55e032a964e8c11d87c3cc2ad6bfde228dcd999d0631cd6e26f8ef9").unwrap()
 }
 
 pub fn encoded_proof() -> HexBinary {
-    HexBinary::from_hex("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000500000000000000000000000011c67adfe52a3782bd518294188c4aafaaf6cdeb0000000000000000000000004905fd2e40b1a037256e32fe1e4bca41ae510d730000000000000000000000004ef5c8d81b6417fa80c320b5fc1d3900506dff540000000000000000000000006c51eec96bf0a8ec799cdd0bbcb4512f8334afe8000000000000000000000000a6e7b3b7a1af4103f540d05776f7dd200210201f0000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000001600000000000000000000000000000000000000000000000000000000000000041eff382a60ef4d917ae0bcbd58ab121fbd7bd53b777773bf1da298d3701565a3379a5b741f881ad739e651a544f2331c085c482857856201c49328ae9ba65cf131c000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041301ff4127c1c5a865732776769c6227e4978a1c4567bc1d2926cd798422f1f507b10989e173c6ebc23334eb7e3c713e940369c418061d5cad1173882f35dc43d1b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041c55581edbf0401d0cd3495522323e45d4521312dafdedf39b4adc8085a3842c74f13c055b72d12ec3afc1e8f9c37b5f660fbefb38165dbe61090923865e158271b00000000000000000000000000000000000000000000000000000000000000").unwrap()
+    HexBinary::from_hex("000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000050000000000000000000000001a642f0e3c3af545e7acbd38b07251b3990914f10000000000000000000000003325a78425f17a7e487eb5666b2bfd93abb06c700000000000000000000000005050a4f4b3f9338c3472dcc01a87c76a144b3c9c000000000000000000000000c48b812bb43401392c037381aca934f4069c0517000000000000000000000000d09ad14080d4b257a819a4f579b8485be88f086c0000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000000000000000000000000000000000000000016000000000000000000000000000000000000000000000000000000000000000417d3d7fe5920cd218db6ba4e8ab3b11d7dd4dd5116d9f3ba33b6cb541586eb9736a4d26227ce3f2780e8e7a52b842b36381bdd695066a8f031668ccfe9f93c3231b0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000415246ee7bfdf066240260c113c00d838e5b9d42bc8eb8f7608c859760da2c1ac045e19e9732a173d5d03bc24e1c5500a4b1f4be3ea05e1bbd09636d4f218763a21b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041d639b40b9fecba73b9f25698604ce8af4aa79a9372a243ae77ed156186cbfebf1353ff2b34025610623a405a229fcd7c2e5f6754c3ab633306d81abeb11e96b71c00000000000000000000000000000000000000000000000000000000000000").unwrap()
 }
 
 pub fn execute_data() -> HexBinary {
-    HexBinary::from_hex("09c5eabe000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000007600000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000034000000000000000000000000000000000000000000000000000000000000002e00000000000000000000000000000000000000000000000000000000000000539000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000013ee2f8af2201994e3518c9ce6848774785c2eef3bdbf9f954899497616dd59af000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000013617070726f7665436f6e747261637443616c6c0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000a4f10f76b86e01b98daf66a3d02a65e14adb07678c3685dc41c2eca11426f8035742fb97ea9f14931152670a5703f18fe8b392f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000967616e616368652d310000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a3078353234343466313833354164633032303836633337436232323635363136303565324531363939620000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000500000000000000000000000011c67adfe52a3782bd518294188c4aafaaf6cdeb0000000000000000000000004905fd2e40b1a037256e32fe1e4bca41ae510d730000000000000000000000004ef5c8d81b6417fa80c320b5fc1d3900506dff540000000000000000000000006c51eec96bf0a8ec799cdd0bbcb4512f8334afe8000000000000000000000000a6e7b3b7a1af4103f540d05776f7dd200210201f0000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000001600000000000000000000000000000000000000000000000000000000000000041eff382a60ef4d917ae0bcbd58ab121fbd7bd53b777773bf1da298d3701565a3379a5b741f881ad739e651a544f2331c085c482857856201c49328ae9ba65cf131c000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041301ff4127c1c5a865732776769c6227e4978a1c4567bc1d2926cd798422f1f507b10989e173c6ebc23334eb7e3c713e940369c418061d5cad1173882f35dc43d1b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041c55581edbf0401d0cd3495522323e45d4521312dafdedf39b4adc8085a3842c74f13c055b72d12ec3afc1e8f9c37b5f660fbefb38165dbe61090923865e158271b00000000000000000000000000000000000000000000000000000000000000").unwrap()
+    HexBinary::from_hex("09c5eabe000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000007600000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000034000000000000000000000000000000000000000000000000000000000000002e00000000000000000000000000000000000000000000000000000000000000539000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000001ff4c4421f7c4a5a3a7c118e83ce2181645a0a6cc39db019a5a7c864c29fd09fe000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000013617070726f7665436f6e747261637443616c6c0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000a4f10f76b86e01b98daf66a3d02a65e14adb07678c3685dc41c2eca11426f8035742fb97ea9f14931152670a5703f18fe8b392f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000967616e616368652d310000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a307835323434346631383335416463303230383663333743623232363536313630356532453136393962000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000050000000000000000000000001a642f0e3c3af545e7acbd38b07251b3990914f10000000000000000000000003325a78425f17a7e487eb5666b2bfd93abb06c700000000000000000000000005050a4f4b3f9338c3472dcc01a87c76a144b3c9c000000000000000000000000c48b812bb43401392c037381aca934f4069c0517000000000000000000000000d09ad14080d4b257a819a4f579b8485be88f086c0000000000000000000000000000000000000000000000000000000000000005000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000000000000000000000000000000000000000016000000000000000000000000000000000000000000000000000000000000000417d3d7fe5920cd218db6ba4e8ab3b11d7dd4dd5116d9f3ba33b6cb541586eb9736a4d26227ce3f2780e8e7a52b842b36381bdd695066a8f031668ccfe9f93c3231b0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000415246ee7bfdf066240260c113c00d838e5b9d42bc8eb8f7608c859760da2c1ac045e19e9732a173d5d03bc24e1c5500a4b1f4be3ea05e1bbd09636d4f218763a21b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000041d639b40b9fecba73b9f25698604ce8af4aa79a9372a243ae77ed156186cbfebf1353ff2b34025610623a405a229fcd7c2e5f6754c3ab633306d81abeb11e96b71c00000000000000000000000000000000000000000000000000000000000000").unwrap()
 }
 
 pub fn threshold() -> MajorityThreshold {
@@ -148,36 +154,36 @@ pub fn operators() -> Vec<TestOperator> {
     [
         (
             "axelar1up3vvhxg4swh2lfeh8n84dat86j6hmgz20d6d3",
-            "0312474390012cfbb621c91295dae42b11daaceffbcb7136045c86537a7b37042c",
-            "6C51eec96bf0a8ec799cdD0Bbcb4512f8334Afe8",
+            "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f",
+            "1a642f0e3c3af545e7acbd38b07251b3990914f1",
             1u128,
             None,
         ),
         (
             "axelar10ad5vqhuw2jgp8x6hf59qjjejlna2nh4sfsklc",
-            "0277b844fb49bf9b5838dc15d79f3c6c7701eb2e0ab066d4aa63ce162b08718d37",
-            "11C67adfe52a3782bd518294188C4AAfaaF6cDeb",
+            "024d4b6cd1361032ca9bd2aeb9d900aa4d45d9ead80ac9423374c451a7254d0766",
+            "5050a4f4b3f9338c3472dcc01a87c76a144b3c9c",
             1u128,
-            Some("eff382a60ef4d917ae0bcbd58ab121fbd7bd53b777773bf1da298d3701565a3379a5b741f881ad739e651a544f2331c085c482857856201c49328ae9ba65cf13"),
+            Some("5246ee7bfdf066240260c113c00d838e5b9d42bc8eb8f7608c859760da2c1ac045e19e9732a173d5d03bc24e1c5500a4b1f4be3ea05e1bbd09636d4f218763a2"),
         ),
         (
             "axelar14g0tmk5ldxxdqtl0utl69ck43cpcvd0ay4lfyt",
-            "03a54c7ad1621b57803bc5bcfe826b713bf5c5a15906ccd08f271d58992bee71d6",
-            "4905FD2e40B1A037256e32fe1e4BCa41AE510d73",
+            "02531fe6068134503d2723133227c867ac8fa6c83c537e9a44c3c5bdbdcb1fe337",
+            "3325a78425f17a7e487eb5666b2bfd93abb06c70",
             1u128,
-            Some("301ff4127c1c5a865732776769c6227e4978a1c4567bc1d2926cd798422f1f507b10989e173c6ebc23334eb7e3c713e940369c418061d5cad1173882f35dc43d"),
+            Some("7d3d7fe5920cd218db6ba4e8ab3b11d7dd4dd5116d9f3ba33b6cb541586eb9736a4d26227ce3f2780e8e7a52b842b36381bdd695066a8f031668ccfe9f93c323"),
         ),
         (
             "axelar1gwd8wd3qkapk8pnwdu4cchah2sjjws6lx694r6",
-            "033a9726a6e2fdc308089c6cab1e6fda2e2bddeb2bcf800990e5fd2c05a270c9df",
-            "a6E7b3b7A1AF4103F540D05776F7dd200210201F",
+            "03462779ad4aad39514614751a71085f2f10e1c7a593e4e030efb5b8721ce55b0b",
+            "c48b812bb43401392c037381aca934f4069c0517",
             1u128,
-            Some("c55581edbf0401d0cd3495522323e45d4521312dafdedf39b4adc8085a3842c74f13c055b72d12ec3afc1e8f9c37b5f660fbefb38165dbe61090923865e15827"),
+            Some("d639b40b9fecba73b9f25698604ce8af4aa79a9372a243ae77ed156186cbfebf1353ff2b34025610623a405a229fcd7c2e5f6754c3ab633306d81abeb11e96b7"),
         ),
         (
             "axelar1fcrwupthhxm6zsd7kw00w2fk530p6wtt8mj92l",
-            "02d1e0cff63aa3e7988e4070242fa37871a9abc79ecf851cce9877297d1316a090",
-            "4ef5C8d81b6417fa80c320B5Fc1D3900506dFf54",
+            "0362c0a046dacce86ddd0343c6d3c7c79c2208ba0d9c9cf24a6d046d21d21f90f7",
+            "d09ad14080d4b257a819a4f579b8485be88f086c",
             1u128,
             None,
         ),
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use axelar_wasm_std::{Participant, Snapshot};
 use connection_router::state::CrossChainId;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{from_binary, HexBinary, StdResult, Uint256};
+use cosmwasm_std::{from_binary, Addr, HexBinary, StdResult, Uint256};
 use cw_storage_plus::{Key, KeyDeserialize, PrimaryKey};
 use multisig::{
     key::{PublicKey, Signature},
@@ -11,11 +11,12 @@ use multisig::{
 };
 use sha3::{Digest, Keccak256};
 
-use crate::encoding::{Data, Encoder};
+use crate::encoding::{Data, Eip712Domain, Encoder};
 
 #[cw_serde]
 pub enum CommandType {
     ApproveContractCall,
+    ApproveContractCallWithMint,
     TransferOperatorship,
 }
 
@@ -23,6 +24,7 @@ impl Display for CommandType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CommandType::ApproveContractCall => write!(f, "approveContractCall"),
+            CommandType::ApproveContractCallWithMint => write!(f, "approveContractCallWithMint"),
             CommandType::TransferOperatorship => write!(f, "transferOperatorship"),
         }
     }
@@ -70,7 +72,14 @@ impl KeyDeserialize for BatchId {
 }
 
 impl BatchId {
-    pub fn new(message_ids: &[CrossChainId], new_worker_set: Option<WorkerSet>) -> BatchId {
+    /// The id is domain-separated by the prover and the destination chain id,
+    /// so the same messages routed through different provers or to different chains never share a batch
+    pub fn new(
+        prover: &Addr,
+        destination_chain_id: Uint256,
+        message_ids: &[CrossChainId],
+        new_worker_set: Option<WorkerSet>,
+    ) -> BatchId {
         let mut message_ids = message_ids
             .iter()
             .map(|id| id.to_string())
@@ -80,7 +89,17 @@ impl BatchId {
         if let Some(new_worker_set) = new_worker_set {
             message_ids.push(new_worker_set.hash().to_string())
         }
-        Keccak256::digest(message_ids.join(",")).as_slice().into()
+
+        let domain = [prover.to_string(), destination_chain_id.to_string()];
+        Keccak256::digest(
+            domain
+                .into_iter()
+                .chain(message_ids)
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .as_slice()
+        .into()
     }
 }
 
@@ -90,6 +109,8 @@ pub struct CommandBatch {
     pub message_ids: Vec<CrossChainId>,
     pub data: Data,
     pub encoder: Encoder,
+    #[serde(default)]
+    pub eip712_domain: Option<Eip712Domain>,
 }
 
 #[cw_serde]
@@ -0,0 +1,276 @@
+use bls12_381::{
+    hash_to_curve::{ExpandMsgXmd, HashToCurve},
+    pairing, G1Affine, G2Affine, G2Projective, Scalar,
+};
+use cosmwasm_std::HexBinary;
+
+use crate::ContractError;
+
+// proof of possession scheme with public keys in G1 and signatures in G2, so signatures over the same message can be aggregated.
+// Aggregating signatures over the same message is only safe against rogue key attacks if every key proved possession of its secret key,
+// so keys can only be registered together with a proof of possession (see bls12_381_verify_possession)
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+const BLS12_381_PUBKEY_LEN: usize = 48;
+pub const BLS12_381_SIGNATURE_LEN: usize = 96;
+
+pub fn bls12_381_verify(
+    msg_hash: &[u8],
+    sig: &[u8],
+    pub_key: &[u8],
+) -> Result<bool, ContractError> {
+    verify(msg_hash, sig, pub_key, DST)
+}
+
+/// Verifies that the proof is a signature of the public key itself, which only the owner of the secret key can produce
+pub fn bls12_381_verify_possession(pub_key: &[u8], proof: &[u8]) -> Result<bool, ContractError> {
+    verify(pub_key, proof, pub_key, POP_DST)
+}
+
+fn verify(msg: &[u8], sig: &[u8], pub_key: &[u8], dst: &[u8]) -> Result<bool, ContractError> {
+    let pub_key = decode_pub_key(pub_key)?;
+    let sig = decode_signature(sig)?;
+
+    Ok(pairing(&pub_key, &hash_to_g2(msg, dst)) == pairing(&G1Affine::generator(), &sig))
+}
+
+fn hash_to_g2(msg: &[u8], dst: &[u8]) -> G2Affine {
+    G2Affine::from(
+        <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(msg, dst),
+    )
+}
+
+/// Secret key of a bls12-381 signer. The contract never signs, this is for workers and tests
+pub struct SecretKey(Scalar);
+
+impl SecretKey {
+    /// Parses a big endian encoded secret key, which must be a non-zero scalar
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let mut le_bytes = *bytes;
+        le_bytes.reverse();
+
+        Option::<Scalar>::from(Scalar::from_bytes(&le_bytes))
+            .filter(|scalar| *scalar != Scalar::zero())
+            .map(SecretKey)
+    }
+
+    /// Derives a secret key from 64 bytes of randomness
+    pub fn from_seed(seed: &[u8; 64]) -> Self {
+        SecretKey(Scalar::from_bytes_wide(seed))
+    }
+
+    /// Big endian encoding of the secret key
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = self.0.to_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    pub fn pub_key(&self) -> HexBinary {
+        HexBinary::from(
+            G1Affine::from(G1Affine::generator() * self.0)
+                .to_compressed()
+                .as_slice(),
+        )
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> HexBinary {
+        self.sign_with_dst(msg, DST)
+    }
+
+    pub fn prove_possession(&self) -> HexBinary {
+        self.sign_with_dst(self.pub_key().as_slice(), POP_DST)
+    }
+
+    fn sign_with_dst(&self, msg: &[u8], dst: &[u8]) -> HexBinary {
+        HexBinary::from(
+            G2Affine::from(hash_to_g2(msg, dst) * self.0)
+                .to_compressed()
+                .as_slice(),
+        )
+    }
+}
+
+/// Aggregates signatures over the same message into a single signature, which verifies against the sum of the signers' public keys
+pub fn bls12_381_aggregate<'a>(
+    sigs: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<HexBinary, ContractError> {
+    let aggregate = sigs
+        .into_iter()
+        .map(decode_signature)
+        .try_fold(G2Projective::identity(), |aggregate, sig| {
+            sig.map(|sig| aggregate + G2Projective::from(sig))
+        })?;
+
+    Ok(HexBinary::from(
+        G2Affine::from(aggregate).to_compressed().as_slice(),
+    ))
+}
+
+pub fn decode_pub_key(pub_key: &[u8]) -> Result<G1Affine, ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidPublicKeyFormat {
+        reason: reason.into(),
+    };
+
+    let bytes: &[u8; BLS12_381_PUBKEY_LEN] = pub_key
+        .try_into()
+        .map_err(|_| invalid("Invalid input length"))?;
+    let pub_key: G1Affine = Option::from(G1Affine::from_compressed(bytes))
+        .ok_or_else(|| invalid("not a point in the G1 subgroup"))?;
+
+    // the identity verifies any signature that is the identity as well
+    if bool::from(pub_key.is_identity()) {
+        return Err(invalid("public key is the identity"));
+    }
+
+    Ok(pub_key)
+}
+
+fn decode_signature(sig: &[u8]) -> Result<G2Affine, ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidSignatureFormat {
+        reason: reason.into(),
+    };
+
+    let bytes: &[u8; BLS12_381_SIGNATURE_LEN] = sig
+        .try_into()
+        .map_err(|_| invalid("Invalid input length"))?;
+
+    Option::from(G2Affine::from_compressed(bytes))
+        .ok_or_else(|| invalid("not a point in the G2 subgroup"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::common::bls12_381_test_data::g2_multiple;
+
+    use super::*;
+
+    // test vector of the proof of possession ciphersuite, as used by the ethereum consensus layer
+    fn secret_key() -> SecretKey {
+        SecretKey::from_bytes(
+            &HexBinary::from_hex(
+                "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+            )
+            .unwrap()
+            .to_array()
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn pub_key() -> HexBinary {
+        HexBinary::from_hex("a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a").unwrap()
+    }
+
+    fn signature() -> HexBinary {
+        HexBinary::from_hex("b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55").unwrap()
+    }
+
+    #[test]
+    fn verify_signature() {
+        let msg = [0u8; 32];
+
+        assert_eq!(secret_key().pub_key(), pub_key());
+        assert_eq!(secret_key().sign(&msg), signature());
+        assert!(bls12_381_verify(&msg, &signature(), &pub_key()).unwrap());
+
+        assert!(!bls12_381_verify(&[1u8; 32], &signature(), &pub_key()).unwrap());
+        assert!(!bls12_381_verify(
+            &msg,
+            &signature(),
+            &SecretKey::from_seed(&[1u8; 64]).pub_key()
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn verify_aggregate_signature() {
+        let msg = [2u8; 32];
+        let secret_keys = [secret_key(), SecretKey::from_seed(&[1u8; 64])];
+        let sigs: Vec<_> = secret_keys.iter().map(|sk| sk.sign(&msg)).collect();
+
+        let aggregate_pub_key = G1Affine::from(
+            secret_keys
+                .iter()
+                .map(|sk| decode_pub_key(sk.pub_key().as_slice()).unwrap())
+                .fold(bls12_381::G1Projective::identity(), |sum, pub_key| {
+                    sum + pub_key
+                }),
+        );
+
+        assert!(bls12_381_verify(
+            &msg,
+            bls12_381_aggregate(sigs.iter().map(HexBinary::as_slice))
+                .unwrap()
+                .as_slice(),
+            &aggregate_pub_key.to_compressed()
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn verify_proof_of_possession() {
+        let proof = secret_key().prove_possession();
+        assert!(bls12_381_verify_possession(&pub_key(), &proof).unwrap());
+
+        // a signature of the public key with the signing domain is no proof of possession
+        let sig = secret_key().sign(pub_key().as_slice());
+        assert!(!bls12_381_verify_possession(&pub_key(), &sig).unwrap());
+
+        let other_key = SecretKey::from_seed(&[1u8; 64]);
+        assert!(!bls12_381_verify_possession(&other_key.pub_key(), &proof).unwrap());
+    }
+
+    #[test]
+    fn secret_key_encoding() {
+        assert_eq!(
+            SecretKey::from_bytes(&secret_key().to_bytes())
+                .unwrap()
+                .pub_key(),
+            pub_key()
+        );
+        assert!(SecretKey::from_bytes(&[0u8; 32]).is_none());
+        assert!(SecretKey::from_bytes(&[0xffu8; 32]).is_none());
+    }
+
+    #[test]
+    fn aggregate_signatures() {
+        let sigs = [g2_multiple(2), g2_multiple(3)];
+
+        assert_eq!(
+            bls12_381_aggregate(sigs.iter().map(HexBinary::as_slice)).unwrap(),
+            g2_multiple(5)
+        );
+        assert_eq!(bls12_381_aggregate([sigs[0].as_slice()]).unwrap(), sigs[0]);
+    }
+
+    #[test]
+    fn aggregate_invalid_signature_fails() {
+        let sig = g2_multiple(2);
+        let mut not_on_curve = sig.to_vec();
+        not_on_curve[95] ^= 1;
+
+        assert!(matches!(
+            bls12_381_aggregate([sig.as_slice(), not_on_curve.as_slice()]),
+            Err(ContractError::InvalidSignatureFormat { .. })
+        ));
+        assert!(matches!(
+            bls12_381_aggregate([&sig.as_slice()[1..]]),
+            Err(ContractError::InvalidSignatureFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_identity_pub_key_fails() {
+        let mut identity = [0u8; BLS12_381_PUBKEY_LEN];
+        identity[0] = 0xc0;
+
+        assert_eq!(
+            decode_pub_key(&identity).unwrap_err(),
+            ContractError::InvalidPublicKeyFormat {
+                reason: "public key is the identity".into()
+            }
+        );
+    }
+}
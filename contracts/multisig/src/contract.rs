@@ -1,20 +1,28 @@
+use axelar_wasm_std::{ensure_state_versions, versioning};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, HexBinary, MessageInfo, Response, StdResult,
-    Uint64,
+    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, HexBinary, MessageInfo, Reply, Response,
+    StdResult, Storage, SubMsgResult, Uint256, Uint64,
 };
+use error_stack::Report;
 
 use crate::{
     events::Event,
-    msg::{ExecuteMsg, InstantiateMsg, Multisig, QueryMsg},
+    msg::{CallbackMsg, ExecuteMsg, InstantiateMsg, Multisig, QueryMsg},
     state::{
-        get_worker_set, Config, CONFIG, SIGNING_SESSIONS, SIGNING_SESSION_COUNTER, WORKER_SETS,
+        clear_signer_liveness, get_worker_set, index_signing_sessions, signing_sessions, Config,
+        CONFIG, SIGNING_SESSION_COUNTER, STATE_VERSIONS, WORKER_SETS,
     },
     types::{MsgToSign, MultisigState},
     ContractError,
 };
 
+pub const SIGNING_CALLBACK_REPLY_ID: u64 = 1;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -26,14 +34,61 @@ pub fn instantiate(
         governance: deps.api.addr_validate(&msg.governance_address)?,
         rewards_contract: deps.api.addr_validate(&msg.rewards_address)?,
         grace_period: msg.grace_period,
+        confirmation_delay: msg.confirmation_delay,
+        retention_policy: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
     SIGNING_SESSION_COUNTER.save(deps.storage, &Uint64::zero())?;
 
+    versioning::init_versions(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &STATE_VERSIONS,
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    _msg: Empty,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    versioning::migrate_state(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &STATE_VERSIONS,
+        migrate_namespace,
+    )?;
+
     Ok(Response::default())
 }
 
+/// Brings the stored data of a namespace from its stored schema version to the one in STATE_VERSIONS
+fn migrate_namespace(
+    storage: &mut dyn Storage,
+    namespace: &str,
+    stored: Option<u64>,
+) -> Result<(), axelar_wasm_std::ContractError> {
+    match (namespace, stored) {
+        // sessions are indexed by status since version 2
+        ("signing_sessions", None | Some(1)) => Ok(index_signing_sessions(storage)?),
+        // lifetime totals can't be split into windows, so liveness starts over with version 1
+        ("signer_liveness", None) => Ok(clear_signer_liveness(storage)?),
+        // state written before versions were recorded is still readable thanks to serde defaults
+        (_, None) => Ok(()),
+        (_, Some(stored)) => Err(Report::new(versioning::Error::MissingMigration {
+            namespace: namespace.to_string(),
+            stored,
+        })
+        .into()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -41,54 +96,132 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
+    ensure_state_versions!(deps.storage, &STATE_VERSIONS);
+
     match msg {
         ExecuteMsg::StartSigningSession {
             worker_set_id,
             msg,
             chain_name,
             sig_verifier,
+            high_value,
+            callback,
+            threshold,
         } => {
-            execute::require_authorized_caller(&deps, info.sender)?;
+            execute::require_authorized_caller(&deps, info.sender.clone())?;
 
             let _sig_verifier = sig_verifier
                 .map(|addr| deps.api.addr_validate(&addr))
                 .transpose()?; // TODO: handle callback
-            execute::start_signing_session(
-                deps,
+            let msg = msg
+                .try_into()
+                .map_err(axelar_wasm_std::ContractError::from)?;
+            let request = execute::SigningRequest {
                 worker_set_id,
-                msg.try_into()
-                    .map_err(axelar_wasm_std::ContractError::from)?,
+                msg,
                 chain_name,
-            )
+                callback: callback.then(|| info.sender.clone()),
+                threshold,
+            };
+
+            if high_value {
+                execute::start_high_value_signing_session(deps, env, info, request)
+            } else {
+                execute::start_signing_session(deps, env, request)
+            }
+        }
+        ExecuteMsg::ConfirmSigningSession { session_id } => {
+            execute::confirm_signing_session(deps, env, info, session_id)
+        }
+        ExecuteMsg::CancelSigningSession { session_id } => {
+            execute::cancel_signing_session(deps, info, session_id)
         }
         ExecuteMsg::SubmitSignature {
             session_id,
             signature,
         } => execute::submit_signature(deps, env, info, session_id, signature),
-        ExecuteMsg::RegisterWorkerSet { worker_set } => {
-            execute::register_worker_set(deps, worker_set)
-        }
-        ExecuteMsg::RegisterPublicKey { public_key } => {
-            execute::register_pub_key(deps, info, public_key)
+        ExecuteMsg::RegisterWorkerSet {
+            worker_set,
+            digest_scheme,
+        } => {
+            // anyone can register a worker set, but only authorized callers decide which scheme its signers use
+            let authorized = match digest_scheme {
+                Some(_) => {
+                    execute::require_authorized_caller(&deps, info.sender)?;
+                    true
+                }
+                None => execute::require_authorized_caller(&deps, info.sender).is_ok(),
+            };
+            execute::register_worker_set(deps, worker_set, digest_scheme, authorized)
         }
+        ExecuteMsg::RegisterPublicKey {
+            public_key,
+            proof_of_possession,
+        } => execute::register_pub_key(deps, info, public_key, proof_of_possession),
         ExecuteMsg::AuthorizeCaller { contract_address } => {
             execute::require_governance(&deps, info.sender)?;
+            let contract_address = deps.api.addr_validate(contract_address.as_str())?;
             execute::authorize_caller(deps, contract_address)
         }
         ExecuteMsg::UnauthorizeCaller { contract_address } => {
             execute::require_governance(&deps, info.sender)?;
             execute::unauthorize_caller(deps, contract_address)
         }
+        ExecuteMsg::UpdateGracePeriod { grace_period } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::update_grace_period(deps, grace_period)
+        }
+        ExecuteMsg::UpdateRetentionPolicy { retention_policy } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::update_retention_policy(deps, retention_policy)
+        }
+        ExecuteMsg::PruneSessions { limit } => execute::prune_sessions(deps, env, limit),
+        ExecuteMsg::DisableKey { worker_set_id } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::disable_key(deps, worker_set_id)
+        }
+        ExecuteMsg::EnableKey { worker_set_id } => {
+            execute::require_governance(&deps, info.sender)?;
+            execute::enable_key(deps, worker_set_id)
+        }
     }
     .map_err(axelar_wasm_std::ContractError::from)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    _deps: DepsMut,
+    _env: Env,
+    reply: Reply,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    match (reply.id, reply.result) {
+        // a failing callback must not prevent the session from completing, so the error is only reported
+        (SIGNING_CALLBACK_REPLY_ID, SubMsgResult::Err(error)) => {
+            Ok(Response::new().add_event(Event::SigningCallbackFailed { error }.into()))
+        }
+        (SIGNING_CALLBACK_REPLY_ID, SubMsgResult::Ok(_)) => {
+            unreachable!("violated invariant: replied successful submessage with ReplyOn::Error")
+        }
+        _ => unreachable!("unknown reply ID"),
+    }
+}
+
 pub mod execute {
-    use connection_router::state::ChainName;
-    use cosmwasm_std::WasmMsg;
+    use std::collections::HashMap;
 
-    use crate::signing::validate_session_signature;
-    use crate::state::{load_session_signatures, save_signature};
+    use connection_router::state::ChainName;
+    use cosmwasm_std::{wasm_execute, Order, Storage, SubMsg, WasmMsg};
+    use cw_storage_plus::Bound;
+
+    use crate::bls::bls12_381_verify_possession;
+    use crate::signing::{validate_session_signature, PendingConfirmation};
+    use crate::state::{
+        add_to_aggregate_signature, archive_session, ensure_key_enabled, load_digest_scheme,
+        load_session_signatures, pending_session_kind, save_signature, update_key_usage,
+        update_signer_liveness, KeyUsage, SignerLiveness, COMPLETED_SESSIONS, DISABLED_KEYS,
+        KEY_DIGEST_SCHEMES, LATE_SIGNATURES, PENDING_SESSION_IDS, SIGNED_DIGEST_SESSION_IDS,
+    };
+    use crate::types::{DigestScheme, RetentionPolicy};
     use crate::worker_set::WorkerSet;
     use crate::{
         key::{KeyTyped, PublicKey, Signature},
@@ -99,13 +232,41 @@ pub mod execute {
 
     use super::*;
 
+    /// Parameters of a request to sign a message, shared by regular and high-value sessions
+    pub struct SigningRequest {
+        pub worker_set_id: String,
+        pub msg: MsgToSign,
+        pub chain_name: ChainName,
+        // contract to notify once the session is completed
+        pub callback: Option<Addr>,
+        pub threshold: Option<Uint256>,
+    }
+
     pub fn start_signing_session(
         deps: DepsMut,
-        worker_set_id: String,
-        msg: MsgToSign,
-        chain_name: ChainName,
+        env: Env,
+        request: SigningRequest,
     ) -> Result<Response, ContractError> {
+        let SigningRequest {
+            worker_set_id,
+            msg,
+            chain_name,
+            callback,
+            threshold,
+        } = request;
         let worker_set = get_worker_set(deps.storage, &worker_set_id)?;
+        ensure_key_enabled(deps.storage, &worker_set_id)?;
+        validate_session_threshold(&worker_set, threshold)?;
+
+        let kind = pending_session_kind(false, callback.as_ref());
+        if let Some(response) = pending_session_response(
+            deps.storage,
+            &worker_set,
+            (&worker_set_id, msg.as_ref(), &kind),
+            threshold,
+        )? {
+            return Ok(response);
+        }
 
         let session_id = SIGNING_SESSION_COUNTER.update(
             deps.storage,
@@ -115,16 +276,203 @@ pub mod execute {
             },
         )?;
 
-        let signing_session = SigningSession::new(session_id, worker_set_id.clone(), msg.clone());
-
-        SIGNING_SESSIONS.save(deps.storage, session_id.into(), &signing_session)?;
+        let mut signing_session =
+            SigningSession::new(session_id, worker_set_id.clone(), msg.clone())
+                .with_callback(callback.clone())
+                .with_threshold(threshold)
+                .with_signing_started_at(env.block.height);
 
-        let event = Event::SigningStarted {
+        let digest_scheme = load_digest_scheme(deps.storage, &worker_set_id)?;
+        let digest = digest_scheme.digest(&msg);
+        let (previous_session_id, signatures) = reuse_signatures(
+            deps.storage,
             session_id,
+            &worker_set_id,
+            &worker_set,
+            &digest,
+        )?
+        .unzip();
+        let signatures = signatures.unwrap_or_default();
+        SIGNED_DIGEST_SESSION_IDS.save(
+            deps.storage,
+            (&worker_set_id, digest.as_ref()),
+            &session_id,
+        )?;
+
+        signing_session.recalculate_session_state(&signatures, &worker_set, env.block.height);
+        signing_sessions().save(deps.storage, session_id.into(), &signing_session)?;
+
+        record_session_started(deps.storage, &worker_set_id)?;
+        record_signers_eligible(deps.storage, &worker_set, &signatures, env.block.height)?;
+
+        let mut response = Response::new().set_data(to_binary(&session_id)?).add_event(
+            Event::SigningStarted {
+                session_id,
+                worker_set_id: worker_set_id.clone(),
+                pub_keys: worker_set.get_pub_keys(),
+                msg: msg.clone(),
+                chain_name,
+                digest_scheme,
+            }
+            .into(),
+        );
+
+        if let Some(previous_session_id) = previous_session_id {
+            let mut participants: Vec<String> = signatures.keys().cloned().collect();
+            participants.sort();
+
+            response = response.add_event(
+                Event::SignaturesReused {
+                    session_id,
+                    previous_session_id,
+                    participants,
+                }
+                .into(),
+            );
+        }
+
+        match signing_session.state {
+            MultisigState::Pending => {
+                PENDING_SESSION_IDS.save(
+                    deps.storage,
+                    (&worker_set_id, msg.as_ref(), &kind),
+                    &session_id,
+                )?;
+            }
+            // the reused signatures already meet the threshold, so the session completes right away
+            MultisigState::Completed { completed_at } => {
+                COMPLETED_SESSIONS.save(deps.storage, (completed_at, session_id.u64()), &())?;
+                update_key_usage(deps.storage, &worker_set_id, |usage| KeyUsage {
+                    sessions_completed: usage.sessions_completed + 1,
+                    ..usage
+                })?;
+
+                response = response.add_event(
+                    Event::SigningCompleted {
+                        session_id,
+                        completed_at,
+                        signatures,
+                    }
+                    .into(),
+                );
+                if let Some(callback) = callback {
+                    response = response.add_submessage(SubMsg::reply_on_error(
+                        wasm_execute(
+                            callback,
+                            &CallbackMsg::SigningCompleted { session_id },
+                            vec![],
+                        )?,
+                        SIGNING_CALLBACK_REPLY_ID,
+                    ));
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    type ReusedSignatures = (Uint64, HashMap<String, Signature>);
+
+    // Copies the signatures of the latest session in which the worker set signed the same digest into the new session.
+    // Returns the id of that session and the copied signatures, or None if there is no such session
+    fn reuse_signatures(
+        store: &mut dyn Storage,
+        session_id: Uint64,
+        worker_set_id: &str,
+        worker_set: &WorkerSet,
+        digest: &MsgToSign,
+    ) -> Result<Option<ReusedSignatures>, ContractError> {
+        let previous_session_id =
+            match SIGNED_DIGEST_SESSION_IDS.may_load(store, (worker_set_id, digest.as_ref()))? {
+                Some(previous_session_id) => previous_session_id,
+                None => return Ok(None),
+            };
+
+        // signatures of pruned sessions are gone
+        let signatures = load_session_signatures(store, previous_session_id.u64())?;
+        if signatures.is_empty() {
+            return Ok(None);
+        }
+
+        for (signer, signature) in &signatures {
+            let signer = Addr::unchecked(signer);
+            save_signature(store, session_id, signature.clone(), &signer)?;
+            if let Signature::Bls12_381(sig) = signature {
+                add_to_aggregate_signature(
+                    store,
+                    session_id.u64(),
+                    worker_set,
+                    &signer,
+                    sig.as_slice(),
+                )?;
+            }
+        }
+
+        Ok(Some((previous_session_id, signatures)))
+    }
+
+    pub fn start_high_value_signing_session(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        request: SigningRequest,
+    ) -> Result<Response, ContractError> {
+        let SigningRequest {
             worker_set_id,
-            pub_keys: worker_set.get_pub_keys(),
             msg,
             chain_name,
+            callback,
+            threshold,
+        } = request;
+        let config = CONFIG.load(deps.storage)?;
+        let worker_set = get_worker_set(deps.storage, &worker_set_id)?;
+        ensure_key_enabled(deps.storage, &worker_set_id)?;
+        validate_session_threshold(&worker_set, threshold)?;
+
+        let kind = pending_session_kind(true, callback.as_ref());
+        if let Some(response) = pending_session_response(
+            deps.storage,
+            &worker_set,
+            (&worker_set_id, msg.as_ref(), &kind),
+            threshold,
+        )? {
+            return Ok(response);
+        }
+
+        let session_id = SIGNING_SESSION_COUNTER.update(
+            deps.storage,
+            |mut counter| -> Result<Uint64, ContractError> {
+                counter += Uint64::one();
+                Ok(counter)
+            },
+        )?;
+
+        PENDING_SESSION_IDS.save(
+            deps.storage,
+            (&worker_set_id, msg.as_ref(), &kind),
+            &session_id,
+        )?;
+        record_session_started(deps.storage, &worker_set_id)?;
+
+        let confirmable_at = env.block.height + config.confirmation_delay;
+        let signing_session = SigningSession::new_high_value(
+            session_id,
+            worker_set_id,
+            msg,
+            PendingConfirmation {
+                initiator: info.sender,
+                confirmable_at,
+                chain_name,
+            },
+        )
+        .with_callback(callback)
+        .with_threshold(threshold);
+
+        signing_sessions().save(deps.storage, session_id.into(), &signing_session)?;
+
+        let event = Event::SigningAwaitingConfirmation {
+            session_id,
+            confirmable_at,
         };
 
         Ok(Response::new()
@@ -132,6 +480,88 @@ pub mod execute {
             .add_event(event.into()))
     }
 
+    pub fn confirm_signing_session(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        session_id: Uint64,
+    ) -> Result<Response, ContractError> {
+        let mut session = signing_sessions()
+            .load(deps.storage, session_id.into())
+            .map_err(|_| ContractError::SigningSessionNotFound { session_id })?;
+
+        let pending_confirmation = session
+            .pending_confirmation
+            .take()
+            .ok_or(ContractError::SigningSessionAlreadyConfirmed { session_id })?;
+
+        if pending_confirmation.initiator != info.sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        ensure_key_enabled(deps.storage, &session.worker_set_id)?;
+
+        if env.block.height < pending_confirmation.confirmable_at {
+            return Err(ContractError::ConfirmationTooEarly {
+                session_id,
+                confirmable_at: pending_confirmation.confirmable_at,
+            });
+        }
+
+        session.signing_started_at = Some(env.block.height);
+        signing_sessions().save(deps.storage, session_id.into(), &session)?;
+
+        let worker_set = get_worker_set(deps.storage, &session.worker_set_id)?;
+        record_signers_eligible(deps.storage, &worker_set, &HashMap::new(), env.block.height)?;
+        let digest_scheme = load_digest_scheme(deps.storage, &session.worker_set_id)?;
+        let event = Event::SigningStarted {
+            session_id,
+            worker_set_id: session.worker_set_id,
+            pub_keys: worker_set.get_pub_keys(),
+            msg: session.msg,
+            chain_name: pending_confirmation.chain_name,
+            digest_scheme,
+        };
+
+        Ok(Response::new().add_event(event.into()))
+    }
+
+    pub fn cancel_signing_session(
+        deps: DepsMut,
+        info: MessageInfo,
+        session_id: Uint64,
+    ) -> Result<Response, ContractError> {
+        let session = signing_sessions()
+            .load(deps.storage, session_id.into())
+            .map_err(|_| ContractError::SigningSessionNotFound { session_id })?;
+
+        let pending_confirmation = session
+            .pending_confirmation
+            .as_ref()
+            .ok_or(ContractError::SigningSessionAlreadyConfirmed { session_id })?;
+
+        let config = CONFIG.load(deps.storage)?;
+        if pending_confirmation.initiator != info.sender && config.governance != info.sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        signing_sessions().remove(deps.storage, session_id.into())?;
+
+        // requests for the same message start a new session instead of joining the cancelled one
+        let kind = pending_session_kind(session.high_value, session.callback.as_ref());
+        let pending_key = (session.worker_set_id.as_str(), session.msg.as_ref(), &*kind);
+        if PENDING_SESSION_IDS.may_load(deps.storage, pending_key)? == Some(session_id) {
+            PENDING_SESSION_IDS.remove(deps.storage, pending_key);
+        }
+
+        let event = Event::SigningCancelled {
+            session_id,
+            cancelled_by: info.sender,
+        };
+
+        Ok(Response::new().add_event(event.into()))
+    }
+
     pub fn submit_signature(
         deps: DepsMut,
         env: Env,
@@ -140,15 +570,18 @@ pub mod execute {
         signature: HexBinary,
     ) -> Result<Response, ContractError> {
         let config = CONFIG.load(deps.storage)?;
-        let mut session = SIGNING_SESSIONS
+        let mut session = signing_sessions()
             .load(deps.storage, session_id.into())
             .map_err(|_| ContractError::SigningSessionNotFound { session_id })?;
+        ensure_key_enabled(deps.storage, &session.worker_set_id)?;
         let worker_set = WORKER_SETS.load(deps.storage, &session.worker_set_id)?;
 
+        // the error is the only record of a rejected submission, events of failed transactions are discarded
         let pub_key = match worker_set.signers.get(&info.sender.to_string()) {
             Some(signer) => Ok(&signer.pub_key),
             None => Err(ContractError::NotAParticipant {
                 session_id,
+                worker_set_id: session.worker_set_id.clone(),
                 signer: info.sender.to_string(),
             }),
         }?;
@@ -156,39 +589,187 @@ pub mod execute {
         let signature: Signature = (pub_key.key_type(), signature).try_into()?;
 
         validate_session_signature(
+            deps.as_ref(),
             &session,
+            &worker_set,
             &info.sender,
             &signature,
-            pub_key,
             config.grace_period,
             env.block.height,
         )?;
         let signature = save_signature(deps.storage, session_id, signature, &info.sender)?;
+        // signatures after completion only count as participation, the completed multisig must not change
+        let late = matches!(session.state, MultisigState::Completed { .. });
+        if late {
+            LATE_SIGNATURES.save(deps.storage, (session_id.u64(), info.sender.as_str()), &())?;
+        }
+        // sessions started before signing started being recorded never counted the signer as eligible
+        if let Some(signing_started_at) = session.signing_started_at {
+            update_signer_liveness(
+                deps.storage,
+                info.sender.as_str(),
+                env.block.height,
+                |liveness| SignerLiveness {
+                    sessions_signed: liveness.sessions_signed + 1,
+                    total_blocks_to_sign: liveness.total_blocks_to_sign
+                        + env.block.height.saturating_sub(signing_started_at),
+                    ..liveness
+                },
+            )?;
+        }
+        if let (Signature::Bls12_381(sig), false) = (&signature, late) {
+            add_to_aggregate_signature(
+                deps.storage,
+                session_id.u64(),
+                &worker_set,
+                &info.sender,
+                sig.as_slice(),
+            )?;
+        }
 
         let signatures = load_session_signatures(deps.storage, session_id.u64())?;
 
         let old_state = session.state.clone();
 
         session.recalculate_session_state(&signatures, &worker_set, env.block.height);
-        SIGNING_SESSIONS.save(deps.storage, session.id.u64(), &session)?;
+        signing_sessions().save(deps.storage, session.id.u64(), &session)?;
 
         let state_changed = old_state != session.state;
 
+        if let MultisigState::Completed { completed_at } = session.state {
+            if state_changed {
+                COMPLETED_SESSIONS.save(deps.storage, (completed_at, session.id.u64()), &())?;
+                // a session with a higher threshold for the same message may have taken over the pending entry
+                let kind = pending_session_kind(session.high_value, session.callback.as_ref());
+                let pending_key = (session.worker_set_id.as_str(), session.msg.as_ref(), &*kind);
+                if PENDING_SESSION_IDS.may_load(deps.storage, pending_key)? == Some(session.id) {
+                    PENDING_SESSION_IDS.remove(deps.storage, pending_key);
+                }
+            }
+        }
+
+        update_key_usage(deps.storage, &session.worker_set_id, |usage| KeyUsage {
+            signatures_collected: usage.signatures_collected + 1,
+            sessions_completed: usage.sessions_completed + u64::from(state_changed),
+            ..usage
+        })?;
+
         signing_response(
-            session_id,
-            session.state,
+            &session,
             state_changed,
             info.sender,
             signature,
+            late,
+            signatures,
             config.rewards_contract.into_string(),
         )
     }
 
+    fn validate_session_threshold(
+        worker_set: &WorkerSet,
+        threshold: Option<Uint256>,
+    ) -> Result<(), ContractError> {
+        let total_weight: Uint256 = worker_set
+            .signers
+            .values()
+            .map(|signer| signer.weight)
+            .sum();
+
+        match threshold {
+            Some(threshold) if threshold < worker_set.threshold || threshold > total_weight => {
+                Err(ContractError::InvalidSessionThreshold {
+                    threshold,
+                    key_threshold: worker_set.threshold,
+                    total_weight,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Duplicate requests to sign a message with the same key are answered with the id of the pending session,
+    /// so signers don't sign the same message twice. The duplicate's callback flag is ignored.
+    /// A request with a higher threshold than the pending session's starts its own session instead,
+    /// so neither the requested threshold is weakened nor the other caller's threshold raised
+    fn pending_session_response(
+        store: &mut dyn Storage,
+        worker_set: &WorkerSet,
+        pending_key: (&str, &[u8], &str),
+        threshold: Option<Uint256>,
+    ) -> Result<Option<Response>, ContractError> {
+        let session_id = match PENDING_SESSION_IDS.may_load(store, pending_key)? {
+            Some(session_id) => session_id,
+            None => return Ok(None),
+        };
+
+        if let Some(threshold) = threshold {
+            let session = signing_sessions().load(store, session_id.u64())?;
+            if threshold > session.threshold(worker_set) {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(Response::new().set_data(to_binary(&session_id)?)))
+    }
+
+    fn record_session_started(
+        store: &mut dyn Storage,
+        worker_set_id: &str,
+    ) -> Result<(), ContractError> {
+        update_key_usage(store, worker_set_id, |usage| KeyUsage {
+            sessions_started: usage.sessions_started + 1,
+            ..usage
+        })?;
+
+        Ok(())
+    }
+
+    // signers whose signature was reused don't need to sign the session, so they are not counted as eligible
+    fn record_signers_eligible(
+        store: &mut dyn Storage,
+        worker_set: &WorkerSet,
+        reused_signatures: &HashMap<String, Signature>,
+        block_height: u64,
+    ) -> Result<(), ContractError> {
+        for signer in worker_set
+            .signers
+            .keys()
+            .filter(|signer| !reused_signatures.contains_key(*signer))
+        {
+            update_signer_liveness(store, signer, block_height, |liveness| SignerLiveness {
+                sessions_eligible: liveness.sessions_eligible + 1,
+                ..liveness
+            })?;
+        }
+
+        Ok(())
+    }
+
     pub fn register_worker_set(
         deps: DepsMut,
         worker_set: WorkerSet,
+        digest_scheme: Option<DigestScheme>,
+        authorized: bool,
     ) -> Result<Response, ContractError> {
         let worker_set_id = worker_set.id();
+        // only the first registration by an authorized caller fixes the scheme, so nobody else can register the key first
+        // and lock it to a scheme its prover doesn't use
+        if authorized {
+            match KEY_DIGEST_SCHEMES.may_load(deps.storage, &worker_set_id)? {
+                // signers of a registered key may already be signing sessions with its scheme, so the scheme can't change anymore
+                Some(stored)
+                    if digest_scheme.is_some_and(|digest_scheme| digest_scheme != stored) =>
+                {
+                    return Err(ContractError::DigestSchemeImmutable { worker_set_id });
+                }
+                Some(_) => (),
+                None => KEY_DIGEST_SCHEMES.save(
+                    deps.storage,
+                    &worker_set_id,
+                    &digest_scheme.unwrap_or_default(),
+                )?,
+            }
+        }
         WORKER_SETS.save(deps.storage, &worker_set_id, &worker_set)?;
 
         Ok(Response::default())
@@ -198,7 +779,17 @@ pub mod execute {
         deps: DepsMut,
         info: MessageInfo,
         public_key: PublicKey,
+        proof_of_possession: Option<HexBinary>,
     ) -> Result<Response, ContractError> {
+        if let PublicKey::Bls12_381(pub_key) = &public_key {
+            let proof = proof_of_possession.ok_or(ContractError::MissingProofOfPossession)?;
+            if !bls12_381_verify_possession(pub_key, &proof)? {
+                return Err(ContractError::InvalidProofOfPossession {
+                    pub_key: pub_key.clone(),
+                });
+            }
+        }
+
         PUB_KEYS.save(
             deps.storage,
             (info.sender.clone(), public_key.key_type()),
@@ -241,6 +832,91 @@ pub mod execute {
         Ok(Response::new().add_event(Event::CallerUnauthorized { contract_address }.into()))
     }
 
+    pub fn disable_key(deps: DepsMut, worker_set_id: String) -> Result<Response, ContractError> {
+        get_worker_set(deps.storage, &worker_set_id)?;
+        DISABLED_KEYS.save(deps.storage, &worker_set_id, &())?;
+
+        Ok(Response::new().add_event(Event::KeyDisabled { worker_set_id }.into()))
+    }
+
+    pub fn enable_key(deps: DepsMut, worker_set_id: String) -> Result<Response, ContractError> {
+        DISABLED_KEYS.remove(deps.storage, &worker_set_id);
+
+        Ok(Response::new().add_event(Event::KeyEnabled { worker_set_id }.into()))
+    }
+
+    pub fn update_grace_period(
+        deps: DepsMut,
+        grace_period: u64,
+    ) -> Result<Response, ContractError> {
+        CONFIG.update(deps.storage, |config| -> Result<Config, ContractError> {
+            Ok(Config {
+                grace_period,
+                ..config
+            })
+        })?;
+
+        Ok(Response::default())
+    }
+
+    pub fn update_retention_policy(
+        deps: DepsMut,
+        retention_policy: Option<RetentionPolicy>,
+    ) -> Result<Response, ContractError> {
+        CONFIG.update(deps.storage, |config| -> Result<Config, ContractError> {
+            Ok(Config {
+                retention_policy,
+                ..config
+            })
+        })?;
+
+        Ok(Response::default())
+    }
+
+    pub fn prune_sessions(deps: DepsMut, env: Env, limit: u32) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let retention_policy = config
+            .retention_policy
+            .ok_or(ContractError::NoRetentionPolicy)?;
+        let latest_session_id = SIGNING_SESSION_COUNTER.load(deps.storage)?.u64();
+
+        // sessions can still receive signatures until the grace period is over, so they must be kept at least until then
+        let grace_period_over = Bound::exclusive((
+            env.block.height.saturating_sub(config.grace_period),
+            u64::MIN,
+        ));
+
+        let prunable = COMPLETED_SESSIONS
+            .keys(
+                deps.storage,
+                None,
+                Some(grace_period_over),
+                Order::Ascending,
+            )
+            .filter(|key| {
+                key.as_ref().map_or(true, |(completed_at, session_id)| {
+                    retention_policy.allows_pruning(
+                        *session_id,
+                        *completed_at,
+                        latest_session_id,
+                        env.block.height,
+                    )
+                })
+            })
+            .take(limit as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let session_ids = prunable
+            .into_iter()
+            .map(|(completed_at, session_id)| {
+                archive_session(deps.storage, session_id, completed_at)
+                    .map(|archived| archived.session_id)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new().add_event(Event::SessionsPruned { session_ids }.into()))
+    }
+
     pub fn require_governance(deps: &DepsMut, sender: Addr) -> Result<(), ContractError> {
         let config = CONFIG.load(deps.storage)?;
         if config.governance != sender {
@@ -250,21 +926,24 @@ pub mod execute {
     }
 
     fn signing_response(
-        session_id: Uint64,
-        session_state: MultisigState,
+        session: &SigningSession,
         state_changed: bool,
         signer: Addr,
         signature: Signature,
+        late: bool,
+        signatures: HashMap<String, Signature>,
         rewards_contract: String,
     ) -> Result<Response, ContractError> {
+        let session_id = session.id;
         let rewards_msg = WasmMsg::Execute {
             contract_addr: rewards_contract,
             msg: to_binary(&rewards::msg::ExecuteMsg::RecordParticipation {
-                event_id: session_id
-                    .to_string()
-                    .try_into()
-                    .expect("couldn't convert session_id to nonempty string"),
+                event_id: rewards::msg::signing_event_id(
+                    &session.worker_set_id,
+                    session.msg.as_ref(),
+                ),
                 worker_address: signer.to_string(),
+                late,
             })?,
             funds: vec![],
         };
@@ -273,22 +952,35 @@ pub mod execute {
             session_id,
             participant: signer,
             signature,
+            late,
         };
 
         let mut response = Response::new()
             .add_message(rewards_msg)
             .add_event(event.into());
 
-        if let MultisigState::Completed { completed_at } = session_state {
+        if let MultisigState::Completed { completed_at } = session.state {
             if state_changed {
                 // only send event if state changed
                 response = response.add_event(
                     Event::SigningCompleted {
                         session_id,
                         completed_at,
+                        signatures,
                     }
                     .into(),
-                )
+                );
+
+                if let Some(callback) = &session.callback {
+                    response = response.add_submessage(SubMsg::reply_on_error(
+                        wasm_execute(
+                            callback,
+                            &CallbackMsg::SigningCompleted { session_id },
+                            vec![],
+                        )?,
+                        SIGNING_CALLBACK_REPLY_ID,
+                    ));
+                }
             }
         }
 
@@ -297,7 +989,7 @@ pub mod execute {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetMultisig { session_id } => to_binary(&query::get_multisig(deps, session_id)?),
         QueryMsg::GetWorkerSet { worker_set_id } => {
@@ -311,33 +1003,106 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             deps.api.addr_validate(&worker_address)?,
             key_type,
         )?),
-    }
-}
-
-pub mod query {
+        QueryMsg::GetKeyStats { worker_set_id } => {
+            to_binary(&query::get_key_stats(deps, worker_set_id)?)
+        }
+        QueryMsg::GetSignerLiveness { signer } => to_binary(&query::get_signer_liveness(
+            deps,
+            deps.api.addr_validate(&signer)?,
+            env.block.height,
+        )?),
+        QueryMsg::GetArchivedSession { session_id } => {
+            to_binary(&query::get_archived_session(deps, session_id)?)
+        }
+        QueryMsg::GetAggregateSignature { session_id } => {
+            to_binary(&query::get_aggregate_signature(deps, session_id)?)
+        }
+        QueryMsg::PendingSessions {
+            participant,
+            start_after,
+            limit,
+        } => to_binary(&query::pending_sessions(
+            deps,
+            deps.api.addr_validate(&participant)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListSigningSessions {
+            start_after,
+            limit,
+            status_filter,
+        } => to_binary(&query::list_signing_sessions(
+            deps,
+            start_after,
+            limit,
+            status_filter,
+        )?),
+        QueryMsg::ListSessionSignatures {
+            session_id,
+            start_after,
+            limit,
+        } => to_binary(&query::list_session_signatures(
+            deps,
+            session_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::VerifySessionSignatures { session_id } => {
+            to_binary(&query::verify_session_signatures(deps, session_id)?)
+        }
+    }
+}
+
+pub mod query {
+    use cosmwasm_std::{Decimal, Order};
+    use cw_storage_plus::Bound;
+
     use crate::{
         key::{KeyType, PublicKey},
-        state::{load_session_signatures, PUB_KEYS},
+        msg::{
+            KeyStats, PendingSession, SessionSignature, SessionStatus, SessionSummary,
+            SignatureVerification, SignerLivenessStats,
+        },
+        signing::{AggregateSignature, ArchivedSession, SigningSession},
+        state::{
+            load_digest_scheme, load_session_signatures, load_signer_liveness, status_index_key,
+            AGGREGATE_SIGNATURES, KEY_USAGE, LATE_SIGNATURES, LIVENESS_WINDOW_BLOCKS, PUB_KEYS,
+            SESSION_ARCHIVE, SIGNATURES,
+        },
         worker_set::WorkerSet,
     };
 
     use super::*;
 
+    pub const MAX_SESSIONS_LIMIT: u32 = 100;
+    pub const MAX_SIGNATURES_LIMIT: u32 = 100;
+
     pub fn get_multisig(deps: Deps, session_id: Uint64) -> StdResult<Multisig> {
-        let session = SIGNING_SESSIONS.load(deps.storage, session_id.into())?;
+        let session = signing_sessions().load(deps.storage, session_id.into())?;
 
         let worker_set = WORKER_SETS.load(deps.storage, &session.worker_set_id)?;
         let signatures = load_session_signatures(deps.storage, session.id.u64())?;
+        let session_threshold = session.threshold(&worker_set);
 
+        // late signatures are left out, so the multisig doesn't change after completion
         let signers_with_sigs = worker_set
             .signers
             .into_iter()
-            .map(|(address, signer)| (signer, signatures.get(&address).cloned()))
+            .map(|(address, signer)| {
+                let signature = signatures
+                    .get(&address)
+                    .filter(|_| {
+                        !LATE_SIGNATURES.has(deps.storage, (session.id.u64(), address.as_str()))
+                    })
+                    .cloned();
+                (signer, signature)
+            })
             .collect::<Vec<_>>();
 
         Ok(Multisig {
             state: session.state,
             quorum: worker_set.threshold,
+            session_threshold,
             signers: signers_with_sigs,
         })
     }
@@ -350,19 +1115,209 @@ pub mod query {
         let raw = PUB_KEYS.load(deps.storage, (worker, key_type))?;
         Ok(PublicKey::try_from((key_type, raw)).expect("could not decode pub key"))
     }
+
+    pub fn get_key_stats(deps: Deps, worker_set_id: String) -> StdResult<KeyStats> {
+        let usage = KEY_USAGE
+            .may_load(deps.storage, &worker_set_id)?
+            .unwrap_or_default();
+
+        Ok(KeyStats {
+            sessions_started: usage.sessions_started,
+            sessions_completed: usage.sessions_completed,
+            signatures_collected: usage.signatures_collected,
+            average_signatures_per_session: Decimal::checked_from_ratio(
+                usage.signatures_collected,
+                usage.sessions_started,
+            )
+            .unwrap_or_default(),
+        })
+    }
+
+    pub fn get_signer_liveness(
+        deps: Deps,
+        signer: Addr,
+        block_height: u64,
+    ) -> StdResult<SignerLivenessStats> {
+        let liveness = load_signer_liveness(deps.storage, signer.as_str(), block_height)?;
+
+        Ok(SignerLivenessStats {
+            sessions_eligible: liveness.sessions_eligible,
+            sessions_signed: liveness.sessions_signed,
+            average_blocks_to_sign: Decimal::checked_from_ratio(
+                liveness.total_blocks_to_sign,
+                liveness.sessions_signed,
+            )
+            .unwrap_or_default(),
+            since_block_height: liveness.window * LIVENESS_WINDOW_BLOCKS,
+        })
+    }
+
+    pub fn get_archived_session(deps: Deps, session_id: Uint64) -> StdResult<ArchivedSession> {
+        SESSION_ARCHIVE.load(deps.storage, session_id.u64())
+    }
+
+    pub fn get_aggregate_signature(
+        deps: Deps,
+        session_id: Uint64,
+    ) -> StdResult<Option<AggregateSignature>> {
+        AGGREGATE_SIGNATURES.may_load(deps.storage, session_id.u64())
+    }
+
+    pub fn pending_sessions(
+        deps: Deps,
+        participant: Addr,
+        start_after: Option<Uint64>,
+        limit: u32,
+    ) -> StdResult<Vec<PendingSession>> {
+        let start = start_after.map(|session_id| Bound::exclusive(session_id.u64()));
+        let mut pending_sessions = vec![];
+
+        for session in signing_sessions()
+            .idx
+            .status
+            .prefix(status_index_key(&SessionStatus::Pending))
+            .range(deps.storage, start, None, Order::Ascending)
+        {
+            if pending_sessions.len() >= limit.min(MAX_SESSIONS_LIMIT) as usize {
+                break;
+            }
+
+            let (_, session) = session?;
+            if awaits_signature(deps, &session, &participant)? {
+                pending_sessions.push(PendingSession {
+                    session_id: session.id,
+                    worker_set_id: session.worker_set_id,
+                    msg: session.msg.into(),
+                });
+            }
+        }
+
+        Ok(pending_sessions)
+    }
+
+    fn awaits_signature(
+        deps: Deps,
+        session: &SigningSession,
+        participant: &Addr,
+    ) -> StdResult<bool> {
+        if session.state != MultisigState::Pending || session.pending_confirmation.is_some() {
+            return Ok(false);
+        }
+
+        let worker_set = WORKER_SETS.load(deps.storage, &session.worker_set_id)?;
+        if !worker_set.signers.contains_key(participant.as_str()) {
+            return Ok(false);
+        }
+
+        Ok(!SIGNATURES.has(deps.storage, (session.id.u64(), participant.as_str())))
+    }
+
+    pub fn list_signing_sessions(
+        deps: Deps,
+        start_after: Option<Uint64>,
+        limit: u32,
+        status_filter: Option<SessionStatus>,
+    ) -> StdResult<Vec<SessionSummary>> {
+        let start = start_after.map(|session_id| Bound::exclusive(session_id.u64()));
+        let sessions = match status_filter {
+            Some(status) => signing_sessions()
+                .idx
+                .status
+                .prefix(status_index_key(&status))
+                .range(deps.storage, start, None, Order::Ascending),
+            None => signing_sessions().range(deps.storage, start, None, Order::Ascending),
+        };
+
+        sessions
+            .take(limit.min(MAX_SESSIONS_LIMIT) as usize)
+            .map(|session| session.map(|(_, session)| session_summary(session)))
+            .collect()
+    }
+
+    fn session_summary(session: SigningSession) -> SessionSummary {
+        SessionSummary {
+            status: session.status(),
+            session_id: session.id,
+            worker_set_id: session.worker_set_id,
+            msg: session.msg.into(),
+            state: session.state,
+        }
+    }
+
+    pub fn list_session_signatures(
+        deps: Deps,
+        session_id: Uint64,
+        start_after: Option<String>,
+        limit: u32,
+    ) -> StdResult<Vec<SessionSignature>> {
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        SIGNATURES
+            .prefix(session_id.u64())
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit.min(MAX_SIGNATURES_LIMIT) as usize)
+            .map(|signature| {
+                signature.map(|(signer, signature)| SessionSignature {
+                    late: LATE_SIGNATURES.has(deps.storage, (session_id.u64(), signer.as_str())),
+                    signer: Addr::unchecked(signer),
+                    signature,
+                })
+            })
+            .collect()
+    }
+
+    pub fn verify_session_signatures(
+        deps: Deps,
+        session_id: Uint64,
+    ) -> StdResult<Vec<SignatureVerification>> {
+        let session = signing_sessions().load(deps.storage, session_id.u64())?;
+        let worker_set = WORKER_SETS.load(deps.storage, &session.worker_set_id)?;
+        let digest = load_digest_scheme(deps.storage, &session.worker_set_id)?.digest(&session.msg);
+
+        SIGNATURES
+            .prefix(session_id.u64())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|signature| {
+                let (signer, signature) = signature?;
+                // a signature that cannot be verified at all is as bad as one that doesn't match
+                let valid = worker_set
+                    .signers
+                    .get(&signer)
+                    .map(|participant| {
+                        signature
+                            .verify(deps.api, &digest, &participant.pub_key)
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(false);
+
+                Ok(SignatureVerification {
+                    signer: Addr::unchecked(signer),
+                    valid,
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::vec;
+    use std::{collections::HashMap, vec};
 
     use crate::{
+        bls::{bls12_381_verify, decode_pub_key},
         key::{KeyType, PublicKey, Signature},
-        msg::Multisig,
-        state::load_session_signatures,
+        msg::{
+            KeyStats, Multisig, PendingSession, SessionSignature, SessionStatus, SessionSummary,
+            SignatureVerification, SignerLivenessStats,
+        },
+        signing::{AggregateSignature, ArchivedSession, SigningSession},
+        state::{
+            load_digest_scheme, load_session_signatures, KEY_DIGEST_SCHEMES, LIVENESS_WINDOWS,
+            LIVENESS_WINDOW_BLOCKS, SIGNATURES, SIGNER_LIVENESS,
+        },
+        test::common::{bls12_381_test_data, ecdsa_test_data, ed25519_test_data},
         test::common::{build_worker_set, TestSigner},
-        test::common::{ecdsa_test_data, ed25519_test_data},
-        types::MultisigState,
+        types::{DigestScheme, MultisigState, RetentionPolicy},
         worker_set::WorkerSet,
     };
 
@@ -370,14 +1325,17 @@ mod tests {
     use cosmwasm_std::{
         from_binary,
         testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-        Addr, Empty, OwnedDeps, Uint256, WasmMsg,
+        Addr, CosmosMsg, Decimal, Empty, OwnedDeps, SubMsg, Uint256, WasmMsg,
     };
 
+    use axelar_wasm_std::nonempty;
+    use cw_storage_plus::Map;
     use serde_json::from_str;
 
     const INSTANTIATOR: &str = "inst";
     const PROVER: &str = "prover";
     const REWARDS_CONTRACT: &str = "rewards";
+    const CONFIRMATION_DELAY: u64 = 10;
 
     fn do_instantiate(deps: DepsMut) -> Result<Response, axelar_wasm_std::ContractError> {
         let info = mock_info(INSTANTIATOR, &[]);
@@ -387,6 +1345,7 @@ mod tests {
             governance_address: "governance".parse().unwrap(),
             rewards_address: REWARDS_CONTRACT.to_string(),
             grace_period: 2,
+            confirmation_delay: CONFIRMATION_DELAY,
         };
 
         instantiate(deps, env, info, msg)
@@ -402,11 +1361,13 @@ mod tests {
         let signers = match key_type {
             KeyType::Ecdsa => ecdsa_test_data::signers(),
             KeyType::Ed25519 => ed25519_test_data::signers(),
+            KeyType::Bls12_381 => bls12_381_test_data::signers(),
         };
 
         let worker_set = build_worker_set(key_type, &signers);
         let msg = ExecuteMsg::RegisterWorkerSet {
             worker_set: worker_set.clone(),
+            digest_scheme: None,
         };
 
         execute(deps, env, info.clone(), msg).map(|res| (res, worker_set))
@@ -427,20 +1388,50 @@ mod tests {
         deps: DepsMut,
         sender: &str,
         worker_set_id: &str,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        do_start_signing_session_with_msg(deps, sender, worker_set_id, ecdsa_test_data::message())
+    }
+
+    fn do_start_signing_session_with_msg(
+        deps: DepsMut,
+        sender: &str,
+        worker_set_id: &str,
+        message: HexBinary,
     ) -> Result<Response, axelar_wasm_std::ContractError> {
         let info = mock_info(sender, &[]);
         let env = mock_env();
 
-        let message = ecdsa_test_data::message();
         let msg = ExecuteMsg::StartSigningSession {
             worker_set_id: worker_set_id.to_string(),
-            msg: message.clone(),
+            msg: message,
             chain_name: "Ethereum".to_string().try_into().unwrap(),
             sig_verifier: None,
+            high_value: false,
+            callback: false,
+            threshold: None,
         };
         execute(deps, env, info, msg)
     }
 
+    fn do_confirm_signing_session(
+        deps: DepsMut,
+        env: Env,
+        sender: &str,
+        session_id: Uint64,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        let msg = ExecuteMsg::ConfirmSigningSession { session_id };
+        execute(deps, env, mock_info(sender, &[]), msg)
+    }
+
+    fn do_cancel_signing_session(
+        deps: DepsMut,
+        sender: &str,
+        session_id: Uint64,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        let msg = ExecuteMsg::CancelSigningSession { session_id };
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)
+    }
+
     fn do_sign(
         deps: DepsMut,
         env: Env,
@@ -459,7 +1450,19 @@ mod tests {
         worker: Addr,
         public_key: PublicKey,
     ) -> Result<Response, axelar_wasm_std::ContractError> {
-        let msg = ExecuteMsg::RegisterPublicKey { public_key };
+        do_register_key_with_proof(deps, worker, public_key, None)
+    }
+
+    fn do_register_key_with_proof(
+        deps: DepsMut,
+        worker: Addr,
+        public_key: PublicKey,
+        proof_of_possession: Option<HexBinary>,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        let msg = ExecuteMsg::RegisterPublicKey {
+            public_key,
+            proof_of_possession,
+        };
         execute(deps, mock_env(), mock_info(worker.as_str(), &[]), msg)
     }
 
@@ -487,6 +1490,30 @@ mod tests {
         execute(deps, env, info, msg)
     }
 
+    fn do_update_retention_policy(
+        deps: DepsMut,
+        sender: &str,
+        retention_policy: Option<RetentionPolicy>,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        let msg = ExecuteMsg::UpdateRetentionPolicy { retention_policy };
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)
+    }
+
+    fn do_prune_sessions(
+        deps: DepsMut,
+        env: Env,
+        limit: u32,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        let msg = ExecuteMsg::PruneSessions { limit };
+        execute(deps, env, mock_info("anyone", &[]), msg)
+    }
+
+    fn pruned_session_ids(res: &Response) -> Vec<Uint64> {
+        let event = res.events.get(0).unwrap();
+        assert_eq!(event.ty, "sessions_pruned".to_string());
+        from_str(get_event_attribute(event, "session_ids").unwrap()).unwrap()
+    }
+
     fn query_registered_public_key(
         deps: Deps,
         worker: Addr,
@@ -613,7 +1640,7 @@ mod tests {
 
             assert!(res.is_ok());
 
-            let session = SIGNING_SESSIONS
+            let session = signing_sessions()
                 .load(deps.as_ref().storage, i as u64 + 1)
                 .unwrap();
 
@@ -652,123 +1679,510 @@ mod tests {
                 from_str(get_event_attribute(event, "pub_keys").unwrap()).unwrap()
             );
             assert_eq!(get_event_attribute(event, "msg").unwrap(), message.to_hex());
+            assert_eq!(
+                get_event_attribute(event, "digest_scheme").unwrap(),
+                "\"raw\""
+            );
         }
     }
 
     #[test]
-    fn start_signing_session_wrong_sender() {
+    fn start_signing_session_joins_pending_session_with_same_msg() {
         let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
         do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
 
-        let sender = "someone else";
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        assert_eq!(res.data, Some(to_binary(&Uint64::one()).unwrap()));
 
-        for worker_set_id in [ecdsa_subkey, ed25519_subkey] {
-            let res = do_start_signing_session(deps.as_mut(), sender, &worker_set_id);
+        // same key and message: the pending session is returned and signers are not notified again
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        assert_eq!(res.data, Some(to_binary(&Uint64::one()).unwrap()));
+        assert!(res.events.is_empty());
+        assert_eq!(
+            SIGNING_SESSION_COUNTER.load(deps.as_ref().storage).unwrap(),
+            Uint64::one()
+        );
 
-            assert_eq!(
-                res.unwrap_err().to_string(),
-                axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
-                    + ": () not found"
-            );
-        }
+        // a different key or message starts a new session
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ed25519_subkey).unwrap();
+        assert_eq!(res.data, Some(to_binary(&Uint64::from(2u64)).unwrap()));
+        let res = do_start_signing_session_with_msg(
+            deps.as_mut(),
+            PROVER,
+            &ecdsa_subkey,
+            HexBinary::from([1; 32]),
+        )
+        .unwrap();
+        assert_eq!(res.data, Some(to_binary(&Uint64::from(3u64)).unwrap()));
+
+        // once the session is completed, the same message can be signed again in a new session
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[0]).unwrap();
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[1]).unwrap();
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        assert_eq!(res.data, Some(to_binary(&Uint64::from(4u64)).unwrap()));
     }
 
     #[test]
-    fn submit_signature() {
-        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+    fn start_signing_session_only_joins_pending_session_of_same_kind() {
+        let (mut deps, ecdsa_subkey, _) = setup();
         do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
 
-        for (key_type, worker_set_id, signers, session_id) in
-            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
-        {
-            do_start_signing_session(deps.as_mut(), PROVER, worker_set_id).unwrap();
+        let start = |deps: DepsMut, high_value: bool, callback: bool| {
+            let msg = ExecuteMsg::StartSigningSession {
+                worker_set_id: ecdsa_subkey.clone(),
+                msg: ecdsa_test_data::message(),
+                chain_name: "Ethereum".to_string().try_into().unwrap(),
+                sig_verifier: None,
+                high_value,
+                callback,
+                threshold: None,
+            };
+            let res = execute(deps, mock_env(), mock_info(PROVER, &[]), msg).unwrap();
+            from_binary::<Uint64>(&res.data.unwrap()).unwrap()
+        };
 
-            let signer = signers.get(0).unwrap().to_owned();
+        assert_eq!(start(deps.as_mut(), false, false), Uint64::one());
+        assert_eq!(start(deps.as_mut(), false, true), Uint64::from(2u64));
+        assert_eq!(start(deps.as_mut(), true, false), Uint64::from(3u64));
+        assert_eq!(start(deps.as_mut(), true, true), Uint64::from(4u64));
 
-            let expected_rewards_msg = WasmMsg::Execute {
-                contract_addr: REWARDS_CONTRACT.to_string(),
-                msg: to_binary(&rewards::msg::ExecuteMsg::RecordParticipation {
-                    event_id: session_id.to_string().try_into().unwrap(),
-                    worker_address: signer.address.clone().into(),
-                })
-                .unwrap(),
-                funds: vec![],
-            }
-            .into();
+        assert_eq!(start(deps.as_mut(), false, false), Uint64::one());
+        assert_eq!(start(deps.as_mut(), false, true), Uint64::from(2u64));
+        assert_eq!(start(deps.as_mut(), true, false), Uint64::from(3u64));
+        assert_eq!(start(deps.as_mut(), true, true), Uint64::from(4u64));
+    }
 
-            let res = do_sign(deps.as_mut(), mock_env(), Uint64::from(session_id), &signer);
+    #[test]
+    fn start_signing_session_reuses_signatures_of_same_digest() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
 
-            assert!(res.is_ok());
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[0]).unwrap();
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[1]).unwrap();
 
-            let session = SIGNING_SESSIONS
-                .load(deps.as_ref().storage, session_id.into())
-                .unwrap();
-            let signatures =
-                load_session_signatures(deps.as_ref().storage, session.id.u64()).unwrap();
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        assert_eq!(res.data, Some(to_binary(&Uint64::from(2u64)).unwrap()));
 
-            assert_eq!(signatures.len(), 1);
-            assert_eq!(
-                signatures
-                    .get(&signer.address.clone().into_string())
-                    .unwrap(),
-                &Signature::try_from((key_type, signer.signature.clone())).unwrap()
-            );
-            assert_eq!(session.state, MultisigState::Pending);
+        let event = res
+            .events
+            .iter()
+            .find(|event| event.ty == "signatures_reused")
+            .unwrap();
+        assert_eq!(get_event_attribute(event, "previous_session_id"), Some("1"));
+        assert_eq!(
+            get_event_attribute(event, "participants"),
+            Some(r#"["signer1","signer2"]"#)
+        );
+        assert!(res
+            .events
+            .iter()
+            .any(|event| event.ty == "signing_completed"));
 
-            let res = res.unwrap();
-            assert_eq!(res.events.len(), 1);
+        let session = signing_sessions().load(deps.as_ref().storage, 2).unwrap();
+        assert!(matches!(session.state, MultisigState::Completed { .. }));
+        assert_eq!(
+            load_session_signatures(deps.as_ref().storage, 2).unwrap(),
+            load_session_signatures(deps.as_ref().storage, 1).unwrap()
+        );
 
-            assert!(res.messages.iter().any(|m| m.msg == expected_rewards_msg));
+        // signatures of pruned sessions can't be reused
+        do_update_retention_policy(
+            deps.as_mut(),
+            "governance",
+            Some(RetentionPolicy::MaxCount { sessions: 0 }),
+        )
+        .unwrap();
+        let mut env = mock_env();
+        env.block.height += 3;
+        do_prune_sessions(deps.as_mut(), env, 10).unwrap();
+
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        assert_eq!(res.data, Some(to_binary(&Uint64::from(3u64)).unwrap()));
+        assert_eq!(res.events.len(), 1);
+        let session = signing_sessions().load(deps.as_ref().storage, 3).unwrap();
+        assert_eq!(session.state, MultisigState::Pending);
+    }
 
-            let event = res.events.get(0).unwrap();
-            assert_eq!(event.ty, "signature_submitted".to_string());
-            assert_eq!(
-                get_event_attribute(event, "session_id").unwrap(),
-                session_id.to_string()
-            );
-            assert_eq!(
-                get_event_attribute(event, "participant").unwrap(),
-                signer.address.into_string()
-            );
+    #[test]
+    fn start_signing_session_with_threshold_override() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let start_session = |deps: DepsMut, threshold: u64| {
+            let msg = ExecuteMsg::StartSigningSession {
+                worker_set_id: ecdsa_subkey.clone(),
+                msg: ecdsa_test_data::message(),
+                chain_name: "Ethereum".to_string().try_into().unwrap(),
+                sig_verifier: None,
+                high_value: false,
+                callback: false,
+                threshold: Some(Uint256::from(threshold)),
+            };
+            execute(deps, mock_env(), mock_info(PROVER, &[]), msg)
+        };
+
+        // the key's threshold is 2 out of a total weight of 3
+        for threshold in [1u64, 4] {
             assert_eq!(
-                get_event_attribute(event, "signature").unwrap(),
-                signer.signature.to_hex()
+                start_session(deps.as_mut(), threshold)
+                    .unwrap_err()
+                    .to_string(),
+                axelar_wasm_std::ContractError::from(ContractError::InvalidSessionThreshold {
+                    threshold: Uint256::from(threshold),
+                    key_threshold: Uint256::from(2u64),
+                    total_weight: Uint256::from(3u64),
+                })
+                .to_string()
             );
         }
+
+        // a higher threshold than the pending session's starts a new session and leaves the pending one untouched
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        let res = start_session(deps.as_mut(), 3).unwrap();
+        let strict_session_id = Uint64::from(2u64);
+        assert_eq!(res.data, Some(to_binary(&strict_session_id).unwrap()));
+
+        let multisig = query::get_multisig(deps.as_ref(), Uint64::one()).unwrap();
+        assert_eq!(multisig.quorum, Uint256::from(2u64));
+        assert_eq!(multisig.session_threshold, Uint256::from(2u64));
+
+        let multisig = query::get_multisig(deps.as_ref(), strict_session_id).unwrap();
+        assert_eq!(multisig.quorum, Uint256::from(2u64));
+        assert_eq!(multisig.session_threshold, Uint256::from(3u64));
+
+        // a lower threshold joins the stricter pending session
+        let res = start_session(deps.as_mut(), 2).unwrap();
+        assert_eq!(res.data, Some(to_binary(&strict_session_id).unwrap()));
+
+        do_sign(deps.as_mut(), mock_env(), strict_session_id, &signers[0]).unwrap();
+        do_sign(deps.as_mut(), mock_env(), strict_session_id, &signers[1]).unwrap();
+        let session = signing_sessions()
+            .load(deps.as_ref().storage, strict_session_id.into())
+            .unwrap();
+        assert_eq!(session.state, MultisigState::Pending);
+
+        do_sign(deps.as_mut(), mock_env(), strict_session_id, &signers[2]).unwrap();
+        let session = signing_sessions()
+            .load(deps.as_ref().storage, strict_session_id.into())
+            .unwrap();
+        assert!(matches!(session.state, MultisigState::Completed { .. }));
     }
 
     #[test]
-    fn submit_signature_completes_session() {
+    fn start_signing_session_wrong_sender() {
         let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
         do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
 
-        for (key_type, subkey, signers, session_id) in
-            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
-        {
-            do_start_signing_session(deps.as_mut(), PROVER, subkey).unwrap();
+        let sender = "someone else";
 
-            let signer = signers.get(0).unwrap().to_owned();
-            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+        for worker_set_id in [ecdsa_subkey, ed25519_subkey] {
+            let res = do_start_signing_session(deps.as_mut(), sender, &worker_set_id);
 
-            // second signature
-            let env = mock_env();
-            let expected_completed_at = env.block.height;
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+                    + ": () not found"
+            );
+        }
+    }
 
-            let signer = signers.get(1).unwrap().to_owned();
-            let res = do_sign(deps.as_mut(), env, session_id, &signer);
+    #[test]
+    fn high_value_signing_session_requires_confirmation() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
 
-            assert!(res.is_ok());
+        let msg = ExecuteMsg::StartSigningSession {
+            worker_set_id: ecdsa_subkey,
+            msg: ecdsa_test_data::message(),
+            chain_name: "Ethereum".to_string().try_into().unwrap(),
+            sig_verifier: None,
+            high_value: true,
+            callback: false,
+            threshold: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(PROVER, &[]), msg).unwrap();
+        let session_id: Uint64 = from_binary(&res.data.unwrap()).unwrap();
+        let confirmable_at = mock_env().block.height + CONFIRMATION_DELAY;
 
-            let session = SIGNING_SESSIONS
-                .load(deps.as_ref().storage, session_id.into())
-                .unwrap();
-            let signatures =
-                load_session_signatures(deps.as_ref().storage, session.id.u64()).unwrap();
+        assert_eq!(res.events.len(), 1);
+        let event = res.events.get(0).unwrap();
+        assert_eq!(event.ty, "signing_awaiting_confirmation".to_string());
+        assert_eq!(
+            get_event_attribute(event, "confirmable_at").unwrap(),
+            confirmable_at.to_string()
+        );
 
-            assert_eq!(signatures.len(), 2);
-            assert_eq!(
-                signatures.get(&signer.address.into_string()).unwrap(),
-                &Signature::try_from((key_type, signer.signature)).unwrap()
+        // signatures are rejected until the session is confirmed
+        let signer = ecdsa_test_data::signers().remove(0);
+        assert_eq!(
+            do_sign(deps.as_mut(), mock_env(), session_id, &signer)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::SigningSessionNotConfirmed {
+                session_id
+            })
+            .to_string()
+        );
+
+        let mut env = mock_env();
+        env.block.height = confirmable_at - 1;
+        assert_eq!(
+            do_confirm_signing_session(deps.as_mut(), env.clone(), PROVER, session_id)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::ConfirmationTooEarly {
+                session_id,
+                confirmable_at
+            })
+            .to_string()
+        );
+
+        env.block.height = confirmable_at;
+        assert_eq!(
+            do_confirm_signing_session(deps.as_mut(), env.clone(), "someone else", session_id)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+
+        let res =
+            do_confirm_signing_session(deps.as_mut(), env.clone(), PROVER, session_id).unwrap();
+        assert_eq!(res.events.len(), 1);
+        let event = res.events.get(0).unwrap();
+        assert_eq!(event.ty, "signing_started".to_string());
+        assert_eq!(
+            get_event_attribute(event, "session_id").unwrap(),
+            session_id.to_string()
+        );
+
+        assert!(do_sign(deps.as_mut(), env.clone(), session_id, &signer).is_ok());
+
+        assert_eq!(
+            do_confirm_signing_session(deps.as_mut(), env, PROVER, session_id)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::SigningSessionAlreadyConfirmed {
+                session_id
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn cancelled_high_value_session_cannot_be_confirmed() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        let msg = ExecuteMsg::StartSigningSession {
+            worker_set_id: ecdsa_subkey,
+            msg: ecdsa_test_data::message(),
+            chain_name: "Ethereum".to_string().try_into().unwrap(),
+            sig_verifier: None,
+            high_value: true,
+            callback: false,
+            threshold: None,
+        };
+        let start = |deps: DepsMut| -> Uint64 {
+            let res = execute(deps, mock_env(), mock_info(PROVER, &[]), msg.clone()).unwrap();
+            from_binary(&res.data.unwrap()).unwrap()
+        };
+
+        let session_id = start(deps.as_mut());
+        assert_eq!(
+            do_cancel_signing_session(deps.as_mut(), "someone else", session_id)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+
+        let res = do_cancel_signing_session(deps.as_mut(), "governance", session_id).unwrap();
+        assert_eq!(res.events.len(), 1);
+        let event = res.events.get(0).unwrap();
+        assert_eq!(event.ty, "signing_cancelled".to_string());
+        assert_eq!(
+            get_event_attribute(event, "session_id").unwrap(),
+            session_id.to_string()
+        );
+
+        let mut env = mock_env();
+        env.block.height += CONFIRMATION_DELAY;
+        assert_eq!(
+            do_confirm_signing_session(deps.as_mut(), env.clone(), PROVER, session_id)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::SigningSessionNotFound {
+                session_id
+            })
+            .to_string()
+        );
+
+        // the initiator can cancel as well, and a new request for the message opens a new session
+        let new_session_id = start(deps.as_mut());
+        assert_ne!(new_session_id, session_id);
+        assert!(do_cancel_signing_session(deps.as_mut(), PROVER, new_session_id).is_ok());
+
+        // confirmed sessions can't be cancelled anymore
+        let session_id = start(deps.as_mut());
+        do_confirm_signing_session(deps.as_mut(), env, PROVER, session_id).unwrap();
+        assert_eq!(
+            do_cancel_signing_session(deps.as_mut(), "governance", session_id)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::SigningSessionAlreadyConfirmed {
+                session_id
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn retried_session_reports_participation_under_the_same_event() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let participation_event_id = |res: Response| -> nonempty::String {
+            res.messages
+                .into_iter()
+                .find_map(|msg| match msg.msg {
+                    CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                        match from_binary(&msg).unwrap() {
+                            rewards::msg::ExecuteMsg::RecordParticipation { event_id, .. } => {
+                                Some(event_id)
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        let event_id = participation_event_id(
+            do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[0]).unwrap(),
+        );
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[1]).unwrap();
+
+        // the message is signed again in a new session, e.g. because the prover retried it
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        let retried_event_id = participation_event_id(
+            do_sign(deps.as_mut(), mock_env(), Uint64::from(2u64), &signers[2]).unwrap(),
+        );
+
+        // the rewards contract counts participation in the same event only once
+        assert_eq!(retried_event_id, event_id);
+        assert_eq!(
+            event_id,
+            rewards::msg::signing_event_id(&ecdsa_subkey, ecdsa_test_data::message().as_ref())
+        );
+    }
+
+    #[test]
+    fn submit_signature() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        for (key_type, worker_set_id, signers, session_id) in
+            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
+        {
+            do_start_signing_session(deps.as_mut(), PROVER, worker_set_id).unwrap();
+
+            let signer = signers.get(0).unwrap().to_owned();
+
+            let expected_rewards_msg = WasmMsg::Execute {
+                contract_addr: REWARDS_CONTRACT.to_string(),
+                msg: to_binary(&rewards::msg::ExecuteMsg::RecordParticipation {
+                    event_id: rewards::msg::signing_event_id(
+                        worker_set_id,
+                        ecdsa_test_data::message().as_ref(),
+                    ),
+                    worker_address: signer.address.clone().into(),
+                    late: false,
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into();
+
+            let res = do_sign(deps.as_mut(), mock_env(), Uint64::from(session_id), &signer);
+
+            assert!(res.is_ok());
+
+            let session = signing_sessions()
+                .load(deps.as_ref().storage, session_id.into())
+                .unwrap();
+            let signatures =
+                load_session_signatures(deps.as_ref().storage, session.id.u64()).unwrap();
+
+            assert_eq!(signatures.len(), 1);
+            assert_eq!(
+                signatures
+                    .get(&signer.address.clone().into_string())
+                    .unwrap(),
+                &Signature::try_from((key_type, signer.signature.clone())).unwrap()
+            );
+            assert_eq!(session.state, MultisigState::Pending);
+
+            let res = res.unwrap();
+            assert_eq!(res.events.len(), 1);
+
+            assert!(res.messages.iter().any(|m| m.msg == expected_rewards_msg));
+
+            let event = res.events.get(0).unwrap();
+            assert_eq!(event.ty, "signature_submitted".to_string());
+            assert_eq!(
+                get_event_attribute(event, "session_id").unwrap(),
+                session_id.to_string()
+            );
+            assert_eq!(
+                get_event_attribute(event, "participant").unwrap(),
+                signer.address.into_string()
+            );
+            assert_eq!(
+                get_event_attribute(event, "signature").unwrap(),
+                signer.signature.to_hex()
+            );
+            assert_eq!(get_event_attribute(event, "late").unwrap(), "false");
+        }
+    }
+
+    #[test]
+    fn submit_signature_completes_session() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        for (key_type, subkey, signers, session_id) in
+            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
+        {
+            do_start_signing_session(deps.as_mut(), PROVER, subkey).unwrap();
+
+            let signer = signers.get(0).unwrap().to_owned();
+            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+
+            // second signature
+            let env = mock_env();
+            let expected_completed_at = env.block.height;
+
+            let signer = signers.get(1).unwrap().to_owned();
+            let res = do_sign(deps.as_mut(), env, session_id, &signer);
+
+            assert!(res.is_ok());
+
+            let session = signing_sessions()
+                .load(deps.as_ref().storage, session_id.into())
+                .unwrap();
+            let signatures =
+                load_session_signatures(deps.as_ref().storage, session.id.u64()).unwrap();
+
+            assert_eq!(signatures.len(), 2);
+            assert_eq!(
+                signatures.get(&signer.address.into_string()).unwrap(),
+                &Signature::try_from((key_type, signer.signature)).unwrap()
             );
             assert_eq!(
                 session.state,
@@ -786,104 +2200,1117 @@ mod tests {
                 get_event_attribute(event, "session_id").unwrap(),
                 session_id.to_string()
             );
+
+            let event_signatures: HashMap<String, HexBinary> =
+                from_str(get_event_attribute(event, "signatures").unwrap()).unwrap();
+            assert_eq!(
+                event_signatures,
+                signatures
+                    .into_iter()
+                    .map(|(participant, signature)| (
+                        participant,
+                        HexBinary::from(signature.as_ref())
+                    ))
+                    .collect::<HashMap<_, _>>()
+            );
+        }
+    }
+
+    #[test]
+    fn submit_signature_aggregates_bls12_381_signatures() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut()).unwrap();
+        let (_, worker_set) = generate_worker_set(KeyType::Bls12_381, deps.as_mut()).unwrap();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        do_start_signing_session(deps.as_mut(), PROVER, &worker_set.id()).unwrap();
+
+        let session_id = Uint64::one();
+        let signers = bls12_381_test_data::signers();
+        for signer in &signers[..2] {
+            do_sign(deps.as_mut(), mock_env(), session_id, signer).unwrap();
+        }
+
+        let session = signing_sessions()
+            .load(deps.as_ref().storage, session_id.u64())
+            .unwrap();
+        assert!(matches!(session.state, MultisigState::Completed { .. }));
+
+        let aggregate: AggregateSignature = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetAggregateSignature { session_id },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        // the aggregate verifies against the sum of the keys of both signers, but not against a single signer's key
+        assert!(!bls12_381_verify(
+            ecdsa_test_data::message().as_slice(),
+            aggregate.signature.as_slice(),
+            signers[0].pub_key.as_slice(),
+        )
+        .unwrap());
+
+        let aggregate_pub_key = signers[..2]
+            .iter()
+            .map(|signer| decode_pub_key(signer.pub_key.as_slice()).unwrap())
+            .fold(bls12_381::G1Projective::identity(), |sum, pub_key| {
+                sum + pub_key
+            });
+        assert!(bls12_381_verify(
+            ecdsa_test_data::message().as_slice(),
+            aggregate.signature.as_slice(),
+            &bls12_381::G1Affine::from(aggregate_pub_key).to_compressed(),
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn submit_signature_completes_session_with_callback() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        let msg = ExecuteMsg::StartSigningSession {
+            worker_set_id: ecdsa_subkey,
+            msg: ecdsa_test_data::message(),
+            chain_name: "Ethereum".to_string().try_into().unwrap(),
+            sig_verifier: None,
+            high_value: false,
+            callback: true,
+            threshold: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(PROVER, &[]), msg).unwrap();
+        let session_id: Uint64 = from_binary(&res.data.unwrap()).unwrap();
+
+        let expected_callback = SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: PROVER.to_string(),
+                msg: to_binary(&CallbackMsg::SigningCompleted { session_id }).unwrap(),
+                funds: vec![],
+            },
+            SIGNING_CALLBACK_REPLY_ID,
+        );
+
+        let signers = ecdsa_test_data::signers();
+
+        // no callback before the session is completed
+        let res = do_sign(deps.as_mut(), mock_env(), session_id, &signers[0]).unwrap();
+        assert!(!res.messages.contains(&expected_callback));
+
+        let res = do_sign(deps.as_mut(), mock_env(), session_id, &signers[1]).unwrap();
+        assert_eq!(
+            res.messages
+                .iter()
+                .filter(|m| **m == expected_callback)
+                .count(),
+            1
+        );
+
+        // no second callback for signatures submitted during the grace period
+        let res = do_sign(deps.as_mut(), mock_env(), session_id, &signers[2]).unwrap();
+        assert!(!res.messages.contains(&expected_callback));
+    }
+
+    #[test]
+    fn failed_callback_does_not_revert() {
+        let mut deps = mock_dependencies();
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: SIGNING_CALLBACK_REPLY_ID,
+                result: SubMsgResult::Err("callback failed".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(res.events.len(), 1);
+        let event = res.events.get(0).unwrap();
+        assert_eq!(event.ty, "signing_callback_failed".to_string());
+        assert_eq!(
+            get_event_attribute(event, "error").unwrap(),
+            "callback failed"
+        );
+    }
+
+    #[test]
+    fn submit_signature_during_grace_period() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        for (_key_type, subkey, signers, session_id) in
+            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
+        {
+            do_start_signing_session(deps.as_mut(), PROVER, subkey).unwrap();
+
+            let signer = signers.get(0).unwrap().to_owned();
+            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+
+            // second signature
+            let signer = signers.get(1).unwrap().to_owned();
+            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+
+            // third signature, grace period
+            let signer = signers.get(2).unwrap().to_owned();
+
+            let expected_rewards_msg = WasmMsg::Execute {
+                contract_addr: REWARDS_CONTRACT.to_string(),
+                msg: to_binary(&rewards::msg::ExecuteMsg::RecordParticipation {
+                    event_id: rewards::msg::signing_event_id(
+                        subkey,
+                        ecdsa_test_data::message().as_ref(),
+                    ),
+                    worker_address: signer.address.clone().into(),
+                    late: true,
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into();
+
+            let mut env = mock_env();
+            env.block.height += 1;
+            let res = do_sign(deps.as_mut(), env, session_id, &signer).unwrap();
+            let signatures =
+                load_session_signatures(deps.as_ref().storage, session_id.u64()).unwrap();
+
+            assert_eq!(signatures.len(), 3);
+            assert!(res.messages.iter().any(|m| m.msg == expected_rewards_msg));
+            assert!(!res
+                .events
+                .iter()
+                .any(|e| e.ty == "signing_completed".to_string())); // event is not re-emitted during grace period
+
+            let event = res
+                .events
+                .iter()
+                .find(|e| e.ty == "signature_submitted")
+                .unwrap();
+            assert_eq!(get_event_attribute(event, "late").unwrap(), "true");
+
+            // the late signature is not part of the completed multisig
+            let multisig: Multisig = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::GetMultisig { session_id },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(
+                multisig
+                    .signers
+                    .iter()
+                    .filter(|(_, signature)| signature.is_some())
+                    .count(),
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn update_grace_period() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let msg = ExecuteMsg::UpdateGracePeriod { grace_period: 10 };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(PROVER, &[]),
+            msg.clone(),
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+        execute(deps.as_mut(), mock_env(), mock_info("governance", &[]), msg).unwrap();
+
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        for signer in signers.iter().take(2) {
+            do_sign(deps.as_mut(), mock_env(), Uint64::one(), signer).unwrap();
+        }
+
+        // accepted well after the instantiated grace period of 2 blocks
+        let mut env = mock_env();
+        env.block.height += 10;
+        do_sign(deps.as_mut(), env, Uint64::one(), &signers[2]).unwrap();
+    }
+
+    #[test]
+    fn submit_signature_grace_period_over() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        for (_key_type, subkey, signers, session_id) in
+            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
+        {
+            do_start_signing_session(deps.as_mut(), PROVER, subkey).unwrap();
+
+            let signer = signers.get(0).unwrap().to_owned();
+            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+
+            // second signature
+            let signer = signers.get(1).unwrap().to_owned();
+            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+
+            // third signature, grace period over
+            let signer = signers.get(2).unwrap().to_owned();
+            let mut env = mock_env();
+            env.block.height += 10;
+            let res = do_sign(deps.as_mut(), env, session_id, &signer);
+
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                axelar_wasm_std::ContractError::from(ContractError::SigningSessionClosed {
+                    session_id: session_id
+                })
+                .to_string()
+            )
+        }
+    }
+
+    #[test]
+    fn submit_signature_invalid_signature() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        for (key_type, worker_set_id, signers, session_id) in
+            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
+        {
+            do_start_signing_session(deps.as_mut(), PROVER, worker_set_id).unwrap();
+
+            // well-formed signature that doesn't match the signer's key and the session's message
+            let invalid_sig = match key_type {
+                KeyType::Ecdsa => "a58c9543b9df54578ec45838948e19afb1c6e4c86b34d9899b10b44e619ea74e19b457611e41a047030ed233af437d7ecff84de97cb6b3c13d73d22874e03511",
+                KeyType::Ed25519 => "1fe264eb7258d48d8feedea4d237ccb20157fbe5eb412bc971d758d072b036a99b06d20853c1f23cdf82085917e08dda2fcfbb5d4d7ee17d74e4988ae81d0308",
+                KeyType::Bls12_381 => unreachable!(),
+            };
+            let signer = TestSigner {
+                signature: HexBinary::from_hex(invalid_sig).unwrap(),
+                ..signers.get(0).unwrap().to_owned()
+            };
+            let res = do_sign(deps.as_mut(), mock_env(), session_id, &signer);
+
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                axelar_wasm_std::ContractError::from(ContractError::InvalidSignature {
+                    session_id,
+                    signer: signer.address.into_string(),
+                })
+                .to_string()
+            );
+
+            let signatures =
+                load_session_signatures(deps.as_ref().storage, session_id.u64()).unwrap();
+            assert!(signatures.is_empty());
+        }
+    }
+
+    #[test]
+    fn unauthorized_registration_does_not_fix_digest_scheme() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut()).unwrap();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let worker_set = build_worker_set(KeyType::Ecdsa, &ecdsa_test_data::signers());
+        let worker_set_id = worker_set.id();
+
+        // someone registers the worker set before the prover does
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("front-runner", &[]),
+            ExecuteMsg::RegisterWorkerSet {
+                worker_set: worker_set.clone(),
+                digest_scheme: None,
+            },
+        )
+        .unwrap();
+        assert!(!KEY_DIGEST_SCHEMES.has(deps.as_ref().storage, &worker_set_id));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(PROVER, &[]),
+            ExecuteMsg::RegisterWorkerSet {
+                worker_set: worker_set.clone(),
+                digest_scheme: Some(DigestScheme::Sha256),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            load_digest_scheme(deps.as_ref().storage, &worker_set_id).unwrap(),
+            DigestScheme::Sha256
+        );
+
+        // registering the worker set again without a scheme doesn't change it
+        for sender in ["front-runner", PROVER] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::RegisterWorkerSet {
+                    worker_set: worker_set.clone(),
+                    digest_scheme: None,
+                },
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            load_digest_scheme(deps.as_ref().storage, &worker_set_id).unwrap(),
+            DigestScheme::Sha256
+        );
+    }
+
+    #[test]
+    fn authorized_registration_without_scheme_fixes_raw_scheme() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut()).unwrap();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let worker_set = build_worker_set(KeyType::Ecdsa, &ecdsa_test_data::signers());
+
+        let register = |deps: DepsMut, digest_scheme| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info(PROVER, &[]),
+                ExecuteMsg::RegisterWorkerSet {
+                    worker_set: worker_set.clone(),
+                    digest_scheme,
+                },
+            )
+        };
+        register(deps.as_mut(), None).unwrap();
+
+        assert_eq!(
+            register(deps.as_mut(), Some(DigestScheme::Sha256))
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::DigestSchemeImmutable {
+                worker_set_id: worker_set.id(),
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn submit_signature_verifies_key_digest_scheme() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut()).unwrap();
+        let worker_set = build_worker_set(KeyType::Ecdsa, &ecdsa_test_data::signers());
+        let ecdsa_subkey = worker_set.id();
+        let register = ExecuteMsg::RegisterWorkerSet {
+            worker_set: worker_set.clone(),
+            digest_scheme: Some(DigestScheme::Keccak256),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(PROVER, &[]),
+            register.clone(),
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+                + ": () not found"
+        );
+
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info(PROVER, &[]), register).unwrap();
+        assert_eq!(
+            KEY_DIGEST_SCHEMES
+                .load(deps.as_ref().storage, &ecdsa_subkey)
+                .unwrap(),
+            DigestScheme::Keccak256
+        );
+
+        // signers may already be signing with the scheme, so it can't be changed anymore
+        for digest_scheme in [DigestScheme::Raw, DigestScheme::Sha256] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(PROVER, &[]),
+                ExecuteMsg::RegisterWorkerSet {
+                    worker_set: worker_set.clone(),
+                    digest_scheme: Some(digest_scheme),
+                },
+            );
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                axelar_wasm_std::ContractError::from(ContractError::DigestSchemeImmutable {
+                    worker_set_id: ecdsa_subkey.clone(),
+                })
+                .to_string()
+            );
+        }
+        for digest_scheme in [Some(DigestScheme::Keccak256), None] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(PROVER, &[]),
+                ExecuteMsg::RegisterWorkerSet {
+                    worker_set: worker_set.clone(),
+                    digest_scheme,
+                },
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            load_digest_scheme(deps.as_ref().storage, &ecdsa_subkey).unwrap(),
+            DigestScheme::Keccak256
+        );
+
+        // the test signature is over the raw message, not its keccak256 hash
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        assert_eq!(
+            get_event_attribute(&res.events[0], "digest_scheme").unwrap(),
+            "\"keccak256\""
+        );
+        let signer = ecdsa_test_data::signers()[0].clone();
+        let res = do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signer);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::InvalidSignature {
+                session_id: Uint64::one(),
+                signer: signer.address.into_string(),
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn submit_signature_from_non_participant() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+
+        let signer = TestSigner {
+            address: Addr::unchecked("not_a_participant"),
+            ..ecdsa_test_data::signers().remove(0)
+        };
+        let res = do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signer);
+
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::NotAParticipant {
+                session_id: Uint64::one(),
+                worker_set_id: ecdsa_subkey,
+                signer: signer.address.into_string(),
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn submit_signature_wrong_session_id() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+
+        let invalid_session_id = Uint64::zero();
+        let signer = ecdsa_test_data::signers().get(0).unwrap().to_owned();
+        let res = do_sign(deps.as_mut(), mock_env(), invalid_session_id, &signer);
+
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::SigningSessionNotFound {
+                session_id: invalid_session_id
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn query_pending_sessions() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let query_pending_after =
+            |deps: Deps, participant: &Addr, start_after: Option<u64>, limit: u32| -> Vec<Uint64> {
+                from_binary::<Vec<PendingSession>>(
+                    &query(
+                        deps,
+                        mock_env(),
+                        QueryMsg::PendingSessions {
+                            participant: participant.to_string(),
+                            start_after: start_after.map(Uint64::from),
+                            limit,
+                        },
+                    )
+                    .unwrap(),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|session| session.session_id)
+                .collect()
+            };
+        let query_pending = |deps: Deps, participant: &Addr, limit: u32| {
+            query_pending_after(deps, participant, None, limit)
+        };
+
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        do_start_signing_session_with_msg(
+            deps.as_mut(),
+            PROVER,
+            &ecdsa_subkey,
+            HexBinary::from([1; 32]),
+        )
+        .unwrap();
+        // sessions awaiting confirmation don't accept signatures yet
+        let msg = ExecuteMsg::StartSigningSession {
+            worker_set_id: ecdsa_subkey.clone(),
+            msg: HexBinary::from([2; 32]),
+            chain_name: "Ethereum".to_string().try_into().unwrap(),
+            sig_verifier: None,
+            high_value: true,
+            callback: false,
+            threshold: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(PROVER, &[]), msg).unwrap();
+
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[0]).unwrap();
+
+        assert_eq!(
+            query_pending(deps.as_ref(), &signers[0].address, 10),
+            vec![Uint64::from(2u64)]
+        );
+        assert_eq!(
+            query_pending(deps.as_ref(), &signers[1].address, 10),
+            vec![Uint64::one(), Uint64::from(2u64)]
+        );
+        assert_eq!(
+            query_pending(deps.as_ref(), &signers[1].address, 1),
+            vec![Uint64::one()]
+        );
+        assert_eq!(
+            query_pending_after(deps.as_ref(), &signers[1].address, Some(1), 10),
+            vec![Uint64::from(2u64)]
+        );
+
+        // completed sessions are no longer pending, even during the grace period
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[1]).unwrap();
+        assert_eq!(
+            query_pending(deps.as_ref(), &signers[2].address, 10),
+            vec![Uint64::from(2u64)]
+        );
+
+        // confirmed sessions accept signatures
+        let mut env = mock_env();
+        env.block.height += CONFIRMATION_DELAY;
+        do_confirm_signing_session(deps.as_mut(), env, PROVER, Uint64::from(3u64)).unwrap();
+        assert_eq!(
+            query_pending(deps.as_ref(), &signers[2].address, 10),
+            vec![Uint64::from(2u64), Uint64::from(3u64)]
+        );
+
+        // not a participant
+        assert!(query_pending(deps.as_ref(), &Addr::unchecked("someone"), 10).is_empty());
+    }
+
+    #[test]
+    fn query_list_signing_sessions() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let list_sessions = |deps: Deps,
+                             start_after: Option<u64>,
+                             limit: u32,
+                             status_filter: Option<SessionStatus>|
+         -> Vec<Uint64> {
+            from_binary::<Vec<SessionSummary>>(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::ListSigningSessions {
+                        start_after: start_after.map(Uint64::from),
+                        limit,
+                        status_filter,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|session| session.session_id)
+            .collect()
+        };
+
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        for i in 2..=3u8 {
+            do_start_signing_session_with_msg(
+                deps.as_mut(),
+                PROVER,
+                &ecdsa_subkey,
+                HexBinary::from([i; 32]),
+            )
+            .unwrap();
+        }
+        let msg = ExecuteMsg::StartSigningSession {
+            worker_set_id: ecdsa_subkey.clone(),
+            msg: HexBinary::from([4; 32]),
+            chain_name: "Ethereum".to_string().try_into().unwrap(),
+            sig_verifier: None,
+            high_value: true,
+            callback: false,
+            threshold: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(PROVER, &[]), msg).unwrap();
+
+        for signer in &signers[..2] {
+            do_sign(deps.as_mut(), mock_env(), Uint64::one(), signer).unwrap();
+        }
+
+        assert_eq!(
+            list_sessions(deps.as_ref(), None, 10, None),
+            (1..=4u64).map(Uint64::from).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            list_sessions(deps.as_ref(), Some(1), 2, None),
+            vec![Uint64::from(2u64), Uint64::from(3u64)]
+        );
+        assert_eq!(
+            list_sessions(deps.as_ref(), None, 10, Some(SessionStatus::Pending)),
+            vec![Uint64::from(2u64), Uint64::from(3u64)]
+        );
+        assert_eq!(
+            list_sessions(deps.as_ref(), Some(2), 1, Some(SessionStatus::Pending)),
+            vec![Uint64::from(3u64)]
+        );
+        assert_eq!(
+            list_sessions(deps.as_ref(), None, 10, Some(SessionStatus::Completed)),
+            vec![Uint64::one()]
+        );
+        assert_eq!(
+            list_sessions(
+                deps.as_ref(),
+                None,
+                10,
+                Some(SessionStatus::AwaitingConfirmation)
+            ),
+            vec![Uint64::from(4u64)]
+        );
+        assert!(list_sessions(deps.as_ref(), Some(4), 10, None).is_empty());
+    }
+
+    #[test]
+    fn query_list_signing_sessions_caps_limit() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        for i in 0..=query::MAX_SESSIONS_LIMIT {
+            let mut msg = [0u8; 32];
+            msg[..4].copy_from_slice(&i.to_be_bytes());
+            do_start_signing_session_with_msg(
+                deps.as_mut(),
+                PROVER,
+                &ecdsa_subkey,
+                HexBinary::from(msg),
+            )
+            .unwrap();
+        }
+
+        for status_filter in [None, Some(SessionStatus::Pending)] {
+            let sessions: Vec<SessionSummary> = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::ListSigningSessions {
+                        start_after: None,
+                        limit: u32::MAX,
+                        status_filter,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(sessions.len(), query::MAX_SESSIONS_LIMIT as usize);
+        }
+    }
+
+    #[test]
+    fn query_list_session_signatures() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let list_signatures = |deps: Deps, start_after: Option<&Addr>, limit: u32| {
+            from_binary::<Vec<SessionSignature>>(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::ListSessionSignatures {
+                        session_id: Uint64::one(),
+                        start_after: start_after.map(Addr::to_string),
+                        limit,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        assert!(list_signatures(deps.as_ref(), None, 10).is_empty());
+
+        for signer in signers.iter().rev().take(2) {
+            do_sign(deps.as_mut(), mock_env(), Uint64::one(), signer).unwrap();
+        }
+
+        let expected = |signer: &TestSigner| SessionSignature {
+            signer: signer.address.clone(),
+            signature: Signature::try_from((KeyType::Ecdsa, signer.signature.clone())).unwrap(),
+            late: false,
+        };
+        let signed = &signers[1..];
+
+        assert_eq!(
+            list_signatures(deps.as_ref(), None, 10),
+            signed.iter().map(expected).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            list_signatures(deps.as_ref(), None, 1),
+            vec![expected(&signed[0])]
+        );
+        assert_eq!(
+            list_signatures(deps.as_ref(), Some(&signed[0].address), 10),
+            vec![expected(&signed[1])]
+        );
+    }
+
+    #[test]
+    fn query_verify_session_signatures() {
+        let (mut deps, _, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ed25519_test_data::signers();
+
+        do_start_signing_session(deps.as_mut(), PROVER, &ed25519_subkey).unwrap();
+        for signer in signers.iter().take(2) {
+            do_sign(deps.as_mut(), mock_env(), Uint64::one(), signer).unwrap();
+        }
+
+        let verify_signatures = |deps: Deps| {
+            from_binary::<Vec<SignatureVerification>>(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::VerifySessionSignatures {
+                        session_id: Uint64::one(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        let expected = |signer: &TestSigner, valid: bool| SignatureVerification {
+            signer: signer.address.clone(),
+            valid,
+        };
+
+        assert_eq!(
+            verify_signatures(deps.as_ref()),
+            vec![expected(&signers[0], true), expected(&signers[1], true)]
+        );
+
+        // replace the second signature with a well-formed one that doesn't match the session's message
+        let invalid_sig = HexBinary::from_hex("1fe264eb7258d48d8feedea4d237ccb20157fbe5eb412bc971d758d072b036a99b06d20853c1f23cdf82085917e08dda2fcfbb5d4d7ee17d74e4988ae81d0308").unwrap();
+        SIGNATURES
+            .save(
+                deps.as_mut().storage,
+                (1, signers[1].address.as_str()),
+                &Signature::try_from((KeyType::Ed25519, invalid_sig)).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            verify_signatures(deps.as_ref()),
+            vec![expected(&signers[0], true), expected(&signers[1], false)]
+        );
+    }
+
+    #[test]
+    fn query_key_stats() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let query_stats = |deps: Deps| -> KeyStats {
+            from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::GetKeyStats {
+                        worker_set_id: ecdsa_subkey.clone(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            query_stats(deps.as_ref()),
+            KeyStats {
+                sessions_started: 0,
+                sessions_completed: 0,
+                signatures_collected: 0,
+                average_signatures_per_session: Decimal::zero(),
+            }
+        );
+
+        // first session is completed by the second signature, and receives a third one during the grace period
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        for signer in signers.iter() {
+            do_sign(deps.as_mut(), mock_env(), Uint64::one(), signer).unwrap();
         }
+
+        // second session signs the same message, so it completes right away with the signatures of the first one.
+        // Reused signatures are not collected again
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+
+        assert_eq!(
+            query_stats(deps.as_ref()),
+            KeyStats {
+                sessions_started: 2,
+                sessions_completed: 2,
+                signatures_collected: 3,
+                average_signatures_per_session: Decimal::from_ratio(3u64, 2u64),
+            }
+        );
     }
 
     #[test]
-    fn submit_signature_during_grace_period() {
-        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+    fn execute_fails_on_partially_migrated_state() {
+        let (mut deps, ecdsa_subkey, _) = setup();
         do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
 
-        for (_key_type, subkey, signers, session_id) in
-            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
-        {
-            do_start_signing_session(deps.as_mut(), PROVER, subkey).unwrap();
-
-            let signer = signers.get(0).unwrap().to_owned();
-            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
-
-            // second signature
-            let signer = signers.get(1).unwrap().to_owned();
-            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
-
-            // third signature, grace period
-            let signer = signers.get(2).unwrap().to_owned();
+        // a migration that bumped the sessions namespace but not the code expecting it
+        versioning::set_state_version(deps.as_mut().storage, "signing_sessions", 3).unwrap();
 
-            let expected_rewards_msg = WasmMsg::Execute {
-                contract_addr: REWARDS_CONTRACT.to_string(),
-                msg: to_binary(&rewards::msg::ExecuteMsg::RecordParticipation {
-                    event_id: session_id.to_string().try_into().unwrap(),
-                    worker_address: signer.address.clone().into(),
-                })
-                .unwrap(),
-                funds: vec![],
+        let res = do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey);
+        assert!(res.unwrap_err().to_string().contains(
+            &versioning::Error::UnexpectedStateVersion {
+                namespace: "signing_sessions".to_string(),
+                stored: Some(3),
+                expected: 2,
             }
-            .into();
+            .to_string()
+        ));
 
-            let mut env = mock_env();
-            env.block.height += 1;
-            let res = do_sign(deps.as_mut(), env, session_id, &signer).unwrap();
-            let signatures =
-                load_session_signatures(deps.as_ref().storage, session_id.u64()).unwrap();
+        // the code can't migrate the state back, so migrating with it must not paper over the mismatch
+        assert!(migrate(deps.as_mut(), mock_env(), Empty {})
+            .unwrap_err()
+            .to_string()
+            .contains(
+                &versioning::Error::UnexpectedStateVersion {
+                    namespace: "signing_sessions".to_string(),
+                    stored: Some(3),
+                    expected: 2,
+                }
+                .to_string()
+            ));
 
-            assert_eq!(signatures.len(), 3);
-            assert!(res.messages.iter().any(|m| m.msg == expected_rewards_msg));
-            assert!(!res
-                .events
-                .iter()
-                .any(|e| e.ty == "signing_completed".to_string())); // event is not re-emitted during grace period
-        }
+        // a namespace behind the code is only stamped once it is migrated, and there is no migration from an unknown version
+        versioning::set_state_version(deps.as_mut().storage, "signing_sessions", 0).unwrap();
+        assert!(migrate(deps.as_mut(), mock_env(), Empty {})
+            .unwrap_err()
+            .to_string()
+            .contains(
+                &versioning::Error::MissingMigration {
+                    namespace: "signing_sessions".to_string(),
+                    stored: 0,
+                }
+                .to_string()
+            ));
+        assert!(do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).is_err());
     }
 
     #[test]
-    fn submit_signature_grace_period_over() {
-        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+    fn migrate_indexes_sessions_by_status() {
+        let (mut deps, ecdsa_subkey, _) = setup();
         do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
 
-        for (_key_type, subkey, signers, session_id) in
-            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
-        {
-            do_start_signing_session(deps.as_mut(), PROVER, subkey).unwrap();
+        // sessions stored before sessions were indexed by status
+        let session = signing_sessions().load(deps.as_ref().storage, 1).unwrap();
+        signing_sessions().remove(deps.as_mut().storage, 1).unwrap();
+        Map::<u64, SigningSession>::new("signing_sessions")
+            .save(deps.as_mut().storage, 1, &session)
+            .unwrap();
+        versioning::set_state_version(deps.as_mut().storage, "signing_sessions", 1).unwrap();
+
+        let pending = |deps: Deps| -> Vec<PendingSession> {
+            from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::PendingSessions {
+                        participant: ecdsa_test_data::signers()[0].address.to_string(),
+                        start_after: None,
+                        limit: 10,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        assert!(pending(deps.as_ref()).is_empty());
 
-            let signer = signers.get(0).unwrap().to_owned();
-            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+        migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
 
-            // second signature
-            let signer = signers.get(1).unwrap().to_owned();
-            do_sign(deps.as_mut(), mock_env(), session_id, &signer).unwrap();
+        assert_eq!(
+            pending(deps.as_ref())
+                .into_iter()
+                .map(|session| session.session_id)
+                .collect::<Vec<_>>(),
+            vec![Uint64::one()]
+        );
+    }
 
-            // third signature, grace period over
-            let signer = signers.get(2).unwrap().to_owned();
-            let mut env = mock_env();
-            env.block.height += 10;
-            let res = do_sign(deps.as_mut(), env, session_id, &signer);
+    #[test]
+    fn migrate_clears_lifetime_signer_liveness() {
+        let (mut deps, _, _) = setup();
+        let signer = ecdsa_test_data::signers()[0].address.to_string();
 
-            assert_eq!(
-                res.unwrap_err().to_string(),
-                axelar_wasm_std::ContractError::from(ContractError::SigningSessionClosed {
-                    session_id: session_id
-                })
-                .to_string()
-            )
-        }
+        // lifetime totals stored before liveness was tracked per window
+        Map::<&str, Uint64>::new("signer_liveness")
+            .save(deps.as_mut().storage, &signer, &Uint64::one())
+            .unwrap();
+        assert!(SIGNER_LIVENESS
+            .may_load(deps.as_ref().storage, &signer)
+            .is_err());
+
+        migrate_namespace(deps.as_mut().storage, "signer_liveness", None).unwrap();
+
+        assert_eq!(
+            SIGNER_LIVENESS
+                .may_load(deps.as_ref().storage, &signer)
+                .unwrap(),
+            None
+        );
     }
 
     #[test]
-    fn submit_signature_wrong_session_id() {
-        let (mut deps, ecdsa_subkey, _) = setup();
+    fn query_signer_liveness() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
         do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+        let signers = ecdsa_test_data::signers();
+
+        let query_liveness_at = |deps: Deps, signer: &TestSigner, height: u64| {
+            let mut env = mock_env();
+            env.block.height = height;
+            from_binary::<SignerLivenessStats>(
+                &query(
+                    deps,
+                    env,
+                    QueryMsg::GetSignerLiveness {
+                        signer: signer.address.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        let query_liveness = |deps: Deps, signer: &TestSigner| -> SignerLivenessStats {
+            query_liveness_at(deps, signer, mock_env().block.height)
+        };
+        let sign_at = |deps: DepsMut, session_id: u64, blocks_to_sign: u64, signer| {
+            let mut env = mock_env();
+            env.block.height += blocks_to_sign;
+            do_sign(deps, env, Uint64::from(session_id), signer).unwrap();
+        };
+
+        // the first two signers complete the first session, the last signer never signs
         do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        sign_at(deps.as_mut(), 1, 2, &signers[0]);
+        sign_at(deps.as_mut(), 1, 4, &signers[1]);
 
-        let invalid_session_id = Uint64::zero();
-        let signer = ecdsa_test_data::signers().get(0).unwrap().to_owned();
-        let res = do_sign(deps.as_mut(), mock_env(), invalid_session_id, &signer);
+        // liveness is aggregated over all keys of a signer
+        do_start_signing_session(deps.as_mut(), PROVER, &ed25519_subkey).unwrap();
+        sign_at(deps.as_mut(), 2, 1, &ed25519_test_data::signers()[0]);
 
         assert_eq!(
-            res.unwrap_err().to_string(),
-            axelar_wasm_std::ContractError::from(ContractError::SigningSessionNotFound {
-                session_id: invalid_session_id
-            })
-            .to_string()
+            query_liveness(deps.as_ref(), &signers[0]),
+            SignerLivenessStats {
+                sessions_eligible: 2,
+                sessions_signed: 2,
+                average_blocks_to_sign: Decimal::from_ratio(3u64, 2u64),
+                since_block_height: 0,
+            }
+        );
+        assert_eq!(
+            query_liveness(deps.as_ref(), &signers[1]),
+            SignerLivenessStats {
+                sessions_eligible: 2,
+                sessions_signed: 1,
+                average_blocks_to_sign: Decimal::from_ratio(4u64, 1u64),
+                since_block_height: 0,
+            }
+        );
+        assert_eq!(
+            query_liveness(deps.as_ref(), &signers[2]),
+            SignerLivenessStats {
+                sessions_eligible: 2,
+                sessions_signed: 0,
+                average_blocks_to_sign: Decimal::zero(),
+                since_block_height: 0,
+            }
+        );
+
+        // the counters roll over once the sessions fall out of the last windows
+        let height = mock_env().block.height + LIVENESS_WINDOWS * LIVENESS_WINDOW_BLOCKS;
+        assert_eq!(
+            query_liveness_at(deps.as_ref(), &signers[0], height),
+            SignerLivenessStats {
+                sessions_eligible: 0,
+                sessions_signed: 0,
+                average_blocks_to_sign: Decimal::zero(),
+                since_block_height: (height / LIVENESS_WINDOW_BLOCKS + 1 - LIVENESS_WINDOWS)
+                    * LIVENESS_WINDOW_BLOCKS,
+            }
+        );
+
+        let mut env = mock_env();
+        env.block.height = height;
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info(PROVER, &[]),
+            ExecuteMsg::StartSigningSession {
+                worker_set_id: ecdsa_subkey.clone(),
+                msg: ecdsa_test_data::message(),
+                chain_name: "Ethereum".to_string().try_into().unwrap(),
+                sig_verifier: None,
+                high_value: false,
+                callback: false,
+                threshold: None,
+            },
+        )
+        .unwrap();
+        // signers that already signed the digest aren't asked again, the last signer is counted in the new window only
+        assert_eq!(
+            query_liveness_at(deps.as_ref(), &signers[2], height),
+            SignerLivenessStats {
+                sessions_eligible: 1,
+                sessions_signed: 0,
+                average_blocks_to_sign: Decimal::zero(),
+                since_block_height: (height / LIVENESS_WINDOW_BLOCKS + 1 - LIVENESS_WINDOWS)
+                    * LIVENESS_WINDOW_BLOCKS,
+            }
+        );
+        assert_eq!(
+            SIGNER_LIVENESS
+                .load(deps.as_ref().storage, signers[2].address.as_str())
+                .unwrap()
+                .len(),
+            1
         );
     }
 
@@ -915,7 +3342,7 @@ mod tests {
             assert!(res.is_ok());
 
             let query_res: Multisig = from_binary(&res.unwrap()).unwrap();
-            let session = SIGNING_SESSIONS
+            let session = signing_sessions()
                 .load(deps.as_ref().storage, session_id.into())
                 .unwrap();
             let worker_set = WORKER_SETS
@@ -1010,6 +3437,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn register_bls12_381_key_requires_proof_of_possession() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut()).unwrap();
+
+        let worker = Addr::unchecked("worker");
+        let secret_key = bls12_381_test_data::secret_key(1);
+        let public_key = PublicKey::try_from((KeyType::Bls12_381, secret_key.pub_key())).unwrap();
+
+        let res = do_register_key(deps.as_mut(), worker.clone(), public_key.clone());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::MissingProofOfPossession)
+                .to_string()
+        );
+
+        // neither a proof of another key nor a signature of the key outside the proof of possession domain are valid proofs
+        for proof in [
+            bls12_381_test_data::secret_key(2).prove_possession(),
+            secret_key.sign(secret_key.pub_key().as_slice()),
+        ] {
+            let res = do_register_key_with_proof(
+                deps.as_mut(),
+                worker.clone(),
+                public_key.clone(),
+                Some(proof),
+            );
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                axelar_wasm_std::ContractError::from(ContractError::InvalidProofOfPossession {
+                    pub_key: secret_key.pub_key()
+                })
+                .to_string()
+            );
+        }
+
+        let res = do_register_key_with_proof(
+            deps.as_mut(),
+            worker.clone(),
+            public_key.clone(),
+            Some(secret_key.prove_possession()),
+        );
+        assert!(res.is_ok());
+
+        let res = query_registered_public_key(deps.as_ref(), worker, KeyType::Bls12_381);
+        assert_eq!(from_binary::<PublicKey>(&res.unwrap()).unwrap(), public_key);
+    }
+
     #[test]
     fn update_key() {
         let mut deps = mock_dependencies();
@@ -1120,6 +3595,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn authorize_caller_invalid_address() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+
+        assert!(do_authorize_caller(deps.as_mut(), Addr::unchecked("Prover")).is_err());
+        assert!(do_start_signing_session(deps.as_mut(), "Prover", &ecdsa_subkey).is_err());
+    }
+
     #[test]
     fn unauthorize_caller_wrong_caller() {
         let mut deps = setup().0;
@@ -1137,4 +3620,199 @@ mod tests {
             axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
         );
     }
+
+    #[test]
+    fn disable_and_enable_key() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        let do_update_key = |deps: DepsMut, sender: &str, msg: ExecuteMsg| {
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        };
+        let disable_msg = ExecuteMsg::DisableKey {
+            worker_set_id: ecdsa_subkey.clone(),
+        };
+        let key_disabled = axelar_wasm_std::ContractError::from(ContractError::KeyDisabled {
+            worker_set_id: ecdsa_subkey.clone(),
+        })
+        .to_string();
+
+        // high-value session started before the key is disabled
+        let msg = ExecuteMsg::StartSigningSession {
+            worker_set_id: ecdsa_subkey.clone(),
+            msg: HexBinary::from([1; 32]),
+            chain_name: "Ethereum".to_string().try_into().unwrap(),
+            sig_verifier: None,
+            high_value: true,
+            callback: false,
+            threshold: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(PROVER, &[]), msg).unwrap();
+        // regular session started before the key is disabled
+        let regular_session_id = Uint64::from(2u64);
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        let signer = &ecdsa_test_data::signers()[0];
+
+        assert_eq!(
+            do_update_key(deps.as_mut(), "user", disable_msg.clone())
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+        assert!(do_update_key(
+            deps.as_mut(),
+            "governance",
+            ExecuteMsg::DisableKey {
+                worker_set_id: "unknown".to_string()
+            }
+        )
+        .is_err());
+
+        let res = do_update_key(deps.as_mut(), "governance", disable_msg).unwrap();
+        assert_eq!(res.events[0].ty, "key_disabled");
+
+        assert_eq!(
+            do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey)
+                .unwrap_err()
+                .to_string(),
+            key_disabled
+        );
+        let mut env = mock_env();
+        env.block.height += CONFIRMATION_DELAY;
+        assert_eq!(
+            do_confirm_signing_session(deps.as_mut(), env.clone(), PROVER, Uint64::one())
+                .unwrap_err()
+                .to_string(),
+            key_disabled
+        );
+        assert_eq!(
+            do_sign(deps.as_mut(), mock_env(), regular_session_id, signer)
+                .unwrap_err()
+                .to_string(),
+            key_disabled
+        );
+
+        // other keys are not affected
+        assert!(do_start_signing_session(deps.as_mut(), PROVER, &ed25519_subkey).is_ok());
+
+        let enable_msg = ExecuteMsg::EnableKey {
+            worker_set_id: ecdsa_subkey.clone(),
+        };
+        assert_eq!(
+            do_update_key(deps.as_mut(), "user", enable_msg.clone())
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+        let res = do_update_key(deps.as_mut(), "governance", enable_msg).unwrap();
+        assert_eq!(res.events[0].ty, "key_enabled");
+
+        assert!(do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).is_ok());
+        assert!(do_confirm_signing_session(deps.as_mut(), env, PROVER, Uint64::one()).is_ok());
+        assert!(do_sign(deps.as_mut(), mock_env(), regular_session_id, signer).is_ok());
+    }
+
+    #[test]
+    fn prune_completed_sessions() {
+        let (mut deps, ecdsa_subkey, ed25519_subkey) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        let completed_at = mock_env().block.height;
+        for (_key_type, subkey, signers, session_id) in
+            signature_test_data(&ecdsa_subkey, &ed25519_subkey)
+        {
+            do_start_signing_session(deps.as_mut(), PROVER, subkey).unwrap();
+            do_sign(deps.as_mut(), mock_env(), session_id, &signers[0]).unwrap();
+            do_sign(deps.as_mut(), mock_env(), session_id, &signers[1]).unwrap();
+        }
+        // pending sessions are never pruned
+        do_start_signing_session_with_msg(
+            deps.as_mut(),
+            PROVER,
+            &ecdsa_subkey,
+            HexBinary::from([1; 32]),
+        )
+        .unwrap();
+
+        let mut env = mock_env();
+        env.block.height = completed_at + 100;
+
+        assert_eq!(
+            do_prune_sessions(deps.as_mut(), env.clone(), 10)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::NoRetentionPolicy).to_string()
+        );
+
+        let retention_policy = Some(RetentionPolicy::MaxAge { blocks: 5 });
+        assert_eq!(
+            do_update_retention_policy(deps.as_mut(), "user", retention_policy.clone())
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+        );
+        do_update_retention_policy(deps.as_mut(), "governance", retention_policy).unwrap();
+
+        // too recent to be pruned
+        env.block.height = completed_at + 5;
+        let res = do_prune_sessions(deps.as_mut(), env.clone(), 10).unwrap();
+        assert!(pruned_session_ids(&res).is_empty());
+
+        env.block.height = completed_at + 6;
+        let res = do_prune_sessions(deps.as_mut(), env.clone(), 1).unwrap();
+        assert_eq!(pruned_session_ids(&res), vec![Uint64::one()]);
+
+        assert!(signing_sessions()
+            .may_load(deps.as_ref().storage, 1)
+            .unwrap()
+            .is_none());
+        assert!(load_session_signatures(deps.as_ref().storage, 1)
+            .unwrap()
+            .is_empty());
+        assert!(query::get_multisig(deps.as_ref(), Uint64::one()).is_err());
+        assert_eq!(
+            query::get_archived_session(deps.as_ref(), Uint64::one()).unwrap(),
+            ArchivedSession {
+                session_id: Uint64::one(),
+                msg: ecdsa_test_data::message().try_into().unwrap(),
+                completed_at,
+            }
+        );
+
+        let res = do_prune_sessions(deps.as_mut(), env, 10).unwrap();
+        assert_eq!(pruned_session_ids(&res), vec![Uint64::from(2u64)]);
+        assert!(signing_sessions()
+            .may_load(deps.as_ref().storage, 3)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn prune_sessions_keeps_grace_period() {
+        let (mut deps, ecdsa_subkey, _) = setup();
+        do_authorize_caller(deps.as_mut(), Addr::unchecked(PROVER)).unwrap();
+
+        let completed_at = mock_env().block.height;
+        let signers = ecdsa_test_data::signers();
+        do_start_signing_session(deps.as_mut(), PROVER, &ecdsa_subkey).unwrap();
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[0]).unwrap();
+        do_sign(deps.as_mut(), mock_env(), Uint64::one(), &signers[1]).unwrap();
+
+        do_update_retention_policy(
+            deps.as_mut(),
+            "governance",
+            Some(RetentionPolicy::MaxCount { sessions: 0 }),
+        )
+        .unwrap();
+
+        // grace period of the test config is 2 blocks
+        let mut env = mock_env();
+        env.block.height = completed_at + 2;
+        let res = do_prune_sessions(deps.as_mut(), env.clone(), 10).unwrap();
+        assert!(pruned_session_ids(&res).is_empty());
+
+        env.block.height = completed_at + 3;
+        let res = do_prune_sessions(deps.as_mut(), env, 10).unwrap();
+        assert_eq!(pruned_session_ids(&res), vec![Uint64::one()]);
+    }
 }
@@ -1,5 +1,5 @@
 use axelar_wasm_std_derive::IntoContractError;
-use cosmwasm_std::{StdError, Uint64};
+use cosmwasm_std::{HexBinary, StdError, Uint256, Uint64};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, IntoContractError)]
@@ -13,8 +13,12 @@ pub enum ContractError {
     #[error("{signer:?} already submitted a signature for signing session {session_id:?}")]
     DuplicateSignature { session_id: Uint64, signer: String },
 
-    #[error("{signer:?} is not a participant in signing session {session_id:?}")]
-    NotAParticipant { session_id: Uint64, signer: String },
+    #[error("{signer:?} is not a participant of key {worker_set_id:?} in signing session {session_id:?}")]
+    NotAParticipant {
+        session_id: Uint64,
+        worker_set_id: String,
+        signer: String,
+    },
 
     #[error("signature verification failed: {reason:?}")]
     SignatureVerificationFailed { reason: String },
@@ -22,6 +26,13 @@ pub enum ContractError {
     #[error("{signer:?} submitted an invalid signature for signing session {session_id:?}")]
     InvalidSignature { session_id: Uint64, signer: String },
 
+    #[error("{signer:?} submitted a signature for signing session {session_id:?} that recovers to public key {recovered_pub_key} instead of the registered key")]
+    SignatureKeyMismatch {
+        session_id: Uint64,
+        signer: String,
+        recovered_pub_key: HexBinary,
+    },
+
     #[error("invalid public key format: {reason:?}")]
     InvalidPublicKeyFormat { reason: String },
 
@@ -37,6 +48,18 @@ pub enum ContractError {
     #[error("signing session {session_id:?} not found")]
     SigningSessionNotFound { session_id: Uint64 },
 
+    #[error("signing session {session_id:?} has not been confirmed yet")]
+    SigningSessionNotConfirmed { session_id: Uint64 },
+
+    #[error("signing session {session_id:?} does not need to be confirmed")]
+    SigningSessionAlreadyConfirmed { session_id: Uint64 },
+
+    #[error("signing session {session_id:?} cannot be confirmed before block {confirmable_at}")]
+    ConfirmationTooEarly {
+        session_id: Uint64,
+        confirmable_at: u64,
+    },
+
     #[error("number of participants does not match number of public keys")]
     PublicKeysMismatchParticipants,
 
@@ -46,6 +69,30 @@ pub enum ContractError {
     #[error("key type mismatch")]
     KeyTypeMismatch,
 
+    #[error("bls12-381 public keys must be registered with a proof of possession")]
+    MissingProofOfPossession,
+
+    #[error("invalid proof of possession for public key {pub_key}")]
+    InvalidProofOfPossession { pub_key: HexBinary },
+
     #[error("caller is not authorized")]
     Unauthorized,
+
+    #[error("no retention policy is set, so no sessions can be pruned")]
+    NoRetentionPolicy,
+
+    #[error("key {worker_set_id} is disabled")]
+    KeyDisabled { worker_set_id: String },
+
+    #[error(
+        "the digest scheme of key {worker_set_id} can't be changed once the key is registered"
+    )]
+    DigestSchemeImmutable { worker_set_id: String },
+
+    #[error("session threshold {threshold} must be between the key's threshold {key_threshold} and its total weight {total_weight}")]
+    InvalidSessionThreshold {
+        threshold: Uint256,
+        key_threshold: Uint256,
+        total_weight: Uint256,
+    },
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use connection_router::state::ChainName;
 use cosmwasm_std::{Addr, HexBinary, Uint64};
@@ -6,7 +6,7 @@ use serde_json::to_string;
 
 use crate::{
     key::{PublicKey, Signature},
-    types::MsgToSign,
+    types::{DigestScheme, MsgToSign},
 };
 
 pub enum Event {
@@ -17,17 +17,46 @@ pub enum Event {
         pub_keys: HashMap<String, PublicKey>,
         msg: MsgToSign,
         chain_name: ChainName,
+        // signers hash the msg with this scheme before signing it
+        digest_scheme: DigestScheme,
     },
-    // Emitted when a participants submits a signature
+    // Emitted instead of SigningStarted when a high-value session is opened. SigningStarted follows once it is confirmed
+    SigningAwaitingConfirmation {
+        session_id: Uint64,
+        confirmable_at: u64,
+    },
+    // Emitted when a high-value session was cancelled before it was confirmed
+    SigningCancelled {
+        session_id: Uint64,
+        cancelled_by: Addr,
+    },
+    // Emitted when a new session takes over the signatures of an earlier session of the same worker set over the same digest
+    SignaturesReused {
+        session_id: Uint64,
+        previous_session_id: Uint64,
+        participants: Vec<String>,
+    },
+    // Emitted when a participants submits a signature. Late signatures are submitted after the session was completed
     SignatureSubmitted {
         session_id: Uint64,
         participant: Addr,
         signature: Signature,
+        late: bool,
     },
-    // Emitted when a signing session was completed
+    // Emitted when a signing session was completed. Carries the signatures collected so far by participant address,
+    // so relayers can assemble the proof from the event stream without querying the session
     SigningCompleted {
         session_id: Uint64,
         completed_at: u64,
+        signatures: HashMap<String, Signature>,
+    },
+    // Emitted when the callback to the contract that started a completed session failed
+    SigningCallbackFailed {
+        error: String,
+    },
+    // Emitted when completed sessions were archived and removed according to the retention policy
+    SessionsPruned {
+        session_ids: Vec<Uint64>,
     },
     PublicKeyRegistered {
         worker: Addr,
@@ -39,6 +68,12 @@ pub enum Event {
     CallerUnauthorized {
         contract_address: Addr,
     },
+    KeyDisabled {
+        worker_set_id: String,
+    },
+    KeyEnabled {
+        worker_set_id: String,
+    },
 }
 
 impl From<Event> for cosmwasm_std::Event {
@@ -50,6 +85,7 @@ impl From<Event> for cosmwasm_std::Event {
                 pub_keys,
                 msg,
                 chain_name: chain,
+                digest_scheme,
             } => cosmwasm_std::Event::new("signing_started")
                 .add_attribute("session_id", session_id)
                 .add_attribute("worker_set_id", worker_set_id)
@@ -59,21 +95,72 @@ impl From<Event> for cosmwasm_std::Event {
                         .expect("violated invariant: pub_keys are not serializable"),
                 )
                 .add_attribute("msg", HexBinary::from(msg).to_hex())
-                .add_attribute("chain", chain),
+                .add_attribute("chain", chain)
+                .add_attribute(
+                    "digest_scheme",
+                    to_string(&digest_scheme)
+                        .expect("violated invariant: digest_scheme is not serializable"),
+                ),
+            Event::SigningAwaitingConfirmation {
+                session_id,
+                confirmable_at,
+            } => cosmwasm_std::Event::new("signing_awaiting_confirmation")
+                .add_attribute("session_id", session_id)
+                .add_attribute("confirmable_at", confirmable_at.to_string()),
+            Event::SigningCancelled {
+                session_id,
+                cancelled_by,
+            } => cosmwasm_std::Event::new("signing_cancelled")
+                .add_attribute("session_id", session_id)
+                .add_attribute("cancelled_by", cancelled_by),
+            Event::SignaturesReused {
+                session_id,
+                previous_session_id,
+                participants,
+            } => cosmwasm_std::Event::new("signatures_reused")
+                .add_attribute("session_id", session_id)
+                .add_attribute("previous_session_id", previous_session_id)
+                .add_attribute(
+                    "participants",
+                    to_string(&participants).expect("failed to serialize participants"),
+                ),
             Event::SignatureSubmitted {
                 session_id,
                 participant,
                 signature,
+                late,
             } => cosmwasm_std::Event::new("signature_submitted")
                 .add_attribute("session_id", session_id)
                 .add_attribute("participant", participant)
-                .add_attribute("signature", HexBinary::from(signature.as_ref()).to_hex()),
+                .add_attribute("signature", HexBinary::from(signature.as_ref()).to_hex())
+                .add_attribute("late", late.to_string()),
             Event::SigningCompleted {
                 session_id,
                 completed_at,
+                signatures,
             } => cosmwasm_std::Event::new("signing_completed")
                 .add_attribute("session_id", session_id)
-                .add_attribute("completed_at", completed_at.to_string()),
+                .add_attribute("completed_at", completed_at.to_string())
+                .add_attribute(
+                    "signatures",
+                    to_string(
+                        &signatures
+                            .into_iter()
+                            .map(|(participant, signature)| {
+                                (participant, HexBinary::from(signature.as_ref()))
+                            })
+                            .collect::<BTreeMap<_, _>>(),
+                    )
+                    .expect("violated invariant: signatures are not serializable"),
+                ),
+            Event::SigningCallbackFailed { error } => {
+                cosmwasm_std::Event::new("signing_callback_failed").add_attribute("error", error)
+            }
+            Event::SessionsPruned { session_ids } => cosmwasm_std::Event::new("sessions_pruned")
+                .add_attribute(
+                    "session_ids",
+                    to_string(&session_ids).expect("failed to serialize session ids"),
+                ),
             Event::PublicKeyRegistered { worker, public_key } => {
                 cosmwasm_std::Event::new("public_key_registered")
                     .add_attribute(
@@ -93,6 +180,10 @@ impl From<Event> for cosmwasm_std::Event {
                 cosmwasm_std::Event::new("caller_unauthorized")
                     .add_attribute("contract_address", contract_address)
             }
+            Event::KeyDisabled { worker_set_id } => cosmwasm_std::Event::new("key_disabled")
+                .add_attribute("worker_set_id", worker_set_id),
+            Event::KeyEnabled { worker_set_id } => cosmwasm_std::Event::new("key_enabled")
+                .add_attribute("worker_set_id", worker_set_id),
         }
     }
 }
@@ -1,6 +1,12 @@
-use crate::{ed25519::ed25519_verify, secp256k1::ecdsa_verify, types::MsgToSign, ContractError};
+use crate::{
+    bls::{bls12_381_verify, decode_pub_key, BLS12_381_SIGNATURE_LEN},
+    ed25519::ed25519_verify,
+    secp256k1::ecdsa_verify,
+    types::MsgToSign,
+    ContractError,
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{HexBinary, StdError, StdResult};
+use cosmwasm_std::{Api, HexBinary, StdError, StdResult};
 use cw_storage_plus::{KeyDeserialize, PrimaryKey};
 use enum_display_derive::Display;
 use serde::{de::Error, Deserialize, Deserializer};
@@ -11,6 +17,7 @@ use std::fmt::Display;
 pub enum KeyType {
     Ecdsa,
     Ed25519,
+    Bls12_381,
 }
 
 #[cw_serde]
@@ -19,6 +26,7 @@ pub enum Signature {
     Ecdsa(NonRecoverable),
     EcdsaRecoverable(Recoverable),
     Ed25519(HexBinary),
+    Bls12_381(HexBinary),
 }
 
 #[cw_serde]
@@ -82,6 +90,41 @@ pub struct Recoverable(HexBinary);
 
 impl Recoverable {
     const LEN: usize = 65;
+
+    /// Recovers the public key that produced this signature over the given message hash.
+    /// Accepts both raw (0-3) and Ethereum style (27-30) recovery bytes.
+    pub fn recover_pub_key(&self, msg: &[u8]) -> Result<HexBinary, ContractError> {
+        let (sig, recovery_byte) = self.0.split_at(NonRecoverable::LEN);
+
+        let sig = k256::ecdsa::Signature::from_slice(sig).map_err(|err| {
+            ContractError::InvalidSignatureFormat {
+                reason: err.to_string(),
+            }
+        })?;
+
+        let recovery_byte = match recovery_byte[0] {
+            byte @ 27..=30 => byte - 27,
+            byte => byte,
+        };
+        let recovery_id = k256::ecdsa::RecoveryId::from_byte(recovery_byte).ok_or_else(|| {
+            ContractError::InvalidSignatureFormat {
+                reason: format!("invalid recovery byte {}", recovery_byte),
+            }
+        })?;
+
+        k256::ecdsa::VerifyingKey::recover_from_prehash(msg, &sig, recovery_id)
+            .map(|key| HexBinary::from(key.to_encoded_point(true).as_bytes()))
+            .map_err(|err| ContractError::InvalidSignatureFormat {
+                reason: err.to_string(),
+            })
+    }
+}
+
+impl Recoverable {
+    /// Drops the recovery byte, e.g. to derive it again when its encoding is unknown
+    pub fn to_non_recoverable(&self) -> NonRecoverable {
+        NonRecoverable(HexBinary::from(&self.0.as_slice()[..NonRecoverable::LEN]))
+    }
 }
 
 impl AsRef<[u8]> for Recoverable {
@@ -115,6 +158,9 @@ pub enum PublicKey {
 
     #[serde(deserialize_with = "deserialize_ed25519_key")]
     Ed25519(HexBinary),
+
+    #[serde(deserialize_with = "deserialize_bls12_381_key")]
+    Bls12_381(HexBinary),
 }
 
 fn deserialize_ecdsa_key<'de, D>(deserializer: D) -> Result<HexBinary, D::Error>
@@ -137,6 +183,16 @@ where
     Ok(pk)
 }
 
+fn deserialize_bls12_381_key<'de, D>(deserializer: D) -> Result<HexBinary, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let pk: HexBinary = Deserialize::deserialize(deserializer)?;
+    PublicKey::try_from((KeyType::Bls12_381, pk.clone()))
+        .map_err(|e| D::Error::custom(format!("failed to deserialize public key: {}", e)))?;
+    Ok(pk)
+}
+
 pub trait KeyTyped {
     fn matches_type<T>(&self, other: &T) -> bool
     where
@@ -153,6 +209,7 @@ impl KeyTyped for PublicKey {
         match self {
             PublicKey::Ecdsa(_) => KeyType::Ecdsa,
             PublicKey::Ed25519(_) => KeyType::Ed25519,
+            PublicKey::Bls12_381(_) => KeyType::Bls12_381,
         }
     }
 }
@@ -162,19 +219,26 @@ impl KeyTyped for Signature {
         match self {
             Signature::Ecdsa(_) | Signature::EcdsaRecoverable(_) => KeyType::Ecdsa,
             Signature::Ed25519(_) => KeyType::Ed25519,
+            Signature::Bls12_381(_) => KeyType::Bls12_381,
         }
     }
 }
 
 impl Signature {
-    pub fn verify(&self, msg: &MsgToSign, pub_key: &PublicKey) -> Result<bool, ContractError> {
+    pub fn verify(
+        &self,
+        api: &dyn Api,
+        msg: &MsgToSign,
+        pub_key: &PublicKey,
+    ) -> Result<bool, ContractError> {
         if !self.matches_type(pub_key) {
             return Err(ContractError::KeyTypeMismatch);
         }
 
         match self.key_type() {
             KeyType::Ecdsa => ecdsa_verify(msg.as_ref(), self, pub_key.as_ref()),
-            KeyType::Ed25519 => ed25519_verify(msg.as_ref(), self.as_ref(), pub_key.as_ref()),
+            KeyType::Ed25519 => ed25519_verify(api, msg.as_ref(), self.as_ref(), pub_key.as_ref()),
+            KeyType::Bls12_381 => bls12_381_verify(msg.as_ref(), self.as_ref(), pub_key.as_ref()),
         }
     }
 }
@@ -238,6 +302,12 @@ impl TryFrom<(KeyType, HexBinary)> for PublicKey {
 
                 Ok(PublicKey::Ed25519(pub_key))
             }
+
+            KeyType::Bls12_381 => {
+                decode_pub_key(pub_key.as_ref())?;
+
+                Ok(PublicKey::Bls12_381(pub_key))
+            }
         }
     }
 }
@@ -250,6 +320,7 @@ impl TryFrom<(KeyType, HexBinary)> for Signature {
             (KeyType::Ecdsa, Recoverable::LEN) => Ok(Signature::EcdsaRecoverable(Recoverable(sig))),
             (KeyType::Ecdsa, NonRecoverable::LEN) => Ok(Signature::Ecdsa(NonRecoverable(sig))),
             (KeyType::Ed25519, ED25519_SIGNATURE_LEN) => Ok(Signature::Ed25519(sig)),
+            (KeyType::Bls12_381, BLS12_381_SIGNATURE_LEN) => Ok(Signature::Bls12_381(sig)),
             (_, _) => Err(ContractError::InvalidSignatureFormat {
                 reason: format!(
                     "could not find a match for key type {} and signature length {}",
@@ -266,6 +337,7 @@ impl AsRef<[u8]> for PublicKey {
         match self {
             PublicKey::Ecdsa(pk) => pk.as_ref(),
             PublicKey::Ed25519(pk) => pk.as_ref(),
+            PublicKey::Bls12_381(pk) => pk.as_ref(),
         }
     }
 }
@@ -276,6 +348,7 @@ impl AsRef<[u8]> for Signature {
             Signature::Ecdsa(sig) => sig.as_ref(),
             Signature::EcdsaRecoverable(sig) => sig.as_ref(),
             Signature::Ed25519(sig) => sig.as_ref(),
+            Signature::Bls12_381(sig) => sig.as_ref(),
         }
     }
 }
@@ -285,17 +358,18 @@ impl From<PublicKey> for HexBinary {
         match original {
             PublicKey::Ecdsa(sig) => sig,
             PublicKey::Ed25519(sig) => sig,
+            PublicKey::Bls12_381(sig) => sig,
         }
     }
 }
 
 #[cfg(test)]
 mod ecdsa_tests {
-    use cosmwasm_std::HexBinary;
+    use cosmwasm_std::{testing::MockApi, HexBinary};
 
     use crate::{key::Signature, test::common::ecdsa_test_data, types::MsgToSign, ContractError};
 
-    use super::{KeyType, PublicKey};
+    use super::{KeyType, PublicKey, Recoverable};
 
     #[test]
     fn deserialize_ecdsa_key() {
@@ -368,7 +442,9 @@ mod ecdsa_tests {
             .unwrap();
         let message = MsgToSign::try_from(ecdsa_test_data::message()).unwrap();
         let public_key = PublicKey::try_from((KeyType::Ecdsa, ecdsa_test_data::pub_key())).unwrap();
-        let result = signature.verify(&message, &public_key).unwrap();
+        let result = signature
+            .verify(&MockApi::default(), &message, &public_key)
+            .unwrap();
         assert_eq!(result, true);
     }
 
@@ -382,10 +458,51 @@ mod ecdsa_tests {
         let signature: Signature = (KeyType::Ecdsa, invalid_signature).try_into().unwrap();
         let message = MsgToSign::try_from(ecdsa_test_data::message()).unwrap();
         let public_key = PublicKey::try_from((KeyType::Ecdsa, ecdsa_test_data::pub_key())).unwrap();
-        let result = signature.verify(&message, &public_key).unwrap();
+        let result = signature
+            .verify(&MockApi::default(), &message, &public_key)
+            .unwrap();
         assert_eq!(result, false);
     }
 
+    #[test]
+    fn recover_pub_key_from_recoverable_signature() {
+        let mut sig = ecdsa_test_data::signature().to_vec();
+        sig.push(0);
+        let recoverable = Recoverable::try_from(HexBinary::from(sig)).unwrap();
+
+        let recovered = recoverable
+            .recover_pub_key(ecdsa_test_data::message().as_ref())
+            .unwrap();
+        assert_eq!(recovered, ecdsa_test_data::pub_key());
+    }
+
+    #[test]
+    fn recover_pub_key_with_ethereum_recovery_byte() {
+        let mut sig = ecdsa_test_data::signature().to_vec();
+        sig.push(27);
+        let recoverable = Recoverable::try_from(HexBinary::from(sig)).unwrap();
+
+        let recovered = recoverable
+            .recover_pub_key(ecdsa_test_data::message().as_ref())
+            .unwrap();
+        assert_eq!(recovered, ecdsa_test_data::pub_key());
+    }
+
+    #[test]
+    fn recover_pub_key_from_different_digest() {
+        let mut sig = ecdsa_test_data::signature().to_vec();
+        sig.push(0);
+        let recoverable = Recoverable::try_from(HexBinary::from(sig)).unwrap();
+
+        let different_digest =
+            HexBinary::from_hex("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90")
+                .unwrap();
+        let recovered = recoverable
+            .recover_pub_key(different_digest.as_ref())
+            .unwrap();
+        assert_ne!(recovered, ecdsa_test_data::pub_key());
+    }
+
     #[test]
     fn test_verify_signature_invalid_pub_key() {
         let invalid_pub_key = HexBinary::from_hex(
@@ -398,14 +515,16 @@ mod ecdsa_tests {
             .unwrap();
         let message = MsgToSign::try_from(ecdsa_test_data::message()).unwrap();
         let public_key = PublicKey::try_from((KeyType::Ecdsa, invalid_pub_key)).unwrap();
-        let result = signature.verify(&message, &public_key).unwrap();
+        let result = signature
+            .verify(&MockApi::default(), &message, &public_key)
+            .unwrap();
         assert_eq!(result, false);
     }
 }
 
 #[cfg(test)]
 mod ed25519_tests {
-    use cosmwasm_std::HexBinary;
+    use cosmwasm_std::{testing::MockApi, HexBinary};
 
     use crate::{key::Signature, test::common::ed25519_test_data, types::MsgToSign, ContractError};
 
@@ -481,7 +600,9 @@ mod ed25519_tests {
         let message = MsgToSign::try_from(ed25519_test_data::message()).unwrap();
         let public_key =
             PublicKey::try_from((KeyType::Ed25519, ed25519_test_data::pub_key())).unwrap();
-        let result = signature.verify(&message, &public_key).unwrap();
+        let result = signature
+            .verify(&MockApi::default(), &message, &public_key)
+            .unwrap();
         assert_eq!(result, true);
     }
 
@@ -496,10 +617,50 @@ mod ed25519_tests {
         let message = MsgToSign::try_from(ed25519_test_data::message()).unwrap();
         let public_key =
             PublicKey::try_from((KeyType::Ed25519, ed25519_test_data::pub_key())).unwrap();
-        let result = signature.verify(&message, &public_key).unwrap();
+        let result = signature
+            .verify(&MockApi::default(), &message, &public_key)
+            .unwrap();
         assert_eq!(result, false);
     }
 
+    #[test]
+    fn signature_is_never_recoverable() {
+        let signature =
+            Signature::try_from((KeyType::Ed25519, ed25519_test_data::signature())).unwrap();
+        assert!(matches!(signature, Signature::Ed25519(_)));
+    }
+
+    #[test]
+    fn signature_with_recovery_byte_is_rejected() {
+        let mut sig = ed25519_test_data::signature().to_vec();
+        sig.push(0);
+
+        assert_eq!(
+            Signature::try_from((KeyType::Ed25519, HexBinary::from(sig))).unwrap_err(),
+            ContractError::InvalidSignatureFormat {
+                reason: "could not find a match for key type Ed25519 and signature length 65"
+                    .into()
+            }
+        );
+    }
+
+    #[test]
+    fn verify_signature_over_different_digest() {
+        let signature =
+            Signature::try_from((KeyType::Ed25519, ed25519_test_data::signature())).unwrap();
+        let different_digest = MsgToSign::try_from(
+            HexBinary::from_hex("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90")
+                .unwrap(),
+        )
+        .unwrap();
+        let public_key =
+            PublicKey::try_from((KeyType::Ed25519, ed25519_test_data::pub_key())).unwrap();
+        let result = signature
+            .verify(&MockApi::default(), &different_digest, &public_key)
+            .unwrap();
+        assert!(!result);
+    }
+
     #[test]
     fn test_verify_signature_invalid_pub_key() {
         let invalid_pub_key =
@@ -510,7 +671,9 @@ mod ed25519_tests {
             Signature::try_from((KeyType::Ed25519, ed25519_test_data::signature())).unwrap();
         let message = MsgToSign::try_from(ed25519_test_data::message()).unwrap();
         let public_key = PublicKey::try_from((KeyType::Ed25519, invalid_pub_key)).unwrap();
-        let result = signature.verify(&message, &public_key).unwrap();
+        let result = signature
+            .verify(&MockApi::default(), &message, &public_key)
+            .unwrap();
         assert_eq!(result, false);
     }
 }
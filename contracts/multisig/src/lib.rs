@@ -14,6 +14,9 @@ mod secp256k1;
 #[cfg(feature = "ed25519")]
 mod ed25519;
 
+#[cfg(feature = "bls12_381")]
+pub mod bls;
+
 #[cfg(test)]
 pub mod test;
 
@@ -1,10 +1,11 @@
 use connection_router::state::ChainName;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, HexBinary, Uint256, Uint64};
+use cosmwasm_std::{Addr, Decimal, HexBinary, Uint256, Uint64};
 
 use crate::{
     key::{KeyType, PublicKey, Signature},
-    types::MultisigState,
+    signing::{AggregateSignature, ArchivedSession},
+    types::{DigestScheme, MultisigState, RetentionPolicy},
     worker_set::WorkerSet,
 };
 
@@ -13,7 +14,8 @@ pub struct InstantiateMsg {
     // the governance address is allowed to modify the authorized caller list for this contract
     pub governance_address: String,
     pub rewards_address: String,
-    pub grace_period: u64, // in blocks after session has been completed
+    pub grace_period: u64,       // in blocks after session has been completed
+    pub confirmation_delay: u64, // in blocks after a high-value session has been started, before it can be confirmed
 }
 
 #[cw_serde]
@@ -29,6 +31,30 @@ pub enum ExecuteMsg {
         If a callback address is provided, signature verification is handled by the contract at that address
         instead of the multisig contract. TODO: define interface for callback */
         sig_verifier: Option<String>,
+        /* High-value sessions, such as operatorship transfers, don't accept signatures right away.
+        The initiating contract must confirm the session with ConfirmSigningSession after the confirmation delay,
+        which leaves a window to catch a malicious digest before it is signed. Signers are only notified once confirmed. */
+        #[serde(default)]
+        high_value: bool,
+        /* If set, the multisig contract executes CallbackMsg::SigningCompleted on the calling contract once the session
+        reaches quorum, so the caller doesn't need to poll GetMultisig. A failing callback doesn't revert the signature
+        submission that completed the session. */
+        #[serde(default)]
+        callback: bool,
+        /* Signing weight needed to complete the session, for messages that warrant a stricter quorum than the key's.
+        Must be at least the key's threshold and at most its total weight. Defaults to the key's threshold.
+        A pending session for the same key and message is only joined if its threshold is at least this one. */
+        #[serde(default)]
+        threshold: Option<Uint256>,
+    },
+    // Can only be called by the contract that started the session, after the confirmation delay has passed.
+    ConfirmSigningSession {
+        session_id: Uint64,
+    },
+    // Can only be called by governance or the contract that started the session, while the session awaits confirmation.
+    // The session is removed, so it can never be confirmed or signed.
+    CancelSigningSession {
+        session_id: Uint64,
     },
     SubmitSignature {
         session_id: Uint64,
@@ -36,9 +62,18 @@ pub enum ExecuteMsg {
     },
     RegisterWorkerSet {
         worker_set: WorkerSet,
+        /* Hash that signers apply to session messages before signing them, for chains that don't sign the message as is.
+        Can only be set by an authorized contract. The first registration by an authorized contract fixes the scheme,
+        to signing the raw message if not set, and it can't be changed afterwards. */
+        #[serde(default)]
+        digest_scheme: Option<DigestScheme>,
     },
     RegisterPublicKey {
         public_key: PublicKey,
+        /* Signature of the public key by its secret key, required for bls12-381 keys.
+        Signatures of bls12-381 keys are aggregated, which is only safe if every key proved that its owner holds the secret key. */
+        #[serde(default)]
+        proof_of_possession: Option<HexBinary>,
     },
     // Authorizes a contract to call StartSigningSession.
     AuthorizeCaller {
@@ -48,6 +83,36 @@ pub enum ExecuteMsg {
     UnauthorizeCaller {
         contract_address: Addr,
     },
+    /* Can only be called by governance. Sets the number of blocks after completion during which late signatures
+    are still accepted. Late signatures are reported to the rewards contract, but don't change the completed multisig. */
+    UpdateGracePeriod {
+        grace_period: u64,
+    },
+    // Can only be called by governance. Pruning is disabled while no retention policy is set.
+    UpdateRetentionPolicy {
+        retention_policy: Option<RetentionPolicy>,
+    },
+    /* Archives and removes up to `limit` completed sessions (oldest completion first) that the retention policy allows
+    to prune, together with their signatures. Can be called by anyone. */
+    PruneSessions {
+        limit: u32,
+    },
+    /* Can only be called by governance. Blocks new signing sessions, confirmations of high-value sessions
+    and signature submissions with a compromised key. Existing sessions and their signatures are kept. */
+    DisableKey {
+        worker_set_id: String,
+    },
+    // Can only be called by governance. Allows signing sessions with a disabled key again.
+    EnableKey {
+        worker_set_id: String,
+    },
+}
+
+// Sent to the contract that started a signing session with the callback flag set.
+// The receiving contract needs to accept it as part of its own execute message.
+#[cw_serde]
+pub enum CallbackMsg {
+    SigningCompleted { session_id: Uint64 },
 }
 
 #[cw_serde]
@@ -64,6 +129,56 @@ pub enum QueryMsg {
         worker_address: String,
         key_type: KeyType,
     },
+
+    // Returns usage statistics of the key of the given worker set, across all contracts that use it.
+    #[returns(KeyStats)]
+    GetKeyStats { worker_set_id: String },
+
+    // Returns liveness statistics of a signer over the last 30 windows of 14400 blocks, across all keys it participates in.
+    #[returns(SignerLivenessStats)]
+    GetSignerLiveness { signer: String },
+
+    // Returns the archive record of a session that has been pruned.
+    #[returns(ArchivedSession)]
+    GetArchivedSession { session_id: Uint64 },
+
+    // Returns the aggregate of the signatures submitted so far and a bitmap of who submitted them,
+    // or nothing if the session is not signed with a bls12-381 key or has no signatures yet.
+    #[returns(Option<AggregateSignature>)]
+    GetAggregateSignature { session_id: Uint64 },
+
+    // Returns up to `limit` open sessions with an id greater than `start_after`, oldest first, that the participant
+    // can sign but has not signed yet. Sessions awaiting confirmation are not included, as they don't accept signatures yet.
+    // The limit is capped at 100.
+    #[returns(Vec<PendingSession>)]
+    PendingSessions {
+        participant: String,
+        start_after: Option<Uint64>,
+        limit: u32,
+    },
+
+    // Returns up to `limit` sessions with an id greater than `start_after`, in ascending order,
+    // optionally only the ones with the given status. Pruned sessions are not included. The limit is capped at 100.
+    #[returns(Vec<SessionSummary>)]
+    ListSigningSessions {
+        start_after: Option<Uint64>,
+        limit: u32,
+        status_filter: Option<SessionStatus>,
+    },
+
+    // Returns up to `limit` signatures submitted to a session, ordered by signer address,
+    // starting after the signer `start_after`. The limit is capped at 100.
+    #[returns(Vec<SessionSignature>)]
+    ListSessionSignatures {
+        session_id: Uint64,
+        start_after: Option<String>,
+        limit: u32,
+    },
+
+    // Verifies all signatures stored for a session against the session's message and the signers' public keys again,
+    // so integrity can be checked on chain instead of trusting the signatures that were accepted on submission.
+    #[returns(Vec<SignatureVerification>)]
+    VerifySessionSignatures { session_id: Uint64 },
 }
 
 #[cw_serde]
@@ -77,6 +192,64 @@ pub struct Signer {
 #[cw_serde]
 pub struct Multisig {
     pub state: MultisigState,
+    // threshold of the worker set, as verified by the destination chain
     pub quorum: Uint256,
+    // signing weight needed to complete this session, at least the quorum
+    pub session_threshold: Uint256,
     pub signers: Vec<(Signer, Option<Signature>)>,
 }
+
+#[cw_serde]
+pub struct PendingSession {
+    pub session_id: Uint64,
+    pub worker_set_id: String,
+    pub msg: HexBinary,
+}
+
+#[cw_serde]
+pub enum SessionStatus {
+    AwaitingConfirmation,
+    Pending,
+    Completed,
+}
+
+#[cw_serde]
+pub struct SessionSummary {
+    pub session_id: Uint64,
+    pub worker_set_id: String,
+    pub msg: HexBinary,
+    pub state: MultisigState,
+    pub status: SessionStatus,
+}
+
+#[cw_serde]
+pub struct SessionSignature {
+    pub signer: Addr,
+    pub signature: Signature,
+    // submitted after the session was completed, so it's not part of the multisig
+    pub late: bool,
+}
+
+#[cw_serde]
+pub struct SignatureVerification {
+    pub signer: Addr,
+    pub valid: bool,
+}
+
+#[cw_serde]
+pub struct KeyStats {
+    pub sessions_started: u64,
+    pub sessions_completed: u64,
+    pub signatures_collected: u64,
+    pub average_signatures_per_session: Decimal,
+}
+
+#[cw_serde]
+pub struct SignerLivenessStats {
+    pub sessions_eligible: u64,
+    pub sessions_signed: u64,
+    // number of blocks between the start of a session and the signer's signature, averaged over all signed sessions
+    pub average_blocks_to_sign: Decimal,
+    // the statistics roll over, they only cover sessions since this block
+    pub since_block_height: u64,
+}
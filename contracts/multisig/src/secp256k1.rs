@@ -4,12 +4,15 @@ use cosmwasm_crypto::secp256k1_verify;
 use crate::key::Signature;
 use crate::ContractError;
 
+const ECDSA_SIGNATURE_LEN: usize = 64;
+
 pub fn ecdsa_verify(
     msg_hash: &[u8],
     sig: &Signature,
     pub_key: &[u8],
 ) -> Result<bool, ContractError> {
-    secp256k1_verify(msg_hash, sig.as_ref(), pub_key).map_err(|err| {
+    // recoverable signatures carry an additional recovery byte that is not part of the verification
+    secp256k1_verify(msg_hash, &sig.as_ref()[0..ECDSA_SIGNATURE_LEN], pub_key).map_err(|err| {
         ContractError::SignatureVerificationFailed {
             reason: err.to_string(),
         }
@@ -1,10 +1,14 @@
 use std::collections::HashMap;
 
+use connection_router::state::ChainName;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint256, Uint64};
+use cosmwasm_std::{Addr, Deps, HexBinary, Uint256, Uint64};
 
 use crate::{
-    key::{PublicKey, Signature},
+    bls::bls12_381_aggregate,
+    key::Signature,
+    msg::SessionStatus,
+    state::load_digest_scheme,
     types::{MsgToSign, MultisigState},
     worker_set::WorkerSet,
     ContractError,
@@ -16,15 +20,126 @@ pub struct SigningSession {
     pub worker_set_id: String,
     pub msg: MsgToSign,
     pub state: MultisigState,
+    // set for high-value sessions until the initiating contract confirms them
+    #[serde(default)]
+    pub pending_confirmation: Option<PendingConfirmation>,
+    // high-value sessions keep the flag after they were confirmed
+    #[serde(default)]
+    pub high_value: bool,
+    // contract to notify once the session is completed
+    #[serde(default)]
+    pub callback: Option<Addr>,
+    // overrides the worker set threshold for this session, never lower than it
+    #[serde(default)]
+    pub threshold: Option<Uint256>,
+    // block at which signers were asked to sign, i.e. when the session started or was confirmed.
+    // None for sessions that were started before it was recorded, or are still awaiting confirmation
+    #[serde(default)]
+    pub signing_started_at: Option<u64>,
+}
+
+/// Compact record of a pruned signing session, kept for audits
+#[cw_serde]
+pub struct ArchivedSession {
+    pub session_id: Uint64,
+    pub msg: MsgToSign,
+    pub completed_at: u64,
+}
+
+/// Aggregate of the bls12-381 signatures submitted to a session so far
+#[cw_serde]
+pub struct AggregateSignature {
+    pub signature: HexBinary,
+    // bit i (byte i / 8, bit i % 8) is set if the i-th signer of the worker set, ordered by address, has signed
+    pub signers: HexBinary,
+}
+
+impl AggregateSignature {
+    pub fn new(signer_count: usize) -> Self {
+        Self {
+            signature: bls12_381_aggregate([])
+                .expect("aggregating no signatures yields the identity"),
+            signers: HexBinary::from(vec![0u8; (signer_count + 7) / 8]),
+        }
+    }
+
+    pub fn add(self, signer_index: usize, sig: &[u8]) -> Result<Self, ContractError> {
+        let mut signers = self.signers.to_vec();
+        signers[signer_index / 8] |= 1 << (signer_index % 8);
+
+        Ok(Self {
+            signature: bls12_381_aggregate([self.signature.as_slice(), sig])?,
+            signers: HexBinary::from(signers),
+        })
+    }
+
+    pub fn has_signed(&self, signer_index: usize) -> bool {
+        self.signers
+            .get(signer_index / 8)
+            .map_or(false, |byte| byte & (1 << (signer_index % 8)) != 0)
+    }
+}
+
+#[cw_serde]
+pub struct PendingConfirmation {
+    pub initiator: Addr,
+    pub confirmable_at: u64, // block from which the session can be confirmed
+    pub chain_name: ChainName, // needed to notify signers once the session is confirmed
 }
 
 impl SigningSession {
+    pub fn status(&self) -> SessionStatus {
+        match (&self.state, &self.pending_confirmation) {
+            (MultisigState::Completed { .. }, _) => SessionStatus::Completed,
+            (MultisigState::Pending, Some(_)) => SessionStatus::AwaitingConfirmation,
+            (MultisigState::Pending, None) => SessionStatus::Pending,
+        }
+    }
+
     pub fn new(session_id: Uint64, worker_set_id: String, msg: MsgToSign) -> Self {
         Self {
             id: session_id,
             worker_set_id,
             msg,
             state: MultisigState::Pending,
+            pending_confirmation: None,
+            high_value: false,
+            callback: None,
+            threshold: None,
+            signing_started_at: None,
+        }
+    }
+
+    pub fn with_signing_started_at(self, block_height: u64) -> Self {
+        Self {
+            signing_started_at: Some(block_height),
+            ..self
+        }
+    }
+
+    pub fn with_callback(self, callback: Option<Addr>) -> Self {
+        Self { callback, ..self }
+    }
+
+    pub fn with_threshold(self, threshold: Option<Uint256>) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Signing weight needed to complete the session
+    pub fn threshold(&self, worker_set: &WorkerSet) -> Uint256 {
+        self.threshold.unwrap_or(worker_set.threshold)
+    }
+
+    pub fn new_high_value(
+        session_id: Uint64,
+        worker_set_id: String,
+        msg: MsgToSign,
+        pending_confirmation: PendingConfirmation,
+    ) -> Self {
+        Self {
+            pending_confirmation: Some(pending_confirmation),
+            high_value: true,
+            ..Self::new(session_id, worker_set_id, msg)
         }
     }
 
@@ -36,7 +151,7 @@ impl SigningSession {
     ) {
         let weight = signers_weight(signatures, worker_set);
 
-        if self.state == MultisigState::Pending && weight >= worker_set.threshold {
+        if self.state == MultisigState::Pending && weight >= self.threshold(worker_set) {
             self.state = MultisigState::Completed {
                 completed_at: block_height,
             };
@@ -45,13 +160,30 @@ impl SigningSession {
 }
 
 pub fn validate_session_signature(
+    deps: Deps,
     session: &SigningSession,
+    worker_set: &WorkerSet,
     signer: &Addr,
     signature: &Signature,
-    pub_key: &PublicKey,
     grace_period: u64,
     block_height: u64,
 ) -> Result<(), ContractError> {
+    let pub_key = &worker_set
+        .signers
+        .get(signer.as_str())
+        .ok_or_else(|| ContractError::NotAParticipant {
+            session_id: session.id,
+            worker_set_id: session.worker_set_id.clone(),
+            signer: signer.to_string(),
+        })?
+        .pub_key;
+
+    if session.pending_confirmation.is_some() {
+        return Err(ContractError::SigningSessionNotConfirmed {
+            session_id: session.id,
+        });
+    }
+
     if matches!(session.state, MultisigState::Completed { completed_at } if completed_at + grace_period < block_height)
     {
         return Err(ContractError::SigningSessionClosed {
@@ -59,7 +191,20 @@ pub fn validate_session_signature(
         });
     }
 
-    if !signature.verify(&session.msg, pub_key)? {
+    let digest = load_digest_scheme(deps.storage, &session.worker_set_id)?.digest(&session.msg);
+    if !signature.verify(deps.api, &digest, pub_key)? {
+        // a recoverable signature that recovers to a valid key, but not to the signer's key, was either produced
+        // over a different digest or with a different key, so the recovered key is reported to help tell them apart
+        if let Signature::EcdsaRecoverable(sig) = signature {
+            if let Ok(recovered_pub_key) = sig.recover_pub_key(digest.as_ref()) {
+                return Err(ContractError::SignatureKeyMismatch {
+                    session_id: session.id,
+                    signer: signer.into(),
+                    recovered_pub_key,
+                });
+            }
+        }
+
         return Err(ContractError::InvalidSignature {
             session_id: session.id,
             signer: signer.into(),
@@ -84,12 +229,18 @@ fn signers_weight(signatures: &HashMap<String, Signature>, worker_set: &WorkerSe
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{testing::MockStorage, Addr, HexBinary};
+    use axelar_wasm_std::Participant;
+    use cosmwasm_std::{
+        testing::{mock_dependencies, MockStorage},
+        Addr, HexBinary,
+    };
 
     use crate::{
-        key::KeyType,
+        key::{KeyType, PublicKey},
+        state::KEY_DIGEST_SCHEMES,
         test::common::build_worker_set,
-        test::common::{ecdsa_test_data, ed25519_test_data},
+        test::common::{bls12_381_test_data::g2_multiple, ecdsa_test_data, ed25519_test_data},
+        types::DigestScheme,
     };
 
     use super::*;
@@ -188,21 +339,29 @@ mod tests {
     #[test]
     fn success_validation() {
         for config in [ecdsa_setup(), ed25519_setup()] {
+            let deps = mock_dependencies();
             let session = config.session;
             let worker_set = config.worker_set;
             let signer = Addr::unchecked(config.signatures.keys().next().unwrap());
             let signature = config.signatures.values().next().unwrap();
-            let pub_key = &worker_set.signers.get(&signer.to_string()).unwrap().pub_key;
 
-            assert!(
-                validate_session_signature(&session, &signer, signature, pub_key, 0, 0).is_ok()
-            );
+            assert!(validate_session_signature(
+                deps.as_ref(),
+                &session,
+                &worker_set,
+                &signer,
+                signature,
+                0,
+                0
+            )
+            .is_ok());
         }
     }
 
     #[test]
     fn success_validation_grace_period() {
         for config in [ecdsa_setup(), ed25519_setup()] {
+            let deps = mock_dependencies();
             let mut session = config.session;
             let worker_set = config.worker_set;
             let signer = Addr::unchecked(config.signatures.keys().next().unwrap());
@@ -210,14 +369,14 @@ mod tests {
             let completed_at = 12345;
             let grace_period = 10;
             let block_height = completed_at + grace_period; // inclusive
-            let pub_key = &worker_set.signers.get(&signer.to_string()).unwrap().pub_key;
 
             session.state = MultisigState::Completed { completed_at };
             assert!(validate_session_signature(
+                deps.as_ref(),
                 &session,
+                &worker_set,
                 &signer,
                 signature,
-                pub_key,
                 grace_period,
                 block_height
             )
@@ -228,6 +387,7 @@ mod tests {
     #[test]
     fn signing_session_closed_validation() {
         for config in [ecdsa_setup(), ed25519_setup()] {
+            let deps = mock_dependencies();
             let mut session = config.session;
             let worker_set = config.worker_set;
             let signer = Addr::unchecked(config.signatures.keys().next().unwrap());
@@ -235,14 +395,14 @@ mod tests {
             let completed_at = 12345;
             let grace_period = 10;
             let block_height = completed_at + grace_period + 1;
-            let pub_key = &worker_set.signers.get(&signer.to_string()).unwrap().pub_key;
 
             session.state = MultisigState::Completed { completed_at };
             let result = validate_session_signature(
+                deps.as_ref(),
                 &session,
+                &worker_set,
                 &signer,
                 signature,
-                pub_key,
                 grace_period,
                 block_height,
             );
@@ -259,21 +419,30 @@ mod tests {
     #[test]
     fn invalid_signature_validation() {
         for config in [ecdsa_setup(), ed25519_setup()] {
+            let deps = mock_dependencies();
             let session = config.session;
             let worker_set = config.worker_set;
             let signer = Addr::unchecked(config.signatures.keys().next().unwrap());
-            let pub_key = &worker_set.signers.get(&signer.to_string()).unwrap().pub_key;
 
             let sig_bytes = match config.key_type {
                 KeyType::Ecdsa =>   "a58c9543b9df54578ec45838948e19afb1c6e4c86b34d9899b10b44e619ea74e19b457611e41a047030ed233af437d7ecff84de97cb6b3c13d73d22874e03511",
                 KeyType::Ed25519 => "1fe264eb7258d48d8feedea4d237ccb20157fbe5eb412bc971d758d072b036a99b06d20853c1f23cdf82085917e08dda2fcfbb5d4d7ee17d74e4988ae81d0308",
+                KeyType::Bls12_381 => unreachable!(),
             };
 
             let invalid_sig: Signature = (config.key_type, HexBinary::from_hex(sig_bytes).unwrap())
                 .try_into()
                 .unwrap();
 
-            let result = validate_session_signature(&session, &signer, &invalid_sig, pub_key, 0, 0);
+            let result = validate_session_signature(
+                deps.as_ref(),
+                &session,
+                &worker_set,
+                &signer,
+                &invalid_sig,
+                0,
+                0,
+            );
 
             assert_eq!(
                 result.unwrap_err(),
@@ -285,28 +454,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn signature_over_different_digest_validation() {
+        let config = ecdsa_setup();
+        let deps = mock_dependencies();
+        let worker_set = config.worker_set;
+        let signer = Addr::unchecked(config.signatures.keys().next().unwrap());
+
+        // the signature is valid for the test message, but the session expects a different digest
+        let different_digest =
+            HexBinary::from_hex("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90")
+                .unwrap();
+        let session = SigningSession::new(
+            Uint64::one(),
+            config.session.worker_set_id,
+            different_digest.try_into().unwrap(),
+        );
+
+        let mut sig_bytes = ecdsa_test_data::signature().to_vec();
+        sig_bytes.push(0);
+        let recoverable_sig: Signature = (config.key_type, HexBinary::from(sig_bytes))
+            .try_into()
+            .unwrap();
+
+        let result = validate_session_signature(
+            deps.as_ref(),
+            &session,
+            &worker_set,
+            &signer,
+            &recoverable_sig,
+            0,
+            0,
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ContractError::SignatureKeyMismatch {
+                session_id: session.id,
+                signer: signer.into(),
+                recovered_pub_key: HexBinary::from_hex(
+                    "02786493b21b8be9117890eee4fdd1d0b19400ab95e6cb40888d7beabce70b1026"
+                )
+                .unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn validation_with_digest_scheme() {
+        // signed by an ed25519 key over the sha256 hash of the test message
+        let pub_key: PublicKey = (
+            KeyType::Ed25519,
+            HexBinary::from_hex("ea4a6c63e29c520abef5507b132ec5f9954776aebebe7b92421eea691446d22c")
+                .unwrap(),
+        )
+            .try_into()
+            .unwrap();
+        let signature: Signature = (KeyType::Ed25519, HexBinary::from_hex("caa85cbc5b23f3b07d310eb2a9448dd6e64e5b07673d7e5ca1aaa2eace8ba53dec2aa62c574539cf4051f56c0bf8a2052d0195031de1ea57e94d13ad51f2f301").unwrap())
+            .try_into()
+            .unwrap();
+        let session = SigningSession::new(
+            Uint64::one(),
+            "subkey".to_string(),
+            ed25519_test_data::message().try_into().unwrap(),
+        );
+        let signer = Addr::unchecked("signer");
+        let worker_set = WorkerSet::new(
+            vec![(
+                Participant {
+                    address: signer.clone(),
+                    weight: Uint256::one().try_into().unwrap(),
+                },
+                pub_key,
+            )],
+            Uint256::one(),
+            0,
+        );
+
+        let mut deps = mock_dependencies();
+        KEY_DIGEST_SCHEMES
+            .save(deps.as_mut().storage, "subkey", &DigestScheme::Sha256)
+            .unwrap();
+        assert!(validate_session_signature(
+            deps.as_ref(),
+            &session,
+            &worker_set,
+            &signer,
+            &signature,
+            0,
+            0
+        )
+        .is_ok());
+
+        KEY_DIGEST_SCHEMES.remove(deps.as_mut().storage, "subkey");
+        assert_eq!(
+            validate_session_signature(
+                deps.as_ref(),
+                &session,
+                &worker_set,
+                &signer,
+                &signature,
+                0,
+                0
+            )
+            .unwrap_err(),
+            ContractError::InvalidSignature {
+                session_id: session.id,
+                signer: signer.into(),
+            }
+        );
+    }
+
     #[test]
     fn signer_not_a_participant_validation() {
         for config in [ecdsa_setup(), ed25519_setup()] {
             let session = config.session;
             let worker_set = config.worker_set;
             let invalid_participant = Addr::unchecked("not_a_participant".to_string());
+            let signature = config.signatures.values().next().unwrap();
 
-            let result = match worker_set.signers.get(&invalid_participant.to_string()) {
-                Some(signer) => Ok(&signer.pub_key),
-                None => Err(ContractError::NotAParticipant {
-                    session_id: session.id,
-                    signer: invalid_participant.to_string(),
-                }),
-            };
+            let result = validate_session_signature(
+                mock_dependencies().as_ref(),
+                &session,
+                &worker_set,
+                &invalid_participant,
+                signature,
+                0,
+                0,
+            );
 
             assert_eq!(
                 result.unwrap_err(),
                 ContractError::NotAParticipant {
                     session_id: session.id,
+                    worker_set_id: session.worker_set_id,
                     signer: invalid_participant.into()
                 }
             );
         }
     }
+
+    #[test]
+    fn aggregate_signature_tracks_signers() {
+        let aggregate = AggregateSignature::new(10)
+            .add(9, g2_multiple(2).as_slice())
+            .unwrap()
+            .add(0, g2_multiple(3).as_slice())
+            .unwrap();
+
+        assert_eq!(aggregate.signature, g2_multiple(5));
+        assert_eq!(
+            aggregate.signers,
+            HexBinary::from(vec![0b0000_0001, 0b0000_0010])
+        );
+        assert!(aggregate.has_signed(0));
+        assert!(aggregate.has_signed(9));
+        assert!(!aggregate.has_signed(1));
+        assert!(!aggregate.has_signed(16));
+    }
 }
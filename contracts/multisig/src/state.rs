@@ -1,12 +1,15 @@
 use std::collections::HashMap;
 
+use axelar_wasm_std::versioning::StateVersion;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, HexBinary, Order, StdResult, Storage, Uint64};
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 use crate::{
     key::{KeyType, Signature},
-    signing::SigningSession,
+    msg::SessionStatus,
+    signing::{AggregateSignature, ArchivedSession, SigningSession},
+    types::{DigestScheme, RetentionPolicy},
     worker_set::WorkerSet,
     ContractError,
 };
@@ -15,16 +18,87 @@ use crate::{
 pub struct Config {
     pub governance: Addr,
     pub rewards_contract: Addr,
-    pub grace_period: u64, // TODO: add update mechanism to change this after instantiation
+    // number of blocks after completion during which late signatures are still accepted
+    pub grace_period: u64,
+    #[serde(default)]
+    pub confirmation_delay: u64,
+    #[serde(default)]
+    pub retention_policy: Option<RetentionPolicy>,
 }
 
+/// Schema version of each storage namespace this code expects. Bump a version whenever its schema changes
+/// in a way that requires migrating stored data, and migrate the data in the migrate entry point
+pub const STATE_VERSIONS: [StateVersion; 4] = [
+    ("config", 1),
+    ("signer_liveness", 1),
+    ("signing_sessions", 2),
+    ("worker_sets", 1),
+];
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const SIGNING_SESSION_COUNTER: Item<Uint64> = Item::new("signing_session_counter");
-pub const SIGNING_SESSIONS: Map<u64, SigningSession> = Map::new("signing_sessions");
+
+pub struct SigningSessionIndexes<'a> {
+    // open sessions can be found without going through all completed ones
+    pub status: MultiIndex<'a, u8, SigningSession, u64>,
+}
+
+impl<'a> IndexList<SigningSession> for SigningSessionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SigningSession>> + '_> {
+        let v: Vec<&dyn Index<SigningSession>> = vec![&self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+const SIGNING_SESSIONS_PKEY: &str = "signing_sessions";
+
+pub fn signing_sessions<'a>() -> IndexedMap<'a, u64, SigningSession, SigningSessionIndexes<'a>> {
+    IndexedMap::new(
+        SIGNING_SESSIONS_PKEY,
+        SigningSessionIndexes {
+            status: MultiIndex::new(
+                |_pk: &[u8], session: &SigningSession| status_index_key(&session.status()),
+                SIGNING_SESSIONS_PKEY,
+                "signing_sessions__status",
+            ),
+        },
+    )
+}
+
+pub fn status_index_key(status: &SessionStatus) -> u8 {
+    match status {
+        SessionStatus::Pending => 0,
+        SessionStatus::AwaitingConfirmation => 1,
+        SessionStatus::Completed => 2,
+    }
+}
+
+/// Saves all sessions again, so sessions stored before sessions were indexed by status are indexed too
+pub fn index_signing_sessions(store: &mut dyn Storage) -> StdResult<()> {
+    let sessions = signing_sessions()
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (session_id, session) in sessions {
+        signing_sessions().save(store, session_id, &session)?;
+    }
+
+    Ok(())
+}
 
 /// Signatures by session id and signer address
 pub const SIGNATURES: Map<(u64, &str), Signature> = Map::new("signatures");
 
+/// Signers whose signature arrived after the session was completed, by session id and signer address.
+/// Late signatures count as participation, but are left out of the completed multisig so the proof doesn't change
+pub const LATE_SIGNATURES: Map<(u64, &str), ()> = Map::new("late_signatures");
+
+/// Completed sessions by completion height and session id, so the oldest ones can be found for pruning
+pub const COMPLETED_SESSIONS: Map<(u64, u64), ()> = Map::new("completed_sessions");
+pub const SESSION_ARCHIVE: Map<u64, ArchivedSession> = Map::new("session_archive");
+
+/// Running aggregate of the signatures of sessions signed with bls12-381 keys
+pub const AGGREGATE_SIGNATURES: Map<u64, AggregateSignature> = Map::new("aggregate_signatures");
+
 pub fn load_session_signatures(
     store: &dyn Storage,
     session_id: u64,
@@ -56,8 +130,83 @@ pub fn save_signature(
     )
 }
 
+pub fn add_to_aggregate_signature(
+    store: &mut dyn Storage,
+    session_id: u64,
+    worker_set: &WorkerSet,
+    signer: &Addr,
+    signature: &[u8],
+) -> Result<AggregateSignature, ContractError> {
+    let signer_index = worker_set
+        .signers
+        .keys()
+        .position(|address| address == signer.as_str())
+        .expect("violated invariant: signer is not a participant of the worker set");
+
+    let aggregate = AGGREGATE_SIGNATURES
+        .may_load(store, session_id)?
+        .unwrap_or_else(|| AggregateSignature::new(worker_set.signers.len()))
+        .add(signer_index, signature)?;
+    AGGREGATE_SIGNATURES.save(store, session_id, &aggregate)?;
+
+    Ok(aggregate)
+}
+
+/// Replaces a completed session and its signatures with an archive record
+pub fn archive_session(
+    store: &mut dyn Storage,
+    session_id: u64,
+    completed_at: u64,
+) -> Result<ArchivedSession, ContractError> {
+    let session = signing_sessions().load(store, session_id)?;
+
+    let signers: Vec<String> = SIGNATURES
+        .prefix(session_id)
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for signer in signers {
+        SIGNATURES.remove(store, (session_id, &signer));
+        LATE_SIGNATURES.remove(store, (session_id, &signer));
+    }
+    signing_sessions().remove(store, session_id)?;
+    AGGREGATE_SIGNATURES.remove(store, session_id);
+    // entries recorded under a digest scheme that was replaced since are not found, they point to no signatures anyway
+    let digest = load_digest_scheme(store, &session.worker_set_id)?.digest(&session.msg);
+    let signed_digest = (session.worker_set_id.as_str(), digest.as_ref());
+    if SIGNED_DIGEST_SESSION_IDS.may_load(store, signed_digest)? == Some(session.id) {
+        SIGNED_DIGEST_SESSION_IDS.remove(store, signed_digest);
+    }
+    COMPLETED_SESSIONS.remove(store, (completed_at, session_id));
+
+    let archived = ArchivedSession {
+        session_id: session.id,
+        msg: session.msg,
+        completed_at,
+    };
+    SESSION_ARCHIVE.save(store, session_id, &archived)?;
+
+    Ok(archived)
+}
+
 type WorkerSetId = str;
 pub const WORKER_SETS: Map<&WorkerSetId, WorkerSet> = Map::new("worker_sets");
+
+/// Ids of pending sessions by worker set id, message and session kind, so duplicate requests to sign the same message with the same key join the existing session.
+/// Entries are removed once the session completes.
+pub const PENDING_SESSION_IDS: Map<(&WorkerSetId, &[u8], &str), Uint64> =
+    Map::new("pending_session_ids");
+
+/// A request only joins a pending session that is high-value if the request is, and that notifies the same callback on completion
+pub fn pending_session_kind(high_value: bool, callback: Option<&Addr>) -> String {
+    let value = if high_value { "high_value" } else { "regular" };
+    format!("{}/{}", value, callback.map_or("", Addr::as_str))
+}
+
+/// Latest session by worker set id and the digest its signers sign, i.e. the message with the digest scheme of the key applied.
+/// The signatures of that session are reused when the worker set is asked to sign the same digest again, until the session is pruned
+pub const SIGNED_DIGEST_SESSION_IDS: Map<(&WorkerSetId, &[u8]), Uint64> =
+    Map::new("signed_digest_session_ids");
+
 pub fn get_worker_set(
     store: &dyn Storage,
     worker_set_id: &str,
@@ -69,6 +218,139 @@ pub fn get_worker_set(
         })
 }
 
+/// Usage counters of a single key (i.e. worker set), aggregated over all contracts that start signing sessions with it
+#[cw_serde]
+#[derive(Default)]
+pub struct KeyUsage {
+    pub sessions_started: u64,
+    pub sessions_completed: u64,
+    /// Total number of valid signatures submitted, including signatures submitted after completion during the grace period
+    pub signatures_collected: u64,
+}
+
+pub const KEY_USAGE: Map<&WorkerSetId, KeyUsage> = Map::new("key_usage");
+
+/// Number of blocks in a liveness window, about a day with 6 second blocks
+pub const LIVENESS_WINDOW_BLOCKS: u64 = 14_400;
+/// Number of most recent liveness windows that signer liveness is reported over
+pub const LIVENESS_WINDOWS: u64 = 30;
+
+/// Liveness counters of a single signer in one window of LIVENESS_WINDOW_BLOCKS blocks, aggregated over all keys it participates in
+#[cw_serde]
+#[derive(Default)]
+pub struct SignerLiveness {
+    /// Index of the window, i.e. the block height divided by LIVENESS_WINDOW_BLOCKS
+    pub window: u64,
+    /// Number of sessions the signer was asked to sign
+    pub sessions_eligible: u64,
+    /// Number of sessions the signer submitted a valid signature for, including signatures submitted during the grace period
+    pub sessions_signed: u64,
+    /// Sum of the blocks between the start of a session and the signer's signature, over all signed sessions
+    pub total_blocks_to_sign: u64,
+}
+
+/// Liveness counters of each signer in the last LIVENESS_WINDOWS windows it was active in, oldest first.
+/// Older windows are dropped when a new one starts, so the counters roll over
+pub const SIGNER_LIVENESS: Map<&str, Vec<SignerLiveness>> = Map::new("signer_liveness");
+
+/// Updates the counters of the signer in the window of the given block
+pub fn update_signer_liveness(
+    store: &mut dyn Storage,
+    signer: &str,
+    block_height: u64,
+    update: impl FnOnce(SignerLiveness) -> SignerLiveness,
+) -> StdResult<Vec<SignerLiveness>> {
+    let window = block_height / LIVENESS_WINDOW_BLOCKS;
+
+    SIGNER_LIVENESS.update(store, signer, |windows| -> StdResult<_> {
+        let mut windows = windows.unwrap_or_default();
+        match windows.last_mut() {
+            Some(last) if last.window == window => *last = update(last.clone()),
+            _ => windows.push(update(SignerLiveness {
+                window,
+                ..SignerLiveness::default()
+            })),
+        }
+        windows.retain(|liveness| liveness.window + LIVENESS_WINDOWS > window);
+
+        Ok(windows)
+    })
+}
+
+/// Removes the liveness counters of all signers
+pub fn clear_signer_liveness(store: &mut dyn Storage) -> StdResult<()> {
+    // raw keys, so counters stored in an older format don't have to be parsed
+    let signers: Vec<_> = SIGNER_LIVENESS
+        .keys_raw(store, None, None, Order::Ascending)
+        .collect();
+    for signer in signers {
+        SIGNER_LIVENESS.remove(store, &String::from_utf8_lossy(&signer));
+    }
+
+    Ok(())
+}
+
+/// Sums the counters of the signer over the last LIVENESS_WINDOWS windows up to the window of the given block.
+/// The window of the result is the oldest window included
+pub fn load_signer_liveness(
+    store: &dyn Storage,
+    signer: &str,
+    block_height: u64,
+) -> StdResult<SignerLiveness> {
+    let window = block_height / LIVENESS_WINDOW_BLOCKS;
+    let oldest_window = (window + 1).saturating_sub(LIVENESS_WINDOWS);
+
+    Ok(SIGNER_LIVENESS
+        .may_load(store, signer)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|liveness| (oldest_window..=window).contains(&liveness.window))
+        .fold(
+            SignerLiveness {
+                window: oldest_window,
+                ..SignerLiveness::default()
+            },
+            |total, liveness| SignerLiveness {
+                window: oldest_window,
+                sessions_eligible: total.sessions_eligible + liveness.sessions_eligible,
+                sessions_signed: total.sessions_signed + liveness.sessions_signed,
+                total_blocks_to_sign: total.total_blocks_to_sign + liveness.total_blocks_to_sign,
+            },
+        ))
+}
+
+// keys that governance disabled, e.g. because they were compromised. No new signing sessions can be started with them
+pub const DISABLED_KEYS: Map<&WorkerSetId, ()> = Map::new("disabled_keys");
+
+// hash that signers of a key apply to session messages before signing, if it's not the raw message
+pub const KEY_DIGEST_SCHEMES: Map<&WorkerSetId, DigestScheme> = Map::new("key_digest_schemes");
+
+pub fn load_digest_scheme(store: &dyn Storage, worker_set_id: &str) -> StdResult<DigestScheme> {
+    Ok(KEY_DIGEST_SCHEMES
+        .may_load(store, worker_set_id)?
+        .unwrap_or_default())
+}
+
+pub fn ensure_key_enabled(store: &dyn Storage, worker_set_id: &str) -> Result<(), ContractError> {
+    if DISABLED_KEYS.has(store, worker_set_id) {
+        return Err(ContractError::KeyDisabled {
+            worker_set_id: worker_set_id.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn update_key_usage(
+    store: &mut dyn Storage,
+    worker_set_id: &str,
+    update: impl FnOnce(KeyUsage) -> KeyUsage,
+) -> StdResult<KeyUsage> {
+    KEY_USAGE.update(store, worker_set_id, |usage| -> StdResult<KeyUsage> {
+        Ok(update(usage.unwrap_or_default()))
+    })
+}
+
 // key type is part of the key so signers can register multiple keys with different types
 pub const PUB_KEYS: Map<(Addr, KeyType), HexBinary> = Map::new("registered_pub_keys");
 
@@ -91,6 +91,40 @@ pub mod ed25519_test_data {
     }
 }
 
+pub mod bls12_381_test_data {
+    use crate::bls::SecretKey;
+
+    use super::*;
+
+    pub fn secret_key(signer_index: u8) -> SecretKey {
+        SecretKey::from_seed(&[signer_index; 64])
+    }
+
+    /// Signers with distinct keys, so their signatures of `ecdsa_test_data::message()` aggregate to a valid signature
+    /// of the sum of their public keys
+    pub fn signers() -> Vec<TestSigner> {
+        (1..=3)
+            .map(|i| TestSigner {
+                address: Addr::unchecked(format!("signer{}", i)),
+                pub_key: secret_key(i).pub_key(),
+                signature: secret_key(i).sign(super::ecdsa_test_data::message().as_slice()),
+            })
+            .collect()
+    }
+
+    /// Compressed multiples of the G2 generator, so aggregates can be checked without a private key
+    pub fn g2_multiple(n: u8) -> HexBinary {
+        let hex = match n {
+            2 => "aa4edef9c1ed7f729f520e47730a124fd70662a904ba1074728114d1031e1572c6c886f6b57ec72a6178288c47c335771638533957d540a9d2370f17cc7ed5863bc0b995b8825e0ee1ea1e1e4d00dbae81f14b0bf3611b78c952aacab827a053",
+            3 => "89380275bbc8e5dcea7dc4dd7e0550ff2ac480905396eda55062650f8d251c96eb480673937cc6d9d6a44aaa56ca66dc122915c824a0857e2ee414a3dccb23ae691ae54329781315a0c75df1c04d6d7a50a030fc866f09d516020ef82324afae",
+            5 => "80fb837804dba8213329db46608b6c121d973363c1234a86dd183baff112709cf97096c5e9a1a770ee9d7dc641a894d60411a5de6730ffece671a9f21d65028cc0f1102378de124562cb1ff49db6f004fcd14d683024b0548eff3d1468df2688",
+            _ => unimplemented!("no test data for this multiple"),
+        };
+
+        HexBinary::from_hex(hex).unwrap()
+    }
+}
+
 pub fn build_worker_set(key_type: KeyType, signers: &Vec<TestSigner>) -> WorkerSet {
     let mut total_weight = Uint256::zero();
     let participants = signers
@@ -1,5 +1,7 @@
+use blake2::{digest::consts::U32, Blake2b};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::HexBinary;
+use sha3::{Digest, Keccak256};
 
 use crate::ContractError;
 
@@ -24,6 +26,32 @@ impl MsgToSign {
     }
 }
 
+/// Hash that signers of a key apply to the message of a session before signing it,
+/// so signatures of chains that don't sign the message as is can be verified on-chain
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum DigestScheme {
+    /// The message is signed as is
+    #[default]
+    Raw,
+    Keccak256,
+    Sha256,
+    Blake2b256,
+}
+
+impl DigestScheme {
+    pub fn digest(&self, msg: &MsgToSign) -> MsgToSign {
+        let digest: Vec<u8> = match self {
+            DigestScheme::Raw => return msg.clone(),
+            DigestScheme::Keccak256 => Keccak256::digest(msg).to_vec(),
+            DigestScheme::Sha256 => <sha2::Sha256 as sha2::Digest>::digest(msg.as_ref()).to_vec(),
+            DigestScheme::Blake2b256 => Blake2b::<U32>::digest(msg).to_vec(),
+        };
+
+        MsgToSign::unchecked(digest.into())
+    }
+}
+
 #[cw_serde]
 pub enum MultisigState {
     Pending,
@@ -32,6 +60,35 @@ pub enum MultisigState {
     },
 }
 
+/// Determines which completed signing sessions can be pruned.
+/// Sessions are never pruned while they can still accept signatures during the grace period.
+#[cw_serde]
+pub enum RetentionPolicy {
+    /// Prune sessions completed more than the given number of blocks ago
+    MaxAge { blocks: u64 },
+    /// Retain only the given number of most recently started sessions
+    MaxCount { sessions: u64 },
+}
+
+impl RetentionPolicy {
+    pub fn allows_pruning(
+        &self,
+        session_id: u64,
+        completed_at: u64,
+        latest_session_id: u64,
+        block_height: u64,
+    ) -> bool {
+        match self {
+            RetentionPolicy::MaxAge { blocks } => {
+                completed_at.saturating_add(*blocks) < block_height
+            }
+            RetentionPolicy::MaxCount { sessions } => {
+                session_id.saturating_add(*sessions) <= latest_session_id
+            }
+        }
+    }
+}
+
 const MESSAGE_HASH_LEN: usize = 32;
 
 impl TryFrom<HexBinary> for MsgToSign {
@@ -61,6 +118,48 @@ mod tests {
         assert_eq!(HexBinary::from(message), hex);
     }
 
+    #[test]
+    fn digest_schemes() {
+        let msg = MsgToSign::unchecked(HexBinary::from([0u8; 32]));
+
+        let digests = [
+            (
+                DigestScheme::Raw,
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                DigestScheme::Keccak256,
+                "290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563",
+            ),
+            (
+                DigestScheme::Sha256,
+                "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925",
+            ),
+            (
+                DigestScheme::Blake2b256,
+                "89eb0d6a8a691dae2cd15ed0369931ce0a949ecafa5c3f93f8121833646e15c3",
+            ),
+        ];
+
+        for (scheme, digest) in digests {
+            assert_eq!(
+                scheme.digest(&msg),
+                MsgToSign::unchecked(HexBinary::from_hex(digest).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn retention_policy_allows_pruning() {
+        let max_age = RetentionPolicy::MaxAge { blocks: 10 };
+        assert!(!max_age.allows_pruning(1, 100, 1, 110));
+        assert!(max_age.allows_pruning(1, 100, 1, 111));
+
+        let max_count = RetentionPolicy::MaxCount { sessions: 3 };
+        assert!(!max_count.allows_pruning(2, 100, 4, 1000));
+        assert!(max_count.allows_pruning(1, 100, 4, 100));
+    }
+
     #[test]
     fn test_try_from_hexbinary_to_message_fails() {
         let hex = HexBinary::from_hex("283786d844a7c4d1d424837074d0c8ec71becdcba4dd42b5307cb543a0e2c8b81c10ad541defd5ce84d2a608fc454827d0b65b4865c8192a2ea1736a5c4b72021b").unwrap();
@@ -56,6 +56,23 @@ impl WorkerSet {
         self.hash().to_hex()
     }
 
+    /// Returns the signers as (address, weight) pairs sorted by address, where `to_address` derives the address
+    /// a destination chain knows a signer by from its public key. This is the canonical operator order that encoders
+    /// must use, the same one `axelar_wasm_std::operators::Operators` hashes the operators in.
+    pub fn operators<E>(
+        &self,
+        to_address: impl Fn(&PublicKey) -> Result<HexBinary, E>,
+    ) -> Result<Vec<(HexBinary, Uint256)>, E> {
+        let mut operators = self
+            .signers
+            .values()
+            .map(|signer| to_address(&signer.pub_key).map(|address| (address, signer.weight)))
+            .collect::<Result<Vec<_>, E>>()?;
+        operators.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(operators)
+    }
+
     pub fn get_pub_keys(&self) -> HashMap<String, PublicKey> {
         self.signers
             .iter()
@@ -63,3 +80,26 @@ impl WorkerSet {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::{
+        key::KeyType,
+        test::common::{build_worker_set, ecdsa_test_data},
+    };
+
+    #[test]
+    fn worker_set_operators_sorted_by_address() {
+        let worker_set = build_worker_set(KeyType::Ecdsa, &ecdsa_test_data::signers());
+
+        let operators = worker_set
+            .operators(|pub_key| Ok::<_, Infallible>(pub_key.clone().into()))
+            .unwrap();
+
+        assert_eq!(operators.len(), worker_set.signers.len());
+        // the test signers share a key, so neighbouring operators may be equal
+        assert!(operators.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    }
+}
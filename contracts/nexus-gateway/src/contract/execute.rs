@@ -133,6 +133,7 @@ mod test {
                 .unwrap()
                 .try_into()
                 .unwrap(),
+                token: None,
                 source_tx_id: vec![0x2f, 0xe4].try_into().unwrap(),
                 source_tx_index: 100,
             },
@@ -147,6 +148,7 @@ mod test {
                 .unwrap()
                 .try_into()
                 .unwrap(),
+                token: None,
                 source_tx_id: vec![0x23, 0xf4].try_into().unwrap(),
                 source_tx_index: 1000,
             },
@@ -248,6 +250,7 @@ mod test {
                 .unwrap()
                 .try_into()
                 .unwrap(),
+                token: None,
             },
             connection_router::Message {
                 cc_id: CrossChainId {
@@ -263,6 +266,7 @@ mod test {
                 .unwrap()
                 .try_into()
                 .unwrap(),
+                token: None,
             },
         ];
         let res = contract.route_to_nexus(Addr::unchecked("router"), msgs);
@@ -309,6 +313,7 @@ mod test {
                 .unwrap()
                 .try_into()
                 .unwrap(),
+                token: None,
             },
             connection_router::Message {
                 cc_id: CrossChainId {
@@ -324,6 +329,7 @@ mod test {
                 .unwrap()
                 .try_into()
                 .unwrap(),
+                token: None,
             },
         ];
         let res = contract.route_to_nexus(Addr::unchecked("router"), msgs);
@@ -1,5 +1,5 @@
 use axelar_wasm_std::nonempty;
-use connection_router::state::{Address, ChainName, CrossChainId, ID_SEPARATOR};
+use connection_router::state::{Address, ChainName, CrossChainId, TokenTransfer, ID_SEPARATOR};
 use cosmwasm_std::{CosmosMsg, CustomMsg};
 use error_stack::{Result, ResultExt};
 use hex::{FromHex, ToHex};
@@ -21,6 +21,8 @@ pub struct Message {
     pub payload_hash: [u8; 32],
     pub source_tx_id: nonempty::Vec<u8>,
     pub source_tx_index: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<TokenTransfer>,
 }
 
 impl CustomMsg for Message {}
@@ -59,6 +61,7 @@ impl From<connection_router::Message> for Message {
             payload_hash: msg.payload_hash,
             source_tx_id,
             source_tx_index,
+            token: msg.token,
         }
     }
 }
@@ -80,6 +83,7 @@ impl From<Message> for connection_router::Message {
             destination_chain: msg.destination_chain,
             destination_address: msg.destination_address,
             payload_hash: msg.payload_hash,
+            token: msg.token,
         }
     }
 }
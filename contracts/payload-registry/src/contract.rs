@@ -0,0 +1,271 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{Config, CONFIG},
+};
+
+mod execute;
+mod query;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            payload_ttl: msg.payload_ttl,
+        },
+    )?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    match msg {
+        ExecuteMsg::StorePayload {
+            payload,
+            message_ids,
+        } => execute::store_payload(deps, &env.block, info.sender, payload, message_ids.into()),
+        ExecuteMsg::ReleasePayload {
+            payload_hash,
+            message_ids,
+        } => execute::release_payload(deps, info.sender, payload_hash, message_ids.into()),
+        ExecuteMsg::PruneExpired { payload_hashes } => {
+            execute::prune_expired(deps, &env.block, payload_hashes)
+        }
+    }
+    .map_err(axelar_wasm_std::ContractError::from)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> Result<Binary, axelar_wasm_std::ContractError> {
+    match msg {
+        QueryMsg::GetPayload { payload_hash } => {
+            to_binary(&query::get_payload(deps, &env.block, payload_hash)?)
+                .map_err(axelar_wasm_std::ContractError::from)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axelar_wasm_std::nonempty;
+    use connection_router::state::CrossChainId;
+    use cosmwasm_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        HexBinary, OwnedDeps,
+    };
+
+    use crate::{error::ContractError, msg::PayloadResponse, state::Payload};
+
+    use super::*;
+
+    const SENDER: &str = "gateway";
+    const PAYLOAD_TTL: u64 = 100;
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("instantiator", &[]),
+            InstantiateMsg {
+                payload_ttl: PAYLOAD_TTL.try_into().unwrap(),
+            },
+        )
+        .unwrap();
+        deps
+    }
+
+    fn message_ids(chains: &[&str]) -> nonempty::Vec<CrossChainId> {
+        chains
+            .iter()
+            .map(|chain| CrossChainId {
+                chain: chain.parse().unwrap(),
+                id: "0x88d7956fd7b6fcec846548d83bd25727f2585b4be3add21438ae9fbb34625924:0"
+                    .parse()
+                    .unwrap(),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+
+    fn payload() -> HexBinary {
+        HexBinary::from_hex("deadbeef").unwrap()
+    }
+
+    fn payload_hash() -> HexBinary {
+        HexBinary::from(Payload::hash(&payload()).as_slice())
+    }
+
+    fn env_at(height: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height = height;
+        env
+    }
+
+    fn store_payload(
+        deps: DepsMut,
+        sender: &str,
+        chains: &[&str],
+        height: u64,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        execute(
+            deps,
+            env_at(height),
+            mock_info(sender, &[]),
+            ExecuteMsg::StorePayload {
+                payload: payload(),
+                message_ids: message_ids(chains),
+            },
+        )
+    }
+
+    fn release_payload(
+        deps: DepsMut,
+        sender: &str,
+        chains: &[&str],
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(sender, &[]),
+            ExecuteMsg::ReleasePayload {
+                payload_hash: payload_hash(),
+                message_ids: message_ids(chains),
+            },
+        )
+    }
+
+    fn query_payload(
+        deps: Deps,
+        height: u64,
+    ) -> Result<PayloadResponse, axelar_wasm_std::ContractError> {
+        query(
+            deps,
+            env_at(height),
+            QueryMsg::GetPayload {
+                payload_hash: payload_hash(),
+            },
+        )
+        .map(|res| from_binary(&res).unwrap())
+    }
+
+    #[test]
+    fn store_payload_once_for_multiple_messages() {
+        let mut deps = setup();
+        let height = mock_env().block.height;
+
+        store_payload(deps.as_mut(), SENDER, &["ethereum", "polygon"], height).unwrap();
+        store_payload(deps.as_mut(), "other gateway", &["avalanche"], height).unwrap();
+        // storing the same reference again does not increase the ref count
+        store_payload(deps.as_mut(), SENDER, &["polygon"], height).unwrap();
+
+        assert_eq!(
+            query_payload(deps.as_ref(), height).unwrap(),
+            PayloadResponse {
+                payload: payload(),
+                ref_count: 3,
+                expires_at: height + PAYLOAD_TTL,
+            }
+        );
+    }
+
+    #[test]
+    fn release_payload_deletes_unreferenced_payload() {
+        let mut deps = setup();
+        let height = mock_env().block.height;
+
+        store_payload(deps.as_mut(), SENDER, &["ethereum", "polygon"], height).unwrap();
+
+        release_payload(deps.as_mut(), SENDER, &["ethereum"]).unwrap();
+        assert_eq!(query_payload(deps.as_ref(), height).unwrap().ref_count, 1);
+
+        release_payload(deps.as_mut(), SENDER, &["polygon"]).unwrap();
+        assert_eq!(
+            query_payload(deps.as_ref(), height)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::PayloadNotFound).to_string()
+        );
+    }
+
+    #[test]
+    fn release_payload_referenced_by_other_sender() {
+        let mut deps = setup();
+        let height = mock_env().block.height;
+
+        store_payload(deps.as_mut(), SENDER, &["ethereum"], height).unwrap();
+
+        let err = release_payload(deps.as_mut(), "other gateway", &["ethereum"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::ReferenceNotFound {
+                message_id: message_ids(&["ethereum"]).as_ref()[0].to_string()
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn prune_expired_payload() {
+        let mut deps = setup();
+        let height = mock_env().block.height;
+
+        store_payload(deps.as_mut(), SENDER, &["ethereum"], height).unwrap();
+
+        let prune = |deps: DepsMut, height: u64| {
+            execute(
+                deps,
+                env_at(height),
+                mock_info("anyone", &[]),
+                ExecuteMsg::PruneExpired {
+                    payload_hashes: vec![payload_hash()],
+                },
+            )
+        };
+
+        let err = prune(deps.as_mut(), height + PAYLOAD_TTL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::PayloadNotExpired).to_string()
+        );
+
+        assert!(query_payload(deps.as_ref(), height + PAYLOAD_TTL + 1).is_err());
+        prune(deps.as_mut(), height + PAYLOAD_TTL + 1).unwrap();
+
+        // references are removed together with the payload, so storing it again starts from scratch
+        store_payload(
+            deps.as_mut(),
+            SENDER,
+            &["ethereum"],
+            height + PAYLOAD_TTL + 1,
+        )
+        .unwrap();
+        assert_eq!(
+            query_payload(deps.as_ref(), height + PAYLOAD_TTL + 1)
+                .unwrap()
+                .ref_count,
+            1
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use axelar_wasm_std::hash::Hash;
+use connection_router::state::CrossChainId;
+use cosmwasm_std::{Addr, BlockInfo, DepsMut, HexBinary, Response};
+use error_stack::{Result, ResultExt};
+
+use crate::{
+    error::ContractError,
+    events::PayloadRegistryEvent,
+    state::{self, Payload},
+};
+
+pub fn store_payload(
+    deps: DepsMut,
+    block: &BlockInfo,
+    sender: Addr,
+    data: HexBinary,
+    message_ids: Vec<CrossChainId>,
+) -> Result<Response, ContractError> {
+    let config = state::load_config(deps.storage);
+    let hash = Payload::hash(&data);
+
+    let mut payload = state::load_payload(deps.storage, &hash)?.unwrap_or(Payload {
+        data,
+        ref_count: 0,
+        expires_at: 0,
+    });
+
+    for message_id in message_ids {
+        if !state::has_reference(deps.storage, &hash, &sender, message_id.clone()) {
+            state::save_reference(deps.storage, &hash, &sender, message_id)?;
+            payload.ref_count += 1;
+        }
+    }
+    payload.expires_at = block.height + u64::from(config.payload_ttl);
+    state::save_payload(deps.storage, &hash, &payload)?;
+
+    Ok(Response::new().add_event(
+        PayloadRegistryEvent::PayloadStored {
+            payload_hash: hash.as_slice().into(),
+            ref_count: payload.ref_count,
+            expires_at: payload.expires_at,
+        }
+        .into(),
+    ))
+}
+
+pub fn release_payload(
+    deps: DepsMut,
+    sender: Addr,
+    payload_hash: HexBinary,
+    message_ids: Vec<CrossChainId>,
+) -> Result<Response, ContractError> {
+    let hash = parse_payload_hash(&payload_hash)?;
+    let mut payload =
+        state::load_payload(deps.storage, &hash)?.ok_or(ContractError::PayloadNotFound)?;
+
+    for message_id in message_ids {
+        if !state::has_reference(deps.storage, &hash, &sender, message_id.clone()) {
+            return Err(ContractError::ReferenceNotFound {
+                message_id: message_id.to_string(),
+            }
+            .into());
+        }
+        state::remove_reference(deps.storage, &hash, &sender, message_id);
+        payload.ref_count -= 1;
+    }
+
+    if payload.ref_count > 0 {
+        state::save_payload(deps.storage, &hash, &payload)?;
+        return Ok(Response::new());
+    }
+
+    state::remove_payload(deps.storage, &hash)?;
+    Ok(Response::new().add_event(PayloadRegistryEvent::PayloadDeleted { payload_hash }.into()))
+}
+
+pub fn prune_expired(
+    deps: DepsMut,
+    block: &BlockInfo,
+    payload_hashes: Vec<HexBinary>,
+) -> Result<Response, ContractError> {
+    let mut events = vec![];
+    for payload_hash in payload_hashes {
+        let hash = parse_payload_hash(&payload_hash)?;
+        let payload =
+            state::load_payload(deps.storage, &hash)?.ok_or(ContractError::PayloadNotFound)?;
+        if !payload.is_expired(block.height) {
+            return Err(ContractError::PayloadNotExpired.into());
+        }
+
+        state::remove_payload(deps.storage, &hash)?;
+        events.push(PayloadRegistryEvent::PayloadDeleted { payload_hash }.into());
+    }
+
+    Ok(Response::new().add_events(events))
+}
+
+pub(crate) fn parse_payload_hash(payload_hash: &HexBinary) -> Result<Hash, ContractError> {
+    Hash::try_from(payload_hash.as_slice()).change_context(ContractError::InvalidPayloadHash)
+}
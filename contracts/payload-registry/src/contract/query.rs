@@ -0,0 +1,23 @@
+use cosmwasm_std::{BlockInfo, Deps, HexBinary};
+use error_stack::Result;
+
+use crate::{error::ContractError, msg::PayloadResponse, state};
+
+use super::execute::parse_payload_hash;
+
+pub fn get_payload(
+    deps: Deps,
+    block: &BlockInfo,
+    payload_hash: HexBinary,
+) -> Result<PayloadResponse, ContractError> {
+    let hash = parse_payload_hash(&payload_hash)?;
+    let payload = state::load_payload(deps.storage, &hash)?
+        .filter(|payload| !payload.is_expired(block.height))
+        .ok_or(ContractError::PayloadNotFound)?;
+
+    Ok(PayloadResponse {
+        payload: payload.data,
+        ref_count: payload.ref_count,
+        expires_at: payload.expires_at,
+    })
+}
@@ -0,0 +1,29 @@
+use axelar_wasm_std_derive::IntoContractError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, IntoContractError)]
+pub enum ContractError {
+    #[error("invalid payload hash")]
+    InvalidPayloadHash,
+
+    #[error("payload not found")]
+    PayloadNotFound,
+
+    #[error("payload has not expired yet")]
+    PayloadNotExpired,
+
+    #[error("message {message_id} does not reference the payload")]
+    ReferenceNotFound { message_id: String },
+
+    #[error("could not save payload")]
+    SavePayload,
+
+    #[error("could not load payload")]
+    LoadPayload,
+
+    #[error("could not save payload reference")]
+    SaveReference,
+
+    #[error("could not load payload reference")]
+    LoadReference,
+}
@@ -0,0 +1,30 @@
+use cosmwasm_std::{Event, HexBinary};
+
+pub enum PayloadRegistryEvent {
+    PayloadStored {
+        payload_hash: HexBinary,
+        ref_count: u64,
+        expires_at: u64,
+    },
+    PayloadDeleted {
+        payload_hash: HexBinary,
+    },
+}
+
+impl From<PayloadRegistryEvent> for Event {
+    fn from(other: PayloadRegistryEvent) -> Self {
+        match other {
+            PayloadRegistryEvent::PayloadStored {
+                payload_hash,
+                ref_count,
+                expires_at,
+            } => Event::new("payload_stored")
+                .add_attribute("payload_hash", payload_hash.to_hex())
+                .add_attribute("ref_count", ref_count.to_string())
+                .add_attribute("expires_at", expires_at.to_string()),
+            PayloadRegistryEvent::PayloadDeleted { payload_hash } => {
+                Event::new("payload_deleted").add_attribute("payload_hash", payload_hash.to_hex())
+            }
+        }
+    }
+}
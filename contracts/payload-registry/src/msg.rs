@@ -0,0 +1,49 @@
+use axelar_wasm_std::nonempty;
+use connection_router::state::CrossChainId;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::HexBinary;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Number of blocks a payload is kept after it was last stored. Expired payloads can be pruned by anyone,
+    /// even if they are still referenced.
+    pub payload_ttl: nonempty::Uint64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    // Permissionless
+    // Stores the payload under its keccak256 hash if it is not stored yet, and adds a reference to it for each of the given messages.
+    // Storing a payload again refreshes its expiry. References are owned by the sender, so only the sender can release them.
+    StorePayload {
+        payload: HexBinary,
+        message_ids: nonempty::Vec<CrossChainId>,
+    },
+
+    // Releases the sender's references to the payload for the given messages. The payload is deleted once no references are left.
+    ReleasePayload {
+        payload_hash: HexBinary,
+        message_ids: nonempty::Vec<CrossChainId>,
+    },
+
+    // Permissionless
+    // Deletes the given expired payloads together with all their references.
+    PruneExpired {
+        payload_hashes: Vec<HexBinary>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    // Expired payloads are treated as not found
+    #[returns(PayloadResponse)]
+    GetPayload { payload_hash: HexBinary },
+}
+
+#[cw_serde]
+pub struct PayloadResponse {
+    pub payload: HexBinary,
+    pub ref_count: u64,
+    pub expires_at: u64,
+}
@@ -0,0 +1,105 @@
+use axelar_wasm_std::{hash::Hash, nonempty};
+use connection_router::state::CrossChainId;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, HexBinary, Order, Storage};
+use cw_storage_plus::{Item, Map};
+use error_stack::{Result, ResultExt};
+use sha3::{Digest, Keccak256};
+
+use crate::error::ContractError;
+
+#[cw_serde]
+pub struct Config {
+    pub payload_ttl: nonempty::Uint64,
+}
+
+#[cw_serde]
+pub struct Payload {
+    pub data: HexBinary,
+    /// Number of messages referencing the payload
+    pub ref_count: u64,
+    /// Block height after which the payload can be pruned, regardless of its references
+    pub expires_at: u64,
+}
+
+impl Payload {
+    pub fn hash(data: &HexBinary) -> Hash {
+        Keccak256::digest(data.as_slice()).into()
+    }
+
+    pub fn is_expired(&self, block_height: u64) -> bool {
+        block_height > self.expires_at
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+const PAYLOADS: Map<&[u8], Payload> = Map::new("payloads");
+// References are keyed by (payload hash, owner, message id), so payloads can be shared by messages of different senders
+const REFERENCES: Map<(&[u8], &Addr, CrossChainId), ()> = Map::new("references");
+
+pub fn load_config(storage: &dyn Storage) -> Config {
+    CONFIG
+        .load(storage)
+        .expect("config should be set during contract instantiation")
+}
+
+pub fn load_payload(storage: &dyn Storage, hash: &Hash) -> Result<Option<Payload>, ContractError> {
+    PAYLOADS
+        .may_load(storage, hash.as_slice())
+        .change_context(ContractError::LoadPayload)
+}
+
+pub fn save_payload(
+    storage: &mut dyn Storage,
+    hash: &Hash,
+    payload: &Payload,
+) -> Result<(), ContractError> {
+    PAYLOADS
+        .save(storage, hash.as_slice(), payload)
+        .change_context(ContractError::SavePayload)
+}
+
+/// Removes the payload and all references to it
+pub fn remove_payload(storage: &mut dyn Storage, hash: &Hash) -> Result<(), ContractError> {
+    let references = REFERENCES
+        .sub_prefix(hash.as_slice())
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .change_context(ContractError::LoadReference)?;
+
+    for (owner, message_id) in references {
+        REFERENCES.remove(storage, (hash.as_slice(), &owner, message_id));
+    }
+    PAYLOADS.remove(storage, hash.as_slice());
+
+    Ok(())
+}
+
+pub fn has_reference(
+    storage: &dyn Storage,
+    hash: &Hash,
+    owner: &Addr,
+    message_id: CrossChainId,
+) -> bool {
+    REFERENCES.has(storage, (hash.as_slice(), owner, message_id))
+}
+
+pub fn save_reference(
+    storage: &mut dyn Storage,
+    hash: &Hash,
+    owner: &Addr,
+    message_id: CrossChainId,
+) -> Result<(), ContractError> {
+    REFERENCES
+        .save(storage, (hash.as_slice(), owner, message_id), &())
+        .change_context(ContractError::SaveReference)
+}
+
+pub fn remove_reference(
+    storage: &mut dyn Storage,
+    hash: &Hash,
+    owner: &Addr,
+    message_id: CrossChainId,
+) {
+    REFERENCES.remove(storage, (hash.as_slice(), owner, message_id))
+}
@@ -1,18 +1,29 @@
 use crate::{
     contract::execute::Contract,
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg},
-    state::{Config, Epoch, StoredParams, CONFIG, PARAMS},
+    events::Event,
+    ics20,
+    msg::{ExecuteMsg, IbcLifecycleComplete, IbcPayout, InstantiateMsg, QueryMsg, SudoMsg},
+    state::{self, Config, Epoch, StoredParams, CONFIG, PARAMS, STATE_VERSIONS},
 };
-use axelar_wasm_std::nonempty;
+use axelar_wasm_std::{ensure_state_versions, nonempty, versioning};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{BankMsg, Coin, DepsMut, Env, MessageInfo, Response};
-use error_stack::ResultExt;
-
-use itertools::Itertools;
+use cosmwasm_std::{
+    to_binary, Binary, ChannelResponse, Deps, DepsMut, Empty, Env, IbcQuery, MessageInfo, Reply,
+    Response, Storage, SubMsgResult, Uint128,
+};
+use error_stack::{Report, ResultExt};
+use std::collections::HashMap;
 
 mod execute;
+mod query;
+
+pub const PAYOUT_HOOK_REPLY_ID: u64 = 1;
+pub const IBC_TRANSFER_REPLY_ID: u64 = 2;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -38,42 +49,109 @@ pub fn instantiate(
             last_updated: Epoch {
                 epoch_num: 0,
                 block_height_started: env.block.height,
+                time_started: env.block.time,
             },
         },
     )?;
 
+    versioning::init_versions(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &STATE_VERSIONS,
+    )?;
+
     Ok(Response::new())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(
+pub fn migrate(
     deps: DepsMut,
+    _env: Env,
+    _msg: Empty,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    versioning::migrate_state(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        &STATE_VERSIONS,
+        migrate_namespace,
+    )?;
+
+    Ok(Response::new())
+}
+
+/// Brings the stored data of a namespace from its stored schema version to the one in STATE_VERSIONS
+fn migrate_namespace(
+    storage: &mut dyn Storage,
+    namespace: &str,
+    stored: Option<u64>,
+) -> Result<(), axelar_wasm_std::ContractError> {
+    match (namespace, stored) {
+        // pools used to be created implicitly, so pools of earlier versions must be registered to keep recording participation
+        ("pools", None | Some(1)) => {
+            state::register_existing_pools(storage).map_err(axelar_wasm_std::ContractError::from)
+        }
+        // state written before versions were recorded is still readable thanks to serde defaults
+        (_, None) => Ok(()),
+        (_, Some(stored)) => Err(Report::new(versioning::Error::MissingMigration {
+            namespace: namespace.to_string(),
+            stored,
+        })
+        .into()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, axelar_wasm_std::ContractError> {
+    ensure_state_versions!(deps.storage, &STATE_VERSIONS);
+
+    let epoch_transition = Contract::new(deps.branch())
+        .observe_epoch(&env.block)
+        .map_err(axelar_wasm_std::ContractError::from)?;
+
     match msg {
         ExecuteMsg::RecordParticipation {
             event_id,
             worker_address,
+            late,
         } => {
             let worker_address = deps.api.addr_validate(&worker_address)?;
             Contract::new(deps)
-                .record_participation(event_id, worker_address, info.sender, env.block.height)
+                .record_participation(event_id, worker_address, info.sender, late, &env.block)
                 .map_err(axelar_wasm_std::ContractError::from)?;
 
             Ok(Response::new())
         }
+        ExecuteMsg::PenalizeWorker {
+            event_id,
+            worker_address,
+        } => {
+            let worker_address = deps.api.addr_validate(&worker_address)?;
+            Contract::new(deps)
+                .penalize_worker(event_id, worker_address, info.sender)
+                .map_err(axelar_wasm_std::ContractError::from)?;
+
+            Ok(Response::new())
+        }
+        ExecuteMsg::CreatePool {
+            contract_address,
+            metadata,
+        } => {
+            let contract_address = deps.api.addr_validate(&contract_address)?;
+            Contract::new(deps).create_pool(contract_address, metadata, info.sender)?;
+
+            Ok(Response::new())
+        }
         ExecuteMsg::AddRewards { contract_address } => {
             let contract_address = deps.api.addr_validate(&contract_address)?;
             let mut contract = Contract::new(deps);
-            let amount = info
-                .funds
-                .iter()
-                .find(|coin| coin.denom == contract.config.rewards_denom)
-                .filter(|_| info.funds.len() == 1) // filter here to make sure expected denom is the only one attached to this message, and other funds aren't silently swallowed
-                .ok_or(ContractError::WrongDenom)?
-                .amount;
+            let amount = attached_rewards(&info, &contract.config.rewards_denom)?;
 
             contract.add_rewards(
                 contract_address,
@@ -82,6 +160,34 @@ pub fn execute(
 
             Ok(Response::new())
         }
+        ExecuteMsg::AddRewardsBatch { pools } => {
+            let pools = pools
+                .into_iter()
+                .map(|(contract_address, amount)| {
+                    deps.api
+                        .addr_validate(&contract_address)
+                        .map(|contract_address| (contract_address, amount))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut contract = Contract::new(deps);
+            let amount = attached_rewards(&info, &contract.config.rewards_denom)?;
+
+            let total = pools
+                .iter()
+                .try_fold(Uint128::zero(), |total, (_, amount)| {
+                    total.checked_add(Uint128::from(*amount))
+                })
+                .map_err(|_| ContractError::RewardsOverflow)?;
+            if total != amount {
+                return Err(ContractError::FundsMismatch.into());
+            }
+
+            for (contract_address, amount) in pools {
+                contract.add_rewards(contract_address, amount)?;
+            }
+
+            Ok(Response::new())
+        }
         ExecuteMsg::DistributeRewards {
             contract_address,
             epoch_count,
@@ -89,38 +195,259 @@ pub fn execute(
             let contract_address = deps.api.addr_validate(&contract_address)?;
             let mut contract = Contract::new(deps);
             let rewards = contract
-                .distribute_rewards(contract_address, env.block.height, epoch_count)
+                .distribute_rewards(contract_address.clone(), &env.block, epoch_count)
+                .map_err(axelar_wasm_std::ContractError::from)?;
+            let rewards = contract
+                .vest_rewards(rewards, &env.block)
                 .map_err(axelar_wasm_std::ContractError::from)?;
 
-            let msgs = rewards
-                .into_iter()
-                .sorted()
-                .map(|(addr, amount)| BankMsg::Send {
-                    to_address: addr.into(),
-                    amount: vec![Coin {
-                        denom: contract.config.rewards_denom.clone(),
-                        amount,
-                    }],
-                });
+            let hooks = contract
+                .payout_hook_msgs(&rewards)
+                .map_err(axelar_wasm_std::ContractError::from)?;
+            let msgs = contract
+                .payout_msgs(rewards, Some(contract_address), &env)
+                .map_err(axelar_wasm_std::ContractError::from)?;
 
-            Ok(Response::new().add_messages(msgs))
+            Ok(Response::new().add_submessages(msgs).add_submessages(hooks))
         }
         ExecuteMsg::UpdateParams { params } => {
-            Contract::new(deps).update_params(params, env.block.height, info.sender)?;
+            Contract::new(deps).update_params(params, &env.block, info.sender)?;
+
+            Ok(Response::new())
+        }
+        ExecuteMsg::DelegateRewards { delegate_address } => {
+            let delegate_address = deps.api.addr_validate(&delegate_address)?;
+            Contract::new(deps).delegate_rewards(info.sender, delegate_address)?;
+
+            Ok(Response::new())
+        }
+        ExecuteMsg::UndelegateRewards => {
+            Contract::new(deps).undelegate_rewards(info.sender)?;
+
+            Ok(Response::new())
+        }
+        ExecuteMsg::SetIbcPayout {
+            channel_id,
+            remote_address,
+        } => {
+            let payout = IbcPayout {
+                channel_id,
+                remote_address,
+            };
+            validate_ibc_payout(deps.as_ref(), &payout)?;
+            Contract::new(deps).set_ibc_payout(info.sender, payout)?;
+
+            Ok(Response::new())
+        }
+        ExecuteMsg::RemoveIbcPayout => {
+            Contract::new(deps).remove_ibc_payout(info.sender)?;
 
             Ok(Response::new())
         }
+        ExecuteMsg::ClaimVested => {
+            let mut contract = Contract::new(deps);
+            let amount = contract
+                .claim_vested(info.sender.clone(), &env.block)
+                .map_err(axelar_wasm_std::ContractError::from)?;
+
+            let rewards = HashMap::from([(info.sender, amount)]);
+            let hooks = contract
+                .payout_hook_msgs(&rewards)
+                .map_err(axelar_wasm_std::ContractError::from)?;
+            let msgs = contract
+                .payout_msgs(rewards, None, &env)
+                .map_err(axelar_wasm_std::ContractError::from)?;
+
+            Ok(Response::new().add_submessages(msgs).add_submessages(hooks))
+        }
+        ExecuteMsg::SetPayoutHook { contract_address } => {
+            let contract_address = deps.api.addr_validate(&contract_address)?;
+            Contract::new(deps).set_payout_hook(info.sender, contract_address)?;
+
+            Ok(Response::new())
+        }
+        ExecuteMsg::RemovePayoutHook => {
+            Contract::new(deps).remove_payout_hook(info.sender)?;
+
+            Ok(Response::new())
+        }
+    }
+    .map(|response| response.add_events(epoch_transition.map(cosmwasm_std::Event::from)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    deps: DepsMut,
+    _env: Env,
+    reply: Reply,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    match (reply.id, reply.result) {
+        (IBC_TRANSFER_REPLY_ID, SubMsgResult::Ok(response)) => {
+            let sequence = response
+                .data
+                .and_then(|data| ics20::transfer_sequence(&data))
+                .ok_or(ContractError::InvalidIbcTransferResponse)?;
+            Contract::new(deps).record_ibc_transfer_sequence(sequence)?;
+
+            Ok(Response::new())
+        }
+        (IBC_TRANSFER_REPLY_ID, SubMsgResult::Err(_)) => {
+            unreachable!("violated invariant: replied failed submessage with ReplyOn::Success")
+        }
+        // a failing payout hook must not revert the payout, so the error is only reported
+        (PAYOUT_HOOK_REPLY_ID, SubMsgResult::Err(error)) => {
+            Ok(Response::new().add_event(Event::PayoutHookFailed { error }.into()))
+        }
+        (PAYOUT_HOOK_REPLY_ID, SubMsgResult::Ok(_)) => {
+            unreachable!("violated invariant: replied successful submessage with ReplyOn::Error")
+        }
+        _ => unreachable!("unknown reply ID"),
+    }
+}
+
+/// Called by the IBC hooks middleware with the outcome of the ICS-20 transfers this contract sent
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(
+    deps: DepsMut,
+    env: Env,
+    msg: SudoMsg,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    let (channel, sequence, success) = match msg {
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel,
+            sequence,
+            success,
+            ..
+        }) => (channel, sequence, success),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout { channel, sequence }) => {
+            (channel, sequence, false)
+        }
+    };
+
+    let event = Contract::new(deps)
+        .conclude_ibc_transfer(channel, sequence, success, &env.block)
+        .map_err(axelar_wasm_std::ContractError::from)?;
+
+    Ok(Response::new().add_events(event.map(cosmwasm_std::Event::from)))
+}
+
+/// Rewards are sent over the payout channel with ICS-20 transfers, so it must be an open ICS-20 channel,
+/// and the remote address must at least be well-formed, otherwise every payout to it fails
+fn validate_ibc_payout(deps: Deps, payout: &IbcPayout) -> error_stack::Result<(), ContractError> {
+    let channel = deps
+        .querier
+        .query::<ChannelResponse>(
+            &IbcQuery::Channel {
+                channel_id: payout.channel_id.to_string(),
+                port_id: Some(ics20::TRANSFER_PORT.to_string()),
+            }
+            .into(),
+        )
+        .change_context(ContractError::InvalidIbcChannel(
+            payout.channel_id.to_string(),
+        ))?
+        .channel;
+
+    match channel {
+        Some(channel) if channel.version == ics20::ICS20_VERSION => (),
+        _ => {
+            return Err(ContractError::InvalidIbcChannel(payout.channel_id.to_string()).into());
+        }
+    }
+
+    bech32::decode(&payout.remote_address)
+        .map_err(|_| ContractError::InvalidRemoteAddress(payout.remote_address.to_string()))?;
+
+    Ok(())
+}
+
+/// Returns the amount of rewards attached to the message. The rewards denom must be the only attached denom,
+/// so that other funds aren't silently swallowed
+fn attached_rewards(info: &MessageInfo, rewards_denom: &str) -> Result<Uint128, ContractError> {
+    info.funds
+        .iter()
+        .find(|coin| coin.denom == rewards_denom)
+        .filter(|_| info.funds.len() == 1)
+        .map(|coin| coin.amount)
+        .ok_or(ContractError::WrongDenom)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> Result<Binary, axelar_wasm_std::ContractError> {
+    match msg {
+        QueryMsg::WorkerStats {
+            contract_address,
+            worker_address,
+        } => {
+            let contract_address = deps.api.addr_validate(&contract_address)?;
+            let worker_address = deps.api.addr_validate(&worker_address)?;
+            to_binary(&query::worker_stats(
+                deps.storage,
+                contract_address,
+                worker_address,
+            )?)
+        }
+        QueryMsg::EpochTally {
+            pool_contract,
+            epoch_num,
+        } => {
+            let pool_contract = deps.api.addr_validate(&pool_contract)?;
+            to_binary(&query::epoch_tally(deps.storage, pool_contract, epoch_num)?)
+        }
+        QueryMsg::Delegations { start_after, limit } => {
+            let start_after = start_after
+                .map(|worker| deps.api.addr_validate(&worker))
+                .transpose()?;
+            to_binary(&query::delegations(deps.storage, start_after, limit)?)
+        }
+        QueryMsg::IbcPayout { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_binary(&query::ibc_payout(deps.storage, address)?)
+        }
+        QueryMsg::PayoutHook { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_binary(&query::payout_hook(deps.storage, address)?)
+        }
+        QueryMsg::PoolMetadata { contract_address } => {
+            let contract_address = deps.api.addr_validate(&contract_address)?;
+            to_binary(&query::pool_metadata(deps.storage, contract_address)?)
+        }
+        QueryMsg::RewardRate {
+            pool_contract,
+            bond,
+        } => {
+            let pool_contract = deps.api.addr_validate(&pool_contract)?;
+            to_binary(&query::reward_rate(
+                deps.storage,
+                &env.block,
+                pool_contract,
+                bond,
+            )?)
+        }
     }
+    .map_err(axelar_wasm_std::ContractError::from)
 }
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{coins, Addr, Binary, BlockInfo, Deps, Env, StdResult, Uint128};
+    use axelar_wasm_std::versioning;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_ibc_channel, mock_info};
+    use cosmwasm_std::{
+        coins, from_slice, Addr, BlockInfo, Decimal, DepsMut, Empty, IbcOrder, Uint128,
+    };
     use cw_multi_test::{App, ContractWrapper, Executor};
 
-    use crate::msg::{ExecuteMsg, InstantiateMsg, Params, QueryMsg};
+    use crate::error::ContractError;
+    use crate::msg::{
+        EpochMode, EpochTallyResponse, ExecuteMsg, IbcLifecycleComplete, InstantiateMsg, Params,
+        PoolMetadata, QueryMsg, SudoMsg, WorkerParticipation, WorkerStats,
+    };
 
-    use super::{execute, instantiate};
+    use super::{execute, instantiate, migrate, query, reply};
 
     /// Tests that the contract entry points (instantiate and execute) work as expected.
     /// Instantiates the contract and calls each of the 4 ExecuteMsg variants.
@@ -138,16 +465,17 @@ mod tests {
                 .init_balance(storage, &user, coins(100000, AXL_DENOMINATION))
                 .unwrap()
         });
-        let code = ContractWrapper::new(
-            execute,
-            instantiate,
-            |_deps: Deps, _env: Env, _msg: QueryMsg| -> StdResult<Binary> { todo!() },
-        );
+        let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
         let code_id = app.store_code(Box::new(code));
 
         let governance_address = Addr::unchecked("governance");
         let initial_params = Params {
             epoch_duration: 10u64.try_into().unwrap(),
+            epoch_mode: EpochMode::Blocks,
+            max_events_per_epoch: None,
+            vesting_epochs: None,
+            availability_bonus: None,
+            participation_carryover: None,
             rewards_per_epoch: Uint128::one().try_into().unwrap(),
             participation_threshold: (1, 2).try_into().unwrap(),
         };
@@ -160,6 +488,11 @@ mod tests {
                     rewards_denom: AXL_DENOMINATION.to_string(),
                     params: Params {
                         epoch_duration: 10u64.try_into().unwrap(),
+                        epoch_mode: EpochMode::Blocks,
+                        max_events_per_epoch: None,
+                        vesting_epochs: None,
+                        availability_bonus: None,
+                        participation_carryover: None,
                         rewards_per_epoch: Uint128::from(100u128).try_into().unwrap(),
                         participation_threshold: (1, 2).try_into().unwrap(),
                     },
@@ -180,6 +513,87 @@ mod tests {
         );
         assert!(res.is_ok());
 
+        // batched rewards must match the attached funds
+        let other_worker_contract = Addr::unchecked("other worker contract");
+        let batch = ExecuteMsg::AddRewardsBatch {
+            pools: vec![
+                (
+                    worker_contract.to_string(),
+                    Uint128::from(100u128).try_into().unwrap(),
+                ),
+                (
+                    other_worker_contract.to_string(),
+                    Uint128::from(50u128).try_into().unwrap(),
+                ),
+            ],
+        };
+        let res = app.execute_contract(
+            user.clone(),
+            contract_address.clone(),
+            &batch,
+            &coins(100, AXL_DENOMINATION),
+        );
+        assert!(res.is_err());
+
+        let res = app.execute_contract(
+            user.clone(),
+            contract_address.clone(),
+            &batch,
+            &coins(150, AXL_DENOMINATION),
+        );
+        assert!(res.is_ok());
+
+        let overflowing_batch = ExecuteMsg::AddRewardsBatch {
+            pools: vec![
+                (
+                    worker_contract.to_string(),
+                    Uint128::MAX.try_into().unwrap(),
+                ),
+                (
+                    other_worker_contract.to_string(),
+                    Uint128::from(1u128).try_into().unwrap(),
+                ),
+            ],
+        };
+        let err = app
+            .execute_contract(
+                user.clone(),
+                contract_address.clone(),
+                &overflowing_batch,
+                &coins(150, AXL_DENOMINATION),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::RewardsOverflow).to_string()
+        );
+
+        let metadata = PoolMetadata {
+            chain_name: Some("ethereum".try_into().unwrap()),
+            description: "voting verifier for ethereum".to_string(),
+        };
+        let res = app.execute_contract(
+            governance_address.clone(),
+            contract_address.clone(),
+            &ExecuteMsg::CreatePool {
+                contract_address: worker_contract.to_string(),
+                metadata: metadata.clone(),
+            },
+            &[],
+        );
+        assert!(res.is_ok());
+
+        let registered: Option<PoolMetadata> = app
+            .wrap()
+            .query_wasm_smart(
+                contract_address.clone(),
+                &QueryMsg::PoolMetadata {
+                    contract_address: worker_contract.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(registered, Some(metadata));
+
         let res = app.execute_contract(
             governance_address,
             contract_address.clone(),
@@ -199,6 +613,7 @@ mod tests {
             &ExecuteMsg::RecordParticipation {
                 event_id: "some event".to_string().try_into().unwrap(),
                 worker_address: worker.to_string(),
+                late: false,
             },
             &[],
         );
@@ -210,11 +625,61 @@ mod tests {
             &ExecuteMsg::RecordParticipation {
                 event_id: "some other event".to_string().try_into().unwrap(),
                 worker_address: worker.to_string(),
+                late: true,
+            },
+            &[],
+        );
+        assert!(res.is_ok());
+
+        // participation is visible before rewards are distributed
+        let tally: EpochTallyResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_address.clone(),
+                &QueryMsg::EpochTally {
+                    pool_contract: worker_contract.to_string(),
+                    epoch_num: 0,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            tally,
+            EpochTallyResponse {
+                event_count: 2,
+                participation: vec![WorkerParticipation {
+                    worker: worker.clone(),
+                    events_participated: 2,
+                    carryover: Decimal::zero(),
+                    threshold_met: true,
+                    penalized: false,
+                    late_events_participated: 1,
+                }],
+            }
+        );
+
+        // the hook doesn't point to an existing contract, so notifying it fails without affecting the payout
+        let hook = Addr::unchecked("restaking");
+        let res = app.execute_contract(
+            worker.clone(),
+            contract_address.clone(),
+            &ExecuteMsg::SetPayoutHook {
+                contract_address: hook.to_string(),
             },
             &[],
         );
         assert!(res.is_ok());
 
+        let registered: Option<Addr> = app
+            .wrap()
+            .query_wasm_smart(
+                contract_address.clone(),
+                &QueryMsg::PayoutHook {
+                    address: worker.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(registered, Some(hook));
+
         // need to change the block height so we can claim rewards
         let old_height = app.block_info().height;
         app.set_block(BlockInfo {
@@ -233,8 +698,243 @@ mod tests {
         );
         assert!(res.is_ok());
 
+        // the first execution in the new epoch reports the epoch transition
+        let events = res.unwrap().events;
+        assert!(events
+            .iter()
+            .any(|event| event.ty == "wasm-epoch_transitioned"));
+        assert!(events
+            .iter()
+            .any(|event| event.ty == "wasm-payout_hook_failed"));
+
         // worker should have been sent the appropriate rewards
-        let balance = app.wrap().query_balance(worker, AXL_DENOMINATION).unwrap();
+        let balance = app
+            .wrap()
+            .query_balance(worker.clone(), AXL_DENOMINATION)
+            .unwrap();
         assert_eq!(balance.amount, Uint128::from(150u128));
+
+        // worker stats should reflect the distributed epoch
+        let stats: WorkerStats = app
+            .wrap()
+            .query_wasm_smart(
+                contract_address,
+                &QueryMsg::WorkerStats {
+                    contract_address: worker_contract.to_string(),
+                    worker_address: worker.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            stats,
+            WorkerStats {
+                events_participated: 2,
+                rewards_earned: Uint128::from(150u128),
+                epochs_above_threshold: 1,
+            }
+        );
+    }
+
+    /// Tests that IBC payouts can only be registered for open ICS-20 channels and bech32 remote addresses
+    #[test]
+    fn set_ibc_payout_requires_open_ics20_channel() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("instantiator", &[]),
+            InstantiateMsg {
+                governance_address: "governance".to_string(),
+                rewards_denom: "uaxl".to_string(),
+                params: Params {
+                    epoch_duration: 10u64.try_into().unwrap(),
+                    epoch_mode: EpochMode::Blocks,
+                    max_events_per_epoch: None,
+                    vesting_epochs: None,
+                    availability_bonus: None,
+                    participation_carryover: None,
+                    rewards_per_epoch: Uint128::from(100u128).try_into().unwrap(),
+                    participation_threshold: (1, 2).try_into().unwrap(),
+                },
+            },
+        )
+        .unwrap();
+
+        let mut ics20_channel = mock_ibc_channel("channel-0", IbcOrder::Unordered, "ics20-1");
+        ics20_channel.endpoint.port_id = "transfer".to_string();
+        let mut other_channel = mock_ibc_channel("channel-1", IbcOrder::Unordered, "other-1");
+        other_channel.endpoint.port_id = "transfer".to_string();
+        deps.querier
+            .update_ibc("transfer", &[ics20_channel, other_channel]);
+
+        let remote_address = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+        for (channel_id, remote_address, err) in [
+            (
+                "channel-1",
+                remote_address,
+                ContractError::InvalidIbcChannel("channel-1".to_string()),
+            ),
+            (
+                "channel-2",
+                remote_address,
+                ContractError::InvalidIbcChannel("channel-2".to_string()),
+            ),
+            (
+                "channel-0",
+                "remote",
+                ContractError::InvalidRemoteAddress("remote".to_string()),
+            ),
+        ] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("worker", &[]),
+                ExecuteMsg::SetIbcPayout {
+                    channel_id: channel_id.try_into().unwrap(),
+                    remote_address: remote_address.try_into().unwrap(),
+                },
+            );
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                axelar_wasm_std::ContractError::from(err).to_string()
+            );
+        }
+
+        assert!(execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("worker", &[]),
+            ExecuteMsg::SetIbcPayout {
+                channel_id: "channel-0".try_into().unwrap(),
+                remote_address: remote_address.try_into().unwrap(),
+            },
+        )
+        .is_ok());
+    }
+
+    /// Tests that the callbacks of the IBC hooks middleware are understood
+    #[test]
+    fn sudo_msg_matches_ibc_hooks_callbacks() {
+        let msg: SudoMsg = from_slice(
+            br#"{"ibc_lifecycle_complete":{"ibc_ack":{"channel":"channel-0","sequence":1,"ack":"e30=","success":false}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+                channel: "channel-0".to_string(),
+                sequence: 1,
+                ack: "e30=".to_string(),
+                success: false,
+            })
+        );
+
+        let msg: SudoMsg = from_slice(
+            br#"{"ibc_lifecycle_complete":{"ibc_timeout":{"channel":"channel-0","sequence":2}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+                channel: "channel-0".to_string(),
+                sequence: 2,
+            })
+        );
+    }
+
+    /// Tests that pools created implicitly before pools had to be registered are registered on migration
+    #[test]
+    fn migrate_registers_existing_pools() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("instantiator", &[]),
+            InstantiateMsg {
+                governance_address: "governance".to_string(),
+                rewards_denom: "uaxl".to_string(),
+                params: Params {
+                    epoch_duration: 10u64.try_into().unwrap(),
+                    epoch_mode: EpochMode::Blocks,
+                    max_events_per_epoch: None,
+                    vesting_epochs: None,
+                    availability_bonus: None,
+                    participation_carryover: None,
+                    rewards_per_epoch: Uint128::from(100u128).try_into().unwrap(),
+                    participation_threshold: (1, 2).try_into().unwrap(),
+                },
+            },
+        )
+        .unwrap();
+
+        let registered_pool = PoolMetadata {
+            chain_name: Some("ethereum".try_into().unwrap()),
+            description: "ethereum voting verifier".to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("governance", &[]),
+            ExecuteMsg::CreatePool {
+                contract_address: "registered contract".to_string(),
+                metadata: registered_pool.clone(),
+            },
+        )
+        .unwrap();
+        for contract_address in ["registered contract", "funded contract"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("user", &coins(100, "uaxl")),
+                ExecuteMsg::AddRewards {
+                    contract_address: contract_address.to_string(),
+                },
+            )
+            .unwrap();
+        }
+
+        // the pools namespace of a contract deployed before pools had to be registered
+        versioning::set_state_version(deps.as_mut().storage, "pools", 1).unwrap();
+
+        let record = |deps: DepsMut| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info("funded contract", &[]),
+                ExecuteMsg::RecordParticipation {
+                    event_id: "event".try_into().unwrap(),
+                    worker_address: "worker".to_string(),
+                    late: false,
+                },
+            )
+        };
+        assert!(record(deps.as_mut()).is_err());
+
+        migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+        let pool_metadata = |contract_address: &str| -> Option<PoolMetadata> {
+            from_slice(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::PoolMetadata {
+                        contract_address: contract_address.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        assert_eq!(pool_metadata("registered contract"), Some(registered_pool));
+        assert_eq!(
+            pool_metadata("funded contract"),
+            Some(PoolMetadata {
+                chain_name: None,
+                description: "registered on migration".to_string(),
+            })
+        );
+        assert_eq!(pool_metadata("unknown contract"), None);
+
+        assert!(record(deps.as_mut()).is_ok());
     }
 }
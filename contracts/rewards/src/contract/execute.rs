@@ -1,18 +1,27 @@
 use axelar_wasm_std::{nonempty, FnExt};
-use cosmwasm_std::{Addr, DepsMut, Uint128};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, BlockInfo, Coin, DepsMut, Env, SubMsg, Uint128, WasmMsg,
+};
 use error_stack::Result;
+use itertools::Itertools;
 use std::collections::HashMap;
 
 use crate::{
+    contract::{IBC_TRANSFER_REPLY_ID, PAYOUT_HOOK_REPLY_ID},
     error::ContractError,
-    msg::Params,
+    events::Event as ContractEvent,
+    ics20,
+    msg::{EpochMode, IbcPayout, Params, PayoutHookMsg, PoolMetadata},
     state::{
-        Config, Epoch, EpochTally, Event, RewardsStore, StorageState, Store, StoredParams, CONFIG,
+        Config, Epoch, EpochTally, Event, IbcTransfer, RewardsStore, StorageState, Store,
+        StoredParams, VestingTranche, CONFIG,
     },
 };
 
 const DEFAULT_EPOCHS_TO_PROCESS: u64 = 10;
 const EPOCH_PAYOUT_DELAY: u64 = 2;
+const IBC_TRANSFER_TIMEOUT_SECONDS: u64 = 60 * 60;
+const PAYOUT_HOOK_GAS_LIMIT: u64 = 200_000;
 
 pub struct Contract<S>
 where
@@ -40,23 +49,44 @@ where
     S: Store,
 {
     /// Returns the current epoch. The current epoch is computed dynamically based on the current
-    /// block height and the epoch duration. If the epoch duration is updated, we store the epoch
-    /// in which the update occurs as the last checkpoint
-    fn current_epoch(&self, cur_block_height: u64) -> Result<Epoch, ContractError> {
+    /// block and the epoch duration, measured either in blocks or in seconds of block time depending on the epoch mode.
+    /// If the epoch duration or mode is updated, we store the epoch in which the update occurs as the last checkpoint
+    fn current_epoch(&self, block: &BlockInfo) -> Result<Epoch, ContractError> {
         let stored_params = self.store.load_params();
         let epoch_duration: u64 = stored_params.params.epoch_duration.into();
         let last_updated_epoch = stored_params.last_updated;
 
-        if cur_block_height < last_updated_epoch.block_height_started {
-            Err(ContractError::BlockHeightInPast.into())
-        } else {
-            let epochs_elapsed =
-                (cur_block_height - last_updated_epoch.block_height_started) / epoch_duration;
-            Ok(Epoch {
-                epoch_num: last_updated_epoch.epoch_num + epochs_elapsed,
-                block_height_started: last_updated_epoch.block_height_started
-                    + (epochs_elapsed * epoch_duration), // result is strictly less than cur_block_height, so multiplication is safe
-            })
+        match stored_params.params.epoch_mode {
+            EpochMode::Blocks => {
+                if block.height < last_updated_epoch.block_height_started {
+                    return Err(ContractError::BlockHeightInPast.into());
+                }
+
+                let epochs_elapsed =
+                    (block.height - last_updated_epoch.block_height_started) / epoch_duration;
+                Ok(Epoch {
+                    epoch_num: last_updated_epoch.epoch_num + epochs_elapsed,
+                    block_height_started: last_updated_epoch.block_height_started
+                        + (epochs_elapsed * epoch_duration), // result is strictly less than cur_block_height, so multiplication is safe
+                    time_started: last_updated_epoch.time_started,
+                })
+            }
+            EpochMode::Seconds => {
+                if block.time < last_updated_epoch.time_started {
+                    return Err(ContractError::BlockTimeInPast.into());
+                }
+
+                let epochs_elapsed = (block.time.seconds()
+                    - last_updated_epoch.time_started.seconds())
+                    / epoch_duration;
+                Ok(Epoch {
+                    epoch_num: last_updated_epoch.epoch_num + epochs_elapsed,
+                    block_height_started: last_updated_epoch.block_height_started,
+                    time_started: last_updated_epoch
+                        .time_started
+                        .plus_seconds(epochs_elapsed * epoch_duration), // result is strictly less than the current block time, so multiplication is safe
+                })
+            }
         }
     }
 
@@ -67,37 +97,103 @@ where
         Ok(())
     }
 
+    pub fn create_pool(
+        &mut self,
+        contract: Addr,
+        metadata: PoolMetadata,
+        sender: Addr,
+    ) -> Result<(), ContractError> {
+        self.require_governance(sender)?;
+
+        if self.store.load_pool_metadata(contract.clone())?.is_some() {
+            return Err(ContractError::PoolAlreadyExists.into());
+        }
+
+        self.store.save_pool_metadata(contract, &metadata)
+    }
+
     pub fn record_participation(
         &mut self,
         event_id: nonempty::String,
         worker: Addr,
         target_contract: Addr,
-        block_height: u64,
+        late: bool,
+        block: &BlockInfo,
     ) -> Result<(), ContractError> {
-        let cur_epoch = self.current_epoch(block_height)?;
+        if self
+            .store
+            .load_pool_metadata(target_contract.clone())?
+            .is_none()
+        {
+            return Err(ContractError::PoolNotRegistered.into());
+        }
+
+        let cur_epoch = self.current_epoch(block)?;
+        let params = self.store.load_params().params;
 
         let event =
-            self.load_or_store_event(event_id, target_contract.clone(), cur_epoch.epoch_num)?;
+            self.load_or_new_event(event_id, target_contract.clone(), cur_epoch.epoch_num)?;
 
-        self.store
+        // the same participation can be reported more than once, e.g. by retried signing sessions for the same message, but only counts once
+        if event.participants.contains(worker.as_str()) {
+            return Ok(());
+        }
+
+        let mut tally = match self
+            .store
             .load_epoch_tally(target_contract.clone(), event.epoch_num)?
-            .unwrap_or(EpochTally::new(
-                target_contract,
-                cur_epoch,
-                self.store.load_params().params,
-            ))
-            .record_participation(worker)
-            .then(|mut tally| {
-                if matches!(event, StorageState::New(_)) {
-                    tally.event_count += 1
-                }
-                self.store.save_epoch_tally(&tally)
-            })
+        {
+            Some(tally) => tally,
+            None => EpochTally::new(target_contract, cur_epoch, params.clone()),
+        };
+
+        if matches!(event, StorageState::New(_)) {
+            let limit_reached = params
+                .max_events_per_epoch
+                .map_or(false, |max| tally.event_count >= u64::from(max));
+            if limit_reached {
+                return Err(ContractError::EventLimitReached.into());
+            }
+            tally.event_count += 1
+        }
+
+        self.store
+            .save_event(&event.into_inner().add_participant(&worker))?;
+
+        tally
+            .record_participation(worker, late)
+            .then(|tally| self.store.save_epoch_tally(&tally))
     }
 
-    fn load_or_store_event(
+    pub fn penalize_worker(
         &mut self,
         event_id: nonempty::String,
+        worker: Addr,
+        target_contract: Addr,
+    ) -> Result<(), ContractError> {
+        let event = self
+            .store
+            .load_event(event_id.to_string(), target_contract.clone())?
+            .ok_or(ContractError::EventNotFound)?;
+
+        let already_distributed = self
+            .store
+            .load_rewards_watermark(target_contract.clone())?
+            .map_or(false, |last_processed| event.epoch_num <= last_processed);
+        if already_distributed {
+            return Err(ContractError::RewardsAlreadyDistributed.into());
+        }
+
+        self.store
+            .load_epoch_tally(target_contract, event.epoch_num)?
+            .ok_or(ContractError::EpochTallyNotFound)?
+            .penalize(worker)
+            .then(|tally| self.store.save_epoch_tally(&tally))
+    }
+
+    fn load_or_new_event(
+        &self,
+        event_id: nonempty::String,
         target_contract: Addr,
         cur_epoch_num: u64,
     ) -> Result<StorageState<Event>, ContractError> {
@@ -106,11 +202,11 @@ where
             .load_event(event_id.to_string(), target_contract.clone())?;
 
         match event {
-            None => {
-                let event = Event::new(event_id, target_contract, cur_epoch_num);
-                self.store.save_event(&event)?;
-                Ok(StorageState::New(event))
-            }
+            None => Ok(StorageState::New(Event::new(
+                event_id,
+                target_contract,
+                cur_epoch_num,
+            ))),
             Some(event) => Ok(StorageState::Existing(event)),
         }
     }
@@ -118,89 +214,157 @@ where
     pub fn distribute_rewards(
         &mut self,
         target_contract: Addr,
-        cur_block_height: u64,
+        block: &BlockInfo,
         epoch_process_limit: Option<u64>,
     ) -> Result<HashMap<Addr, Uint128>, ContractError> {
         let epoch_process_limit = epoch_process_limit.unwrap_or(DEFAULT_EPOCHS_TO_PROCESS);
-        let cur_epoch = self.current_epoch(cur_block_height)?;
+        let cur_epoch = self.current_epoch(block)?;
 
         let from = self
             .store
             .load_rewards_watermark(target_contract.clone())?
             .map_or(0, |last_processed| last_processed + 1);
 
-        let to = std::cmp::min(
-            (from + epoch_process_limit).saturating_sub(1), // for process limit =1 "from" and "to" must be equal
-            cur_epoch.epoch_num.saturating_sub(EPOCH_PAYOUT_DELAY),
-        );
+        let to = cur_epoch.epoch_num.saturating_sub(EPOCH_PAYOUT_DELAY);
 
-        if to < from || cur_epoch.epoch_num < EPOCH_PAYOUT_DELAY {
+        if to < from || cur_epoch.epoch_num < EPOCH_PAYOUT_DELAY || epoch_process_limit == 0 {
             return Err(ContractError::NoRewardsToDistribute.into());
         }
 
-        let rewards = self.process_rewards_for_epochs(target_contract.clone(), from, to)?;
+        // only epochs with events count towards the limit, so long stretches of inactivity are skipped at no cost
+        let tallies = self.store.load_epoch_tallies(
+            target_contract.clone(),
+            from,
+            to,
+            epoch_process_limit,
+        )?;
+
+        // if the limit was hit, later epochs might still have tallies, so they must not be marked as distributed
+        let to = match tallies.last() {
+            Some(last) if tallies.len() as u64 == epoch_process_limit => last.epoch.epoch_num,
+            _ => to,
+        };
+
+        let rewards = self.process_rewards_for_epochs(target_contract.clone(), tallies)?;
         self.store.save_rewards_watermark(target_contract, to)?;
-        Ok(rewards)
+        self.credit_delegates(rewards)
+    }
+
+    /// Moves the rewards of workers that delegated their rewards to their respective delegates
+    fn credit_delegates(
+        &self,
+        rewards: HashMap<Addr, Uint128>,
+    ) -> Result<HashMap<Addr, Uint128>, ContractError> {
+        rewards
+            .into_iter()
+            .try_fold(HashMap::new(), |mut credited, (worker, amount)| {
+                let recipient = self.store.load_delegate(worker.clone())?.unwrap_or(worker);
+                *credited.entry(recipient).or_default() += amount;
+                Ok(credited)
+            })
     }
 
     fn process_rewards_for_epochs(
         &mut self,
         target_contract: Addr,
-        from: u64,
-        to: u64,
+        tallies: Vec<EpochTally>,
     ) -> Result<HashMap<Addr, Uint128>, ContractError> {
-        let rewards = self.cumulate_rewards(&target_contract, from, to);
+        let tallies = tallies
+            .into_iter()
+            .map(|tally| self.credit_carryover(tally))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rewards = cumulate_rewards(&tallies);
         self.store
             .load_rewards_pool(target_contract.clone())?
             .sub_reward(rewards.values().sum())?
             .then(|pool| self.store.save_rewards_pool(&pool))?;
 
+        tallies
+            .iter()
+            .try_for_each(|tally| self.update_worker_stats(tally))?;
+
         Ok(rewards)
     }
 
-    fn cumulate_rewards(
-        &mut self,
-        target_contract: &Addr,
-        from: u64,
-        to: u64,
-    ) -> HashMap<Addr, Uint128> {
-        self.iterate_epoch_tallies(target_contract, from, to)
-            .map(|tally| tally.rewards_by_worker())
-            .fold(HashMap::new(), merge_rewards)
-    }
-
-    fn iterate_epoch_tallies<'a>(
-        &'a mut self,
-        target_contract: &'a Addr,
-        from: u64,
-        to: u64,
-    ) -> impl Iterator<Item = EpochTally> + 'a {
-        (from..=to).filter_map(|epoch_num| {
+    /// Credits the tally with the near misses of the previous epoch and fixes the credit it carries into the next epoch.
+    /// Epochs are distributed in order, so the credit of the previous epoch was fixed when it was distributed,
+    /// and participation recorded in an epoch after its distribution can't change it anymore
+    fn credit_carryover(&mut self, tally: EpochTally) -> Result<EpochTally, ContractError> {
+        let carryover = match tally.epoch.epoch_num.checked_sub(1) {
+            Some(prev_epoch_num) => self
+                .store
+                .load_epoch_tally(tally.contract.clone(), prev_epoch_num)?
+                .map(|prev_tally| match prev_tally.carried_over {
+                    Some(carried_over) => carried_over,
+                    None => prev_tally.next_carryover(),
+                })
+                .unwrap_or_default(),
+            None => HashMap::new(),
+        };
+
+        let mut tally = tally.with_carryover(carryover);
+        tally.carried_over = Some(tally.next_carryover());
+        self.store.save_epoch_tally(&tally)?;
+
+        Ok(tally)
+    }
+
+    fn update_worker_stats(&mut self, tally: &EpochTally) -> Result<(), ContractError> {
+        let rewards = tally.rewards_by_worker();
+        let workers_above_threshold = tally.workers_to_reward();
+
+        for (worker, participated) in &tally.participation {
+            let worker = Addr::unchecked(worker); // Ok to convert unchecked here, since we only store valid addresses
+            let stats = self
+                .store
+                .load_worker_stats(tally.contract.clone(), worker.clone())?
+                .record_epoch(
+                    *participated,
+                    workers_above_threshold.contains(&worker),
+                    rewards.get(&worker).copied().unwrap_or_default(),
+                );
             self.store
-                .load_epoch_tally(target_contract.clone(), epoch_num)
-                .unwrap_or_default()
-        })
+                .save_worker_stats(tally.contract.clone(), worker, &stats)?;
+        }
+
+        Ok(())
     }
 
     pub fn update_params(
         &mut self,
         new_params: Params,
-        block_height: u64,
+        block: &BlockInfo,
         sender: Addr,
     ) -> Result<(), ContractError> {
         self.require_governance(sender)?;
-        let cur_epoch = self.current_epoch(block_height)?;
+        let cur_epoch = self.current_epoch(block)?;
+        let cur_epoch_mode = self.store.load_params().params.epoch_mode;
         // If the param update reduces the epoch duration such that the current epoch immediately ends,
         // start a new epoch at this block, incrementing the current epoch number by 1.
         // This prevents us from jumping forward an arbitrary number of epochs, and maintains consistency for past events.
         // (i.e. we are in epoch 0, which started at block 0 and epoch duration is 1000. At epoch 500, the params
         // are updated to shorten the epoch duration to 100 blocks. We set the epoch number to 1, to prevent skipping
         // epochs 1-4, and so all events prior to the start of epoch 1 have an epoch number of 0)
-        let should_end =
-            cur_epoch.block_height_started + u64::from(new_params.epoch_duration) < block_height;
+        // Switching between block and time based epochs always starts a new epoch, because the start of the current epoch
+        // is only known in the unit of the previous mode.
+        let should_end = new_params.epoch_mode != cur_epoch_mode
+            || match new_params.epoch_mode {
+                EpochMode::Blocks => {
+                    cur_epoch.block_height_started + u64::from(new_params.epoch_duration)
+                        < block.height
+                }
+                EpochMode::Seconds => {
+                    cur_epoch
+                        .time_started
+                        .plus_seconds(new_params.epoch_duration.into())
+                        < block.time
+                }
+            };
         let cur_epoch = if should_end {
             Epoch {
-                block_height_started: block_height,
+                block_height_started: block.height,
+                time_started: block.time,
                 epoch_num: cur_epoch.epoch_num + 1,
             }
         } else {
@@ -213,6 +377,276 @@ where
         Ok(())
     }
 
+    /// Records the current epoch as observed. If no execution has observed the current epoch before, returns an event
+    /// describing the transition from the previously observed epoch. Until the first observation, the epoch in which the params
+    /// were last updated counts as observed
+    pub fn observe_epoch(
+        &mut self,
+        block: &BlockInfo,
+    ) -> Result<Option<ContractEvent>, ContractError> {
+        let cur_epoch = self.current_epoch(block)?;
+        let last_epoch = self
+            .store
+            .load_last_observed_epoch()?
+            .unwrap_or_else(|| self.store.load_params().last_updated);
+
+        if cur_epoch.epoch_num <= last_epoch.epoch_num {
+            return Ok(None);
+        }
+
+        self.store.save_last_observed_epoch(&cur_epoch)?;
+        Ok(Some(ContractEvent::EpochTransitioned {
+            old_epoch: last_epoch,
+            new_epoch: cur_epoch,
+        }))
+    }
+
+    pub fn delegate_rewards(&mut self, worker: Addr, delegate: Addr) -> Result<(), ContractError> {
+        if worker == delegate {
+            return Err(ContractError::SelfDelegation.into());
+        }
+
+        self.store.save_delegate(worker, delegate)
+    }
+
+    pub fn undelegate_rewards(&mut self, worker: Addr) -> Result<(), ContractError> {
+        self.store
+            .load_delegate(worker.clone())?
+            .ok_or(ContractError::DelegationNotFound)?;
+
+        self.store.remove_delegate(worker);
+        Ok(())
+    }
+
+    pub fn set_ibc_payout(
+        &mut self,
+        address: Addr,
+        payout: IbcPayout,
+    ) -> Result<(), ContractError> {
+        self.store.save_ibc_payout(address, &payout)
+    }
+
+    pub fn remove_ibc_payout(&mut self, address: Addr) -> Result<(), ContractError> {
+        self.store
+            .load_ibc_payout(address.clone())?
+            .ok_or(ContractError::IbcPayoutNotFound)?;
+
+        self.store.remove_ibc_payout(address);
+        Ok(())
+    }
+
+    pub fn set_payout_hook(&mut self, address: Addr, hook: Addr) -> Result<(), ContractError> {
+        self.store.save_payout_hook(address, hook)
+    }
+
+    pub fn remove_payout_hook(&mut self, address: Addr) -> Result<(), ContractError> {
+        self.store
+            .load_payout_hook(address.clone())?
+            .ok_or(ContractError::PayoutHookNotFound)?;
+
+        self.store.remove_payout_hook(address);
+        Ok(())
+    }
+
+    /// Credits the distributed rewards to the vesting ledger if vesting is enabled, so that they unlock linearly starting with the current epoch.
+    /// Returns the rewards that must be sent right away, which is none of them if vesting is enabled and all of them otherwise
+    pub fn vest_rewards(
+        &mut self,
+        rewards: HashMap<Addr, Uint128>,
+        block: &BlockInfo,
+    ) -> Result<HashMap<Addr, Uint128>, ContractError> {
+        let vesting_epochs = match self.store.load_params().params.vesting_epochs {
+            Some(vesting_epochs) => u64::from(vesting_epochs),
+            None => return Ok(rewards),
+        };
+        let cur_epoch = self.current_epoch(block)?;
+
+        for (recipient, amount) in rewards {
+            let mut tranches = self.store.load_vesting_tranches(recipient.clone())?;
+            tranches.push(VestingTranche {
+                amount,
+                claimed: Uint128::zero(),
+                start_epoch: cur_epoch.epoch_num,
+                vesting_epochs,
+            });
+            self.store.save_vesting_tranches(recipient, &tranches)?;
+        }
+
+        Ok(HashMap::new())
+    }
+
+    /// Marks all unlocked rewards of the recipient as claimed and returns the claimed amount
+    pub fn claim_vested(
+        &mut self,
+        recipient: Addr,
+        block: &BlockInfo,
+    ) -> Result<Uint128, ContractError> {
+        let cur_epoch = self.current_epoch(block)?;
+
+        let mut total_claimed = Uint128::zero();
+        let mut tranches = vec![];
+        for mut tranche in self.store.load_vesting_tranches(recipient.clone())? {
+            let claimable = tranche.claimable(cur_epoch.epoch_num);
+            tranche.claimed += claimable;
+            total_claimed += claimable;
+
+            if !tranche.is_fully_claimed() {
+                tranches.push(tranche);
+            }
+        }
+
+        if total_claimed.is_zero() {
+            return Err(ContractError::NoVestedRewards.into());
+        }
+
+        self.store.save_vesting_tranches(recipient, &tranches)?;
+        Ok(total_claimed)
+    }
+
+    /// Creates the messages that send the distributed rewards to their recipients. Recipients that registered an IBC payout
+    /// receive their rewards on the remote chain via an ICS-20 transfer, all others via a bank transfer on this chain.
+    /// ICS-20 transfers are queued until their reply reports the packet sequence, so that a refund can be credited back
+    /// to the given pool, or to the recipient's vesting ledger if the rewards were claimed from it
+    pub fn payout_msgs(
+        &mut self,
+        rewards: HashMap<Addr, Uint128>,
+        pool: Option<Addr>,
+        env: &Env,
+    ) -> Result<Vec<SubMsg>, ContractError> {
+        let mut msgs = vec![];
+        for (recipient, amount) in rewards.into_iter().sorted() {
+            let coin = Coin {
+                denom: self.config.rewards_denom.clone(),
+                amount,
+            };
+
+            let msg = match self.store.load_ibc_payout(recipient.clone())? {
+                Some(payout) => {
+                    self.store.push_sent_ibc_transfer(&IbcTransfer {
+                        channel_id: payout.channel_id.to_string(),
+                        recipient,
+                        amount,
+                        pool: pool.clone(),
+                    })?;
+
+                    SubMsg::reply_on_success(
+                        ics20::transfer_msg(
+                            &payout.channel_id,
+                            env.contract.address.as_str(),
+                            &payout.remote_address,
+                            &coin,
+                            env.block.time.plus_seconds(IBC_TRANSFER_TIMEOUT_SECONDS),
+                        ),
+                        IBC_TRANSFER_REPLY_ID,
+                    )
+                }
+                None => SubMsg::new(BankMsg::Send {
+                    to_address: recipient.into(),
+                    amount: vec![coin],
+                }),
+            };
+            msgs.push(msg);
+        }
+
+        Ok(msgs)
+    }
+
+    /// Records the packet sequence of the oldest ICS-20 transfer that is waiting for it
+    pub fn record_ibc_transfer_sequence(&mut self, sequence: u64) -> Result<(), ContractError> {
+        let transfer = self
+            .store
+            .pop_sent_ibc_transfer()?
+            .expect("violated invariant: replied to an ibc transfer that wasn't queued");
+
+        self.store.save_ibc_transfer(sequence, &transfer)
+    }
+
+    /// Concludes an ICS-20 transfer once its outcome is reported. If the transfer failed or timed out, the refunded rewards
+    /// are credited back to the pool they were distributed from, or to the recipient's vesting ledger, unlocked right away
+    pub fn conclude_ibc_transfer(
+        &mut self,
+        channel_id: String,
+        sequence: u64,
+        success: bool,
+        block: &BlockInfo,
+    ) -> Result<Option<ContractEvent>, ContractError> {
+        let transfer = self
+            .store
+            .load_ibc_transfer(channel_id.clone(), sequence)?
+            .ok_or(ContractError::IbcTransferNotFound {
+                channel_id: channel_id.clone(),
+                sequence,
+            })?;
+        self.store.remove_ibc_transfer(channel_id, sequence);
+
+        if success {
+            return Ok(None);
+        }
+
+        match transfer.pool.clone() {
+            Some(contract) => {
+                let mut pool = self.store.load_rewards_pool(contract)?;
+                pool.balance += transfer.amount;
+                self.store.save_rewards_pool(&pool)?;
+            }
+            None => {
+                // rewards can only be claimed once an epoch has passed, so the refund arrives in epoch 1 or later and unlocks immediately
+                let cur_epoch = self.current_epoch(block)?;
+                let mut tranches = self
+                    .store
+                    .load_vesting_tranches(transfer.recipient.clone())?;
+                tranches.push(VestingTranche {
+                    amount: transfer.amount,
+                    claimed: Uint128::zero(),
+                    start_epoch: cur_epoch.epoch_num.saturating_sub(1),
+                    vesting_epochs: 1,
+                });
+                self.store
+                    .save_vesting_tranches(transfer.recipient.clone(), &tranches)?;
+            }
+        }
+
+        Ok(Some(ContractEvent::IbcTransferRefunded {
+            recipient: transfer.recipient,
+            amount: transfer.amount,
+            pool: transfer.pool,
+        }))
+    }
+
+    /// Creates the notifications for the payout hooks of the rewards recipients. Each hook runs with a fixed gas limit
+    /// and only replies on error, so a misbehaving hook can neither revert nor starve the payout itself
+    pub fn payout_hook_msgs(
+        &self,
+        rewards: &HashMap<Addr, Uint128>,
+    ) -> Result<Vec<SubMsg>, ContractError> {
+        let mut msgs = vec![];
+        for (recipient, amount) in rewards.iter().sorted() {
+            if let Some(hook) = self.store.load_payout_hook(recipient.clone())? {
+                let msg = PayoutHookMsg::RewardsPaid {
+                    recipient: recipient.clone(),
+                    amount: Coin {
+                        denom: self.config.rewards_denom.clone(),
+                        amount: *amount,
+                    },
+                };
+
+                msgs.push(
+                    SubMsg::reply_on_error(
+                        WasmMsg::Execute {
+                            contract_addr: hook.into(),
+                            msg: to_binary(&msg).expect("failed to serialize payout hook msg"),
+                            funds: vec![],
+                        },
+                        PAYOUT_HOOK_REPLY_ID,
+                    )
+                    .with_gas_limit(PAYOUT_HOOK_GAS_LIMIT),
+                );
+            }
+        }
+
+        Ok(msgs)
+    }
+
     pub fn add_rewards(
         &mut self,
         contract: Addr,
@@ -227,6 +661,13 @@ where
     }
 }
 
+fn cumulate_rewards(tallies: &[EpochTally]) -> HashMap<Addr, Uint128> {
+    tallies
+        .iter()
+        .map(|tally| tally.rewards_by_worker())
+        .fold(HashMap::new(), merge_rewards)
+}
+
 /// Merges rewards_2 into rewards_1. For each (address, amount) pair in rewards_2,
 /// adds the rewards amount to the existing rewards amount in rewards_1. If the
 /// address is not yet in rewards_1, initializes the rewards amount to the amount in
@@ -247,17 +688,31 @@ fn merge_rewards(
 #[cfg(test)]
 mod test {
     use std::{
-        collections::HashMap,
+        collections::{HashMap, HashSet, VecDeque},
         sync::{Arc, RwLock},
     };
 
-    use axelar_wasm_std::nonempty;
-    use cosmwasm_std::{Addr, Uint128, Uint64};
+    use axelar_wasm_std::{nonempty, test_utils::check_invariant};
+    use cosmwasm_std::{
+        testing::mock_env, to_binary, Addr, BankMsg, BlockInfo, Coin, Decimal, SubMsg, Timestamp,
+        Uint128, Uint64, WasmMsg,
+    };
+    use itertools::Itertools;
+    use proptest::{prop_assert, prop_assert_eq};
 
     use crate::{
+        contract::{IBC_TRANSFER_REPLY_ID, PAYOUT_HOOK_REPLY_ID},
         error::ContractError,
-        msg::Params,
-        state::{self, Config, Epoch, EpochTally, Event, RewardsPool, Store, StoredParams},
+        events::Event as ContractEvent,
+        ics20,
+        msg::{
+            signing_event_id, EpochMode, IbcPayout, Params, ParticipationCarryover, PayoutHookMsg,
+            PoolMetadata, WorkerStats,
+        },
+        state::{
+            self, Config, Epoch, EpochTally, Event, IbcTransfer, RewardsPool, Store, StoredParams,
+            VestingTranche,
+        },
     };
 
     use super::Contract;
@@ -269,16 +724,20 @@ mod test {
         let block_height_started = 250u64;
         let epoch_duration = 100u64;
         let contract = setup(cur_epoch_num, block_height_started, epoch_duration);
-        let new_epoch = contract.current_epoch(block_height_started).unwrap();
+        let new_epoch = contract
+            .current_epoch(&block_at(block_height_started))
+            .unwrap();
         assert_eq!(new_epoch.epoch_num, cur_epoch_num);
         assert_eq!(new_epoch.block_height_started, block_height_started);
 
-        let new_epoch = contract.current_epoch(block_height_started + 1).unwrap();
+        let new_epoch = contract
+            .current_epoch(&block_at(block_height_started + 1))
+            .unwrap();
         assert_eq!(new_epoch.epoch_num, cur_epoch_num);
         assert_eq!(new_epoch.block_height_started, block_height_started);
 
         let new_epoch = contract
-            .current_epoch(block_height_started + epoch_duration - 1)
+            .current_epoch(&block_at(block_height_started + epoch_duration - 1))
             .unwrap();
         assert_eq!(new_epoch.epoch_num, cur_epoch_num);
         assert_eq!(new_epoch.block_height_started, block_height_started);
@@ -292,9 +751,11 @@ mod test {
         let block_height_started = 250u64;
         let epoch_duration = 100u64;
         let contract = setup(cur_epoch_num, block_height_started, epoch_duration);
-        assert!(contract.current_epoch(block_height_started - 1).is_err());
         assert!(contract
-            .current_epoch(block_height_started - epoch_duration)
+            .current_epoch(&block_at(block_height_started - 1))
+            .is_err());
+        assert!(contract
+            .current_epoch(&block_at(block_height_started - epoch_duration))
             .is_err());
     }
 
@@ -331,13 +792,179 @@ mod test {
         ];
 
         for (height, expected_epoch_num, expected_block_start) in test_cases {
-            let new_epoch = contract.current_epoch(height).unwrap();
+            let new_epoch = contract.current_epoch(&block_at(height)).unwrap();
 
             assert_eq!(new_epoch.epoch_num, expected_epoch_num);
             assert_eq!(new_epoch.block_height_started, expected_block_start);
         }
     }
 
+    /// Tests that for any block at or after the stored epoch's start, the computed epoch contains the block
+    /// and is as many epochs ahead as there are full epoch durations in between
+    #[test]
+    fn current_epoch_contains_block() {
+        let strategy = (0..1_000u64, 0..1_000_000u64, 1..10_000u64, 0..1_000_000u64);
+
+        check_invariant(
+            strategy,
+            |(cur_epoch_num, block_height_started, epoch_duration, blocks_elapsed)| {
+                let contract = setup(cur_epoch_num, block_height_started, epoch_duration);
+                let height = block_height_started + blocks_elapsed;
+
+                let epoch = contract.current_epoch(&block_at(height)).unwrap();
+
+                prop_assert!(epoch.block_height_started <= height);
+                prop_assert!(height < epoch.block_height_started + epoch_duration);
+                prop_assert_eq!(
+                    epoch.epoch_num,
+                    cur_epoch_num + blocks_elapsed / epoch_duration
+                );
+                Ok(())
+            },
+        );
+    }
+
+    /// Tests that time based epochs are computed from the block time, independently of the block height
+    #[test]
+    fn current_epoch_seconds_mode() {
+        let mut contract = setup(0, 0, 100);
+        let epoch_duration = 600u64;
+        let params = Params {
+            epoch_duration: epoch_duration.try_into().unwrap(),
+            epoch_mode: EpochMode::Seconds,
+            ..contract.store.load_params().params
+        };
+        let update_block = block_at(1000);
+        contract
+            .update_params(params, &update_block, contract.config.governance.clone())
+            .unwrap();
+
+        // elements are (block, expected epoch number, expected epoch start time)
+        let test_cases = vec![
+            (update_block.clone(), 11, update_block.time),
+            (
+                BlockInfo {
+                    height: update_block.height + 1,
+                    time: update_block.time.plus_seconds(epoch_duration - 1),
+                    ..update_block.clone()
+                },
+                11,
+                update_block.time,
+            ),
+            (
+                // block times got slower, so only a few blocks are produced during an epoch
+                BlockInfo {
+                    height: update_block.height + 2,
+                    time: update_block.time.plus_seconds(epoch_duration),
+                    ..update_block.clone()
+                },
+                12,
+                update_block.time.plus_seconds(epoch_duration),
+            ),
+            (
+                // block times got faster, so a lot of blocks are produced during an epoch
+                BlockInfo {
+                    height: update_block.height + 10_000,
+                    time: update_block.time.plus_seconds(epoch_duration * 10 + 1),
+                    ..update_block.clone()
+                },
+                21,
+                update_block.time.plus_seconds(epoch_duration * 10),
+            ),
+        ];
+
+        for (block, expected_epoch_num, expected_time_start) in test_cases {
+            let new_epoch = contract.current_epoch(&block).unwrap();
+
+            assert_eq!(new_epoch.epoch_num, expected_epoch_num);
+            assert_eq!(new_epoch.time_started, expected_time_start);
+        }
+
+        let block_in_the_past = BlockInfo {
+            time: update_block.time.minus_seconds(1),
+            ..update_block
+        };
+        assert!(matches!(
+            contract
+                .current_epoch(&block_in_the_past)
+                .unwrap_err()
+                .current_context(),
+            ContractError::BlockTimeInPast
+        ));
+    }
+
+    /// Tests that switching between block and time based epochs always starts a new epoch at the block of the update
+    #[test]
+    fn switch_epoch_mode() {
+        let cur_epoch_num = 1u64;
+        let block_height_started = 250u64;
+        let epoch_duration = 100u64;
+        let mut contract = setup(cur_epoch_num, block_height_started, epoch_duration);
+        let initial_params = contract.store.load_params().params;
+
+        // switch to seconds in the middle of an epoch
+        let switch_to_seconds = block_at(block_height_started + epoch_duration / 2);
+        contract
+            .update_params(
+                Params {
+                    epoch_mode: EpochMode::Seconds,
+                    ..initial_params.clone()
+                },
+                &switch_to_seconds,
+                contract.config.governance.clone(),
+            )
+            .unwrap();
+
+        let epoch = contract.current_epoch(&switch_to_seconds).unwrap();
+        assert_eq!(epoch.epoch_num, cur_epoch_num + 1);
+        assert_eq!(epoch.block_height_started, switch_to_seconds.height);
+        assert_eq!(epoch.time_started, switch_to_seconds.time);
+
+        // the duration is now interpreted as seconds, so the epoch ends after 100 seconds instead of 100 blocks
+        let epoch = contract
+            .current_epoch(&BlockInfo {
+                height: switch_to_seconds.height + epoch_duration - 1,
+                time: switch_to_seconds.time.plus_seconds(epoch_duration),
+                ..switch_to_seconds.clone()
+            })
+            .unwrap();
+        assert_eq!(epoch.epoch_num, cur_epoch_num + 2);
+
+        // switch back to blocks in the middle of an epoch
+        let switch_to_blocks = BlockInfo {
+            height: switch_to_seconds.height + epoch_duration,
+            time: switch_to_seconds.time.plus_seconds(epoch_duration * 3 / 2),
+            ..switch_to_seconds
+        };
+        contract
+            .update_params(
+                initial_params,
+                &switch_to_blocks,
+                contract.config.governance.clone(),
+            )
+            .unwrap();
+
+        let epoch = contract.current_epoch(&switch_to_blocks).unwrap();
+        assert_eq!(epoch.epoch_num, cur_epoch_num + 3);
+        assert_eq!(epoch.block_height_started, switch_to_blocks.height);
+        assert_eq!(epoch.time_started, switch_to_blocks.time);
+
+        // block time is irrelevant again, only the height counts
+        let epoch = contract
+            .current_epoch(&BlockInfo {
+                height: switch_to_blocks.height + epoch_duration - 1,
+                time: switch_to_blocks.time.plus_seconds(epoch_duration * 100),
+                ..switch_to_blocks.clone()
+            })
+            .unwrap();
+        assert_eq!(epoch.epoch_num, cur_epoch_num + 3);
+
+        let epoch = contract
+            .current_epoch(&block_at(switch_to_blocks.height + epoch_duration))
+            .unwrap();
+        assert_eq!(epoch.epoch_num, cur_epoch_num + 4);
+    }
+
     /// Tests that multiple participation events for the same contract within a given epoch are recorded correctly
     #[test]
     fn record_participation_multiple_events() {
@@ -348,6 +975,7 @@ mod test {
         let mut contract = setup(cur_epoch_num, epoch_block_start, epoch_duration);
 
         let worker_contract = Addr::unchecked("some contract");
+        create_pool(&mut contract, &worker_contract);
 
         let mut simulated_participation = HashMap::new();
         simulated_participation.insert(Addr::unchecked("worker_1"), 10);
@@ -366,7 +994,8 @@ mod test {
                             event_id,
                             worker.clone(),
                             worker_contract.clone(),
-                            cur_height,
+                            false,
+                            &block_at(cur_height),
                         )
                         .unwrap();
                 }
@@ -391,52 +1020,348 @@ mod test {
         }
     }
 
-    /// Tests that the participation event is recorded correctly when the event spans multiple epochs
+    /// Tests that participation reported more than once for the same event, e.g. by retried signing sessions, only counts once
     #[test]
-    fn record_participation_epoch_boundary() {
-        let starting_epoch_num = 1u64;
-        let block_height_started = 250u64;
+    fn record_participation_same_event_counts_once() {
+        let cur_epoch_num = 1u64;
+        let epoch_block_start = 250u64;
         let epoch_duration = 100u64;
 
-        let mut contract = setup(starting_epoch_num, block_height_started, epoch_duration);
+        let mut contract = setup(cur_epoch_num, epoch_block_start, epoch_duration);
 
         let worker_contract = Addr::unchecked("some contract");
+        create_pool(&mut contract, &worker_contract);
+        let event_id = signing_event_id("key", &[1; 32]);
 
-        let workers = vec![
-            Addr::unchecked("worker_1"),
-            Addr::unchecked("worker_2"),
-            Addr::unchecked("worker_3"),
-        ];
-        // this is the height just before the next epoch starts
-        let height_at_epoch_end = block_height_started + epoch_duration - 1;
-        // workers participate in consecutive blocks
-        for (i, workers) in workers.iter().enumerate() {
+        for worker in ["worker_1", "worker_1", "worker_2"] {
             contract
                 .record_participation(
-                    "some event".to_string().try_into().unwrap(),
-                    workers.clone(),
+                    event_id.clone(),
+                    Addr::unchecked(worker),
                     worker_contract.clone(),
-                    height_at_epoch_end + i as u64,
+                    false,
+                    &block_at(epoch_block_start),
                 )
                 .unwrap();
         }
 
-        let cur_epoch = contract.current_epoch(height_at_epoch_end).unwrap();
-        assert_ne!(starting_epoch_num + 1, cur_epoch.epoch_num);
-
         let tally = contract
             .store
-            .load_epoch_tally(worker_contract.clone(), starting_epoch_num)
+            .load_epoch_tally(worker_contract.clone(), cur_epoch_num)
+            .unwrap()
             .unwrap();
-        assert!(tally.is_some());
-
-        let tally = tally.unwrap();
-
         assert_eq!(tally.event_count, 1);
-        assert_eq!(tally.participation.len(), workers.len());
-        for w in workers {
-            assert_eq!(tally.participation.get(&w.to_string()), Some(&1));
-        }
+        assert_eq!(tally.participation.get("worker_1"), Some(&1));
+        assert_eq!(tally.participation.get("worker_2"), Some(&1));
+
+        let event = contract
+            .store
+            .load_event(event_id.into(), worker_contract)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            event.participants,
+            HashSet::from(["worker_1".to_string(), "worker_2".to_string()])
+        );
+    }
+
+    /// Tests that new events are rejected once the per epoch limit is reached, while existing events can still be participated in
+    #[test]
+    fn record_participation_event_limit() {
+        let cur_epoch_num = 1u64;
+        let epoch_block_start = 250u64;
+        let epoch_duration = 100u64;
+
+        let mut contract = setup(cur_epoch_num, epoch_block_start, epoch_duration);
+        let params = Params {
+            max_events_per_epoch: Some(2u64.try_into().unwrap()),
+            vesting_epochs: None,
+            availability_bonus: None,
+            participation_carryover: None,
+            ..contract.store.load_params().params
+        };
+        contract
+            .update_params(
+                params,
+                &block_at(epoch_block_start),
+                contract.config.governance.clone(),
+            )
+            .unwrap();
+
+        let worker_contract = Addr::unchecked("some contract");
+        create_pool(&mut contract, &worker_contract);
+        let worker = Addr::unchecked("worker");
+        let record = |contract: &mut Contract<state::MockStore>, event_id: &str, height: u64| {
+            contract.record_participation(
+                event_id.try_into().unwrap(),
+                worker.clone(),
+                worker_contract.clone(),
+                false,
+                &block_at(height),
+            )
+        };
+
+        record(&mut contract, "event_1", epoch_block_start).unwrap();
+        record(&mut contract, "event_2", epoch_block_start).unwrap();
+        assert!(matches!(
+            record(&mut contract, "event_3", epoch_block_start)
+                .unwrap_err()
+                .current_context(),
+            ContractError::EventLimitReached
+        ));
+
+        // participation in already recorded events is still counted
+        let other_worker = Addr::unchecked("other worker");
+        contract
+            .record_participation(
+                "event_1".try_into().unwrap(),
+                other_worker.clone(),
+                worker_contract.clone(),
+                false,
+                &block_at(epoch_block_start + 1),
+            )
+            .unwrap();
+
+        let tally = contract
+            .store
+            .load_epoch_tally(worker_contract.clone(), cur_epoch_num)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tally.event_count, 2);
+        assert_eq!(tally.participation.get(other_worker.as_str()), Some(&1));
+
+        // the limit is reset in the next epoch
+        record(&mut contract, "event_4", epoch_block_start + epoch_duration).unwrap();
+    }
+
+    /// Tests that a penalized worker is excluded from the rewards of the epoch in which the event was recorded
+    #[test]
+    fn penalize_worker() {
+        let cur_epoch_num = 0u64;
+        let block_height_started = 0u64;
+        let epoch_duration = 1000u64;
+        let rewards_per_epoch = 100u128;
+
+        let mut contract = setup_with_params(
+            cur_epoch_num,
+            block_height_started,
+            epoch_duration,
+            rewards_per_epoch,
+            (1, 2),
+        );
+        let worker1 = Addr::unchecked("worker1");
+        let worker2 = Addr::unchecked("worker2");
+        let worker_contract = Addr::unchecked("worker contract");
+        create_pool(&mut contract, &worker_contract);
+        let event_id: nonempty::String = "some event".try_into().unwrap();
+
+        for worker in [&worker1, &worker2] {
+            contract
+                .record_participation(
+                    event_id.clone(),
+                    worker.clone(),
+                    worker_contract.clone(),
+                    false,
+                    &block_at(block_height_started),
+                )
+                .unwrap();
+        }
+
+        contract
+            .penalize_worker(event_id, worker2.clone(), worker_contract.clone())
+            .unwrap();
+
+        let tally = contract
+            .store
+            .load_epoch_tally(worker_contract.clone(), cur_epoch_num)
+            .unwrap()
+            .unwrap();
+        assert!(tally.penalized.contains(worker2.as_str()));
+
+        contract
+            .add_rewards(
+                worker_contract.clone(),
+                Uint128::from(rewards_per_epoch).try_into().unwrap(),
+            )
+            .unwrap();
+        let rewards = contract
+            .distribute_rewards(
+                worker_contract,
+                &block_at(block_height_started + epoch_duration * 2),
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            rewards,
+            HashMap::from([(worker1, Uint128::from(rewards_per_epoch))])
+        );
+    }
+
+    /// Penalizing a worker for an unknown event should fail
+    #[test]
+    fn penalize_worker_unknown_event() {
+        let mut contract = setup(0, 0, 1000);
+
+        let res = contract.penalize_worker(
+            "some event".try_into().unwrap(),
+            Addr::unchecked("worker"),
+            Addr::unchecked("worker contract"),
+        );
+        assert!(matches!(
+            res.unwrap_err().current_context(),
+            ContractError::EventNotFound
+        ));
+    }
+
+    /// Penalizing a worker after the rewards for the epoch were distributed should fail
+    #[test]
+    fn penalize_worker_after_distribution() {
+        let block_height_started = 0u64;
+        let epoch_duration = 1000u64;
+        let mut contract = setup(0, block_height_started, epoch_duration);
+        let worker = Addr::unchecked("worker");
+        let worker_contract = Addr::unchecked("worker contract");
+        create_pool(&mut contract, &worker_contract);
+        let event_id: nonempty::String = "some event".try_into().unwrap();
+
+        contract
+            .record_participation(
+                event_id.clone(),
+                worker.clone(),
+                worker_contract.clone(),
+                false,
+                &block_at(block_height_started),
+            )
+            .unwrap();
+        contract
+            .add_rewards(
+                worker_contract.clone(),
+                Uint128::from(100u128).try_into().unwrap(),
+            )
+            .unwrap();
+        contract
+            .distribute_rewards(
+                worker_contract.clone(),
+                &block_at(block_height_started + epoch_duration * 2),
+                None,
+            )
+            .unwrap();
+
+        let res = contract.penalize_worker(event_id, worker, worker_contract);
+        assert!(matches!(
+            res.unwrap_err().current_context(),
+            ContractError::RewardsAlreadyDistributed
+        ));
+    }
+
+    /// Participation can only be recorded for registered pools, and only governance can register a pool, once
+    #[test]
+    fn record_participation_requires_registered_pool() {
+        let mut contract = setup(0, 0, 1000);
+        let worker_contract = Addr::unchecked("worker contract");
+        let record = |contract: &mut Contract<state::MockStore>| {
+            contract.record_participation(
+                "some event".try_into().unwrap(),
+                Addr::unchecked("worker"),
+                worker_contract.clone(),
+                false,
+                &block_at(0),
+            )
+        };
+
+        assert!(matches!(
+            record(&mut contract).unwrap_err().current_context(),
+            ContractError::PoolNotRegistered
+        ));
+
+        let metadata = PoolMetadata {
+            chain_name: None,
+            description: "multisig".to_string(),
+        };
+        let res = contract.create_pool(
+            worker_contract.clone(),
+            metadata.clone(),
+            Addr::unchecked("not governance"),
+        );
+        assert!(matches!(
+            res.unwrap_err().current_context(),
+            ContractError::Unauthorized
+        ));
+
+        let governance = contract.config.governance.clone();
+        contract
+            .create_pool(
+                worker_contract.clone(),
+                metadata.clone(),
+                governance.clone(),
+            )
+            .unwrap();
+        assert_eq!(
+            contract
+                .store
+                .load_pool_metadata(worker_contract.clone())
+                .unwrap(),
+            Some(metadata.clone())
+        );
+        assert!(record(&mut contract).is_ok());
+
+        let res = contract.create_pool(worker_contract.clone(), metadata, governance);
+        assert!(matches!(
+            res.unwrap_err().current_context(),
+            ContractError::PoolAlreadyExists
+        ));
+    }
+
+    /// Tests that the participation event is recorded correctly when the event spans multiple epochs
+    #[test]
+    fn record_participation_epoch_boundary() {
+        let starting_epoch_num = 1u64;
+        let block_height_started = 250u64;
+        let epoch_duration = 100u64;
+
+        let mut contract = setup(starting_epoch_num, block_height_started, epoch_duration);
+
+        let worker_contract = Addr::unchecked("some contract");
+        create_pool(&mut contract, &worker_contract);
+
+        let workers = vec![
+            Addr::unchecked("worker_1"),
+            Addr::unchecked("worker_2"),
+            Addr::unchecked("worker_3"),
+        ];
+        // this is the height just before the next epoch starts
+        let height_at_epoch_end = block_height_started + epoch_duration - 1;
+        // workers participate in consecutive blocks
+        for (i, workers) in workers.iter().enumerate() {
+            contract
+                .record_participation(
+                    "some event".to_string().try_into().unwrap(),
+                    workers.clone(),
+                    worker_contract.clone(),
+                    false,
+                    &block_at(height_at_epoch_end + i as u64),
+                )
+                .unwrap();
+        }
+
+        let cur_epoch = contract
+            .current_epoch(&block_at(height_at_epoch_end))
+            .unwrap();
+        assert_ne!(starting_epoch_num + 1, cur_epoch.epoch_num);
+
+        let tally = contract
+            .store
+            .load_epoch_tally(worker_contract.clone(), starting_epoch_num)
+            .unwrap();
+        assert!(tally.is_some());
+
+        let tally = tally.unwrap();
+
+        assert_eq!(tally.event_count, 1);
+        assert_eq!(tally.participation.len(), workers.len());
+        for w in workers {
+            assert_eq!(tally.participation.get(&w.to_string()), Some(&1));
+        }
 
         let tally = contract
             .store
@@ -445,6 +1370,106 @@ mod test {
         assert!(tally.is_none());
     }
 
+    /// Tests that a near miss in one epoch is credited in the tally of the next epoch once the epochs are distributed,
+    /// and that participation recorded in an epoch after the next one started still counts towards its carryover
+    #[test]
+    fn distribute_rewards_carries_over_near_misses() {
+        let cur_epoch_num = 1u64;
+        let epoch_block_start = 250u64;
+        let epoch_duration = 100u64;
+
+        let mut contract = setup(cur_epoch_num, epoch_block_start, epoch_duration);
+        let params = Params {
+            participation_carryover: Some(ParticipationCarryover {
+                near_miss_threshold: (1, 4).try_into().unwrap(),
+                decay: (1, 2).try_into().unwrap(),
+            }),
+            ..contract.store.load_params().params
+        };
+        contract
+            .update_params(
+                params,
+                &block_at(epoch_block_start),
+                contract.config.governance.clone(),
+            )
+            .unwrap();
+
+        let worker_contract = Addr::unchecked("some contract");
+        create_pool(&mut contract, &worker_contract);
+        let mut record = |worker: &str, event_id: String, height: u64| {
+            contract
+                .record_participation(
+                    event_id.try_into().unwrap(),
+                    Addr::unchecked(worker),
+                    worker_contract.clone(),
+                    false,
+                    &block_at(height),
+                )
+                .unwrap();
+        };
+
+        // worker_2 and worker_4 narrowly miss the threshold of 1/2 in the first epoch with 1/4, and worker_3 doesn't participate enough
+        for i in 0..8 {
+            record("worker_1", format!("event {}", i), epoch_block_start);
+        }
+        for worker in ["worker_2", "worker_4"] {
+            record(worker, "event 0".to_string(), epoch_block_start);
+            record(worker, "event 1".to_string(), epoch_block_start);
+        }
+        record("worker_3", "event 0".to_string(), epoch_block_start);
+
+        // with 3/8 in the next epoch, worker_2 meets the threshold only because of the credit of 1/4 * 1/2
+        let next_epoch_block = epoch_block_start + epoch_duration;
+        for i in 0..8 {
+            record("worker_1", format!("next event {}", i), next_epoch_block);
+        }
+        for worker in ["worker_2", "worker_4"] {
+            for i in 0..3 {
+                record(worker, format!("next event {}", i), next_epoch_block);
+            }
+        }
+
+        // worker_4 still votes on events of the first epoch, and meets the threshold there, so it doesn't carry any credit over
+        record("worker_4", "event 2".to_string(), next_epoch_block);
+        record("worker_4", "event 3".to_string(), next_epoch_block);
+
+        contract
+            .add_rewards(
+                worker_contract.clone(),
+                Uint128::from(1000u128).try_into().unwrap(),
+            )
+            .unwrap();
+        let rewards = contract
+            .distribute_rewards(
+                worker_contract.clone(),
+                &block_at(epoch_block_start + epoch_duration * 3),
+                None,
+            )
+            .unwrap();
+
+        let tally = contract
+            .store
+            .load_epoch_tally(worker_contract, cur_epoch_num + 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            tally.carryover,
+            HashMap::from([("worker_2".to_string(), Decimal::from_ratio(1u64, 8u64))])
+        );
+        assert!(tally.meets_threshold("worker_2"));
+        assert!(!tally.meets_threshold("worker_4"));
+
+        // worker_2 is rewarded for the second epoch only, worker_4 for the first one only
+        assert_eq!(
+            rewards,
+            HashMap::from([
+                (Addr::unchecked("worker_1"), Uint128::from(100u128)),
+                (Addr::unchecked("worker_2"), Uint128::from(50u128)),
+                (Addr::unchecked("worker_4"), Uint128::from(50u128)),
+            ])
+        );
+    }
+
     /// Tests that participation events for different contracts are recorded correctly
     #[test]
     fn record_participation_multiple_contracts() {
@@ -468,6 +1493,10 @@ mod test {
             (Addr::unchecked("contract_3"), 2),
         );
 
+        for (worker_contract, _) in simulated_participation.values() {
+            create_pool(&mut contract, worker_contract);
+        }
+
         for (worker, (worker_contract, events_participated)) in &simulated_participation {
             for i in 0..*events_participated {
                 let event_id = i.to_string().try_into().unwrap();
@@ -476,7 +1505,8 @@ mod test {
                         event_id,
                         worker.clone(),
                         worker_contract.clone(),
-                        block_height_started,
+                        false,
+                        &block_at(block_height_started),
                     )
                     .unwrap();
             }
@@ -524,15 +1554,20 @@ mod test {
                 .unwrap(),
             participation_threshold: (Uint64::new(2), Uint64::new(3)).try_into().unwrap(),
             epoch_duration: epoch_duration.try_into().unwrap(), // keep this the same to not affect epoch computation
+            epoch_mode: EpochMode::Blocks,
+            max_events_per_epoch: None,
+            vesting_epochs: None,
+            availability_bonus: None,
+            participation_carryover: None,
         };
 
         // the epoch shouldn't change when the params are updated, since we are not changing the epoch duration
-        let expected_epoch = contract.current_epoch(cur_height).unwrap();
+        let expected_epoch = contract.current_epoch(&block_at(cur_height)).unwrap();
 
         contract
             .update_params(
                 new_params.clone(),
-                cur_height,
+                &block_at(cur_height),
                 contract.config.governance.clone(),
             )
             .unwrap();
@@ -540,7 +1575,7 @@ mod test {
         assert_eq!(stored.params, new_params);
 
         // current epoch shouldn't have changed
-        let cur_epoch = contract.current_epoch(cur_height).unwrap();
+        let cur_epoch = contract.current_epoch(&block_at(cur_height)).unwrap();
         assert_eq!(expected_epoch.epoch_num, cur_epoch.epoch_num);
         assert_eq!(
             expected_epoch.block_height_started,
@@ -563,11 +1598,16 @@ mod test {
             rewards_per_epoch: cosmwasm_std::Uint128::from(100u128).try_into().unwrap(),
             participation_threshold: (Uint64::new(2), Uint64::new(3)).try_into().unwrap(),
             epoch_duration: epoch_duration.try_into().unwrap(),
+            epoch_mode: EpochMode::Blocks,
+            max_events_per_epoch: None,
+            vesting_epochs: None,
+            availability_bonus: None,
+            participation_carryover: None,
         };
 
         let res = contract.update_params(
             new_params.clone(),
-            initial_epoch_start,
+            &block_at(initial_epoch_start),
             Addr::unchecked("some non governance address"),
         );
         assert!(res.is_err());
@@ -594,7 +1634,7 @@ mod test {
         let cur_height = initial_epoch_start + initial_epoch_duration * epochs_elapsed + 10; // add 10 here just to be a little past the epoch boundary
 
         // epoch shouldn't change if we are extending the duration
-        let epoch_prior_to_update = contract.current_epoch(cur_height).unwrap();
+        let epoch_prior_to_update = contract.current_epoch(&block_at(cur_height)).unwrap();
 
         let new_epoch_duration = initial_epoch_duration * 2;
         let new_params = Params {
@@ -605,24 +1645,24 @@ mod test {
         contract
             .update_params(
                 new_params.clone(),
-                cur_height,
+                &block_at(cur_height),
                 contract.config.governance.clone(),
             )
             .unwrap();
 
         // current epoch shouldn't change
-        let epoch = contract.current_epoch(cur_height).unwrap();
+        let epoch = contract.current_epoch(&block_at(cur_height)).unwrap();
         assert_eq!(epoch, epoch_prior_to_update);
 
         // we increased the epoch duration, so adding the initial epoch duration should leave us in the same epoch
         let epoch = contract
-            .current_epoch(cur_height + initial_epoch_duration)
+            .current_epoch(&block_at(cur_height + initial_epoch_duration))
             .unwrap();
         assert_eq!(epoch, epoch_prior_to_update);
 
         // check that we can correctly compute the start of the next epoch
         let next_epoch = contract
-            .current_epoch(cur_height + new_epoch_duration)
+            .current_epoch(&block_at(cur_height + new_epoch_duration))
             .unwrap();
         assert_eq!(next_epoch.epoch_num, epoch_prior_to_update.epoch_num + 1);
         assert_eq!(
@@ -649,7 +1689,7 @@ mod test {
         let cur_height = initial_epoch_start + initial_epoch_duration * epochs_elapsed;
 
         let new_epoch_duration = initial_epoch_duration / 2;
-        let epoch_prior_to_update = contract.current_epoch(cur_height).unwrap();
+        let epoch_prior_to_update = contract.current_epoch(&block_at(cur_height)).unwrap();
         // we are shortening the epoch, but not so much it causes the epoch number to change. We want to remain in the same epoch
         assert!(cur_height - epoch_prior_to_update.block_height_started < new_epoch_duration);
 
@@ -660,18 +1700,18 @@ mod test {
         contract
             .update_params(
                 new_params.clone(),
-                cur_height,
+                &block_at(cur_height),
                 contract.config.governance.clone(),
             )
             .unwrap();
 
         // current epoch shouldn't have changed
-        let epoch = contract.current_epoch(cur_height).unwrap();
+        let epoch = contract.current_epoch(&block_at(cur_height)).unwrap();
         assert_eq!(epoch_prior_to_update, epoch);
 
         // adding the new epoch duration should increase the epoch number by 1
         let epoch = contract
-            .current_epoch(cur_height + new_epoch_duration)
+            .current_epoch(&block_at(cur_height + new_epoch_duration))
             .unwrap();
         assert_eq!(epoch.epoch_num, epoch_prior_to_update.epoch_num + 1);
         assert_eq!(
@@ -700,7 +1740,7 @@ mod test {
         // simulate progressing far enough into the epoch such that shortening the epoch duration would change the epoch
         let cur_height =
             initial_epoch_start + initial_epoch_duration * epochs_elapsed + new_epoch_duration * 2;
-        let epoch_prior_to_update = contract.current_epoch(cur_height).unwrap();
+        let epoch_prior_to_update = contract.current_epoch(&block_at(cur_height)).unwrap();
 
         let new_params = Params {
             epoch_duration: 10.try_into().unwrap(),
@@ -709,19 +1749,19 @@ mod test {
         contract
             .update_params(
                 new_params.clone(),
-                cur_height,
+                &block_at(cur_height),
                 contract.config.governance.clone(),
             )
             .unwrap();
 
         // should be in new epoch now
-        let epoch = contract.current_epoch(cur_height).unwrap();
+        let epoch = contract.current_epoch(&block_at(cur_height)).unwrap();
         assert_eq!(epoch.epoch_num, epoch_prior_to_update.epoch_num + 1);
         assert_eq!(epoch.block_height_started, cur_height);
 
         // moving forward the new epoch duration # of blocks should increment the epoch
         let epoch = contract
-            .current_epoch(cur_height + new_epoch_duration)
+            .current_epoch(&block_at(cur_height + new_epoch_duration))
             .unwrap();
         assert_eq!(epoch.epoch_num, epoch_prior_to_update.epoch_num + 2);
         assert_eq!(epoch.block_height_started, cur_height + new_epoch_duration);
@@ -758,131 +1798,636 @@ mod test {
             .add_rewards(worker_contract.clone(), added_amount.try_into().unwrap())
             .unwrap();
 
-        let pool = contract.store.load_rewards_pool(worker_contract).unwrap();
-        assert_eq!(pool.balance, initial_amount + added_amount);
+        let pool = contract.store.load_rewards_pool(worker_contract).unwrap();
+        assert_eq!(pool.balance, initial_amount + added_amount);
+    }
+
+    /// Tests that rewards are added correctly with multiple contracts
+    #[test]
+    fn added_rewards_for_multiple_contracts_should_be_reflected_in_multiple_pools() {
+        let cur_epoch_num = 1u64;
+        let block_height_started = 250u64;
+        let epoch_duration = 100u64;
+
+        let mut contract = setup(cur_epoch_num, block_height_started, epoch_duration);
+        // a vector of (worker contract, rewards amounts) pairs
+        let test_data = vec![
+            (Addr::unchecked("contract_1"), vec![100, 200, 50]),
+            (Addr::unchecked("contract_2"), vec![25, 500, 70]),
+            (Addr::unchecked("contract_3"), vec![1000, 500, 2000]),
+        ];
+
+        for (worker_contract, rewards) in &test_data {
+            for amount in rewards {
+                contract
+                    .add_rewards(
+                        worker_contract.clone(),
+                        cosmwasm_std::Uint128::from(*amount).try_into().unwrap(),
+                    )
+                    .unwrap();
+            }
+        }
+
+        for (worker_contract, rewards) in test_data {
+            let pool = contract.store.load_rewards_pool(worker_contract).unwrap();
+            assert_eq!(
+                pool.balance,
+                cosmwasm_std::Uint128::from(rewards.iter().sum::<u128>())
+            );
+        }
+    }
+
+    /// Tests that rewards are distributed correctly based on participation
+    #[test]
+    fn distribute_rewards() {
+        let cur_epoch_num = 0u64;
+        let block_height_started = 0u64;
+        let epoch_duration = 1000u64;
+        let rewards_per_epoch = 100u128;
+        let participation_threshold = (2, 3);
+
+        let mut contract = setup_with_params(
+            cur_epoch_num,
+            block_height_started,
+            epoch_duration,
+            rewards_per_epoch,
+            participation_threshold,
+        );
+        let worker1 = Addr::unchecked("worker1");
+        let worker2 = Addr::unchecked("worker2");
+        let worker3 = Addr::unchecked("worker3");
+        let worker4 = Addr::unchecked("worker4");
+        let epoch_count = 4;
+        // Simulate 4 epochs worth of events with 4 workers
+        // Each epoch has 3 possible events to participate in
+        // The integer values represent which events a specific worker participated in during that epoch
+        // Events in different epochs are considered distinct; we append the epoch number when generating the event id
+        // The below participation corresponds to the following:
+        // 2 workers rewarded in epoch 0, no workers in epoch 1 (no events in that epoch), no workers in epoch 2 (but still some events), and then 4 (all) workers in epoch 3
+        let worker_participation_per_epoch = HashMap::from([
+            (
+                worker1.clone(),
+                [vec![1, 2, 3], vec![], vec![1], vec![2, 3]], // represents the worker participated in events 1,2 and 3 in epoch 0, no events in epoch 1, event 1 in epoch 2, and events 2 and 3 in epoch 3
+            ),
+            (worker2.clone(), [vec![], vec![], vec![2], vec![1, 2, 3]]),
+            (worker3.clone(), [vec![1, 2], vec![], vec![3], vec![1, 2]]),
+            (worker4.clone(), [vec![1], vec![], vec![2], vec![2, 3]]),
+        ]);
+        // The expected rewards per worker over all 4 epochs. Based on the above participation
+        let expected_rewards_per_worker: HashMap<Addr, u128> = HashMap::from([
+            (
+                worker1.clone(),
+                rewards_per_epoch / 2 + rewards_per_epoch / 4,
+            ),
+            (worker2.clone(), rewards_per_epoch / 4),
+            (
+                worker3.clone(),
+                rewards_per_epoch / 2 + rewards_per_epoch / 4,
+            ),
+            (worker4.clone(), rewards_per_epoch / 4),
+        ]);
+        let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
+
+        for (worker, events_participated) in worker_participation_per_epoch.clone() {
+            for epoch in 0..epoch_count {
+                for event in &events_participated[epoch] {
+                    let event_id = event.to_string() + &epoch.to_string() + "event";
+                    let _ = contract.record_participation(
+                        event_id.clone().try_into().unwrap(),
+                        worker.clone(),
+                        contract_addr.clone(),
+                        false,
+                        &block_at(block_height_started + epoch as u64 * epoch_duration),
+                    );
+                }
+            }
+        }
+
+        // we add 2 epochs worth of rewards. There were 2 epochs of participation, but only 2 epochs where rewards should be given out
+        // This tests we are accounting correctly, and only removing from the pool when we actually give out rewards
+        let rewards_added = 2 * rewards_per_epoch;
+        let _ = contract.add_rewards(
+            contract_addr.clone(),
+            Uint128::from(rewards_added).try_into().unwrap(),
+        );
+
+        let rewards_claimed = contract
+            .distribute_rewards(
+                contract_addr,
+                &block_at(block_height_started + epoch_duration * (epoch_count + 2) as u64),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(rewards_claimed.len(), worker_participation_per_epoch.len());
+        for (worker, rewards) in expected_rewards_per_worker {
+            assert!(rewards_claimed.contains_key(&worker));
+            assert_eq!(rewards_claimed.get(&worker), Some(&Uint128::from(rewards)));
+        }
+    }
+
+    /// Tests that worker stats accumulate participation and rewards across distributed epochs
+    #[test]
+    fn distribute_rewards_updates_worker_stats() {
+        let cur_epoch_num = 0u64;
+        let block_height_started = 0u64;
+        let epoch_duration = 1000u64;
+        let rewards_per_epoch = 100u128;
+        let participation_threshold = (2, 3);
+
+        let mut contract = setup_with_params(
+            cur_epoch_num,
+            block_height_started,
+            epoch_duration,
+            rewards_per_epoch,
+            participation_threshold,
+        );
+        let worker1 = Addr::unchecked("worker1");
+        let worker2 = Addr::unchecked("worker2");
+        let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
+
+        // worker1 participates in all 3 events of both epochs, worker2 only in one event of epoch 0
+        let participation = [
+            (
+                worker1.clone(),
+                vec![
+                    (0u64, "a"),
+                    (0, "b"),
+                    (0, "c"),
+                    (1, "a"),
+                    (1, "b"),
+                    (1, "c"),
+                ],
+            ),
+            (worker2.clone(), vec![(0, "a")]),
+        ];
+        for (worker, events) in participation {
+            for (epoch, event) in events {
+                contract
+                    .record_participation(
+                        format!("{}{}", event, epoch).try_into().unwrap(),
+                        worker.clone(),
+                        contract_addr.clone(),
+                        false,
+                        &block_at(block_height_started + epoch * epoch_duration),
+                    )
+                    .unwrap();
+            }
+        }
+
+        contract
+            .add_rewards(
+                contract_addr.clone(),
+                Uint128::from(2 * rewards_per_epoch).try_into().unwrap(),
+            )
+            .unwrap();
+
+        contract
+            .distribute_rewards(
+                contract_addr.clone(),
+                &block_at(block_height_started + epoch_duration * 3),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            contract
+                .store
+                .load_worker_stats(contract_addr.clone(), worker1)
+                .unwrap(),
+            WorkerStats {
+                events_participated: 6,
+                rewards_earned: Uint128::from(2 * rewards_per_epoch),
+                epochs_above_threshold: 2,
+            }
+        );
+        assert_eq!(
+            contract
+                .store
+                .load_worker_stats(contract_addr.clone(), worker2)
+                .unwrap(),
+            WorkerStats {
+                events_participated: 1,
+                rewards_earned: Uint128::zero(),
+                epochs_above_threshold: 0,
+            }
+        );
+        assert_eq!(
+            contract
+                .store
+                .load_worker_stats(contract_addr, Addr::unchecked("worker3"))
+                .unwrap(),
+            WorkerStats::default()
+        );
+    }
+
+    /// Tests that rewards of a worker that delegated its rewards are credited to the delegate, while worker stats are still tracked for the worker
+    #[test]
+    fn distribute_rewards_to_delegate() {
+        let block_height_started = 0u64;
+        let epoch_duration = 1000u64;
+        let rewards_per_epoch = 100u128;
+
+        let mut contract = setup_with_params(
+            0,
+            block_height_started,
+            epoch_duration,
+            rewards_per_epoch,
+            (1, 2),
+        );
+        let worker1 = Addr::unchecked("worker1");
+        let worker2 = Addr::unchecked("worker2");
+        let worker3 = Addr::unchecked("worker3");
+        let staking_pool = Addr::unchecked("staking pool");
+        let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
+
+        for worker in [&worker1, &worker2, &worker3] {
+            contract
+                .record_participation(
+                    "some event".try_into().unwrap(),
+                    worker.clone(),
+                    contract_addr.clone(),
+                    false,
+                    &block_at(block_height_started),
+                )
+                .unwrap();
+        }
+
+        contract
+            .delegate_rewards(worker1.clone(), staking_pool.clone())
+            .unwrap();
+        contract
+            .delegate_rewards(worker2.clone(), staking_pool.clone())
+            .unwrap();
+        contract
+            .delegate_rewards(worker3.clone(), staking_pool.clone())
+            .unwrap();
+        contract.undelegate_rewards(worker3.clone()).unwrap();
+
+        contract
+            .add_rewards(
+                contract_addr.clone(),
+                Uint128::from(rewards_per_epoch).try_into().unwrap(),
+            )
+            .unwrap();
+        let rewards = contract
+            .distribute_rewards(
+                contract_addr.clone(),
+                &block_at(block_height_started + epoch_duration * 2),
+                None,
+            )
+            .unwrap();
+
+        let reward_per_worker = rewards_per_epoch / 3;
+        assert_eq!(
+            rewards,
+            HashMap::from([
+                (staking_pool, Uint128::from(2 * reward_per_worker)),
+                (worker3, Uint128::from(reward_per_worker)),
+            ])
+        );
+        assert_eq!(
+            contract
+                .store
+                .load_worker_stats(contract_addr, worker1)
+                .unwrap()
+                .rewards_earned,
+            Uint128::from(reward_per_worker)
+        );
+    }
+
+    /// Workers cannot delegate to themselves, and can only undelegate existing delegations
+    #[test]
+    fn delegate_rewards_invalid() {
+        let mut contract = setup(0, 0, 1000);
+        let worker = Addr::unchecked("worker");
+
+        assert!(matches!(
+            contract
+                .delegate_rewards(worker.clone(), worker.clone())
+                .unwrap_err()
+                .current_context(),
+            ContractError::SelfDelegation
+        ));
+        assert!(matches!(
+            contract
+                .undelegate_rewards(worker)
+                .unwrap_err()
+                .current_context(),
+            ContractError::DelegationNotFound
+        ));
+    }
+
+    /// Tests that an epoch transition is reported only by the first observation of a new epoch
+    #[test]
+    fn observe_epoch() {
+        let block_height_started = 0u64;
+        let epoch_duration = 100u64;
+        let mut contract = setup(0, block_height_started, epoch_duration);
+
+        // the epoch in which the params were set counts as observed
+        assert!(contract
+            .observe_epoch(&block_at(block_height_started + 10))
+            .unwrap()
+            .is_none());
+
+        // skipped epochs are not reported individually
+        let event = contract
+            .observe_epoch(&block_at(block_height_started + epoch_duration * 3 + 10))
+            .unwrap();
+        match event {
+            Some(ContractEvent::EpochTransitioned {
+                old_epoch,
+                new_epoch,
+            }) => {
+                assert_eq!(old_epoch.epoch_num, 0);
+                assert_eq!(old_epoch.block_height_started, block_height_started);
+                assert_eq!(new_epoch.epoch_num, 3);
+                assert_eq!(
+                    new_epoch.block_height_started,
+                    block_height_started + epoch_duration * 3
+                );
+            }
+            _ => panic!("expected epoch transition"),
+        }
+
+        assert!(contract
+            .observe_epoch(&block_at(block_height_started + epoch_duration * 3 + 20))
+            .unwrap()
+            .is_none());
+
+        let event = contract
+            .observe_epoch(&block_at(block_height_started + epoch_duration * 4))
+            .unwrap();
+        assert!(matches!(
+            event,
+            Some(ContractEvent::EpochTransitioned { old_epoch, new_epoch })
+                if old_epoch.epoch_num == 3 && new_epoch.epoch_num == 4
+        ));
+    }
+
+    /// Tests that vested rewards unlock linearly over the vesting epochs and can be claimed as they unlock
+    #[test]
+    fn vest_and_claim_rewards() {
+        let epoch_duration = 100u64;
+        let mut contract = setup(0, 0, epoch_duration);
+        let worker = Addr::unchecked("worker");
+        let rewards = HashMap::from([(worker.clone(), Uint128::from(100u128))]);
+
+        // without vesting, rewards are sent right away
+        assert_eq!(
+            contract
+                .vest_rewards(rewards.clone(), &block_at(10))
+                .unwrap(),
+            rewards
+        );
+
+        let mut stored_params = contract.store.load_params();
+        stored_params.params.vesting_epochs = Some(4u64.try_into().unwrap());
+        contract.store.save_params(&stored_params).unwrap();
+
+        assert!(contract
+            .vest_rewards(rewards, &block_at(10))
+            .unwrap()
+            .is_empty());
+
+        // nothing is unlocked in the epoch of the distribution
+        assert_eq!(
+            contract
+                .claim_vested(worker.clone(), &block_at(50))
+                .unwrap_err()
+                .current_context(),
+            &ContractError::NoVestedRewards
+        );
+
+        assert_eq!(
+            contract
+                .claim_vested(worker.clone(), &block_at(epoch_duration + 10))
+                .unwrap(),
+            Uint128::from(25u128)
+        );
+        assert_eq!(
+            contract
+                .claim_vested(worker.clone(), &block_at(epoch_duration + 50))
+                .unwrap_err()
+                .current_context(),
+            &ContractError::NoVestedRewards
+        );
+        assert_eq!(
+            contract
+                .claim_vested(worker.clone(), &block_at(epoch_duration * 3 + 10))
+                .unwrap(),
+            Uint128::from(50u128)
+        );
+        assert_eq!(
+            contract
+                .claim_vested(worker.clone(), &block_at(epoch_duration * 10))
+                .unwrap(),
+            Uint128::from(25u128)
+        );
+        assert!(contract
+            .store
+            .load_vesting_tranches(worker)
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Tests that rewards of recipients with a registered IBC payout are sent over IBC, while all other recipients are paid locally
+    #[test]
+    fn payout_msgs_over_ibc() {
+        let mut contract = setup(0, 0, 1000);
+        let worker1 = Addr::unchecked("worker1");
+        let worker2 = Addr::unchecked("worker2");
+        let pool = Addr::unchecked("pool");
+        let payout = IbcPayout {
+            channel_id: "channel-0".try_into().unwrap(),
+            remote_address: "cosmos1remote".try_into().unwrap(),
+        };
+
+        contract
+            .set_ibc_payout(worker1.clone(), payout.clone())
+            .unwrap();
+
+        let env = mock_env();
+        let rewards = HashMap::from([
+            (worker1.clone(), Uint128::from(10u128)),
+            (worker2.clone(), Uint128::from(20u128)),
+        ]);
+        let msgs = contract
+            .payout_msgs(rewards.clone(), Some(pool.clone()), &env)
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                SubMsg::reply_on_success(
+                    ics20::transfer_msg(
+                        &payout.channel_id,
+                        env.contract.address.as_str(),
+                        &payout.remote_address,
+                        &Coin::new(10, "AXL"),
+                        env.block
+                            .time
+                            .plus_seconds(super::IBC_TRANSFER_TIMEOUT_SECONDS)
+                    ),
+                    IBC_TRANSFER_REPLY_ID
+                ),
+                SubMsg::new(BankMsg::Send {
+                    to_address: worker2.to_string(),
+                    amount: vec![Coin::new(20, "AXL")],
+                }),
+            ]
+        );
+        assert_eq!(
+            contract.store.pop_sent_ibc_transfer().unwrap(),
+            Some(IbcTransfer {
+                channel_id: "channel-0".to_string(),
+                recipient: worker1.clone(),
+                amount: Uint128::from(10u128),
+                pool: Some(pool),
+            })
+        );
+
+        contract.remove_ibc_payout(worker1.clone()).unwrap();
+        let msgs = contract.payout_msgs(rewards, None, &env).unwrap();
+        assert_eq!(
+            msgs[0],
+            SubMsg::new(BankMsg::Send {
+                to_address: worker1.to_string(),
+                amount: vec![Coin::new(10, "AXL")],
+            })
+        );
+        assert_eq!(contract.store.pop_sent_ibc_transfer().unwrap(), None);
+
+        assert!(matches!(
+            contract
+                .remove_ibc_payout(worker1)
+                .unwrap_err()
+                .current_context(),
+            ContractError::IbcPayoutNotFound
+        ));
     }
 
-    /// Tests that rewards are added correctly with multiple contracts
+    /// Tests that failed and timed out IBC payouts are credited back to the pool they were distributed from,
+    /// or to the vesting ledger if they were claimed from it, while successful ones are only cleaned up
     #[test]
-    fn added_rewards_for_multiple_contracts_should_be_reflected_in_multiple_pools() {
-        let cur_epoch_num = 1u64;
-        let block_height_started = 250u64;
-        let epoch_duration = 100u64;
+    fn conclude_ibc_transfer_credits_refunds() {
+        let mut contract = setup(0, 0, 100);
+        let worker = Addr::unchecked("worker");
+        let pool = Addr::unchecked("pool");
+        contract
+            .set_ibc_payout(
+                worker.clone(),
+                IbcPayout {
+                    channel_id: "channel-0".try_into().unwrap(),
+                    remote_address: "cosmos1remote".try_into().unwrap(),
+                },
+            )
+            .unwrap();
 
-        let mut contract = setup(cur_epoch_num, block_height_started, epoch_duration);
-        // a vector of (worker contract, rewards amounts) pairs
-        let test_data = vec![
-            (Addr::unchecked("contract_1"), vec![100, 200, 50]),
-            (Addr::unchecked("contract_2"), vec![25, 500, 70]),
-            (Addr::unchecked("contract_3"), vec![1000, 500, 2000]),
-        ];
+        let env = mock_env();
+        let rewards = HashMap::from([(worker.clone(), Uint128::from(10u128))]);
+        for sequence in 1..=3 {
+            contract
+                .payout_msgs(rewards.clone(), Some(pool.clone()), &env)
+                .unwrap();
+            contract.record_ibc_transfer_sequence(sequence).unwrap();
+        }
+        contract.payout_msgs(rewards, None, &env).unwrap();
+        contract.record_ibc_transfer_sequence(4).unwrap();
 
-        for (worker_contract, rewards) in &test_data {
-            for amount in rewards {
-                contract
-                    .add_rewards(
-                        worker_contract.clone(),
-                        cosmwasm_std::Uint128::from(*amount).try_into().unwrap(),
-                    )
-                    .unwrap();
-            }
+        let block = block_at(150);
+        assert!(contract
+            .conclude_ibc_transfer("channel-0".to_string(), 1, true, &block)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            contract
+                .store
+                .load_rewards_pool(pool.clone())
+                .unwrap()
+                .balance,
+            Uint128::zero()
+        );
+
+        for sequence in 2..=3 {
+            let event = contract
+                .conclude_ibc_transfer("channel-0".to_string(), sequence, false, &block)
+                .unwrap();
+            assert!(matches!(
+                event,
+                Some(ContractEvent::IbcTransferRefunded { recipient, amount, pool: Some(refunded_pool) })
+                    if recipient == worker && amount == Uint128::from(10u128) && refunded_pool == pool
+            ));
         }
+        assert_eq!(
+            contract.store.load_rewards_pool(pool).unwrap().balance,
+            Uint128::from(20u128)
+        );
 
-        for (worker_contract, rewards) in test_data {
-            let pool = contract.store.load_rewards_pool(worker_contract).unwrap();
-            assert_eq!(
-                pool.balance,
-                cosmwasm_std::Uint128::from(rewards.iter().sum::<u128>())
-            );
+        contract
+            .conclude_ibc_transfer("channel-0".to_string(), 4, false, &block)
+            .unwrap();
+        assert_eq!(
+            contract.claim_vested(worker, &block).unwrap(),
+            Uint128::from(10u128)
+        );
+
+        match contract.conclude_ibc_transfer("channel-0".to_string(), 1, false, &block) {
+            Err(err) => assert!(matches!(
+                err.current_context(),
+                ContractError::IbcTransferNotFound { .. }
+            )),
+            Ok(_) => panic!("expected concluded transfer to be removed"),
         }
     }
 
-    /// Tests that rewards are distributed correctly based on participation
+    /// Tests that only recipients with a registered payout hook get notified, and that the notifications are isolated from the payout
     #[test]
-    fn distribute_rewards() {
-        let cur_epoch_num = 0u64;
-        let block_height_started = 0u64;
-        let epoch_duration = 1000u64;
-        let rewards_per_epoch = 100u128;
-        let participation_threshold = (2, 3);
-
-        let mut contract = setup_with_params(
-            cur_epoch_num,
-            block_height_started,
-            epoch_duration,
-            rewards_per_epoch,
-            participation_threshold,
-        );
+    fn payout_hook_msgs() {
+        let mut contract = setup(0, 0, 1000);
         let worker1 = Addr::unchecked("worker1");
         let worker2 = Addr::unchecked("worker2");
-        let worker3 = Addr::unchecked("worker3");
-        let worker4 = Addr::unchecked("worker4");
-        let epoch_count = 4;
-        // Simulate 4 epochs worth of events with 4 workers
-        // Each epoch has 3 possible events to participate in
-        // The integer values represent which events a specific worker participated in during that epoch
-        // Events in different epochs are considered distinct; we append the epoch number when generating the event id
-        // The below participation corresponds to the following:
-        // 2 workers rewarded in epoch 0, no workers in epoch 1 (no events in that epoch), no workers in epoch 2 (but still some events), and then 4 (all) workers in epoch 3
-        let worker_participation_per_epoch = HashMap::from([
-            (
-                worker1.clone(),
-                [vec![1, 2, 3], vec![], vec![1], vec![2, 3]], // represents the worker participated in events 1,2 and 3 in epoch 0, no events in epoch 1, event 1 in epoch 2, and events 2 and 3 in epoch 3
-            ),
-            (worker2.clone(), [vec![], vec![], vec![2], vec![1, 2, 3]]),
-            (worker3.clone(), [vec![1, 2], vec![], vec![3], vec![1, 2]]),
-            (worker4.clone(), [vec![1], vec![], vec![2], vec![2, 3]]),
-        ]);
-        // The expected rewards per worker over all 4 epochs. Based on the above participation
-        let expected_rewards_per_worker: HashMap<Addr, u128> = HashMap::from([
-            (
-                worker1.clone(),
-                rewards_per_epoch / 2 + rewards_per_epoch / 4,
-            ),
-            (worker2.clone(), rewards_per_epoch / 4),
-            (
-                worker3.clone(),
-                rewards_per_epoch / 2 + rewards_per_epoch / 4,
-            ),
-            (worker4.clone(), rewards_per_epoch / 4),
-        ]);
-        let contract_addr = Addr::unchecked("worker_contract");
+        let hook = Addr::unchecked("restaking");
 
-        for (worker, events_participated) in worker_participation_per_epoch.clone() {
-            for epoch in 0..epoch_count {
-                for event in &events_participated[epoch] {
-                    let event_id = event.to_string() + &epoch.to_string() + "event";
-                    let _ = contract.record_participation(
-                        event_id.clone().try_into().unwrap(),
-                        worker.clone(),
-                        contract_addr.clone(),
-                        block_height_started + epoch as u64 * epoch_duration,
-                    );
-                }
-            }
-        }
+        contract
+            .set_payout_hook(worker1.clone(), hook.clone())
+            .unwrap();
 
-        // we add 2 epochs worth of rewards. There were 2 epochs of participation, but only 2 epochs where rewards should be given out
-        // This tests we are accounting correctly, and only removing from the pool when we actually give out rewards
-        let rewards_added = 2 * rewards_per_epoch;
-        let _ = contract.add_rewards(
-            contract_addr.clone(),
-            Uint128::from(rewards_added).try_into().unwrap(),
+        let rewards = HashMap::from([
+            (worker1.clone(), Uint128::from(10u128)),
+            (worker2.clone(), Uint128::from(20u128)),
+        ]);
+        let msgs = contract.payout_hook_msgs(&rewards).unwrap();
+        assert_eq!(
+            msgs,
+            vec![SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: hook.to_string(),
+                    msg: to_binary(&PayoutHookMsg::RewardsPaid {
+                        recipient: worker1.clone(),
+                        amount: Coin::new(10, "AXL"),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                },
+                PAYOUT_HOOK_REPLY_ID,
+            )
+            .with_gas_limit(super::PAYOUT_HOOK_GAS_LIMIT)]
         );
 
-        let rewards_claimed = contract
-            .distribute_rewards(
-                contract_addr,
-                block_height_started + epoch_duration * (epoch_count + 2) as u64,
-                None,
-            )
-            .unwrap();
+        contract.remove_payout_hook(worker1.clone()).unwrap();
+        assert!(contract.payout_hook_msgs(&rewards).unwrap().is_empty());
 
-        assert_eq!(rewards_claimed.len(), worker_participation_per_epoch.len());
-        for (worker, rewards) in expected_rewards_per_worker {
-            assert!(rewards_claimed.contains_key(&worker));
-            assert_eq!(rewards_claimed.get(&worker), Some(&Uint128::from(rewards)));
-        }
+        assert!(matches!(
+            contract
+                .remove_payout_hook(worker1)
+                .unwrap_err()
+                .current_context(),
+            ContractError::PayoutHookNotFound
+        ));
     }
 
     /// Tests that rewards are distributed correctly for a specified number of epochs, and that pagination works correctly
@@ -903,6 +2448,7 @@ mod test {
         );
         let worker = Addr::unchecked("worker");
         let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
 
         for height in block_height_started..block_height_started + epoch_duration * 9 {
             let event_id = height.to_string() + "event";
@@ -910,7 +2456,8 @@ mod test {
                 event_id.try_into().unwrap(),
                 worker.clone(),
                 contract_addr.clone(),
-                height,
+                false,
+                &block_at(height),
             );
         }
 
@@ -927,7 +2474,11 @@ mod test {
         // distribute 5 epochs worth of rewards
         let epochs_to_process = 5;
         let rewards_claimed = contract
-            .distribute_rewards(contract_addr.clone(), cur_height, Some(epochs_to_process))
+            .distribute_rewards(
+                contract_addr.clone(),
+                &block_at(cur_height),
+                Some(epochs_to_process),
+            )
             .unwrap();
         assert_eq!(rewards_claimed.len(), 1);
         assert!(rewards_claimed.contains_key(&worker));
@@ -938,7 +2489,7 @@ mod test {
 
         // distribute the remaining epochs worth of rewards
         let rewards_claimed = contract
-            .distribute_rewards(contract_addr.clone(), cur_height, None)
+            .distribute_rewards(contract_addr.clone(), &block_at(cur_height), None)
             .unwrap();
         assert_eq!(rewards_claimed.len(), 1);
         assert!(rewards_claimed.contains_key(&worker));
@@ -951,6 +2502,61 @@ mod test {
         );
     }
 
+    /// Tests that epochs without events don't count towards the epoch count, so distribution jumps directly between non-empty epochs
+    #[test]
+    fn distribute_rewards_skips_empty_epochs() {
+        let block_height_started = 0u64;
+        let epoch_duration = 1000u64;
+        let rewards_per_epoch = 100u128;
+
+        let mut contract = setup_with_params(
+            0,
+            block_height_started,
+            epoch_duration,
+            rewards_per_epoch,
+            (1, 2),
+        );
+        let worker = Addr::unchecked("worker");
+        let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
+
+        let active_epochs = [0u64, 5000];
+        for epoch in active_epochs {
+            contract
+                .record_participation(
+                    format!("event in epoch {}", epoch).try_into().unwrap(),
+                    worker.clone(),
+                    contract_addr.clone(),
+                    false,
+                    &block_at(block_height_started + epoch_duration * epoch),
+                )
+                .unwrap();
+        }
+
+        contract
+            .add_rewards(
+                contract_addr.clone(),
+                Uint128::from(rewards_per_epoch * 2).try_into().unwrap(),
+            )
+            .unwrap();
+
+        let cur_height = block_height_started + epoch_duration * 5002;
+        for _ in active_epochs {
+            let rewards = contract
+                .distribute_rewards(contract_addr.clone(), &block_at(cur_height), Some(1))
+                .unwrap();
+            assert_eq!(
+                rewards,
+                HashMap::from([(worker.clone(), Uint128::from(rewards_per_epoch))])
+            );
+        }
+
+        let err = contract
+            .distribute_rewards(contract_addr, &block_at(cur_height), Some(1))
+            .unwrap_err();
+        assert_eq!(err.current_context(), &ContractError::NoRewardsToDistribute);
+    }
+
     /// Tests that we do not distribute rewards for a given epoch until two epochs later
     #[test]
     fn distribute_rewards_too_early() {
@@ -969,12 +2575,14 @@ mod test {
         );
         let worker = Addr::unchecked("worker");
         let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
 
         let _ = contract.record_participation(
             "event".try_into().unwrap(),
             worker.clone(),
             contract_addr.clone(),
-            block_height_started,
+            false,
+            &block_at(block_height_started),
         );
 
         let rewards_added = 1000u128;
@@ -985,7 +2593,7 @@ mod test {
 
         // too early, still in the same epoch
         let err = contract
-            .distribute_rewards(contract_addr.clone(), block_height_started, None)
+            .distribute_rewards(contract_addr.clone(), &block_at(block_height_started), None)
             .unwrap_err();
         assert_eq!(err.current_context(), &ContractError::NoRewardsToDistribute);
 
@@ -993,7 +2601,7 @@ mod test {
         let err = contract
             .distribute_rewards(
                 contract_addr.clone(),
-                block_height_started + epoch_duration,
+                &block_at(block_height_started + epoch_duration),
                 None,
             )
             .unwrap_err();
@@ -1003,7 +2611,7 @@ mod test {
         let rewards_claimed = contract
             .distribute_rewards(
                 contract_addr.clone(),
-                block_height_started + epoch_duration * 2,
+                &block_at(block_height_started + epoch_duration * 2),
                 None,
             )
             .unwrap();
@@ -1013,7 +2621,7 @@ mod test {
         let err = contract
             .distribute_rewards(
                 contract_addr,
-                block_height_started + epoch_duration * 2,
+                &block_at(block_height_started + epoch_duration * 2),
                 None,
             )
             .unwrap_err();
@@ -1039,12 +2647,14 @@ mod test {
         );
         let worker = Addr::unchecked("worker");
         let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
 
         let _ = contract.record_participation(
             "event".try_into().unwrap(),
             worker.clone(),
             contract_addr.clone(),
-            block_height_started,
+            false,
+            &block_at(block_height_started),
         );
 
         // rewards per epoch is 100, we only add 10
@@ -1057,7 +2667,7 @@ mod test {
         let err = contract
             .distribute_rewards(
                 contract_addr.clone(),
-                block_height_started + epoch_duration * 2,
+                &block_at(block_height_started + epoch_duration * 2),
                 None,
             )
             .unwrap_err();
@@ -1074,7 +2684,7 @@ mod test {
 
         let result = contract.distribute_rewards(
             contract_addr,
-            block_height_started + epoch_duration * 2,
+            &block_at(block_height_started + epoch_duration * 2),
             None,
         );
         assert!(result.is_ok());
@@ -1099,12 +2709,14 @@ mod test {
         );
         let worker = Addr::unchecked("worker");
         let contract_addr = Addr::unchecked("worker_contract");
+        create_pool(&mut contract, &contract_addr);
 
         let _ = contract.record_participation(
             "event".try_into().unwrap(),
             worker.clone(),
             contract_addr.clone(),
-            block_height_started,
+            false,
+            &block_at(block_height_started),
         );
 
         let rewards_added = 1000u128;
@@ -1116,7 +2728,7 @@ mod test {
         let rewards_claimed = contract
             .distribute_rewards(
                 contract_addr.clone(),
-                block_height_started + epoch_duration * 2,
+                &block_at(block_height_started + epoch_duration * 2),
                 None,
             )
             .unwrap();
@@ -1126,19 +2738,51 @@ mod test {
         let err = contract
             .distribute_rewards(
                 contract_addr,
-                block_height_started + epoch_duration * 2,
+                &block_at(block_height_started + epoch_duration * 2),
                 None,
             )
             .unwrap_err();
         assert_eq!(err.current_context(), &ContractError::NoRewardsToDistribute);
     }
 
+    const BLOCK_TIME_SECONDS: u64 = 5;
+
+    /// Returns a block at the given height, assuming a constant block time
+    fn block_at(height: u64) -> BlockInfo {
+        BlockInfo {
+            height,
+            time: Timestamp::from_seconds(height * BLOCK_TIME_SECONDS),
+            chain_id: "test-chain".to_string(),
+        }
+    }
+
+    fn create_pool(contract: &mut Contract<state::MockStore>, pool_contract: &Addr) {
+        let governance = contract.config.governance.clone();
+        contract
+            .create_pool(
+                pool_contract.clone(),
+                PoolMetadata {
+                    chain_name: Some("ethereum".try_into().unwrap()),
+                    description: "test pool".to_string(),
+                },
+                governance,
+            )
+            .unwrap();
+    }
+
     fn create_contract(
         params_store: Arc<RwLock<StoredParams>>,
         events_store: Arc<RwLock<HashMap<(String, Addr), Event>>>,
         tally_store: Arc<RwLock<HashMap<(Addr, u64), EpochTally>>>,
         rewards_store: Arc<RwLock<HashMap<Addr, RewardsPool>>>,
         watermark_store: Arc<RwLock<HashMap<Addr, u64>>>,
+        worker_stats_store: Arc<RwLock<HashMap<(Addr, Addr), WorkerStats>>>,
+        delegations_store: Arc<RwLock<HashMap<Addr, Addr>>>,
+        ibc_payouts_store: Arc<RwLock<HashMap<Addr, IbcPayout>>>,
+        payout_hooks_store: Arc<RwLock<HashMap<Addr, Addr>>>,
+        observed_epoch_store: Arc<RwLock<Option<Epoch>>>,
+        vesting_store: Arc<RwLock<HashMap<Addr, Vec<VestingTranche>>>>,
+        pool_metadata_store: Arc<RwLock<HashMap<Addr, PoolMetadata>>>,
     ) -> Contract<state::MockStore> {
         let mut store = state::MockStore::new();
         let params_store_cloned = params_store.clone();
@@ -1170,6 +2814,21 @@ mod test {
                 let tally_store = tally_store_cloned.read().unwrap();
                 Ok(tally_store.get(&(contract, epoch_num)).cloned())
             });
+        let tally_store_cloned = tally_store.clone();
+        store
+            .expect_load_epoch_tallies()
+            .returning(move |contract, from, to, limit| {
+                let tally_store = tally_store_cloned.read().unwrap();
+                Ok(tally_store
+                    .iter()
+                    .filter(|((tally_contract, epoch_num), _)| {
+                        *tally_contract == contract && (from..=to).contains(epoch_num)
+                    })
+                    .sorted_by_key(|((_, epoch_num), _)| *epoch_num)
+                    .take(limit as usize)
+                    .map(|(_, tally)| tally.clone())
+                    .collect())
+            });
         store.expect_save_epoch_tally().returning(move |tally| {
             let mut tally_store = tally_store.write().unwrap();
             tally_store.insert(
@@ -1196,6 +2855,21 @@ mod test {
             Ok(())
         });
 
+        let pool_metadata_store_cloned = pool_metadata_store.clone();
+        store
+            .expect_load_pool_metadata()
+            .returning(move |contract| {
+                let pool_metadata_store = pool_metadata_store_cloned.read().unwrap();
+                Ok(pool_metadata_store.get(&contract).cloned())
+            });
+        store
+            .expect_save_pool_metadata()
+            .returning(move |contract, metadata| {
+                let mut pool_metadata_store = pool_metadata_store.write().unwrap();
+                pool_metadata_store.insert(contract, metadata.clone());
+                Ok(())
+            });
+
         let watermark_store_cloned = watermark_store.clone();
         store
             .expect_load_rewards_watermark()
@@ -1210,6 +2884,147 @@ mod test {
                 watermark_store.insert(contract, epoch_num);
                 Ok(())
             });
+
+        let worker_stats_store_cloned = worker_stats_store.clone();
+        store
+            .expect_load_worker_stats()
+            .returning(move |contract, worker| {
+                let worker_stats_store = worker_stats_store_cloned.read().unwrap();
+                Ok(worker_stats_store
+                    .get(&(contract, worker))
+                    .cloned()
+                    .unwrap_or_default())
+            });
+        store
+            .expect_save_worker_stats()
+            .returning(move |contract, worker, stats| {
+                let mut worker_stats_store = worker_stats_store.write().unwrap();
+                worker_stats_store.insert((contract, worker), stats.clone());
+                Ok(())
+            });
+
+        let delegations_store_cloned = delegations_store.clone();
+        store.expect_load_delegate().returning(move |worker| {
+            let delegations_store = delegations_store_cloned.read().unwrap();
+            Ok(delegations_store.get(&worker).cloned())
+        });
+        let delegations_store_cloned = delegations_store.clone();
+        store
+            .expect_save_delegate()
+            .returning(move |worker, delegate| {
+                let mut delegations_store = delegations_store_cloned.write().unwrap();
+                delegations_store.insert(worker, delegate);
+                Ok(())
+            });
+        store.expect_remove_delegate().returning(move |worker| {
+            let mut delegations_store = delegations_store.write().unwrap();
+            delegations_store.remove(&worker);
+        });
+
+        let ibc_payouts_store_cloned = ibc_payouts_store.clone();
+        store.expect_load_ibc_payout().returning(move |address| {
+            let ibc_payouts_store = ibc_payouts_store_cloned.read().unwrap();
+            Ok(ibc_payouts_store.get(&address).cloned())
+        });
+        let ibc_payouts_store_cloned = ibc_payouts_store.clone();
+        store
+            .expect_save_ibc_payout()
+            .returning(move |address, payout| {
+                let mut ibc_payouts_store = ibc_payouts_store_cloned.write().unwrap();
+                ibc_payouts_store.insert(address, payout.clone());
+                Ok(())
+            });
+        store.expect_remove_ibc_payout().returning(move |address| {
+            let mut ibc_payouts_store = ibc_payouts_store.write().unwrap();
+            ibc_payouts_store.remove(&address);
+        });
+
+        let sent_ibc_transfers_store = Arc::new(RwLock::new(VecDeque::new()));
+        let sent_ibc_transfers_store_cloned = sent_ibc_transfers_store.clone();
+        store
+            .expect_push_sent_ibc_transfer()
+            .returning(move |transfer| {
+                let mut sent_ibc_transfers_store = sent_ibc_transfers_store_cloned.write().unwrap();
+                sent_ibc_transfers_store.push_back(transfer.clone());
+                Ok(())
+            });
+        store.expect_pop_sent_ibc_transfer().returning(move || {
+            let mut sent_ibc_transfers_store = sent_ibc_transfers_store.write().unwrap();
+            Ok(sent_ibc_transfers_store.pop_front())
+        });
+
+        let ibc_transfers_store = Arc::new(RwLock::new(HashMap::new()));
+        let ibc_transfers_store_cloned = ibc_transfers_store.clone();
+        store
+            .expect_load_ibc_transfer()
+            .returning(move |channel_id, sequence| {
+                let ibc_transfers_store = ibc_transfers_store_cloned.read().unwrap();
+                Ok(ibc_transfers_store.get(&(channel_id, sequence)).cloned())
+            });
+        let ibc_transfers_store_cloned = ibc_transfers_store.clone();
+        store
+            .expect_save_ibc_transfer()
+            .returning(move |sequence, transfer: &IbcTransfer| {
+                let mut ibc_transfers_store = ibc_transfers_store_cloned.write().unwrap();
+                ibc_transfers_store
+                    .insert((transfer.channel_id.clone(), sequence), transfer.clone());
+                Ok(())
+            });
+        store
+            .expect_remove_ibc_transfer()
+            .returning(move |channel_id, sequence| {
+                let mut ibc_transfers_store = ibc_transfers_store.write().unwrap();
+                ibc_transfers_store.remove(&(channel_id, sequence));
+            });
+
+        let payout_hooks_store_cloned = payout_hooks_store.clone();
+        store.expect_load_payout_hook().returning(move |address| {
+            let payout_hooks_store = payout_hooks_store_cloned.read().unwrap();
+            Ok(payout_hooks_store.get(&address).cloned())
+        });
+        let payout_hooks_store_cloned = payout_hooks_store.clone();
+        store
+            .expect_save_payout_hook()
+            .returning(move |address, hook| {
+                let mut payout_hooks_store = payout_hooks_store_cloned.write().unwrap();
+                payout_hooks_store.insert(address, hook);
+                Ok(())
+            });
+        store.expect_remove_payout_hook().returning(move |address| {
+            let mut payout_hooks_store = payout_hooks_store.write().unwrap();
+            payout_hooks_store.remove(&address);
+        });
+
+        let observed_epoch_store_cloned = observed_epoch_store.clone();
+        store
+            .expect_load_last_observed_epoch()
+            .returning(move || Ok(observed_epoch_store_cloned.read().unwrap().clone()));
+        store
+            .expect_save_last_observed_epoch()
+            .returning(move |epoch| {
+                let mut observed_epoch_store = observed_epoch_store.write().unwrap();
+                *observed_epoch_store = Some(epoch.clone());
+                Ok(())
+            });
+
+        let vesting_store_cloned = vesting_store.clone();
+        store
+            .expect_load_vesting_tranches()
+            .returning(move |recipient| {
+                let vesting_store = vesting_store_cloned.read().unwrap();
+                Ok(vesting_store.get(&recipient).cloned().unwrap_or_default())
+            });
+        store
+            .expect_save_vesting_tranches()
+            .returning(move |recipient, tranches| {
+                let mut vesting_store = vesting_store.write().unwrap();
+                if tranches.is_empty() {
+                    vesting_store.remove(&recipient);
+                } else {
+                    vesting_store.insert(recipient, tranches.to_vec());
+                }
+                Ok(())
+            });
         Contract {
             store,
             config: Config {
@@ -1225,6 +3040,13 @@ mod test {
         tally_store: Arc<RwLock<HashMap<(Addr, u64), EpochTally>>>,
         rewards_store: Arc<RwLock<HashMap<Addr, RewardsPool>>>,
         watermark_store: Arc<RwLock<HashMap<Addr, u64>>>,
+        worker_stats_store: Arc<RwLock<HashMap<(Addr, Addr), WorkerStats>>>,
+        delegations_store: Arc<RwLock<HashMap<Addr, Addr>>>,
+        ibc_payouts_store: Arc<RwLock<HashMap<Addr, IbcPayout>>>,
+        payout_hooks_store: Arc<RwLock<HashMap<Addr, Addr>>>,
+        observed_epoch_store: Arc<RwLock<Option<Epoch>>>,
+        vesting_store: Arc<RwLock<HashMap<Addr, Vec<VestingTranche>>>>,
+        pool_metadata_store: Arc<RwLock<HashMap<Addr, PoolMetadata>>>,
     ) -> Contract<state::MockStore> {
         create_contract(
             params_store,
@@ -1232,6 +3054,13 @@ mod test {
             tally_store,
             rewards_store,
             watermark_store,
+            worker_stats_store,
+            delegations_store,
+            ibc_payouts_store,
+            payout_hooks_store,
+            observed_epoch_store,
+            vesting_store,
+            pool_metadata_store,
         )
     }
 
@@ -1248,12 +3077,18 @@ mod test {
         let current_epoch = Epoch {
             epoch_num: cur_epoch_num,
             block_height_started,
+            time_started: block_at(block_height_started).time,
         };
 
         let stored_params = StoredParams {
             params: Params {
                 participation_threshold: participation_threshold.try_into().unwrap(),
                 epoch_duration: epoch_duration.try_into().unwrap(),
+                epoch_mode: EpochMode::Blocks,
+                max_events_per_epoch: None,
+                vesting_epochs: None,
+                availability_bonus: None,
+                participation_carryover: None,
                 rewards_per_epoch,
             },
             last_updated: current_epoch.clone(),
@@ -1263,12 +3098,26 @@ mod test {
         let events_store = Arc::new(RwLock::new(HashMap::new()));
         let tally_store = Arc::new(RwLock::new(HashMap::new()));
         let watermark_store = Arc::new(RwLock::new(HashMap::new()));
+        let worker_stats_store = Arc::new(RwLock::new(HashMap::new()));
+        let delegations_store = Arc::new(RwLock::new(HashMap::new()));
+        let ibc_payouts_store = Arc::new(RwLock::new(HashMap::new()));
+        let payout_hooks_store = Arc::new(RwLock::new(HashMap::new()));
+        let observed_epoch_store = Arc::new(RwLock::new(None));
+        let vesting_store = Arc::new(RwLock::new(HashMap::new()));
+        let pool_metadata_store = Arc::new(RwLock::new(HashMap::new()));
         setup_with_stores(
             stored_params,
             events_store,
             tally_store,
             rewards_store,
             watermark_store,
+            worker_stats_store,
+            delegations_store,
+            ibc_payouts_store,
+            payout_hooks_store,
+            observed_epoch_store,
+            vesting_store,
+            pool_metadata_store,
         )
     }
 
@@ -0,0 +1,273 @@
+use axelar_wasm_std::nonempty;
+use cosmwasm_std::{Addr, BlockInfo, Decimal, Storage, Uint128};
+use error_stack::{Result, ResultExt};
+use itertools::Itertools;
+
+use crate::{
+    error::ContractError,
+    msg::{
+        Delegation, EpochMode, EpochTallyResponse, IbcPayout, PoolMetadata, RewardRateResponse,
+        WorkerParticipation, WorkerStats,
+    },
+    state::{self, StoredParams},
+};
+
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+pub const MAX_DELEGATIONS_LIMIT: u32 = 100;
+
+pub fn worker_stats(
+    storage: &dyn Storage,
+    contract: Addr,
+    worker: Addr,
+) -> Result<WorkerStats, ContractError> {
+    state::load_worker_stats(storage, contract, worker)
+}
+
+pub fn epoch_tally(
+    storage: &dyn Storage,
+    contract: Addr,
+    epoch_num: u64,
+) -> Result<EpochTallyResponse, ContractError> {
+    let tally = state::load_epoch_tally(storage, contract, epoch_num)?
+        .ok_or(ContractError::EpochTallyNotFound)?;
+
+    let participation = tally
+        .participation
+        .iter()
+        .sorted()
+        .map(|(worker, participated)| WorkerParticipation {
+            worker: Addr::unchecked(worker), // Ok to convert unchecked here, since we only store valid addresses
+            events_participated: *participated,
+            carryover: tally.carryover.get(worker).copied().unwrap_or_default(),
+            threshold_met: tally.meets_threshold(worker),
+            penalized: tally.penalized.contains(worker),
+            late_events_participated: tally
+                .late_participation
+                .get(worker)
+                .copied()
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(EpochTallyResponse {
+        event_count: tally.event_count,
+        participation,
+    })
+}
+
+pub fn delegations(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: u32,
+) -> Result<Vec<Delegation>, ContractError> {
+    state::load_delegations(storage, start_after, limit.min(MAX_DELEGATIONS_LIMIT))
+}
+
+pub fn ibc_payout(
+    storage: &dyn Storage,
+    address: Addr,
+) -> Result<Option<IbcPayout>, ContractError> {
+    state::load_ibc_payout(storage, address)
+}
+
+pub fn payout_hook(storage: &dyn Storage, address: Addr) -> Result<Option<Addr>, ContractError> {
+    state::load_payout_hook(storage, address)
+}
+
+pub fn pool_metadata(
+    storage: &dyn Storage,
+    contract: Addr,
+) -> Result<Option<PoolMetadata>, ContractError> {
+    state::load_pool_metadata(storage, contract)
+}
+
+pub fn reward_rate(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    contract: Addr,
+    bond: Option<nonempty::Uint128>,
+) -> Result<RewardRateResponse, ContractError> {
+    let stored_params = state::PARAMS
+        .load(storage)
+        .change_context(ContractError::LoadParams)?;
+    let rewards_per_epoch: Uint128 = stored_params.params.rewards_per_epoch.into();
+
+    let worker_count = state::load_latest_epoch_tally(storage, contract.clone())?
+        .map_or(0, |tally| tally.workers_to_reward().len() as u64);
+    let rewards_per_worker_per_epoch = rewards_per_epoch / Uint128::from(worker_count.max(1));
+
+    let pool = state::load_rewards_pool(storage, contract)?;
+    let runway_epochs =
+        u64::try_from((pool.balance / rewards_per_epoch).u128()).unwrap_or(u64::MAX);
+
+    let epochs_per_year = epochs_per_year(&stored_params, block);
+    let rewards_per_worker_per_year = epochs_per_year.map(|epochs| {
+        rewards_per_worker_per_epoch.saturating_mul(Uint128::from(epochs.min(runway_epochs)))
+    });
+    let annual_rate = bond
+        .zip(rewards_per_worker_per_year)
+        .and_then(|(bond, rewards)| Decimal::checked_from_ratio(rewards, bond).ok());
+
+    Ok(RewardRateResponse {
+        worker_count,
+        rewards_per_worker_per_epoch,
+        runway_epochs,
+        epochs_per_year,
+        rewards_per_worker_per_year,
+        annual_rate,
+    })
+}
+
+fn epochs_per_year(stored_params: &StoredParams, block: &BlockInfo) -> Option<u64> {
+    let epoch_duration: u64 = stored_params.params.epoch_duration.into();
+
+    let seconds_per_epoch = match stored_params.params.epoch_mode {
+        EpochMode::Seconds => epoch_duration,
+        EpochMode::Blocks => {
+            // the params update is the only point at which both height and time are recorded, so the block time is averaged since then
+            let last_updated = &stored_params.last_updated;
+            let blocks_elapsed = block
+                .height
+                .checked_sub(last_updated.block_height_started)
+                .filter(|blocks| *blocks > 0)?;
+            let seconds_elapsed = block
+                .time
+                .seconds()
+                .checked_sub(last_updated.time_started.seconds())?;
+
+            u64::try_from(
+                Uint128::from(epoch_duration)
+                    .multiply_ratio(seconds_elapsed, blocks_elapsed)
+                    .u128(),
+            )
+            .ok()?
+        }
+    };
+
+    SECONDS_PER_YEAR.checked_div(seconds_per_epoch)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+
+    use crate::msg::{EpochMode, Params};
+    use crate::state::{Epoch, EpochTally, RewardsPool, RewardsStore, Store, StoredParams, PARAMS};
+
+    use super::reward_rate;
+
+    fn params(epoch_mode: EpochMode, epoch_duration: u64) -> Params {
+        Params {
+            epoch_duration: epoch_duration.try_into().unwrap(),
+            epoch_mode,
+            rewards_per_epoch: Uint128::from(1000u128).try_into().unwrap(),
+            participation_threshold: (1, 2).try_into().unwrap(),
+            max_events_per_epoch: None,
+            vesting_epochs: None,
+            availability_bonus: None,
+            participation_carryover: None,
+        }
+    }
+
+    #[test]
+    fn reward_rate_in_seconds_mode() {
+        let mut deps = mock_dependencies();
+        let contract = Addr::unchecked("pool contract");
+        let epoch = Epoch {
+            epoch_num: 0,
+            block_height_started: 0,
+            time_started: Timestamp::from_seconds(0),
+        };
+        let params = params(EpochMode::Seconds, 24 * 60 * 60);
+
+        PARAMS
+            .save(
+                deps.as_mut().storage,
+                &StoredParams {
+                    params: params.clone(),
+                    last_updated: epoch.clone(),
+                },
+            )
+            .unwrap();
+
+        let mut store = RewardsStore {
+            storage: deps.as_mut().storage,
+        };
+        let tally = ["worker1", "worker2", "worker3", "worker4"]
+            .into_iter()
+            .fold(
+                EpochTally::new(contract.clone(), epoch, params),
+                |tally, worker| tally.record_participation(Addr::unchecked(worker), false),
+            );
+        store
+            .save_epoch_tally(&EpochTally {
+                event_count: 1,
+                ..tally
+            })
+            .unwrap();
+        store
+            .save_rewards_pool(&RewardsPool {
+                contract: contract.clone(),
+                balance: Uint128::from(100_000u128),
+            })
+            .unwrap();
+
+        let res = reward_rate(
+            deps.as_ref().storage,
+            &mock_env().block,
+            contract.clone(),
+            Some(Uint128::from(10_000u128).try_into().unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(res.worker_count, 4);
+        assert_eq!(res.rewards_per_worker_per_epoch, Uint128::from(250u128));
+        assert_eq!(res.runway_epochs, 100);
+        assert_eq!(res.epochs_per_year, Some(365));
+        // the pool can only fund 100 of the 365 epochs
+        assert_eq!(
+            res.rewards_per_worker_per_year,
+            Some(Uint128::from(25_000u128))
+        );
+        assert_eq!(res.annual_rate, Some(Decimal::from_ratio(25u128, 10u128)));
+    }
+
+    #[test]
+    fn reward_rate_in_blocks_mode() {
+        let mut deps = mock_dependencies();
+        let contract = Addr::unchecked("pool contract");
+        let mut block = mock_env().block;
+
+        PARAMS
+            .save(
+                deps.as_mut().storage,
+                &StoredParams {
+                    params: params(EpochMode::Blocks, 100),
+                    last_updated: Epoch {
+                        epoch_num: 0,
+                        block_height_started: block.height,
+                        time_started: block.time,
+                    },
+                },
+            )
+            .unwrap();
+
+        // block time can't be estimated yet
+        let res = reward_rate(deps.as_ref().storage, &block, contract.clone(), None).unwrap();
+        assert_eq!(res.worker_count, 0);
+        assert_eq!(res.rewards_per_worker_per_epoch, Uint128::from(1000u128));
+        assert_eq!(res.runway_epochs, 0);
+        assert_eq!(res.epochs_per_year, None);
+        assert_eq!(res.rewards_per_worker_per_year, None);
+        assert_eq!(res.annual_rate, None);
+
+        // 5 seconds per block, so 500 seconds per epoch
+        block.height += 10;
+        block.time = block.time.plus_seconds(50);
+        let res = reward_rate(deps.as_ref().storage, &block, contract, None).unwrap();
+        assert_eq!(res.epochs_per_year, Some(365 * 24 * 60 * 60 / 500));
+        assert_eq!(res.rewards_per_worker_per_year, Some(Uint128::zero()));
+    }
+}
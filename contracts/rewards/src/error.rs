@@ -18,6 +18,33 @@ pub enum ContractError {
     #[error("error saving rewards watermark")]
     SaveRewardsWatermark,
 
+    #[error("error saving worker stats")]
+    SaveWorkerStats,
+
+    #[error("error saving delegation")]
+    SaveDelegation,
+
+    #[error("error saving ibc payout")]
+    SaveIbcPayout,
+
+    #[error("error saving last observed epoch")]
+    SaveLastObservedEpoch,
+
+    #[error("error saving vesting tranches")]
+    SaveVestingTranches,
+
+    #[error("error saving pool metadata")]
+    SavePoolMetadata,
+
+    #[error("error saving payout hook")]
+    SavePayoutHook,
+
+    #[error("error saving ibc transfer")]
+    SaveIbcTransfer,
+
+    #[error("error loading params")]
+    LoadParams,
+
     #[error("error loading epoch tally")]
     LoadEpochTally,
 
@@ -30,24 +57,102 @@ pub enum ContractError {
     #[error("error loading rewards watermark")]
     LoadRewardsWatermark,
 
+    #[error("error loading worker stats")]
+    LoadWorkerStats,
+
+    #[error("error loading delegation")]
+    LoadDelegation,
+
+    #[error("error loading ibc payout")]
+    LoadIbcPayout,
+
+    #[error("error loading last observed epoch")]
+    LoadLastObservedEpoch,
+
+    #[error("error loading vesting tranches")]
+    LoadVestingTranches,
+
+    #[error("error loading pool metadata")]
+    LoadPoolMetadata,
+
+    #[error("error loading payout hook")]
+    LoadPayoutHook,
+
+    #[error("error loading ibc transfer")]
+    LoadIbcTransfer,
+
     #[error("invalid event id")]
     InvalidEventId,
 
+    #[error("maximum number of events for the current epoch reached")]
+    EventLimitReached,
+
+    #[error("event not found")]
+    EventNotFound,
+
+    #[error("epoch tally not found")]
+    EpochTallyNotFound,
+
+    #[error("rewards for the epoch have already been distributed")]
+    RewardsAlreadyDistributed,
+
     #[error("specified block has already passed")]
     BlockHeightInPast,
 
+    #[error("specified block time has already passed")]
+    BlockTimeInPast,
+
     #[error("rewards pool balance insufficient")]
     PoolBalanceInsufficient,
 
     #[error("no rewards to distribute")]
     NoRewardsToDistribute,
 
+    #[error("no vested rewards to claim")]
+    NoVestedRewards,
+
     #[error("caller is not authorized")]
     Unauthorized,
 
+    #[error("cannot delegate rewards to self")]
+    SelfDelegation,
+
+    #[error("no rewards delegation found")]
+    DelegationNotFound,
+
+    #[error("no ibc payout found")]
+    IbcPayoutNotFound,
+
+    #[error("channel {0} is not an open ics-20 channel")]
+    InvalidIbcChannel(String),
+
+    #[error("remote address {0} is not a bech32 address")]
+    InvalidRemoteAddress(String),
+
+    #[error("ibc transfer of channel {channel_id} with sequence {sequence} not found")]
+    IbcTransferNotFound { channel_id: String, sequence: u64 },
+
+    #[error("invalid ibc transfer response")]
+    InvalidIbcTransferResponse,
+
+    #[error("no payout hook found")]
+    PayoutHookNotFound,
+
+    #[error("rewards pool is already registered")]
+    PoolAlreadyExists,
+
+    #[error("rewards pool is not registered")]
+    PoolNotRegistered,
+
     #[error("wrong denom for rewards")]
     WrongDenom,
 
+    #[error("attached funds don't match the total amount of rewards")]
+    FundsMismatch,
+
+    #[error("total amount of rewards overflows")]
+    RewardsOverflow,
+
     #[error("rewards amount is zero")]
     ZeroRewards,
 }
@@ -0,0 +1,66 @@
+use cosmwasm_std::{Addr, Uint128};
+
+use crate::state::Epoch;
+
+pub enum Event {
+    // Emitted by the first execution that observes a new epoch. Epochs without any execution in them are skipped
+    EpochTransitioned {
+        old_epoch: Epoch,
+        new_epoch: Epoch,
+    },
+    // Emitted when the payout hook of a rewards recipient failed. The payout itself is not affected
+    PayoutHookFailed {
+        error: String,
+    },
+    // Emitted when an ICS-20 transfer of rewards failed or timed out, and the refund was credited back
+    IbcTransferRefunded {
+        recipient: Addr,
+        amount: Uint128,
+        pool: Option<Addr>,
+    },
+}
+
+impl From<Event> for cosmwasm_std::Event {
+    fn from(other: Event) -> Self {
+        match other {
+            Event::EpochTransitioned {
+                old_epoch,
+                new_epoch,
+            } => cosmwasm_std::Event::new("epoch_transitioned")
+                .add_attribute("old_epoch_num", old_epoch.epoch_num.to_string())
+                .add_attribute(
+                    "old_epoch_block_height_started",
+                    old_epoch.block_height_started.to_string(),
+                )
+                .add_attribute(
+                    "old_epoch_time_started",
+                    old_epoch.time_started.seconds().to_string(),
+                )
+                .add_attribute("new_epoch_num", new_epoch.epoch_num.to_string())
+                .add_attribute(
+                    "new_epoch_block_height_started",
+                    new_epoch.block_height_started.to_string(),
+                )
+                .add_attribute(
+                    "new_epoch_time_started",
+                    new_epoch.time_started.seconds().to_string(),
+                ),
+            Event::PayoutHookFailed { error } => {
+                cosmwasm_std::Event::new("payout_hook_failed").add_attribute("error", error)
+            }
+            Event::IbcTransferRefunded {
+                recipient,
+                amount,
+                pool,
+            } => {
+                let event = cosmwasm_std::Event::new("ibc_transfer_refunded")
+                    .add_attribute("recipient", recipient)
+                    .add_attribute("amount", amount);
+                match pool {
+                    Some(pool) => event.add_attribute("pool", pool),
+                    None => event,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use cosmwasm_std::{Coin, CosmosMsg, Timestamp};
+
+const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+pub const TRANSFER_PORT: &str = "transfer";
+pub const ICS20_VERSION: &str = "ics20-1";
+
+/// Creates an ICS-20 transfer that reports its outcome back to the sender. IbcMsg::Transfer can't carry a memo,
+/// so the transfer is sent as a raw MsgTransfer with the memo that tells the IBC hooks middleware which contract to call back
+pub fn transfer_msg(
+    channel_id: &str,
+    sender: &str,
+    receiver: &str,
+    amount: &Coin,
+    timeout: Timestamp,
+) -> CosmosMsg {
+    let mut coin = vec![];
+    encode_string(1, &amount.denom, &mut coin);
+    encode_string(2, &amount.amount.to_string(), &mut coin);
+
+    let mut value = vec![];
+    encode_string(1, TRANSFER_PORT, &mut value);
+    encode_string(2, channel_id, &mut value);
+    encode_bytes(3, &coin, &mut value);
+    encode_string(4, sender, &mut value);
+    encode_string(5, receiver, &mut value);
+    encode_varint_field(7, timeout.nanos(), &mut value);
+    encode_string(
+        8,
+        &format!(r#"{{"ibc_callback":"{}"}}"#, sender),
+        &mut value,
+    );
+
+    CosmosMsg::Stargate {
+        type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+        value: value.into(),
+    }
+}
+
+/// Decodes the packet sequence from the response of a MsgTransfer. The sequence identifies the transfer in the IBC hooks callback
+pub fn transfer_sequence(response: &[u8]) -> Option<u64> {
+    let mut bytes = response.iter().copied();
+    while let Some(key) = decode_varint(&mut bytes) {
+        match (key >> 3, key & 0x7) {
+            (1, 0) => return decode_varint(&mut bytes),
+            (_, 0) => {
+                decode_varint(&mut bytes)?;
+            }
+            (_, 2) => {
+                let len = decode_varint(&mut bytes)?;
+                for _ in 0..len {
+                    bytes.next()?;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+fn encode_string(field: u64, value: &str, buf: &mut Vec<u8>) {
+    encode_bytes(field, value.as_bytes(), buf)
+}
+
+fn encode_bytes(field: u64, value: &[u8], buf: &mut Vec<u8>) {
+    encode_varint(field << 3 | 2, buf);
+    encode_varint(value.len() as u64, buf);
+    buf.extend_from_slice(value);
+}
+
+fn encode_varint_field(field: u64, value: u64, buf: &mut Vec<u8>) {
+    encode_varint(field << 3, buf);
+    encode_varint(value, buf);
+}
+
+fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn decode_varint(bytes: &mut impl Iterator<Item = u8>) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = bytes.next()?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{Coin, CosmosMsg, HexBinary, Timestamp};
+
+    use super::{transfer_msg, transfer_sequence};
+
+    #[test]
+    fn transfer_msg_encodes_msg_transfer_with_callback_memo() {
+        let msg = transfer_msg(
+            "channel-0",
+            "rewards",
+            "cosmos1remote",
+            &Coin::new(300, "uaxl"),
+            Timestamp::from_seconds(1),
+        );
+
+        // encoded with prost from the MsgTransfer proto definition
+        let expected_value = HexBinary::from_hex("0a087472616e7366657212096368616e6e656c2d301a0b0a047561786c12033330302207726577617264732a0d636f736d6f733172656d6f7465388094ebdc03421a7b226962635f63616c6c6261636b223a2272657761726473227d").unwrap();
+        match msg {
+            CosmosMsg::Stargate { type_url, value } => {
+                assert_eq!(type_url, "/ibc.applications.transfer.v1.MsgTransfer");
+                assert_eq!(HexBinary::from(value.as_slice()), expected_value);
+            }
+            _ => panic!("unexpected msg"),
+        }
+    }
+
+    #[test]
+    fn transfer_sequence_decodes_msg_transfer_response() {
+        assert_eq!(transfer_sequence(&[0x08, 0x2a]), Some(42));
+        assert_eq!(transfer_sequence(&[0x08, 0xac, 0x02]), Some(300));
+        // unknown fields are skipped
+        assert_eq!(transfer_sequence(&[0x12, 0x01, 0xff, 0x08, 0x01]), Some(1));
+        assert_eq!(transfer_sequence(&[]), None);
+        assert_eq!(transfer_sequence(&[0x08, 0x80]), None);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod contract;
 pub mod error;
+mod events;
+mod ics20;
 pub mod msg;
 mod state;
@@ -1,5 +1,6 @@
 use axelar_wasm_std::{nonempty, Threshold};
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, Decimal, HexBinary, Uint128};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -10,10 +11,15 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub struct Params {
-    /// How often rewards are calculated, specified in number of blocks. Participation is calculated over this window. So if epoch_duration is 500
-    /// blocks, workers are rewarded for their participation within each 500 block window.
+    /// How often rewards are calculated, specified in number of blocks or seconds depending on epoch_mode. Participation is calculated over this window.
+    /// So if epoch_duration is 500 blocks, workers are rewarded for their participation within each 500 block window.
     pub epoch_duration: nonempty::Uint64,
 
+    /// Unit in which epoch_duration is measured. Block heights drift from wall clock time when block times change,
+    /// so epochs can alternatively be measured in seconds of block time. Defaults to blocks.
+    #[serde(default)]
+    pub epoch_mode: EpochMode,
+
     /// Total number of tokens distributed as rewards per epoch. Tokens are split equally amongst all participating workers for a given epoch
     pub rewards_per_epoch: nonempty::Uint128,
 
@@ -22,6 +28,55 @@ pub struct Params {
     /// and there are 100 events in a given epoch, workers must have participated in at least 90 events to receive rewards.
     /// Participation is reset at the beginning of each epoch, so participation in previous epochs does not affect rewards for future epochs.
     pub participation_threshold: Threshold,
+
+    /// Maximum number of distinct events that can be recorded per contract in a single epoch. Once reached, participation
+    /// in new events is rejected until the next epoch starts, while participation in already recorded events is still counted.
+    /// This bounds the storage growth and gas cost of tally updates a single contract can cause. No limit if not set.
+    #[serde(default)]
+    pub max_events_per_epoch: Option<nonempty::Uint64>,
+
+    /// Number of epochs over which distributed rewards vest. If set, distributed rewards are not sent right away, but unlock linearly
+    /// over this many epochs, starting with the epoch in which they are distributed, and recipients claim them with ClaimVested.
+    /// Rewards are sent immediately if not set.
+    #[serde(default)]
+    pub vesting_epochs: Option<nonempty::Uint64>,
+
+    /// Bonus for workers whose participation exceeds a second, higher threshold. The bonus is only paid out of the remainder
+    /// of rewards_per_epoch that can't be split equally amongst the rewarded workers, so it never increases the total rewards of an epoch.
+    /// No bonus if not set.
+    #[serde(default)]
+    pub availability_bonus: Option<AvailabilityBonus>,
+
+    /// Credit for workers that narrowly miss the participation threshold, carried into the next epoch. Smooths rewards for workers
+    /// whose participation was split unluckily by an epoch boundary. No credit is carried over if not set.
+    #[serde(default)]
+    pub participation_carryover: Option<ParticipationCarryover>,
+}
+
+#[cw_serde]
+pub struct AvailabilityBonus {
+    /// Participation threshold workers must meet to receive the bonus. Only has an effect if higher than participation_threshold
+    pub threshold: Threshold,
+    /// Bonus as a percentage of the regular reward per worker, so 20 multiplies the rewards of highly available workers by 1.2.
+    /// If the remainder can't cover the full bonus for all eligible workers, it is split equally amongst them instead
+    pub bonus_percent: nonempty::Uint64,
+}
+
+#[cw_serde]
+pub struct ParticipationCarryover {
+    /// Participation that is below participation_threshold, but meets this threshold, is a near miss. Only has an effect if lower than participation_threshold
+    pub near_miss_threshold: Threshold,
+    /// Fraction of a near miss participation that is credited to the worker's participation in the next epoch. Since credit is only carried
+    /// over on a near miss, and the credited participation is decayed again, credit fades out over consecutive epochs
+    pub decay: Threshold,
+}
+
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum EpochMode {
+    #[default]
+    Blocks,
+    Seconds,
 }
 
 #[cw_serde]
@@ -37,9 +92,22 @@ pub enum ExecuteMsg {
     RecordParticipation {
         event_id: nonempty::String,
         worker_address: String,
+        /// Whether the worker participated after the event had already reached quorum. Late participation counts
+        /// towards the participation threshold like any other, but is tracked separately in the epoch tally
+        #[serde(default)]
+        late: bool,
+    },
+
+    /// Penalize a worker for misbehaving in a specific event, such as voting incorrectly. The worker is excluded from rewards
+    /// for the epoch in which the event was recorded, and its share is split amongst the remaining rewarded workers.
+    /// Must be called by the same contract that recorded the event, and before rewards for that epoch are distributed.
+    PenalizeWorker {
+        event_id: nonempty::String,
+        worker_address: String,
     },
 
-    /// Distribute rewards up to epoch T - 2 (i.e. if we are currently in epoch 10, distribute all undistributed rewards for epochs 0-8) and send the required number of tokens to each worker
+    /// Distribute rewards up to epoch T - 2 (i.e. if we are currently in epoch 10, distribute all undistributed rewards for epochs 0-8) and send the required number of tokens to each worker,
+    /// or to the worker's delegate if the worker delegated its rewards. If vesting is enabled, the tokens are credited to the recipient's vesting ledger instead
     DistributeRewards {
         /// Address of contract for which to process rewards. For example, address of a voting verifier instance.
         contract_address: String,
@@ -47,6 +115,14 @@ pub enum ExecuteMsg {
         epoch_count: Option<u64>,
     },
 
+    /// Registers a rewards pool for the given contract along with descriptive metadata. Participation can only be recorded
+    /// for contracts with a registered pool. Callable only by governance.
+    CreatePool {
+        /// Address of contract for which to reward participation. For example, address of a voting verifier instance.
+        contract_address: String,
+        metadata: PoolMetadata,
+    },
+
     /// Start a new reward pool for the given contract if none exists. Otherwise, add tokens to an existing reward pool.
     /// Any attached funds with a denom matching the rewards denom are added to the pool.
     AddRewards {
@@ -54,10 +130,200 @@ pub enum ExecuteMsg {
         contract_address: String,
     },
 
+    /// Adds tokens to the reward pools of multiple contracts at once, starting new pools where none exist. The attached funds
+    /// must be exactly the sum of all amounts, in the rewards denom.
+    AddRewardsBatch {
+        /// Pairs of (contract address, amount to add to the pool of that contract)
+        pools: Vec<(String, nonempty::Uint128)>,
+    },
+
     /// Overwrites the currently stored params. Callable only by governance.
     UpdateParams { params: Params },
+
+    /// Delegates the rewards of the sender (a worker) to another address, such as a staking pool. Participation is still
+    /// tracked for the worker, but distributed rewards are sent to the delegate. Replaces any existing delegation of the sender.
+    DelegateRewards { delegate_address: String },
+
+    /// Removes the rewards delegation of the sender, so that rewards are sent to the worker itself again.
+    UndelegateRewards,
+
+    /// Registers an IBC channel and an address on the chain at the other end of that channel. Rewards distributed to the sender
+    /// are then sent to the remote address via an ICS-20 transfer instead of a local bank transfer. Replaces any existing registration of the sender.
+    /// The channel must be an open ICS-20 channel and the remote address a bech32 address.
+    /// If a transfer fails or times out, the refunded tokens are credited back to the pool they were distributed from,
+    /// or to the sender's vesting ledger if they were claimed from it. They are not re-sent.
+    SetIbcPayout {
+        channel_id: nonempty::String,
+        remote_address: nonempty::String,
+    },
+
+    /// Removes the IBC payout registration of the sender, so that rewards are sent to the sender on this chain again.
+    RemoveIbcPayout,
+
+    /// Sends all vested and not yet claimed rewards of the sender to the sender, or to its IBC payout destination if registered.
+    ClaimVested,
+
+    /// Registers a contract that is executed with PayoutHookMsg::RewardsPaid whenever rewards are paid out to the sender, for example
+    /// to restake them automatically. The notification runs with a small fixed gas limit, and if it fails, the payout still goes through.
+    /// Replaces any existing hook of the sender.
+    SetPayoutHook { contract_address: String },
+
+    /// Removes the payout hook of the sender, so that it is no longer notified of payouts.
+    RemovePayoutHook,
+}
+
+/// Event id under which participation in signing a digest with a key is recorded. It is derived from the key and the digest
+/// instead of the signing session, so participation in retried sessions for the same message only counts once
+pub fn signing_event_id(key_id: &str, digest: &[u8]) -> nonempty::String {
+    format!("{}_{}", key_id, HexBinary::from(digest).to_hex())
+        .try_into()
+        .expect("signing event id can't be empty")
+}
+
+/// Sent to the payout hook of a rewards recipient after rewards were paid out to that recipient.
+/// The hook contract needs to accept it as part of its own execute message.
+#[cw_serde]
+pub enum PayoutHookMsg {
+    RewardsPaid { recipient: Addr, amount: Coin },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    /// Gets the lifetime statistics of a worker for the given contract, as of the most recent rewards distribution
+    #[returns(WorkerStats)]
+    WorkerStats {
+        contract_address: String,
+        worker_address: String,
+    },
+
+    /// Gets up to `limit` current rewards delegations, ordered by worker address and starting after the worker `start_after`.
+    /// The limit is capped at 100
+    #[returns(Vec<Delegation>)]
+    Delegations {
+        start_after: Option<String>,
+        limit: u32,
+    },
+
+    /// Gets the participation recorded so far for the given contract in the given epoch. The tally is live until rewards for the epoch
+    /// are distributed, so the result can change for the current and the two previous epochs
+    #[returns(EpochTallyResponse)]
+    EpochTally {
+        pool_contract: String,
+        epoch_num: u64,
+    },
+
+    /// Gets the IBC payout registration of the given address, if any
+    #[returns(Option<IbcPayout>)]
+    IbcPayout { address: String },
+
+    /// Gets the payout hook registered by the given address, if any
+    #[returns(Option<Addr>)]
+    PayoutHook { address: String },
+
+    /// Gets the metadata of the rewards pool of the given contract, if the pool is registered
+    #[returns(Option<PoolMetadata>)]
+    PoolMetadata { contract_address: String },
+
+    /// Estimates the rewards a single worker earns from the given contract's pool, based on the current params, the number of workers
+    /// rewarded in the most recent epoch with recorded participation, and the pool balance. Meant for display purposes only
+    #[returns(RewardRateResponse)]
+    RewardRate {
+        pool_contract: String,
+        /// If set, the annualized rewards are also expressed as a rate relative to this bond
+        bond: Option<nonempty::Uint128>,
+    },
+}
+
+#[cw_serde]
+pub struct Delegation {
+    pub worker: Addr,
+    pub delegate: Addr,
+}
+
+#[cw_serde]
+pub struct EpochTallyResponse {
+    /// Number of distinct events recorded in the epoch
+    pub event_count: u64,
+    /// Participation of each worker that participated in at least one event, ordered by worker address
+    pub participation: Vec<WorkerParticipation>,
+}
+
+#[cw_serde]
+pub struct WorkerParticipation {
+    pub worker: Addr,
+    pub events_participated: u64,
+    /// Credit carried over from a near miss in the previous epoch, as a fraction of this epoch's events
+    pub carryover: Decimal,
+    /// Whether the worker's participation, including any carried over credit, meets the participation threshold, given the events recorded so far
+    pub threshold_met: bool,
+    /// Whether the worker was penalized in this epoch, which excludes it from rewards regardless of participation
+    pub penalized: bool,
+    /// Number of events the worker participated in after they had already reached quorum, included in events_participated
+    pub late_events_participated: u64,
+}
+
+#[cw_serde]
+pub struct RewardRateResponse {
+    /// Number of workers rewarded in the most recent epoch with recorded participation. If no worker has been rewarded yet,
+    /// the estimates are for a single worker
+    pub worker_count: u64,
+    pub rewards_per_worker_per_epoch: Uint128,
+    /// Number of full epochs the current pool balance can fund
+    pub runway_epochs: u64,
+    /// Estimated number of epochs per year. If epochs are measured in blocks, this is derived from the average block time since the params
+    /// were last updated, so it's not set until at least one block has passed since then
+    pub epochs_per_year: Option<u64>,
+    /// Rewards per worker over the next year, limited by the runway of the pool
+    pub rewards_per_worker_per_year: Option<Uint128>,
+    /// rewards_per_worker_per_year relative to the bond given in the query, so 0.1 means a yearly yield of 10%
+    pub annual_rate: Option<Decimal>,
+}
+
+/// Descriptive information about a rewards pool, set when the pool is registered
+#[cw_serde]
+pub struct PoolMetadata {
+    /// Chain served by the pool's contract. Not set for contracts serving multiple chains, such as multisig
+    pub chain_name: Option<nonempty::String>,
+    pub description: String,
+}
+
+/// Callbacks of the IBC hooks middleware, which reports the outcome of the ICS-20 transfers sent by this contract
+#[cw_serde]
+pub enum SudoMsg {
+    IbcLifecycleComplete(IbcLifecycleComplete),
+}
+
+#[cw_serde]
+pub enum IbcLifecycleComplete {
+    IbcAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    IbcTimeout {
+        channel: String,
+        sequence: u64,
+    },
+}
+
+/// Destination on a remote chain to which rewards are sent via ICS-20
+#[cw_serde]
+pub struct IbcPayout {
+    pub channel_id: nonempty::String,
+    pub remote_address: nonempty::String,
+}
+
+/// Cumulative participation and rewards of a single worker for a single contract. Updated each time rewards are distributed,
+/// so epochs that have not been distributed yet are not included.
+#[cw_serde]
+#[derive(Default)]
+pub struct WorkerStats {
+    /// Total number of events the worker participated in
+    pub events_participated: u64,
+    /// Total amount of rewards earned by the worker
+    pub rewards_earned: Uint128,
+    /// Number of epochs in which the worker met the participation threshold
+    pub epochs_above_threshold: u64,
+}
@@ -1,14 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::ops::Deref;
 
-use axelar_wasm_std::{nonempty, Threshold};
+use axelar_wasm_std::{nonempty, versioning::StateVersion, Threshold};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Storage, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Fraction, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Bound, Deque, Item, Map};
 use error_stack::{Result, ResultExt};
 use mockall::automock;
 
-use crate::{error::ContractError, msg::Params};
+use crate::{
+    error::ContractError,
+    msg::{Delegation, IbcPayout, Params, PoolMetadata, WorkerStats},
+};
 
 #[cw_serde]
 pub struct Config {
@@ -30,6 +33,14 @@ pub struct EpochTally {
     pub participation: HashMap<String, u64>, // maps a worker address to participation count. Can't use Addr as key else deserialization will fail
     pub epoch: Epoch,
     pub params: Params,
+    #[serde(default)]
+    pub penalized: HashSet<String>, // workers that misbehaved during this epoch and are excluded from rewards
+    #[serde(default)]
+    pub carryover: HashMap<String, Decimal>, // credit carried over from the previous epoch, as a fraction of this epoch's events. Only set once this epoch is distributed
+    #[serde(default)]
+    pub carried_over: Option<HashMap<String, Decimal>>, // credit carried into the next epoch, fixed when this epoch is distributed
+    #[serde(default)]
+    pub late_participation: HashMap<String, u64>, // maps a worker address to the part of its participation count that came after quorum
 }
 
 impl EpochTally {
@@ -40,16 +51,36 @@ impl EpochTally {
             participation: HashMap::new(),
             epoch,
             params,
+            penalized: HashSet::new(),
+            carryover: HashMap::new(),
+            carried_over: None,
+            late_participation: HashMap::new(),
         }
     }
 
+    pub fn with_carryover(self, carryover: HashMap<String, Decimal>) -> Self {
+        Self { carryover, ..self }
+    }
+
     /// IMPORTANT: worker address must be validated before calling this function
     /// TODO: panic if address is invalid?
-    pub fn record_participation(mut self, worker: Addr) -> Self {
+    pub fn record_participation(mut self, worker: Addr, late: bool) -> Self {
         self.participation
             .entry(worker.to_string())
             .and_modify(|count| *count += 1)
             .or_insert(1);
+        if late {
+            *self
+                .late_participation
+                .entry(worker.to_string())
+                .or_default() += 1;
+        }
+        self
+    }
+
+    /// IMPORTANT: worker address must be validated before calling this function
+    pub fn penalize(mut self, worker: Addr) -> Self {
+        self.penalized.insert(worker.to_string());
         self
     }
 
@@ -67,23 +98,133 @@ impl EpochTally {
             return HashMap::new();
         }
 
+        let remainder =
+            total_rewards - rewards_per_worker * Uint128::from(workers_to_reward.len() as u128);
+        let bonus_per_worker =
+            self.availability_bonus(&workers_to_reward, rewards_per_worker, remainder);
+
         workers_to_reward
             .into_iter()
-            .map(|worker| (worker, rewards_per_worker))
+            .map(|worker| {
+                let bonus = bonus_per_worker.get(&worker).copied().unwrap_or_default();
+                (worker, rewards_per_worker + bonus)
+            })
+            .collect()
+    }
+
+    /// Returns the bonus of each rewarded worker that meets the availability threshold, funded only from the given budget
+    fn availability_bonus(
+        &self,
+        workers_to_reward: &[Addr],
+        rewards_per_worker: Uint128,
+        budget: Uint128,
+    ) -> HashMap<Addr, Uint128> {
+        let bonus = match &self.params.availability_bonus {
+            Some(bonus) => bonus,
+            None => return HashMap::new(),
+        };
+
+        let highly_available: Vec<&Addr> = workers_to_reward
+            .iter()
+            .filter(|worker| {
+                self.participation
+                    .get(worker.as_str())
+                    .map_or(false, |participated| {
+                        self.participation_at_least(*participated, bonus.threshold)
+                    })
+            })
+            .collect();
+
+        let full_bonus = rewards_per_worker.multiply_ratio(u64::from(bonus.bonus_percent), 100u128);
+        let bonus_per_worker = budget
+            .checked_div(Uint128::from(highly_available.len() as u128))
+            .unwrap_or_default()
+            .min(full_bonus);
+
+        if bonus_per_worker.is_zero() {
+            return HashMap::new();
+        }
+
+        highly_available
+            .into_iter()
+            .map(|worker| (worker.clone(), bonus_per_worker))
             .collect()
     }
 
-    fn workers_to_reward(&self) -> Vec<Addr> {
+    pub fn workers_to_reward(&self) -> Vec<Addr> {
         self.participation
             .iter()
+            .filter(|(worker, _)| !self.penalized.contains(*worker))
+            .filter(|(worker, _)| self.meets_threshold(worker))
+            .map(|(worker, _)| Addr::unchecked(worker)) // Ok to convert unchecked here, since we only store valid addresses
+            .collect()
+    }
+
+    /// Returns true if the worker's participation, including any carried over credit, meets the participation threshold of this epoch
+    pub fn meets_threshold(&self, worker: &str) -> bool {
+        let participated = self.participation.get(worker).copied().unwrap_or_default();
+
+        match self.carryover.get(worker) {
+            Some(credit) if participated > 0 => {
+                self.participation_ratio(participated) + credit
+                    >= to_decimal(self.params.participation_threshold)
+            }
+            _ => self.participation_at_least(participated, self.params.participation_threshold),
+        }
+    }
+
+    /// Returns the credit that workers narrowly missing the participation threshold in this epoch carry into the next epoch.
+    /// Penalized workers don't carry over any credit
+    pub fn next_carryover(&self) -> HashMap<String, Decimal> {
+        let carryover = match &self.params.participation_carryover {
+            Some(carryover) => carryover,
+            None => return HashMap::new(),
+        };
+
+        self.participation
+            .iter()
+            .filter(|(worker, _)| !self.penalized.contains(*worker))
+            .filter(|(worker, _)| !self.meets_threshold(worker))
             .filter_map(|(worker, participated)| {
-                Threshold::try_from((*participated, self.event_count))
-                    .ok()
-                    .filter(|participation| participation >= &self.params.participation_threshold)
-                    .map(|_| Addr::unchecked(worker)) // Ok to convert unchecked here, since we only store valid addresses
+                let participation = self.participation_ratio(*participated)
+                    + self.carryover.get(worker).copied().unwrap_or_default();
+
+                (participation >= to_decimal(carryover.near_miss_threshold))
+                    .then(|| (worker.clone(), participation * to_decimal(carryover.decay)))
             })
             .collect()
     }
+
+    fn participation_ratio(&self, participated: u64) -> Decimal {
+        Decimal::checked_from_ratio(participated, self.event_count).unwrap_or_default()
+    }
+
+    fn participation_at_least(&self, participated: u64, threshold: Threshold) -> bool {
+        Threshold::try_from((participated, self.event_count))
+            .map(|participation| participation >= threshold)
+            .unwrap_or(false)
+    }
+}
+
+fn to_decimal(threshold: Threshold) -> Decimal {
+    Decimal::from_ratio(threshold.numerator(), threshold.denominator())
+}
+
+impl WorkerStats {
+    /// Adds the outcome of a single processed epoch to the worker's lifetime statistics
+    pub fn record_epoch(
+        mut self,
+        events_participated: u64,
+        above_threshold: bool,
+        rewards: Uint128,
+    ) -> Self {
+        self.events_participated += events_participated;
+        self.rewards_earned += rewards;
+        if above_threshold {
+            self.epochs_above_threshold += 1;
+        }
+        self
+    }
 }
 
 #[cw_serde]
@@ -91,6 +232,8 @@ pub struct Event {
     pub event_id: nonempty::String,
     pub contract: Addr,
     pub epoch_num: u64,
+    #[serde(default)]
+    pub participants: HashSet<String>, // workers whose participation in this event was recorded
 }
 
 impl Event {
@@ -99,14 +242,25 @@ impl Event {
             event_id,
             contract,
             epoch_num,
+            participants: HashSet::new(),
         }
     }
+
+    /// IMPORTANT: worker address must be validated before calling this function
+    pub fn add_participant(mut self, worker: &Addr) -> Self {
+        self.participants.insert(worker.to_string());
+        self
+    }
 }
 
 #[cw_serde]
 pub struct Epoch {
     pub epoch_num: u64,
+    /// Only tracked while epochs are measured in blocks. Otherwise, this is the height at which the params were last updated
     pub block_height_started: u64,
+    /// Only tracked while epochs are measured in seconds. Otherwise, this is the time at which the params were last updated
+    #[serde(default)]
+    pub time_started: Timestamp,
 }
 
 #[cw_serde]
@@ -134,6 +288,46 @@ impl RewardsPool {
     }
 }
 
+/// Rewards credited to a recipient in a single distribution, which unlock linearly over a number of epochs
+#[cw_serde]
+pub struct VestingTranche {
+    pub amount: Uint128,
+    pub claimed: Uint128,
+    pub start_epoch: u64,
+    pub vesting_epochs: u64,
+}
+
+impl VestingTranche {
+    /// Amount unlocked as of the given epoch, including the amount that has already been claimed
+    pub fn unlocked(&self, epoch_num: u64) -> Uint128 {
+        let epochs_elapsed = epoch_num
+            .saturating_sub(self.start_epoch)
+            .min(self.vesting_epochs);
+
+        self.amount
+            .multiply_ratio(epochs_elapsed, self.vesting_epochs)
+    }
+
+    pub fn claimable(&self, epoch_num: u64) -> Uint128 {
+        self.unlocked(epoch_num) - self.claimed
+    }
+
+    pub fn is_fully_claimed(&self) -> bool {
+        self.claimed == self.amount
+    }
+}
+
+/// ICS-20 transfer of rewards that hasn't been acknowledged yet. If it fails or times out, the refunded rewards
+/// are credited back to where they were paid out from
+#[cw_serde]
+pub struct IbcTransfer {
+    pub channel_id: String,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    /// Pool the rewards were distributed from, or None if the recipient claimed them from the vesting ledger
+    pub pool: Option<Addr>,
+}
+
 #[automock]
 pub trait Store {
     fn load_params(&self) -> StoredParams;
@@ -148,8 +342,39 @@ pub trait Store {
         epoch_num: u64,
     ) -> Result<Option<EpochTally>, ContractError>;
 
+    /// Loads up to `limit` tallies of the contract between epochs `from` and `to` (inclusive), in ascending epoch order.
+    /// Epochs without any events have no tally and are skipped
+    fn load_epoch_tallies(
+        &self,
+        contract: Addr,
+        from: u64,
+        to: u64,
+        limit: u64,
+    ) -> Result<Vec<EpochTally>, ContractError>;
+
     fn load_rewards_pool(&self, contract: Addr) -> Result<RewardsPool, ContractError>;
 
+    fn load_pool_metadata(&self, contract: Addr) -> Result<Option<PoolMetadata>, ContractError>;
+
+    fn load_worker_stats(&self, contract: Addr, worker: Addr)
+        -> Result<WorkerStats, ContractError>;
+
+    fn load_delegate(&self, worker: Addr) -> Result<Option<Addr>, ContractError>;
+
+    fn load_ibc_payout(&self, address: Addr) -> Result<Option<IbcPayout>, ContractError>;
+
+    fn load_payout_hook(&self, address: Addr) -> Result<Option<Addr>, ContractError>;
+
+    fn load_ibc_transfer(
+        &self,
+        channel_id: String,
+        sequence: u64,
+    ) -> Result<Option<IbcTransfer>, ContractError>;
+
+    fn load_last_observed_epoch(&self) -> Result<Option<Epoch>, ContractError>;
+
+    fn load_vesting_tranches(&self, recipient: Addr) -> Result<Vec<VestingTranche>, ContractError>;
+
     fn save_params(&mut self, params: &StoredParams) -> Result<(), ContractError>;
 
     fn save_rewards_watermark(
@@ -163,8 +388,61 @@ pub trait Store {
     fn save_epoch_tally(&mut self, tally: &EpochTally) -> Result<(), ContractError>;
 
     fn save_rewards_pool(&mut self, pool: &RewardsPool) -> Result<(), ContractError>;
+
+    fn save_pool_metadata(
+        &mut self,
+        contract: Addr,
+        metadata: &PoolMetadata,
+    ) -> Result<(), ContractError>;
+
+    fn save_worker_stats(
+        &mut self,
+        contract: Addr,
+        worker: Addr,
+        stats: &WorkerStats,
+    ) -> Result<(), ContractError>;
+
+    fn save_delegate(&mut self, worker: Addr, delegate: Addr) -> Result<(), ContractError>;
+
+    fn remove_delegate(&mut self, worker: Addr);
+
+    fn save_ibc_payout(&mut self, address: Addr, payout: &IbcPayout) -> Result<(), ContractError>;
+
+    fn remove_ibc_payout(&mut self, address: Addr);
+
+    /// Queues a transfer that is being sent, until its packet sequence is known
+    fn push_sent_ibc_transfer(&mut self, transfer: &IbcTransfer) -> Result<(), ContractError>;
+
+    /// Takes the oldest queued transfer. Transfers are sent in the order they are queued, so it is the one the sequence belongs to
+    fn pop_sent_ibc_transfer(&mut self) -> Result<Option<IbcTransfer>, ContractError>;
+
+    fn save_ibc_transfer(
+        &mut self,
+        sequence: u64,
+        transfer: &IbcTransfer,
+    ) -> Result<(), ContractError>;
+
+    fn remove_ibc_transfer(&mut self, channel_id: String, sequence: u64);
+
+    fn save_payout_hook(&mut self, address: Addr, hook: Addr) -> Result<(), ContractError>;
+
+    fn remove_payout_hook(&mut self, address: Addr);
+
+    fn save_last_observed_epoch(&mut self, epoch: &Epoch) -> Result<(), ContractError>;
+
+    /// Removes the recipient from the vesting ledger if there are no tranches left
+    fn save_vesting_tranches(
+        &mut self,
+        recipient: Addr,
+        tranches: &[VestingTranche],
+    ) -> Result<(), ContractError>;
 }
 
+/// Schema version of each storage namespace this code expects. Bump a version whenever its schema changes
+/// in a way that requires migrating stored data, and migrate the data in the migrate entry point
+pub const STATE_VERSIONS: [StateVersion; 4] =
+    [("config", 1), ("params", 1), ("pools", 2), ("tallies", 1)];
+
 /// Current rewards parameters, along with when the params were updated
 pub const PARAMS: Item<StoredParams> = Item::new("params");
 
@@ -177,12 +455,165 @@ const EVENTS: Map<(String, Addr), Event> = Map::new("events");
 /// Maps a contract address to the rewards pool for that contract
 const POOLS: Map<Addr, RewardsPool> = Map::new("pools");
 
+/// Maps a contract address to the metadata of its registered rewards pool. Participation is only recorded for contracts in this map
+const POOL_METADATA: Map<Addr, PoolMetadata> = Map::new("pool_metadata");
+
 /// Maps a contract address to the epoch number of the most recent epoch for which rewards were distributed. All epochs prior
 /// have had rewards distributed already and all epochs after have not yet had rewards distributed for this contract
 const WATERMARKS: Map<Addr, u64> = Map::new("rewards_watermarks");
 
+/// Maps a (contract address, worker address) pair to the lifetime statistics of that worker for that contract
+const WORKER_STATS: Map<(Addr, Addr), WorkerStats> = Map::new("worker_stats");
+
+/// Maps a worker address to the address its rewards are delegated to
+const DELEGATIONS: Map<Addr, Addr> = Map::new("delegations");
+
+/// Maps a rewards recipient address to the remote chain destination its rewards are sent to
+const IBC_PAYOUTS: Map<Addr, IbcPayout> = Map::new("ibc_payouts");
+
+/// Transfers that were sent in the current execution and whose packet sequence is not known yet
+const SENT_IBC_TRANSFERS: Deque<IbcTransfer> = Deque::new("sent_ibc_transfers");
+
+/// Maps a (channel id, packet sequence) pair to the transfer that is waiting for its acknowledgement
+const IBC_TRANSFERS: Map<(String, u64), IbcTransfer> = Map::new("ibc_transfers");
+
+/// Maps a rewards recipient address to the contract that is notified when rewards are paid out to it
+const PAYOUT_HOOKS: Map<Addr, Addr> = Map::new("payout_hooks");
+
+/// Maps a rewards recipient to the vesting tranches that haven't been fully claimed yet
+const VESTING_LEDGER: Map<Addr, Vec<VestingTranche>> = Map::new("vesting_ledger");
+
+/// The most recent epoch seen by any execution, used to detect epoch transitions
+const LAST_OBSERVED_EPOCH: Item<Epoch> = Item::new("last_observed_epoch");
+
 pub const CONFIG: Item<Config> = Item::new("config");
 
+pub(crate) fn load_worker_stats(
+    storage: &dyn Storage,
+    contract: Addr,
+    worker: Addr,
+) -> Result<WorkerStats, ContractError> {
+    WORKER_STATS
+        .may_load(storage, (contract, worker))
+        .change_context(ContractError::LoadWorkerStats)
+        .map(Option::unwrap_or_default)
+}
+
+pub(crate) fn load_epoch_tally(
+    storage: &dyn Storage,
+    contract: Addr,
+    epoch_num: u64,
+) -> Result<Option<EpochTally>, ContractError> {
+    TALLIES
+        .may_load(storage, (contract, epoch_num))
+        .change_context(ContractError::LoadEpochTally)
+}
+
+/// Loads the tally of the most recent epoch in which any participation was recorded for the contract
+pub(crate) fn load_latest_epoch_tally(
+    storage: &dyn Storage,
+    contract: Addr,
+) -> Result<Option<EpochTally>, ContractError> {
+    TALLIES
+        .prefix(contract)
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+        .map(|res| res.map(|(_, tally)| tally))
+        .change_context(ContractError::LoadEpochTally)
+}
+
+pub(crate) fn load_rewards_pool(
+    storage: &dyn Storage,
+    contract: Addr,
+) -> Result<RewardsPool, ContractError> {
+    POOLS
+        .may_load(storage, contract.clone())
+        .change_context(ContractError::LoadRewardsPool)
+        .map(|pool| {
+            pool.unwrap_or(RewardsPool {
+                contract,
+                balance: Uint128::zero(),
+            })
+        })
+}
+
+pub(crate) fn load_delegations(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: u32,
+) -> Result<Vec<Delegation>, ContractError> {
+    DELEGATIONS
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|res| res.map(|(worker, delegate)| Delegation { worker, delegate }))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .change_context(ContractError::LoadDelegation)
+}
+
+pub(crate) fn load_ibc_payout(
+    storage: &dyn Storage,
+    address: Addr,
+) -> Result<Option<IbcPayout>, ContractError> {
+    IBC_PAYOUTS
+        .may_load(storage, address)
+        .change_context(ContractError::LoadIbcPayout)
+}
+
+pub(crate) fn load_payout_hook(
+    storage: &dyn Storage,
+    address: Addr,
+) -> Result<Option<Addr>, ContractError> {
+    PAYOUT_HOOKS
+        .may_load(storage, address)
+        .change_context(ContractError::LoadPayoutHook)
+}
+
+/// Registers the pools that were created implicitly, by adding rewards or recording participation, before pools had to be registered.
+/// Their chain is unknown, so they are registered without one
+pub(crate) fn register_existing_pools(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let funded = POOLS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()
+        .change_context(ContractError::LoadRewardsPool)?;
+    let tallied = TALLIES
+        .keys(storage, None, None, Order::Ascending)
+        .map(|key| key.map(|(contract, _)| contract))
+        .collect::<StdResult<Vec<_>>>()
+        .change_context(ContractError::LoadEpochTally)?;
+
+    for contract in funded.into_iter().chain(tallied).collect::<BTreeSet<_>>() {
+        if load_pool_metadata(storage, contract.clone())?.is_none() {
+            POOL_METADATA
+                .save(
+                    storage,
+                    contract,
+                    &PoolMetadata {
+                        chain_name: None,
+                        description: "registered on migration".to_string(),
+                    },
+                )
+                .change_context(ContractError::SavePoolMetadata)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn load_pool_metadata(
+    storage: &dyn Storage,
+    contract: Addr,
+) -> Result<Option<PoolMetadata>, ContractError> {
+    POOL_METADATA
+        .may_load(storage, contract)
+        .change_context(ContractError::LoadPoolMetadata)
+}
+
 pub struct RewardsStore<'a> {
     pub storage: &'a mut dyn Storage,
 }
@@ -209,21 +640,82 @@ impl Store for RewardsStore<'_> {
         contract: Addr,
         epoch_num: u64,
     ) -> Result<Option<EpochTally>, ContractError> {
+        load_epoch_tally(self.storage, contract, epoch_num)
+    }
+
+    fn load_epoch_tallies(
+        &self,
+        contract: Addr,
+        from: u64,
+        to: u64,
+        limit: u64,
+    ) -> Result<Vec<EpochTally>, ContractError> {
+        // tallies are keyed by contract first, so the epochs of a contract's tallies act as a sorted index of non-empty epochs
         TALLIES
-            .may_load(self.storage, (contract, epoch_num))
+            .prefix(contract)
+            .range(
+                self.storage,
+                Some(Bound::inclusive(from)),
+                Some(Bound::inclusive(to)),
+                Order::Ascending,
+            )
+            .take(limit as usize)
+            .map(|res| res.map(|(_, tally)| tally))
+            .collect::<std::result::Result<Vec<_>, _>>()
             .change_context(ContractError::LoadEpochTally)
     }
 
     fn load_rewards_pool(&self, contract: Addr) -> Result<RewardsPool, ContractError> {
-        POOLS
-            .may_load(self.storage, contract.clone())
-            .change_context(ContractError::LoadRewardsPool)
-            .map(|pool| {
-                pool.unwrap_or(RewardsPool {
-                    contract,
-                    balance: Uint128::zero(),
-                })
-            })
+        load_rewards_pool(self.storage, contract)
+    }
+
+    fn load_pool_metadata(&self, contract: Addr) -> Result<Option<PoolMetadata>, ContractError> {
+        load_pool_metadata(self.storage, contract)
+    }
+
+    fn load_worker_stats(
+        &self,
+        contract: Addr,
+        worker: Addr,
+    ) -> Result<WorkerStats, ContractError> {
+        load_worker_stats(self.storage, contract, worker)
+    }
+
+    fn load_delegate(&self, worker: Addr) -> Result<Option<Addr>, ContractError> {
+        DELEGATIONS
+            .may_load(self.storage, worker)
+            .change_context(ContractError::LoadDelegation)
+    }
+
+    fn load_ibc_payout(&self, address: Addr) -> Result<Option<IbcPayout>, ContractError> {
+        load_ibc_payout(self.storage, address)
+    }
+
+    fn load_payout_hook(&self, address: Addr) -> Result<Option<Addr>, ContractError> {
+        load_payout_hook(self.storage, address)
+    }
+
+    fn load_ibc_transfer(
+        &self,
+        channel_id: String,
+        sequence: u64,
+    ) -> Result<Option<IbcTransfer>, ContractError> {
+        IBC_TRANSFERS
+            .may_load(self.storage, (channel_id, sequence))
+            .change_context(ContractError::LoadIbcTransfer)
+    }
+
+    fn load_last_observed_epoch(&self) -> Result<Option<Epoch>, ContractError> {
+        LAST_OBSERVED_EPOCH
+            .may_load(self.storage)
+            .change_context(ContractError::LoadLastObservedEpoch)
+    }
+
+    fn load_vesting_tranches(&self, recipient: Addr) -> Result<Vec<VestingTranche>, ContractError> {
+        VESTING_LEDGER
+            .may_load(self.storage, recipient)
+            .change_context(ContractError::LoadVestingTranches)
+            .map(Option::unwrap_or_default)
     }
 
     fn save_params(&mut self, params: &StoredParams) -> Result<(), ContractError> {
@@ -267,6 +759,108 @@ impl Store for RewardsStore<'_> {
             .save(self.storage, pool.contract.clone(), pool)
             .change_context(ContractError::SaveRewardsPool)
     }
+
+    fn save_pool_metadata(
+        &mut self,
+        contract: Addr,
+        metadata: &PoolMetadata,
+    ) -> Result<(), ContractError> {
+        POOL_METADATA
+            .save(self.storage, contract, metadata)
+            .change_context(ContractError::SavePoolMetadata)
+    }
+
+    fn save_worker_stats(
+        &mut self,
+        contract: Addr,
+        worker: Addr,
+        stats: &WorkerStats,
+    ) -> Result<(), ContractError> {
+        WORKER_STATS
+            .save(self.storage, (contract, worker), stats)
+            .change_context(ContractError::SaveWorkerStats)
+    }
+
+    fn save_delegate(&mut self, worker: Addr, delegate: Addr) -> Result<(), ContractError> {
+        DELEGATIONS
+            .save(self.storage, worker, &delegate)
+            .change_context(ContractError::SaveDelegation)
+    }
+
+    fn remove_delegate(&mut self, worker: Addr) {
+        DELEGATIONS.remove(self.storage, worker)
+    }
+
+    fn save_ibc_payout(&mut self, address: Addr, payout: &IbcPayout) -> Result<(), ContractError> {
+        IBC_PAYOUTS
+            .save(self.storage, address, payout)
+            .change_context(ContractError::SaveIbcPayout)
+    }
+
+    fn remove_ibc_payout(&mut self, address: Addr) {
+        IBC_PAYOUTS.remove(self.storage, address)
+    }
+
+    fn push_sent_ibc_transfer(&mut self, transfer: &IbcTransfer) -> Result<(), ContractError> {
+        SENT_IBC_TRANSFERS
+            .push_back(self.storage, transfer)
+            .change_context(ContractError::SaveIbcTransfer)
+    }
+
+    fn pop_sent_ibc_transfer(&mut self) -> Result<Option<IbcTransfer>, ContractError> {
+        SENT_IBC_TRANSFERS
+            .pop_front(self.storage)
+            .change_context(ContractError::LoadIbcTransfer)
+    }
+
+    fn save_ibc_transfer(
+        &mut self,
+        sequence: u64,
+        transfer: &IbcTransfer,
+    ) -> Result<(), ContractError> {
+        IBC_TRANSFERS
+            .save(
+                self.storage,
+                (transfer.channel_id.clone(), sequence),
+                transfer,
+            )
+            .change_context(ContractError::SaveIbcTransfer)
+    }
+
+    fn remove_ibc_transfer(&mut self, channel_id: String, sequence: u64) {
+        IBC_TRANSFERS.remove(self.storage, (channel_id, sequence))
+    }
+
+    fn save_payout_hook(&mut self, address: Addr, hook: Addr) -> Result<(), ContractError> {
+        PAYOUT_HOOKS
+            .save(self.storage, address, &hook)
+            .change_context(ContractError::SavePayoutHook)
+    }
+
+    fn remove_payout_hook(&mut self, address: Addr) {
+        PAYOUT_HOOKS.remove(self.storage, address)
+    }
+
+    fn save_last_observed_epoch(&mut self, epoch: &Epoch) -> Result<(), ContractError> {
+        LAST_OBSERVED_EPOCH
+            .save(self.storage, epoch)
+            .change_context(ContractError::SaveLastObservedEpoch)
+    }
+
+    fn save_vesting_tranches(
+        &mut self,
+        recipient: Addr,
+        tranches: &[VestingTranche],
+    ) -> Result<(), ContractError> {
+        if tranches.is_empty() {
+            VESTING_LEDGER.remove(self.storage, recipient);
+            return Ok(());
+        }
+
+        VESTING_LEDGER
+            .save(self.storage, recipient, &tranches.to_vec())
+            .change_context(ContractError::SaveVestingTranches)
+    }
 }
 
 pub(crate) enum StorageState<T> {
@@ -274,6 +868,15 @@ pub(crate) enum StorageState<T> {
     New(T),
 }
 
+impl<T> StorageState<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            StorageState::Existing(value) => value,
+            StorageState::New(value) => value,
+        }
+    }
+}
+
 impl<T> Deref for StorageState<T> {
     type Target = T;
 
@@ -287,14 +890,26 @@ impl<T> Deref for StorageState<T> {
 
 #[cfg(test)]
 mod test {
-    use super::{Epoch, EpochTally, Event, RewardsPool, RewardsStore, Store};
+    use super::{
+        load_delegations, load_ibc_payout, load_payout_hook, load_pool_metadata,
+        register_existing_pools, Epoch, EpochTally, Event, RewardsPool, RewardsStore, Store,
+    };
     use crate::error::ContractError;
-    use crate::{msg::Params, state::StoredParams};
-    use cosmwasm_std::{testing::mock_dependencies, Addr, Uint128, Uint64};
-    use std::collections::HashMap;
+    use crate::{
+        msg::{
+            AvailabilityBonus, Delegation, EpochMode, IbcPayout, Params, ParticipationCarryover,
+            PoolMetadata, WorkerStats,
+        },
+        state::StoredParams,
+    };
+    use axelar_wasm_std::test_utils::{addr, check_invariant, nonempty_uint128};
+    use cosmwasm_std::{testing::mock_dependencies, Addr, Decimal, Timestamp, Uint128, Uint64};
+    use proptest::prelude::*;
+    use std::collections::{HashMap, HashSet};
 
     /// Test that the rewards are
     /// - distributed evenly to all workers that reach quorum
+    /// - not distributed to penalized workers
     /// - no rewards if there are no workers
     /// - no rewards if rewards per epoch is too low for number of workers
     #[test]
@@ -302,6 +917,11 @@ mod test {
         let tally = EpochTally {
             params: Params {
                 epoch_duration: 100u64.try_into().unwrap(),
+                epoch_mode: EpochMode::Blocks,
+                max_events_per_epoch: None,
+                vesting_epochs: None,
+                availability_bonus: None,
+                participation_carryover: None,
                 rewards_per_epoch: Uint128::new(1000).try_into().unwrap(),
                 participation_threshold: (1, 2).try_into().unwrap(),
             },
@@ -315,7 +935,12 @@ mod test {
             epoch: Epoch {
                 epoch_num: 1u64,
                 block_height_started: 0u64,
+                time_started: Timestamp::from_seconds(0),
             },
+            penalized: HashSet::new(),
+            carryover: HashMap::new(),
+            carried_over: None,
+            late_participation: HashMap::new(),
         };
 
         let test_cases = vec![
@@ -327,6 +952,11 @@ mod test {
                     (Addr::unchecked("worker3"), Uint128::from(500u128)),
                 ]),
             ),
+            (
+                // penalized workers are excluded, and their share goes to the remaining workers
+                tally.clone().penalize(Addr::unchecked("worker3")),
+                HashMap::from([(Addr::unchecked("worker1"), Uint128::from(1000u128))]),
+            ),
             (
                 // no rewards if there are no workers
                 EpochTally {
@@ -354,6 +984,230 @@ mod test {
         }
     }
 
+    /// Test that the availability bonus is
+    /// - paid to rewarded workers meeting the bonus threshold
+    /// - funded only from the remainder, split equally if it can't cover the full bonus
+    /// - capped at the configured percentage of the regular reward
+    #[test]
+    fn rewards_by_worker_with_availability_bonus() {
+        let params = Params {
+            epoch_duration: 100u64.try_into().unwrap(),
+            epoch_mode: EpochMode::Blocks,
+            max_events_per_epoch: None,
+            vesting_epochs: None,
+            availability_bonus: Some(AvailabilityBonus {
+                threshold: (95, 100).try_into().unwrap(),
+                bonus_percent: 10u64.try_into().unwrap(),
+            }),
+            participation_carryover: None,
+            rewards_per_epoch: Uint128::new(1001).try_into().unwrap(),
+            participation_threshold: (1, 2).try_into().unwrap(),
+        };
+        let tally = EpochTally {
+            params: params.clone(),
+            contract: Addr::unchecked("worker contract"),
+            event_count: 100u64,
+            participation: HashMap::from([
+                ("worker1".into(), 100u64),
+                ("worker2".into(), 96u64),
+                ("worker3".into(), 60u64),
+            ]),
+            epoch: Epoch {
+                epoch_num: 1u64,
+                block_height_started: 0u64,
+                time_started: Timestamp::from_seconds(0),
+            },
+            penalized: HashSet::new(),
+            carryover: HashMap::new(),
+            carried_over: None,
+            late_participation: HashMap::new(),
+        };
+        let with_params =
+            |rewards_per_epoch: u128, threshold: (u64, u64), bonus_percent: u64| EpochTally {
+                params: Params {
+                    rewards_per_epoch: Uint128::new(rewards_per_epoch).try_into().unwrap(),
+                    availability_bonus: Some(AvailabilityBonus {
+                        threshold: threshold.try_into().unwrap(),
+                        bonus_percent: bonus_percent.try_into().unwrap(),
+                    }),
+                    ..params.clone()
+                },
+                ..tally.clone()
+            };
+
+        let test_cases = vec![
+            (
+                // the remainder is split equally if it can't cover the full bonus
+                tally.clone(),
+                [334u128, 334, 333],
+            ),
+            (
+                // only workers meeting the bonus threshold get the bonus
+                with_params(1001, (99, 100), 10),
+                [335, 333, 333],
+            ),
+            (
+                // the bonus is capped at the configured percentage
+                with_params(11, (99, 100), 34),
+                [4, 3, 3],
+            ),
+            (
+                // no bonus without a remainder
+                with_params(999, (95, 100), 10),
+                [333, 333, 333],
+            ),
+        ];
+
+        for (tally, expected) in test_cases {
+            let rewards = tally.rewards_by_worker();
+            assert_eq!(
+                rewards,
+                HashMap::from([
+                    (Addr::unchecked("worker1"), Uint128::from(expected[0])),
+                    (Addr::unchecked("worker2"), Uint128::from(expected[1])),
+                    (Addr::unchecked("worker3"), Uint128::from(expected[2])),
+                ])
+            );
+        }
+    }
+
+    /// Test that credit is
+    /// - only carried over by workers that narrowly miss the threshold and are not penalized
+    /// - counted towards the participation threshold of the next epoch
+    /// - decayed again if the worker narrowly misses the threshold in the next epoch as well
+    #[test]
+    fn next_carryover() {
+        let tally = EpochTally {
+            params: Params {
+                epoch_duration: 100u64.try_into().unwrap(),
+                epoch_mode: EpochMode::Blocks,
+                max_events_per_epoch: None,
+                vesting_epochs: None,
+                availability_bonus: None,
+                participation_carryover: Some(ParticipationCarryover {
+                    near_miss_threshold: (8, 10).try_into().unwrap(),
+                    decay: (1, 2).try_into().unwrap(),
+                }),
+                rewards_per_epoch: Uint128::new(1000).try_into().unwrap(),
+                participation_threshold: (9, 10).try_into().unwrap(),
+            },
+            contract: Addr::unchecked("worker contract"),
+            event_count: 100u64,
+            participation: HashMap::from([
+                ("worker1".into(), 95u64),
+                ("worker2".into(), 85u64),
+                ("worker3".into(), 70u64),
+                ("worker4".into(), 85u64),
+            ]),
+            epoch: Epoch {
+                epoch_num: 1u64,
+                block_height_started: 0u64,
+                time_started: Timestamp::from_seconds(0),
+            },
+            penalized: HashSet::from(["worker4".into()]),
+            carryover: HashMap::new(),
+            carried_over: None,
+            late_participation: HashMap::new(),
+        };
+
+        let carryover = tally.next_carryover();
+        assert_eq!(
+            carryover,
+            HashMap::from([("worker2".into(), Decimal::permille(425))])
+        );
+
+        let next_tally = |participated: u64| {
+            EpochTally {
+                participation: HashMap::from([("worker2".into(), participated)]),
+                penalized: HashSet::new(),
+                ..tally.clone()
+            }
+            .with_carryover(carryover.clone())
+        };
+
+        // 50% + 42.5% meets the threshold, so the credit is used up
+        assert!(next_tally(50).meets_threshold("worker2"));
+        assert!(next_tally(50).next_carryover().is_empty());
+
+        // 45% + 42.5% is another near miss
+        assert!(!next_tally(45).meets_threshold("worker2"));
+        assert_eq!(
+            next_tally(45).next_carryover(),
+            HashMap::from([("worker2".into(), Decimal::from_ratio(4375u64, 10000u64))])
+        );
+    }
+
+    /// Test that for any participation, regardless of availability bonus, rewards are
+    /// - never more than the rewards per epoch in total
+    /// - only paid to workers that meet the participation threshold and are not penalized
+    #[test]
+    fn rewards_by_worker_never_exceed_rewards_per_epoch() {
+        let strategy = (1..1_000u64, nonempty_uint128(), 1..=100u64, any::<bool>()).prop_flat_map(
+            |(event_count, rewards_per_epoch, bonus_percent, with_bonus)| {
+                let workers =
+                    prop::collection::hash_map(addr(), (0..=event_count, any::<bool>()), 0..20);
+                (
+                    Just(event_count),
+                    Just(rewards_per_epoch),
+                    Just(bonus_percent),
+                    Just(with_bonus),
+                    workers,
+                )
+            },
+        );
+
+        check_invariant(
+            strategy,
+            |(event_count, rewards_per_epoch, bonus_percent, with_bonus, workers)| {
+                let tally = EpochTally {
+                    params: Params {
+                        epoch_duration: 100u64.try_into().unwrap(),
+                        epoch_mode: EpochMode::Blocks,
+                        max_events_per_epoch: None,
+                        vesting_epochs: None,
+                        availability_bonus: with_bonus.then(|| AvailabilityBonus {
+                            threshold: (95, 100).try_into().unwrap(),
+                            bonus_percent: bonus_percent.try_into().unwrap(),
+                        }),
+                        participation_carryover: None,
+                        rewards_per_epoch,
+                        participation_threshold: (1, 2).try_into().unwrap(),
+                    },
+                    contract: Addr::unchecked("worker contract"),
+                    event_count,
+                    participation: workers
+                        .iter()
+                        .map(|(worker, (participated, _))| (worker.to_string(), *participated))
+                        .collect(),
+                    epoch: Epoch {
+                        epoch_num: 1u64,
+                        block_height_started: 0u64,
+                        time_started: Timestamp::from_seconds(0),
+                    },
+                    penalized: workers
+                        .iter()
+                        .filter(|(_, (_, penalized))| *penalized)
+                        .map(|(worker, _)| worker.to_string())
+                        .collect(),
+                    carryover: HashMap::new(),
+                    carried_over: None,
+                    late_participation: HashMap::new(),
+                };
+
+                let rewards = tally.rewards_by_worker();
+
+                let total: Uint128 = rewards.values().copied().sum();
+                prop_assert!(total <= rewards_per_epoch.into());
+                for worker in rewards.keys() {
+                    let (_, penalized) = workers[worker];
+                    prop_assert!(!penalized);
+                    prop_assert!(tally.meets_threshold(worker.as_str()));
+                }
+                Ok(())
+            },
+        );
+    }
+
     #[test]
     fn sub_reward_from_pool() {
         let pool = RewardsPool {
@@ -380,11 +1234,17 @@ mod test {
             params: Params {
                 participation_threshold: (Uint64::new(1), Uint64::new(2)).try_into().unwrap(),
                 epoch_duration: 100u64.try_into().unwrap(),
+                epoch_mode: EpochMode::Blocks,
+                max_events_per_epoch: None,
+                vesting_epochs: None,
+                availability_bonus: None,
+                participation_carryover: None,
                 rewards_per_epoch: Uint128::from(1000u128).try_into().unwrap(),
             },
             last_updated: Epoch {
                 epoch_num: 1,
                 block_height_started: 1,
+                time_started: Timestamp::from_seconds(1),
             },
         };
         // save an initial params, then load it
@@ -401,6 +1261,7 @@ mod test {
             last_updated: Epoch {
                 epoch_num: 2,
                 block_height_started: 101,
+                time_started: Timestamp::from_seconds(101),
             },
         };
         assert!(store.save_params(&new_params).is_ok());
@@ -417,6 +1278,7 @@ mod test {
         let epoch = Epoch {
             epoch_num: 10,
             block_height_started: 1000,
+            time_started: Timestamp::from_seconds(1000),
         };
         let contract = Addr::unchecked("some contract");
 
@@ -471,6 +1333,7 @@ mod test {
             contract: Addr::unchecked("some contract"),
             event_id: "some event".try_into().unwrap(),
             epoch_num: 2,
+            participants: HashSet::from(["worker".to_string()]),
         };
 
         let res = store.save_event(&event);
@@ -517,18 +1380,24 @@ mod test {
         let epoch = Epoch {
             epoch_num,
             block_height_started: 1,
+            time_started: Timestamp::from_seconds(1),
         };
         let mut tally = EpochTally::new(
             contract.clone(),
             epoch,
             Params {
                 epoch_duration: 100u64.try_into().unwrap(),
+                epoch_mode: EpochMode::Blocks,
+                max_events_per_epoch: None,
+                vesting_epochs: None,
+                availability_bonus: None,
+                participation_carryover: None,
                 rewards_per_epoch: rewards_rate,
                 participation_threshold: (1, 2).try_into().unwrap(),
             },
         );
 
-        tally = tally.record_participation(Addr::unchecked("worker"));
+        tally = tally.record_participation(Addr::unchecked("worker"), false);
 
         let res = store.save_epoch_tally(&tally);
         assert!(res.is_ok());
@@ -576,4 +1445,279 @@ mod test {
         assert!(loaded.is_ok());
         assert!(loaded.as_ref().unwrap().balance.is_zero());
     }
+
+    #[test]
+    fn save_and_load_pool_metadata() {
+        let mut mock_deps = mock_dependencies();
+        let mut store = RewardsStore {
+            storage: &mut mock_deps.storage,
+        };
+
+        let contract = Addr::unchecked("some contract");
+        let metadata = PoolMetadata {
+            chain_name: Some("ethereum".try_into().unwrap()),
+            description: "voting verifier for ethereum".to_string(),
+        };
+
+        let loaded = store.load_pool_metadata(contract.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+
+        let res = store.save_pool_metadata(contract.clone(), &metadata);
+        assert!(res.is_ok());
+
+        let loaded = load_pool_metadata(store.storage, contract);
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), Some(metadata));
+
+        let loaded = store.load_pool_metadata(Addr::unchecked("a different contract"));
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+    }
+
+    #[test]
+    fn record_epoch_in_worker_stats() {
+        let stats = WorkerStats::default()
+            .record_epoch(3, true, Uint128::from(50u128))
+            .record_epoch(1, false, Uint128::zero())
+            .record_epoch(5, true, Uint128::from(25u128));
+
+        assert_eq!(
+            stats,
+            WorkerStats {
+                events_participated: 9,
+                rewards_earned: Uint128::from(75u128),
+                epochs_above_threshold: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn save_and_load_worker_stats() {
+        let mut mock_deps = mock_dependencies();
+        let mut store = RewardsStore {
+            storage: &mut mock_deps.storage,
+        };
+
+        let contract = Addr::unchecked("some contract");
+        let worker = Addr::unchecked("worker");
+
+        // should be empty at first
+        let loaded = store.load_worker_stats(contract.clone(), worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), WorkerStats::default());
+
+        let stats = WorkerStats::default().record_epoch(10, true, Uint128::from(100u128));
+        let res = store.save_worker_stats(contract.clone(), worker.clone(), &stats);
+        assert!(res.is_ok());
+
+        let loaded = store.load_worker_stats(contract.clone(), worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), stats);
+
+        // same worker but different contract should be empty
+        let loaded = store.load_worker_stats(Addr::unchecked("different contract"), worker);
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), WorkerStats::default());
+
+        // same contract but different worker should be empty
+        let loaded = store.load_worker_stats(contract, Addr::unchecked("different worker"));
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), WorkerStats::default());
+    }
+
+    #[test]
+    fn save_load_and_remove_delegate() {
+        let mut mock_deps = mock_dependencies();
+        let mut store = RewardsStore {
+            storage: &mut mock_deps.storage,
+        };
+
+        let worker = Addr::unchecked("worker");
+        let delegate = Addr::unchecked("delegate");
+
+        let loaded = store.load_delegate(worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+
+        let res = store.save_delegate(worker.clone(), delegate.clone());
+        assert!(res.is_ok());
+
+        let loaded = store.load_delegate(worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), Some(delegate.clone()));
+
+        let delegations = load_delegations(store.storage, None, 10);
+        assert!(delegations.is_ok());
+        assert_eq!(
+            delegations.unwrap(),
+            vec![Delegation {
+                worker: worker.clone(),
+                delegate
+            }]
+        );
+
+        store.remove_delegate(worker.clone());
+        let loaded = store.load_delegate(worker);
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+    }
+
+    #[test]
+    fn load_delegations_paginated() {
+        let mut mock_deps = mock_dependencies();
+        let mut store = RewardsStore {
+            storage: &mut mock_deps.storage,
+        };
+
+        let delegations: Vec<_> = (0..5)
+            .map(|i| Delegation {
+                worker: Addr::unchecked(format!("worker{}", i)),
+                delegate: Addr::unchecked(format!("delegate{}", i)),
+            })
+            .collect();
+        for delegation in &delegations {
+            store
+                .save_delegate(delegation.worker.clone(), delegation.delegate.clone())
+                .unwrap();
+        }
+
+        let first_page = load_delegations(store.storage, None, 2).unwrap();
+        assert_eq!(first_page, delegations[..2]);
+
+        let second_page =
+            load_delegations(store.storage, Some(first_page[1].worker.clone()), 2).unwrap();
+        assert_eq!(second_page, delegations[2..4]);
+
+        let last_page =
+            load_delegations(store.storage, Some(second_page[1].worker.clone()), 2).unwrap();
+        assert_eq!(last_page, delegations[4..]);
+    }
+
+    #[test]
+    fn save_load_and_remove_ibc_payout() {
+        let mut mock_deps = mock_dependencies();
+        let mut store = RewardsStore {
+            storage: &mut mock_deps.storage,
+        };
+
+        let worker = Addr::unchecked("worker");
+        let payout = IbcPayout {
+            channel_id: "channel-0".try_into().unwrap(),
+            remote_address: "cosmos1remote".try_into().unwrap(),
+        };
+
+        let loaded = store.load_ibc_payout(worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+
+        let res = store.save_ibc_payout(worker.clone(), &payout);
+        assert!(res.is_ok());
+
+        let loaded = load_ibc_payout(store.storage, worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), Some(payout));
+
+        store.remove_ibc_payout(worker.clone());
+        let loaded = store.load_ibc_payout(worker);
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+    }
+
+    #[test]
+    fn save_load_and_remove_payout_hook() {
+        let mut mock_deps = mock_dependencies();
+        let mut store = RewardsStore {
+            storage: &mut mock_deps.storage,
+        };
+
+        let worker = Addr::unchecked("worker");
+        let hook = Addr::unchecked("restaking");
+
+        let loaded = store.load_payout_hook(worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+
+        let res = store.save_payout_hook(worker.clone(), hook.clone());
+        assert!(res.is_ok());
+
+        let loaded = load_payout_hook(store.storage, worker.clone());
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), Some(hook));
+
+        store.remove_payout_hook(worker.clone());
+        let loaded = store.load_payout_hook(worker);
+        assert!(loaded.is_ok());
+        assert_eq!(loaded.unwrap(), None);
+    }
+
+    #[test]
+    fn register_existing_funded_and_tallied_pools() {
+        let mut mock_deps = mock_dependencies();
+        let mut store = RewardsStore {
+            storage: &mut mock_deps.storage,
+        };
+
+        let registered = Addr::unchecked("registered");
+        let registered_metadata = PoolMetadata {
+            chain_name: Some("ethereum".try_into().unwrap()),
+            description: "ethereum voting verifier".to_string(),
+        };
+        store
+            .save_pool_metadata(registered.clone(), &registered_metadata)
+            .unwrap();
+
+        let funded = Addr::unchecked("funded");
+        for contract in [&registered, &funded] {
+            store
+                .save_rewards_pool(&RewardsPool {
+                    contract: contract.clone(),
+                    balance: Uint128::from(100u128),
+                })
+                .unwrap();
+        }
+
+        let tallied = Addr::unchecked("tallied");
+        let params = Params {
+            participation_threshold: (Uint64::new(1), Uint64::new(2)).try_into().unwrap(),
+            epoch_duration: 100u64.try_into().unwrap(),
+            epoch_mode: EpochMode::Blocks,
+            max_events_per_epoch: None,
+            vesting_epochs: None,
+            availability_bonus: None,
+            participation_carryover: None,
+            rewards_per_epoch: Uint128::from(1000u128).try_into().unwrap(),
+        };
+        for epoch_num in 1..3 {
+            let epoch = Epoch {
+                epoch_num,
+                block_height_started: epoch_num * 100,
+                time_started: Timestamp::from_seconds(epoch_num * 100),
+            };
+            store
+                .save_epoch_tally(&EpochTally::new(tallied.clone(), epoch, params.clone()))
+                .unwrap();
+        }
+
+        register_existing_pools(store.storage).unwrap();
+
+        assert_eq!(
+            load_pool_metadata(store.storage, registered).unwrap(),
+            Some(registered_metadata)
+        );
+        let migrated_metadata = PoolMetadata {
+            chain_name: None,
+            description: "registered on migration".to_string(),
+        };
+        for contract in [funded, tallied] {
+            assert_eq!(
+                load_pool_metadata(store.storage, contract).unwrap(),
+                Some(migrated_metadata.clone())
+            );
+        }
+        assert_eq!(
+            load_pool_metadata(store.storage, Addr::unchecked("unknown")).unwrap(),
+            None
+        );
+    }
 }
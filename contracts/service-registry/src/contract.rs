@@ -99,6 +99,9 @@ pub fn execute(
             chains,
         } => execute::declare_chains_support(deps, info, service_name, chains),
         ExecuteMsg::BondWorker { service_name } => execute::bond_worker(deps, info, service_name),
+        ExecuteMsg::BondVestingWorker { service_name } => {
+            execute::bond_vesting_worker(deps, env, info, service_name)
+        }
         ExecuteMsg::UnbondWorker { service_name } => {
             execute::unbond_worker(deps, env, info, service_name)
         }
@@ -112,7 +115,10 @@ pub fn execute(
 pub mod execute {
     use connection_router::state::ChainName;
 
-    use crate::state::{AuthorizationState, WORKERS, WORKERS_PER_CHAIN};
+    use crate::state::{
+        AuthorizationState, VestingBond, VESTING_BONDS, WORKERS, WORKERS_PER_CHAIN,
+    };
+    use crate::vesting;
 
     use super::*;
 
@@ -206,11 +212,7 @@ pub mod execute {
             .ok_or(ContractError::ServiceNotFound)?;
 
         let bond = if !info.funds.is_empty() {
-            info.funds
-                .iter()
-                .find(|coin| coin.denom == service.bond_denom)
-                .ok_or(ContractError::WrongDenom)?
-                .amount
+            bond_amount(&info.funds, &service.bond_denom)?
         } else {
             Uint128::zero() // sender can rebond currently unbonding funds by just sending no new funds
         };
@@ -237,6 +239,60 @@ pub mod execute {
         Ok(Response::new())
     }
 
+    pub fn bond_vesting_worker(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        service_name: String,
+    ) -> Result<Response, ContractError> {
+        let service = SERVICES
+            .may_load(deps.storage, &service_name)?
+            .ok_or(ContractError::ServiceNotFound)?;
+
+        if info.funds.is_empty() {
+            return Err(ContractError::NoFundsToBond);
+        }
+        let bond = bond_amount(&info.funds, &service.bond_denom)?;
+
+        // the schedule comes from the chain, so workers can't pick when their stake becomes claimable
+        let schedule = vesting::query_vesting_schedule(&deps.querier, &info.sender)?
+            .filter(|schedule| schedule.end_time > env.block.time)
+            .ok_or(ContractError::NotVesting)?;
+        let original_vesting = schedule.original_vesting_of(&service.bond_denom);
+        if original_vesting.is_zero() {
+            return Err(ContractError::NotVesting);
+        }
+
+        let vesting_bond =
+            match VESTING_BONDS.may_load(deps.storage, (&service_name, &info.sender))? {
+                Some(vesting_bond) => vesting_bond.add(bond, schedule.end_time),
+                None => VestingBond {
+                    amount: bond,
+                    vested_at: schedule.end_time,
+                },
+            };
+        // no more can be vesting than the account's schedule covers
+        VESTING_BONDS.save(
+            deps.storage,
+            (&service_name, &info.sender),
+            &VestingBond {
+                amount: vesting_bond.amount.min(original_vesting),
+                ..vesting_bond
+            },
+        )?;
+
+        // the vesting funds are bonded like any other stake, so they remain slashable
+        bond_worker(deps, info, service_name)
+    }
+
+    fn bond_amount(funds: &[Coin], bond_denom: &str) -> Result<Uint128, ContractError> {
+        Ok(funds
+            .iter()
+            .find(|coin| coin.denom == bond_denom)
+            .ok_or(ContractError::WrongDenom)?
+            .amount)
+    }
+
     pub fn declare_chains_support(
         deps: DepsMut,
         info: MessageInfo,
@@ -276,6 +332,15 @@ pub mod execute {
 
         let bonding_state = worker.bonding_state.unbond(can_unbond, env.block.time)?;
 
+        // a vesting bond that has vested by now doesn't hold back any of the unbonding stake anymore
+        if let Some(vesting_bond) =
+            VESTING_BONDS.may_load(deps.storage, (&service_name, &info.sender))?
+        {
+            if vesting_bond.locked_at(env.block.time).is_zero() {
+                VESTING_BONDS.remove(deps.storage, (&service_name, &info.sender));
+            }
+        }
+
         WORKERS.save(
             deps.storage,
             (&service_name, &info.sender),
@@ -304,8 +369,37 @@ pub mod execute {
 
         let (bonding_state, released_bond) = worker
             .bonding_state
+            .clone()
             .claim_stake(env.block.time, service.unbonding_period_days as u64)?;
 
+        // stake that is still vesting stays locked (and slashable) until it has vested
+        let vesting_bond = VESTING_BONDS.may_load(deps.storage, (&service_name, &info.sender))?;
+        let (bonding_state, released_bond) = match (vesting_bond, worker.bonding_state.clone()) {
+            (Some(vesting_bond), BondingState::Unbonding { unbonded_at, .. })
+                if !vesting_bond.locked_at(env.block.time).is_zero() =>
+            {
+                let locked = vesting_bond.locked_at(env.block.time);
+                if released_bond <= locked {
+                    return Err(ContractError::StakeStillVesting(vesting_bond.vested_at));
+                }
+
+                (
+                    BondingState::Unbonding {
+                        amount: locked,
+                        unbonded_at,
+                    },
+                    released_bond - locked,
+                )
+            }
+            (vesting_bond, _) => {
+                if vesting_bond.is_some() {
+                    VESTING_BONDS.remove(deps.storage, (&service_name, &info.sender));
+                }
+
+                (bonding_state, released_bond)
+            }
+        };
+
         WORKERS.save(
             deps.storage,
             (&service_name, &info.sender),
@@ -341,13 +435,20 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
         QueryMsg::GetService { service_name } => {
             to_binary(&query::get_service(deps, service_name)?).map_err(|err| err.into())
         }
+        QueryMsg::GetVestingBond {
+            service_name,
+            worker,
+        } => to_binary(&query::get_vesting_bond(deps, service_name, worker)?)
+            .map_err(|err| err.into()),
     }
 }
 
 pub mod query {
     use connection_router::state::ChainName;
 
-    use crate::state::{AuthorizationState, WORKERS, WORKERS_PER_CHAIN};
+    use crate::state::{
+        AuthorizationState, VestingBond, VESTING_BONDS, WORKERS, WORKERS_PER_CHAIN,
+    };
 
     use super::*;
 
@@ -389,9 +490,290 @@ pub mod query {
             .ok_or(ContractError::WorkerNotFound)
     }
 
+    pub fn get_vesting_bond(
+        deps: Deps,
+        service_name: String,
+        worker: String,
+    ) -> Result<Option<VestingBond>, ContractError> {
+        VESTING_BONDS
+            .may_load(
+                deps.storage,
+                (&service_name, &deps.api.addr_validate(&worker)?),
+            )
+            .map_err(ContractError::from)
+    }
+
     pub fn get_service(deps: Deps, service_name: String) -> Result<Service, ContractError> {
         SERVICES
             .may_load(deps.storage, &service_name)?
             .ok_or(ContractError::ServiceNotFound)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use cosmwasm_std::{
+        coins, from_binary,
+        testing::{mock_env, mock_info, MockApi, MockStorage},
+        CosmosMsg, OwnedDeps, Timestamp,
+    };
+
+    use crate::state::{VestingBond, WORKERS};
+    use crate::vesting::tests::{vesting_account, AccountQuerier, BASE_ACCOUNT};
+
+    use super::*;
+
+    const GOVERNANCE: &str = "governance";
+    const WORKER: &str = "worker";
+    const SERVICE_NAME: &str = "validators";
+    const AXL_DENOMINATION: &str = "uaxl";
+    const UNBONDING_PERIOD_DAYS: u16 = 1;
+
+    fn setup(account: impl Into<String>) -> OwnedDeps<MockStorage, MockApi, AccountQuerier> {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: AccountQuerier::new(account),
+            custom_query_type: PhantomData,
+        };
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            InstantiateMsg {
+                governance_account: GOVERNANCE.into(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(GOVERNANCE, &[]),
+            ExecuteMsg::RegisterService {
+                service_name: SERVICE_NAME.into(),
+                service_contract: Addr::unchecked("nowhere"),
+                min_num_workers: 0,
+                max_num_workers: Some(100),
+                min_worker_bond: Uint128::new(100),
+                bond_denom: AXL_DENOMINATION.into(),
+                unbonding_period_days: UNBONDING_PERIOD_DAYS,
+                description: "Some service".into(),
+            },
+        )
+        .unwrap();
+
+        deps
+    }
+
+    fn env_at(time: Timestamp) -> Env {
+        let mut env = mock_env();
+        env.block.time = time;
+        env
+    }
+
+    fn execute_as_worker(
+        deps: DepsMut,
+        env: Env,
+        msg: ExecuteMsg,
+        funds: u128,
+    ) -> Result<Response, axelar_wasm_std::ContractError> {
+        let funds = if funds == 0 {
+            vec![]
+        } else {
+            coins(funds, AXL_DENOMINATION)
+        };
+        execute(deps, env, mock_info(WORKER, &funds), msg)
+    }
+
+    fn bond_vesting_worker() -> ExecuteMsg {
+        ExecuteMsg::BondVestingWorker {
+            service_name: SERVICE_NAME.into(),
+        }
+    }
+
+    fn claim_stake() -> ExecuteMsg {
+        ExecuteMsg::ClaimStake {
+            service_name: SERVICE_NAME.into(),
+        }
+    }
+
+    fn query_vesting_bond(deps: Deps) -> Option<VestingBond> {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::GetVestingBond {
+                    service_name: SERVICE_NAME.into(),
+                    worker: WORKER.into(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn query_bonding_state(deps: Deps) -> BondingState {
+        WORKERS
+            .load(deps.storage, (SERVICE_NAME, &Addr::unchecked(WORKER)))
+            .unwrap()
+            .bonding_state
+    }
+
+    fn released_stake(res: Response) -> Uint128 {
+        match &res.messages[0].msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                assert_eq!(to_address, WORKER);
+                amount[0].amount
+            }
+            _ => panic!("unexpected msg"),
+        }
+    }
+
+    #[test]
+    fn vesting_stake_stays_locked_until_vested() {
+        let now = mock_env().block.time;
+        // the chain reports vesting schedules in whole seconds
+        let vested_at = Timestamp::from_seconds(now.plus_days(10).seconds());
+        let mut deps = setup(vesting_account(100, vested_at));
+
+        execute_as_worker(
+            deps.as_mut(),
+            mock_env(),
+            ExecuteMsg::BondWorker {
+                service_name: SERVICE_NAME.into(),
+            },
+            100,
+        )
+        .unwrap();
+        execute_as_worker(deps.as_mut(), mock_env(), bond_vesting_worker(), 100).unwrap();
+
+        // vesting funds count towards the bond, and are locked until the account's schedule ends
+        assert_eq!(
+            query_bonding_state(deps.as_ref()),
+            BondingState::Bonded {
+                amount: Uint128::new(200)
+            }
+        );
+        assert_eq!(
+            query_vesting_bond(deps.as_ref()),
+            Some(VestingBond {
+                amount: Uint128::new(100),
+                vested_at,
+            })
+        );
+
+        execute_as_worker(
+            deps.as_mut(),
+            mock_env(),
+            ExecuteMsg::UnbondWorker {
+                service_name: SERVICE_NAME.into(),
+            },
+            0,
+        )
+        .unwrap();
+
+        // only the liquid part of the stake is released once unbonding is over
+        let unbonded = env_at(now.plus_days(UNBONDING_PERIOD_DAYS.into()));
+        let res = execute_as_worker(deps.as_mut(), unbonded.clone(), claim_stake(), 0).unwrap();
+        assert_eq!(released_stake(res), Uint128::new(100));
+        assert_eq!(
+            query_bonding_state(deps.as_ref()),
+            BondingState::Unbonding {
+                amount: Uint128::new(100),
+                unbonded_at: now,
+            }
+        );
+
+        assert_eq!(
+            execute_as_worker(deps.as_mut(), unbonded, claim_stake(), 0)
+                .unwrap_err()
+                .to_string(),
+            axelar_wasm_std::ContractError::from(ContractError::StakeStillVesting(vested_at))
+                .to_string()
+        );
+
+        let res = execute_as_worker(deps.as_mut(), env_at(vested_at), claim_stake(), 0).unwrap();
+        assert_eq!(released_stake(res), Uint128::new(100));
+        assert_eq!(query_bonding_state(deps.as_ref()), BondingState::Unbonded);
+        assert_eq!(query_vesting_bond(deps.as_ref()), None);
+    }
+
+    #[test]
+    fn vesting_stake_is_capped_at_original_vesting() {
+        let vested_at = Timestamp::from_seconds(mock_env().block.time.plus_days(10).seconds());
+        let mut deps = setup(vesting_account(150, vested_at));
+
+        execute_as_worker(deps.as_mut(), mock_env(), bond_vesting_worker(), 100).unwrap();
+        execute_as_worker(deps.as_mut(), mock_env(), bond_vesting_worker(), 100).unwrap();
+
+        assert_eq!(
+            query_bonding_state(deps.as_ref()),
+            BondingState::Bonded {
+                amount: Uint128::new(200)
+            }
+        );
+        assert_eq!(
+            query_vesting_bond(deps.as_ref()),
+            Some(VestingBond {
+                amount: Uint128::new(150),
+                vested_at,
+            })
+        );
+    }
+
+    #[test]
+    fn bond_vesting_worker_requires_vesting_account() {
+        let not_vesting =
+            axelar_wasm_std::ContractError::from(ContractError::NotVesting).to_string();
+
+        let mut deps = setup(BASE_ACCOUNT);
+        assert_eq!(
+            execute_as_worker(deps.as_mut(), mock_env(), bond_vesting_worker(), 100)
+                .unwrap_err()
+                .to_string(),
+            not_vesting
+        );
+
+        // the schedule has already ended
+        let mut deps = setup(vesting_account(100, mock_env().block.time));
+        assert_eq!(
+            execute_as_worker(deps.as_mut(), mock_env(), bond_vesting_worker(), 100)
+                .unwrap_err()
+                .to_string(),
+            not_vesting
+        );
+        assert_eq!(query_vesting_bond(deps.as_ref()), None);
+    }
+
+    #[test]
+    fn unbond_worker_drops_vested_bond() {
+        let now = mock_env().block.time;
+        let vested_at = Timestamp::from_seconds(now.plus_days(1).seconds());
+        let mut deps = setup(vesting_account(100, vested_at));
+
+        execute_as_worker(deps.as_mut(), mock_env(), bond_vesting_worker(), 100).unwrap();
+        execute_as_worker(
+            deps.as_mut(),
+            env_at(vested_at),
+            ExecuteMsg::UnbondWorker {
+                service_name: SERVICE_NAME.into(),
+            },
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(query_vesting_bond(deps.as_ref()), None);
+
+        let res = execute_as_worker(
+            deps.as_mut(),
+            env_at(vested_at.plus_days(UNBONDING_PERIOD_DAYS.into())),
+            claim_stake(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(released_stake(res), Uint128::new(100));
+    }
+}
@@ -1,6 +1,6 @@
 use axelar_wasm_std::nonempty;
 use axelar_wasm_std_derive::IntoContractError;
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp};
 use thiserror::Error;
 
 use crate::state::BondingState;
@@ -27,4 +27,10 @@ pub enum ContractError {
     WorkerNotFound,
     #[error("invalid bonding state `{0:?}` for this operation")]
     InvalidBondingState(BondingState),
+    #[error("no funds to bond")]
+    NoFundsToBond,
+    #[error("sender is not a vesting account with vesting funds in the bond denomination")]
+    NotVesting,
+    #[error("vesting stake can't be claimed before {0}")]
+    StakeStillVesting(Timestamp),
 }
@@ -40,6 +40,12 @@ pub enum ExecuteMsg {
     BondWorker {
         service_name: String,
     },
+    // Locks up funds sent from a vesting account as stake. The funds count towards the bond like any other stake,
+    // but are tracked as vesting stake, up to the account's original vesting amount, and can't be claimed back
+    // before the account's vesting schedule ends, even after unbonding. Called by the worker.
+    BondVestingWorker {
+        service_name: String,
+    },
     // Initiates unbonding of staked funds. Called by the worker.
     UnbondWorker {
         service_name: String,
@@ -67,4 +73,10 @@ pub enum QueryMsg {
         service_name: String,
         worker: String,
     },
+
+    #[returns(Option<crate::state::VestingBond>)]
+    GetVestingBond {
+        service_name: String,
+        worker: String,
+    },
 }
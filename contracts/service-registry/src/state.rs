@@ -122,6 +122,33 @@ impl BondingState {
     }
 }
 
+/// Portion of a worker's bond that was sent from a vesting account. It counts towards the bond
+/// (and is therefore slashable) like any other stake, but can't be claimed before `vested_at`,
+/// the end of the account's vesting schedule on chain.
+#[cw_serde]
+pub struct VestingBond {
+    pub amount: Uint128,
+    pub vested_at: Timestamp,
+}
+
+impl VestingBond {
+    pub fn add(self, to_add: Uint128, vested_at: Timestamp) -> Self {
+        VestingBond {
+            amount: self.amount + to_add,
+            vested_at: self.vested_at.max(vested_at),
+        }
+    }
+
+    /// Amount that must stay locked in the registry at the given time
+    pub fn locked_at(&self, time: Timestamp) -> Uint128 {
+        if time < self.vested_at {
+            self.amount
+        } else {
+            Uint128::zero()
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub enum AuthorizationState {
     NotAuthorized,
@@ -134,6 +161,8 @@ pub const SERVICES: Map<&str, Service> = Map::new("services");
 pub const WORKERS_PER_CHAIN: Map<(&str, &ChainName, &Addr), ()> = Map::new("workers_per_chain");
 // maps (service_name, worker_address) -> Worker
 pub const WORKERS: Map<(&str, &Addr), Worker> = Map::new("workers");
+// maps (service_name, worker_address) -> VestingBond
+pub const VESTING_BONDS: Map<(&str, &Addr), VestingBond> = Map::new("vesting_bonds");
 
 #[cfg(test)]
 mod tests {
@@ -369,6 +398,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vesting_bond_add() {
+        let bond = VestingBond {
+            amount: Uint128::from(100u32),
+            vested_at: Timestamp::from_seconds(100),
+        };
+
+        let bond = bond.add(Uint128::from(50u32), Timestamp::from_seconds(50));
+        assert_eq!(
+            bond,
+            VestingBond {
+                amount: Uint128::from(150u32),
+                vested_at: Timestamp::from_seconds(100),
+            }
+        );
+
+        let bond = bond.add(Uint128::from(50u32), Timestamp::from_seconds(200));
+        assert_eq!(
+            bond,
+            VestingBond {
+                amount: Uint128::from(200u32),
+                vested_at: Timestamp::from_seconds(200),
+            }
+        );
+    }
+
+    #[test]
+    fn test_vesting_bond_locked_at() {
+        let bond = VestingBond {
+            amount: Uint128::from(100u32),
+            vested_at: Timestamp::from_seconds(100),
+        };
+
+        assert_eq!(
+            bond.locked_at(Timestamp::from_seconds(99)),
+            Uint128::from(100u32)
+        );
+        assert_eq!(
+            bond.locked_at(Timestamp::from_seconds(100)),
+            Uint128::zero()
+        );
+    }
+
     #[test]
     fn test_unbonded_claim_stake() {
         let state = BondingState::Unbonded;
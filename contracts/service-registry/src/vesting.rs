@@ -0,0 +1,185 @@
+use cosmwasm_std::{
+    Addr, Coin, QuerierWrapper, QueryRequest, StdError, StdResult, Timestamp, Uint128,
+};
+use serde::Deserialize;
+
+pub const ACCOUNT_QUERY_PATH: &str = "/cosmos.auth.v1beta1.Query/Account";
+
+/// Vesting schedule of a cosmos vesting account, as far as the registry needs it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub original_vesting: Vec<Coin>,
+    pub end_time: Timestamp,
+}
+
+impl VestingSchedule {
+    pub fn original_vesting_of(&self, denom: &str) -> Uint128 {
+        self.original_vesting
+            .iter()
+            .filter(|coin| coin.denom == denom)
+            .map(|coin| coin.amount)
+            .sum()
+    }
+}
+
+// JSON encoding of the QueryAccountResponse that the chain returns for stargate queries. Every vesting account type
+// (delayed, continuous, periodic and permanent locked) embeds a base_vesting_account, other account types don't
+#[derive(Deserialize)]
+struct QueryAccountResponse {
+    account: Account,
+}
+
+#[derive(Deserialize)]
+struct Account {
+    #[serde(default)]
+    base_vesting_account: Option<BaseVestingAccount>,
+}
+
+#[derive(Deserialize)]
+struct BaseVestingAccount {
+    original_vesting: Vec<Coin>,
+    // int64 values are encoded as strings
+    end_time: String,
+}
+
+/// Looks up the vesting schedule of an account in the chain's auth module. Returns None if the account is not a vesting account.
+/// The chain must accept the `/cosmos.auth.v1beta1.Query/Account` stargate query
+pub fn query_vesting_schedule(
+    querier: &QuerierWrapper,
+    account: &Addr,
+) -> StdResult<Option<VestingSchedule>> {
+    let response: QueryAccountResponse = querier.query(&QueryRequest::Stargate {
+        path: ACCOUNT_QUERY_PATH.to_string(),
+        data: account_request(account).into(),
+    })?;
+
+    response
+        .account
+        .base_vesting_account
+        .map(|account| {
+            let end_time = account
+                .end_time
+                .parse::<u64>()
+                .map_err(|err| StdError::parse_err("end_time", err))?;
+
+            Ok(VestingSchedule {
+                original_vesting: account.original_vesting,
+                end_time: Timestamp::from_seconds(end_time),
+            })
+        })
+        .transpose()
+}
+
+/// Protobuf encoding of a QueryAccountRequest for the account
+pub fn account_request(account: &Addr) -> Vec<u8> {
+    let address = account.as_bytes();
+
+    let mut request = vec![1 << 3 | 2];
+    let mut len = address.len();
+    while len >= 0x80 {
+        request.push((len as u8 & 0x7f) | 0x80);
+        len >>= 7;
+    }
+    request.push(len as u8);
+    request.extend_from_slice(address);
+
+    request
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use cosmwasm_std::{
+        coins, from_slice, testing::MockQuerier, Addr, Binary, ContractResult, Empty, Querier,
+        QuerierResult, QuerierWrapper, QueryRequest, SystemResult, Timestamp,
+    };
+
+    use super::{account_request, query_vesting_schedule, VestingSchedule, ACCOUNT_QUERY_PATH};
+
+    pub const BASE_ACCOUNT: &str = r#"{
+        "@type": "/cosmos.auth.v1beta1.BaseAccount",
+        "address": "axelar1",
+        "account_number": "1",
+        "sequence": "0"
+    }"#;
+
+    /// JSON encoding of a continuous vesting account that vests the given uaxl amount until the end time
+    pub fn vesting_account(amount: u128, end_time: Timestamp) -> String {
+        format!(
+            r#"{{
+                "@type": "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+                "base_vesting_account": {{
+                    "base_account": {{ "address": "axelar1", "account_number": "1", "sequence": "0" }},
+                    "original_vesting": [{{ "denom": "uaxl", "amount": "{}" }}],
+                    "delegated_free": [],
+                    "delegated_vesting": [],
+                    "end_time": "{}"
+                }},
+                "start_time": "0"
+            }}"#,
+            amount,
+            end_time.seconds()
+        )
+    }
+
+    /// Answers account queries with the given JSON encoded account, and all other queries like the mock querier
+    pub struct AccountQuerier {
+        pub account: String,
+        pub base: MockQuerier,
+    }
+
+    impl AccountQuerier {
+        pub fn new(account: impl Into<String>) -> Self {
+            AccountQuerier {
+                account: account.into(),
+                base: MockQuerier::new(&[]),
+            }
+        }
+    }
+
+    impl Querier for AccountQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            match from_slice::<QueryRequest<Empty>>(bin_request) {
+                Ok(QueryRequest::Stargate { path, .. }) if path == ACCOUNT_QUERY_PATH => {
+                    SystemResult::Ok(ContractResult::Ok(Binary::from(
+                        format!(r#"{{ "account": {} }}"#, self.account).as_bytes(),
+                    )))
+                }
+                _ => self.base.raw_query(bin_request),
+            }
+        }
+    }
+
+    #[test]
+    fn account_request_encodes_address() {
+        assert_eq!(
+            account_request(&Addr::unchecked("axelar1")),
+            [&[0x0a, 7][..], b"axelar1"].concat()
+        );
+    }
+
+    #[test]
+    fn query_vesting_schedule_of_vesting_account() {
+        let querier =
+            AccountQuerier::new(vesting_account(100, Timestamp::from_seconds(1700000000)));
+
+        assert_eq!(
+            query_vesting_schedule(&QuerierWrapper::new(&querier), &Addr::unchecked("axelar1"))
+                .unwrap(),
+            Some(VestingSchedule {
+                original_vesting: coins(100, "uaxl"),
+                end_time: Timestamp::from_seconds(1700000000),
+            })
+        );
+    }
+
+    #[test]
+    fn query_vesting_schedule_of_base_account() {
+        let querier = AccountQuerier::new(BASE_ACCOUNT);
+
+        assert_eq!(
+            query_vesting_schedule(&QuerierWrapper::new(&querier), &Addr::unchecked("axelar1"))
+                .unwrap(),
+            None
+        );
+    }
+}
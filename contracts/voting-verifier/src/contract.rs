@@ -1,13 +1,17 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Attribute, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+    to_binary, Attribute, Binary, Deps, DepsMut, Env, Event, MessageInfo, Reply, Response,
+    StdResult, SubMsgResult,
 };
 
+use crate::events::PenaltyFailed;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use crate::state::{Config, CONFIG};
 use crate::{execute, query};
 
+pub const PENALTY_REPLY_ID: u64 = 1;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -24,6 +28,16 @@ pub fn instantiate(
         confirmation_height: msg.confirmation_height,
         source_chain: msg.source_chain,
         rewards_contract: deps.api.addr_validate(&msg.rewards_address)?,
+        fraud_window: msg.fraud_window,
+        governance: msg
+            .governance_address
+            .map(|address| deps.api.addr_validate(&address))
+            .transpose()?,
+        max_messages_per_poll: msg.max_messages_per_poll,
+        finalization_reward: None,
+        dispute_window: None,
+        dispute_deposit: None,
+        dispute_resolution_period: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -40,8 +54,41 @@ pub fn execute(
 ) -> Result<Response, axelar_wasm_std::ContractError> {
     match msg {
         ExecuteMsg::VerifyMessages { messages } => execute::verify_messages(deps, env, messages),
-        ExecuteMsg::Vote { poll_id, votes } => execute::vote(deps, env, info, poll_id, votes),
-        ExecuteMsg::EndPoll { poll_id } => execute::end_poll(deps, env, poll_id),
+        ExecuteMsg::Vote {
+            poll_id,
+            votes,
+            justification,
+        } => execute::vote(deps, env, info, poll_id, votes, justification),
+        ExecuteMsg::EndPoll { poll_id } => execute::end_poll(deps, env, info, poll_id),
+        ExecuteMsg::ChallengeMessages { messages } => {
+            execute::challenge_messages(deps, env, info, messages)
+        }
+        ExecuteMsg::OverridePollOutcome { poll_id, results } => {
+            execute::override_poll_outcome(deps, info, poll_id, results)
+        }
+        ExecuteMsg::UpdateConfirmationHeight {
+            confirmation_height,
+        } => execute::update_confirmation_height(deps, info, confirmation_height),
+        ExecuteMsg::UpdateFinalizationReward { reward } => {
+            execute::update_finalization_reward(deps, info, reward)
+        }
+        ExecuteMsg::UpdateDisputeParams {
+            dispute_window,
+            dispute_deposit,
+            dispute_resolution_period,
+        } => execute::update_dispute_params(
+            deps,
+            info,
+            dispute_window,
+            dispute_deposit,
+            dispute_resolution_period,
+        ),
+        ExecuteMsg::DisputePollOutcome { poll_id } => {
+            execute::dispute_poll_outcome(deps, env, info, poll_id)
+        }
+        ExecuteMsg::SettleLapsedDispute { poll_id } => {
+            execute::settle_lapsed_dispute(deps, env, poll_id)
+        }
         ExecuteMsg::VerifyWorkerSet {
             message_id,
             new_operators,
@@ -50,15 +97,51 @@ pub fn execute(
     .map_err(axelar_wasm_std::ContractError::from)
 }
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(
+    _deps: DepsMut,
+    _env: Env,
+    reply: Reply,
+) -> Result<Response, axelar_wasm_std::ContractError> {
+    match (reply.id, reply.result) {
+        // e.g. rewards for the poll were already distributed, which must not prevent the override
+        (PENALTY_REPLY_ID, SubMsgResult::Err(error)) => {
+            Ok(Response::new().add_event(PenaltyFailed { error }.into()))
+        }
+        (PENALTY_REPLY_ID, SubMsgResult::Ok(_)) => {
+            unreachable!("violated invariant: replied successful submessage with ReplyOn::Error")
+        }
+        _ => unreachable!("unknown reply ID"),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::IsVerified { messages } => to_binary(&query::is_verified(deps, &messages)?),
+        QueryMsg::IsVerified { messages } => {
+            to_binary(&query::is_verified(deps, &messages, env.block.height)?)
+        }
 
-        QueryMsg::GetPoll { poll_id: _ } => {
-            todo!()
+        QueryMsg::GetPoll { poll_id } => to_binary(&query::poll(deps, poll_id)?),
+        QueryMsg::IsWorkerSetVerified { new_operators } => to_binary(
+            &query::is_worker_set_verified(deps, &new_operators, env.block.height)?,
+        ),
+        QueryMsg::GetPollMetrics => to_binary(&query::poll_metrics(deps)?),
+        QueryMsg::GetVerificationCosts { source_chain } => {
+            to_binary(&query::verification_costs(deps, source_chain)?)
         }
-        QueryMsg::IsWorkerSetVerified { new_operators } => {
-            to_binary(&query::is_worker_set_verified(deps, &new_operators)?)
+        QueryMsg::GetMessagesByTxId { tx_id } => {
+            to_binary(&query::messages_by_tx_id(deps, &tx_id, env.block.height)?)
         }
+        QueryMsg::GetVerificationRequest {
+            request_id,
+            start_after,
+            limit,
+        } => to_binary(&query::verification_request(
+            deps,
+            request_id,
+            start_after,
+            limit,
+            env.block.height,
+        )?),
     }
 }
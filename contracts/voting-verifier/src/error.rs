@@ -1,8 +1,11 @@
-use axelar_wasm_std::{nonempty, voting};
+use axelar_wasm_std::{
+    nonempty,
+    voting::{self, PollId},
+};
 use axelar_wasm_std_derive::IntoContractError;
 use connection_router;
-use connection_router::state::ChainName;
-use cosmwasm_std::StdError;
+use connection_router::state::{ChainName, CrossChainId};
+use cosmwasm_std::{Addr, Coin, StdError};
 use service_registry;
 use thiserror::Error;
 
@@ -29,6 +32,9 @@ pub enum ContractError {
     #[error("invalid message id {0}")]
     InvalidMessageID(String),
 
+    #[error("message {0} carries a token transfer, which can't be verified")]
+    TokenTransferNotSupported(CrossChainId),
+
     #[error("poll not found")]
     PollNotFound,
 
@@ -37,6 +43,30 @@ pub enum ContractError {
 
     #[error("worker set already confirmed")]
     WorkerSetAlreadyConfirmed,
+
+    #[error("{0} is not an active worker")]
+    NotActiveWorker(Addr),
+
+    #[error("message {0} is not provisionally verified or its fraud window has ended")]
+    MessageNotChallengeable(CrossChainId),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("poll {} has not been ended yet", String::from(*.0))]
+    PollNotFinished(PollId),
+
+    #[error("the outcome of poll {} has already been overridden", String::from(*.0))]
+    PollAlreadyOverridden(PollId),
+
+    #[error("the outcome of poll {} can't be disputed", String::from(*.0))]
+    PollNotDisputable(PollId),
+
+    #[error("disputes require a deposit of exactly {0}")]
+    InvalidDisputeDeposit(Coin),
+
+    #[error("poll {} has no dispute that passed its resolution deadline", String::from(*.0))]
+    DisputeNotLapsed(PollId),
 }
 
 impl From<ContractError> for StdError {
@@ -1,12 +1,12 @@
 use std::vec::Vec;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Attribute, Event};
+use cosmwasm_std::{Addr, Attribute, Coin, Event};
 
 use axelar_wasm_std::nonempty;
 use axelar_wasm_std::operators::Operators;
 use axelar_wasm_std::voting::{PollId, Vote};
-use connection_router::state::{Address, ChainName, Message, ID_SEPARATOR};
+use connection_router::state::{Address, ChainName, CrossChainId, Message, ID_SEPARATOR};
 
 use crate::error::ContractError;
 use crate::state::Config;
@@ -158,7 +158,7 @@ impl TryFrom<Message> for TxEventConfirmation {
     }
 }
 
-fn parse_message_id(
+pub(crate) fn parse_message_id(
     message_id: &nonempty::String,
 ) -> Result<(nonempty::String, u64), ContractError> {
     // expected format: <tx_id>:<index>
@@ -176,6 +176,283 @@ fn parse_message_id(
     ))
 }
 
+pub struct MessagesProvisionallyVerified {
+    pub messages: Vec<CrossChainId>,
+    pub fraud_window_ends: u64,
+}
+
+impl From<MessagesProvisionallyVerified> for Event {
+    fn from(other: MessagesProvisionallyVerified) -> Self {
+        Event::new("messages_provisionally_verified")
+            .add_attribute(
+                "messages",
+                serde_json::to_string(&other.messages).expect("failed to serialize messages"),
+            )
+            .add_attribute("fraud_window_ends", other.fraud_window_ends.to_string())
+    }
+}
+
+pub struct MessagesChallenged {
+    pub messages: Vec<CrossChainId>,
+    pub challenger: Addr,
+}
+
+impl From<MessagesChallenged> for Event {
+    fn from(other: MessagesChallenged) -> Self {
+        Event::new("messages_challenged")
+            .add_attribute(
+                "messages",
+                serde_json::to_string(&other.messages).expect("failed to serialize messages"),
+            )
+            .add_attribute("challenger", other.challenger)
+    }
+}
+
+/// Emitted when every message of a verification call that isn't verified yet is already part of an open poll.
+/// References the last poll to expire, so callers know when retrying makes sense again
+pub struct VerificationInProgress {
+    pub poll_id: PollId,
+    pub expires_at: u64,
+}
+
+impl From<VerificationInProgress> for Event {
+    fn from(other: VerificationInProgress) -> Self {
+        Event::new("verification_in_progress")
+            .add_attribute(
+                "poll_id",
+                serde_json::to_string(&other.poll_id).expect("failed to serialize poll_id"),
+            )
+            .add_attribute("expires_at", other.expires_at.to_string())
+    }
+}
+
+/// Emitted alongside `PollEnded` when a poll expired before reaching consensus on every item.
+/// Items without consensus are not verified, so they can be submitted for verification again
+pub struct PollExpired {
+    pub poll_id: PollId,
+    /// indices of the poll items without consensus, in the order they were polled
+    pub items_without_consensus: Vec<usize>,
+}
+
+impl From<PollExpired> for Event {
+    fn from(other: PollExpired) -> Self {
+        Event::new("poll_expired")
+            .add_attribute(
+                "poll_id",
+                serde_json::to_string(&other.poll_id).expect("failed to serialize poll_id"),
+            )
+            .add_attribute(
+                "items_without_consensus",
+                serde_json::to_string(&other.items_without_consensus)
+                    .expect("failed to serialize items_without_consensus"),
+            )
+    }
+}
+
+/// Report of the workers who voted against the outcome of a poll that governance overrode, and are penalized for it
+pub struct PollOutcomeOverridden {
+    pub poll_id: PollId,
+    pub previous_results: Vec<Option<Vote>>,
+    pub results: Vec<Vote>,
+    pub incorrect_votes: Vec<IncorrectVote>,
+}
+
+#[cw_serde]
+pub struct IncorrectVote {
+    pub worker: Addr,
+    pub votes: Vec<Vote>,
+}
+
+impl From<PollOutcomeOverridden> for Event {
+    fn from(other: PollOutcomeOverridden) -> Self {
+        Event::new("poll_outcome_overridden")
+            .add_attribute(
+                "poll_id",
+                serde_json::to_string(&other.poll_id).expect("failed to serialize poll_id"),
+            )
+            .add_attribute(
+                "previous_results",
+                serde_json::to_string(&other.previous_results)
+                    .expect("failed to serialize previous_results"),
+            )
+            .add_attribute(
+                "results",
+                serde_json::to_string(&other.results).expect("failed to serialize results"),
+            )
+            .add_attribute(
+                "incorrect_votes",
+                serde_json::to_string(&other.incorrect_votes)
+                    .expect("failed to serialize incorrect_votes"),
+            )
+    }
+}
+
+/// Emitted when messages submitted for verification are polled, possibly split across multiple polls
+pub struct VerificationRequested {
+    pub request_id: u64,
+    pub poll_ids: Vec<PollId>,
+}
+
+impl From<VerificationRequested> for Event {
+    fn from(other: VerificationRequested) -> Self {
+        Event::new("verification_requested")
+            .add_attribute("request_id", other.request_id.to_string())
+            .add_attribute(
+                "poll_ids",
+                serde_json::to_string(&other.poll_ids).expect("failed to serialize poll_ids"),
+            )
+    }
+}
+
+pub struct FinalizationRewardPaid {
+    pub poll_id: PollId,
+    pub recipient: Addr,
+    pub reward: Coin,
+}
+
+impl From<FinalizationRewardPaid> for Event {
+    fn from(other: FinalizationRewardPaid) -> Self {
+        Event::new("finalization_reward_paid")
+            .add_attribute(
+                "poll_id",
+                serde_json::to_string(&other.poll_id).expect("failed to serialize poll_id"),
+            )
+            .add_attribute("recipient", other.recipient)
+            .add_attribute("reward", other.reward.to_string())
+    }
+}
+
+pub struct PollDisputed {
+    pub poll_id: PollId,
+    pub challenger: Addr,
+    pub deposit: Option<Coin>,
+}
+
+impl From<PollDisputed> for Event {
+    fn from(other: PollDisputed) -> Self {
+        Event::new("poll_disputed")
+            .add_attribute(
+                "poll_id",
+                serde_json::to_string(&other.poll_id).expect("failed to serialize poll_id"),
+            )
+            .add_attribute("challenger", other.challenger)
+            .add_attribute(
+                "deposit",
+                serde_json::to_string(&other.deposit).expect("failed to serialize deposit"),
+            )
+    }
+}
+
+pub struct DisputeLapsed {
+    pub poll_id: PollId,
+    pub challenger: Addr,
+    pub deposit: Option<Coin>,
+}
+
+impl From<DisputeLapsed> for Event {
+    fn from(other: DisputeLapsed) -> Self {
+        Event::new("dispute_lapsed")
+            .add_attribute(
+                "poll_id",
+                serde_json::to_string(&other.poll_id).expect("failed to serialize poll_id"),
+            )
+            .add_attribute("challenger", other.challenger)
+            .add_attribute(
+                "deposit",
+                serde_json::to_string(&other.deposit).expect("failed to serialize deposit"),
+            )
+    }
+}
+
+pub struct ConfirmationHeightUpdated {
+    pub previous_confirmation_height: u64,
+    pub confirmation_height: u64,
+}
+
+impl From<ConfirmationHeightUpdated> for Event {
+    fn from(other: ConfirmationHeightUpdated) -> Self {
+        Event::new("confirmation_height_updated")
+            .add_attribute(
+                "previous_confirmation_height",
+                other.previous_confirmation_height.to_string(),
+            )
+            .add_attribute("confirmation_height", other.confirmation_height.to_string())
+    }
+}
+
+pub struct PenaltyFailed {
+    pub error: String,
+}
+
+impl From<PenaltyFailed> for Event {
+    fn from(other: PenaltyFailed) -> Self {
+        Event::new("penalty_failed").add_attribute("error", other.error)
+    }
+}
+
+/// Status transition of a single polled message. Every transition is its own event carrying the chain, id and poll of the message
+/// as separate attributes, so the verification of a message can be followed without querying the contract
+pub struct MessageStatusChanged {
+    pub cc_id: CrossChainId,
+    pub poll_id: PollId,
+    pub transition: MessageStatusTransition,
+}
+
+pub enum MessageStatusTransition {
+    PollCreated,
+    VoteCast { voter: Addr, vote: Vote },
+    QuorumReached { vote: Vote },
+    Verified,
+    Rejected { vote: Vote },
+    // named differently from the poll level `poll_expired` event, so both can be told apart by their type
+    Expired,
+}
+
+impl MessageStatusTransition {
+    /// Transition of a message when the outcome of its poll is final
+    pub fn from_outcome(outcome: Option<Vote>) -> Self {
+        match outcome {
+            Some(Vote::SucceededOnChain) => MessageStatusTransition::Verified,
+            Some(vote) => MessageStatusTransition::Rejected { vote },
+            None => MessageStatusTransition::Expired,
+        }
+    }
+}
+
+impl From<MessageStatusChanged> for Event {
+    fn from(other: MessageStatusChanged) -> Self {
+        let event = match other.transition {
+            MessageStatusTransition::PollCreated => Event::new("poll_created"),
+            MessageStatusTransition::VoteCast { voter, vote } => Event::new("vote_cast")
+                .add_attribute("voter", voter)
+                .add_attribute(
+                    "vote",
+                    serde_json::to_string(&vote).expect("failed to serialize vote"),
+                ),
+            MessageStatusTransition::QuorumReached { vote } => Event::new("quorum_reached")
+                .add_attribute(
+                    "vote",
+                    serde_json::to_string(&vote).expect("failed to serialize vote"),
+                ),
+            MessageStatusTransition::Verified => Event::new("message_verified"),
+            MessageStatusTransition::Rejected { vote } => Event::new("message_rejected")
+                .add_attribute(
+                    "vote",
+                    serde_json::to_string(&vote).expect("failed to serialize vote"),
+                ),
+            MessageStatusTransition::Expired => Event::new("message_expired"),
+        };
+
+        event
+            .add_attribute("chain", other.cc_id.chain.to_string())
+            .add_attribute("message_id", other.cc_id.id.to_string())
+            .add_attribute(
+                "poll_id",
+                serde_json::to_string(&other.poll_id).expect("failed to serialize poll_id"),
+            )
+    }
+}
+
 pub struct Voted {
     pub poll_id: PollId,
     pub voter: Addr,
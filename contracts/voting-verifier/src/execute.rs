@@ -1,24 +1,38 @@
 use axelar_wasm_std::operators::Operators;
 use cosmwasm_std::{
-    to_binary, Deps, DepsMut, Env, MessageInfo, QueryRequest, Response, Storage, WasmMsg, WasmQuery,
+    to_binary, Addr, BankMsg, Coin, Deps, DepsMut, Env, Event, MessageInfo, QueryRequest, Response,
+    Storage, SubMsg, Uint256, WasmMsg, WasmQuery,
 };
 
-use axelar_wasm_std::voting::{PollId, Vote};
+use axelar_wasm_std::voting::{self, PollId, PollStatus, Vote};
 use axelar_wasm_std::{nonempty, snapshot, voting::WeightedPoll};
-use connection_router::state::{ChainName, Message};
+use connection_router::state::{ChainName, CrossChainId, Message};
 use service_registry::msg::QueryMsg;
-use service_registry::state::Worker;
+use service_registry::state::{BondingState, Worker};
 
+use crate::contract::PENALTY_REPLY_ID;
 use crate::error::ContractError;
 use crate::events::{
-    PollEnded, PollMetadata, PollStarted, TxEventConfirmation, Voted, WorkerSetConfirmation,
+    ConfirmationHeightUpdated, DisputeLapsed, FinalizationRewardPaid, IncorrectVote,
+    MessageStatusChanged, MessageStatusTransition, MessagesChallenged,
+    MessagesProvisionallyVerified, PollDisputed, PollEnded, PollExpired, PollMetadata,
+    PollOutcomeOverridden, PollStarted, TxEventConfirmation, VerificationInProgress,
+    VerificationRequested, Voted, WorkerSetConfirmation,
 };
-use crate::msg::{EndPollResponse, VerifyMessagesResponse};
+use crate::msg::{EndPollResponse, VerifyMessagesResponse, VoteJustification};
 use crate::query::{
-    is_verified, is_worker_set_verified, msg_verification_status, VerificationStatus,
+    is_verified, msg_verification_status, worker_set_verification_status, VerificationStatus,
+};
+use crate::state::{
+    self, escrow_deposit, release_deposit, save_tx_message, update_verification_costs, Config,
+    Dispute, Poll, PollContent, ProvisionalMessage, VerificationCosts, POLL_MESSAGES,
+    POLL_WORKER_SETS, PROVISIONAL_MESSAGES,
+};
+use crate::state::{
+    CONFIG, ESCROWED_DEPOSITS, POLLS, POLL_CONTENT_HASHES, POLL_DISPUTES, POLL_DISPUTE_WINDOW_ENDS,
+    POLL_ID, POLL_METRICS, POLL_OVERRIDES, POLL_START_HEIGHTS, VERIFICATION_REQUESTS,
+    VERIFICATION_REQUEST_ID, VOTE_JUSTIFICATIONS,
 };
-use crate::state::{self, Poll, PollContent, POLL_MESSAGES, POLL_WORKER_SETS};
-use crate::state::{CONFIG, POLLS, POLL_ID};
 
 pub fn verify_worker_set(
     deps: DepsMut,
@@ -26,7 +40,11 @@ pub fn verify_worker_set(
     message_id: nonempty::String,
     new_operators: Operators,
 ) -> Result<Response, ContractError> {
-    if is_worker_set_verified(deps.as_ref(), &new_operators)? {
+    // a worker set within the dispute window of its poll is confirmed already, unless governance overrides the outcome
+    if matches!(
+        worker_set_verification_status(deps.as_ref(), &new_operators, env.block.height)?,
+        VerificationStatus::Verified | VerificationStatus::Disputable
+    ) {
         return Err(ContractError::WorkerSetAlreadyConfirmed);
     }
 
@@ -34,18 +52,14 @@ pub fn verify_worker_set(
     let snapshot = take_snapshot(deps.as_ref(), &config.source_chain)?;
     let participants = snapshot.get_participants();
 
-    let poll_id = create_worker_set_poll(
-        deps.storage,
-        env.block.height,
-        config.block_expiry,
-        snapshot,
-    )?;
+    let poll_id = create_worker_set_poll(deps.storage, &config, env.block.height, snapshot)?;
 
     POLL_WORKER_SETS.save(
         deps.storage,
         &new_operators.hash(),
         &PollContent::<Operators>::new(new_operators.clone(), poll_id),
     )?;
+    POLL_CONTENT_HASHES.save(deps.storage, poll_id, &vec![new_operators.hash()])?;
 
     Ok(Response::new().add_event(
         PollStarted::WorkerSet {
@@ -81,97 +95,393 @@ pub fn verify_messages(
         Err(ContractError::SourceChainMismatch(source_chain))?;
     }
 
+    // workers only check the contract call on the source chain, so a token transfer attached to the message would be approved unchecked
+    if let Some(message) = messages.iter().find(|message| message.token.is_some()) {
+        Err(ContractError::TokenTransferNotSupported(
+            message.cc_id.clone(),
+        ))?;
+    }
+
     let config = CONFIG.load(deps.storage)?;
 
-    let response = Response::new().set_data(to_binary(&VerifyMessagesResponse {
-        verification_statuses: is_verified(deps.as_ref(), &messages)?,
-    })?);
+    let verification_statuses = is_verified(deps.as_ref(), &messages, env.block.height)?;
 
-    let messages = messages
-        .into_iter()
-        .map(|message| {
-            msg_verification_status(deps.as_ref(), &message).map(|status| (status, message))
-        })
+    let statuses = messages
+        .iter()
+        .map(|message| msg_verification_status(deps.as_ref(), message, env.block.height))
         .collect::<Result<Vec<_>, _>>()?;
 
-    let msgs_to_verify: Vec<Message> = messages
-        .into_iter()
-        .filter_map(|(status, message)| match status {
-            VerificationStatus::FailedToVerify | VerificationStatus::NotVerified => Some(message),
-            VerificationStatus::InProgress | VerificationStatus::Verified => None,
-        })
-        .collect();
+    // in optimistic mode, new messages are accepted right away, but messages that already failed a poll are polled again
+    let mut msgs_to_verify: Vec<Message> = vec![];
+    let mut msgs_to_accept: Vec<Message> = vec![];
+    let mut msgs_in_progress: Vec<Message> = vec![];
+    for (status, message) in statuses.into_iter().zip(messages.iter().cloned()) {
+        match status {
+            VerificationStatus::NotVerified if config.fraud_window.is_some() => {
+                msgs_to_accept.push(message)
+            }
+            VerificationStatus::FailedToVerify | VerificationStatus::NotVerified => {
+                msgs_to_verify.push(message)
+            }
+            VerificationStatus::InProgress => msgs_in_progress.push(message),
+            VerificationStatus::ProvisionallyVerified
+            | VerificationStatus::Verified
+            | VerificationStatus::Disputable => (),
+        }
+    }
+
+    // nothing left to do but wait for the open polls, so let the caller know how long to wait instead of silently succeeding.
+    // This is not an error, because failing would revert the caller's whole transaction
+    let response =
+        if msgs_to_verify.is_empty() && msgs_to_accept.is_empty() && !msgs_in_progress.is_empty() {
+            let (poll_id, expires_at) = last_expiring_poll(deps.as_ref(), &msgs_in_progress)?;
+            Response::new().add_event(
+                VerificationInProgress {
+                    poll_id,
+                    expires_at,
+                }
+                .into(),
+            )
+        } else {
+            Response::new()
+        };
+
+    let response = match config.fraud_window {
+        Some(fraud_window) if !msgs_to_accept.is_empty() => {
+            let fraud_window_ends = env.block.height + fraud_window;
+            for message in &msgs_to_accept {
+                save_tx_message(deps.storage, message)?;
+                PROVISIONAL_MESSAGES.save(
+                    deps.storage,
+                    &message.hash(),
+                    &ProvisionalMessage {
+                        content: message.clone(),
+                        fraud_window_ends,
+                    },
+                )?;
+            }
+
+            response.add_event(
+                MessagesProvisionallyVerified {
+                    messages: msgs_to_accept
+                        .into_iter()
+                        .map(|message| message.cc_id)
+                        .collect(),
+                    fraud_window_ends,
+                }
+                .into(),
+            )
+        }
+        _ => response,
+    };
 
     if msgs_to_verify.is_empty() {
-        return Ok(response);
+        return Ok(response.set_data(to_binary(&VerifyMessagesResponse {
+            verification_statuses,
+            request_id: None,
+        })?));
+    }
+
+    // the whole request is recorded, so its progress can be followed even if it is split into multiple polls
+    let request_id = VERIFICATION_REQUEST_ID.incr(deps.storage)?;
+    for (idx, message) in messages.iter().enumerate() {
+        VERIFICATION_REQUESTS.save(
+            deps.storage,
+            (request_id, idx.try_into().expect("too many messages")),
+            message,
+        )?;
     }
 
-    let snapshot = take_snapshot(deps.as_ref(), &msgs_to_verify[0].cc_id.chain)?;
+    let (poll_ids, poll_events): (Vec<_>, Vec<_>) =
+        start_messages_polls(deps, &env, &config, msgs_to_verify)?
+            .into_iter()
+            .unzip();
+
+    Ok(response
+        .set_data(to_binary(&VerifyMessagesResponse {
+            verification_statuses,
+            request_id: Some(request_id),
+        })?)
+        .add_events(poll_events.into_iter().flatten())
+        .add_event(
+            VerificationRequested {
+                request_id,
+                poll_ids,
+            }
+            .into(),
+        ))
+}
+
+pub fn challenge_messages(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    messages: Vec<Message>,
+) -> Result<Response, ContractError> {
+    if messages.is_empty() {
+        Err(ContractError::EmptyMessages)?;
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    if !take_snapshot(deps.as_ref(), &config.source_chain)?
+        .get_participants()
+        .contains(&info.sender)
+    {
+        return Err(ContractError::NotActiveWorker(info.sender));
+    }
+
+    for message in &messages {
+        if msg_verification_status(deps.as_ref(), message, env.block.height)?
+            != VerificationStatus::ProvisionallyVerified
+        {
+            return Err(ContractError::MessageNotChallengeable(
+                message.cc_id.clone(),
+            ));
+        }
+
+        PROVISIONAL_MESSAGES.remove(deps.storage, &message.hash());
+    }
+
+    let challenged = MessagesChallenged {
+        messages: messages
+            .iter()
+            .map(|message| message.cc_id.clone())
+            .collect(),
+        challenger: info.sender,
+    };
+
+    Ok(Response::new().add_event(challenged.into()).add_events(
+        start_messages_polls(deps, &env, &config, messages)?
+            .into_iter()
+            .flat_map(|(_, events)| events),
+    ))
+}
+
+fn last_expiring_poll(deps: Deps, messages: &[Message]) -> Result<(PollId, u64), ContractError> {
+    Ok(messages
+        .iter()
+        .map(|message| {
+            let poll_id = POLL_MESSAGES.load(deps.storage, &message.hash())?.poll_id;
+            match POLLS.load(deps.storage, poll_id)? {
+                Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll) => {
+                    Ok((poll_id, poll.expires_at))
+                }
+            }
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?
+        .into_iter()
+        .max_by_key(|(_, expires_at)| *expires_at)
+        .expect("violated invariant: no messages in progress"))
+}
+
+// messages beyond the configured maximum poll size are split into multiple polls, so the gas cost of voting on a poll stays bounded
+fn start_messages_polls(
+    mut deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    messages: Vec<Message>,
+) -> Result<Vec<(PollId, Vec<Event>)>, ContractError> {
+    let max_poll_size = config
+        .max_messages_per_poll
+        .map(|max| u64::from(max) as usize)
+        .unwrap_or(messages.len());
+
+    messages
+        .chunks(max_poll_size)
+        .map(|messages| start_messages_poll(deps.branch(), env, config, messages.to_vec()))
+        .collect()
+}
+
+fn start_messages_poll(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    messages: Vec<Message>,
+) -> Result<(PollId, Vec<Event>), ContractError> {
+    let source_chain = &messages[0].cc_id.chain;
+    let snapshot = take_snapshot(deps.as_ref(), source_chain)?;
     let participants = snapshot.get_participants();
     let id = create_messages_poll(
         deps.storage,
+        config,
         env.block.height,
-        config.block_expiry,
         snapshot,
-        msgs_to_verify.len(),
+        messages.len(),
     )?;
 
-    for (idx, message) in msgs_to_verify.iter().enumerate() {
+    for (idx, message) in messages.iter().enumerate() {
+        save_tx_message(deps.storage, message)?;
         POLL_MESSAGES.save(
             deps.storage,
             &message.hash(),
             &state::PollContent::<Message>::new(message.clone(), id, idx),
         )?;
     }
+    POLL_CONTENT_HASHES.save(
+        deps.storage,
+        id,
+        &messages.iter().map(Message::hash).collect(),
+    )?;
 
-    let messages = msgs_to_verify
+    let status_events = messages
+        .iter()
+        .map(|message| {
+            MessageStatusChanged {
+                cc_id: message.cc_id.clone(),
+                poll_id: id,
+                transition: MessageStatusTransition::PollCreated,
+            }
+            .into()
+        })
+        .collect::<Vec<Event>>();
+
+    let messages = messages
         .into_iter()
         .map(TryInto::try_into)
         .collect::<Result<Vec<TxEventConfirmation>, _>>()?;
 
-    Ok(response.add_event(
-        PollStarted::Messages {
-            messages,
-            metadata: PollMetadata {
-                poll_id: id,
-                source_chain: config.source_chain,
-                source_gateway_address: config.source_gateway_address,
-                confirmation_height: config.confirmation_height,
-                expires_at: env.block.height + config.block_expiry,
-                participants,
-            },
-        }
-        .into(),
+    let poll_started = PollStarted::Messages {
+        messages,
+        metadata: PollMetadata {
+            poll_id: id,
+            source_chain: config.source_chain.clone(),
+            source_gateway_address: config.source_gateway_address.clone(),
+            confirmation_height: config.confirmation_height,
+            expires_at: env.block.height + config.block_expiry,
+            participants,
+        },
+    };
+
+    Ok((
+        id,
+        std::iter::once(poll_started.into())
+            .chain(status_events)
+            .collect(),
     ))
 }
 
+// worker set polls and polls started before their content was indexed have no messages to report status changes for
+fn polled_messages(
+    storage: &dyn Storage,
+    poll_id: PollId,
+    poll: &Poll,
+) -> Result<Vec<CrossChainId>, ContractError> {
+    match (poll, POLL_CONTENT_HASHES.may_load(storage, poll_id)?) {
+        (Poll::Messages(_), Some(hashes)) => hashes
+            .iter()
+            .map(|hash| Ok(POLL_MESSAGES.load(storage, hash)?.content.cc_id))
+            .collect(),
+        _ => Ok(vec![]),
+    }
+}
+
+fn message_status_events(
+    messages: Vec<CrossChainId>,
+    poll_id: PollId,
+    transitions: impl IntoIterator<Item = Option<MessageStatusTransition>>,
+) -> Vec<Event> {
+    messages
+        .into_iter()
+        .zip(transitions)
+        .filter_map(|(cc_id, transition)| {
+            transition.map(|transition| {
+                MessageStatusChanged {
+                    cc_id,
+                    poll_id,
+                    transition,
+                }
+                .into()
+            })
+        })
+        .collect()
+}
+
+fn poll_results(poll: &Poll) -> Vec<Option<Vote>> {
+    match poll {
+        Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll) => poll.state().results,
+    }
+}
+
 pub fn vote(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     poll_id: PollId,
     votes: Vec<Vote>,
+    justification: Option<VoteJustification>,
 ) -> Result<Response, ContractError> {
     let poll = POLLS
         .may_load(deps.storage, poll_id)?
-        .ok_or(ContractError::PollNotFound)?
-        .try_map(|poll| {
-            poll.cast_vote(env.block.height, &info.sender, votes)
-                .map_err(ContractError::from)
-        })?;
+        .ok_or(ContractError::PollNotFound)?;
+    let had_quorum = has_quorum_on_all_items(&poll);
+    let previous_results = poll_results(&poll);
+
+    let poll = poll.try_map(|poll| {
+        poll.cast_vote(env.block.height, &info.sender, votes.clone())
+            .map_err(ContractError::from)
+    })?;
 
     POLLS.save(deps.storage, poll_id, &poll)?;
+    if let Some(justification) = justification {
+        VOTE_JUSTIFICATIONS.save(deps.storage, (poll_id, &info.sender), &justification)?;
+    }
 
-    Ok(Response::new().add_event(
-        Voted {
-            poll_id,
-            voter: info.sender,
+    let source_chain = CONFIG.load(deps.storage)?.source_chain;
+    update_verification_costs(deps.storage, &source_chain, VerificationCosts::record_vote)?;
+
+    if !had_quorum && has_quorum_on_all_items(&poll) {
+        // polls started before metrics were tracked have no start height
+        if let Some(start_height) = POLL_START_HEIGHTS.may_load(deps.storage, poll_id)? {
+            let metrics = POLL_METRICS
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                .record_quorum_reached(env.block.height - start_height);
+            POLL_METRICS.save(deps.storage, &metrics)?;
         }
-        .into(),
-    ))
+    }
+
+    let messages = polled_messages(deps.storage, poll_id, &poll)?;
+    let votes_cast = message_status_events(
+        messages.clone(),
+        poll_id,
+        votes.into_iter().map(|vote| {
+            Some(MessageStatusTransition::VoteCast {
+                voter: info.sender.clone(),
+                vote,
+            })
+        }),
+    );
+    let quorums_reached = message_status_events(
+        messages,
+        poll_id,
+        previous_results
+            .into_iter()
+            .zip(poll_results(&poll))
+            .map(|(previous, current)| match (previous, current) {
+                (None, Some(vote)) => Some(MessageStatusTransition::QuorumReached { vote }),
+                _ => None,
+            }),
+    );
+
+    Ok(Response::new()
+        .add_event(
+            Voted {
+                poll_id,
+                voter: info.sender,
+            }
+            .into(),
+        )
+        .add_events(votes_cast)
+        .add_events(quorums_reached))
 }
 
-pub fn end_poll(deps: DepsMut, env: Env, poll_id: PollId) -> Result<Response, ContractError> {
+pub fn end_poll(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: PollId,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
     let poll = POLLS
@@ -185,6 +495,12 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: PollId) -> Result<Response, Co
         Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll) => poll.state(),
     };
 
+    let metrics = POLL_METRICS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .record_poll_ended(&poll_result.results);
+    POLL_METRICS.save(deps.storage, &metrics)?;
+
     // TODO: change rewards contract interface to accept a list of addresses to avoid creating multiple wasm messages
     let rewards_msgs = poll_result
         .consensus_participants
@@ -197,12 +513,31 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: PollId) -> Result<Response, Co
                     .try_into()
                     .expect("couldn't convert poll id to nonempty string"),
                 worker_address: address.to_string(),
+                late: poll_result.late_participants.contains(address),
             })
             .expect("failed to serialize message for rewards contract"),
             funds: vec![],
         });
 
-    Ok(Response::new()
+    let outcomes = message_status_events(
+        polled_messages(deps.storage, poll_id, &poll)?,
+        poll_id,
+        poll_result
+            .results
+            .iter()
+            .cloned()
+            .map(|result| Some(MessageStatusTransition::from_outcome(result))),
+    );
+
+    let items_without_consensus = poll_result
+        .results
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| result.is_none())
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+
+    let response = Response::new()
         .add_messages(rewards_msgs)
         .add_event(
             PollEnded {
@@ -211,7 +546,311 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: PollId) -> Result<Response, Co
             }
             .into(),
         )
-        .set_data(to_binary(&EndPollResponse { poll_result })?))
+        .add_events(outcomes);
+
+    let response = if items_without_consensus.is_empty() {
+        response
+    } else {
+        response.add_event(
+            PollExpired {
+                poll_id: poll_result.poll_id,
+                items_without_consensus,
+            }
+            .into(),
+        )
+    };
+
+    // a poll can only be ended once, so only the first caller is rewarded.
+    // Ending the poll must not depend on the contract being funded, so the reward is skipped if the balance doesn't cover it.
+    // Deposits of open disputes are held in escrow and don't count towards the balance
+    let response = match config.finalization_reward {
+        Some(reward)
+            if !reward.amount.is_zero()
+                && deps
+                    .querier
+                    .query_balance(&env.contract.address, &reward.denom)?
+                    .amount
+                    .saturating_sub(
+                        ESCROWED_DEPOSITS
+                            .may_load(deps.storage, &reward.denom)?
+                            .unwrap_or_default(),
+                    )
+                    >= reward.amount =>
+        {
+            response
+                .add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: vec![reward.clone()],
+                })
+                .add_event(
+                    FinalizationRewardPaid {
+                        poll_id,
+                        recipient: info.sender,
+                        reward,
+                    }
+                    .into(),
+                )
+        }
+        _ => response,
+    };
+
+    Ok(response.set_data(to_binary(&EndPollResponse { poll_result })?))
+}
+
+pub fn override_poll_outcome(
+    deps: DepsMut,
+    info: MessageInfo,
+    poll_id: PollId,
+    results: Vec<Vote>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.governance.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let poll = POLLS
+        .may_load(deps.storage, poll_id)?
+        .ok_or(ContractError::PollNotFound)?;
+    let messages = polled_messages(deps.storage, poll_id, &poll)?;
+    let poll = match poll {
+        Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll) => poll,
+    };
+
+    if poll.status != PollStatus::Finished {
+        return Err(ContractError::PollNotFinished(poll_id));
+    }
+
+    if POLL_OVERRIDES.has(deps.storage, poll_id) {
+        return Err(ContractError::PollAlreadyOverridden(poll_id));
+    }
+
+    if results.len() as u64 != poll.poll_size {
+        return Err(voting::Error::InvalidVoteSize.into());
+    }
+
+    POLL_OVERRIDES.save(deps.storage, poll_id, &results)?;
+
+    let incorrect_votes = poll
+        .participation
+        .iter()
+        .filter_map(|(worker, participation)| match &participation.vote {
+            Some(votes) if *votes != results => Some(IncorrectVote {
+                worker: Addr::unchecked(worker), // Ok to convert unchecked here, since the snapshot only contains valid addresses
+                votes: votes.clone(),
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // the participation of the penalized workers was recorded under the poll id when the poll ended
+    let penalties = incorrect_votes
+        .iter()
+        .map(|incorrect_vote| {
+            Ok(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: config.rewards_contract.to_string(),
+                    msg: to_binary(&rewards::msg::ExecuteMsg::PenalizeWorker {
+                        event_id: poll_id
+                            .to_string()
+                            .try_into()
+                            .expect("couldn't convert poll id to nonempty string"),
+                        worker_address: incorrect_vote.worker.to_string(),
+                    })?,
+                    funds: vec![],
+                },
+                PENALTY_REPLY_ID,
+            ))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    let previous_results = poll.state().results;
+
+    // the override resolves an open dispute and releases its deposit from escrow. A challenger who was right gets the deposit back
+    let refund = match POLL_DISPUTES.may_load(deps.storage, poll_id)? {
+        Some(Dispute {
+            challenger,
+            deposit: Some(deposit),
+            ..
+        }) => {
+            release_deposit(deps.storage, &deposit)?;
+            (previous_results != results.iter().cloned().map(Some).collect::<Vec<_>>()).then(|| {
+                BankMsg::Send {
+                    to_address: challenger.to_string(),
+                    amount: vec![deposit],
+                }
+            })
+        }
+        _ => None,
+    };
+    POLL_DISPUTES.remove(deps.storage, poll_id);
+
+    // only messages whose outcome changed transition to a new status
+    let outcomes = message_status_events(
+        messages,
+        poll_id,
+        previous_results
+            .iter()
+            .zip(results.iter())
+            .map(|(previous, result)| {
+                (previous.as_ref() != Some(result))
+                    .then(|| MessageStatusTransition::from_outcome(Some(result.clone())))
+            }),
+    );
+
+    Ok(Response::new()
+        .add_submessages(penalties)
+        .add_messages(refund)
+        .add_events(outcomes)
+        .add_event(
+            PollOutcomeOverridden {
+                poll_id,
+                previous_results,
+                results,
+                incorrect_votes,
+            }
+            .into(),
+        ))
+}
+
+pub fn dispute_poll_outcome(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: PollId,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if !POLLS.has(deps.storage, poll_id) {
+        return Err(ContractError::PollNotFound);
+    }
+
+    let window_ends = match POLL_DISPUTE_WINDOW_ENDS.may_load(deps.storage, poll_id)? {
+        Some(window_ends) if env.block.height <= window_ends => window_ends,
+        _ => return Err(ContractError::PollNotDisputable(poll_id)),
+    };
+    if POLL_OVERRIDES.has(deps.storage, poll_id) || POLL_DISPUTES.has(deps.storage, poll_id) {
+        return Err(ContractError::PollNotDisputable(poll_id));
+    }
+
+    let deposit = if config.governance.as_ref() == Some(&info.sender) {
+        None
+    } else {
+        let deposit = config.dispute_deposit.ok_or(ContractError::Unauthorized)?;
+        if info.funds != vec![deposit.clone()] {
+            return Err(ContractError::InvalidDisputeDeposit(deposit));
+        }
+        escrow_deposit(deps.storage, &deposit)?;
+        Some(deposit)
+    };
+
+    // the resolution deadline counts from the end of the dispute window, so a lapsed poll can't be disputed again
+    let resolution_period = config
+        .dispute_resolution_period
+        .or(config.dispute_window)
+        .unwrap_or_default();
+    POLL_DISPUTES.save(
+        deps.storage,
+        poll_id,
+        &Dispute {
+            challenger: info.sender.clone(),
+            deposit: deposit.clone(),
+            resolution_deadline: Some(window_ends + resolution_period),
+        },
+    )?;
+
+    Ok(Response::new().add_event(
+        PollDisputed {
+            poll_id,
+            challenger: info.sender,
+            deposit,
+        }
+        .into(),
+    ))
+}
+
+// the result of the vote stands for a dispute that governance didn't resolve in time. The contract keeps the deposit
+pub fn settle_lapsed_dispute(
+    deps: DepsMut,
+    env: Env,
+    poll_id: PollId,
+) -> Result<Response, ContractError> {
+    let dispute = match POLL_DISPUTES.may_load(deps.storage, poll_id)? {
+        Some(dispute) if dispute.is_lapsed(env.block.height) => dispute,
+        _ => return Err(ContractError::DisputeNotLapsed(poll_id)),
+    };
+
+    if let Some(deposit) = &dispute.deposit {
+        release_deposit(deps.storage, deposit)?;
+    }
+    POLL_DISPUTES.remove(deps.storage, poll_id);
+
+    Ok(Response::new().add_event(
+        DisputeLapsed {
+            poll_id,
+            challenger: dispute.challenger,
+            deposit: dispute.deposit,
+        }
+        .into(),
+    ))
+}
+
+pub fn update_dispute_params(
+    deps: DepsMut,
+    info: MessageInfo,
+    dispute_window: Option<u64>,
+    dispute_deposit: Option<Coin>,
+    dispute_resolution_period: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.governance.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    config.dispute_window = dispute_window;
+    config.dispute_deposit = dispute_deposit;
+    config.dispute_resolution_period = dispute_resolution_period;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new())
+}
+
+pub fn update_finalization_reward(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward: Option<Coin>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.governance.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    config.finalization_reward = reward;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new())
+}
+
+pub fn update_confirmation_height(
+    deps: DepsMut,
+    info: MessageInfo,
+    confirmation_height: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.governance.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let previous_confirmation_height = config.confirmation_height;
+    config.confirmation_height = confirmation_height;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_event(
+        ConfirmationHeightUpdated {
+            previous_confirmation_height,
+            confirmation_height,
+        }
+        .into(),
+    ))
 }
 
 fn take_snapshot(deps: Deps, chain: &ChainName) -> Result<snapshot::Snapshot, ContractError> {
@@ -229,9 +868,11 @@ fn take_snapshot(deps: Deps, chain: &ChainName) -> Result<snapshot::Snapshot, Co
         msg: to_binary(&active_workers_query)?,
     }))?;
 
+    // the snapshot is taken when the poll is created, so bond changes during the poll don't affect its outcome
     let participants = workers
         .into_iter()
-        .map(service_registry::state::Worker::try_into)
+        .map(stake_weighted_participant)
+        .filter_map(Result::transpose)
         .collect::<Result<Vec<snapshot::Participant>, _>>()?;
 
     Ok(snapshot::Snapshot::new(
@@ -240,31 +881,180 @@ fn take_snapshot(deps: Deps, chain: &ChainName) -> Result<snapshot::Snapshot, Co
     ))
 }
 
+// votes are weighted by the bonded amount of the worker. Workers without any bond get no say in the poll
+fn stake_weighted_participant(
+    worker: Worker,
+) -> Result<Option<snapshot::Participant>, ContractError> {
+    match worker.bonding_state {
+        BondingState::Bonded { amount } => Ok(nonempty::Uint256::try_from(Uint256::from(amount))
+            .ok()
+            .map(|weight| snapshot::Participant {
+                address: worker.address,
+                weight,
+            })),
+        bonding_state => Err(ContractError::from(
+            service_registry::ContractError::InvalidBondingState(bonding_state),
+        )),
+    }
+}
+
 fn create_worker_set_poll(
     store: &mut dyn Storage,
+    config: &Config,
     block_height: u64,
-    expiry: u64,
     snapshot: snapshot::Snapshot,
 ) -> Result<PollId, ContractError> {
     let id = POLL_ID.incr(store)?;
 
-    let poll = WeightedPoll::new(id, snapshot, block_height + expiry, 1);
+    let expires_at = block_height + config.block_expiry;
+    let poll = WeightedPoll::new(id, snapshot, expires_at, 1);
     POLLS.save(store, id, &state::Poll::ConfirmWorkerSet(poll))?;
+    record_poll_opened(store, &config.source_chain, id, block_height, 1)?;
+    record_dispute_window(store, config, id, expires_at)?;
 
     Ok(id)
 }
 
 fn create_messages_poll(
     store: &mut dyn Storage,
+    config: &Config,
     block_height: u64,
-    expiry: u64,
     snapshot: snapshot::Snapshot,
     poll_size: usize,
 ) -> Result<PollId, ContractError> {
     let id = POLL_ID.incr(store)?;
 
-    let poll = WeightedPoll::new(id, snapshot, block_height + expiry, poll_size);
+    let expires_at = block_height + config.block_expiry;
+    let poll = WeightedPoll::new(id, snapshot, expires_at, poll_size);
     POLLS.save(store, id, &state::Poll::Messages(poll))?;
+    record_poll_opened(
+        store,
+        &config.source_chain,
+        id,
+        block_height,
+        poll_size as u64,
+    )?;
+    record_dispute_window(store, config, id, expires_at)?;
 
     Ok(id)
 }
+
+// the dispute window is fixed when the poll starts, so later changes to it don't affect the outcome of running polls
+fn record_dispute_window(
+    store: &mut dyn Storage,
+    config: &Config,
+    poll_id: PollId,
+    expires_at: u64,
+) -> Result<(), ContractError> {
+    if let Some(dispute_window) = config.dispute_window {
+        POLL_DISPUTE_WINDOW_ENDS.save(store, poll_id, &(expires_at + dispute_window))?;
+    }
+    Ok(())
+}
+
+fn record_poll_opened(
+    store: &mut dyn Storage,
+    source_chain: &ChainName,
+    poll_id: PollId,
+    block_height: u64,
+    poll_size: u64,
+) -> Result<(), ContractError> {
+    POLL_START_HEIGHTS.save(store, poll_id, &block_height)?;
+    let metrics = POLL_METRICS
+        .may_load(store)?
+        .unwrap_or_default()
+        .record_poll_opened();
+    POLL_METRICS.save(store, &metrics)?;
+    update_verification_costs(store, source_chain, |costs| {
+        costs.record_poll_opened(poll_size)
+    })?;
+
+    Ok(())
+}
+
+fn has_quorum_on_all_items(poll: &Poll) -> bool {
+    match poll {
+        Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll) => {
+            poll.state().results.iter().all(Option::is_some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axelar_wasm_std::Threshold;
+    use cosmwasm_std::{Addr, Uint128};
+    use service_registry::state::AuthorizationState;
+
+    use super::*;
+
+    fn worker(address: &str, bonding_state: BondingState) -> Worker {
+        Worker {
+            address: Addr::unchecked(address),
+            bonding_state,
+            authorization_state: AuthorizationState::Authorized,
+            service_name: "service_name".to_string(),
+        }
+    }
+
+    #[test]
+    fn participants_are_weighted_by_bond() {
+        let participants = [
+            worker(
+                "worker1",
+                BondingState::Bonded {
+                    amount: 100u128.into(),
+                },
+            ),
+            worker(
+                "worker2",
+                BondingState::Bonded {
+                    amount: 300u128.into(),
+                },
+            ),
+            worker(
+                "worker3",
+                BondingState::Bonded {
+                    amount: Uint128::zero(),
+                },
+            ),
+        ]
+        .into_iter()
+        .map(stake_weighted_participant)
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+        assert_eq!(
+            participants
+                .iter()
+                .map(|participant| (participant.address.as_str(), *participant.weight.as_ref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("worker1", Uint256::from(100u128)),
+                ("worker2", Uint256::from(300u128))
+            ]
+        );
+
+        // quorum is computed over the bonded weight, so the worker with the larger bond reaches it alone
+        let snapshot = snapshot::Snapshot::new(
+            Threshold::try_from((2u64, 3u64))
+                .unwrap()
+                .try_into()
+                .unwrap(),
+            participants.try_into().unwrap(),
+        );
+        assert_eq!(*snapshot.quorum.as_ref(), Uint256::from(267u128));
+    }
+
+    #[test]
+    fn unbonding_workers_are_rejected() {
+        assert!(stake_weighted_participant(worker(
+            "worker",
+            BondingState::RequestedUnbonding {
+                amount: 100u128.into()
+            }
+        ))
+        .is_err());
+    }
+}
@@ -1,13 +1,17 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, HexBinary, Uint256};
 
 use axelar_wasm_std::{
     nonempty,
     operators::Operators,
-    voting::{PollId, PollState, Vote},
+    voting::{PollId, PollState, PollStatus, Tallies, Vote},
     MajorityThreshold,
 };
 use connection_router::state::{ChainName, CrossChainId, Message};
 
+use crate::query::VerificationStatus;
+use crate::state::Dispute;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     // params to query register service
@@ -20,6 +24,13 @@ pub struct InstantiateMsg {
     pub confirmation_height: u64,
     pub source_chain: ChainName,
     pub rewards_address: String,
+    // if set, messages are provisionally verified without a poll, and only polled if challenged
+    // by an active worker within this many blocks
+    pub fraud_window: Option<u64>,
+    // if set, this account can override the outcome of finished polls, e.g. after a poll concluded on the wrong outcome
+    pub governance_address: Option<String>,
+    // if set, batches with more messages are split into multiple polls, to keep the gas cost of voting on a poll bounded
+    pub max_messages_per_poll: Option<nonempty::Uint64>,
 }
 
 #[cw_serde]
@@ -34,6 +45,9 @@ pub enum ExecuteMsg {
     Vote {
         poll_id: PollId,
         votes: Vec<Vote>,
+        // source chain data the worker based its votes on, stored for audits of divergent votes
+        #[serde(default)]
+        justification: Option<VoteJustification>,
     },
 
     // returns a vector of true/false values, indicating current verification status for each message
@@ -42,6 +56,52 @@ pub enum ExecuteMsg {
         messages: Vec<Message>,
     },
 
+    // Starts a poll for messages that were provisionally verified in optimistic mode, if they are still within their fraud window.
+    // Can only be called by an active worker
+    ChallengeMessages {
+        messages: Vec<Message>,
+    },
+
+    // Replaces the outcome of a finished poll. Workers who voted against the new outcome are penalized in the rewards contract.
+    // Can only be called by governance
+    OverridePollOutcome {
+        poll_id: PollId,
+        results: Vec<Vote>,
+    },
+
+    // Sets the number of blocks a source chain transaction must be buried under before workers vote on it.
+    // Only applies to polls started afterwards. Can only be called by governance
+    UpdateConfirmationHeight {
+        confirmation_height: u64,
+    },
+
+    // Sets the reward paid out of the contract's balance to whoever ends a poll, or removes it if None.
+    // Can only be called by governance
+    UpdateFinalizationReward {
+        reward: Option<Coin>,
+    },
+
+    // Sets the dispute window, the deposit required to dispute a poll and the number of blocks after the dispute window
+    // governance has to resolve a dispute, which defaults to the dispute window. The dispute window only applies to polls started afterwards.
+    // Can only be called by governance
+    UpdateDisputeParams {
+        dispute_window: Option<u64>,
+        dispute_deposit: Option<Coin>,
+        #[serde(default)]
+        dispute_resolution_period: Option<u64>,
+    },
+
+    // Disputes the outcome of a poll within its dispute window, which holds back its verified results until governance overrides the outcome
+    // or the dispute lapses. Governance can dispute for free, anyone else needs to attach the dispute deposit
+    DisputePollOutcome {
+        poll_id: PollId,
+    },
+
+    // Removes a dispute that governance didn't resolve before its deadline. The contract keeps the deposit
+    SettleLapsedDispute {
+        poll_id: PollId,
+    },
+
     // Starts a poll to confirm a worker set update on the external evm gateway
     VerifyWorkerSet {
         message_id: nonempty::String,
@@ -49,16 +109,59 @@ pub enum ExecuteMsg {
     },
 }
 
+/// Source chain data a worker observed when casting its votes. It isn't checked by the contract
+#[cw_serde]
+pub struct VoteJustification {
+    /// Hash of the source chain transaction the worker looked up
+    pub tx_hash: Option<nonempty::String>,
+    /// Receipts root of the source chain block the transaction was included in
+    pub receipt_root: Option<HexBinary>,
+}
+
+#[cw_serde]
+pub struct PollResponse {
+    pub poll_id: PollId,
+    /// None if the poll was started before the content of polls was indexed
+    pub content: Option<PolledContent>,
+    pub status: PollStatus,
+    pub expires_at: u64,
+    pub quorum: Uint256,
+    /// Weight of the votes for each outcome, per poll item
+    pub tallies: Vec<Tallies>,
+    pub results: Vec<Option<Vote>>,
+    /// Outcome set by governance, which takes precedence over the results of the vote
+    pub overridden_results: Option<Vec<Vote>>,
+    /// Last block height at which the outcome can be disputed, None if the poll has no dispute window
+    pub dispute_window_ends: Option<u64>,
+    /// Dispute of the outcome that wasn't resolved or settled yet, if any
+    pub dispute: Option<Dispute>,
+    /// Vote of every worker in the poll's snapshot, ordered by worker address
+    pub votes: Vec<WorkerVote>,
+}
+
 #[cw_serde]
-pub struct Poll {
-    poll_id: PollId,
-    messages: Vec<Message>,
+pub enum PolledContent {
+    Messages(Vec<Message>),
+    WorkerSet(Operators),
+}
+
+#[cw_serde]
+pub struct WorkerVote {
+    pub worker: Addr,
+    pub weight: Uint256,
+    /// None if the worker hasn't voted
+    pub vote: Option<Vec<Vote>>,
+    /// Whether the vote was cast after every item had already reached consensus
+    pub late: bool,
+    /// Source chain data the worker attached to its vote, if any
+    pub justification: Option<VoteJustification>,
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    #[returns(Poll)]
+    // Returns the polled content, the vote of each worker and the tallied weight of each poll item
+    #[returns(PollResponse)]
     GetPoll { poll_id: PollId },
 
     #[returns(Vec<(connection_router::state::CrossChainId, bool)>)]
@@ -66,14 +169,61 @@ pub enum QueryMsg {
 
     #[returns(bool)]
     IsWorkerSetVerified { new_operators: Operators },
+
+    #[returns(PollMetricsResponse)]
+    GetPollMetrics,
+
+    // Returns the cumulative number of polls, polled items and votes spent on verifying content of the given source chain
+    #[returns(VerificationCostsResponse)]
+    GetVerificationCosts { source_chain: ChainName },
+
+    // Returns the verification status of all messages originating from the given source chain transaction
+    // that were submitted for verification
+    #[returns(Vec<MessageStatus>)]
+    GetMessagesByTxId { tx_id: nonempty::String },
+
+    // Returns up to `limit` messages of a verification request with their verification status, in the order they were submitted,
+    // starting after the message at index `start_after`. The messages can be spread across multiple polls
+    #[returns(Vec<MessageStatus>)]
+    GetVerificationRequest {
+        request_id: u64,
+        start_after: Option<u32>,
+        limit: u32,
+    },
+}
+
+#[cw_serde]
+pub struct MessageStatus {
+    pub message: Message,
+    pub status: VerificationStatus,
 }
 
 #[cw_serde]
 pub struct VerifyMessagesResponse {
     pub verification_statuses: Vec<(CrossChainId, bool)>,
+    /// Id to query the progress of the request with, None if no poll was started
+    pub request_id: Option<u64>,
 }
 
 #[cw_serde]
 pub struct EndPollResponse {
     pub poll_result: PollState,
 }
+
+#[cw_serde]
+pub struct PollMetricsResponse {
+    pub polls_opened: u64,
+    pub polls_succeeded: u64,
+    pub polls_failed: u64,
+    pub polls_expired: u64,
+    // average number of blocks between the start of a poll and reaching consensus on every item,
+    // None if no poll has reached consensus yet
+    pub average_blocks_to_quorum: Option<u64>,
+}
+
+#[cw_serde]
+pub struct VerificationCostsResponse {
+    pub polls_opened: u64,
+    pub items_polled: u64,
+    pub votes_cast: u64,
+}
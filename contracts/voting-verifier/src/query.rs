@@ -1,28 +1,40 @@
 use axelar_wasm_std::operators::Operators;
-use axelar_wasm_std::voting::{PollStatus, Vote};
-use connection_router::state::{CrossChainId, Message};
+use axelar_wasm_std::voting::{PollId, PollStatus, Vote};
+use connection_router::state::{ChainName, CrossChainId, Message};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Deps;
+use cosmwasm_std::{Addr, Deps, Order};
+use cw_storage_plus::Bound;
 
 use crate::error::ContractError;
-use crate::state::{self, Poll, PollContent, POLLS, POLL_MESSAGES, POLL_WORKER_SETS};
+use crate::msg::{
+    MessageStatus, PollMetricsResponse, PollResponse, PolledContent, VerificationCostsResponse,
+    WorkerVote,
+};
+use crate::state::{
+    self, Poll, PollContent, POLLS, POLL_CONTENT_HASHES, POLL_DISPUTES, POLL_DISPUTE_WINDOW_ENDS,
+    POLL_MESSAGES, POLL_METRICS, POLL_OVERRIDES, POLL_WORKER_SETS, PROVISIONAL_MESSAGES,
+    TX_MESSAGES, VERIFICATION_COSTS, VERIFICATION_REQUESTS, VOTE_JUSTIFICATIONS,
+};
 
 #[cw_serde]
 pub enum VerificationStatus {
     Verified,
     FailedToVerify,
-    InProgress,  // still in an open poll
-    NotVerified, // not in a poll
+    InProgress,            // still in an open poll
+    ProvisionallyVerified, // accepted in optimistic mode, but can still be challenged
+    NotVerified,           // not in a poll
+    Disputable, // verified by a poll, but its outcome is still within the dispute window or disputed
 }
 
 pub fn is_verified(
     deps: Deps,
     messages: &[Message],
+    block_height: u64,
 ) -> Result<Vec<(CrossChainId, bool)>, ContractError> {
     messages
         .iter()
         .map(|message| {
-            msg_verification_status(deps, message).map(|status| {
+            msg_verification_status(deps, message, block_height).map(|status| {
                 (
                     message.cc_id.to_owned(),
                     matches!(status, VerificationStatus::Verified),
@@ -32,33 +44,182 @@ pub fn is_verified(
         .collect::<Result<Vec<_>, _>>()
 }
 
-pub fn is_worker_set_verified(deps: Deps, operators: &Operators) -> Result<bool, ContractError> {
+pub fn is_worker_set_verified(
+    deps: Deps,
+    operators: &Operators,
+    block_height: u64,
+) -> Result<bool, ContractError> {
     Ok(matches!(
-        worker_set_verification_status(deps, operators)?,
+        worker_set_verification_status(deps, operators, block_height)?,
         VerificationStatus::Verified
     ))
 }
 
+pub fn poll(deps: Deps, poll_id: PollId) -> Result<PollResponse, ContractError> {
+    let poll = POLLS
+        .may_load(deps.storage, poll_id)?
+        .ok_or(ContractError::PollNotFound)?;
+
+    let content = match POLL_CONTENT_HASHES.may_load(deps.storage, poll_id)? {
+        Some(hashes) => Some(match &poll {
+            Poll::Messages(_) => PolledContent::Messages(
+                hashes
+                    .iter()
+                    .map(|hash| Ok(POLL_MESSAGES.load(deps.storage, hash)?.content))
+                    .collect::<Result<Vec<_>, ContractError>>()?,
+            ),
+            Poll::ConfirmWorkerSet(_) => {
+                let hash = hashes
+                    .first()
+                    .expect("violated invariant: worker set poll without content");
+                PolledContent::WorkerSet(POLL_WORKER_SETS.load(deps.storage, hash)?.content)
+            }
+        }),
+        None => None,
+    };
+
+    let poll = match poll {
+        Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll) => poll,
+    };
+
+    let votes = poll
+        .participation
+        .iter()
+        .map(|(worker, participation)| {
+            let worker = Addr::unchecked(worker); // Ok to convert unchecked here, since the snapshot only contains valid addresses
+            Ok(WorkerVote {
+                justification: VOTE_JUSTIFICATIONS.may_load(deps.storage, (poll_id, &worker))?,
+                worker,
+                weight: participation.weight.into(),
+                vote: participation.vote.clone(),
+                late: participation.late,
+            })
+        })
+        .collect::<Result<_, ContractError>>()?;
+
+    Ok(PollResponse {
+        poll_id,
+        content,
+        results: poll.state().results,
+        overridden_results: POLL_OVERRIDES.may_load(deps.storage, poll_id)?,
+        dispute_window_ends: POLL_DISPUTE_WINDOW_ENDS.may_load(deps.storage, poll_id)?,
+        dispute: POLL_DISPUTES.may_load(deps.storage, poll_id)?,
+        status: poll.status,
+        expires_at: poll.expires_at,
+        quorum: poll.quorum.into(),
+        tallies: poll.tallies,
+        votes,
+    })
+}
+
+pub fn poll_metrics(deps: Deps) -> Result<PollMetricsResponse, ContractError> {
+    let metrics = POLL_METRICS.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(PollMetricsResponse {
+        polls_opened: metrics.polls_opened,
+        polls_succeeded: metrics.polls_succeeded,
+        polls_failed: metrics.polls_failed,
+        polls_expired: metrics.polls_expired,
+        average_blocks_to_quorum: metrics.average_blocks_to_quorum(),
+    })
+}
+
+pub fn verification_costs(
+    deps: Deps,
+    source_chain: ChainName,
+) -> Result<VerificationCostsResponse, ContractError> {
+    let costs = VERIFICATION_COSTS
+        .may_load(deps.storage, source_chain)?
+        .unwrap_or_default();
+
+    Ok(VerificationCostsResponse {
+        polls_opened: costs.polls_opened,
+        items_polled: costs.items_polled,
+        votes_cast: costs.votes_cast,
+    })
+}
+
+pub fn messages_by_tx_id(
+    deps: Deps,
+    tx_id: &str,
+    block_height: u64,
+) -> Result<Vec<MessageStatus>, ContractError> {
+    TX_MESSAGES
+        .prefix(tx_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            let (_, message) = entry?;
+            let status = msg_verification_status(deps, &message, block_height)?;
+            Ok(MessageStatus { message, status })
+        })
+        .collect()
+}
+
+pub fn verification_request(
+    deps: Deps,
+    request_id: u64,
+    start_after: Option<u32>,
+    limit: u32,
+    block_height: u64,
+) -> Result<Vec<MessageStatus>, ContractError> {
+    let start = start_after.map(Bound::exclusive);
+
+    VERIFICATION_REQUESTS
+        .prefix(request_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|entry| {
+            let (_, message) = entry?;
+            let status = msg_verification_status(deps, &message, block_height)?;
+            Ok(MessageStatus { message, status })
+        })
+        .collect()
+}
+
 pub fn msg_verification_status(
     deps: Deps,
     message: &Message,
+    block_height: u64,
 ) -> Result<VerificationStatus, ContractError> {
     let loaded_poll_content = POLL_MESSAGES.may_load(deps.storage, &message.hash())?;
-    Ok(verification_status(deps, loaded_poll_content, message))
+    if loaded_poll_content.is_some() {
+        return Ok(verification_status(
+            deps,
+            loaded_poll_content,
+            message,
+            block_height,
+        ));
+    }
+
+    // challenged messages are removed from the provisional messages, so they are covered by the poll above
+    match PROVISIONAL_MESSAGES.may_load(deps.storage, &message.hash())? {
+        Some(provisional) if block_height > provisional.fraud_window_ends => {
+            Ok(VerificationStatus::Verified)
+        }
+        Some(_) => Ok(VerificationStatus::ProvisionallyVerified),
+        None => Ok(VerificationStatus::NotVerified),
+    }
 }
 
 pub fn worker_set_verification_status(
     deps: Deps,
     operators: &Operators,
+    block_height: u64,
 ) -> Result<VerificationStatus, ContractError> {
     let poll_content = POLL_WORKER_SETS.may_load(deps.storage, &operators.hash())?;
-    Ok(verification_status(deps, poll_content, operators))
+    Ok(verification_status(
+        deps,
+        poll_content,
+        operators,
+        block_height,
+    ))
 }
 
 fn verification_status<T: PartialEq + std::fmt::Debug>(
     deps: Deps,
     stored_poll_content: Option<PollContent<T>>,
     content: &T,
+    block_height: u64,
 ) -> VerificationStatus {
     match stored_poll_content {
         Some(stored) => {
@@ -71,15 +232,24 @@ fn verification_status<T: PartialEq + std::fmt::Debug>(
                 .load(deps.storage, stored.poll_id)
                 .expect("invalid invariant: message poll not found");
 
-            let verified = match &poll {
-                Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll) => {
+            let overridden_result = POLL_OVERRIDES
+                .may_load(deps.storage, stored.poll_id)
+                .expect("failed to load poll override")
+                .map(|results| results[stored.index_in_poll as usize].clone());
+
+            let is_overridden = overridden_result.is_some();
+            let verified = match (&poll, overridden_result) {
+                (_, Some(result)) => result == Vote::SucceededOnChain,
+                (Poll::Messages(poll) | Poll::ConfirmWorkerSet(poll), None) => {
                     poll.consensus(stored.index_in_poll)
                         .expect("invalid invariant: message not found in poll")
                         == Some(Vote::SucceededOnChain) // TODO: consider Vote::FailedOnChain?
                 }
             };
 
-            if verified {
+            if verified && !is_overridden && is_disputable(deps, stored.poll_id, block_height) {
+                VerificationStatus::Disputable
+            } else if verified {
                 VerificationStatus::Verified
             } else if is_finished(&poll) {
                 VerificationStatus::FailedToVerify
@@ -91,6 +261,20 @@ fn verification_status<T: PartialEq + std::fmt::Debug>(
     }
 }
 
+// an override by governance is final, so this only applies to outcomes that weren't overridden.
+// A lapsed dispute no longer holds back the result of the vote
+fn is_disputable(deps: Deps, poll_id: PollId, block_height: u64) -> bool {
+    let window_ends = POLL_DISPUTE_WINDOW_ENDS
+        .may_load(deps.storage, poll_id)
+        .expect("failed to load dispute window");
+    let disputed = POLL_DISPUTES
+        .may_load(deps.storage, poll_id)
+        .expect("failed to load dispute")
+        .map_or(false, |dispute| !dispute.is_lapsed(block_height));
+
+    disputed || window_ends.map_or(false, |window_ends| block_height <= window_ends)
+}
+
 fn is_finished(poll: &state::Poll) -> bool {
     match poll {
         state::Poll::Messages(poll) | state::Poll::ConfirmWorkerSet(poll) => {
@@ -136,12 +320,12 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            msg_verification_status(deps.as_ref(), &msg).unwrap(),
+            msg_verification_status(deps.as_ref(), &msg, 0).unwrap(),
             VerificationStatus::InProgress
         );
         assert_eq!(
             vec![(msg.cc_id.clone(), false)],
-            is_verified(deps.as_ref(), &[msg]).unwrap()
+            is_verified(deps.as_ref(), &[msg], 0).unwrap()
         );
     }
 
@@ -172,12 +356,12 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            msg_verification_status(deps.as_ref(), &msg).unwrap(),
+            msg_verification_status(deps.as_ref(), &msg, 0).unwrap(),
             VerificationStatus::Verified
         );
         assert_eq!(
             vec![(msg.cc_id.clone(), true)],
-            is_verified(deps.as_ref(), &[msg]).unwrap()
+            is_verified(deps.as_ref(), &[msg], 0).unwrap()
         );
     }
 
@@ -207,12 +391,12 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            msg_verification_status(deps.as_ref(), &msg).unwrap(),
+            msg_verification_status(deps.as_ref(), &msg, 0).unwrap(),
             VerificationStatus::FailedToVerify
         );
         assert_eq!(
             vec![(msg.cc_id.clone(), false)],
-            is_verified(deps.as_ref(), &[msg]).unwrap()
+            is_verified(deps.as_ref(), &[msg], 0).unwrap()
         );
     }
 
@@ -222,12 +406,48 @@ mod tests {
         let msg = message(1);
 
         assert_eq!(
-            msg_verification_status(deps.as_ref(), &msg).unwrap(),
+            msg_verification_status(deps.as_ref(), &msg, 0).unwrap(),
             VerificationStatus::NotVerified
         );
         assert_eq!(
             vec![(msg.cc_id.clone(), false)],
-            is_verified(deps.as_ref(), &[msg]).unwrap()
+            is_verified(deps.as_ref(), &[msg], 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn verification_status_provisionally_verified() {
+        let mut deps = mock_dependencies();
+        let fraud_window_ends = 10;
+
+        let msg = message(1);
+        PROVISIONAL_MESSAGES
+            .save(
+                deps.as_mut().storage,
+                &msg.hash(),
+                &state::ProvisionalMessage {
+                    content: msg.clone(),
+                    fraud_window_ends,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            msg_verification_status(deps.as_ref(), &msg, fraud_window_ends).unwrap(),
+            VerificationStatus::ProvisionallyVerified
+        );
+        assert_eq!(
+            vec![(msg.cc_id.clone(), false)],
+            is_verified(deps.as_ref(), &[msg.clone()], fraud_window_ends).unwrap()
+        );
+
+        assert_eq!(
+            msg_verification_status(deps.as_ref(), &msg, fraud_window_ends + 1).unwrap(),
+            VerificationStatus::Verified
+        );
+        assert_eq!(
+            vec![(msg.cc_id.clone(), true)],
+            is_verified(deps.as_ref(), &[msg], fraud_window_ends + 1).unwrap()
         );
     }
 
@@ -241,6 +461,7 @@ mod tests {
             destination_chain: format!("destination_chain{id}").parse().unwrap(),
             destination_address: format!("destination_address{id}").parse().unwrap(),
             payload_hash: [0; 32],
+            token: None,
         }
     }
 
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 
 use axelar_wasm_std::{
@@ -7,12 +7,14 @@ use axelar_wasm_std::{
     hash::Hash,
     nonempty,
     operators::Operators,
-    voting::{PollId, WeightedPoll},
+    voting::{PollId, Vote, WeightedPoll},
     MajorityThreshold,
 };
 use connection_router::state::{ChainName, Message};
 
 use crate::error::ContractError;
+use crate::events::parse_message_id;
+use crate::msg::VoteJustification;
 
 #[cw_serde]
 pub struct Config {
@@ -24,6 +26,20 @@ pub struct Config {
     pub confirmation_height: u64,
     pub source_chain: ChainName,
     pub rewards_contract: Addr,
+    #[serde(default)]
+    pub fraud_window: Option<u64>, // number of blocks during which a provisionally verified message can be challenged
+    #[serde(default)]
+    pub governance: Option<Addr>, // account that can override the outcome of finished polls
+    #[serde(default)]
+    pub max_messages_per_poll: Option<nonempty::Uint64>, // larger batches of messages are split into multiple polls
+    #[serde(default)]
+    pub finalization_reward: Option<Coin>, // paid out of the contract's balance to whoever ends a poll
+    #[serde(default)]
+    pub dispute_window: Option<u64>, // number of blocks after a poll expires during which its outcome can be disputed
+    #[serde(default)]
+    pub dispute_deposit: Option<Coin>, // deposit for disputes by anyone but governance, who can't dispute if not set
+    #[serde(default)]
+    pub dispute_resolution_period: Option<u64>, // number of blocks after the dispute window governance has to resolve a dispute, defaults to the dispute window
 }
 
 #[cw_serde]
@@ -45,6 +61,78 @@ impl Poll {
     }
 }
 
+/// Cumulative statistics about the polls of this contract, so governance can evaluate parameter changes with on-chain evidence
+#[cw_serde]
+#[derive(Default)]
+pub struct PollMetrics {
+    pub polls_opened: u64,
+    /// Polls that reached consensus on every item, with every item having succeeded on chain
+    pub polls_succeeded: u64,
+    /// Polls that reached consensus on every item, with at least one item having failed on chain or not being found
+    pub polls_failed: u64,
+    /// Polls that expired without reaching consensus on every item
+    pub polls_expired: u64,
+    /// Number of polls that reached consensus on every item before expiring
+    pub polls_reached_quorum: u64,
+    /// Sum of the number of blocks between the start of a poll and reaching consensus on every item
+    pub total_blocks_to_quorum: u64,
+}
+
+impl PollMetrics {
+    pub fn record_poll_opened(mut self) -> Self {
+        self.polls_opened += 1;
+        self
+    }
+
+    pub fn record_quorum_reached(mut self, blocks_to_quorum: u64) -> Self {
+        self.polls_reached_quorum += 1;
+        self.total_blocks_to_quorum += blocks_to_quorum;
+        self
+    }
+
+    pub fn record_poll_ended(mut self, results: &[Option<Vote>]) -> Self {
+        if results.iter().any(Option::is_none) {
+            self.polls_expired += 1;
+        } else if results
+            .iter()
+            .all(|result| result == &Some(Vote::SucceededOnChain))
+        {
+            self.polls_succeeded += 1;
+        } else {
+            self.polls_failed += 1;
+        }
+        self
+    }
+
+    pub fn average_blocks_to_quorum(&self) -> Option<u64> {
+        self.total_blocks_to_quorum
+            .checked_div(self.polls_reached_quorum)
+    }
+}
+
+/// Cumulative verification work done for a source chain, so governance can set verification fees proportional to it
+#[cw_serde]
+#[derive(Default)]
+pub struct VerificationCosts {
+    pub polls_opened: u64,
+    /// Number of messages and worker sets that were polled
+    pub items_polled: u64,
+    pub votes_cast: u64,
+}
+
+impl VerificationCosts {
+    pub fn record_poll_opened(mut self, poll_size: u64) -> Self {
+        self.polls_opened += 1;
+        self.items_polled += poll_size;
+        self
+    }
+
+    pub fn record_vote(mut self) -> Self {
+        self.votes_cast += 1;
+        self
+    }
+}
+
 #[cw_serde]
 pub struct PollContent<T> {
     pub content: T, // content is stored for migration purposes in case the hash changes
@@ -72,6 +160,33 @@ impl PollContent<Operators> {
     }
 }
 
+/// A dispute of the outcome of a poll, which holds back its verified results until governance resolves it with an override.
+/// If governance doesn't act before the resolution deadline, the dispute lapses and the result of the vote stands
+#[cw_serde]
+pub struct Dispute {
+    pub challenger: Addr,
+    /// refunded if governance changes the outcome of the poll, kept by the contract otherwise
+    pub deposit: Option<Coin>,
+    /// last block height at which governance can resolve the dispute, None for disputes that never lapse
+    #[serde(default)]
+    pub resolution_deadline: Option<u64>,
+}
+
+impl Dispute {
+    pub fn is_lapsed(&self, block_height: u64) -> bool {
+        self.resolution_deadline
+            .map_or(false, |deadline| block_height > deadline)
+    }
+}
+
+/// A message accepted without a poll in optimistic mode. It counts as verified once the fraud window has passed without a challenge
+#[cw_serde]
+pub struct ProvisionalMessage {
+    pub content: Message,
+    /// last block height at which the message can be challenged
+    pub fraud_window_ends: u64,
+}
+
 pub const POLL_ID: counter::Counter<PollId> = counter::Counter::new("poll_id");
 
 pub const POLLS: Map<PollId, Poll> = Map::new("polls");
@@ -81,3 +196,72 @@ pub const POLL_MESSAGES: Map<&Hash, PollContent<Message>> = Map::new("poll_messa
 pub const CONFIG: Item<Config> = Item::new("config");
 
 pub const POLL_WORKER_SETS: Map<&Hash, PollContent<Operators>> = Map::new("poll_worker_sets");
+
+// hashes of the messages or the worker set of a poll, in the order they were polled. Polls started before the content was indexed have no entry
+pub const POLL_CONTENT_HASHES: Map<PollId, Vec<Hash>> = Map::new("poll_content_hashes");
+
+// messages accepted in optimistic mode that have not been challenged
+pub const PROVISIONAL_MESSAGES: Map<&Hash, ProvisionalMessage> = Map::new("provisional_messages");
+
+// messages that were polled or accepted in optimistic mode, by the id of the source transaction they originate from
+pub const TX_MESSAGES: Map<(&str, &Hash), Message> = Map::new("tx_messages");
+
+// outcomes of finished polls that were overridden by governance, they take precedence over the results of the vote
+pub const POLL_OVERRIDES: Map<PollId, Vec<Vote>> = Map::new("poll_overrides");
+
+pub const POLL_METRICS: Item<PollMetrics> = Item::new("poll_metrics");
+
+// source chain data workers attached to their votes, by poll and worker
+pub const VOTE_JUSTIFICATIONS: Map<(PollId, &Addr), VoteJustification> =
+    Map::new("vote_justifications");
+
+// last block height at which the outcome of a poll can be disputed. Polls started without a dispute window have no entry
+pub const POLL_DISPUTE_WINDOW_ENDS: Map<PollId, u64> = Map::new("poll_dispute_window_ends");
+
+pub const POLL_DISPUTES: Map<PollId, Dispute> = Map::new("poll_disputes");
+
+// total deposits of open disputes by denom. They are held in escrow, so they can't be paid out as finalization rewards
+pub const ESCROWED_DEPOSITS: Map<&str, Uint128> = Map::new("escrowed_deposits");
+
+// block height at which a poll was started, used to compute the time to quorum
+pub const POLL_START_HEIGHTS: Map<PollId, u64> = Map::new("poll_start_heights");
+
+pub const VERIFICATION_COSTS: Map<ChainName, VerificationCosts> = Map::new("verification_costs");
+
+pub const VERIFICATION_REQUEST_ID: counter::Counter<u64> =
+    counter::Counter::new("verification_request_id");
+
+// messages of a verification request that started polls, by request id and index in the request
+pub const VERIFICATION_REQUESTS: Map<(u64, u32), Message> = Map::new("verification_requests");
+
+pub fn save_tx_message(store: &mut dyn Storage, message: &Message) -> Result<(), ContractError> {
+    let (tx_id, _) = parse_message_id(&message.cc_id.id)?;
+    TX_MESSAGES.save(store, (&tx_id, &message.hash()), message)?;
+    Ok(())
+}
+
+pub fn update_verification_costs(
+    store: &mut dyn Storage,
+    source_chain: &ChainName,
+    f: impl FnOnce(VerificationCosts) -> VerificationCosts,
+) -> Result<VerificationCosts, ContractError> {
+    Ok(
+        VERIFICATION_COSTS.update(store, source_chain.clone(), |costs| {
+            StdResult::Ok(f(costs.unwrap_or_default()))
+        })?,
+    )
+}
+
+pub fn escrow_deposit(store: &mut dyn Storage, deposit: &Coin) -> Result<(), ContractError> {
+    ESCROWED_DEPOSITS.update(store, &deposit.denom, |escrowed| {
+        StdResult::Ok(escrowed.unwrap_or_default() + deposit.amount)
+    })?;
+    Ok(())
+}
+
+pub fn release_deposit(store: &mut dyn Storage, deposit: &Coin) -> Result<(), ContractError> {
+    ESCROWED_DEPOSITS.update(store, &deposit.denom, |escrowed| {
+        StdResult::Ok(escrowed.unwrap_or_default().saturating_sub(deposit.amount))
+    })?;
+    Ok(())
+}
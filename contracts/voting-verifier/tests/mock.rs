@@ -67,19 +67,17 @@ pub fn make_mock_service_registry(app: &mut App) -> Addr {
     );
     let code_id = app.store_code(Box::new(code));
 
-    let contract_address = app
-        .instantiate_contract(
-            code_id,
-            Addr::unchecked("sender"),
-            &InstantiateMsg {
-                governance_account: Addr::unchecked("governance").into(),
-            },
-            &[],
-            "Contract",
-            None,
-        )
-        .unwrap();
-    contract_address
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked("sender"),
+        &InstantiateMsg {
+            governance_account: Addr::unchecked("governance").into(),
+        },
+        &[],
+        "Contract",
+        None,
+    )
+    .unwrap()
 }
 
 #[cw_serde]
@@ -99,7 +97,7 @@ pub fn mock_rewards_query(
     _env: Env,
     _msg: rewards::msg::QueryMsg,
 ) -> StdResult<Binary> {
-    Ok(to_binary("")?)
+    to_binary("")
 }
 
 pub fn make_mock_rewards(app: &mut App) -> Addr {
@@ -110,15 +108,13 @@ pub fn make_mock_rewards(app: &mut App) -> Addr {
     );
     let code_id = app.store_code(Box::new(code));
 
-    let contract_address = app
-        .instantiate_contract(
-            code_id,
-            Addr::unchecked("sender"),
-            &MockRewardsInstantiateMsg,
-            &[],
-            "Contract",
-            None,
-        )
-        .unwrap();
-    contract_address
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked("sender"),
+        &MockRewardsInstantiateMsg,
+        &[],
+        "Contract",
+        None,
+    )
+    .unwrap()
 }
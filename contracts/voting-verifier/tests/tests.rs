@@ -1,13 +1,14 @@
-use axelar_wasm_std::voting::Vote;
-use cosmwasm_std::{from_binary, Addr, Uint64};
-use cw_multi_test::{App, ContractWrapper, Executor};
+use axelar_wasm_std::voting::{PollStatus, Vote};
+use cosmwasm_std::{coin, coins, from_binary, Addr, Attribute, HexBinary, Uint256, Uint64};
+use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 
 use axelar_wasm_std::operators::Operators;
 use axelar_wasm_std::{nonempty, Threshold};
-use connection_router::state::{ChainName, CrossChainId, Message, ID_SEPARATOR};
+use connection_router::state::{ChainName, CrossChainId, Message, TokenTransfer, ID_SEPARATOR};
 use mock::make_mock_rewards;
 use service_registry::state::Worker;
-use voting_verifier::events::TxEventConfirmation;
+use voting_verifier::events::{IncorrectVote, TxEventConfirmation};
+use voting_verifier::query::VerificationStatus;
 use voting_verifier::{contract, error::ContractError, msg};
 
 use crate::mock::make_mock_service_registry;
@@ -15,12 +16,30 @@ use crate::mock::make_mock_service_registry;
 pub mod mock;
 
 const SENDER: &str = "sender";
+const GOVERNANCE: &str = "governance";
 const POLL_BLOCK_EXPIRY: u64 = 100;
 fn source_chain() -> ChainName {
     "source_chain".parse().unwrap()
 }
 
 fn initialize_contract(app: &mut App, service_registry_address: nonempty::String) -> Addr {
+    initialize_contract_with_fraud_window(app, service_registry_address, None)
+}
+
+fn initialize_contract_with_fraud_window(
+    app: &mut App,
+    service_registry_address: nonempty::String,
+    fraud_window: Option<u64>,
+) -> Addr {
+    instantiate(app, service_registry_address, fraud_window, None)
+}
+
+fn instantiate(
+    app: &mut App,
+    service_registry_address: nonempty::String,
+    fraud_window: Option<u64>,
+    max_messages_per_poll: Option<nonempty::Uint64>,
+) -> Addr {
     let rewards_address = make_mock_rewards(app).into();
 
     let msg = msg::InstantiateMsg {
@@ -35,23 +54,24 @@ fn initialize_contract(app: &mut App, service_registry_address: nonempty::String
         source_gateway_address: "gateway_address".parse().unwrap(),
         source_chain: source_chain(),
         rewards_address,
+        fraud_window,
+        governance_address: Some(GOVERNANCE.to_string()),
+        max_messages_per_poll,
     };
 
-    let code = ContractWrapper::new(contract::execute, contract::instantiate, contract::query);
+    let code = ContractWrapper::new(contract::execute, contract::instantiate, contract::query)
+        .with_reply(contract::reply);
     let code_id = app.store_code(Box::new(code));
 
-    let address = app
-        .instantiate_contract(
-            code_id,
-            Addr::unchecked(SENDER),
-            &msg,
-            &[],
-            "voting-verifier",
-            None,
-        )
-        .unwrap();
-
-    address
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(SENDER),
+        &msg,
+        &[],
+        "voting-verifier",
+        None,
+    )
+    .unwrap()
 }
 
 fn message_id(id: &str, index: u64) -> nonempty::String {
@@ -71,6 +91,7 @@ fn messages(len: u64) -> Vec<Message> {
             destination_chain: format!("destination_chain{i}").parse().unwrap(),
             destination_address: format!("destination_address{i}").parse().unwrap(),
             payload_hash: [0; 32],
+            token: None,
         })
         .collect()
 }
@@ -95,6 +116,7 @@ fn should_failed_if_messages_are_not_from_same_source() {
                 destination_chain: "destination_chain1".parse().unwrap(),
                 destination_address: "destination_address1".parse().unwrap(),
                 payload_hash: [0; 32],
+                token: None,
             },
             Message {
                 cc_id: CrossChainId {
@@ -105,6 +127,7 @@ fn should_failed_if_messages_are_not_from_same_source() {
                 destination_chain: "destination_chain2".parse().unwrap(),
                 destination_address: "destination_address2".parse().unwrap(),
                 payload_hash: [0; 32],
+                token: None,
             },
         ],
     };
@@ -121,6 +144,42 @@ fn should_failed_if_messages_are_not_from_same_source() {
     );
 }
 
+#[test]
+fn should_fail_if_messages_carry_tokens() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let mut messages = messages(2);
+    messages[1].token = Some(TokenTransfer {
+        symbol: "AXL".try_into().unwrap(),
+        amount: Uint256::from(100u128).try_into().unwrap(),
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address,
+            &msg::ExecuteMsg::VerifyMessages {
+                messages: messages.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<axelar_wasm_std::ContractError>()
+            .unwrap()
+            .to_string(),
+        axelar_wasm_std::ContractError::from(ContractError::TokenTransferNotSupported(
+            messages[1].cc_id.clone()
+        ))
+        .to_string()
+    );
+}
+
 #[test]
 fn should_verify_messages_if_not_verified() {
     let mut app = App::default();
@@ -160,6 +219,81 @@ fn should_verify_messages_if_not_verified() {
     );
 }
 
+#[test]
+fn should_emit_message_status_events() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    // message ids of the events of the given type, in the order they were emitted
+    let message_ids = |res: &cw_multi_test::AppResponse, ty: &str| -> Vec<String> {
+        res.events
+            .iter()
+            .filter(|event| event.ty == format!("wasm-{}", ty))
+            .map(|event| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.key == "message_id")
+                    .unwrap()
+                    .value
+                    .clone()
+            })
+            .collect()
+    };
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &msg::ExecuteMsg::VerifyMessages {
+                messages: messages(2),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(message_ids(&res, "poll_created"), vec!["id:0", "id:1"]);
+
+    let vote = |app: &mut App, voter: &str| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            contract_address.clone(),
+            &msg::ExecuteMsg::Vote {
+                poll_id: Uint64::one().into(),
+                votes: vec![Vote::SucceededOnChain, Vote::NotFound],
+                justification: None,
+            },
+            &[],
+        )
+        .unwrap()
+    };
+
+    let res = vote(&mut app, "addr1");
+    assert_eq!(message_ids(&res, "vote_cast"), vec!["id:0", "id:1"]);
+    assert!(message_ids(&res, "quorum_reached").is_empty());
+
+    let res = vote(&mut app, "addr2");
+    assert_eq!(message_ids(&res, "quorum_reached"), vec!["id:0", "id:1"]);
+
+    app.update_block(|block| block.height += POLL_BLOCK_EXPIRY);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address,
+            &msg::ExecuteMsg::EndPoll {
+                poll_id: Uint64::one().into(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(message_ids(&res, "message_verified"), vec!["id:0"]);
+    assert_eq!(message_ids(&res, "message_rejected"), vec!["id:1"]);
+    assert!(message_ids(&res, "message_expired").is_empty());
+}
+
 #[test]
 fn should_not_verify_messages_if_in_progress() {
     let mut app = App::default();
@@ -213,6 +347,54 @@ fn should_not_verify_messages_if_in_progress() {
     assert_eq!(messages.len() as u64, new_messages);
 }
 
+#[test]
+fn should_report_poll_if_all_messages_in_progress() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages(2),
+    };
+    let res = app
+        .execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+    let expires_at = res
+        .events
+        .into_iter()
+        .find(|event| event.ty == "wasm-messages_poll_started")
+        .unwrap()
+        .attributes
+        .into_iter()
+        .find_map(|attribute| {
+            if attribute.key == "expires_at" {
+                Some(attribute.value.parse::<u64>().unwrap())
+            } else {
+                None
+            }
+        })
+        .unwrap();
+
+    let res = app
+        .execute_contract(Addr::unchecked(SENDER), contract_address, &msg, &[])
+        .unwrap();
+    let event = res
+        .events
+        .into_iter()
+        .find(|event| event.ty == "wasm-verification_in_progress")
+        .unwrap();
+    assert_eq!(
+        event.attributes[1..],
+        vec![
+            Attribute::new("poll_id", "\"1\""),
+            Attribute::new("expires_at", expires_at.to_string())
+        ]
+    );
+}
+
 #[test]
 fn should_retry_if_message_not_verified() {
     let mut app = App::default();
@@ -263,7 +445,821 @@ fn should_retry_if_message_not_verified() {
     )
     .unwrap();
 
-    assert_eq!(messages.len() as u64, 1);
+    assert_eq!(messages.len() as u64, 1);
+}
+
+#[test]
+fn should_emit_poll_expired_for_items_without_consensus() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let messages = messages(2);
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    // the workers agree on the first message only
+    for (voter, votes) in [
+        (
+            "addr1",
+            vec![Vote::SucceededOnChain, Vote::SucceededOnChain],
+        ),
+        ("addr2", vec![Vote::SucceededOnChain, Vote::NotFound]),
+    ] {
+        let msg = msg::ExecuteMsg::Vote {
+            poll_id: Uint64::one().into(),
+            votes,
+            justification: None,
+        };
+        app.execute_contract(Addr::unchecked(voter), contract_address.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let end_poll = msg::ExecuteMsg::EndPoll {
+        poll_id: Uint64::one().into(),
+    };
+
+    // anyone can end the poll, but only once it expired
+    assert!(app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            contract_address.clone(),
+            &end_poll,
+            &[]
+        )
+        .is_err());
+
+    app.update_block(|block| block.height += POLL_BLOCK_EXPIRY);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            contract_address.clone(),
+            &end_poll,
+            &[],
+        )
+        .unwrap();
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-poll_expired")
+        .unwrap();
+    let items_without_consensus = event
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == "items_without_consensus")
+        .unwrap();
+    assert_eq!(items_without_consensus.value, "[1]");
+
+    let statuses: Vec<(CrossChainId, bool)> = app
+        .wrap()
+        .query_wasm_smart(
+            contract_address.clone(),
+            &msg::QueryMsg::IsVerified {
+                messages: messages.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        statuses,
+        vec![
+            (messages[0].cc_id.clone(), true),
+            (messages[1].cc_id.clone(), false)
+        ]
+    );
+
+    // the message without consensus is polled again
+    let res = app
+        .execute_contract(Addr::unchecked(SENDER), contract_address, &msg, &[])
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-messages_poll_started"));
+}
+
+#[test]
+fn should_penalize_workers_who_voted_against_overridden_outcome() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let messages = messages(1);
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    for voter in ["addr1", "addr2"] {
+        let msg = msg::ExecuteMsg::Vote {
+            poll_id: Uint64::one().into(),
+            votes: vec![Vote::NotFound],
+            justification: None,
+        };
+        app.execute_contract(Addr::unchecked(voter), contract_address.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let override_msg = msg::ExecuteMsg::OverridePollOutcome {
+        poll_id: Uint64::one().into(),
+        results: vec![Vote::SucceededOnChain],
+    };
+
+    // only finished polls can be overridden
+    assert!(app
+        .execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            contract_address.clone(),
+            &override_msg,
+            &[]
+        )
+        .is_err());
+
+    app.update_block(|block| block.height += POLL_BLOCK_EXPIRY);
+    app.execute_contract(
+        Addr::unchecked(SENDER),
+        contract_address.clone(),
+        &msg::ExecuteMsg::EndPoll {
+            poll_id: Uint64::one().into(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // only governance can override the outcome
+    assert!(app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &override_msg,
+            &[]
+        )
+        .is_err());
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            contract_address.clone(),
+            &override_msg,
+            &[],
+        )
+        .unwrap();
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-poll_outcome_overridden")
+        .unwrap();
+    let incorrect_votes: Vec<IncorrectVote> = serde_json::from_str(
+        &event
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == "incorrect_votes")
+            .unwrap()
+            .value,
+    )
+    .unwrap();
+    assert_eq!(
+        incorrect_votes,
+        vec![
+            IncorrectVote {
+                worker: Addr::unchecked("addr1"),
+                votes: vec![Vote::NotFound],
+            },
+            IncorrectVote {
+                worker: Addr::unchecked("addr2"),
+                votes: vec![Vote::NotFound],
+            },
+        ]
+    );
+
+    // both workers are penalized in the rewards contract
+    assert_eq!(
+        res.events
+            .iter()
+            .filter(|event| event.ty == "execute")
+            .count(),
+        3
+    );
+
+    let statuses: Vec<(CrossChainId, bool)> = app
+        .wrap()
+        .query_wasm_smart(
+            contract_address.clone(),
+            &msg::QueryMsg::IsVerified {
+                messages: messages.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(statuses, vec![(messages[0].cc_id.clone(), true)]);
+
+    assert!(app
+        .execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            contract_address,
+            &override_msg,
+            &[]
+        )
+        .is_err());
+}
+
+#[test]
+fn should_start_polls_with_updated_confirmation_height() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let update_msg = msg::ExecuteMsg::UpdateConfirmationHeight {
+        confirmation_height: 200,
+    };
+
+    // only governance can update the confirmation height
+    let err = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &update_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<axelar_wasm_std::ContractError>()
+            .unwrap()
+            .to_string(),
+        axelar_wasm_std::ContractError::from(ContractError::Unauthorized).to_string()
+    );
+
+    app.execute_contract(
+        Addr::unchecked(GOVERNANCE),
+        contract_address.clone(),
+        &update_msg,
+        &[],
+    )
+    .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address,
+            &msg::ExecuteMsg::VerifyMessages {
+                messages: messages(1),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-messages_poll_started")
+        .unwrap();
+    assert_eq!(
+        event
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == "confirmation_height")
+            .unwrap()
+            .value,
+        "200"
+    );
+}
+
+#[test]
+fn should_split_large_requests_into_multiple_polls() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address = instantiate(
+        &mut app,
+        service_registry_address.as_ref().parse().unwrap(),
+        None,
+        Some(2u64.try_into().unwrap()),
+    );
+
+    let messages = messages(5);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &msg::ExecuteMsg::VerifyMessages {
+                messages: messages.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let reply: msg::VerifyMessagesResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(reply.request_id, Some(1));
+
+    let poll_sizes = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-messages_poll_started")
+        .map(|event| {
+            let messages: Vec<TxEventConfirmation> = serde_json::from_str(
+                &event
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.key == "messages")
+                    .unwrap()
+                    .value,
+            )
+            .unwrap();
+            messages.len()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(poll_sizes, vec![2, 2, 1]);
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-verification_requested")
+        .unwrap();
+    assert_eq!(
+        event
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == "poll_ids")
+            .unwrap()
+            .value,
+        r#"["1","2","3"]"#
+    );
+
+    // all messages of the request can be paged through, regardless of the poll they are in
+    let query = |start_after: Option<u32>| -> Vec<msg::MessageStatus> {
+        app.wrap()
+            .query_wasm_smart(
+                contract_address.clone(),
+                &msg::QueryMsg::GetVerificationRequest {
+                    request_id: 1,
+                    start_after,
+                    limit: 3,
+                },
+            )
+            .unwrap()
+    };
+
+    let first_page = query(None);
+    let second_page = query(Some(2));
+    assert_eq!(first_page.len(), 3);
+    assert_eq!(second_page.len(), 2);
+    assert_eq!(
+        first_page
+            .into_iter()
+            .chain(second_page)
+            .map(|status| (status.message, status.status))
+            .collect::<Vec<_>>(),
+        messages
+            .into_iter()
+            .map(|message| (message, VerificationStatus::InProgress))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn should_reward_first_caller_of_end_poll() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(GOVERNANCE), coins(15, "uaxl"))
+            .unwrap()
+    });
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let update_msg = msg::ExecuteMsg::UpdateFinalizationReward {
+        reward: Some(coin(10, "uaxl")),
+    };
+
+    // only governance can set the reward
+    assert!(app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &update_msg,
+            &[]
+        )
+        .is_err());
+
+    app.execute_contract(
+        Addr::unchecked(GOVERNANCE),
+        contract_address.clone(),
+        &update_msg,
+        &[],
+    )
+    .unwrap();
+    app.send_tokens(
+        Addr::unchecked(GOVERNANCE),
+        contract_address.clone(),
+        &coins(15, "uaxl"),
+    )
+    .unwrap();
+
+    for message in messages(2) {
+        app.execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &msg::ExecuteMsg::VerifyMessages {
+                messages: vec![message],
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    app.update_block(|block| block.height += POLL_BLOCK_EXPIRY);
+
+    let end_poll = |app: &mut App, caller: &str, poll_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(caller),
+            contract_address.clone(),
+            &msg::ExecuteMsg::EndPoll {
+                poll_id: Uint64::from(poll_id).into(),
+            },
+            &[],
+        )
+    };
+    let balance = |app: &App, address: &str| {
+        app.wrap()
+            .query_balance(address, "uaxl")
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    let res = end_poll(&mut app, "finalizer", 1).unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-finalization_reward_paid"));
+    assert_eq!(balance(&app, "finalizer"), 10);
+
+    // the poll was already ended, so nobody else can claim the reward
+    assert!(end_poll(&mut app, "other_finalizer", 1).is_err());
+
+    // the remaining balance doesn't cover the reward, the poll is still ended
+    let res = end_poll(&mut app, "other_finalizer", 2).unwrap();
+    assert!(!res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-finalization_reward_paid"));
+    assert_eq!(balance(&app, "other_finalizer"), 0);
+    assert_eq!(balance(&app, contract_address.as_str()), 5);
+}
+
+#[test]
+fn should_hold_back_verified_messages_during_dispute_window() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("challenger"), coins(10, "uaxl"))
+            .unwrap()
+    });
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let dispute_window = 10;
+    app.execute_contract(
+        Addr::unchecked(GOVERNANCE),
+        contract_address.clone(),
+        &msg::ExecuteMsg::UpdateDisputeParams {
+            dispute_window: Some(dispute_window),
+            dispute_deposit: Some(coin(10, "uaxl")),
+            dispute_resolution_period: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let messages = messages(2);
+    for (i, message) in messages.iter().enumerate() {
+        app.execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &msg::ExecuteMsg::VerifyMessages {
+                messages: vec![message.clone()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        for voter in ["addr1", "addr2"] {
+            app.execute_contract(
+                Addr::unchecked(voter),
+                contract_address.clone(),
+                &msg::ExecuteMsg::Vote {
+                    poll_id: Uint64::from(i as u64 + 1).into(),
+                    votes: vec![Vote::SucceededOnChain],
+                    justification: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+    }
+
+    app.update_block(|block| block.height += POLL_BLOCK_EXPIRY);
+    for poll_id in [1u64, 2] {
+        app.execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &msg::ExecuteMsg::EndPoll {
+                poll_id: Uint64::from(poll_id).into(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let is_verified = |app: &App| -> Vec<bool> {
+        app.wrap()
+            .query_wasm_smart::<Vec<(CrossChainId, bool)>>(
+                contract_address.clone(),
+                &msg::QueryMsg::IsVerified {
+                    messages: messages.clone(),
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|(_, verified)| verified)
+            .collect()
+    };
+
+    // the results are held back until the dispute window has passed
+    assert_eq!(is_verified(&app), vec![false, false]);
+
+    let dispute_msg = msg::ExecuteMsg::DisputePollOutcome {
+        poll_id: Uint64::one().into(),
+    };
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("challenger"),
+            contract_address.clone(),
+            &dispute_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<axelar_wasm_std::ContractError>()
+            .unwrap()
+            .to_string(),
+        axelar_wasm_std::ContractError::from(ContractError::InvalidDisputeDeposit(coin(
+            10, "uaxl"
+        )))
+        .to_string()
+    );
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked("challenger"),
+            contract_address.clone(),
+            &dispute_msg,
+            &coins(10, "uaxl"),
+        )
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-poll_disputed"));
+
+    // a poll can only be disputed once
+    assert!(app
+        .execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            contract_address.clone(),
+            &dispute_msg,
+            &[],
+        )
+        .is_err());
+
+    // the undisputed poll becomes final after the window, the disputed one waits for governance
+    app.update_block(|block| block.height += dispute_window + 1);
+    assert_eq!(is_verified(&app), vec![false, true]);
+
+    assert!(app
+        .execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            contract_address.clone(),
+            &msg::ExecuteMsg::DisputePollOutcome {
+                poll_id: Uint64::from(2u64).into(),
+            },
+            &[],
+        )
+        .is_err());
+
+    // the challenger was right, so the deposit is refunded
+    app.execute_contract(
+        Addr::unchecked(GOVERNANCE),
+        contract_address.clone(),
+        &msg::ExecuteMsg::OverridePollOutcome {
+            poll_id: Uint64::one().into(),
+            results: vec![Vote::NotFound],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance("challenger", "uaxl")
+            .unwrap()
+            .amount
+            .u128(),
+        10
+    );
+    assert_eq!(is_verified(&app), vec![false, true]);
+}
+
+#[test]
+fn should_let_unresolved_disputes_lapse_and_keep_deposits_in_escrow() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("challenger"), coins(10, "uaxl"))
+            .unwrap()
+    });
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let dispute_window = 10;
+    let dispute_resolution_period = 5;
+    for msg in [
+        msg::ExecuteMsg::UpdateDisputeParams {
+            dispute_window: Some(dispute_window),
+            dispute_deposit: Some(coin(10, "uaxl")),
+            dispute_resolution_period: Some(dispute_resolution_period),
+        },
+        msg::ExecuteMsg::UpdateFinalizationReward {
+            reward: Some(coin(10, "uaxl")),
+        },
+    ] {
+        app.execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            contract_address.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    let messages = messages(3);
+    for message in messages.iter() {
+        app.execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &msg::ExecuteMsg::VerifyMessages {
+                messages: vec![message.clone()],
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    for voter in ["addr1", "addr2"] {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            contract_address.clone(),
+            &msg::ExecuteMsg::Vote {
+                poll_id: Uint64::one().into(),
+                votes: vec![Vote::SucceededOnChain],
+                justification: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    app.update_block(|block| block.height += POLL_BLOCK_EXPIRY);
+
+    let end_poll = |app: &mut App, poll_id: u64| {
+        app.execute_contract(
+            Addr::unchecked("finalizer"),
+            contract_address.clone(),
+            &msg::ExecuteMsg::EndPoll {
+                poll_id: Uint64::from(poll_id).into(),
+            },
+            &[],
+        )
+        .unwrap()
+    };
+    let reward_paid = |res: &AppResponse| {
+        res.events
+            .iter()
+            .any(|event| event.ty == "wasm-finalization_reward_paid")
+    };
+    let is_verified = |app: &App| -> bool {
+        app.wrap()
+            .query_wasm_smart::<Vec<(CrossChainId, bool)>>(
+                contract_address.clone(),
+                &msg::QueryMsg::IsVerified {
+                    messages: vec![messages[0].clone()],
+                },
+            )
+            .unwrap()[0]
+            .1
+    };
+
+    end_poll(&mut app, 1);
+    app.execute_contract(
+        Addr::unchecked("challenger"),
+        contract_address.clone(),
+        &msg::ExecuteMsg::DisputePollOutcome {
+            poll_id: Uint64::one().into(),
+        },
+        &coins(10, "uaxl"),
+    )
+    .unwrap();
+
+    // the deposit is held in escrow, so it isn't paid out as a finalization reward
+    assert!(!reward_paid(&end_poll(&mut app, 2)));
+
+    let settle_msg = msg::ExecuteMsg::SettleLapsedDispute {
+        poll_id: Uint64::one().into(),
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &settle_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<axelar_wasm_std::ContractError>()
+            .unwrap()
+            .to_string(),
+        axelar_wasm_std::ContractError::from(ContractError::DisputeNotLapsed(Uint64::one().into()))
+            .to_string()
+    );
+
+    // governance has until the resolution deadline to resolve the dispute
+    app.update_block(|block| block.height += dispute_window + 1);
+    assert!(!is_verified(&app));
+
+    // afterwards the dispute lapses and the result of the vote stands
+    app.update_block(|block| block.height += dispute_resolution_period);
+    assert!(is_verified(&app));
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &settle_msg,
+            &[],
+        )
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-dispute_lapsed"));
+    assert!(is_verified(&app));
+
+    // the lapsed poll can't be disputed again
+    assert!(app
+        .execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            contract_address.clone(),
+            &msg::ExecuteMsg::DisputePollOutcome {
+                poll_id: Uint64::one().into(),
+            },
+            &[],
+        )
+        .is_err());
+
+    // the contract kept the deposit, which now funds finalization rewards
+    assert!(reward_paid(&end_poll(&mut app, 3)));
+    assert_eq!(
+        app.wrap()
+            .query_balance("challenger", "uaxl")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
 }
 
 #[test]
@@ -324,6 +1320,7 @@ fn should_query_message_statuses() {
                 }
             })
             .collect::<Vec<Vote>>(),
+        justification: None,
     };
 
     app.execute_contract(
@@ -364,6 +1361,153 @@ fn should_query_message_statuses() {
     );
 }
 
+#[test]
+fn should_query_poll_with_votes() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let messages = messages(2);
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    let votes = vec![Vote::SucceededOnChain, Vote::NotFound];
+    let justification = msg::VoteJustification {
+        tx_hash: Some("tx_hash".parse().unwrap()),
+        receipt_root: Some(HexBinary::from_hex("abcd").unwrap()),
+    };
+    let msg = msg::ExecuteMsg::Vote {
+        poll_id: Uint64::one().into(),
+        votes: votes.clone(),
+        justification: Some(justification.clone()),
+    };
+    app.execute_contract(
+        Addr::unchecked("addr1"),
+        contract_address.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap();
+
+    let poll: msg::PollResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract_address.clone(),
+            &msg::QueryMsg::GetPoll {
+                poll_id: Uint64::one().into(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(poll.content, Some(msg::PolledContent::Messages(messages)));
+    assert_eq!(poll.status, PollStatus::InProgress);
+    assert_eq!(poll.expires_at, app.block_info().height + POLL_BLOCK_EXPIRY);
+    assert_eq!(poll.quorum, Uint256::from(134u128));
+    assert_eq!(poll.results, vec![None, None]);
+    assert_eq!(
+        poll.votes,
+        vec![
+            msg::WorkerVote {
+                worker: Addr::unchecked("addr1"),
+                weight: Uint256::from(100u128),
+                vote: Some(votes),
+                late: false,
+                justification: Some(justification),
+            },
+            msg::WorkerVote {
+                worker: Addr::unchecked("addr2"),
+                weight: Uint256::from(100u128),
+                vote: None,
+                late: false,
+                justification: None,
+            },
+        ]
+    );
+
+    let res: Result<msg::PollResponse, _> = app.wrap().query_wasm_smart(
+        contract_address,
+        &msg::QueryMsg::GetPoll {
+            poll_id: Uint64::from(2u64).into(),
+        },
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn should_query_messages_by_tx_id() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    // all generated messages originate from the transaction "id"
+    let mut messages = messages(2);
+    let mut other_tx_message = messages[0].clone();
+    other_tx_message.cc_id.id = message_id("other_tx", 0);
+    messages.push(other_tx_message.clone());
+
+    let query_by_tx_id = |app: &App, tx_id: &str| -> Vec<msg::MessageStatus> {
+        let mut statuses: Vec<msg::MessageStatus> = app
+            .wrap()
+            .query_wasm_smart(
+                contract_address.clone(),
+                &msg::QueryMsg::GetMessagesByTxId {
+                    tx_id: tx_id.parse().unwrap(),
+                },
+            )
+            .unwrap();
+        statuses.sort_by_key(|status| status.message.cc_id.id.to_string());
+        statuses
+    };
+
+    // messages are only known once they are submitted for verification
+    assert!(query_by_tx_id(&app, "id").is_empty());
+
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    assert_eq!(
+        query_by_tx_id(&app, "id"),
+        messages[..2]
+            .iter()
+            .map(|message| msg::MessageStatus {
+                message: message.clone(),
+                status: VerificationStatus::InProgress,
+            })
+            .collect::<Vec<_>>()
+    );
+
+    let msg = msg::ExecuteMsg::Vote {
+        poll_id: Uint64::one().into(),
+        votes: vec![Vote::SucceededOnChain; messages.len()],
+        justification: None,
+    };
+    for voter in ["addr1", "addr2"] {
+        app.execute_contract(Addr::unchecked(voter), contract_address.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    assert_eq!(
+        query_by_tx_id(&app, "other_tx"),
+        vec![msg::MessageStatus {
+            message: other_tx_message,
+            status: VerificationStatus::Verified,
+        }]
+    );
+    assert!(query_by_tx_id(&app, "unknown_tx").is_empty());
+}
+
 #[test]
 fn should_start_worker_set_confirmation() {
     let mut app = App::default();
@@ -386,7 +1530,7 @@ fn should_start_worker_set_confirmation() {
     };
     let res: Result<bool, _> = app.wrap().query_wasm_smart(contract_address, &query);
     assert!(res.is_ok());
-    assert_eq!(res.unwrap(), false);
+    assert!(!res.unwrap());
 }
 
 #[test]
@@ -420,6 +1564,7 @@ fn should_confirm_worker_set() {
     let msg = msg::ExecuteMsg::Vote {
         poll_id: 1u64.into(),
         votes: vec![Vote::SucceededOnChain],
+        justification: None,
     };
     for worker in workers {
         let res = app.execute_contract(worker.address.clone(), contract_address.clone(), &msg, &[]);
@@ -439,7 +1584,7 @@ fn should_confirm_worker_set() {
     };
     let res: Result<bool, _> = app.wrap().query_wasm_smart(contract_address, &query);
     assert!(res.is_ok());
-    assert_eq!(res.unwrap(), true);
+    assert!(res.unwrap());
 }
 
 #[test]
@@ -473,6 +1618,7 @@ fn should_not_confirm_worker_set() {
     let msg = msg::ExecuteMsg::Vote {
         poll_id: 1u64.into(),
         votes: vec![Vote::NotFound],
+        justification: None,
     };
     for worker in workers {
         let res = app.execute_contract(worker.address.clone(), contract_address.clone(), &msg, &[]);
@@ -492,7 +1638,7 @@ fn should_not_confirm_worker_set() {
     };
     let res: Result<bool, _> = app.wrap().query_wasm_smart(contract_address, &query);
     assert!(res.is_ok());
-    assert_eq!(res.unwrap(), false);
+    assert!(!res.unwrap());
 }
 
 #[test]
@@ -526,6 +1672,7 @@ fn should_confirm_worker_set_after_failed() {
     let msg = msg::ExecuteMsg::Vote {
         poll_id: 1u64.into(),
         votes: vec![Vote::NotFound],
+        justification: None,
     };
     for worker in &workers {
         let res = app.execute_contract(worker.address.clone(), contract_address.clone(), &msg, &[]);
@@ -547,7 +1694,7 @@ fn should_confirm_worker_set_after_failed() {
         .wrap()
         .query_wasm_smart(contract_address.clone(), &query);
     assert!(res.is_ok());
-    assert_eq!(res.unwrap(), false);
+    assert!(!res.unwrap());
 
     // try again, and this time vote true
     let msg = msg::ExecuteMsg::VerifyWorkerSet {
@@ -560,6 +1707,7 @@ fn should_confirm_worker_set_after_failed() {
     let msg = msg::ExecuteMsg::Vote {
         poll_id: 2u64.into(),
         votes: vec![Vote::SucceededOnChain],
+        justification: None,
     };
     for worker in workers {
         let res = app.execute_contract(worker.address.clone(), contract_address.clone(), &msg, &[]);
@@ -579,7 +1727,7 @@ fn should_confirm_worker_set_after_failed() {
     };
     let res: Result<bool, _> = app.wrap().query_wasm_smart(contract_address, &query);
     assert!(res.is_ok());
-    assert_eq!(res.unwrap(), true);
+    assert!(res.unwrap());
 }
 
 #[test]
@@ -613,6 +1761,7 @@ fn should_not_confirm_twice() {
     let msg = msg::ExecuteMsg::Vote {
         poll_id: 1u64.into(),
         votes: vec![Vote::SucceededOnChain],
+        justification: None,
     };
     for worker in workers {
         let res = app.execute_contract(worker.address.clone(), contract_address.clone(), &msg, &[]);
@@ -642,3 +1791,275 @@ fn should_not_confirm_twice() {
         axelar_wasm_std::ContractError::from(ContractError::WorkerSetAlreadyConfirmed).to_string()
     );
 }
+
+#[test]
+fn should_track_poll_metrics() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let messages = messages(2);
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = msg::ExecuteMsg::VerifyWorkerSet {
+        message_id: message_id("id", 0),
+        new_operators: Operators::new(vec![(vec![0, 1, 0, 1].into(), 1u64.into())], 1u64.into()),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    // both workers need to vote to reach quorum, so quorum is reached with the second vote
+    let msg = msg::ExecuteMsg::Vote {
+        poll_id: Uint64::one().into(),
+        votes: vec![Vote::SucceededOnChain; messages.len()],
+        justification: None,
+    };
+    for (voter, blocks_elapsed) in [("addr1", 1), ("addr2", 2)] {
+        app.update_block(|block| block.height += blocks_elapsed);
+        app.execute_contract(Addr::unchecked(voter), contract_address.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    app.update_block(|block| block.height += POLL_BLOCK_EXPIRY);
+
+    for poll_id in [1u64, 2u64] {
+        let msg = msg::ExecuteMsg::EndPoll {
+            poll_id: Uint64::from(poll_id).into(),
+        };
+        app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let metrics: msg::PollMetricsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_address, &msg::QueryMsg::GetPollMetrics)
+        .unwrap();
+
+    assert_eq!(
+        metrics,
+        msg::PollMetricsResponse {
+            polls_opened: 2,
+            polls_succeeded: 1,
+            polls_failed: 0,
+            polls_expired: 1,
+            average_blocks_to_quorum: Some(3),
+        }
+    );
+}
+
+#[test]
+fn should_track_verification_costs() {
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address =
+        initialize_contract(&mut app, service_registry_address.as_ref().parse().unwrap());
+
+    let messages = messages(2);
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = msg::ExecuteMsg::VerifyWorkerSet {
+        message_id: message_id("id", 0),
+        new_operators: Operators::new(vec![(vec![0, 1, 0, 1].into(), 1u64.into())], 1u64.into()),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = msg::ExecuteMsg::Vote {
+        poll_id: Uint64::one().into(),
+        votes: vec![Vote::SucceededOnChain; messages.len()],
+        justification: None,
+    };
+    for voter in ["addr1", "addr2"] {
+        app.execute_contract(Addr::unchecked(voter), contract_address.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let costs: msg::VerificationCostsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract_address.clone(),
+            &msg::QueryMsg::GetVerificationCosts {
+                source_chain: source_chain(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        costs,
+        msg::VerificationCostsResponse {
+            polls_opened: 2,
+            items_polled: 3,
+            votes_cast: 2,
+        }
+    );
+
+    let costs: msg::VerificationCostsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract_address,
+            &msg::QueryMsg::GetVerificationCosts {
+                source_chain: "other_chain".parse().unwrap(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        costs,
+        msg::VerificationCostsResponse {
+            polls_opened: 0,
+            items_polled: 0,
+            votes_cast: 0,
+        }
+    );
+}
+
+#[test]
+fn should_verify_messages_optimistically() {
+    let fraud_window = 10;
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address = initialize_contract_with_fraud_window(
+        &mut app,
+        service_registry_address.as_ref().parse().unwrap(),
+        Some(fraud_window),
+    );
+
+    let messages = messages(2);
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    let res = app
+        .execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-messages_provisionally_verified"));
+    assert!(!res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-messages_poll_started"));
+
+    let query = msg::QueryMsg::IsVerified {
+        messages: messages.clone(),
+    };
+    let statuses: Vec<(CrossChainId, bool)> = app
+        .wrap()
+        .query_wasm_smart(contract_address.clone(), &query)
+        .unwrap();
+    assert!(statuses.iter().all(|(_, verified)| !verified));
+
+    app.update_block(|block| block.height += fraud_window + 1);
+
+    let statuses: Vec<(CrossChainId, bool)> = app
+        .wrap()
+        .query_wasm_smart(contract_address.clone(), &query)
+        .unwrap();
+    assert!(statuses.iter().all(|(_, verified)| *verified));
+
+    // messages can't be challenged once the fraud window has ended
+    let res = app.execute_contract(
+        Addr::unchecked("addr1"),
+        contract_address,
+        &msg::ExecuteMsg::ChallengeMessages { messages },
+        &[],
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn should_poll_challenged_messages() {
+    let fraud_window = 10;
+    let mut app = App::default();
+
+    let service_registry_address = make_mock_service_registry(&mut app);
+
+    let contract_address = initialize_contract_with_fraud_window(
+        &mut app,
+        service_registry_address.as_ref().parse().unwrap(),
+        Some(fraud_window),
+    );
+
+    let messages = messages(2);
+    let msg = msg::ExecuteMsg::VerifyMessages {
+        messages: messages.clone(),
+    };
+    app.execute_contract(Addr::unchecked(SENDER), contract_address.clone(), &msg, &[])
+        .unwrap();
+
+    let challenge = msg::ExecuteMsg::ChallengeMessages {
+        messages: messages.clone(),
+    };
+
+    // only active workers can challenge
+    let err = app
+        .execute_contract(
+            Addr::unchecked(SENDER),
+            contract_address.clone(),
+            &challenge,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<axelar_wasm_std::ContractError>()
+            .unwrap()
+            .to_string(),
+        axelar_wasm_std::ContractError::from(ContractError::NotActiveWorker(Addr::unchecked(
+            SENDER
+        )))
+        .to_string()
+    );
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked("addr1"),
+            contract_address.clone(),
+            &challenge,
+            &[],
+        )
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-messages_poll_started"));
+
+    // challenged messages are not verified by the passing of the fraud window, only by the poll
+    app.update_block(|block| block.height += fraud_window + 1);
+    let query = msg::QueryMsg::IsVerified {
+        messages: messages.clone(),
+    };
+    let statuses: Vec<(CrossChainId, bool)> = app
+        .wrap()
+        .query_wasm_smart(contract_address.clone(), &query)
+        .unwrap();
+    assert!(statuses.iter().all(|(_, verified)| !verified));
+
+    let msg = msg::ExecuteMsg::Vote {
+        poll_id: Uint64::one().into(),
+        votes: vec![Vote::SucceededOnChain; messages.len()],
+        justification: None,
+    };
+    for voter in ["addr1", "addr2"] {
+        app.execute_contract(Addr::unchecked(voter), contract_address.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let statuses: Vec<(CrossChainId, bool)> = app
+        .wrap()
+        .query_wasm_smart(contract_address, &query)
+        .unwrap();
+    assert!(statuses.iter().all(|(_, verified)| *verified));
+}
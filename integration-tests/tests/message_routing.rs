@@ -36,6 +36,7 @@ fn single_message_can_be_verified_and_routed_and_proven_and_rewards_are_distribu
         .as_slice()
         .try_into()
         .unwrap(),
+        token: None,
     }];
     let msg_ids: Vec<CrossChainId> = msgs.iter().map(|msg| msg.cc_id.clone()).collect();
 
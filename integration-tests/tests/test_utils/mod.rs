@@ -83,6 +83,7 @@ pub fn vote_success_for_all_messages(
             &voting_verifier::msg::ExecuteMsg::Vote {
                 poll_id,
                 votes: vec![Vote::SucceededOnChain; messages.len()],
+                justification: None,
             },
             &[],
         );
@@ -103,6 +104,7 @@ pub fn vote_true_for_worker_set(
             &voting_verifier::msg::ExecuteMsg::Vote {
                 poll_id,
                 votes: vec![Vote::SucceededOnChain; 1],
+                justification: None,
             },
             &[],
         );
@@ -139,7 +141,49 @@ pub fn construct_proof_and_sign(
     );
     assert!(response.is_ok());
 
-    sign_proof(app, multisig_address, workers, response.unwrap())
+    let response = confirm_signing_session(app, multisig_prover_address, response.unwrap());
+    sign_proof(app, multisig_address, workers, response)
+}
+
+/// Confirms the signing session of a proof that transfers operatorship once its confirmation delay has passed.
+/// Returns the response that started signing, which is the given one if the session didn't need confirmation
+pub fn confirm_signing_session(
+    app: &mut App,
+    multisig_prover_address: &Addr,
+    response: AppResponse,
+) -> AppResponse {
+    let (session_id, confirmable_at) = match (
+        get_event_attribute(
+            &response.events,
+            "wasm-signing_awaiting_confirmation",
+            "session_id",
+        ),
+        get_event_attribute(
+            &response.events,
+            "wasm-signing_awaiting_confirmation",
+            "confirmable_at",
+        ),
+    ) {
+        (Some(session_id), Some(confirmable_at)) => (
+            Uint64::try_from(session_id.value.as_str()).unwrap(),
+            confirmable_at.value.parse::<u64>().unwrap(),
+        ),
+        _ => return response,
+    };
+
+    advance_at_least_to_height(app, confirmable_at);
+
+    let response = app.execute_contract(
+        Addr::unchecked("prover_admin"),
+        multisig_prover_address.clone(),
+        &multisig_prover::msg::ExecuteMsg::ConfirmSigningSession {
+            multisig_session_id: session_id,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    response.unwrap()
 }
 
 pub fn sign_proof(
@@ -234,6 +278,8 @@ pub fn get_proof(
         multisig_prover_address,
         &multisig_prover::msg::QueryMsg::GetProof {
             multisig_session_id: *multisig_session_id,
+            fee_payer: None,
+            include_signing_progress: false,
         },
     );
     assert!(query_response.is_ok());
@@ -319,6 +365,11 @@ pub fn setup_protocol(service_name: nonempty::String) -> Protocol {
 
     let rewards_params = rewards::msg::Params {
         epoch_duration: nonempty::Uint64::try_from(10u64).unwrap(),
+        epoch_mode: rewards::msg::EpochMode::Blocks,
+        max_events_per_epoch: None,
+        vesting_epochs: None,
+        availability_bonus: None,
+        participation_carryover: None,
         rewards_per_epoch: Uint128::from(100u128).try_into().unwrap(),
         participation_threshold: (1, 2).try_into().unwrap(),
     };
@@ -336,6 +387,7 @@ pub fn setup_protocol(service_name: nonempty::String) -> Protocol {
             rewards_address: rewards_address.to_string(),
             governance_address: governance_address.to_string(),
             grace_period: 2,
+            confirmation_delay: 10,
         },
     );
     let service_registry_address = instantiate_service_registry(
@@ -345,6 +397,20 @@ pub fn setup_protocol(service_name: nonempty::String) -> Protocol {
         },
     );
     // voting rewards are added when setting up individual chains
+    let response = app.execute_contract(
+        governance_address.clone(),
+        rewards_address.clone(),
+        &rewards::msg::ExecuteMsg::CreatePool {
+            contract_address: multisig_address.to_string(),
+            metadata: rewards::msg::PoolMetadata {
+                chain_name: None,
+                description: "multisig".to_string(),
+            },
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
     let response = app.execute_contract(
         genesis.clone(),
         rewards_address.clone(),
@@ -443,6 +509,7 @@ pub fn register_workers(
                 public_key: PublicKey::Ecdsa(HexBinary::from(
                     worker.key_pair.encoded_verifying_key(),
                 )),
+                proof_of_possession: None,
             },
             &[],
         );
@@ -595,6 +662,9 @@ pub fn setup_chain(protocol: &mut Protocol, chain_name: ChainName) -> Chain {
             confirmation_height: 5,
             source_chain: chain_name.clone(),
             rewards_address: protocol.rewards_address.to_string(),
+            fraud_window: None,
+            governance_address: Some(protocol.governance_address.to_string()),
+            max_messages_per_poll: None,
         },
     );
     let gateway_address = instantiate_gateway(
@@ -602,12 +672,14 @@ pub fn setup_chain(protocol: &mut Protocol, chain_name: ChainName) -> Chain {
         gateway::msg::InstantiateMsg {
             router_address: protocol.router_address.to_string(),
             verifier_address: voting_verifier_address.to_string(),
+            ibc_subscribers: vec![],
         },
     );
     let multisig_prover_address = instantiate_multisig_prover(
         &mut protocol.app,
         multisig_prover::msg::InstantiateMsg {
-            admin_address: Addr::unchecked("doesn't matter").to_string(),
+            admin_address: Addr::unchecked("prover_admin").to_string(),
+            governance_address: Addr::unchecked("doesn't matter").to_string(),
             gateway_address: gateway_address.to_string(),
             multisig_address: protocol.multisig_address.to_string(),
             service_registry_address: protocol.service_registry_address.to_string(),
@@ -619,6 +691,11 @@ pub fn setup_chain(protocol: &mut Protocol, chain_name: ChainName) -> Chain {
             worker_set_diff_threshold: 1,
             encoder: multisig_prover::encoding::Encoder::Abi,
             key_type: multisig::key::KeyType::Ecdsa,
+            safe_mode: false,
+            embed_fee_payer: false,
+            digest_scheme: None,
+            eip712_domain: None,
+            max_operators: None,
         },
     );
     let response = protocol.app.execute_contract(
@@ -649,6 +726,20 @@ pub fn setup_chain(protocol: &mut Protocol, chain_name: ChainName) -> Chain {
     );
     assert!(response.is_ok());
 
+    let response = protocol.app.execute_contract(
+        protocol.governance_address.clone(),
+        protocol.rewards_address.clone(),
+        &rewards::msg::ExecuteMsg::CreatePool {
+            contract_address: voting_verifier_address.to_string(),
+            metadata: rewards::msg::PoolMetadata {
+                chain_name: Some(chain_name.to_string().try_into().unwrap()),
+                description: format!("voting verifier for {}", chain_name),
+            },
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
     let response = protocol.app.execute_contract(
         protocol.genesis_address.clone(),
         protocol.rewards_address.clone(),
@@ -696,7 +787,8 @@ pub fn instantiate_multisig(app: &mut App, instantiate_msg: multisig::msg::Insta
         multisig::contract::execute,
         multisig::contract::instantiate,
         multisig::contract::query,
-    );
+    )
+    .with_reply(multisig::contract::reply);
     let code_id = app.store_code(Box::new(code));
 
     let contract_addr = app.instantiate_contract(
@@ -717,7 +809,8 @@ pub fn instantiate_rewards(app: &mut App, instantiate_msg: rewards::msg::Instant
         rewards::contract::execute,
         rewards::contract::instantiate,
         |_: Deps, _: Env, _: rewards::msg::QueryMsg| -> StdResult<Binary> { todo!() },
-    );
+    )
+    .with_reply(rewards::contract::reply);
     let code_id = app.store_code(Box::new(code));
 
     let contract_addr = app.instantiate_contract(
@@ -62,6 +62,11 @@ fn worker_set_can_be_initialized_and_then_manually_updated() {
         Addr::unchecked("relayer"),
         ethereum.multisig_prover_address.clone(),
     );
+    let response = test_utils::confirm_signing_session(
+        &mut protocol.app,
+        &ethereum.multisig_prover_address,
+        response,
+    );
 
     // sign with old workers
     let session_id = test_utils::sign_proof(
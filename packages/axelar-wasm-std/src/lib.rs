@@ -14,6 +14,10 @@ pub mod hex;
 pub mod nonempty;
 pub mod operators;
 pub mod snapshot;
+pub mod submsg;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 pub mod threshold;
 pub mod utils;
+pub mod versioning;
 pub mod voting;
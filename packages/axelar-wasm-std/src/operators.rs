@@ -36,6 +36,9 @@ mod tests {
     use super::*;
 
     use hex;
+    use proptest::prelude::*;
+
+    use crate::test_utils::{check_invariant, operators};
 
     // If this test fails, it means the operator hash has changed and therefore a migration is needed.
     #[test]
@@ -63,4 +66,22 @@ mod tests {
 
         assert_eq!(hex::encode(operators.hash()), expected_operators_hash);
     }
+
+    #[test]
+    fn hash_independent_of_operator_order() {
+        check_invariant(operators(20), |operators| {
+            let reversed = Operators::new(
+                operators
+                    .weights_by_addresses
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .collect(),
+                operators.threshold,
+            );
+
+            prop_assert_eq!(reversed.hash(), operators.hash());
+            Ok(())
+        });
+    }
 }
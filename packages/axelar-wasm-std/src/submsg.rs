@@ -0,0 +1,391 @@
+/*
+   Module for keeping track of submessages that expect a reply.
+   Each submessage is dispatched with a unique reply id and recorded as pending, together with
+   some context the contract needs to process the reply. When the reply comes in, the pending
+   submessage is looked up by its id. Failed submessages can be retried according to a retry policy:
+   a retry is only allowed after waiting for a number of blocks that doubles with every attempt.
+*/
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    CosmosMsg, Reply, ReplyOn, StdError, Storage, SubMsg, SubMsgResponse, SubMsgResult,
+};
+use cw_storage_plus::Map;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::counter::Counter;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("no pending submessage with reply id {0}")]
+    UnknownReplyId(u64),
+
+    #[error("submessage with reply id {0} has no failed attempt to retry")]
+    NotRetryable(u64),
+
+    #[error("submessage with reply id {id} cannot be retried before block {retry_after}")]
+    RetryTooEarly { id: u64, retry_after: u64 },
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. If set to 1, failed submessages are never retried
+    pub max_attempts: u32,
+    /// Number of blocks to wait before the first retry. The waiting time doubles with every further attempt
+    pub base_backoff_blocks: u64,
+}
+
+impl RetryPolicy {
+    pub const NO_RETRIES: RetryPolicy = RetryPolicy {
+        max_attempts: 1,
+        base_backoff_blocks: 0,
+    };
+
+    fn backoff(&self, attempts: u32) -> u64 {
+        self.base_backoff_blocks
+            .saturating_mul(2u64.saturating_pow(attempts.saturating_sub(1)))
+    }
+}
+
+#[cw_serde]
+pub struct PendingSubMsg<T> {
+    pub context: T,
+    pub msg: CosmosMsg,
+    pub reply_on: ReplyOn,
+    pub attempts: u32,
+    /// Set once an attempt failed and the submessage can be retried, starting at this block height
+    pub retry_after: Option<u64>,
+}
+
+impl<T> PendingSubMsg<T> {
+    fn to_sub_msg(&self, id: u64) -> SubMsg {
+        SubMsg {
+            id,
+            msg: self.msg.clone(),
+            gas_limit: None,
+            reply_on: self.reply_on.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReplyOutcome<T> {
+    Succeeded {
+        context: T,
+        response: SubMsgResponse,
+    },
+    /// The attempt failed, but the submessage can be retried starting at block height retry_after
+    RetryScheduled {
+        context: T,
+        error: String,
+        retry_after: u64,
+    },
+    /// The attempt failed and no retries are left
+    Failed { context: T, error: String },
+}
+
+pub struct PendingSubMsgs<'a, T> {
+    reply_id: Counter<'a, u64>,
+    pending: Map<'a, u64, PendingSubMsg<T>>,
+}
+
+impl<'a, T> PendingSubMsgs<'a, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    pub const fn new(reply_id_namespace: &'a str, pending_namespace: &'a str) -> Self {
+        PendingSubMsgs {
+            reply_id: Counter::new(reply_id_namespace),
+            pending: Map::new(pending_namespace),
+        }
+    }
+
+    /// Records the message as pending and returns a submessage with a unique reply id to dispatch it
+    pub fn submit(
+        &self,
+        storage: &mut dyn Storage,
+        msg: CosmosMsg,
+        context: T,
+        reply_on: ReplyOn,
+    ) -> Result<SubMsg, Error> {
+        let id = self.reply_id.incr(storage)?;
+        let pending = PendingSubMsg {
+            context,
+            msg,
+            reply_on,
+            attempts: 1,
+            retry_after: None,
+        };
+        self.pending.save(storage, id, &pending)?;
+
+        Ok(pending.to_sub_msg(id))
+    }
+
+    /// Resolves the pending submessage the reply belongs to. Succeeded submessages are removed, failed ones are kept
+    /// for a later retry as long as the policy allows further attempts. Failures that would only repeat themselves,
+    /// as decided by the caller with `retriable`, fail right away without using up attempts
+    pub fn handle_reply(
+        &self,
+        storage: &mut dyn Storage,
+        block_height: u64,
+        reply: Reply,
+        policy: &RetryPolicy,
+        retriable: bool,
+    ) -> Result<ReplyOutcome<T>, Error> {
+        let mut pending = self
+            .pending
+            .may_load(storage, reply.id)?
+            .ok_or(Error::UnknownReplyId(reply.id))?;
+
+        match reply.result {
+            SubMsgResult::Ok(response) => {
+                self.pending.remove(storage, reply.id);
+                Ok(ReplyOutcome::Succeeded {
+                    context: pending.context,
+                    response,
+                })
+            }
+            SubMsgResult::Err(error) if retriable && pending.attempts < policy.max_attempts => {
+                let retry_after = block_height + policy.backoff(pending.attempts);
+                pending.retry_after = Some(retry_after);
+                self.pending.save(storage, reply.id, &pending)?;
+                Ok(ReplyOutcome::RetryScheduled {
+                    context: pending.context,
+                    error,
+                    retry_after,
+                })
+            }
+            SubMsgResult::Err(error) => {
+                self.pending.remove(storage, reply.id);
+                Ok(ReplyOutcome::Failed {
+                    context: pending.context,
+                    error,
+                })
+            }
+        }
+    }
+
+    /// Returns the submessage of a failed attempt to dispatch it again, once its backoff has passed
+    pub fn retry(
+        &self,
+        storage: &mut dyn Storage,
+        block_height: u64,
+        id: u64,
+    ) -> Result<SubMsg, Error> {
+        let mut pending = self
+            .pending
+            .may_load(storage, id)?
+            .ok_or(Error::UnknownReplyId(id))?;
+
+        let retry_after = pending.retry_after.ok_or(Error::NotRetryable(id))?;
+        if block_height < retry_after {
+            return Err(Error::RetryTooEarly { id, retry_after });
+        }
+
+        pending.attempts += 1;
+        pending.retry_after = None;
+        self.pending.save(storage, id, &pending)?;
+
+        Ok(pending.to_sub_msg(id))
+    }
+
+    pub fn may_load(
+        &self,
+        storage: &dyn Storage,
+        id: u64,
+    ) -> Result<Option<PendingSubMsg<T>>, Error> {
+        Ok(self.pending.may_load(storage, id)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{BankMsg, Binary};
+
+    use super::*;
+
+    const PENDING: PendingSubMsgs<String> = PendingSubMsgs::new("reply_id", "pending");
+
+    fn msg() -> CosmosMsg {
+        BankMsg::Burn { amount: vec![] }.into()
+    }
+
+    fn reply(id: u64, result: SubMsgResult) -> Reply {
+        Reply { id, result }
+    }
+
+    fn failure() -> SubMsgResult {
+        SubMsgResult::Err("out of gas".to_string())
+    }
+
+    #[test]
+    fn submit_assigns_unique_reply_ids() {
+        let mut store = MockStorage::new();
+
+        let first = PENDING
+            .submit(&mut store, msg(), "first".to_string(), ReplyOn::Always)
+            .unwrap();
+        let second = PENDING
+            .submit(&mut store, msg(), "second".to_string(), ReplyOn::Success)
+            .unwrap();
+
+        assert_ne!(first.id, second.id);
+        assert_eq!(first.reply_on, ReplyOn::Always);
+        assert_eq!(second.reply_on, ReplyOn::Success);
+        assert_eq!(
+            PENDING
+                .may_load(&store, second.id)
+                .unwrap()
+                .unwrap()
+                .context,
+            "second"
+        );
+    }
+
+    #[test]
+    fn successful_reply_removes_pending_submsg() {
+        let mut store = MockStorage::new();
+        let sub_msg = PENDING
+            .submit(&mut store, msg(), "context".to_string(), ReplyOn::Always)
+            .unwrap();
+
+        let response = SubMsgResponse {
+            events: vec![],
+            data: Some(Binary::from(b"data")),
+        };
+        let outcome = PENDING
+            .handle_reply(
+                &mut store,
+                1,
+                reply(sub_msg.id, SubMsgResult::Ok(response.clone())),
+                &RetryPolicy::NO_RETRIES,
+                true,
+            )
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            ReplyOutcome::Succeeded {
+                context: "context".to_string(),
+                response
+            }
+        );
+        assert_eq!(PENDING.may_load(&store, sub_msg.id).unwrap(), None);
+    }
+
+    #[test]
+    fn unknown_reply_id() {
+        let mut store = MockStorage::new();
+
+        assert_eq!(
+            PENDING.handle_reply(
+                &mut store,
+                1,
+                reply(42, failure()),
+                &RetryPolicy::NO_RETRIES,
+                true,
+            ),
+            Err(Error::UnknownReplyId(42))
+        );
+    }
+
+    #[test]
+    fn failed_reply_is_retried_with_backoff() {
+        let mut store = MockStorage::new();
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_backoff_blocks: 10,
+        };
+        let sub_msg = PENDING
+            .submit(&mut store, msg(), "context".to_string(), ReplyOn::Always)
+            .unwrap();
+
+        assert_eq!(
+            PENDING.retry(&mut store, 1, sub_msg.id),
+            Err(Error::NotRetryable(sub_msg.id))
+        );
+
+        // first failure waits for the base backoff
+        let outcome = PENDING
+            .handle_reply(&mut store, 100, reply(sub_msg.id, failure()), &policy, true)
+            .unwrap();
+        assert_eq!(
+            outcome,
+            ReplyOutcome::RetryScheduled {
+                context: "context".to_string(),
+                error: "out of gas".to_string(),
+                retry_after: 110
+            }
+        );
+        assert_eq!(
+            PENDING.retry(&mut store, 109, sub_msg.id),
+            Err(Error::RetryTooEarly {
+                id: sub_msg.id,
+                retry_after: 110
+            })
+        );
+        assert_eq!(PENDING.retry(&mut store, 110, sub_msg.id).unwrap(), sub_msg);
+
+        // second failure waits twice as long
+        let outcome = PENDING
+            .handle_reply(&mut store, 110, reply(sub_msg.id, failure()), &policy, true)
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            ReplyOutcome::RetryScheduled {
+                retry_after: 130,
+                ..
+            }
+        ));
+        PENDING.retry(&mut store, 130, sub_msg.id).unwrap();
+
+        // no attempts left
+        let outcome = PENDING
+            .handle_reply(&mut store, 130, reply(sub_msg.id, failure()), &policy, true)
+            .unwrap();
+        assert_eq!(
+            outcome,
+            ReplyOutcome::Failed {
+                context: "context".to_string(),
+                error: "out of gas".to_string(),
+            }
+        );
+        assert_eq!(PENDING.may_load(&store, sub_msg.id).unwrap(), None);
+    }
+
+    #[test]
+    fn failed_reply_that_is_not_retriable_fails_right_away() {
+        let mut store = MockStorage::new();
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_backoff_blocks: 10,
+        };
+        let sub_msg = PENDING
+            .submit(&mut store, msg(), "context".to_string(), ReplyOn::Always)
+            .unwrap();
+
+        let outcome = PENDING
+            .handle_reply(
+                &mut store,
+                100,
+                reply(sub_msg.id, failure()),
+                &policy,
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            outcome,
+            ReplyOutcome::Failed {
+                context: "context".to_string(),
+                error: "out of gas".to_string(),
+            }
+        );
+        assert_eq!(PENDING.may_load(&store, sub_msg.id).unwrap(), None);
+    }
+}
@@ -0,0 +1,116 @@
+//! Proptest strategies for types that are common across contracts, and a harness to check contract invariants with them.
+//! Only compiled with the `test-utils` feature, which is meant to be enabled for dev-dependencies.
+
+use cosmwasm_std::{Addr, HexBinary, Uint128, Uint256};
+use proptest::prelude::*;
+use proptest::test_runner::{TestCaseResult, TestRunner};
+
+use crate::{nonempty, operators::Operators};
+
+const MAX_OPERATOR_WEIGHT: u64 = 1_000_000;
+
+/// Lowercase alphanumeric addresses short enough to also pass the address validation of the mock api
+pub fn addr() -> impl Strategy<Value = Addr> {
+    "[a-z][a-z0-9]{2,53}".prop_map(Addr::unchecked)
+}
+
+pub fn uint128() -> impl Strategy<Value = Uint128> {
+    any::<u128>().prop_map(Uint128::from)
+}
+
+pub fn nonempty_uint128() -> impl Strategy<Value = nonempty::Uint128> {
+    (1..=u128::MAX).prop_map(|value| {
+        Uint128::from(value)
+            .try_into()
+            .expect("generated value must not be zero")
+    })
+}
+
+pub fn nonempty_string() -> impl Strategy<Value = nonempty::String> {
+    "\\PC{1,64}".prop_map(|value| {
+        value
+            .try_into()
+            .expect("generated string must not be empty")
+    })
+}
+
+/// Message ids in the `<tx hash>:<event index>` format used by EVM chains
+pub fn message_id() -> impl Strategy<Value = nonempty::String> {
+    (any::<[u8; 32]>(), any::<u32>()).prop_map(|(tx_hash, event_index)| {
+        format!(
+            "0x{}:{}",
+            HexBinary::from(tx_hash.to_vec()).to_hex(),
+            event_index
+        )
+        .try_into()
+        .expect("generated message id must not be empty")
+    })
+}
+
+/// Worker sets of up to `max_size` operators with distinct compressed-key-sized public keys and non-zero weights,
+/// and a threshold that the operators can meet
+pub fn operators(max_size: usize) -> impl Strategy<Value = Operators> {
+    prop::collection::hash_map(
+        prop::collection::vec(any::<u8>(), 33),
+        1..=MAX_OPERATOR_WEIGHT,
+        1..=max_size,
+    )
+    .prop_flat_map(|operators| {
+        let total_weight: u64 = operators.values().sum();
+        (Just(operators), 1..=total_weight)
+    })
+    .prop_map(|(operators, threshold)| {
+        Operators::new(
+            operators
+                .into_iter()
+                .map(|(pub_key, weight)| (HexBinary::from(pub_key), Uint256::from(weight)))
+                .collect(),
+            Uint256::from(threshold),
+        )
+    })
+}
+
+/// Checks `invariant` against values generated by `strategy`. On failure, panics with the minimal input violating the invariant.
+/// Use the `prop_assert` macros inside the invariant so failing inputs can be shrunk.
+pub fn check_invariant<S: Strategy>(strategy: S, invariant: impl Fn(S::Value) -> TestCaseResult) {
+    if let Err(err) = TestRunner::default().run(&strategy, invariant) {
+        panic!("invariant violated: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::mock_dependencies, Api};
+    use proptest::prop_assert;
+
+    use super::*;
+
+    #[test]
+    fn generated_addresses_are_valid() {
+        let deps = mock_dependencies();
+
+        check_invariant(addr(), |addr| {
+            prop_assert!(deps.api.addr_validate(addr.as_str()).is_ok());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn generated_message_ids_have_tx_hash_and_index() {
+        check_invariant(message_id(), |id| {
+            let (tx_hash, event_index) = id.split_once(':').unwrap();
+            prop_assert!(tx_hash.starts_with("0x") && tx_hash.len() == 66);
+            prop_assert!(event_index.parse::<u32>().is_ok());
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "invariant violated")]
+    fn check_invariant_panics_on_violation() {
+        check_invariant(uint128(), |value| {
+            prop_assert!(value < Uint128::from(u64::MAX));
+            Ok(())
+        });
+    }
+}
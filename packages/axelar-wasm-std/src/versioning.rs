@@ -0,0 +1,327 @@
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::Map;
+use error_stack::{bail, Report, ResultExt};
+use thiserror::Error;
+
+/// Schema version of each storage namespace of a contract, recorded next to the cw2 contract version
+const STATE_VERSIONS: Map<&str, u64> = Map::new("state_versions");
+
+/// Storage namespace of a contract together with the schema version the contract code expects
+pub type StateVersion<'a> = (&'a str, u64);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("failed to access version info in storage")]
+    Storage,
+    #[error("cannot migrate contract {stored} with code of contract {expected}")]
+    WrongContract { stored: String, expected: String },
+    #[error("state of namespace {namespace} is at version {stored:?}, but version {expected} is expected")]
+    UnexpectedStateVersion {
+        namespace: String,
+        stored: Option<u64>,
+        expected: u64,
+    },
+    #[error("no migration of namespace {namespace} from version {stored}")]
+    MissingMigration { namespace: String, stored: u64 },
+}
+
+/// Records the contract version with cw2 and the schema versions of the given namespaces. Call this on instantiation
+pub fn init_versions(
+    storage: &mut dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+    state_versions: &[StateVersion],
+) -> error_stack::Result<(), Error> {
+    cw2::set_contract_version(storage, contract_name, contract_version)
+        .change_context(Error::Storage)?;
+
+    for (namespace, version) in state_versions {
+        set_state_version(storage, namespace, *version).change_context(Error::Storage)?;
+    }
+
+    Ok(())
+}
+
+/// Runs the given migration for each namespace whose stored schema version is behind the expected one, passing it the namespace and the stored version,
+/// and records the new versions once all namespaces are migrated. Nothing is migrated if the stored state belongs to another contract
+/// or any namespace is ahead of the code, because state can't be migrated back
+pub fn migrate_state<E>(
+    storage: &mut dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+    state_versions: &[StateVersion],
+    mut migrate_namespace: impl FnMut(&mut dyn Storage, &str, Option<u64>) -> Result<(), E>,
+) -> Result<(), E>
+where
+    E: From<Report<Error>>,
+{
+    ensure_contract(storage, contract_name)?;
+
+    let mut outdated = vec![];
+    for (namespace, expected) in state_versions {
+        match load_state_version(storage, namespace).change_context(Error::Storage)? {
+            Some(stored) if stored == *expected => {}
+            Some(stored) if stored > *expected => {
+                return Err(Report::new(Error::UnexpectedStateVersion {
+                    namespace: namespace.to_string(),
+                    stored: Some(stored),
+                    expected: *expected,
+                })
+                .into())
+            }
+            stored => outdated.push((*namespace, stored)),
+        }
+    }
+
+    for (namespace, stored) in outdated {
+        migrate_namespace(storage, namespace, stored)?;
+    }
+
+    migrate_versions(storage, contract_name, contract_version, state_versions)?;
+
+    Ok(())
+}
+
+/// Records the new contract version with cw2 and the schema versions of the given namespaces, after checking that the stored state belongs to the same contract.
+/// Call this at the end of a migration, once all namespaces have been migrated to the given versions
+pub fn migrate_versions(
+    storage: &mut dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+    state_versions: &[StateVersion],
+) -> error_stack::Result<(), Error> {
+    ensure_contract(storage, contract_name)?;
+
+    init_versions(storage, contract_name, contract_version, state_versions)
+}
+
+fn ensure_contract(storage: &dyn Storage, contract_name: &str) -> error_stack::Result<(), Error> {
+    // contracts instantiated before versions were recorded have no cw2 info yet
+    if let Some(stored) = cw2::CONTRACT
+        .may_load(storage)
+        .change_context(Error::Storage)?
+    {
+        if stored.contract != contract_name {
+            bail!(Error::WrongContract {
+                stored: stored.contract,
+                expected: contract_name.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn set_state_version(
+    storage: &mut dyn Storage,
+    namespace: &str,
+    version: u64,
+) -> StdResult<()> {
+    STATE_VERSIONS.save(storage, namespace, &version)
+}
+
+pub fn load_state_version(storage: &dyn Storage, namespace: &str) -> StdResult<Option<u64>> {
+    STATE_VERSIONS.may_load(storage, namespace)
+}
+
+/// Fails if the schema version recorded for the namespace differs from the expected one,
+/// e.g. because a migration did not complete or the contract code was replaced without migrating its state
+pub fn ensure_state_version(
+    storage: &dyn Storage,
+    namespace: &str,
+    expected: u64,
+) -> error_stack::Result<(), Error> {
+    let stored = load_state_version(storage, namespace).change_context(Error::Storage)?;
+
+    if stored != Some(expected) {
+        return Err(Report::new(Error::UnexpectedStateVersion {
+            namespace: namespace.to_string(),
+            stored,
+            expected,
+        }));
+    }
+
+    Ok(())
+}
+
+/// Returns early with an error if any of the given namespaces is not at the expected schema version.
+/// Works in any function whose error type can be converted from an error-stack report,
+/// e.g. `ensure_state_versions!(deps.storage, &STATE_VERSIONS);`
+#[macro_export]
+macro_rules! ensure_state_versions {
+    ($storage:expr, $state_versions:expr) => {
+        for (namespace, version) in $state_versions {
+            $crate::versioning::ensure_state_version($storage, namespace, *version)?;
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+
+    use super::*;
+
+    const STATE_VERSIONS: [StateVersion; 2] = [("config", 1), ("sessions", 2)];
+
+    fn ensure_versions(
+        storage: &dyn Storage,
+        state_versions: &[StateVersion],
+    ) -> Result<(), crate::ContractError> {
+        ensure_state_versions!(storage, state_versions);
+        Ok(())
+    }
+
+    #[test]
+    fn guard_passes_after_init() {
+        let mut storage = MockStorage::new();
+        assert!(ensure_versions(&storage, &STATE_VERSIONS).is_err());
+
+        init_versions(&mut storage, "contract", "0.1.0", &STATE_VERSIONS).unwrap();
+
+        assert!(ensure_versions(&storage, &STATE_VERSIONS).is_ok());
+        assert_eq!(
+            cw2::get_contract_version(&storage).unwrap(),
+            cw2::ContractVersion {
+                contract: "contract".to_string(),
+                version: "0.1.0".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn guard_fails_on_partially_migrated_state() {
+        let mut storage = MockStorage::new();
+        init_versions(&mut storage, "contract", "0.1.0", &STATE_VERSIONS).unwrap();
+
+        // only the first namespace was migrated
+        set_state_version(&mut storage, "config", 2).unwrap();
+
+        assert_eq!(
+            ensure_state_version(&storage, "config", 1)
+                .unwrap_err()
+                .current_context(),
+            &Error::UnexpectedStateVersion {
+                namespace: "config".to_string(),
+                stored: Some(2),
+                expected: 1,
+            }
+        );
+        assert!(ensure_versions(&storage, &[("config", 2), ("sessions", 3)]).is_err());
+        assert!(ensure_versions(&storage, &[("config", 2), ("sessions", 2)]).is_ok());
+    }
+
+    #[test]
+    fn migrate_records_new_versions() {
+        let mut storage = MockStorage::new();
+
+        // state without any version info can be migrated
+        migrate_versions(&mut storage, "contract", "0.1.0", &STATE_VERSIONS).unwrap();
+        assert!(ensure_versions(&storage, &STATE_VERSIONS).is_ok());
+
+        migrate_versions(&mut storage, "contract", "0.2.0", &[("config", 2)]).unwrap();
+        assert_eq!(load_state_version(&storage, "config").unwrap(), Some(2));
+        assert_eq!(load_state_version(&storage, "sessions").unwrap(), Some(2));
+        assert_eq!(
+            cw2::get_contract_version(&storage).unwrap().version,
+            "0.2.0"
+        );
+
+        assert_eq!(
+            migrate_versions(&mut storage, "other-contract", "0.3.0", &STATE_VERSIONS)
+                .unwrap_err()
+                .current_context(),
+            &Error::WrongContract {
+                stored: "contract".to_string(),
+                expected: "other-contract".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_state_migrates_outdated_namespaces_before_recording_versions() {
+        let mut storage = MockStorage::new();
+        init_versions(&mut storage, "contract", "0.1.0", &STATE_VERSIONS).unwrap();
+
+        let new_versions = [("config", 2), ("sessions", 2), ("pools", 1)];
+        let mut migrated = vec![];
+        migrate_state(
+            &mut storage,
+            "contract",
+            "0.2.0",
+            &new_versions,
+            |storage, namespace, stored| -> error_stack::Result<(), Error> {
+                // versions are only recorded once every namespace is migrated
+                assert_eq!(load_state_version(storage, "config").unwrap(), Some(1));
+                migrated.push((namespace.to_string(), stored));
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            migrated,
+            vec![("config".to_string(), Some(1)), ("pools".to_string(), None)]
+        );
+        assert!(ensure_versions(&storage, &new_versions).is_ok());
+    }
+
+    #[test]
+    fn migrate_state_keeps_versions_if_a_migration_fails() {
+        let mut storage = MockStorage::new();
+        init_versions(&mut storage, "contract", "0.1.0", &STATE_VERSIONS).unwrap();
+
+        let new_versions = [("config", 2), ("sessions", 3)];
+        let res = migrate_state(
+            &mut storage,
+            "contract",
+            "0.2.0",
+            &new_versions,
+            |_, namespace, stored| match namespace {
+                "config" => Ok(()),
+                _ => Err(Report::new(Error::MissingMigration {
+                    namespace: namespace.to_string(),
+                    stored: stored.unwrap(),
+                })),
+            },
+        );
+
+        assert_eq!(
+            res.unwrap_err().current_context(),
+            &Error::MissingMigration {
+                namespace: "sessions".to_string(),
+                stored: 2,
+            }
+        );
+        assert!(ensure_versions(&storage, &STATE_VERSIONS).is_ok());
+        assert_eq!(
+            cw2::get_contract_version(&storage).unwrap().version,
+            "0.1.0"
+        );
+    }
+
+    #[test]
+    fn migrate_state_fails_on_state_ahead_of_the_code() {
+        let mut storage = MockStorage::new();
+        init_versions(&mut storage, "contract", "0.2.0", &[("config", 2)]).unwrap();
+
+        let res = migrate_state(
+            &mut storage,
+            "contract",
+            "0.1.0",
+            &[("config", 1)],
+            |_, _, _| -> error_stack::Result<(), Error> {
+                panic!("no namespace should be migrated")
+            },
+        );
+
+        assert_eq!(
+            res.unwrap_err().current_context(),
+            &Error::UnexpectedStateVersion {
+                namespace: "config".to_string(),
+                stored: Some(2),
+                expected: 1,
+            }
+        );
+    }
+}
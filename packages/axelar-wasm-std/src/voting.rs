@@ -22,7 +22,7 @@ use std::str::FromStr;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, StdError, StdResult, Uint256, Uint64};
-use cw_storage_plus::{IntKey, Key, KeyDeserialize, PrimaryKey};
+use cw_storage_plus::{IntKey, Key, KeyDeserialize, Prefixer, PrimaryKey};
 use num_traits::One;
 use strum::EnumIter;
 use strum::EnumString;
@@ -117,6 +117,12 @@ impl<'a> PrimaryKey<'a> for PollId {
     }
 }
 
+impl<'a> Prefixer<'a> for PollId {
+    fn prefix(&self) -> Vec<Key> {
+        vec![Key::Val64(self.0.to_be_bytes())]
+    }
+}
+
 impl KeyDeserialize for PollId {
     type Output = Self;
 
@@ -192,6 +198,9 @@ pub struct PollState {
     pub results: Vec<Option<Vote>>,
     /// List of participants who voted for the winning result
     pub consensus_participants: Vec<String>,
+    /// Subset of the consensus participants who voted after every item had already reached consensus
+    #[serde(default)]
+    pub late_participants: Vec<String>,
 }
 
 #[cw_serde]
@@ -204,6 +213,9 @@ pub enum PollStatus {
 pub struct Participation {
     pub weight: nonempty::Uint256,
     pub vote: Option<Vec<Vote>>,
+    /// Whether the vote was cast after every item had already reached consensus
+    #[serde(default)]
+    pub late: bool,
 }
 
 #[cw_serde]
@@ -230,6 +242,7 @@ impl WeightedPoll {
                     Participation {
                         weight: participant.weight,
                         vote: None,
+                        late: false,
                     },
                 )
             })
@@ -268,7 +281,7 @@ impl WeightedPoll {
             .map(|tallies| tallies.consensus(quorum))
             .collect();
 
-        let consensus_participants = self
+        let consensus_participants: Vec<String> = self
             .participation
             .iter()
             .filter_map(|(address, participation)| {
@@ -286,10 +299,17 @@ impl WeightedPoll {
             })
             .collect();
 
+        let late_participants = consensus_participants
+            .iter()
+            .filter(|address| self.participation[address.as_str()].late)
+            .cloned()
+            .collect();
+
         PollState {
             poll_id: self.poll_id,
             results,
             consensus_participants,
+            late_participants,
         }
     }
 
@@ -324,6 +344,13 @@ impl WeightedPoll {
             return Err(Error::AlreadyVoted);
         }
 
+        // votes are accepted until the poll expires, even if every item already reached consensus
+        let quorum = self.quorum.into();
+        participation.late = self
+            .tallies
+            .iter()
+            .all(|tallies| tallies.consensus(quorum).is_some());
+
         self.tallies
             .iter_mut()
             .zip(votes.iter())
@@ -357,6 +384,7 @@ mod tests {
             &Participation {
                 weight: nonempty::Uint256::try_from(Uint256::from(100u64)).unwrap(),
                 vote: None,
+                late: false,
             }
         );
 
@@ -369,6 +397,7 @@ mod tests {
             &Participation {
                 weight: nonempty::Uint256::try_from(Uint256::from(100u64)).unwrap(),
                 vote: Some(votes),
+                late: false,
             }
         );
     }
@@ -467,6 +496,7 @@ mod tests {
                 poll_id: PollId::from(Uint64::one()),
                 results: vec![Some(Vote::SucceededOnChain), Some(Vote::SucceededOnChain)],
                 consensus_participants: vec!["addr1".to_string(), "addr2".to_string(),],
+                late_participants: vec![],
             }
         );
     }
@@ -493,10 +523,40 @@ mod tests {
                 poll_id: PollId::from(Uint64::one()),
                 results: vec![Some(Vote::SucceededOnChain), Some(Vote::SucceededOnChain)],
                 consensus_participants: vec!["addr1".to_string(), "addr3".to_string(),],
+                late_participants: vec![],
             }
         );
     }
 
+    #[test]
+    fn votes_after_consensus_are_late() {
+        let poll = new_poll(2, 2, vec!["addr1", "addr2", "addr3"]);
+        let votes = vec![Vote::SucceededOnChain, Vote::SucceededOnChain];
+
+        let poll = poll
+            .cast_vote(1, &Addr::unchecked("addr1"), votes.clone())
+            .unwrap()
+            .cast_vote(1, &Addr::unchecked("addr2"), votes.clone())
+            .unwrap()
+            .cast_vote(1, &Addr::unchecked("addr3"), votes)
+            .unwrap();
+
+        assert!(!poll.participation["addr1"].late);
+        assert!(!poll.participation["addr2"].late);
+        assert!(poll.participation["addr3"].late);
+
+        let result = poll.finish(2).unwrap().state();
+        assert_eq!(
+            result.consensus_participants,
+            vec![
+                "addr1".to_string(),
+                "addr2".to_string(),
+                "addr3".to_string()
+            ]
+        );
+        assert_eq!(result.late_participants, vec!["addr3".to_string()]);
+    }
+
     fn new_poll(expires_at: u64, poll_size: usize, participants: Vec<&str>) -> WeightedPoll {
         let participants: nonempty::Vec<Participant> = participants
             .into_iter()